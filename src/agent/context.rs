@@ -4,8 +4,13 @@
 //! and message history for LLM conversations. It also provides `RuntimeContext`
 //! for injecting environment-awareness into the agent's system prompt.
 
+use std::borrow::Cow;
+use std::collections::HashMap;
+
 use chrono::Local;
 
+use super::prompt_template::{PromptTemplate, PromptVars};
+use crate::config::{ChannelPromptConfig, ChannelPromptMode};
 use crate::session::Message;
 
 /// Format a timestamp envelope for a user message.
@@ -259,6 +264,7 @@ impl RuntimeContext {
 /// let messages = builder.build_messages(&[], "Hello!");
 /// assert_eq!(messages.len(), 2); // system + user message
 /// ```
+#[derive(Clone)]
 pub struct ContextBuilder {
     /// The system prompt to use
     system_prompt: String,
@@ -270,6 +276,13 @@ pub struct ContextBuilder {
     runtime_context: Option<RuntimeContext>,
     /// Optional memory context to append to system prompt
     memory_context: Option<String>,
+    /// Optional user template replacing `system_prompt`
+    prompt_template: Option<PromptTemplate>,
+    /// Per-channel prompt overrides, keyed by channel name
+    channel_prompts: HashMap<String, ChannelPromptConfig>,
+    /// Name and prompt of the active hand, kept when a channel replaces
+    /// the system prompt
+    active_hand: Option<(String, String)>,
 }
 
 impl ContextBuilder {
@@ -290,6 +303,9 @@ impl ContextBuilder {
             skills_prompt: None,
             runtime_context: None,
             memory_context: None,
+            prompt_template: None,
+            channel_prompts: HashMap::new(),
+            active_hand: None,
         }
     }
 
@@ -312,6 +328,41 @@ impl ContextBuilder {
         self
     }
 
+    /// Override the system prompt for conversations on `channel`.
+    ///
+    /// See [`ChannelPromptMode`] for how the override combines with the
+    /// system prompt.
+    pub fn with_channel_prompt(mut self, channel: &str, prompt: ChannelPromptConfig) -> Self {
+        self.channel_prompts.insert(channel.to_string(), prompt);
+        self
+    }
+
+    /// Record the active hand whose prompt is the system prompt, so a
+    /// channel prompt in replace mode keeps the hand's instructions.
+    pub fn with_active_hand(mut self, name: &str, prompt: &str) -> Self {
+        self.active_hand = Some((name.to_string(), prompt.to_string()));
+        self
+    }
+
+    /// Use a system-prompt template instead of the system prompt.
+    ///
+    /// The template is rendered on every build so `{{date}}`, `{{user}}` and
+    /// `{{tools}}` stay current; see [`PromptTemplate`] for the variables.
+    ///
+    /// # Example
+    /// ```rust
+    /// use zeptoclaw::agent::{ContextBuilder, PromptTemplate};
+    ///
+    /// let builder = ContextBuilder::new()
+    ///     .with_prompt_template(PromptTemplate::new("Assist in {{workspace}}.").with_workspace("/ws"));
+    /// let system = builder.build_system_message();
+    /// assert!(system.content.contains("Assist in /ws."));
+    /// ```
+    pub fn with_prompt_template(mut self, template: PromptTemplate) -> Self {
+        self.prompt_template = Some(template);
+        self
+    }
+
     /// Set SOUL.md identity content, prepended before the system prompt.
     ///
     /// SOUL.md defines the agent's personality, values, and behavioral
@@ -420,6 +471,39 @@ impl ContextBuilder {
         self
     }
 
+    /// The system prompt, or the rendered template when one is set, with
+    /// `channel`'s override applied.
+    fn base_prompt(&self, vars: Option<&PromptVars>, channel: Option<&str>) -> Cow<'_, str> {
+        let base = self.default_prompt(vars);
+        let Some(over) = channel
+            .and_then(|c| self.channel_prompts.get(c))
+            .filter(|p| !p.prompt.trim().is_empty())
+        else {
+            return base;
+        };
+        match over.mode {
+            ChannelPromptMode::Prepend => Cow::Owned(format!("{}\n\n{}", over.prompt, base)),
+            ChannelPromptMode::Replace => match &self.active_hand {
+                Some((name, prompt)) => Cow::Owned(format!(
+                    "{}\n\n## Active Hand: {}\n\n{}",
+                    over.prompt, name, prompt
+                )),
+                None => Cow::Borrowed(&over.prompt),
+            },
+        }
+    }
+
+    /// The system prompt, or the rendered template when one is set.
+    fn default_prompt(&self, vars: Option<&PromptVars>) -> Cow<'_, str> {
+        match (&self.prompt_template, vars) {
+            (Some(template), Some(vars)) => Cow::Owned(template.render(vars)),
+            (Some(template), None) => {
+                Cow::Owned(template.render(&PromptVars::now("the user", Vec::new())))
+            }
+            (None, _) => Cow::Borrowed(&self.system_prompt),
+        }
+    }
+
     /// Build the system message with all configured content.
     ///
     /// # Returns
@@ -440,7 +524,7 @@ impl ContextBuilder {
             content.push_str(soul);
             content.push_str("\n\n");
         }
-        content.push_str(&self.system_prompt);
+        content.push_str(&self.base_prompt(None, None));
         if let Some(ref skills) = self.skills_prompt {
             content.push_str("\n\n## Available Skills\n\n");
             content.push_str(skills);
@@ -462,13 +546,18 @@ impl ContextBuilder {
     ///
     /// When `memory_override` is `Some`, it replaces the stored
     /// `memory_context`. `Some("")` suppresses memory injection.
-    fn build_system_message_with_memory_override(&self, memory_override: Option<&str>) -> Message {
+    fn build_system_message_with_memory_override(
+        &self,
+        memory_override: Option<&str>,
+        vars: Option<&PromptVars>,
+        channel: Option<&str>,
+    ) -> Message {
         let mut content = String::new();
         if let Some(ref soul) = self.soul_prompt {
             content.push_str(soul);
             content.push_str("\n\n");
         }
-        content.push_str(&self.system_prompt);
+        content.push_str(&self.base_prompt(vars, channel));
         if let Some(ref skills) = self.skills_prompt {
             content.push_str("\n\n## Available Skills\n\n");
             content.push_str(skills);
@@ -551,7 +640,38 @@ impl ContextBuilder {
         user_input: &str,
         memory_override: Option<&str>,
     ) -> Vec<Message> {
-        let mut messages = vec![self.build_system_message_with_memory_override(memory_override)];
+        self.build_messages_with_prompt_vars(history, user_input, memory_override, None)
+    }
+
+    /// Build the full message list, rendering the prompt template (if any)
+    /// with per-message `vars`.
+    ///
+    /// Works like `build_messages_with_memory_override`; `vars` is ignored
+    /// when no template is set.
+    pub fn build_messages_with_prompt_vars(
+        &self,
+        history: &[Message],
+        user_input: &str,
+        memory_override: Option<&str>,
+        vars: Option<&PromptVars>,
+    ) -> Vec<Message> {
+        self.build_channel_messages(history, user_input, memory_override, vars, None)
+    }
+
+    /// Build the full message list for a conversation on `channel`,
+    /// applying that channel's prompt override (if any).
+    ///
+    /// Works like `build_messages_with_prompt_vars`.
+    pub fn build_channel_messages(
+        &self,
+        history: &[Message],
+        user_input: &str,
+        memory_override: Option<&str>,
+        vars: Option<&PromptVars>,
+        channel: Option<&str>,
+    ) -> Vec<Message> {
+        let mut messages =
+            vec![self.build_system_message_with_memory_override(memory_override, vars, channel)];
         messages.extend(history.iter().cloned());
         if !user_input.is_empty() {
             let content = if let Some(ref ctx) = self.runtime_context {
@@ -574,6 +694,11 @@ impl ContextBuilder {
         &self.system_prompt
     }
 
+    /// Check if a system-prompt template is configured.
+    pub fn has_prompt_template(&self) -> bool {
+        self.prompt_template.is_some()
+    }
+
     /// Check if a SOUL.md identity is configured.
     pub fn has_soul(&self) -> bool {
         self.soul_prompt.is_some()
@@ -1152,4 +1277,94 @@ mod tests {
         assert!(FIRST_RUN_PERSONA_PROMPT.contains("concise"));
         assert!(FIRST_RUN_PERSONA_PROMPT.contains("persona_pref"));
     }
+
+    #[test]
+    fn test_prompt_template_replaces_system_prompt() {
+        let builder = ContextBuilder::new()
+            .with_soul("SOUL")
+            .with_prompt_template(
+                PromptTemplate::new("Help {{user}} using {{tools}}.").with_hand("coder"),
+            );
+        assert!(builder.has_prompt_template());
+        let vars = PromptVars {
+            date: "today".into(),
+            user: "Bob".into(),
+            tools: vec!["shell".into()],
+        };
+        let messages = builder.build_messages_with_prompt_vars(&[], "Hi", None, Some(&vars));
+        assert_eq!(messages[0].content, "SOUL\n\nHelp Bob using shell.");
+        assert!(!messages[0].content.contains("ZeptoClaw"));
+    }
+
+    #[test]
+    fn test_channel_prompt_override() {
+        let builder = ContextBuilder::new()
+            .with_soul("SOUL")
+            .with_channel_prompt(
+                "telegram",
+                ChannelPromptConfig {
+                    prompt: "You are a public support bot.".into(),
+                    mode: ChannelPromptMode::Prepend,
+                },
+            )
+            .with_channel_prompt(
+                "slack",
+                ChannelPromptConfig {
+                    prompt: "Internal team assistant.".into(),
+                    mode: ChannelPromptMode::Replace,
+                },
+            );
+
+        let telegram = builder.build_channel_messages(&[], "Hi", None, None, Some("telegram"));
+        assert!(telegram[0]
+            .content
+            .starts_with("SOUL\n\nYou are a public support bot.\n\n"));
+        assert!(telegram[0].content.contains("ZeptoClaw"));
+
+        let slack = builder.build_channel_messages(&[], "Hi", None, None, Some("slack"));
+        assert_eq!(slack[0].content, "SOUL\n\nInternal team assistant.");
+
+        // Channels without an override use the default prompt.
+        let discord = builder.build_channel_messages(&[], "Hi", None, None, Some("discord"));
+        assert_eq!(
+            discord[0].content,
+            builder.build_messages(&[], "Hi")[0].content
+        );
+        assert!(!discord[0].content.contains("support bot"));
+    }
+
+    #[test]
+    fn test_channel_prompt_replace_keeps_active_hand() {
+        let builder = ContextBuilder::new()
+            .with_system_prompt("Review code carefully.")
+            .with_active_hand("coder", "Review code carefully.")
+            .with_channel_prompt(
+                "slack",
+                ChannelPromptConfig {
+                    prompt: "Internal team assistant.".into(),
+                    mode: ChannelPromptMode::Replace,
+                },
+            );
+        let slack = builder.build_channel_messages(&[], "Hi", None, None, Some("slack"));
+        assert_eq!(
+            slack[0].content,
+            "Internal team assistant.\n\n## Active Hand: coder\n\nReview code carefully."
+        );
+    }
+
+    #[test]
+    fn test_without_template_vars_are_ignored() {
+        let builder = ContextBuilder::new();
+        assert!(!builder.has_prompt_template());
+        let vars = PromptVars {
+            user: "Bob".into(),
+            ..Default::default()
+        };
+        let messages = builder.build_messages_with_prompt_vars(&[], "Hi", None, Some(&vars));
+        assert!(messages[0].content.contains("ZeptoClaw"));
+        assert_eq!(
+            messages[0].content,
+            builder.build_messages(&[], "Hi")[0].content
+        );
+    }
 }
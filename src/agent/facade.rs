@@ -35,13 +35,14 @@ use serde_json::Value;
 use tokio::sync::Mutex;
 use tracing::{debug, info, warn};
 
-use crate::error::{Result, ZeptoError};
+use crate::error::{Result, ToolErrorKind, ZeptoError};
 use crate::providers::{ChatOptions, LLMProvider};
 use crate::safety::taint::TaintEngine;
 use crate::safety::{SafetyConfig, SafetyLayer};
 use crate::session::{Message, ToolCall};
 use crate::tools::approval::{ApprovalConfig, ApprovalGate, ApprovalRequest, ApprovalResponse};
-use crate::tools::{Tool, ToolContext, ToolRegistry};
+use crate::tools::risk::RiskLevel;
+use crate::tools::{Tool, ToolCategory, ToolContext, ToolRegistry};
 use crate::utils::metrics::MetricsCollector;
 
 const DEFAULT_MAX_ITERATIONS: usize = 10;
@@ -62,9 +63,19 @@ async fn resolve_tool_approval(
     gate: &ApprovalGate,
     approval_handler: Option<&ApprovalHandler>,
     tool_name: &str,
+    category: Option<ToolCategory>,
     args: &Value,
 ) -> Option<String> {
-    if !gate.requires_approval(tool_name) {
+    if let Some(category) = category {
+        if gate.risk_level(tool_name, category, args) == RiskLevel::Blocked {
+            return Some(format!(
+                "Tool '{}' is blocked by the tool risk policy (category: {})",
+                tool_name, category
+            ));
+        }
+    }
+    if !gate.requires_approval(tool_name) && !gate.risk_requires_approval(tool_name, category, args)
+    {
         return None;
     }
 
@@ -199,6 +210,7 @@ impl ZeptoAgent {
                     &self.approval_gate,
                     self.approval_handler.as_ref(),
                     &tc.name,
+                    self.tools.get(&tc.name).map(|tool| tool.category()),
                     &args,
                 )
                 .await
@@ -231,22 +243,30 @@ impl ZeptoAgent {
                         }
                         Ok(Ok(Err(e))) => {
                             warn!("[ZeptoAgent] Tool '{}' failed: {}", tc.name, e);
-                            format!("Tool error: {e}")
+                            e.to_tool_result()
                         }
                         Ok(Err(_panic)) => {
                             warn!("[ZeptoAgent] Tool '{}' panicked during execution", tc.name);
-                            format!("Tool error: Tool '{}' panicked during execution", tc.name)
+                            ZeptoError::tool(
+                                ToolErrorKind::Internal,
+                                format!("Tool '{}' panicked during execution", tc.name),
+                            )
+                            .to_tool_result()
                         }
                         Err(_) => {
                             warn!(
                                 "[ZeptoAgent] Tool '{}' timed out after {:?}",
                                 tc.name, self.tool_timeout
                             );
-                            format!(
-                                "Tool error: Tool '{}' timed out after {}s",
-                                tc.name,
-                                self.tool_timeout.as_secs_f64()
+                            ZeptoError::tool(
+                                ToolErrorKind::Transient,
+                                format!(
+                                    "Tool '{}' timed out after {}s",
+                                    tc.name,
+                                    self.tool_timeout.as_secs_f64()
+                                ),
                             )
+                            .to_tool_result()
                         }
                     }
                 }
@@ -931,6 +951,7 @@ mod tests {
                 require_for: vec!["dangerous_tool".into()],
                 dangerous_tools: vec![],
                 auto_approve_timeout_secs: 0,
+                ..Default::default()
             })
             .approval_handler(|_| async { ApprovalResponse::Denied("nope".into()) })
             .build()
@@ -964,6 +985,7 @@ mod tests {
                 require_for: vec!["dangerous_tool".into()],
                 dangerous_tools: vec![],
                 auto_approve_timeout_secs: 0,
+                ..Default::default()
             })
             .approval_handler(|_| async { ApprovalResponse::Approved })
             .build()
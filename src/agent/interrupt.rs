@@ -0,0 +1,170 @@
+//! Per-session interruption of agent runs.
+//!
+//! Every run registers a [`CancellationToken`] under its session key for its
+//! duration. Interrupting the session (the `/stop` chat command, or
+//! [`AgentLoop::interrupt`](super::AgentLoop::interrupt) from other
+//! front-ends) cancels the token; the tool loop checks it between
+//! iterations, so the tool call in flight finishes and the run then returns
+//! what it has so far instead of asking the model for more work.
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::sync::Arc;
+//! use zeptoclaw::agent::interrupt::InterruptRegistry;
+//!
+//! let registry = Arc::new(InterruptRegistry::new());
+//! assert!(!registry.interrupt("telegram:42"));
+//!
+//! let run = registry.begin("telegram:42");
+//! assert!(registry.interrupt("telegram:42"));
+//! assert!(run.is_cancelled());
+//! drop(run);
+//! assert!(!registry.is_running("telegram:42"));
+//! ```
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use tokio_util::sync::CancellationToken;
+
+/// Chat command that interrupts the sender's active run.
+pub const STOP_COMMAND: &str = "/stop";
+
+/// Reply to `/stop` when a run was interrupted.
+pub const STOP_ACK_MESSAGE: &str = "Stopping after the current step...";
+/// Reply to `/stop` when nothing is running in the conversation.
+pub const NOTHING_TO_STOP_MESSAGE: &str = "Nothing is running right now.";
+
+/// Whether a message is the `/stop` command (Telegram's `/stop@botname`
+/// form included).
+pub fn is_stop_command(content: &str) -> bool {
+    let command = content.trim();
+    let command = command.split('@').next().unwrap_or(command);
+    command.eq_ignore_ascii_case(STOP_COMMAND)
+}
+
+/// Reply returned by an interrupted run: the partial answer so far, if any,
+/// followed by a note on where the run stopped.
+pub fn interrupted_reply(partial: &str, tool_calls: u32) -> String {
+    let note = format!(
+        "Stopped at your request after {} tool call{}.",
+        tool_calls,
+        if tool_calls == 1 { "" } else { "s" }
+    );
+    if partial.trim().is_empty() {
+        note
+    } else {
+        format!("{}\n\n_{}_", partial.trim_end(), note)
+    }
+}
+
+/// Cancellation tokens of the runs currently active, by session key.
+#[derive(Debug, Default)]
+pub struct InterruptRegistry {
+    active: Mutex<HashMap<String, CancellationToken>>,
+}
+
+impl InterruptRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a run for `session_key`; it stays interruptible until the
+    /// returned guard is dropped.
+    pub fn begin(self: &Arc<Self>, session_key: &str) -> RunInterrupt {
+        let token = CancellationToken::new();
+        self.lock().insert(session_key.to_string(), token.clone());
+        RunInterrupt {
+            registry: Arc::clone(self),
+            session_key: session_key.to_string(),
+            token,
+        }
+    }
+
+    /// Signal the active run of `session_key` to stop. Returns `false` when
+    /// the session has no active run.
+    pub fn interrupt(&self, session_key: &str) -> bool {
+        match self.lock().get(session_key) {
+            Some(token) => {
+                token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Whether `session_key` has an active run.
+    pub fn is_running(&self, session_key: &str) -> bool {
+        self.lock().contains_key(session_key)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, CancellationToken>> {
+        self.active.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Interrupt token held by an active run; unregistered on drop.
+#[derive(Debug)]
+pub struct RunInterrupt {
+    registry: Arc<InterruptRegistry>,
+    session_key: String,
+    token: CancellationToken,
+}
+
+impl RunInterrupt {
+    /// The run's cancellation token.
+    pub fn token(&self) -> &CancellationToken {
+        &self.token
+    }
+
+    /// Whether the run was asked to stop.
+    pub fn is_cancelled(&self) -> bool {
+        self.token.is_cancelled()
+    }
+}
+
+impl Drop for RunInterrupt {
+    fn drop(&mut self) {
+        self.registry.lock().remove(&self.session_key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stop_command_and_reply() {
+        assert!(is_stop_command("/stop"));
+        assert!(is_stop_command("  /STOP "));
+        assert!(is_stop_command("/stop@zepto_bot"));
+        assert!(!is_stop_command("/stopwatch"));
+        assert!(!is_stop_command("please stop"));
+
+        assert_eq!(
+            interrupted_reply("", 1),
+            "Stopped at your request after 1 tool call."
+        );
+        assert_eq!(
+            interrupted_reply("Found 3 files so far.\n", 2),
+            "Found 3 files so far.\n\n_Stopped at your request after 2 tool calls._"
+        );
+    }
+
+    #[test]
+    fn test_interrupt_targets_only_its_session() {
+        let registry = Arc::new(InterruptRegistry::new());
+        let first = registry.begin("telegram:1");
+        let second = registry.begin("telegram:2");
+
+        assert!(registry.interrupt("telegram:1"));
+        assert!(first.is_cancelled());
+        assert!(!second.is_cancelled());
+
+        drop(first);
+        assert!(!registry.interrupt("telegram:1"));
+        assert!(registry.is_running("telegram:2"));
+    }
+}
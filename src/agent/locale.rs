@@ -0,0 +1,385 @@
+//! Per-conversation reply language.
+//!
+//! The locale of a conversation is resolved from, in order of precedence:
+//!
+//! 1. An explicit per-user override (`locale.overrides` in config, or the
+//!    `locale_override` inbound metadata set by a channel command).
+//! 2. The channel's user metadata (`locale`, e.g. Telegram's `language_code`).
+//! 3. A lightweight language guess on the incoming text.
+//! 4. The locale previously stored on the session.
+//!
+//! When the result differs from the configured default, a "respond in
+//! <language>" hint is appended to the system prompt.
+
+use crate::bus::InboundMessage;
+use crate::config::LocaleConfig;
+
+/// Inbound metadata key carrying the sender's locale as reported by the channel.
+pub const LOCALE_METADATA_KEY: &str = "locale";
+/// Inbound metadata key carrying an explicit, user-chosen locale.
+pub const LOCALE_OVERRIDE_METADATA_KEY: &str = "locale_override";
+
+/// Minimum number of letters before text-based detection is attempted.
+const MIN_DETECT_LETTERS: usize = 12;
+/// Minimum stopword hits for a Latin-script language guess.
+const MIN_STOPWORD_HITS: usize = 2;
+
+/// Supported languages: (code, English name).
+const LANGUAGES: &[(&str, &str)] = &[
+    ("ar", "Arabic"),
+    ("de", "German"),
+    ("el", "Greek"),
+    ("en", "English"),
+    ("es", "Spanish"),
+    ("fr", "French"),
+    ("he", "Hebrew"),
+    ("hi", "Hindi"),
+    ("id", "Indonesian"),
+    ("it", "Italian"),
+    ("ja", "Japanese"),
+    ("ko", "Korean"),
+    ("ms", "Malay"),
+    ("nl", "Dutch"),
+    ("pt", "Portuguese"),
+    ("ru", "Russian"),
+    ("th", "Thai"),
+    ("tr", "Turkish"),
+    ("uk", "Ukrainian"),
+    ("vi", "Vietnamese"),
+    ("zh", "Chinese"),
+];
+
+/// Common function words per Latin-script language.
+const STOPWORDS: &[(&str, &[&str])] = &[
+    (
+        "en",
+        &[
+            "the", "and", "is", "are", "you", "what", "how", "with", "this", "please",
+        ],
+    ),
+    (
+        "es",
+        &[
+            "el", "la", "los", "las", "que", "por", "para", "una", "cómo", "qué", "está", "pero",
+        ],
+    ),
+    (
+        "fr",
+        &[
+            "le", "la", "les", "est", "une", "des", "pour", "avec", "vous", "je", "pas", "qui",
+        ],
+    ),
+    (
+        "de",
+        &[
+            "der", "die", "das", "und", "ist", "nicht", "ich", "mit", "ein", "eine", "wie", "bitte",
+        ],
+    ),
+    (
+        "pt",
+        &[
+            "o", "os", "as", "que", "não", "uma", "para", "com", "você", "está", "obrigado", "como",
+        ],
+    ),
+    (
+        "it",
+        &[
+            "il", "lo", "gli", "che", "non", "una", "per", "sono", "come", "della", "grazie",
+            "questo",
+        ],
+    ),
+    (
+        "nl",
+        &[
+            "de", "het", "een", "en", "is", "niet", "ik", "wat", "hoe", "met", "van", "dank",
+        ],
+    ),
+    (
+        "id",
+        &[
+            "yang", "dan", "tidak", "saya", "apa", "ini", "itu", "dengan", "untuk", "bisa",
+            "tolong", "sudah",
+        ],
+    ),
+    (
+        "ms",
+        &[
+            "yang", "dan", "tidak", "saya", "apa", "ini", "itu", "dengan", "untuk", "boleh",
+            "tolong", "sudah", "awak",
+        ],
+    ),
+    (
+        "tr",
+        &[
+            "bir",
+            "ve",
+            "bu",
+            "için",
+            "ne",
+            "nasıl",
+            "değil",
+            "ile",
+            "mı",
+            "mi",
+            "lütfen",
+            "teşekkürler",
+        ],
+    ),
+    (
+        "vi",
+        &[
+            "và", "là", "của", "không", "có", "tôi", "bạn", "này", "được", "cho", "với", "làm",
+        ],
+    ),
+];
+
+/// Normalize a locale tag to its lowercase primary language subtag
+/// (`"pt-BR"` → `"pt"`, `"zh_Hans"` → `"zh"`).
+///
+/// Returns `None` for empty or non-alphabetic tags.
+pub fn normalize_locale(tag: &str) -> Option<String> {
+    let primary = tag.trim().split(['-', '_']).next()?.to_ascii_lowercase();
+    if (2..=3).contains(&primary.len()) && primary.chars().all(|c| c.is_ascii_lowercase()) {
+        Some(primary)
+    } else {
+        None
+    }
+}
+
+/// English display name for a language code, if known.
+pub fn language_name(code: &str) -> Option<&'static str> {
+    LANGUAGES
+        .iter()
+        .find(|(c, _)| *c == code)
+        .map(|(_, name)| *name)
+}
+
+/// Guess the language of `text`.
+///
+/// Non-Latin scripts are identified by character ranges; Latin-script
+/// languages by stopword hits.  Returns `None` when the text is too short or
+/// the guess is ambiguous.
+pub fn detect_language(text: &str) -> Option<&'static str> {
+    let letters: Vec<char> = text.chars().filter(|c| c.is_alphabetic()).collect();
+    if letters.len() < MIN_DETECT_LETTERS {
+        return None;
+    }
+
+    if let Some(code) = detect_script(&letters) {
+        return Some(code);
+    }
+
+    let lower = text.to_lowercase();
+    let words: Vec<&str> = lower
+        .split(|c: char| !c.is_alphabetic())
+        .filter(|w| !w.is_empty())
+        .collect();
+    let mut best: Option<(&'static str, usize)> = None;
+    let mut tied = false;
+    for (code, stopwords) in STOPWORDS {
+        let hits = words.iter().filter(|w| stopwords.contains(*w)).count();
+        match best {
+            Some((_, top)) if hits == top => tied = true,
+            Some((_, top)) if hits < top => {}
+            _ => {
+                best = Some((*code, hits));
+                tied = false;
+            }
+        }
+    }
+    match best {
+        Some((code, hits)) if hits >= MIN_STOPWORD_HITS && !tied => Some(code),
+        _ => None,
+    }
+}
+
+/// Identify a non-Latin script when it makes up most of the letters.
+fn detect_script(letters: &[char]) -> Option<&'static str> {
+    let count = |range: &[std::ops::RangeInclusive<u32>]| {
+        letters
+            .iter()
+            .filter(|c| range.iter().any(|r| r.contains(&(**c as u32))))
+            .count()
+    };
+    let kana = count(&[0x3040..=0x30FF]);
+    let hangul = count(&[0xAC00..=0xD7AF, 0x1100..=0x11FF]);
+    let han = count(&[0x4E00..=0x9FFF, 0x3400..=0x4DBF]);
+    let cyrillic = count(&[0x0400..=0x04FF]);
+    let ukrainian = letters
+        .iter()
+        .filter(|c| matches!(**c, 'і' | 'ї' | 'є' | 'ґ' | 'І' | 'Ї' | 'Є' | 'Ґ'))
+        .count();
+
+    let scripts = [
+        // Japanese text mixes kana with Han characters.
+        ("ja", if kana > 0 { kana + han } else { 0 }),
+        ("ko", hangul),
+        ("zh", han),
+        ("uk", if ukrainian > 0 { cyrillic } else { 0 }),
+        ("ru", cyrillic),
+        ("ar", count(&[0x0600..=0x06FF])),
+        ("he", count(&[0x0590..=0x05FF])),
+        ("el", count(&[0x0370..=0x03FF])),
+        ("hi", count(&[0x0900..=0x097F])),
+        ("th", count(&[0x0E00..=0x0E7F])),
+    ];
+    scripts
+        .iter()
+        .find(|(_, n)| *n * 2 > letters.len())
+        .map(|(code, _)| *code)
+}
+
+/// Resolve the conversation locale for an inbound message.
+///
+/// `stored` is the locale already recorded on the session; it is kept when
+/// nothing in the new message says otherwise.
+pub fn resolve_locale(
+    config: &LocaleConfig,
+    msg: &InboundMessage,
+    stored: Option<&str>,
+) -> Option<String> {
+    let override_key = format!("{}:{}", msg.channel, msg.sender_id);
+    let explicit = config
+        .overrides
+        .get(&override_key)
+        .or_else(|| config.overrides.get(&msg.sender_id))
+        .or_else(|| msg.metadata.get(LOCALE_OVERRIDE_METADATA_KEY));
+    if let Some(locale) = explicit.and_then(|l| normalize_locale(l)) {
+        return Some(locale);
+    }
+
+    if let Some(locale) = msg
+        .metadata
+        .get(LOCALE_METADATA_KEY)
+        .and_then(|l| normalize_locale(l))
+    {
+        return Some(locale);
+    }
+
+    if config.detect {
+        if let Some(code) = detect_language(&msg.content) {
+            return Some(code.to_string());
+        }
+    }
+
+    stored.map(str::to_string)
+}
+
+/// System-prompt hint for `locale`, or `None` when it matches the default.
+pub fn locale_hint(locale: &str, default_locale: &str) -> Option<String> {
+    if normalize_locale(default_locale).as_deref() == Some(locale) {
+        return None;
+    }
+    let language = language_name(locale).unwrap_or(locale);
+    Some(format!(
+        "## Language\n\nRespond in {} (locale: {}) unless the user explicitly asks for another language.",
+        language, locale
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> LocaleConfig {
+        LocaleConfig::default()
+    }
+
+    #[test]
+    fn test_normalize_locale() {
+        assert_eq!(normalize_locale("pt-BR").as_deref(), Some("pt"));
+        assert_eq!(normalize_locale("zh_Hans").as_deref(), Some("zh"));
+        assert_eq!(normalize_locale(" EN ").as_deref(), Some("en"));
+        assert_eq!(normalize_locale(""), None);
+        assert_eq!(normalize_locale("12"), None);
+    }
+
+    #[test]
+    fn test_detect_language_by_script() {
+        assert_eq!(
+            detect_language("Привет, как у тебя дела сегодня?"),
+            Some("ru")
+        );
+        assert_eq!(
+            detect_language("今日はとても良い天気ですね、散歩に行きましょう"),
+            Some("ja")
+        );
+        assert_eq!(
+            detect_language("안녕하세요 오늘 날씨가 정말 좋네요"),
+            Some("ko")
+        );
+        assert_eq!(
+            detect_language("你好，今天天气怎么样？我们去公园散步吧"),
+            Some("zh")
+        );
+    }
+
+    #[test]
+    fn test_detect_language_by_stopwords() {
+        assert_eq!(
+            detect_language("Hola, ¿cómo está el clima para el fin de semana?"),
+            Some("es")
+        );
+        assert_eq!(
+            detect_language("Bonjour, est-ce que vous pouvez m'aider avec le rapport?"),
+            Some("fr")
+        );
+        assert_eq!(
+            detect_language("What is the weather like and how are you today?"),
+            Some("en")
+        );
+        assert_eq!(detect_language("ok thanks"), None);
+    }
+
+    #[test]
+    fn test_resolve_prefers_explicit_override() {
+        let mut cfg = config();
+        cfg.overrides.insert("telegram:42".into(), "de".into());
+        let msg = InboundMessage::new(
+            "telegram",
+            "42",
+            "chat",
+            "Hola, ¿cómo está el clima para el fin de semana?",
+        )
+        .with_metadata(LOCALE_METADATA_KEY, "es-MX");
+        assert_eq!(
+            resolve_locale(&cfg, &msg, Some("fr")).as_deref(),
+            Some("de")
+        );
+
+        let msg = msg.with_metadata(LOCALE_OVERRIDE_METADATA_KEY, "it");
+        assert_eq!(resolve_locale(&config(), &msg, None).as_deref(), Some("it"));
+    }
+
+    #[test]
+    fn test_resolve_channel_metadata_then_detection_then_stored() {
+        let msg = InboundMessage::new("telegram", "1", "chat", "ok")
+            .with_metadata(LOCALE_METADATA_KEY, "pt-BR");
+        assert_eq!(resolve_locale(&config(), &msg, None).as_deref(), Some("pt"));
+
+        let msg = InboundMessage::new("cli", "1", "chat", "Привет, как у тебя дела сегодня?");
+        assert_eq!(
+            resolve_locale(&config(), &msg, Some("fr")).as_deref(),
+            Some("ru")
+        );
+
+        let msg = InboundMessage::new("cli", "1", "chat", "ok");
+        assert_eq!(
+            resolve_locale(&config(), &msg, Some("fr")).as_deref(),
+            Some("fr")
+        );
+
+        let mut cfg = config();
+        cfg.detect = false;
+        let msg = InboundMessage::new("cli", "1", "chat", "Привет, как у тебя дела сегодня?");
+        assert_eq!(resolve_locale(&cfg, &msg, None), None);
+    }
+
+    #[test]
+    fn test_locale_hint_skips_default() {
+        assert_eq!(locale_hint("en", "en-US"), None);
+        let hint = locale_hint("es", "en").unwrap();
+        assert!(hint.contains("Respond in Spanish"));
+        assert!(locale_hint("xx", "en").unwrap().contains("Respond in xx"));
+    }
+}
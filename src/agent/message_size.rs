@@ -0,0 +1,171 @@
+//! Early size check for inbound messages.
+//!
+//! A user pasting a megabyte of logs would otherwise fill the context window
+//! and run up provider cost before the request fails deep inside the
+//! provider. Messages longer than `agents.defaults.max_message_chars` are
+//! handled before any session or provider work:
+//!
+//! - [`OversizedMessageMode::Attach`] (default) saves the full text to the
+//!   session's uploads as `pasted-message.txt` and hands the model a preview
+//!   plus the file path, so it can read the parts it needs with file tools.
+//! - [`OversizedMessageMode::Reject`] refuses the message and asks the user
+//!   to summarize it or send it as a file.
+//!
+//! If the paste cannot be saved (upload limits), the message is rejected.
+
+use tracing::{info, warn};
+
+use crate::bus::InboundMessage;
+use crate::config::OversizedMessageMode;
+use crate::error::{Result, ZeptoError};
+use crate::session::uploads::{annotate, UploadStore};
+
+/// Characters of an oversized message kept inline as a preview.
+pub const PREVIEW_CHARS: usize = 2_000;
+
+/// File name an oversized message is saved under.
+pub const PASTE_FILENAME: &str = "pasted-message.txt";
+
+/// How an inbound message of a given size is handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SizeDecision {
+    /// Within the limit; process as is.
+    Accept,
+    /// Over the limit; save it as an upload and send a preview.
+    Attach,
+    /// Over the limit; refuse it.
+    Reject,
+}
+
+/// Decide how to handle a message of `chars` characters.
+///
+/// `max_chars` of 0 disables the check.
+pub fn size_decision(chars: usize, max_chars: usize, mode: OversizedMessageMode) -> SizeDecision {
+    if max_chars == 0 || chars <= max_chars {
+        return SizeDecision::Accept;
+    }
+    match mode {
+        OversizedMessageMode::Attach => SizeDecision::Attach,
+        OversizedMessageMode::Reject => SizeDecision::Reject,
+    }
+}
+
+/// The error returned for a refused oversized message.
+pub fn too_large_error(chars: usize, max_chars: usize) -> ZeptoError {
+    ZeptoError::Tool(format!(
+        "Message too large ({} characters, limit {}). Please summarize it or send it as a \
+         file attachment instead.",
+        chars, max_chars
+    ))
+}
+
+/// Apply the size limit to `msg`.
+///
+/// Returns `Ok(None)` when the message is within the limit, `Ok(Some(..))`
+/// with the content replaced by a preview and file note when it was
+/// redirected to an upload, or the [`too_large_error`] when it is refused.
+pub async fn enforce_message_size(
+    msg: &InboundMessage,
+    max_chars: usize,
+    mode: OversizedMessageMode,
+    uploads: &UploadStore,
+) -> Result<Option<InboundMessage>> {
+    // Bytes bound characters from above, so short messages skip the count.
+    if max_chars == 0 || msg.content.len() <= max_chars {
+        return Ok(None);
+    }
+    let chars = msg.content.chars().count();
+    match size_decision(chars, max_chars, mode) {
+        SizeDecision::Accept => Ok(None),
+        SizeDecision::Reject => {
+            info!(session = %msg.session_key, chars, "Rejected oversized message");
+            Err(too_large_error(chars, max_chars))
+        }
+        SizeDecision::Attach => {
+            let path = match uploads
+                .save(
+                    &msg.session_key,
+                    Some(PASTE_FILENAME),
+                    msg.content.as_bytes(),
+                )
+                .await
+            {
+                Ok(path) => path,
+                Err(e) => {
+                    warn!(session = %msg.session_key, error = %e, "Could not save oversized message");
+                    return Err(too_large_error(chars, max_chars));
+                }
+            };
+            info!(session = %msg.session_key, chars, path = %path, "Redirected oversized message to upload");
+            let preview: String = msg.content.chars().take(PREVIEW_CHARS).collect();
+            let note = format!(
+                "[message too long ({} characters); showing the first {}. Full text saved at {} \
+                 — read or search that file for the rest]",
+                chars, PREVIEW_CHARS, path
+            );
+            let mut redirected = msg.clone();
+            redirected.content = annotate(&preview, &[note]);
+            Ok(Some(redirected))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_size_threshold_decision() {
+        use OversizedMessageMode::{Attach, Reject};
+
+        assert_eq!(size_decision(100, 100, Reject), SizeDecision::Accept);
+        assert_eq!(size_decision(101, 100, Reject), SizeDecision::Reject);
+        assert_eq!(size_decision(101, 100, Attach), SizeDecision::Attach);
+        // 0 disables the check.
+        assert_eq!(size_decision(10_000_000, 0, Reject), SizeDecision::Accept);
+
+        let err = too_large_error(250_000, 100_000).to_string();
+        assert!(err.contains("250000 characters, limit 100000"), "{err}");
+        assert!(err.contains("send it as a file attachment"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_oversized_message_routed_to_upload_or_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let uploads = UploadStore::new(dir.path());
+        let paste = "ERROR connection reset\n".repeat(200);
+        let msg = InboundMessage::new("telegram", "u1", "42", &paste);
+
+        // Under the limit: untouched.
+        let kept = enforce_message_size(&msg, 10_000, OversizedMessageMode::Reject, &uploads)
+            .await
+            .unwrap();
+        assert!(kept.is_none());
+
+        let err = enforce_message_size(&msg, 1_000, OversizedMessageMode::Reject, &uploads)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Message too large"));
+
+        let redirected = enforce_message_size(&msg, 1_000, OversizedMessageMode::Attach, &uploads)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(redirected.content.len() < paste.len());
+        assert!(redirected
+            .content
+            .contains("message too long (4600 characters)"));
+        let saved = dir
+            .path()
+            .join(uploads.session_dir(&msg.session_key))
+            .join(PASTE_FILENAME);
+        assert_eq!(std::fs::read_to_string(saved).unwrap(), paste);
+
+        // No room for the upload: fall back to refusing.
+        let full = UploadStore::new(dir.path()).with_session_limits(1, 0);
+        let err = enforce_message_size(&msg, 1_000, OversizedMessageMode::Attach, &full)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Message too large"));
+    }
+}
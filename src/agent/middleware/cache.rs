@@ -69,6 +69,7 @@ impl Middleware for CacheMiddleware {
 
         let user_content = &ctx.inbound.content;
         let key = ResponseCache::cache_key(model_name, system_prompt, user_content);
+        let model_name = model_name.to_string();
 
         // Check for a cache hit.  The MutexGuard must be dropped before
         // any .await point to remain Send.
@@ -96,7 +97,7 @@ impl Middleware for CacheMiddleware {
         {
             let token_count = usage.as_ref().map(|u| u.completion_tokens).unwrap_or(0);
             if let Ok(mut cache) = cache_mutex.lock() {
-                cache.put(key, response.clone(), token_count);
+                cache.put_for_model(key, &model_name, response.clone(), token_count);
                 debug!("Cached initial LLM response");
             }
         }
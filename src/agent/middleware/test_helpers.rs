@@ -80,6 +80,7 @@ impl ToolExecutor for MockToolExecutor {
             is_error: false,
             is_async: false,
             pause_for_input: false,
+            structured: None,
         })
     }
 }
@@ -151,6 +152,7 @@ pub fn test_context(subsystems: Arc<Subsystems>) -> PipelineContext {
             chat_id: "test-chat".to_string(),
             media: vec![],
             metadata: HashMap::new(),
+            trace_id: String::new(),
         },
         config: Arc::new(Config::default()),
         session: None,
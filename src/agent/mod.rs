@@ -59,18 +59,28 @@ pub mod compaction;
 mod context;
 pub mod context_monitor;
 pub mod facade;
+pub mod interrupt;
+pub mod locale;
 mod r#loop;
 pub mod loop_guard;
+pub mod message_size;
 pub mod middleware;
 pub mod pipeline;
+pub mod plan;
+pub mod prompt_template;
+pub mod run_limit;
 pub mod scratchpad;
 pub mod tool_call_limit;
+pub mod tool_dedup;
+pub mod usage_footer;
 
 pub use budget::TokenBudget;
 pub use context::{format_message_envelope, ContextBuilder, RuntimeContext};
 pub use context_monitor::{CompactionStrategy, ContextMonitor, PreflightAction};
 pub use facade::{ZeptoAgent, ZeptoAgentBuilder};
+pub use prompt_template::{PromptTemplate, PromptVars};
 pub use r#loop::AgentLoop;
 pub use r#loop::{ToolFeedback, ToolFeedbackPhase};
+pub use run_limit::{RunLimiter, RunPermit};
 pub use scratchpad::SwarmScratchpad;
 pub use tool_call_limit::ToolCallLimitTracker;
@@ -0,0 +1,220 @@
+//! Plan-then-execute mode.
+//!
+//! With `agents.defaults.plan_mode` enabled, a new request is first answered
+//! with a numbered step plan drafted without any tool access. The plan is
+//! stored on the session as a [`PendingPlan`] and the agent only acts once
+//! the user approves it; a cancel reply drops it, and a plan left
+//! unapproved past `plan_approval_timeout_secs` expires. Any other reply is
+//! treated as a new or revised request and gets a fresh plan.
+
+use crate::bus::{ActionStyle, OutputAction, StructuredOutput};
+use crate::session::PendingPlan;
+
+/// Action ID of the "approve" button attached to a proposed plan.
+pub const PLAN_APPROVE_ACTION: &str = "plan_approve";
+/// Action ID of the "cancel" button attached to a proposed plan.
+pub const PLAN_CANCEL_ACTION: &str = "plan_cancel";
+
+/// Instructions appended to the request when asking the model for a plan.
+pub const PLAN_INSTRUCTIONS: &str = "Before doing anything, write a short numbered plan \
+(1., 2., 3., ...) of the steps and tools you would use to complete the request above. \
+Do not carry out any step yet and do not add commentary after the list. \
+If the request is conversational or can be answered directly without tools, just answer it \
+instead of writing a plan.";
+
+/// Reply sent when the user cancels a pending plan.
+pub const PLAN_CANCELLED_MESSAGE: &str = "Plan cancelled. Nothing was executed.";
+
+/// Reply sent when the user approves or cancels a plan that already expired.
+pub const PLAN_EXPIRED_MESSAGE: &str =
+    "That plan expired before it was approved, so nothing was executed. Send the request again for a fresh plan.";
+
+/// How a user reply relates to a pending plan.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlanReply {
+    /// Run the plan.
+    Approve,
+    /// Drop the plan without running it.
+    Cancel,
+    /// Anything else: a new or revised request.
+    Other,
+}
+
+/// Classify a user reply to a proposed plan.
+///
+/// # Example
+///
+/// ```
+/// use zeptoclaw::agent::plan::{classify_reply, PlanReply};
+///
+/// assert_eq!(classify_reply("Yes!"), PlanReply::Approve);
+/// assert_eq!(classify_reply("cancel"), PlanReply::Cancel);
+/// assert_eq!(classify_reply("yes, but skip step 2"), PlanReply::Other);
+/// ```
+pub fn classify_reply(text: &str) -> PlanReply {
+    let normalized = text
+        .trim()
+        .trim_end_matches(['.', '!'])
+        .trim()
+        .to_lowercase();
+    match normalized.as_str() {
+        "yes" | "y" | "ok" | "okay" | "approve" | "approved" | "go" | "go ahead" | "proceed"
+        | "run it" | "do it" | "lgtm" => PlanReply::Approve,
+        "no" | "n" | "cancel" | "stop" | "abort" | "reject" | "never mind" | "nevermind" => {
+            PlanReply::Cancel
+        }
+        _ => PlanReply::Other,
+    }
+}
+
+/// What the agent does with an inbound message in plan mode.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlanGate {
+    /// Draft a plan for the message without running any tools.
+    Draft,
+    /// Run the approved plan.
+    Execute(PendingPlan),
+    /// Answer with this text without calling the model.
+    Reply(&'static str),
+}
+
+/// Decide how to handle `input` given the session's pending plan, which the
+/// caller has already taken off the session.
+///
+/// # Example
+///
+/// ```
+/// use zeptoclaw::agent::plan::{resolve, PlanGate};
+/// use zeptoclaw::session::PendingPlan;
+///
+/// let plan = PendingPlan::new("deploy", vec!["Build".into(), "Ship".into()]);
+/// assert_eq!(resolve(None, "deploy the app", 600), PlanGate::Draft);
+/// assert_eq!(resolve(Some(plan.clone()), "yes", 600), PlanGate::Execute(plan));
+/// ```
+pub fn resolve(pending: Option<PendingPlan>, input: &str, timeout_secs: u64) -> PlanGate {
+    let Some(plan) = pending else {
+        return PlanGate::Draft;
+    };
+    match (classify_reply(input), plan.is_expired(timeout_secs)) {
+        (PlanReply::Other, _) => PlanGate::Draft,
+        (_, true) => PlanGate::Reply(PLAN_EXPIRED_MESSAGE),
+        (PlanReply::Approve, false) => PlanGate::Execute(plan),
+        (PlanReply::Cancel, false) => PlanGate::Reply(PLAN_CANCELLED_MESSAGE),
+    }
+}
+
+/// Approve / cancel buttons for channels that render message actions.
+/// Pressing one sends back the matching `yes` / `no` reply.
+pub fn approval_actions() -> StructuredOutput {
+    StructuredOutput::new()
+        .with_action(
+            OutputAction::new(PLAN_APPROVE_ACTION, "Run plan", "yes")
+                .with_style(ActionStyle::Primary),
+        )
+        .with_action(
+            OutputAction::new(PLAN_CANCEL_ACTION, "Cancel", "no").with_style(ActionStyle::Danger),
+        )
+}
+
+/// Extract the numbered steps (`1.` or `1)`) from a drafted plan.
+///
+/// Returns an empty list when the model answered directly instead.
+pub fn parse_steps(text: &str) -> Vec<String> {
+    text.lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            let digits = line.chars().take_while(char::is_ascii_digit).count();
+            if digits == 0 {
+                return None;
+            }
+            let rest = line[digits..].strip_prefix(['.', ')'])?;
+            let step = rest.trim();
+            (!step.is_empty()).then(|| step.to_string())
+        })
+        .collect()
+}
+
+/// Render a proposed plan for the user, ending with the approval question.
+pub fn render_plan(plan: &PendingPlan, timeout_secs: u64) -> String {
+    let steps: Vec<String> = plan
+        .steps
+        .iter()
+        .enumerate()
+        .map(|(i, step)| format!("{}. {}", i + 1, step))
+        .collect();
+    let expiry = if timeout_secs > 0 {
+        format!(" (expires in {} min)", timeout_secs.div_ceil(60))
+    } else {
+        String::new()
+    };
+    format!(
+        "**Proposed plan**\n{}\n\nReply **yes** to run it or **no** to cancel{}.",
+        steps.join("\n"),
+        expiry
+    )
+}
+
+/// User message that replaces the approval reply so the agent runs the plan.
+pub fn execution_prompt(plan: &PendingPlan) -> String {
+    let steps: Vec<String> = plan
+        .steps
+        .iter()
+        .enumerate()
+        .map(|(i, step)| format!("{}. {}", i + 1, step))
+        .collect();
+    format!(
+        "Plan approved. Carry out this plan for the request below, step by step, then report the outcome.\n\nRequest: {}\n\nPlan:\n{}",
+        plan.task,
+        steps.join("\n")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_steps_accepts_numbered_lists_only() {
+        let draft = "Here is the plan:\n1. Read config.toml\n2) Update the port\n\n3.   Restart the service\nDone.";
+        assert_eq!(
+            parse_steps(draft),
+            vec!["Read config.toml", "Update the port", "Restart the service"]
+        );
+        assert!(parse_steps("It is 4pm in Tokyo. 2024 was a leap year.").is_empty());
+    }
+
+    #[test]
+    fn test_resolve_expires_and_cancels_pending_plans() {
+        let plan = PendingPlan::new("deploy", vec!["Build".into()]);
+        assert_eq!(
+            resolve(Some(plan.clone()), "No.", 600),
+            PlanGate::Reply(PLAN_CANCELLED_MESSAGE)
+        );
+        assert_eq!(
+            resolve(Some(plan.clone()), "deploy to staging instead", 600),
+            PlanGate::Draft
+        );
+
+        let mut stale = plan;
+        stale.created_at -= chrono::Duration::seconds(601);
+        assert_eq!(
+            resolve(Some(stale.clone()), "yes", 600),
+            PlanGate::Reply(PLAN_EXPIRED_MESSAGE)
+        );
+        assert!(matches!(
+            resolve(Some(stale), "yes", 0),
+            PlanGate::Execute(_)
+        ));
+    }
+
+    #[test]
+    fn test_render_and_execution_prompt_number_steps() {
+        let plan = PendingPlan::new("bump the port", vec!["Read".into(), "Write".into()]);
+        let rendered = render_plan(&plan, 600);
+        assert!(rendered.contains("1. Read\n2. Write"));
+        assert!(rendered.ends_with("(expires in 10 min)."));
+        let prompt = execution_prompt(&plan);
+        assert!(prompt.contains("Request: bump the port"));
+        assert!(prompt.contains("2. Write"));
+    }
+}
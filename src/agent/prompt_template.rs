@@ -0,0 +1,165 @@
+//! User-defined system-prompt templates.
+//!
+//! A template replaces the built-in system prompt and may reference these
+//! variables, using the same `{{name}}` syntax as composed tools:
+//!
+//! | Variable        | Value                                           |
+//! |-----------------|-------------------------------------------------|
+//! | `{{date}}`      | Current local date, e.g. `Tuesday, 2026-03-03`  |
+//! | `{{workspace}}` | Workspace directory                             |
+//! | `{{tools}}`     | Comma-separated names of the available tools    |
+//! | `{{user}}`      | Display name (or ID) of the message sender      |
+//! | `{{hand}}`      | Active hand name, or `none`                     |
+//!
+//! The template is loaded from `agents.defaults.system_prompt_template`.
+//! SOUL.md, skills, runtime context and memory are still added around it.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use chrono::Local;
+
+use crate::error::{Result, ZeptoError};
+use crate::utils::string::interpolate;
+
+/// Values that change per message.
+#[derive(Debug, Clone, Default)]
+pub struct PromptVars {
+    /// Rendered `{{date}}`.
+    pub date: String,
+    /// Rendered `{{user}}`.
+    pub user: String,
+    /// Tool names joined into `{{tools}}`.
+    pub tools: Vec<String>,
+}
+
+impl PromptVars {
+    /// Variables for a message sent now by `user` with `tools` available.
+    pub fn now(user: &str, tools: Vec<String>) -> Self {
+        Self {
+            date: Local::now().format("%A, %Y-%m-%d").to_string(),
+            user: user.to_string(),
+            tools,
+        }
+    }
+}
+
+/// A system-prompt template with its static variables.
+#[derive(Debug, Clone)]
+pub struct PromptTemplate {
+    template: String,
+    workspace: String,
+    hand: Option<String>,
+}
+
+impl PromptTemplate {
+    /// Create a template from its text.
+    pub fn new(template: impl Into<String>) -> Self {
+        Self {
+            template: template.into(),
+            workspace: String::new(),
+            hand: None,
+        }
+    }
+
+    /// Load a template file. Empty files are rejected so a typo cannot
+    /// silently blank the system prompt.
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path).map_err(|e| {
+            ZeptoError::Config(format!(
+                "Failed to read system prompt template {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        if text.trim().is_empty() {
+            return Err(ZeptoError::Config(format!(
+                "System prompt template {} is empty",
+                path.display()
+            )));
+        }
+        Ok(Self::new(text.trim()))
+    }
+
+    /// Set the `{{workspace}}` value.
+    pub fn with_workspace(mut self, workspace: impl Into<String>) -> Self {
+        self.workspace = workspace.into();
+        self
+    }
+
+    /// Set the `{{hand}}` value.
+    pub fn with_hand(mut self, hand: impl Into<String>) -> Self {
+        self.hand = Some(hand.into());
+        self
+    }
+
+    /// Raw template text.
+    pub fn template(&self) -> &str {
+        &self.template
+    }
+
+    /// Render the template with per-message variables.
+    pub fn render(&self, vars: &PromptVars) -> String {
+        let tools = if vars.tools.is_empty() {
+            "none".to_string()
+        } else {
+            vars.tools.join(", ")
+        };
+        let values = HashMap::from([
+            ("date".to_string(), vars.date.clone()),
+            ("workspace".to_string(), self.workspace.clone()),
+            ("tools".to_string(), tools),
+            ("user".to_string(), vars.user.clone()),
+            (
+                "hand".to_string(),
+                self.hand.clone().unwrap_or_else(|| "none".to_string()),
+            ),
+        ]);
+        interpolate(&self.template, &values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_substitutes_all_variables() {
+        let template = PromptTemplate::new(
+            "Today is {{date}}. You help {{user}} in {{workspace}} as {{hand}}. Tools: {{tools}}. {{unknown}}",
+        )
+        .with_workspace("/home/me/ws")
+        .with_hand("researcher");
+        let vars = PromptVars {
+            date: "Monday, 2026-01-05".into(),
+            user: "Alice".into(),
+            tools: vec!["shell".into(), "web_search".into()],
+        };
+        assert_eq!(
+            template.render(&vars),
+            "Today is Monday, 2026-01-05. You help Alice in /home/me/ws as researcher. \
+Tools: shell, web_search. {{unknown}}"
+        );
+    }
+
+    #[test]
+    fn test_render_defaults_for_missing_values() {
+        let rendered = PromptTemplate::new("{{hand}} / {{tools}}").render(&PromptVars::default());
+        assert_eq!(rendered, "none / none");
+    }
+
+    #[test]
+    fn test_load_rejects_empty_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("prompt.md");
+        std::fs::write(&path, "  \n").unwrap();
+        assert!(PromptTemplate::load(&path).is_err());
+        assert!(PromptTemplate::load(&dir.path().join("missing.md")).is_err());
+
+        std::fs::write(&path, "Hello {{user}}\n").unwrap();
+        assert_eq!(
+            PromptTemplate::load(&path).unwrap().template(),
+            "Hello {{user}}"
+        );
+    }
+}
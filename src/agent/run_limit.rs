@@ -0,0 +1,167 @@
+//! Bound on concurrently active agent runs.
+//!
+//! Every `process_message` call holds a [`RunPermit`] for its duration, so
+//! bursts of inbound messages, spawned background tasks and API requests
+//! cannot fan out into an unbounded number of in-flight LLM conversations.
+//! Beyond the limit a run either waits for a free slot
+//! ([`RunOverflowMode::Queue`]) or is turned away with a "busy" error
+//! ([`RunOverflowMode::Reject`]).
+//!
+//! # Example
+//!
+//! ```rust
+//! use zeptoclaw::agent::run_limit::RunLimiter;
+//! use zeptoclaw::config::RunOverflowMode;
+//!
+//! # tokio_test::block_on(async {
+//! let limiter = RunLimiter::new(1, RunOverflowMode::Reject);
+//! let permit = limiter.admit().await.unwrap();
+//! assert_eq!(limiter.active(), 1);
+//! assert!(limiter.admit().await.is_err());
+//! drop(permit);
+//! assert!(limiter.admit().await.is_ok());
+//! # });
+//! ```
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::config::RunOverflowMode;
+use crate::error::{Result, ZeptoError};
+
+/// Reply sent when a run is rejected because every slot is taken.
+pub const AGENT_BUSY_MESSAGE: &str =
+    "I'm busy with other conversations right now. Please try again in a moment.";
+
+/// Semaphore-backed limit on concurrently active agent runs.
+#[derive(Debug)]
+pub struct RunLimiter {
+    /// `None` when unlimited.
+    semaphore: Option<Arc<Semaphore>>,
+    max: usize,
+    overflow: RunOverflowMode,
+    active: Arc<AtomicUsize>,
+}
+
+impl RunLimiter {
+    /// Create a limiter admitting `max` concurrent runs (`0` = unlimited).
+    pub fn new(max: usize, overflow: RunOverflowMode) -> Self {
+        Self {
+            semaphore: (max > 0).then(|| Arc::new(Semaphore::new(max))),
+            max,
+            overflow,
+            active: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Create a limiter that admits every run.
+    pub fn unlimited() -> Self {
+        Self::new(0, RunOverflowMode::default())
+    }
+
+    /// Admit a run, waiting for a slot or failing with [`ZeptoError::Busy`]
+    /// depending on the overflow mode. The slot is released when the
+    /// returned permit is dropped.
+    pub async fn admit(&self) -> Result<RunPermit> {
+        let permit = match &self.semaphore {
+            None => None,
+            Some(semaphore) => Some(match self.overflow {
+                RunOverflowMode::Queue => Arc::clone(semaphore)
+                    .acquire_owned()
+                    .await
+                    .map_err(|_| ZeptoError::Busy(AGENT_BUSY_MESSAGE.to_string()))?,
+                RunOverflowMode::Reject => Arc::clone(semaphore)
+                    .try_acquire_owned()
+                    .map_err(|_| ZeptoError::Busy(AGENT_BUSY_MESSAGE.to_string()))?,
+            }),
+        };
+        self.active.fetch_add(1, Ordering::SeqCst);
+        Ok(RunPermit {
+            _permit: permit,
+            active: Arc::clone(&self.active),
+        })
+    }
+
+    /// Number of runs currently holding a permit.
+    pub fn active(&self) -> usize {
+        self.active.load(Ordering::SeqCst)
+    }
+
+    /// Configured maximum (`0` = unlimited).
+    pub fn max(&self) -> usize {
+        self.max
+    }
+}
+
+/// Slot held by an active agent run; released on drop.
+#[derive(Debug)]
+pub struct RunPermit {
+    _permit: Option<OwnedSemaphorePermit>,
+    active: Arc<AtomicUsize>,
+}
+
+impl Drop for RunPermit {
+    fn drop(&mut self) {
+        self.active.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_reject_mode_admits_up_to_limit() {
+        let limiter = RunLimiter::new(2, RunOverflowMode::Reject);
+        let first = limiter.admit().await.unwrap();
+        let _second = limiter.admit().await.unwrap();
+        assert_eq!(limiter.active(), 2);
+
+        let err = limiter.admit().await.unwrap_err();
+        assert!(matches!(err, ZeptoError::Busy(_)));
+        assert_eq!(limiter.active(), 2);
+
+        drop(first);
+        assert_eq!(limiter.active(), 1);
+        assert!(limiter.admit().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_queue_mode_waits_for_a_free_slot() {
+        let limiter = Arc::new(RunLimiter::new(1, RunOverflowMode::Queue));
+        let held = limiter.admit().await.unwrap();
+
+        let waiter = {
+            let limiter = Arc::clone(&limiter);
+            tokio::spawn(async move { limiter.admit().await.map(|_| ()) })
+        };
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!waiter.is_finished());
+        assert_eq!(limiter.active(), 1);
+
+        drop(held);
+        tokio::time::timeout(Duration::from_secs(1), waiter)
+            .await
+            .expect("queued run admitted after release")
+            .unwrap()
+            .unwrap();
+        assert_eq!(limiter.active(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_unlimited_counts_active_runs() {
+        let limiter = RunLimiter::unlimited();
+        let permits: Vec<_> = futures::future::join_all((0..5).map(|_| limiter.admit()))
+            .await
+            .into_iter()
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(limiter.active(), 5);
+        assert_eq!(limiter.max(), 0);
+        drop(permits);
+        assert_eq!(limiter.active(), 0);
+    }
+}
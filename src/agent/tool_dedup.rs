@@ -0,0 +1,158 @@
+//! Per-run execution guard for non-idempotent tool calls.
+//!
+//! When a provider call fails transiently and the agent loop retries, the
+//! model can re-issue a tool call that already ran. For tools that report
+//! [`Tool::is_idempotent`] as `false` (sending mail, creating calendar
+//! events) that would repeat a side effect. [`ExecutionDedup`] remembers the
+//! result of each successful non-idempotent call, keyed by a SHA-256 hash of
+//! the tool name and arguments, and hands it back for identical replays
+//! within the same run.
+//!
+//! [`Tool::is_idempotent`]: crate::tools::Tool::is_idempotent
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+/// Note prepended to a replayed call's cached result.
+pub const REPLAY_NOTE: &str =
+    "[Not executed again: an identical call already ran in this turn. Previous result follows.]";
+
+/// Results of non-idempotent tool calls made during one agent run.
+#[derive(Debug, Default)]
+pub struct ExecutionDedup {
+    results: Mutex<HashMap<String, String>>,
+}
+
+impl ExecutionDedup {
+    /// Create an empty guard for a new run.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Result to return instead of executing, if this exact non-idempotent
+    /// call already succeeded in this run.
+    pub fn replay(&self, name: &str, args: &Value, idempotent: bool) -> Option<String> {
+        if idempotent {
+            return None;
+        }
+        let results = self.results.lock().unwrap_or_else(|e| e.into_inner());
+        results
+            .get(&call_key(name, args))
+            .map(|result| format!("{}\n{}", REPLAY_NOTE, result))
+    }
+
+    /// Remember the result of a successful call.
+    ///
+    /// Idempotent calls are not stored; failed calls should not be recorded
+    /// so they can be retried.
+    pub fn record(&self, name: &str, args: &Value, idempotent: bool, result: &str) {
+        if idempotent {
+            return;
+        }
+        let mut results = self.results.lock().unwrap_or_else(|e| e.into_inner());
+        results.insert(call_key(name, args), result.to_string());
+    }
+}
+
+/// Hex SHA-256 of `name` and the serialized `args`.
+fn call_key(name: &str, args: &Value) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(name.as_bytes());
+    hasher.update([0]);
+    hasher.update(args.to_string().as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Result;
+    use crate::tools::{Tool, ToolContext, ToolOutput};
+    use async_trait::async_trait;
+    use serde_json::json;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    struct CountingTool {
+        idempotent: bool,
+        runs: AtomicU32,
+    }
+
+    #[async_trait]
+    impl Tool for CountingTool {
+        fn name(&self) -> &str {
+            "counting"
+        }
+        fn description(&self) -> &str {
+            "Counts executions"
+        }
+        fn parameters(&self) -> Value {
+            json!({"type": "object"})
+        }
+        async fn execute(&self, _args: Value, _ctx: &ToolContext) -> Result<ToolOutput> {
+            let run = self.runs.fetch_add(1, Ordering::SeqCst) + 1;
+            Ok(ToolOutput::llm_only(format!("run {}", run)))
+        }
+        fn is_idempotent(&self, _args: &Value) -> bool {
+            self.idempotent
+        }
+    }
+
+    /// Mirror of the agent loop: replay if possible, otherwise execute and record.
+    async fn call(dedup: &ExecutionDedup, tool: &CountingTool, args: Value) -> String {
+        let idempotent = tool.is_idempotent(&args);
+        if let Some(cached) = dedup.replay(tool.name(), &args, idempotent) {
+            return cached;
+        }
+        let output = tool
+            .execute(args.clone(), &ToolContext::new())
+            .await
+            .unwrap();
+        dedup.record(tool.name(), &args, idempotent, &output.for_llm);
+        output.for_llm
+    }
+
+    #[tokio::test]
+    async fn test_repeated_non_idempotent_call_returns_cached_result() {
+        let dedup = ExecutionDedup::new();
+        let tool = CountingTool {
+            idempotent: false,
+            runs: AtomicU32::new(0),
+        };
+        let args = json!({"to": "a@example.com", "body": "hi"});
+
+        assert_eq!(call(&dedup, &tool, args.clone()).await, "run 1");
+        let replayed = call(&dedup, &tool, args).await;
+        assert_eq!(replayed, format!("{}\nrun 1", REPLAY_NOTE));
+        assert_eq!(tool.runs.load(Ordering::SeqCst), 1);
+
+        // Different arguments are a different call.
+        assert_eq!(
+            call(&dedup, &tool, json!({"to": "b@example.com"})).await,
+            "run 2"
+        );
+
+        // A new run starts with a clean slate.
+        let next_run = ExecutionDedup::new();
+        assert_eq!(
+            call(&next_run, &tool, json!({"to": "b@example.com"})).await,
+            "run 3"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_idempotent_call_re_executes() {
+        let dedup = ExecutionDedup::new();
+        let tool = CountingTool {
+            idempotent: true,
+            runs: AtomicU32::new(0),
+        };
+        let args = json!({"path": "notes.md"});
+
+        assert_eq!(call(&dedup, &tool, args.clone()).await, "run 1");
+        assert_eq!(call(&dedup, &tool, args).await, "run 2");
+        assert_eq!(tool.runs.load(Ordering::SeqCst), 2);
+    }
+}
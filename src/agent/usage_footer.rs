@@ -0,0 +1,144 @@
+//! Per-reply usage footer.
+//!
+//! When `agents.defaults.usage_footer` is on (or the user enabled the
+//! `usage_footer` preference), each chat reply ends with a compact summary of
+//! the run, e.g. `(1.2k in / 340 out tok · $0.004 · gemini-2.0-flash · 2 tools)`.
+//! The footer is markdown, so each channel renders it with its own dialect.
+
+use std::collections::HashMap;
+
+use crate::utils::cost::{estimate_cost, ModelPricing};
+
+/// Token, model and tool usage of one agent run.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RunUsage {
+    /// Prompt tokens across every LLM call of the run.
+    pub input_tokens: u64,
+    /// Completion tokens across every LLM call of the run.
+    pub output_tokens: u64,
+    /// Model that served the run.
+    pub model: String,
+    /// Tool calls executed during the run.
+    pub tool_calls: u32,
+}
+
+impl RunUsage {
+    /// Render the footer line (without surrounding markdown).
+    ///
+    /// The cost is omitted for models without known pricing and the tool
+    /// count when no tools ran.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use zeptoclaw::agent::usage_footer::RunUsage;
+    ///
+    /// let usage = RunUsage {
+    ///     input_tokens: 1_234,
+    ///     output_tokens: 340,
+    ///     model: "my-model".to_string(),
+    ///     tool_calls: 0,
+    /// };
+    /// assert_eq!(usage.footer(&HashMap::new()), "(1.2k in / 340 out tok · my-model)");
+    /// ```
+    pub fn footer(&self, custom_pricing: &HashMap<String, ModelPricing>) -> String {
+        let mut parts = vec![format!(
+            "{} in / {} out tok",
+            compact_count(self.input_tokens),
+            compact_count(self.output_tokens)
+        )];
+        let cost = estimate_cost(
+            &self.model,
+            u32::try_from(self.input_tokens).unwrap_or(u32::MAX),
+            u32::try_from(self.output_tokens).unwrap_or(u32::MAX),
+            custom_pricing,
+        );
+        if let Some(cost) = cost {
+            parts.push(format_cost(cost));
+        }
+        if !self.model.is_empty() {
+            parts.push(self.model.clone());
+        }
+        match self.tool_calls {
+            0 => {}
+            1 => parts.push("1 tool".to_string()),
+            n => parts.push(format!("{} tools", n)),
+        }
+        format!("({})", parts.join(" · "))
+    }
+
+    /// Append the footer to `reply` as an italic line of its own.
+    pub fn append_to(&self, reply: &str, custom_pricing: &HashMap<String, ModelPricing>) -> String {
+        format!("{}\n\n_{}_", reply.trim_end(), self.footer(custom_pricing))
+    }
+}
+
+/// `950`, `1.2k`, `45k`, `1.5M`.
+fn compact_count(n: u64) -> String {
+    let scaled = |value: f64, suffix: &str| {
+        if value < 10.0 {
+            format!("{:.1}{}", value, suffix).replace(&format!(".0{}", suffix), suffix)
+        } else {
+            format!("{:.0}{}", value, suffix)
+        }
+    };
+    match n {
+        0..=999 => n.to_string(),
+        1_000..=999_999 => scaled(n as f64 / 1_000.0, "k"),
+        _ => scaled(n as f64 / 1_000_000.0, "M"),
+    }
+}
+
+/// `$0.004`, `$0.12`, `<$0.001`.
+fn format_cost(cost: f64) -> String {
+    if cost < 0.001 {
+        "<$0.001".to_string()
+    } else if cost < 0.01 {
+        format!("${:.3}", cost)
+    } else {
+        format!("${:.2}", cost)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_footer_with_known_usage() {
+        let mut pricing = HashMap::new();
+        pricing.insert(
+            "gemini-2.0-flash".to_string(),
+            ModelPricing {
+                input_cost_per_million: 0.10,
+                output_cost_per_million: 8.0,
+            },
+        );
+        let usage = RunUsage {
+            input_tokens: 1_200,
+            output_tokens: 340,
+            model: "gemini-2.0-flash".to_string(),
+            tool_calls: 2,
+        };
+        // 1200 * 0.10 / 1M + 340 * 8.0 / 1M = 0.00284
+        assert_eq!(
+            usage.footer(&pricing),
+            "(1.2k in / 340 out tok · $0.003 · gemini-2.0-flash · 2 tools)"
+        );
+        assert_eq!(
+            usage.append_to("Done.\n", &pricing),
+            "Done.\n\n_(1.2k in / 340 out tok · $0.003 · gemini-2.0-flash · 2 tools)_"
+        );
+    }
+
+    #[test]
+    fn test_compact_counts_and_costs() {
+        assert_eq!(compact_count(999), "999");
+        assert_eq!(compact_count(1_000), "1k");
+        assert_eq!(compact_count(45_300), "45k");
+        assert_eq!(compact_count(1_500_000), "1.5M");
+        assert_eq!(format_cost(0.0004), "<$0.001");
+        assert_eq!(format_cost(0.1234), "$0.12");
+    }
+}
@@ -1,7 +1,14 @@
 //! Panel event bus — bridges agent loop events to WebSocket clients.
+//!
+//! Each client reads through a [`ClientSubscription`], which filters events by
+//! topic and buffers them in a bounded queue.  When a slow client falls
+//! behind, the oldest buffered events are dropped and replaced by a single
+//! [`PanelEvent::Dropped`] marker; the broadcaster is never blocked.
 
 use serde::Serialize;
-use tokio::sync::broadcast;
+use std::collections::{HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+use tokio::sync::{broadcast, Notify};
 
 /// Events emitted by the agent loop and consumed by WebSocket clients.
 #[derive(Debug, Clone, Serialize)]
@@ -14,17 +21,185 @@ pub enum PanelEvent {
     /// A tool execution failed.
     ToolFailed { tool: String, error: String },
     /// An inbound message was received on a channel.
-    MessageReceived { channel: String, chat_id: String },
+    MessageReceived {
+        channel: String,
+        chat_id: String,
+        #[serde(skip_serializing_if = "String::is_empty")]
+        trace_id: String,
+    },
     /// An agent run has started for a session.
-    AgentStarted { session_key: String },
+    AgentStarted {
+        session_key: String,
+        #[serde(skip_serializing_if = "String::is_empty")]
+        trace_id: String,
+    },
     /// An agent run has completed.
-    AgentDone { session_key: String, tokens: u64 },
+    AgentDone {
+        session_key: String,
+        tokens: u64,
+        #[serde(skip_serializing_if = "String::is_empty")]
+        trace_id: String,
+    },
     /// Context compaction occurred.
     Compaction { from_tokens: u64, to_tokens: u64 },
     /// A channel's status changed.
     ChannelStatus { channel: String, status: String },
     /// A cron job fired.
     CronFired { job_id: String, status: String },
+    /// Marker sent to a client that fell behind: `count` events were dropped.
+    Dropped { count: u64 },
+}
+
+impl PanelEvent {
+    /// The event kind, matching the serialized `type` tag (e.g. `tool_done`).
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::ToolStarted { .. } => "tool_started",
+            Self::ToolDone { .. } => "tool_done",
+            Self::ToolFailed { .. } => "tool_failed",
+            Self::MessageReceived { .. } => "message_received",
+            Self::AgentStarted { .. } => "agent_started",
+            Self::AgentDone { .. } => "agent_done",
+            Self::Compaction { .. } => "compaction",
+            Self::ChannelStatus { .. } => "channel_status",
+            Self::CronFired { .. } => "cron_fired",
+            Self::Dropped { .. } => "dropped",
+        }
+    }
+
+    /// The topic group of the event (e.g. `tool` for all tool events).
+    pub fn topic(&self) -> &'static str {
+        match self {
+            Self::ToolStarted { .. } | Self::ToolDone { .. } | Self::ToolFailed { .. } => "tool",
+            Self::MessageReceived { .. } => "message",
+            Self::AgentStarted { .. } | Self::AgentDone { .. } => "agent",
+            Self::Compaction { .. } => "compaction",
+            Self::ChannelStatus { .. } => "channel",
+            Self::CronFired { .. } => "cron",
+            Self::Dropped { .. } => "dropped",
+        }
+    }
+}
+
+/// Per-client topic filter.
+///
+/// An empty filter accepts every event.  Otherwise an event passes when its
+/// [`kind`](PanelEvent::kind) or [`topic`](PanelEvent::topic) is listed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EventFilter {
+    topics: HashSet<String>,
+}
+
+impl EventFilter {
+    /// Parse a comma-separated topic list such as `"tool,agent_done"`.
+    pub fn parse(spec: &str) -> Self {
+        let topics = spec
+            .split(',')
+            .map(|t| t.trim().to_ascii_lowercase())
+            .filter(|t| !t.is_empty())
+            .collect();
+        Self { topics }
+    }
+
+    /// Returns `true` if `event` should be delivered to the client.
+    pub fn matches(&self, event: &PanelEvent) -> bool {
+        self.topics.is_empty()
+            || self.topics.contains(event.kind())
+            || self.topics.contains(event.topic())
+    }
+}
+
+/// Bounded per-client event queue that drops the oldest events on overflow.
+#[derive(Debug)]
+pub struct ClientBuffer {
+    events: VecDeque<PanelEvent>,
+    capacity: usize,
+    dropped: u64,
+    closed: bool,
+}
+
+impl ClientBuffer {
+    /// Default number of events buffered per client.
+    pub const DEFAULT_CAPACITY: usize = 256;
+
+    /// Create a buffer holding at most `capacity` events (minimum 1).
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            events: VecDeque::with_capacity(capacity),
+            capacity,
+            dropped: 0,
+            closed: false,
+        }
+    }
+
+    /// Queue an event, evicting the oldest one if the buffer is full.
+    pub fn push(&mut self, event: PanelEvent) {
+        if self.events.len() == self.capacity {
+            self.events.pop_front();
+            self.dropped += 1;
+        }
+        self.events.push_back(event);
+    }
+
+    /// Record events lost before they reached the buffer.
+    pub fn record_dropped(&mut self, count: u64) {
+        self.dropped += count;
+    }
+
+    /// Take all queued events.  If anything was dropped since the last drain,
+    /// a [`PanelEvent::Dropped`] marker is emitted first.
+    pub fn drain(&mut self) -> Vec<PanelEvent> {
+        let mut out = Vec::with_capacity(self.events.len() + 1);
+        if self.dropped > 0 {
+            out.push(PanelEvent::Dropped {
+                count: self.dropped,
+            });
+            self.dropped = 0;
+        }
+        out.extend(self.events.drain(..));
+        out
+    }
+
+    fn is_empty(&self) -> bool {
+        self.events.is_empty() && self.dropped == 0
+    }
+}
+
+/// A filtered, buffered subscription to the [`EventBus`].
+///
+/// A background task pumps events from the broadcast channel into the
+/// client's [`ClientBuffer`] so a slow reader only ever loses its own oldest
+/// events.  The task is aborted when the subscription is dropped.
+pub struct ClientSubscription {
+    buffer: Arc<Mutex<ClientBuffer>>,
+    notify: Arc<Notify>,
+    pump: tokio::task::JoinHandle<()>,
+}
+
+impl ClientSubscription {
+    /// Wait for the next batch of events.  Returns `None` once the bus has
+    /// shut down and every buffered event has been delivered.
+    pub async fn next_batch(&self) -> Option<Vec<PanelEvent>> {
+        loop {
+            {
+                let mut buffer = self.buffer.lock().unwrap_or_else(|e| e.into_inner());
+                if !buffer.is_empty() {
+                    return Some(buffer.drain());
+                }
+                if buffer.closed {
+                    return None;
+                }
+            }
+            self.notify.notified().await;
+        }
+    }
+}
+
+impl Drop for ClientSubscription {
+    fn drop(&mut self) {
+        self.pump.abort();
+    }
 }
 
 /// Broadcast-based event bus for panel real-time events.
@@ -50,6 +225,44 @@ impl EventBus {
         self.tx.subscribe()
     }
 
+    /// Subscribe with a topic filter and a bounded per-client buffer of
+    /// `capacity` events.
+    pub fn subscribe_filtered(&self, filter: EventFilter, capacity: usize) -> ClientSubscription {
+        let mut rx = self.tx.subscribe();
+        let buffer = Arc::new(Mutex::new(ClientBuffer::new(capacity)));
+        let notify = Arc::new(Notify::new());
+
+        let pump = tokio::spawn({
+            let buffer = Arc::clone(&buffer);
+            let notify = Arc::clone(&notify);
+            async move {
+                loop {
+                    let event = rx.recv().await;
+                    let closed = {
+                        let mut buffer = buffer.lock().unwrap_or_else(|e| e.into_inner());
+                        match event {
+                            Ok(event) if filter.matches(&event) => buffer.push(event),
+                            Ok(_) => continue,
+                            Err(broadcast::error::RecvError::Lagged(n)) => buffer.record_dropped(n),
+                            Err(broadcast::error::RecvError::Closed) => buffer.closed = true,
+                        }
+                        buffer.closed
+                    };
+                    notify.notify_one();
+                    if closed {
+                        return;
+                    }
+                }
+            }
+        });
+
+        ClientSubscription {
+            buffer,
+            notify,
+            pump,
+        }
+    }
+
     /// Get the current number of active subscribers.
     pub fn receiver_count(&self) -> usize {
         self.tx.receiver_count()
@@ -85,6 +298,7 @@ mod tests {
         let mut rx2 = bus.subscribe();
         bus.send(PanelEvent::AgentStarted {
             session_key: "test:123".into(),
+            trace_id: String::new(),
         });
         assert!(rx1.recv().await.is_ok());
         assert!(rx2.recv().await.is_ok());
@@ -109,6 +323,78 @@ mod tests {
         assert_eq!(bus.receiver_count(), 2);
     }
 
+    #[tokio::test]
+    async fn test_filtered_subscription_delivers_only_subscribed_kinds() {
+        let bus = EventBus::new(16);
+        let sub = bus.subscribe_filtered(EventFilter::parse("tool, agent_done"), 16);
+        bus.send(PanelEvent::ToolStarted {
+            tool: "shell".into(),
+        });
+        bus.send(PanelEvent::AgentStarted {
+            session_key: "s".into(),
+            trace_id: String::new(),
+        });
+        bus.send(PanelEvent::CronFired {
+            job_id: "j".into(),
+            status: "ok".into(),
+        });
+        bus.send(PanelEvent::AgentDone {
+            session_key: "s".into(),
+            tokens: 10,
+            trace_id: String::new(),
+        });
+        drop(bus);
+
+        let mut kinds = Vec::new();
+        while let Some(batch) = sub.next_batch().await {
+            kinds.extend(batch.iter().map(PanelEvent::kind));
+        }
+        assert_eq!(kinds, vec!["tool_started", "agent_done"]);
+    }
+
+    #[test]
+    fn test_empty_filter_accepts_everything() {
+        let filter = EventFilter::parse(" , ");
+        assert!(filter.matches(&PanelEvent::Compaction {
+            from_tokens: 10,
+            to_tokens: 5,
+        }));
+    }
+
+    #[test]
+    fn test_client_buffer_drops_oldest_with_marker() {
+        let mut buffer = ClientBuffer::new(2);
+        for i in 0..5 {
+            buffer.push(PanelEvent::AgentDone {
+                session_key: format!("s{i}"),
+                tokens: i,
+                trace_id: String::new(),
+            });
+        }
+
+        let drained = buffer.drain();
+        assert_eq!(drained.len(), 3);
+        assert!(matches!(drained[0], PanelEvent::Dropped { count: 3 }));
+        assert!(matches!(
+            drained[1],
+            PanelEvent::AgentDone { tokens: 3, .. }
+        ));
+        assert!(matches!(
+            drained[2],
+            PanelEvent::AgentDone { tokens: 4, .. }
+        ));
+        assert!(buffer.drain().is_empty());
+    }
+
+    #[test]
+    fn test_client_buffer_counts_lagged_events() {
+        let mut buffer = ClientBuffer::new(4);
+        buffer.record_dropped(7);
+        let drained = buffer.drain();
+        assert_eq!(drained.len(), 1);
+        assert!(matches!(drained[0], PanelEvent::Dropped { count: 7 }));
+    }
+
     #[test]
     fn test_panel_event_serialization() {
         let event = PanelEvent::ToolDone {
@@ -120,4 +406,14 @@ mod tests {
         assert!(json.contains(r#""tool":"web_search""#));
         assert!(json.contains(r#""duration_ms":230"#));
     }
+
+    #[test]
+    fn test_agent_event_includes_trace_id() {
+        let event = PanelEvent::AgentStarted {
+            session_key: "telegram:1".into(),
+            trace_id: "abc123".into(),
+        };
+        let json = serde_json::to_string(&event).unwrap();
+        assert!(json.contains(r#""trace_id":"abc123""#));
+    }
 }
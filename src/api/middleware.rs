@@ -5,7 +5,7 @@
 //! Accepts both static API tokens and short-lived HS256 JWTs issued by
 //! `POST /api/auth/login`.
 //!
-//! For mutating requests (POST/PUT/DELETE), the middleware also validates an
+//! For mutating requests (POST/PUT/PATCH/DELETE), the middleware also validates an
 //! `X-CSRF-Token` header — except on the login endpoint itself, which is
 //! exempt because it runs before the caller has a token.
 
@@ -92,6 +92,24 @@ pub fn validate_csrf_token(token: &str, secret: &str) -> bool {
 // Auth middleware
 // ---------------------------------------------------------------------------
 
+/// Identity of an authenticated caller, attached to the request as an
+/// extension by [`auth_middleware`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PanelIdentity {
+    /// JWT subject, or `"api-token"` for the static API token.
+    pub subject: String,
+    /// Whether the caller may perform admin-only operations such as editing
+    /// the runtime config.
+    pub is_admin: bool,
+}
+
+impl PanelIdentity {
+    /// Subject recorded for callers using the static API token.
+    pub const API_TOKEN_SUBJECT: &'static str = "api-token";
+    /// JWT subject granted the admin role (issued by `/api/auth/login`).
+    pub const ADMIN_SUBJECT: &'static str = "admin";
+}
+
 /// Middleware that checks for `Authorization: Bearer <token>` header.
 ///
 /// Skips auth for:
@@ -105,15 +123,20 @@ pub fn validate_csrf_token(token: &str, secret: &str) -> bool {
 /// 2. Short-lived HS256 JWT issued by `/api/auth/login` (validated against
 ///    `state.jwt_secret`)
 ///
-/// For mutating methods (POST/PUT/DELETE) on authenticated endpoints, the
-/// middleware additionally validates the `X-CSRF-Token` header.  The login
+/// The static API token and the panel's `admin` JWT subject are granted the
+/// admin role; the resulting [`PanelIdentity`] is inserted into the request
+/// extensions for handlers that need it.
+///
+/// For mutating methods (POST/PUT/PATCH/DELETE) on authenticated endpoints,
+/// the middleware additionally validates the `X-CSRF-Token` header.  The login
 /// endpoint is exempt because the caller does not yet possess a token.
 pub async fn auth_middleware(
     State(state): State<Arc<AppState>>,
-    request: Request<axum::body::Body>,
+    mut request: Request<axum::body::Body>,
     next: Next,
 ) -> Result<Response, StatusCode> {
-    let path = request.uri().path();
+    let path = request.uri().path().to_string();
+    let path = path.as_str();
 
     // Public endpoints that skip auth entirely.
     if path == "/api/health"
@@ -135,22 +158,34 @@ pub async fn auth_middleware(
             let token = &header[7..];
 
             // Accept static API token OR a valid JWT.
-            let is_valid = token == state.api_token
-                || crate::api::auth::validate_jwt(token, &state.jwt_secret).is_ok();
-
-            if !is_valid {
-                return Err(StatusCode::UNAUTHORIZED);
-            }
+            let identity = if token == state.api_token {
+                PanelIdentity {
+                    subject: PanelIdentity::API_TOKEN_SUBJECT.to_string(),
+                    is_admin: true,
+                }
+            } else {
+                match crate::api::auth::validate_jwt(token, &state.jwt_secret) {
+                    Ok(claims) => PanelIdentity {
+                        is_admin: claims.sub == PanelIdentity::ADMIN_SUBJECT,
+                        subject: claims.sub,
+                    },
+                    Err(_) => return Err(StatusCode::UNAUTHORIZED),
+                }
+            };
 
             // For mutating methods, require a valid CSRF token as well.
             let method = request.method();
             if matches!(
                 *method,
-                axum::http::Method::POST | axum::http::Method::PUT | axum::http::Method::DELETE
+                axum::http::Method::POST
+                    | axum::http::Method::PUT
+                    | axum::http::Method::PATCH
+                    | axum::http::Method::DELETE
             ) {
                 // OpenAI-compatible API endpoints are authenticated via Bearer token
                 // but exempt from CSRF (they are not browser-originated).
                 if path.starts_with("/v1/") {
+                    request.extensions_mut().insert(identity);
                     return Ok(next.run(request).await);
                 }
 
@@ -164,6 +199,7 @@ pub async fn auth_middleware(
                 }
             }
 
+            request.extensions_mut().insert(identity);
             Ok(next.run(request).await)
         }
         _ => Err(StatusCode::UNAUTHORIZED),
@@ -558,4 +594,66 @@ mod tests {
         let resp = app.oneshot(req).await.unwrap();
         assert_eq!(resp.status(), StatusCode::OK);
     }
+
+    // -----------------------------------------------------------------------
+    // Identity / roles
+    // -----------------------------------------------------------------------
+
+    fn identity_app(state: Arc<AppState>) -> Router {
+        Router::new()
+            .route(
+                "/api/whoami",
+                get(|ext: Option<axum::Extension<PanelIdentity>>| async move {
+                    match ext {
+                        Some(axum::Extension(id)) => format!("{}:{}", id.subject, id.is_admin),
+                        None => "none".to_string(),
+                    }
+                }),
+            )
+            .route(
+                "/api/protected",
+                axum::routing::patch(|| async { "patched" }),
+            )
+            .layer(axum_mw::from_fn_with_state(state, auth_middleware))
+    }
+
+    async fn whoami(app: Router, token: &str) -> String {
+        let req = Request::builder()
+            .uri("/api/whoami")
+            .header("authorization", format!("Bearer {token}"))
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        let bytes = axum::body::to_bytes(resp.into_body(), 1024).await.unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_static_token_is_admin() {
+        let app = identity_app(make_state());
+        assert_eq!(whoami(app, "static-test-token").await, "api-token:true");
+    }
+
+    #[tokio::test]
+    async fn test_jwt_role_follows_subject() {
+        let state = make_state();
+        let admin = panel_auth::generate_jwt("admin", &state.jwt_secret, 3600).unwrap();
+        let viewer = panel_auth::generate_jwt("viewer", &state.jwt_secret, 3600).unwrap();
+        let app = identity_app(state);
+        assert_eq!(whoami(app.clone(), &admin).await, "admin:true");
+        assert_eq!(whoami(app, &viewer).await, "viewer:false");
+    }
+
+    #[tokio::test]
+    async fn test_patch_without_csrf_token_returns_403() {
+        let app = identity_app(make_state());
+        let req = Request::builder()
+            .method(Method::PATCH)
+            .uri("/api/protected")
+            .header("authorization", "Bearer static-test-token")
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    }
 }
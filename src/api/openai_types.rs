@@ -463,7 +463,8 @@ pub fn done_chunk(model: &str, id: &str, created: u64) -> ChatCompletionChunk {
 /// Map a `StreamEvent` to the corresponding SSE chunk (if any).
 ///
 /// Returns `None` for events that have no chunk representation (e.g.,
-/// empty `ToolCalls` or `Error` events).
+/// empty `ToolCalls` or `Error` events). Early `ToolCallReady` events are
+/// skipped: the calls are sent once, with the final `ToolCalls` list.
 pub fn chunk_from_stream_event(
     event: &StreamEvent,
     model: &str,
@@ -480,7 +481,7 @@ pub fn chunk_from_stream_event(
         StreamEvent::ToolCalls(calls) if !calls.is_empty() => {
             Some(tool_calls_chunk(calls, model, id, created))
         }
-        StreamEvent::ToolCalls(_) => None,
+        StreamEvent::ToolCalls(_) | StreamEvent::ToolCallReady(_) => None,
     }
 }
 
@@ -3,9 +3,19 @@
 //! Provides `POST /api/auth/login` which exchanges a valid password for a
 //! short-lived HS256 JWT.  The JWT is subsequently accepted by the auth
 //! middleware on all protected endpoints.
+//!
+//! Failed logins are throttled per client IP with the same brute-force
+//! lockout used by device pairing; a locked-out client receives 429 with the
+//! remaining lockout time.
 
-use axum::{extract::State, http::StatusCode, Json};
+use axum::{
+    extract::{ConnectInfo, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
 use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
 use std::sync::Arc;
 
 use crate::api::server::AppState;
@@ -31,6 +41,9 @@ pub struct LoginResponse {
 // Handler
 // ============================================================================
 
+/// Panel username embedded in issued JWTs.
+const PANEL_USERNAME: &str = "admin";
+
 /// `POST /api/auth/login` — exchange a password for a JWT.
 ///
 /// If `AppState.password_hash` is `None`, password-based login is not
@@ -39,26 +52,62 @@ pub struct LoginResponse {
 ///
 /// On success returns a 24-hour HS256 JWT that is accepted by all protected
 /// endpoints alongside the static API token.
+///
+/// Failures are counted per client IP (or per username when the peer address
+/// is unknown).  Once `AppState.login_lockout` locks the client out, requests
+/// are rejected with 429 and a `Retry-After` header until the lockout expires,
+/// without checking the password.  A successful login clears the count.
 pub async fn login(
     State(state): State<Arc<AppState>>,
+    peer: Option<Extension<ConnectInfo<SocketAddr>>>,
     Json(body): Json<LoginRequest>,
-) -> Result<Json<LoginResponse>, StatusCode> {
-    match &state.password_hash {
-        Some(hash) => {
-            let ok = crate::api::auth::verify_password(&body.password, hash)
-                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-
-            if ok {
-                let token = crate::api::auth::generate_jwt("admin", &state.jwt_secret, 86_400)
-                    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-                Ok(Json(LoginResponse { token }))
-            } else {
-                Err(StatusCode::UNAUTHORIZED)
-            }
-        }
-        // Password auth is not configured — callers must use a static API token.
-        None => Err(StatusCode::NOT_FOUND),
+) -> Result<Json<LoginResponse>, Response> {
+    // Password auth is not configured — callers must use a static API token.
+    let Some(hash) = &state.password_hash else {
+        return Err(StatusCode::NOT_FOUND.into_response());
+    };
+
+    let identifier = match peer {
+        Some(Extension(ConnectInfo(addr))) => addr.ip().to_string(),
+        None => format!("user:{PANEL_USERNAME}"),
+    };
+
+    let remaining = state
+        .login_lockout
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remaining(&identifier);
+    if let Some(remaining) = remaining {
+        // Round up so clients never retry a moment too early.
+        let secs = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+        return Err((
+            StatusCode::TOO_MANY_REQUESTS,
+            [(header::RETRY_AFTER, secs.to_string())],
+            Json(serde_json::json!({
+                "error": "too many failed login attempts",
+                "retry_after_secs": secs,
+            })),
+        )
+            .into_response());
     }
+
+    let ok = crate::api::auth::verify_password(&body.password, hash)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR.into_response())?;
+
+    let mut lockout = state
+        .login_lockout
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    if !ok {
+        lockout.record_failure(&identifier);
+        return Err(StatusCode::UNAUTHORIZED.into_response());
+    }
+    lockout.clear(&identifier);
+    drop(lockout);
+
+    let token = crate::api::auth::generate_jwt(PANEL_USERNAME, &state.jwt_secret, 86_400)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR.into_response())?;
+    Ok(Json(LoginResponse { token }))
 }
 
 // ============================================================================
@@ -81,6 +130,27 @@ mod tests {
         Arc::new(state)
     }
 
+    fn make_state_with_lockout(password: &str, max_attempts: u32) -> Arc<AppState> {
+        let hash = panel_auth::hash_password(password).expect("hash must succeed");
+        let mut state = AppState::new("tok".into(), EventBus::new(8));
+        state.password_hash = Some(hash);
+        state.login_lockout = Arc::new(std::sync::Mutex::new(
+            crate::security::LockoutTracker::new(max_attempts, 300),
+        ));
+        Arc::new(state)
+    }
+
+    async fn attempt(app: Router, password: &str) -> axum::response::Response {
+        let body = serde_json::json!({ "password": password }).to_string();
+        let req = Request::builder()
+            .method("POST")
+            .uri("/api/auth/login")
+            .header("content-type", "application/json")
+            .body(Body::from(body))
+            .unwrap();
+        app.oneshot(req).await.unwrap()
+    }
+
     fn make_state_no_password() -> Arc<AppState> {
         let bus = EventBus::new(8);
         Arc::new(AppState::new("tok".into(), bus))
@@ -148,4 +218,50 @@ mod tests {
         let resp = app.oneshot(req).await.unwrap();
         assert_eq!(resp.status(), StatusCode::NOT_FOUND);
     }
+
+    #[tokio::test]
+    async fn test_login_locks_out_after_max_failures() {
+        let state = make_state_with_lockout("hunter2", 3);
+        let app = make_app(state);
+
+        for _ in 0..3 {
+            let resp = attempt(app.clone(), "wrong").await;
+            assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        }
+
+        // Locked out: even the correct password is rejected with 429.
+        let resp = attempt(app, "hunter2").await;
+        assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+        let retry_after: u64 = resp.headers()[header::RETRY_AFTER]
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!(retry_after > 0 && retry_after <= 300);
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(json["retry_after_secs"], retry_after);
+    }
+
+    #[tokio::test]
+    async fn test_successful_login_clears_failures() {
+        let state = make_state_with_lockout("hunter2", 3);
+        let app = make_app(state.clone());
+
+        for _ in 0..2 {
+            attempt(app.clone(), "wrong").await;
+        }
+        let resp = attempt(app.clone(), "hunter2").await;
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        // The count was reset, so two more failures stay within the allowance.
+        for _ in 0..2 {
+            let resp = attempt(app.clone(), "wrong").await;
+            assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        }
+        let resp = attempt(app, "hunter2").await;
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
 }
@@ -0,0 +1,436 @@
+//! Runtime configuration routes.
+//!
+//! `GET /api/config` returns the current config with secret values (and every
+//! value in `env` / `headers` maps) replaced by `"***"`.  `PATCH /api/config`
+//! applies a JSON merge patch (RFC 7386), validates the result, and persists
+//! it to the config file, where the gateway's config watcher picks up
+//! hot-reloadable sections.  Both require the admin role.
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::{Extension, Json};
+use serde_json::{json, Map, Value};
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::api::middleware::PanelIdentity;
+use crate::api::server::AppState;
+use crate::config::validate::{self, Diagnostic, DiagnosticLevel};
+use crate::config::watcher::{changed_sections, HOT_RELOAD_SECTIONS};
+use crate::config::Config;
+use crate::security::encryption::is_secret_field;
+
+/// Placeholder returned in place of secret values.
+pub const REDACTED: &str = "***";
+
+pub async fn get_config(
+    State(state): State<Arc<AppState>>,
+    identity: Option<Extension<PanelIdentity>>,
+) -> Result<Json<Value>, StatusCode> {
+    if !matches!(identity, Some(Extension(ref id)) if id.is_admin) {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    let config = match &state.config_path {
+        Some(path) => {
+            let raw = read_raw(path).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            serde_json::from_value::<Config>(raw).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        }
+        None => match &state.config {
+            Some(config) => config.as_ref().clone(),
+            None => return Err(StatusCode::NOT_FOUND),
+        },
+    };
+
+    let mut value = serde_json::to_value(&config).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    redact_secrets(&mut value);
+    Ok(Json(json!({ "config": value })))
+}
+
+pub async fn patch_config(
+    State(state): State<Arc<AppState>>,
+    identity: Option<Extension<PanelIdentity>>,
+    Json(patch): Json<Value>,
+) -> (StatusCode, Json<Value>) {
+    if !matches!(identity, Some(Extension(ref id)) if id.is_admin) {
+        return error(StatusCode::FORBIDDEN, "admin role required");
+    }
+    let Some(path) = state.config_path.as_deref() else {
+        return error(StatusCode::NOT_FOUND, "config persistence is not enabled");
+    };
+    if !patch.is_object() {
+        return error(StatusCode::BAD_REQUEST, "patch must be a JSON object");
+    }
+
+    let Ok(raw) = read_raw(path) else {
+        return error(StatusCode::INTERNAL_SERVER_ERROR, "failed to read config");
+    };
+    let Ok(old) = serde_json::from_value::<Config>(raw.clone()) else {
+        return error(StatusCode::INTERNAL_SERVER_ERROR, "failed to parse config");
+    };
+
+    let mut merged = raw;
+    merge_patch(&mut merged, &patch);
+
+    let new = match validate_patched(&merged) {
+        Ok(config) => config,
+        Err(errors) => {
+            let errors: Vec<Value> = errors
+                .iter()
+                .map(|d| json!({ "path": d.path, "message": d.message }))
+                .collect();
+            return (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Json(json!({ "errors": errors })),
+            );
+        }
+    };
+
+    let changed = changed_sections(&old, &new);
+    if !changed.is_empty() {
+        if let Err(e) = write_raw(path, &merged) {
+            tracing::warn!(path = %path.display(), error = %e, "Failed to persist config");
+            return error(StatusCode::INTERNAL_SERVER_ERROR, "failed to write config");
+        }
+    }
+
+    let (hot_reload, restart_required): (Vec<String>, Vec<String>) = changed
+        .iter()
+        .cloned()
+        .partition(|s| HOT_RELOAD_SECTIONS.contains(&s.as_str()));
+
+    (
+        StatusCode::OK,
+        Json(json!({
+            "changed": changed,
+            "hot_reload": hot_reload,
+            "restart_required": restart_required,
+        })),
+    )
+}
+
+fn error(status: StatusCode, message: &str) -> (StatusCode, Json<Value>) {
+    (status, Json(json!({ "error": message })))
+}
+
+/// Reads the config file as raw JSON, keeping `ENC[...]` values untouched.
+/// A missing file is treated as an empty config.
+fn read_raw(path: &Path) -> crate::error::Result<Value> {
+    if !path.exists() {
+        return Ok(Value::Object(Map::new()));
+    }
+    let content = std::fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+fn write_raw(path: &Path, value: &Value) -> crate::error::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(value)?)?;
+    Ok(())
+}
+
+/// Keys holding maps with user-chosen names (e.g. `mcp.servers[].env`,
+/// `custom_tools[].env`) whose values are all treated as secrets.
+const SECRET_MAP_KEYS: &[&str] = &["env", "headers"];
+
+/// Returns `true` for keys whose string values must never leave the server.
+/// Matching ignores ASCII case.
+fn is_sensitive_key(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    is_secret_field(&key)
+        || matches!(key.as_str(), "password" | "authtoken" | "secret_key")
+        || key.ends_with("_token")
+        || key.ends_with("_secret")
+        || key.ends_with("_api_key")
+}
+
+/// Returns `true` for keys whose object value is a map of secret values.
+fn is_secret_map_key(key: &str) -> bool {
+    SECRET_MAP_KEYS.iter().any(|k| key.eq_ignore_ascii_case(k))
+}
+
+/// Replaces `s` with [`REDACTED`] unless it is empty.
+fn redact(s: &mut String) {
+    if !s.is_empty() {
+        *s = REDACTED.to_string();
+    }
+}
+
+/// Replaces every non-empty string stored under a sensitive key, or inside
+/// an `env` / `headers` map, with [`REDACTED`].
+fn redact_secrets(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, val) in map.iter_mut() {
+                match val {
+                    Value::String(s) if is_sensitive_key(key) => redact(s),
+                    Value::Object(entries) if is_secret_map_key(key) => {
+                        for entry in entries.values_mut() {
+                            if let Value::String(s) = entry {
+                                redact(s);
+                            }
+                        }
+                    }
+                    other => redact_secrets(other),
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_secrets),
+        _ => {}
+    }
+}
+
+/// Applies a JSON merge patch to `target`.
+///
+/// `null` removes a key and objects merge recursively.  A sensitive key (or an
+/// `env` / `headers` entry) set to [`REDACTED`] keeps its existing value, so a
+/// redacted `GET` response can be edited and sent back without clobbering
+/// secrets.
+fn merge_patch(target: &mut Value, patch: &Value) {
+    merge_patch_inner(target, patch, false);
+}
+
+/// [`merge_patch`], where `secret_map` marks `target` as an `env` / `headers`
+/// map whose every value is redacted.
+fn merge_patch_inner(target: &mut Value, patch: &Value, secret_map: bool) {
+    let Value::Object(patch) = patch else {
+        *target = patch.clone();
+        return;
+    };
+    if !target.is_object() {
+        *target = Value::Object(Map::new());
+    }
+    let Value::Object(target) = target else {
+        return;
+    };
+    for (key, value) in patch {
+        match value {
+            Value::Null => {
+                target.remove(key);
+            }
+            Value::String(s) if s == REDACTED && (secret_map || is_sensitive_key(key)) => {}
+            _ => merge_patch_inner(
+                target.entry(key.clone()).or_insert(Value::Null),
+                value,
+                is_secret_map_key(key),
+            ),
+        }
+    }
+}
+
+/// Validates a patched raw config, returning the typed config or the list of
+/// error diagnostics that blocked it.
+fn validate_patched(raw: &Value) -> std::result::Result<Config, Vec<Diagnostic>> {
+    let mut errors: Vec<Diagnostic> = validate::validate_config(raw)
+        .into_iter()
+        .filter(|d| d.level == DiagnosticLevel::Error)
+        .collect();
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    let config = match serde_json::from_value::<Config>(raw.clone()) {
+        Ok(config) => config,
+        Err(e) => {
+            return Err(vec![Diagnostic {
+                level: DiagnosticLevel::Error,
+                path: String::new(),
+                message: format!("Invalid config: {e}"),
+            }])
+        }
+    };
+
+    errors.extend(
+        validate::validate_provider_api_bases(&config)
+            .into_iter()
+            .filter(|d| d.level == DiagnosticLevel::Error),
+    );
+    if errors.is_empty() {
+        Ok(config)
+    } else {
+        Err(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::events::EventBus;
+    use tempfile::TempDir;
+
+    fn state_with_file(contents: &str) -> (TempDir, Arc<AppState>) {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.json");
+        std::fs::write(&path, contents).unwrap();
+        let mut state = AppState::new("tok".into(), EventBus::new(16));
+        state.config_path = Some(path);
+        (dir, Arc::new(state))
+    }
+
+    fn admin() -> Option<Extension<PanelIdentity>> {
+        Some(Extension(PanelIdentity {
+            subject: PanelIdentity::API_TOKEN_SUBJECT.to_string(),
+            is_admin: true,
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_get_config_redacts_secrets() {
+        let (_dir, state) = state_with_file(
+            r#"{"providers":{"anthropic":{"api_key":"sk-ant-secret"}},
+                "channels":{"telegram":{"token":"123:abc"}}}"#,
+        );
+        let Json(body) = get_config(State(state), admin()).await.unwrap();
+        assert_eq!(
+            body["config"]["providers"]["anthropic"]["api_key"],
+            REDACTED
+        );
+        assert_eq!(body["config"]["channels"]["telegram"]["token"], REDACTED);
+        assert!(!body.to_string().contains("sk-ant-secret"));
+        assert!(body["config"]["agents"]["defaults"]["max_tokens"].is_number());
+    }
+
+    #[tokio::test]
+    async fn test_get_config_without_source_returns_404() {
+        let state = Arc::new(AppState::new("tok".into(), EventBus::new(16)));
+        assert_eq!(
+            get_config(State(state), admin()).await.unwrap_err(),
+            StatusCode::NOT_FOUND
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_config_requires_admin() {
+        let (_dir, state) = state_with_file("{}");
+        let viewer = Some(Extension(PanelIdentity {
+            subject: "viewer".into(),
+            is_admin: false,
+        }));
+        assert_eq!(
+            get_config(State(state.clone()), viewer).await.unwrap_err(),
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            get_config(State(state), None).await.unwrap_err(),
+            StatusCode::FORBIDDEN
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_config_redacts_env_maps() {
+        let (_dir, state) = state_with_file(
+            r#"{"mcp":{"servers":[{"name":"gh","command":"gh-mcp","env":{"GITHUB_PAT":"ghp_secret"}}]},
+                "custom_tools":[{"name":"deploy","description":"d","command":"deploy.sh","env":{"DEPLOY_KEY":"dk_secret"}}]}"#,
+        );
+        let Json(body) = get_config(State(state), admin()).await.unwrap();
+        assert_eq!(
+            body["config"]["mcp"]["servers"][0]["env"]["GITHUB_PAT"],
+            REDACTED
+        );
+        assert_eq!(
+            body["config"]["custom_tools"][0]["env"]["DEPLOY_KEY"],
+            REDACTED
+        );
+        assert_eq!(body["config"]["mcp"]["servers"][0]["command"], "gh-mcp");
+    }
+
+    #[test]
+    fn test_sensitive_keys_ignore_case() {
+        assert!(is_sensitive_key("API_KEY"));
+        assert!(is_sensitive_key("Bot_Token"));
+        assert!(is_sensitive_key("Password"));
+        assert!(!is_sensitive_key("model"));
+    }
+
+    #[tokio::test]
+    async fn test_patch_config_rejects_invalid_without_applying() {
+        let original = r#"{"gateway":{"port":8080}}"#;
+        let (_dir, state) = state_with_file(original);
+        let path = state.config_path.clone().unwrap();
+
+        let (status, Json(body)) = patch_config(
+            State(state),
+            admin(),
+            Json(json!({ "gateway": { "port": "not-a-port" } })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert!(!body["errors"].as_array().unwrap().is_empty());
+        assert_eq!(std::fs::read_to_string(path).unwrap(), original);
+    }
+
+    #[tokio::test]
+    async fn test_patch_config_rejects_unknown_field() {
+        let (_dir, state) = state_with_file("{}");
+        let (status, Json(body)) =
+            patch_config(State(state), admin(), Json(json!({ "gatway": {} }))).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["errors"][0]["path"], "gatway");
+    }
+
+    #[tokio::test]
+    async fn test_patch_config_requires_admin() {
+        let (_dir, state) = state_with_file("{}");
+        let viewer = Some(Extension(PanelIdentity {
+            subject: "viewer".into(),
+            is_admin: false,
+        }));
+        let (status, _) = patch_config(
+            State(state.clone()),
+            viewer,
+            Json(json!({ "gateway": { "port": 9000 } })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        let (status, _) = patch_config(
+            State(state),
+            None,
+            Json(json!({ "gateway": { "port": 9000 } })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_patch_config_persists_and_classifies() {
+        let (_dir, state) = state_with_file(r#"{"providers":{"anthropic":{"api_key":"sk-keep"}}}"#);
+        let path = state.config_path.clone().unwrap();
+
+        let (status, Json(body)) = patch_config(
+            State(state),
+            admin(),
+            Json(json!({
+                "gateway": { "port": 9191 },
+                "safety": { "enabled": !Config::default().safety.enabled },
+                "providers": { "anthropic": { "api_key": REDACTED } },
+            })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["hot_reload"], json!(["safety"]));
+        assert_eq!(body["restart_required"], json!(["gateway"]));
+
+        let saved: Value = serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        assert_eq!(saved["gateway"]["port"], 9191);
+        assert_eq!(saved["providers"]["anthropic"]["api_key"], "sk-keep");
+    }
+
+    #[test]
+    fn test_merge_patch_keeps_redacted_env_values() {
+        let mut target = json!({ "env": { "TOKEN": "keep", "MODE": "a" } });
+        merge_patch(
+            &mut target,
+            &json!({ "env": { "TOKEN": REDACTED, "MODE": "b" } }),
+        );
+        assert_eq!(target, json!({ "env": { "TOKEN": "keep", "MODE": "b" } }));
+    }
+
+    #[test]
+    fn test_merge_patch_null_removes_key() {
+        let mut target = json!({ "a": 1, "b": { "c": 2, "d": 3 } });
+        merge_patch(&mut target, &json!({ "a": null, "b": { "c": null } }));
+        assert_eq!(target, json!({ "b": { "d": 3 } }));
+    }
+}
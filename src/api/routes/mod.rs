@@ -1,5 +1,6 @@
 pub mod auth;
 pub mod channels;
+pub mod config;
 pub mod cron;
 pub mod health;
 pub mod metrics;
@@ -1,6 +1,6 @@
 //! WebSocket event streaming for the panel.
 
-use crate::api::events::EventBus;
+use crate::api::events::{ClientBuffer, EventBus, EventFilter};
 use crate::api::server::AppState;
 use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
 use axum::extract::{Query, State};
@@ -15,6 +15,11 @@ use std::sync::Arc;
 /// query parameter before upgrading.  Both static API tokens and valid JWTs
 /// are accepted.
 ///
+/// An optional `?topics=tool,agent_done` parameter restricts the stream to
+/// the listed event kinds or topic groups.  Each client gets a bounded
+/// buffer; when it lags, the oldest events are dropped and a `dropped`
+/// marker event reports how many were lost.
+///
 /// Enforces a hard cap of [`AppState::MAX_WS_CONNECTIONS`] concurrent
 /// WebSocket connections via a semaphore stored in [`AppState`].  When the
 /// cap is reached the handler responds with HTTP 503 before the upgrade so
@@ -53,34 +58,38 @@ pub async fn ws_events(
     };
 
     let event_bus = state.event_bus.clone();
+    let filter = params
+        .get("topics")
+        .map(|spec| EventFilter::parse(spec))
+        .unwrap_or_default();
     // Move the permit into the connection task so it is dropped (released)
     // only when the WebSocket connection closes.
-    ws.on_upgrade(move |socket| handle_ws(socket, event_bus, permit))
+    ws.on_upgrade(move |socket| handle_ws(socket, event_bus, filter, permit))
 }
 
 async fn handle_ws(
     mut socket: WebSocket,
     event_bus: EventBus,
+    filter: EventFilter,
     // Held for the lifetime of the connection; dropped when this future
     // resolves, which releases the semaphore permit.
     _permit: tokio::sync::OwnedSemaphorePermit,
 ) {
-    let mut rx = event_bus.subscribe();
-    loop {
+    let subscription = event_bus.subscribe_filtered(filter, ClientBuffer::DEFAULT_CAPACITY);
+    'conn: loop {
         tokio::select! {
-            event = rx.recv() => {
-                match event {
-                    Ok(e) => {
-                        let json = match serde_json::to_string(&e) {
-                            Ok(j) => j,
-                            Err(_) => continue,
-                        };
-                        if socket.send(Message::Text(json.into())).await.is_err() {
-                            break; // Client disconnected
-                        }
+            batch = subscription.next_batch() => {
+                let Some(batch) = batch else {
+                    break;
+                };
+                for e in batch {
+                    let json = match serde_json::to_string(&e) {
+                        Ok(j) => j,
+                        Err(_) => continue,
+                    };
+                    if socket.send(Message::Text(json.into())).await.is_err() {
+                        break 'conn; // Client disconnected
                     }
-                    Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
-                    Err(_) => break,
                 }
             }
             msg = socket.recv() => {
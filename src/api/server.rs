@@ -35,6 +35,8 @@ pub struct AppState {
     /// holds it for the lifetime of the connection; once the semaphore is
     /// exhausted the handler returns HTTP 503.
     pub ws_semaphore: Arc<tokio::sync::Semaphore>,
+    /// Brute-force lockout for `POST /api/auth/login`, keyed by client IP.
+    pub login_lockout: Arc<std::sync::Mutex<crate::security::LockoutTracker>>,
     // ── Real data stores (all optional — set when wired from gateway/CLI) ───
     /// Session manager for reading and deleting conversation sessions.
     pub session_manager: Option<Arc<crate::session::SessionManager>>,
@@ -51,6 +53,11 @@ pub struct AppState {
     pub provider: Option<Arc<dyn crate::providers::LLMProvider>>,
     /// Immutable config snapshot for model listing and provider resolution.
     pub config: Option<Arc<crate::config::Config>>,
+    /// Config file backing `GET`/`PATCH /api/config`.
+    ///
+    /// When set, the config endpoints read and persist this file; the
+    /// gateway's config watcher then applies hot-reloadable sections.
+    pub config_path: Option<PathBuf>,
 }
 
 impl AppState {
@@ -64,6 +71,9 @@ impl AppState {
             password_hash: None,
             jwt_secret: uuid::Uuid::new_v4().to_string(),
             ws_semaphore: Arc::new(tokio::sync::Semaphore::new(Self::MAX_WS_CONNECTIONS)),
+            login_lockout: Arc::new(std::sync::Mutex::new(crate::security::LockoutTracker::new(
+                5, 300,
+            ))),
             session_manager: None,
             task_store: None,
             health_registry: None,
@@ -71,6 +81,7 @@ impl AppState {
             metrics_collector: None,
             provider: None,
             config: None,
+            config_path: None,
         }
     }
 }
@@ -109,7 +120,13 @@ pub fn build_router(
         });
     let cors = CorsLayer::new()
         .allow_origin(AllowOrigin::exact(origin_value))
-        .allow_methods([Method::GET, Method::POST, Method::PUT, Method::DELETE])
+        .allow_methods([
+            Method::GET,
+            Method::POST,
+            Method::PUT,
+            Method::PATCH,
+            Method::DELETE,
+        ])
         .allow_headers([
             HeaderName::from_static("content-type"),
             HeaderName::from_static("authorization"),
@@ -124,6 +141,11 @@ pub fn build_router(
         // Health & metrics
         .route("/api/health", get(super::routes::health::get_health))
         .route("/api/metrics", get(super::routes::metrics::get_metrics))
+        // Config (PATCH requires the admin role)
+        .route(
+            "/api/config",
+            get(super::routes::config::get_config).patch(super::routes::config::patch_config),
+        )
         // Sessions
         .route("/api/sessions", get(super::routes::sessions::list_sessions))
         .route(
@@ -210,7 +232,12 @@ pub async fn start_server(
     let addr = format!("{}:{}", config.bind, config.api_port);
     let listener = tokio::net::TcpListener::bind(&addr).await?;
     tracing::info!("Panel API server listening on {addr}");
-    axum::serve(listener, app).await?;
+    // Expose the peer address so login lockouts can be keyed by client IP.
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+    )
+    .await?;
     Ok(())
 }
 
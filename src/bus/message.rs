@@ -23,6 +23,10 @@ pub struct InboundMessage {
     pub session_key: String,
     /// Additional metadata key-value pairs
     pub metadata: HashMap<String, String>,
+    /// Correlation ID generated at channel ingress and carried through the
+    /// bus, agent, provider, and response for log tracing.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub trace_id: String,
 }
 
 /// Represents an outgoing message to be sent via a channel
@@ -39,6 +43,129 @@ pub struct OutboundMessage {
     /// Additional metadata key-value pairs for channel-specific delivery hints
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub metadata: HashMap<String, String>,
+    /// Files to deliver alongside the text (channels without file support ignore these)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<MediaAttachment>,
+    /// Trace ID of the inbound message this responds to (empty if unrelated)
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub trace_id: String,
+    /// Rich rendering of the content (fields, buttons) for channels that
+    /// support it; others send `content` as plain text
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub structured: Option<StructuredOutput>,
+}
+
+/// Inbound metadata key carrying the ID of a pressed message button.
+///
+/// Channels that render [`OutputAction`] buttons publish clicks as inbound
+/// messages whose content is the action's `value` and whose metadata holds
+/// the action ID under this key.
+pub const ACTION_ID_METADATA_KEY: &str = "action_id";
+
+/// Outbound metadata key identifying a streamed reply.
+///
+/// Successive outbound messages with the same stream ID carry the full text
+/// so far; channels that support editing update a single message in place.
+pub const STREAM_ID_METADATA_KEY: &str = "stream_id";
+
+/// Outbound metadata key marking the last message of a streamed reply.
+pub const STREAM_FINAL_METADATA_KEY: &str = "stream_final";
+
+/// Channel-agnostic structured output: an optional title, labelled fields
+/// and action buttons.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StructuredOutput {
+    /// Short heading shown above the content
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Label/value pairs (e.g. tool result fields)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<StructuredField>,
+    /// Buttons the user can press (e.g. approve / reject)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub actions: Vec<OutputAction>,
+}
+
+/// A labelled value in a [`StructuredOutput`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StructuredField {
+    pub label: String,
+    pub value: String,
+}
+
+/// A button in a [`StructuredOutput`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OutputAction {
+    /// Stable identifier reported back when the button is pressed
+    pub id: String,
+    /// Button text
+    pub label: String,
+    /// Text sent back to the agent as the user's reply when pressed
+    pub value: String,
+    /// Visual emphasis
+    #[serde(default)]
+    pub style: ActionStyle,
+}
+
+/// Visual emphasis of an [`OutputAction`] button.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ActionStyle {
+    #[default]
+    Default,
+    Primary,
+    Danger,
+}
+
+impl StructuredOutput {
+    /// Creates an empty structured output.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the title (builder pattern).
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Appends a labelled field (builder pattern).
+    pub fn with_field(mut self, label: impl Into<String>, value: impl Into<String>) -> Self {
+        self.fields.push(StructuredField {
+            label: label.into(),
+            value: value.into(),
+        });
+        self
+    }
+
+    /// Appends an action button (builder pattern).
+    pub fn with_action(mut self, action: OutputAction) -> Self {
+        self.actions.push(action);
+        self
+    }
+}
+
+impl OutputAction {
+    /// Creates a button whose value is sent back when pressed.
+    pub fn new(id: impl Into<String>, label: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            label: label.into(),
+            value: value.into(),
+            style: ActionStyle::Default,
+        }
+    }
+
+    /// Sets the button style (builder pattern).
+    pub fn with_style(mut self, style: ActionStyle) -> Self {
+        self.style = style;
+        self
+    }
+}
+
+/// Generates a new trace ID (32 lowercase hex characters).
+pub fn new_trace_id() -> String {
+    uuid::Uuid::new_v4().simple().to_string()
 }
 
 /// Represents a media attachment (image, audio, video, or document)
@@ -72,7 +199,8 @@ pub enum MediaType {
 impl InboundMessage {
     /// Creates a new inbound message with the required fields.
     ///
-    /// The session key is automatically generated as "channel:chat_id".
+    /// The session key is automatically generated as "channel:chat_id" and a
+    /// fresh trace ID is assigned.
     ///
     /// # Arguments
     /// * `channel` - The source channel (e.g., "telegram")
@@ -96,6 +224,7 @@ impl InboundMessage {
             media: Vec::new(),
             session_key: format!("{}:{}", channel, chat_id),
             metadata: HashMap::new(),
+            trace_id: new_trace_id(),
         }
     }
 
@@ -134,6 +263,13 @@ impl InboundMessage {
         self
     }
 
+    /// Overrides the trace ID, e.g. to keep a re-queued message correlated
+    /// with its original (builder pattern).
+    pub fn with_trace_id(mut self, trace_id: &str) -> Self {
+        self.trace_id = trace_id.to_string();
+        self
+    }
+
     /// Checks if this message has any media attached.
     pub fn has_media(&self) -> bool {
         !self.media.is_empty()
@@ -162,6 +298,9 @@ impl OutboundMessage {
             content: content.to_string(),
             reply_to: None,
             metadata: HashMap::new(),
+            attachments: Vec::new(),
+            trace_id: String::new(),
+            structured: None,
         }
     }
 
@@ -186,7 +325,40 @@ impl OutboundMessage {
         self
     }
 
-    /// Creates an outbound message as a response to an inbound message.
+    /// Sets the trace ID linking this message to its inbound request (builder pattern).
+    pub fn with_trace_id(mut self, trace_id: &str) -> Self {
+        self.trace_id = trace_id.to_string();
+        self
+    }
+
+    /// Attaches a structured rendering of the content (builder pattern).
+    pub fn with_structured(mut self, structured: StructuredOutput) -> Self {
+        self.structured = Some(structured);
+        self
+    }
+
+    /// Adds a file attachment to the outbound message (builder pattern).
+    ///
+    /// # Example
+    /// ```
+    /// use zeptoclaw::bus::message::{MediaAttachment, MediaType, OutboundMessage};
+    ///
+    /// let msg = OutboundMessage::new("email", "me@example.com", "Report attached")
+    ///     .with_attachment(
+    ///         MediaAttachment::new(MediaType::Document)
+    ///             .with_filename("report.pdf")
+    ///             .with_mime_type("application/pdf")
+    ///             .with_data(b"%PDF-1.7".to_vec()),
+    ///     );
+    /// assert_eq!(msg.attachments.len(), 1);
+    /// ```
+    pub fn with_attachment(mut self, attachment: MediaAttachment) -> Self {
+        self.attachments.push(attachment);
+        self
+    }
+
+    /// Creates an outbound message as a response to an inbound message,
+    /// carrying over its trace ID.
     ///
     /// # Example
     /// ```
@@ -196,9 +368,10 @@ impl OutboundMessage {
     /// let response = OutboundMessage::reply_to(&inbound, "Hello back!");
     /// assert_eq!(response.channel, "telegram");
     /// assert_eq!(response.chat_id, "chat456");
+    /// assert_eq!(response.trace_id, inbound.trace_id);
     /// ```
     pub fn reply_to(msg: &InboundMessage, content: &str) -> Self {
-        Self::new(&msg.channel, &msg.chat_id, content)
+        Self::new(&msg.channel, &msg.chat_id, content).with_trace_id(&msg.trace_id)
     }
 }
 
@@ -341,6 +514,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_outbound_message_with_attachment() {
+        let msg = OutboundMessage::new("email", "me@example.com", "See attached").with_attachment(
+            MediaAttachment::new(MediaType::Document)
+                .with_filename("report.pdf")
+                .with_data(vec![1, 2, 3]),
+        );
+
+        assert_eq!(msg.attachments.len(), 1);
+        assert_eq!(msg.attachments[0].filename.as_deref(), Some("report.pdf"));
+
+        let json = serde_json::to_string(&msg).unwrap();
+        let roundtrip: OutboundMessage = serde_json::from_str(&json).unwrap();
+        assert_eq!(roundtrip.attachments[0].data, Some(vec![1, 2, 3]));
+
+        let plain = serde_json::to_string(&OutboundMessage::new("email", "x", "y")).unwrap();
+        assert!(!plain.contains("attachments"));
+    }
+
     #[test]
     fn test_outbound_reply_to_inbound() {
         let inbound = InboundMessage::new("telegram", "user123", "chat456", "Hello");
@@ -425,4 +617,36 @@ mod tests {
             Some(&"ops-thread".to_string())
         );
     }
+
+    #[test]
+    fn test_inbound_message_gets_unique_trace_id() {
+        let a = InboundMessage::new("telegram", "u", "c", "one");
+        let b = InboundMessage::new("telegram", "u", "c", "two");
+        assert_eq!(a.trace_id.len(), 32);
+        assert!(a.trace_id.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(a.trace_id, b.trace_id);
+    }
+
+    #[test]
+    fn test_trace_id_preserved_from_inbound_to_outbound() {
+        let inbound = InboundMessage::new("slack", "u", "c", "hi");
+        let json = serde_json::to_string(&inbound).unwrap();
+        let on_bus: InboundMessage = serde_json::from_str(&json).unwrap();
+        assert_eq!(on_bus.trace_id, inbound.trace_id);
+
+        let reply = OutboundMessage::reply_to(&on_bus, "hello");
+        assert_eq!(reply.trace_id, inbound.trace_id);
+
+        let json = serde_json::to_string(&reply).unwrap();
+        let delivered: OutboundMessage = serde_json::from_str(&json).unwrap();
+        assert_eq!(delivered.trace_id, inbound.trace_id);
+    }
+
+    #[test]
+    fn test_unrelated_outbound_has_no_trace_id() {
+        let msg = OutboundMessage::new("telegram", "c", "broadcast");
+        assert!(msg.trace_id.is_empty());
+        let json = serde_json::to_string(&msg).unwrap();
+        assert!(!json.contains("trace_id"));
+    }
 }
@@ -42,7 +42,11 @@
 
 pub mod message;
 
-pub use message::{InboundMessage, MediaAttachment, MediaType, OutboundMessage};
+pub use message::{
+    new_trace_id, ActionStyle, InboundMessage, MediaAttachment, MediaType, OutboundMessage,
+    OutputAction, StructuredField, StructuredOutput, ACTION_ID_METADATA_KEY,
+    STREAM_FINAL_METADATA_KEY, STREAM_ID_METADATA_KEY,
+};
 
 use crate::error::{Result, ZeptoError};
 use std::sync::Arc;
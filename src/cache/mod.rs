@@ -2,4 +2,4 @@
 
 pub mod response_cache;
 
-pub use response_cache::{CacheStats, ResponseCache};
+pub use response_cache::{parse_age, CacheStats, ResponseCache};
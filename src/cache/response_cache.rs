@@ -1,12 +1,18 @@
 //! LLM response cache with TTL expiry and LRU eviction.
 //!
-//! Persists to `~/.zeptoclaw/cache/responses.json`. Cache key is a SHA-256
+//! Persists to `~/.zeptoclaw/cache/responses.json`, or gzip-compressed to
+//! `responses.json.gz` when compression is enabled. Cache key is a SHA-256
 //! digest of `(model, system_prompt, user_prompt)`. Entries expire after a
-//! configurable TTL and are evicted LRU when the store reaches capacity.
+//! configurable TTL and are evicted LRU when the store reaches either its
+//! entry cap or its byte cap, whichever trips first.
 
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
+use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 use tracing::{debug, warn};
@@ -24,20 +30,44 @@ pub struct CacheEntry {
     pub accessed_at: u64,
     /// Number of cache hits for this entry.
     pub hit_count: u32,
+    /// Model that produced the response, used for per-model invalidation.
+    /// `None` for entries written before the model was recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+}
+
+impl CacheEntry {
+    /// Approximate bytes this entry occupies when stored under `key`.
+    pub fn size_bytes(&self, key: &str) -> usize {
+        key.len() + self.response.len() + self.model.as_ref().map_or(0, String::len)
+    }
 }
 
 /// Persistent store serialized to JSON.
 #[derive(Debug, Serialize, Deserialize, Default)]
 struct CacheStore {
     entries: HashMap<String, CacheEntry>,
+    /// Cumulative number of entries evicted to satisfy the capacity caps.
+    #[serde(default)]
+    evictions: u64,
+    /// Cumulative number of lookups that found no live entry.
+    #[serde(default)]
+    misses: u64,
 }
 
+/// Leading bytes of every gzip stream.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
 /// LLM response cache with TTL expiry, LRU eviction, and JSON persistence.
 pub struct ResponseCache {
     store: CacheStore,
     path: PathBuf,
     ttl_secs: u64,
     max_entries: usize,
+    /// Maximum total bytes across all entries (`0` = unlimited).
+    max_bytes: usize,
+    /// Gzip the JSON when saving. Loading detects the format from content.
+    compress: bool,
 }
 
 impl ResponseCache {
@@ -46,35 +76,59 @@ impl ResponseCache {
     /// Loads existing entries from `~/.zeptoclaw/cache/responses.json` on disk.
     /// `max_entries` is clamped to a minimum of 1 to prevent infinite loops.
     pub fn new(ttl_secs: u64, max_entries: usize) -> Self {
-        let path = dirs::home_dir()
+        Self::with_compression(ttl_secs, max_entries, false)
+    }
+
+    /// Create a cache at the default location, optionally gzip-compressed.
+    ///
+    /// With `compress` set the cache lives at `responses.json.gz`; an existing
+    /// plain `responses.json` is loaded once as a starting point so enabling
+    /// compression does not discard previously cached responses.
+    pub fn with_compression(ttl_secs: u64, max_entries: usize, compress: bool) -> Self {
+        let dir = dirs::home_dir()
             .unwrap_or_else(|| PathBuf::from("."))
             .join(".zeptoclaw")
-            .join("cache")
-            .join("responses.json");
-        let store = Self::load_from_disk(&path);
-        Self {
-            store,
-            path,
-            ttl_secs,
-            max_entries: max_entries.max(1),
+            .join("cache");
+        let plain = dir.join("responses.json");
+        if !compress {
+            return Self::with_path(plain, ttl_secs, max_entries);
+        }
+
+        let mut cache = Self::with_path(dir.join("responses.json.gz"), ttl_secs, max_entries);
+        if cache.store.entries.is_empty() && plain.exists() {
+            cache.store = Self::load_from_disk(&plain);
         }
+        cache
     }
 
     /// Create a cache backed by an explicit on-disk path.
     ///
     /// Bypasses the default `~/.zeptoclaw/cache/responses.json` so tests can
     /// use isolated per-process paths and avoid cross-test interference.
+    /// Paths ending in `.gz` are saved gzip-compressed.
     pub fn with_path<P: Into<PathBuf>>(path: P, ttl_secs: u64, max_entries: usize) -> Self {
         let path = path.into();
         let store = Self::load_from_disk(&path);
+        let compress = path.extension().is_some_and(|ext| ext == "gz");
         Self {
             store,
             path,
             ttl_secs,
             max_entries: max_entries.max(1),
+            max_bytes: 0,
+            compress,
         }
     }
 
+    /// Cap the total size of cached entries at `max_bytes` (`0` = unlimited).
+    ///
+    /// Enforced alongside `max_entries` on every insert; whichever cap trips
+    /// first drives LRU eviction.
+    pub fn with_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
     /// Build a deterministic cache key: SHA-256 of `(model, system_prompt, user_prompt)`.
     ///
     /// Uses length-prefixed encoding to prevent separator collision attacks
@@ -92,7 +146,8 @@ impl ResponseCache {
 
     /// Look up a cached response. Returns `None` if the key is absent or expired.
     ///
-    /// On hit, updates `accessed_at` and increments `hit_count` in memory.
+    /// On hit, updates `accessed_at` and increments `hit_count` in memory;
+    /// on miss, increments the miss counter.
     /// Does NOT persist to disk on hit — bookkeeping fields are flushed on
     /// the next `put()` or `clear()` call, avoiding O(n) disk writes per read.
     pub fn get(&mut self, key: &str) -> Option<String> {
@@ -107,6 +162,7 @@ impl ResponseCache {
             Some(true) => {
                 debug!(key = %&key[..8.min(key.len())], "Cache entry expired, removing");
                 self.store.entries.remove(key);
+                self.store.misses = self.store.misses.saturating_add(1);
                 // Deferred disk write — flushed on next put() or clear()
                 None
             }
@@ -116,32 +172,65 @@ impl ResponseCache {
                 entry.hit_count = entry.hit_count.saturating_add(1);
                 Some(entry.response.clone())
             }
-            None => None,
+            None => {
+                self.store.misses = self.store.misses.saturating_add(1);
+                None
+            }
         }
     }
 
     /// Store a response in the cache.
     ///
-    /// Evicts expired entries first, then LRU entries if at capacity.
+    /// Evicts expired entries first, then LRU entries until both the entry
+    /// cap and the byte cap have room. A response that alone exceeds the
+    /// byte cap is not cached, so one huge reply cannot flush the store.
     pub fn put(&mut self, key: String, response: String, token_count: u32) {
+        self.insert(key, None, response, token_count);
+    }
+
+    /// Store a response in the cache, recording the model that produced it
+    /// so it can later be purged with [`invalidate_model`](Self::invalidate_model).
+    pub fn put_for_model(&mut self, key: String, model: &str, response: String, token_count: u32) {
+        self.insert(key, Some(model.to_string()), response, token_count);
+    }
+
+    fn insert(&mut self, key: String, model: Option<String>, response: String, token_count: u32) {
         let now = Self::now_secs();
+        let entry = CacheEntry {
+            response,
+            token_count,
+            created_at: now,
+            accessed_at: now,
+            hit_count: 0,
+            model,
+        };
+        let entry_bytes = entry.size_bytes(&key);
+        if self.max_bytes > 0 && entry_bytes > self.max_bytes {
+            debug!(
+                bytes = entry_bytes,
+                max_bytes = self.max_bytes,
+                "Response exceeds cache byte cap, not caching"
+            );
+            return;
+        }
+
         // Evict expired entries first
         self.evict_expired(now);
-        // LRU eviction if at capacity (guard max_entries=0 to prevent infinite loop)
+        // Replacing an entry frees its bytes and slot before the cap checks.
+        self.store.entries.remove(&key);
+        // LRU eviction until both caps have room (guard max_entries=0 to
+        // prevent infinite loop)
         let effective_max = self.max_entries.max(1);
-        while self.store.entries.len() >= effective_max {
-            self.evict_lru();
+        let mut total_bytes = self.total_bytes();
+        while self.store.entries.len() >= effective_max
+            || (self.max_bytes > 0 && total_bytes + entry_bytes > self.max_bytes)
+        {
+            match self.evict_lru() {
+                Some(freed) => total_bytes = total_bytes.saturating_sub(freed),
+                None => break,
+            }
         }
-        self.store.entries.insert(
-            key,
-            CacheEntry {
-                response,
-                token_count,
-                created_at: now,
-                accessed_at: now,
-                hit_count: 0,
-            },
-        );
+        self.store.entries.insert(key, entry);
         self.save_to_disk();
     }
 
@@ -161,15 +250,53 @@ impl ResponseCache {
             .sum();
         CacheStats {
             total_entries: self.store.entries.len(),
+            total_bytes: self.total_bytes(),
             total_hits,
             total_tokens_saved,
+            evictions: self.store.evictions,
+            misses: self.store.misses,
         }
     }
 
-    /// Remove all entries from the cache.
-    pub fn clear(&mut self) {
+    /// Remove all entries from the cache and persist the empty store.
+    ///
+    /// Returns the number of entries removed.
+    pub fn clear(&mut self) -> usize {
+        let removed = self.store.entries.len();
         self.store.entries.clear();
         self.save_to_disk();
+        removed
+    }
+
+    /// Remove every entry for which `predicate(key, entry)` returns `true`.
+    ///
+    /// Persists the remaining entries when anything was removed and returns
+    /// the number of entries removed.
+    pub fn invalidate_matching<F>(&mut self, mut predicate: F) -> usize
+    where
+        F: FnMut(&str, &CacheEntry) -> bool,
+    {
+        let before = self.store.entries.len();
+        self.store.entries.retain(|k, e| !predicate(k, e));
+        let removed = before - self.store.entries.len();
+        if removed > 0 {
+            debug!(removed, "Invalidated response cache entries");
+            self.save_to_disk();
+        }
+        removed
+    }
+
+    /// Remove all entries produced by `model`.
+    ///
+    /// Entries cached before the model was recorded never match.
+    pub fn invalidate_model(&mut self, model: &str) -> usize {
+        self.invalidate_matching(|_, e| e.model.as_deref() == Some(model))
+    }
+
+    /// Remove all entries created more than `max_age_secs` seconds ago.
+    pub fn invalidate_older_than(&mut self, max_age_secs: u64) -> usize {
+        let now = Self::now_secs();
+        self.invalidate_matching(|_, e| now.saturating_sub(e.created_at) > max_age_secs)
     }
 
     /// Return the number of entries currently in the cache.
@@ -184,6 +311,14 @@ impl ResponseCache {
 
     // -- private helpers ---------------------------------------------------
 
+    fn total_bytes(&self) -> usize {
+        self.store
+            .entries
+            .iter()
+            .map(|(k, e)| e.size_bytes(k))
+            .sum()
+    }
+
     fn evict_expired(&mut self, now: u64) {
         let ttl = self.ttl_secs;
         self.store
@@ -191,17 +326,18 @@ impl ResponseCache {
             .retain(|_, e| now.saturating_sub(e.created_at) <= ttl);
     }
 
-    fn evict_lru(&mut self) {
-        if let Some(lru_key) = self
+    /// Evict the least recently used entry, returning the bytes it freed.
+    fn evict_lru(&mut self) -> Option<usize> {
+        let lru_key = self
             .store
             .entries
             .iter()
             .min_by_key(|(_, e)| e.accessed_at)
-            .map(|(k, _)| k.clone())
-        {
-            debug!(key = %&lru_key[..8.min(lru_key.len())], "Evicting LRU cache entry");
-            self.store.entries.remove(&lru_key);
-        }
+            .map(|(k, _)| k.clone())?;
+        debug!(key = %&lru_key[..8.min(lru_key.len())], "Evicting LRU cache entry");
+        let entry = self.store.entries.remove(&lru_key)?;
+        self.store.evictions = self.store.evictions.saturating_add(1);
+        Some(entry.size_bytes(&lru_key))
     }
 
     fn now_secs() -> u64 {
@@ -212,8 +348,8 @@ impl ResponseCache {
     }
 
     fn load_from_disk(path: &Path) -> CacheStore {
-        match std::fs::read_to_string(path) {
-            Ok(data) => match serde_json::from_str(&data) {
+        match std::fs::read(path) {
+            Ok(bytes) => match Self::decode(&bytes) {
                 Ok(store) => store,
                 Err(e) => {
                     warn!("Response cache file is corrupt, starting empty: {}", e);
@@ -228,14 +364,45 @@ impl ResponseCache {
         }
     }
 
+    /// Parse a persisted store, gunzipping first if the data is compressed.
+    ///
+    /// Detection is by magic bytes rather than file name so plain JSON and
+    /// gzip files load regardless of which path they were written to.
+    fn decode(bytes: &[u8]) -> std::io::Result<CacheStore> {
+        let json = if bytes.starts_with(&GZIP_MAGIC) {
+            let mut json = String::new();
+            GzDecoder::new(bytes).read_to_string(&mut json)?;
+            json
+        } else {
+            String::from_utf8(bytes.to_vec())
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?
+        };
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    fn encode(&self) -> std::io::Result<Vec<u8>> {
+        if self.compress {
+            // Compact JSON: whitespace only costs CPU once compressed.
+            let json = serde_json::to_vec(&self.store)?;
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(&json)?;
+            encoder.finish()
+        } else {
+            Ok(serde_json::to_vec_pretty(&self.store)?)
+        }
+    }
+
     fn save_to_disk(&self) {
         if let Some(parent) = self.path.parent() {
             let _ = std::fs::create_dir_all(parent);
         }
-        if let Ok(data) = serde_json::to_string_pretty(&self.store) {
-            if let Err(e) = std::fs::write(&self.path, data) {
-                warn!("Failed to save response cache: {}", e);
+        match self.encode() {
+            Ok(data) => {
+                if let Err(e) = std::fs::write(&self.path, data) {
+                    warn!("Failed to save response cache: {}", e);
+                }
             }
+            Err(e) => warn!("Failed to encode response cache: {}", e),
         }
     }
 }
@@ -245,10 +412,35 @@ impl ResponseCache {
 pub struct CacheStats {
     /// Number of entries currently in the cache.
     pub total_entries: usize,
+    /// Approximate bytes occupied by all entries.
+    pub total_bytes: usize,
     /// Cumulative number of cache hits across all entries.
     pub total_hits: u64,
     /// Estimated total tokens saved by cache hits.
     pub total_tokens_saved: u64,
+    /// Cumulative number of entries evicted by the entry or byte cap.
+    pub evictions: u64,
+    /// Cumulative number of lookups that found no live entry.
+    pub misses: u64,
+}
+
+impl CacheStats {
+    /// Fraction of lookups served from the cache, or `None` before any lookup.
+    ///
+    /// Hits are counted on the entries currently cached, so purged entries
+    /// no longer contribute theirs.
+    pub fn hit_rate(&self) -> Option<f64> {
+        let lookups = self.total_hits + self.misses;
+        (lookups > 0).then(|| self.total_hits as f64 / lookups as f64)
+    }
+}
+
+/// Parse an age like `90`, `90s`, `30m`, `12h` or `7d` into seconds.
+///
+/// Returns `None` when the input is not a number with an optional
+/// `s`/`m`/`h`/`d` suffix.
+pub fn parse_age(input: &str) -> Option<u64> {
+    crate::utils::duration::parse_duration_secs(input)
 }
 
 #[cfg(test)]
@@ -266,6 +458,8 @@ mod tests {
             path: PathBuf::from(format!("/tmp/zeptoclaw-test-cache-{tid:?}-{id}.json")),
             ttl_secs: 3600,
             max_entries: 5,
+            max_bytes: 0,
+            compress: false,
         }
     }
 
@@ -372,6 +566,175 @@ mod tests {
         assert!(cache.is_empty());
     }
 
+    #[test]
+    fn test_cache_clear_empties_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("responses.json");
+        let mut cache = ResponseCache::with_path(&path, 3600, 10);
+        cache.put("k1".into(), "r1".into(), 10);
+        cache.put("k2".into(), "r2".into(), 10);
+        assert_eq!(cache.clear(), 2);
+        assert!(cache.is_empty());
+
+        let reloaded = ResponseCache::with_path(&path, 3600, 10);
+        assert!(reloaded.is_empty(), "cleared state must be persisted");
+    }
+
+    #[test]
+    fn test_invalidate_matching_counts_removed() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("responses.json");
+        let mut cache = ResponseCache::with_path(&path, 3600, 10);
+        cache.put("keep-1".into(), "r".into(), 1);
+        cache.put("drop-1".into(), "r".into(), 1);
+        cache.put("drop-2".into(), "r".into(), 1);
+
+        assert_eq!(cache.invalidate_matching(|k, _| k.starts_with("drop")), 2);
+        assert_eq!(cache.invalidate_matching(|k, _| k.starts_with("drop")), 0);
+        assert_eq!(cache.len(), 1);
+
+        let reloaded = ResponseCache::with_path(&path, 3600, 10);
+        assert_eq!(reloaded.len(), 1);
+        assert!(reloaded.store.entries.contains_key("keep-1"));
+    }
+
+    #[test]
+    fn test_invalidate_model() {
+        let mut cache = test_cache();
+        cache.put_for_model("a".into(), "gpt-4", "r".into(), 1);
+        cache.put_for_model("b".into(), "gpt-4", "r".into(), 1);
+        cache.put_for_model("c".into(), "claude", "r".into(), 1);
+        cache.put("legacy".into(), "r".into(), 1);
+
+        assert_eq!(cache.invalidate_model("gpt-4"), 2);
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get("c").is_some());
+        assert!(cache.get("legacy").is_some());
+    }
+
+    #[test]
+    fn test_invalidate_older_than() {
+        let mut cache = test_cache();
+        cache.put("old".into(), "r".into(), 1);
+        cache.put("new".into(), "r".into(), 1);
+        cache.store.entries.get_mut("old").unwrap().created_at -= 600;
+
+        assert_eq!(cache.invalidate_older_than(300), 1);
+        assert!(cache.get("new").is_some());
+        assert!(cache.get("old").is_none());
+    }
+
+    #[test]
+    fn test_legacy_entry_without_model_loads() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("responses.json");
+        std::fs::write(
+            &path,
+            r#"{"entries":{"k":{"response":"r","token_count":1,"created_at":1,"accessed_at":1,"hit_count":0}}}"#,
+        )
+        .unwrap();
+        let cache = ResponseCache::with_path(&path, u64::MAX, 10);
+        assert_eq!(cache.len(), 1);
+        assert!(cache.store.entries["k"].model.is_none());
+    }
+
+    #[test]
+    fn test_compressed_cache_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("responses.json.gz");
+        let mut cache = ResponseCache::with_path(&path, 3600, 10);
+        cache.put_for_model("k1".into(), "gpt-4", "first".into(), 10);
+        cache.put("k2".into(), "second".into(), 20);
+
+        let bytes = std::fs::read(&path).unwrap();
+        assert!(bytes.starts_with(&GZIP_MAGIC), "file should be gzip");
+
+        let mut reloaded = ResponseCache::with_path(&path, 3600, 10);
+        assert_eq!(reloaded.len(), 2);
+        assert_eq!(reloaded.get("k1"), Some("first".into()));
+        assert_eq!(reloaded.get("k2"), Some("second".into()));
+        assert_eq!(reloaded.store.entries["k1"].model.as_deref(), Some("gpt-4"));
+    }
+
+    #[test]
+    fn test_legacy_plain_json_still_loads() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("responses.json");
+        let mut plain = ResponseCache::with_path(&path, 3600, 10);
+        plain.put("k".into(), "plain".into(), 5);
+        let text = std::fs::read_to_string(&path).unwrap();
+        assert!(
+            text.trim_start().starts_with('{'),
+            "file should be plain JSON"
+        );
+
+        // A plain JSON file loads even from a `.gz` path.
+        let gz_path = dir.path().join("responses.json.gz");
+        std::fs::copy(&path, &gz_path).unwrap();
+        let mut reloaded = ResponseCache::with_path(&gz_path, 3600, 10);
+        assert_eq!(reloaded.get("k"), Some("plain".into()));
+    }
+
+    #[test]
+    fn test_byte_cap_evicts_lru() {
+        // Each entry is 2-byte key + 100-byte response = 102 bytes.
+        let mut cache = test_cache().with_max_bytes(350);
+        for i in 0..3 {
+            cache.put(format!("k{i}"), "x".repeat(100), 1);
+            cache
+                .store
+                .entries
+                .get_mut(&format!("k{i}"))
+                .unwrap()
+                .accessed_at = i;
+        }
+        assert_eq!(cache.stats().evictions, 0);
+
+        // A 202-byte entry needs two LRU evictions to fit under 350 bytes.
+        cache.put("k3".into(), "y".repeat(200), 1);
+        assert!(!cache.store.entries.contains_key("k0"));
+        assert!(!cache.store.entries.contains_key("k1"));
+        assert!(cache.store.entries.contains_key("k2"));
+        assert!(cache.store.entries.contains_key("k3"));
+
+        let stats = cache.stats();
+        assert_eq!(stats.evictions, 2);
+        assert_eq!(stats.total_bytes, 102 + 202);
+        assert!(stats.total_bytes <= 350);
+    }
+
+    #[test]
+    fn test_oversized_entry_is_not_cached() {
+        let mut cache = test_cache().with_max_bytes(100);
+        cache.put("small".into(), "ok".into(), 1);
+        cache.put("huge".into(), "z".repeat(500), 1);
+        assert!(cache.store.entries.contains_key("small"));
+        assert!(!cache.store.entries.contains_key("huge"));
+        assert_eq!(cache.stats().evictions, 0);
+    }
+
+    #[test]
+    fn test_entry_cap_evictions_counted() {
+        let mut cache = test_cache(); // max 5 entries
+        for i in 0..7 {
+            cache.put(format!("k{i}"), "v".into(), 1);
+        }
+        let stats = cache.stats();
+        assert_eq!(stats.total_entries, 5);
+        assert_eq!(stats.evictions, 2);
+    }
+
+    #[test]
+    fn test_replacing_entry_does_not_evict() {
+        let mut cache = test_cache(); // max 5 entries
+        for i in 0..5 {
+            cache.put(format!("k{i}"), "v".into(), 1);
+        }
+        cache.put("k0".into(), "v2".into(), 1);
+        assert_eq!(cache.len(), 5);
+        assert_eq!(cache.stats().evictions, 0);
+    }
+
     #[test]
     fn test_cache_hit_increments_count() {
         let mut cache = test_cache();
@@ -410,6 +773,8 @@ mod tests {
             path: PathBuf::from("/tmp/zeptoclaw-test-clamp.json"),
             ttl_secs: 3600,
             max_entries: 0,
+            max_bytes: 0,
+            compress: false,
         };
         // Direct struct construction bypasses the clamp in new(), but
         // the eviction loop still needs to not infinite-loop. We test
@@ -427,5 +792,7 @@ mod tests {
         assert!(!cfg.enabled);
         assert_eq!(cfg.ttl_secs, 3600);
         assert_eq!(cfg.max_entries, 500);
+        assert_eq!(cfg.max_bytes, 16 * 1024 * 1024);
+        assert!(!cfg.compress);
     }
 }
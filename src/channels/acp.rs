@@ -1004,6 +1004,9 @@ mod tests {
             content: "hello".to_string(),
             reply_to: None,
             metadata: Default::default(),
+            attachments: Vec::new(),
+            trace_id: String::new(),
+            structured: None,
         };
         let result = channel.send(msg).await;
         assert!(result.is_ok());
@@ -1278,6 +1281,9 @@ mod tests {
             content: "hello".to_string(),
             reply_to: None,
             metadata: Default::default(),
+            attachments: Vec::new(),
+            trace_id: String::new(),
+            structured: None,
         };
         let result = channel.send(msg).await;
         assert!(result.is_ok());
@@ -1313,6 +1319,9 @@ mod tests {
             content: "proactive message".to_string(),
             reply_to: None,
             metadata: Default::default(),
+            attachments: Vec::new(),
+            trace_id: String::new(),
+            structured: None,
         };
         let result = channel.send(msg).await;
         assert!(result.is_ok());
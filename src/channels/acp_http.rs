@@ -1254,6 +1254,9 @@ mod tests {
             content: "hello".to_string(),
             reply_to: None,
             metadata: Default::default(),
+            attachments: Vec::new(),
+            trace_id: String::new(),
+            structured: None,
         };
         assert!(ch.send(msg).await.is_ok());
         // pending entry must be untouched
@@ -1273,6 +1276,9 @@ mod tests {
             content: "hello".to_string(),
             reply_to: None,
             metadata: Default::default(),
+            attachments: Vec::new(),
+            trace_id: String::new(),
+            structured: None,
         };
         assert!(ch.send(msg).await.is_ok());
         assert!(ch.state.lock().await.sessions.is_empty());
@@ -1302,6 +1308,9 @@ mod tests {
             content: "agent reply".to_string(),
             reply_to: None,
             metadata: Default::default(),
+            attachments: Vec::new(),
+            trace_id: String::new(),
+            structured: None,
         };
         assert!(ch.send(msg).await.is_ok());
         let (content, cancelled) = rx.await.expect("must receive payload");
@@ -1335,6 +1344,9 @@ mod tests {
             content: "reply after cancel".to_string(),
             reply_to: None,
             metadata: Default::default(),
+            attachments: Vec::new(),
+            trace_id: String::new(),
+            structured: None,
         };
         assert!(ch.send(msg).await.is_ok());
         let (_content, cancelled) = rx.await.expect("must receive payload");
@@ -1059,7 +1059,7 @@ impl DiscordChannel {
                                                             if let Some(mut inbound) =
                                                                 Self::parse_message_create(data, &allowlist, deny_by_default)
                                                             {
-                                                                // Download image and text document attachments
+                                                                // Download attachments; the agent saves them into the workspace
                                                                 if let Ok(msg_data) = serde_json::from_value::<MessageCreateData>(data.clone()) {
                                                                     let mut attachment_info = Vec::new();
                                                                     for att in &msg_data.attachments {
@@ -1080,62 +1080,55 @@ impl DiscordChannel {
 
                                                                         let content_type = att.content_type.as_ref().unwrap();
 
-                                                                        // Determine media type - only handle images and text documents
-                                                                        let media_type = if content_type.starts_with("image/") {
-                                                                            Some(MediaType::Image)
-                                                                        } else if content_type.starts_with("text/")
-                                                                            || content_type == "application/json" {
-                                                                            Some(MediaType::Document)
+                                                                        // Images are shown to the model; everything else is a
+                                                                        // document (text documents are also inlined).
+                                                                        let mt = if content_type.starts_with("image/") {
+                                                                            MediaType::Image
                                                                         } else {
-                                                                            None
+                                                                            MediaType::Document
                                                                         };
 
-                                                                        if let Some(mt) = media_type {
-                                                                            // Fetch attachment with timeout to avoid blocking gateway loop
-                                                                            let fetch_timeout = Duration::from_secs(ATTACHMENT_FETCH_TIMEOUT_SECS);
-                                                                            let fetch_result = tokio::time::timeout(
-                                                                                fetch_timeout,
-                                                                                async {
-                                                                                    let bytes = client
-                                                                                        .get(&att.url)
-                                                                                        .send()
-                                                                                        .await?
-                                                                                        .error_for_status()?
-                                                                                        .bytes()
-                                                                                        .await?;
-                                                                                    Ok::<_, reqwest::Error>(bytes)
-                                                                                }
-                                                                            ).await;
-
-                                                                            match fetch_result {
-                                                                                Ok(Ok(bytes)) => {
-                                                                                    // Successfully downloaded - add media attachment.
-                                                                                    // No metadata line needed: images display visually,
-                                                                                    // text docs get inlined with full content by inbound_to_message().
-                                                                                    let mut media = MediaAttachment::new(mt.clone())
-                                                                                        .with_data(bytes.to_vec())
-                                                                                        .with_mime_type(content_type);
-                                                                                    if let Some(ref name) = att.filename {
-                                                                                        media = media.with_filename(name);
-                                                                                    }
-                                                                                    inbound = inbound.with_media(media);
-                                                                                }
-                                                                                Ok(Err(e)) => {
-                                                                                    warn!("Failed to download Discord attachment {}: {}", filename, e);
-                                                                                    attachment_info.push(format!("[Attachment: {} (download failed)]", filename));
-                                                                                }
-                                                                                Err(_) => {
-                                                                                    warn!("Timeout downloading Discord attachment: {}", filename);
-                                                                                    attachment_info.push(format!("[Attachment: {} (download timeout)]", filename));
+                                                                        // Fetch attachment with timeout to avoid blocking gateway loop
+                                                                        let fetch_timeout = Duration::from_secs(ATTACHMENT_FETCH_TIMEOUT_SECS);
+                                                                        let fetch_result = tokio::time::timeout(
+                                                                            fetch_timeout,
+                                                                            async {
+                                                                                let bytes = client
+                                                                                    .get(&att.url)
+                                                                                    .send()
+                                                                                    .await?
+                                                                                    .error_for_status()?
+                                                                                    .bytes()
+                                                                                    .await?;
+                                                                                Ok::<_, reqwest::Error>(bytes)
+                                                                            }
+                                                                        ).await;
+
+                                                                        match fetch_result {
+                                                                            Ok(Ok(bytes)) => {
+                                                                                // Successfully downloaded - add media attachment.
+                                                                                // No metadata line needed: the agent notes where the
+                                                                                // file was saved when it stores the upload.
+                                                                                let mut media = MediaAttachment::new(mt.clone())
+                                                                                    .with_data(bytes.to_vec())
+                                                                                    .with_mime_type(content_type);
+                                                                                if let Some(ref name) = att.filename {
+                                                                                    media = media.with_filename(name);
                                                                                 }
+                                                                                inbound = inbound.with_media(media);
+                                                                            }
+                                                                            Ok(Err(e)) => {
+                                                                                warn!("Failed to download Discord attachment {}: {}", filename, e);
+                                                                                attachment_info.push(format!("[Attachment: {} (download failed)]", filename));
+                                                                            }
+                                                                            Err(_) => {
+                                                                                warn!("Timeout downloading Discord attachment: {}", filename);
+                                                                                attachment_info.push(format!("[Attachment: {} (download timeout)]", filename));
                                                                             }
-                                                                        } else {
-                                                                            // Unsupported MIME type
-                                                                            attachment_info.push(format!("[Attachment: {} (unsupported type: {})]", filename, content_type));
                                                                         }
                                                                     }
 
-                                                                    // Append attachment info to message content for non-image files
+                                                                    // Append notes about attachments that could not be downloaded
                                                                     if !attachment_info.is_empty() {
                                                                         let mut content = inbound.content.clone();
                                                                         if !content.is_empty() {
@@ -1314,6 +1307,10 @@ impl Channel for DiscordChannel {
     }
 
     async fn send(&self, msg: OutboundMessage) -> Result<()> {
+        self.send_with_ref(msg).await.map(|_| ())
+    }
+
+    async fn send_with_ref(&self, msg: OutboundMessage) -> Result<Option<String>> {
         if !self.running.load(Ordering::SeqCst) {
             return Err(ZeptoError::Channel(
                 "Discord channel not running".to_string(),
@@ -1336,7 +1333,7 @@ impl Channel for DiscordChannel {
             self.create_thread(token, channel_id, &thread_req, &msg.content)
                 .await?;
             info!("Discord: thread created successfully");
-            return Ok(());
+            return Ok(None);
         }
 
         let payload = Self::build_send_payload(&msg)?;
@@ -1364,9 +1361,49 @@ impl Channel for DiscordChannel {
         }
 
         info!("Discord: message sent successfully");
+        Ok(serde_json::from_str::<Value>(&body)
+            .ok()
+            .and_then(|v| v.get("id").and_then(Value::as_str).map(str::to_string)))
+    }
+
+    async fn edit_message(&self, chat_id: &str, message_ref: &str, new_text: &str) -> Result<()> {
+        let token = self.config.token.trim();
+        if token.is_empty() {
+            return Err(ZeptoError::Config("Discord bot token is empty".to_string()));
+        }
+
+        let payload =
+            Self::build_send_payload(&OutboundMessage::new("discord", chat_id, new_text))?;
+        let url = format!(
+            "{}/channels/{}/messages/{}",
+            DISCORD_API_BASE,
+            chat_id.trim(),
+            message_ref
+        );
+        let response = self
+            .http_client
+            .patch(&url)
+            .header("Authorization", format!("Bot {}", token))
+            .json(&payload)
+            .send()
+            .await
+            .map_err(|e| ZeptoError::Channel(format!("Failed to call Discord API: {}", e)))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(ZeptoError::Channel(format!(
+                "Discord edit returned HTTP {}: {}",
+                status, body
+            )));
+        }
         Ok(())
     }
 
+    fn supports_edit(&self) -> bool {
+        true
+    }
+
     fn is_running(&self) -> bool {
         self.running.load(Ordering::SeqCst)
     }
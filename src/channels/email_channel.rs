@@ -3,7 +3,8 @@
 //! Feature-gated behind `channel-email`:
 //! - Without the feature: channel compiles but `start()` / `send()` return a
 //!   clear error telling the user to rebuild.
-//! - With the feature: full IMAP IDLE loop with automatic reconnect and SMTP
+//! - With the feature: full IMAP IDLE loop with automatic reconnect
+//!   (exponential backoff with jitter, reported to the health registry) and SMTP
 //!   send via STARTTLS.
 //!
 //! # Example configuration
@@ -33,21 +34,373 @@ use futures::FutureExt;
 use tracing::{error, info, warn};
 
 use async_trait::async_trait;
-use std::collections::HashSet;
 use std::sync::{
     atomic::{AtomicBool, Ordering},
     Arc,
 };
-use tokio::sync::Mutex;
+use std::time::Duration;
 
 #[cfg(feature = "channel-email")]
-use crate::bus::{InboundMessage, MediaAttachment, MediaType};
-use crate::bus::{MessageBus, OutboundMessage};
+use crate::bus::{InboundMessage, MediaType};
+use crate::bus::{MediaAttachment, MessageBus, OutboundMessage};
 use crate::config::EmailConfig;
 use crate::error::{Result, ZeptoError};
+use crate::health::{HealthRegistry, HealthStatus};
+use crate::providers::retry::compute_delay;
 
+use super::format::{escape_html, format_markdown, MarkdownDialect};
+use super::seen::SeenIds;
 use super::{BaseChannelConfig, Channel};
 
+/// Base delay before the first IMAP reconnect attempt.
+const IMAP_RECONNECT_BASE_MS: u64 = 1_000;
+
+/// Upper bound on the IMAP reconnect delay.
+const IMAP_RECONNECT_MAX_MS: u64 = 60_000;
+
+/// Consecutive failed sessions after which the channel is reported `Down`
+/// (roughly three minutes of continuous failure with the default delays).
+const IMAP_DOWN_AFTER_FAILURES: u32 = 8;
+
+// ---------------------------------------------------------------------------
+// Reconnect backoff (always compiled)
+// ---------------------------------------------------------------------------
+
+/// Exponential backoff state for the IMAP IDLE reconnect loop.
+///
+/// The delay doubles on every consecutive failure (plus jitter, capped at
+/// [`IMAP_RECONNECT_MAX_MS`]) and resets once a session connects.
+#[derive(Debug, Default)]
+struct ImapReconnectState {
+    consecutive_failures: u32,
+}
+
+impl ImapReconnectState {
+    /// Record a successful connection and reset the backoff.
+    fn on_connected(&mut self) -> HealthStatus {
+        self.consecutive_failures = 0;
+        HealthStatus::Ok
+    }
+
+    /// Record a failed session, returning the delay before the next attempt
+    /// and the health status to report.
+    fn on_failure(&mut self, jitter_ms: u64) -> (Duration, HealthStatus) {
+        let delay = imap_backoff_delay(self.consecutive_failures, jitter_ms);
+        self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+        let status = if self.consecutive_failures >= IMAP_DOWN_AFTER_FAILURES {
+            HealthStatus::Down
+        } else {
+            HealthStatus::Degraded
+        };
+        (delay, status)
+    }
+}
+
+/// Delay before reconnect attempt `attempt` (0-indexed).
+fn imap_backoff_delay(attempt: u32, jitter_ms: u64) -> Duration {
+    Duration::from_millis(compute_delay(
+        attempt,
+        IMAP_RECONNECT_BASE_MS,
+        IMAP_RECONNECT_MAX_MS,
+        jitter_ms,
+    ))
+}
+
+/// Jitter in `[0, IMAP_RECONNECT_BASE_MS)`, derived from the clock so no
+/// `rand` dependency is needed.
+#[cfg_attr(not(feature = "channel-email"), allow(dead_code))]
+fn imap_backoff_jitter_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos() as u64 % IMAP_RECONNECT_BASE_MS)
+        .unwrap_or(0)
+}
+
+// ---------------------------------------------------------------------------
+// Inbound text normalization helpers (always compiled)
+// ---------------------------------------------------------------------------
+
+/// Elements whose content never reaches the text output.
+const HTML_SKIP_ELEMENTS: &[&str] = &["script", "style", "head", "title", "template"];
+
+/// Elements rendered on their own line(s).
+const HTML_BLOCK_ELEMENTS: &[&str] = &[
+    "p", "div", "section", "article", "header", "footer", "table", "tr", "ul", "ol", "h1", "h2",
+    "h3", "h4", "h5", "h6", "pre", "hr",
+];
+
+fn html_walk(element: scraper::ElementRef<'_>, out: &mut String) {
+    use scraper::node::Node;
+
+    for child in element.children() {
+        match child.value() {
+            Node::Text(text) => {
+                let mut last_space = out.ends_with(' ') || out.ends_with('\n');
+                for ch in text.chars() {
+                    if ch.is_whitespace() {
+                        if !last_space {
+                            out.push(' ');
+                            last_space = true;
+                        }
+                    } else {
+                        out.push(ch);
+                        last_space = false;
+                    }
+                }
+            }
+            Node::Element(el) => {
+                let tag = el.name.local.as_ref();
+                if HTML_SKIP_ELEMENTS.contains(&tag) {
+                    continue;
+                }
+                let Some(child_ref) = scraper::ElementRef::wrap(child) else {
+                    continue;
+                };
+                match tag {
+                    "br" => out.push('\n'),
+                    "li" => {
+                        ensure_newline(out);
+                        out.push_str("- ");
+                        html_walk(child_ref, out);
+                        ensure_newline(out);
+                    }
+                    "td" | "th" => {
+                        html_walk(child_ref, out);
+                        out.push(' ');
+                    }
+                    "blockquote" => {
+                        let mut inner = String::new();
+                        html_walk(child_ref, &mut inner);
+                        ensure_newline(out);
+                        for line in tidy_text(&inner).lines() {
+                            out.push_str("> ");
+                            out.push_str(line);
+                            out.push('\n');
+                        }
+                    }
+                    "a" => {
+                        let before = out.len();
+                        html_walk(child_ref, out);
+                        let text = out[before..].trim().to_string();
+                        if let Some(href) = el.attr("href") {
+                            if href.starts_with("http") && text != href {
+                                if text.is_empty() {
+                                    out.push_str(href);
+                                } else {
+                                    out.push_str(&format!(" ({href})"));
+                                }
+                            }
+                        }
+                    }
+                    _ if HTML_BLOCK_ELEMENTS.contains(&tag) => {
+                        ensure_newline(out);
+                        html_walk(child_ref, out);
+                        ensure_newline(out);
+                    }
+                    _ => html_walk(child_ref, out),
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn ensure_newline(out: &mut String) {
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+}
+
+/// Trim every line, collapse runs of blank lines to one, and trim the ends.
+fn tidy_text(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut blank_run = false;
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() {
+            blank_run = !out.is_empty();
+            continue;
+        }
+        if blank_run {
+            out.push('\n');
+            blank_run = false;
+        }
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str(line);
+    }
+    out
+}
+
+/// Whether `line` starts quoted reply history. `next` is the following line,
+/// used for attributions and headers that wrap onto two lines.
+fn is_reply_delimiter(line: &str, next: Option<&str>) -> bool {
+    let lower = line.to_lowercase();
+    let next_lower = next.map(str::to_lowercase).unwrap_or_default();
+
+    if lower.starts_with("on ") && (lower.ends_with("wrote:") || next_lower.ends_with("wrote:")) {
+        return true;
+    }
+    if lower.starts_with("---") && lower.contains("original message") {
+        return true;
+    }
+    if lower.starts_with("from:")
+        && (next_lower.starts_with("sent:") || next_lower.starts_with("date:"))
+    {
+        return true;
+    }
+    line.len() >= 10 && line.chars().all(|c| c == '_')
+}
+
+// ---------------------------------------------------------------------------
+// Outbound message helpers (always compiled)
+// ---------------------------------------------------------------------------
+
+/// Subject used when outbound content has no `Subject:` header line.
+const DEFAULT_EMAIL_SUBJECT: &str = "ZeptoClaw Message";
+
+/// A validated outbound attachment ready to be encoded as a MIME part.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmailAttachment {
+    pub filename: String,
+    pub mime_type: String,
+    pub data: Vec<u8>,
+}
+
+/// Outbound metadata key for extra `To` recipients (comma-separated).
+pub const EMAIL_TO_METADATA_KEY: &str = "email_to";
+/// Outbound metadata key for `Cc` recipients (comma-separated).
+pub const EMAIL_CC_METADATA_KEY: &str = "email_cc";
+/// Outbound metadata key for `Bcc` recipients (comma-separated).
+pub const EMAIL_BCC_METADATA_KEY: &str = "email_bcc";
+/// Inbound metadata key carrying the other thread participants, used to
+/// expand replies when `reply_all` is enabled.
+pub const EMAIL_REPLY_ALL_METADATA_KEY: &str = "email_reply_all";
+
+/// Resolved recipient lists for one outbound email.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EmailRecipients {
+    pub to: Vec<String>,
+    pub cc: Vec<String>,
+    pub bcc: Vec<String>,
+}
+
+/// Minimal syntactic check for a bare `local@domain` address.
+///
+/// Full RFC 5322 parsing happens in lettre when the message is built; this
+/// only rejects obvious garbage early with a clear error.
+pub fn is_valid_email_address(addr: &str) -> bool {
+    let Some((local, domain)) = addr.rsplit_once('@') else {
+        return false;
+    };
+    !local.is_empty()
+        && !domain.is_empty()
+        && !domain.starts_with('.')
+        && !domain.ends_with('.')
+        && domain.contains('.')
+        && !addr
+            .chars()
+            .any(|c| c.is_whitespace() || matches!(c, '<' | '>' | ',' | ';'))
+}
+
+/// Split a comma/semicolon separated address list into trimmed entries.
+pub fn parse_address_list(raw: &str) -> Vec<String> {
+    raw.split([',', ';'])
+        .map(str::trim)
+        .filter(|a| !a.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Whether `addr` matches an address list in the `allowed_senders` syntax:
+/// `"*"`, an exact address, or a domain with or without a leading `@`
+/// (all case-insensitive). An empty list matches nothing.
+pub fn address_matches(list: &[String], addr: &str) -> bool {
+    let addr_lower = addr.to_lowercase();
+    list.iter().any(|allowed| {
+        if allowed == "*" {
+            true
+        } else if allowed.starts_with('@') {
+            addr_lower.ends_with(&allowed.to_lowercase())
+        } else if allowed.contains('@') {
+            allowed.eq_ignore_ascii_case(addr)
+        } else {
+            addr_lower.ends_with(&format!("@{}", allowed.to_lowercase()))
+        }
+    })
+}
+
+/// Split outbound content into `(subject, body)`.
+///
+/// Content starting with `Subject: ...` uses that line as the subject and the
+/// remainder (after a blank line or newline) as the body.
+pub fn split_subject(content: &str) -> (String, String) {
+    let Some(rest) = content.strip_prefix("Subject: ") else {
+        return (DEFAULT_EMAIL_SUBJECT.to_string(), content.to_string());
+    };
+    if let Some(pos) = rest.find("\n\n") {
+        (rest[..pos].to_string(), rest[pos + 2..].to_string())
+    } else if let Some(pos) = rest.find('\n') {
+        (rest[..pos].to_string(), rest[pos + 1..].to_string())
+    } else {
+        (DEFAULT_EMAIL_SUBJECT.to_string(), content.to_string())
+    }
+}
+
+/// HTML alternative for a Markdown body, or `None` when the body has no
+/// markup and the plain-text part alone reads the same.
+pub fn html_body(body: &str) -> Option<String> {
+    let html = format_markdown(body, MarkdownDialect::Html);
+    (html != escape_html(body).replace('\n', "<br>\n")).then_some(html)
+}
+
+/// Validate outbound attachments and fill in defaults.
+///
+/// Every attachment must carry inline `data` (URL-only media cannot be
+/// attached), and the combined size must not exceed `max_total_bytes`.
+/// Missing filenames become `attachment-N` and missing MIME types become
+/// `application/octet-stream`.
+pub fn prepare_attachments(
+    attachments: &[MediaAttachment],
+    max_total_bytes: usize,
+) -> Result<Vec<EmailAttachment>> {
+    let mut total = 0usize;
+    let mut prepared = Vec::with_capacity(attachments.len());
+
+    for (i, att) in attachments.iter().enumerate() {
+        let filename = att
+            .filename
+            .as_deref()
+            .map(str::trim)
+            .filter(|f| !f.is_empty())
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("attachment-{}", i + 1));
+        let data = att.data.clone().ok_or_else(|| {
+            ZeptoError::Channel(format!(
+                "Email attachment '{filename}' has no inline data (URL-only media cannot be attached)"
+            ))
+        })?;
+
+        total = total.saturating_add(data.len());
+        if total > max_total_bytes {
+            return Err(ZeptoError::Channel(format!(
+                "Email attachments exceed size limit ({total} > {max_total_bytes} bytes) at '{filename}'"
+            )));
+        }
+
+        prepared.push(EmailAttachment {
+            filename,
+            mime_type: att
+                .mime_type
+                .clone()
+                .unwrap_or_else(|| "application/octet-stream".to_string()),
+            data,
+        });
+    }
+
+    Ok(prepared)
+}
+
 // ---------------------------------------------------------------------------
 // Channel struct (always compiled)
 // ---------------------------------------------------------------------------
@@ -62,9 +415,13 @@ pub struct EmailChannel {
     base_config: BaseChannelConfig,
     bus: Arc<MessageBus>,
     running: Arc<AtomicBool>,
-    /// Tracks Message-IDs seen in the current session to avoid reprocessing on
-    /// reconnect.
-    seen_ids: Arc<Mutex<HashSet<String>>>,
+    /// Message-IDs already processed, bounded and persisted so reconnects and
+    /// restarts do not reprocess them.
+    seen_ids: Arc<SeenIds>,
+    /// Backoff state shared with the IDLE reconnect loop.
+    reconnect: Arc<std::sync::Mutex<ImapReconnectState>>,
+    /// Health registry for reporting reconnect state, if the manager has one.
+    health_registry: Option<HealthRegistry>,
 }
 
 impl EmailChannel {
@@ -88,7 +445,9 @@ impl EmailChannel {
             base_config,
             bus,
             running: Arc::new(AtomicBool::new(false)),
-            seen_ids: Arc::new(Mutex::new(HashSet::new())),
+            seen_ids: Arc::new(SeenIds::default()),
+            reconnect: Arc::new(std::sync::Mutex::new(ImapReconnectState::default())),
+            health_registry: None,
         }
     }
 
@@ -108,53 +467,265 @@ impl EmailChannel {
             return !self.config.deny_by_default;
         }
 
-        if list.iter().any(|a| a == "*") {
-            return true;
-        }
-
-        let from_lower = from.to_lowercase();
-        list.iter().any(|allowed| {
-            if allowed.starts_with('@') {
-                from_lower.ends_with(&allowed.to_lowercase())
-            } else if allowed.contains('@') {
-                allowed.eq_ignore_ascii_case(from)
-            } else {
-                from_lower.ends_with(&format!("@{}", allowed.to_lowercase()))
-            }
-        })
+        address_matches(list, from)
     }
 
     /// Extract plain text body from a `mail_parser::Message`.
     ///
+    /// A real `text/plain` part is preferred; HTML-only messages are converted
+    /// with [`EmailChannel::html_to_text`].
+    ///
     /// Only available when the `channel-email` feature is enabled.
     #[cfg(feature = "channel-email")]
     pub fn extract_plain_text(msg: &mail_parser::Message) -> String {
-        msg.body_text(0).map(|s| s.to_string()).unwrap_or_else(|| {
-            msg.body_html(0)
-                .map(|h| Self::strip_html(h.as_ref()))
-                .unwrap_or_default()
-        })
+        match msg.text_part(0) {
+            Some(part) if !part.is_text_html() => {
+                msg.body_text(0).map(|s| s.to_string()).unwrap_or_default()
+            }
+            _ => msg
+                .body_html(0)
+                .map(|h| Self::html_to_text(h.as_ref()))
+                .or_else(|| msg.body_text(0).map(|s| s.to_string()))
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Normalize an inbound body: optionally drop quoted reply history and
+    /// tidy surrounding whitespace.
+    pub fn normalize_inbound_body(text: &str, strip_quotes: bool) -> String {
+        let text = text.replace("\r\n", "\n");
+        if strip_quotes {
+            Self::strip_quoted_reply(&text)
+        } else {
+            text.trim().to_string()
+        }
     }
 
-    /// Naive HTML tag stripper (no external dep required).
+    /// Convert an HTML email body to readable plain text.
+    ///
+    /// Block elements become line breaks, list items get a `- ` bullet, links
+    /// keep their URL, `<script>`/`<style>` are dropped, and `<blockquote>`
+    /// content is prefixed with `> ` so [`EmailChannel::strip_quoted_reply`]
+    /// can remove it.
+    pub fn html_to_text(html: &str) -> String {
+        let document = scraper::Html::parse_fragment(html);
+        let mut out = String::new();
+        html_walk(document.root_element(), &mut out);
+        tidy_text(&out)
+    }
+
+    /// Remove quoted reply history, keeping only the new content.
+    ///
+    /// Everything from the first reply delimiter on is dropped (`On ... wrote:`,
+    /// `-----Original Message-----`, an Outlook `From:`/`Sent:` header block or
+    /// `____` separator), as are `>`-quoted lines. If nothing would remain,
+    /// the original text is returned so a message is never emptied entirely.
+    pub fn strip_quoted_reply(text: &str) -> String {
+        let lines: Vec<&str> = text.lines().collect();
+        let mut kept: Vec<&str> = Vec::with_capacity(lines.len());
+
+        for (i, line) in lines.iter().enumerate() {
+            let trimmed = line.trim();
+            let next = lines.get(i + 1).map(|l| l.trim());
+            if is_reply_delimiter(trimmed, next) {
+                break;
+            }
+            if trimmed.starts_with('>') {
+                continue;
+            }
+            kept.push(line.trim_end());
+        }
+
+        let stripped = tidy_text(&kept.join("\n"));
+        if stripped.is_empty() {
+            text.trim().to_string()
+        } else {
+            stripped
+        }
+    }
+
+    /// Resolve the `To`/`Cc`/`Bcc` lists for an outbound message.
+    ///
+    /// `chat_id` is always the first `To` address; `email_to`, `email_cc` and
+    /// `email_bcc` metadata add to it and every entry must be a valid address.
+    /// With `reply_all` enabled, the thread participants from inbound metadata
+    /// are added to `Cc` when they pass `allowed_senders`; malformed entries
+    /// and our own address are skipped silently. Duplicates (case-insensitive)
+    /// are kept only in the first list they appear in.
+    pub fn resolve_recipients(&self, msg: &OutboundMessage) -> Result<EmailRecipients> {
+        let mut seen: HashSet<String> = HashSet::new();
+        let mut push = |list: &mut Vec<String>, addr: &str| {
+            if seen.insert(addr.to_lowercase()) {
+                list.push(addr.to_string());
+            }
+        };
+        let explicit = |key: &str| -> Result<Vec<String>> {
+            let addrs = msg
+                .metadata
+                .get(key)
+                .map(|raw| parse_address_list(raw))
+                .unwrap_or_default();
+            if let Some(bad) = addrs.iter().find(|a| !is_valid_email_address(a)) {
+                return Err(ZeptoError::Channel(format!(
+                    "Invalid email address '{bad}' in {key}"
+                )));
+            }
+            Ok(addrs)
+        };
+
+        let chat_id = msg.chat_id.trim();
+        if !is_valid_email_address(chat_id) {
+            return Err(ZeptoError::Channel(format!(
+                "Invalid email address '{chat_id}'"
+            )));
+        }
+
+        let mut recipients = EmailRecipients::default();
+        push(&mut recipients.to, chat_id);
+        for addr in explicit(EMAIL_TO_METADATA_KEY)? {
+            push(&mut recipients.to, &addr);
+        }
+        for addr in explicit(EMAIL_CC_METADATA_KEY)? {
+            push(&mut recipients.cc, &addr);
+        }
+        if self.config.reply_all {
+            let participants = msg
+                .metadata
+                .get(EMAIL_REPLY_ALL_METADATA_KEY)
+                .map(|raw| parse_address_list(raw))
+                .unwrap_or_default();
+            for addr in participants {
+                if is_valid_email_address(&addr)
+                    && !addr.eq_ignore_ascii_case(&self.config.username)
+                    && self.is_sender_allowed(&addr)
+                {
+                    push(&mut recipients.cc, &addr);
+                }
+            }
+        }
+        for addr in explicit(EMAIL_BCC_METADATA_KEY)? {
+            push(&mut recipients.bcc, &addr);
+        }
+        Ok(recipients)
+    }
+
+    /// Strip HTML tags from an email body.
+    #[deprecated(note = "Use html_to_text() instead")]
     pub fn strip_html(html: &str) -> String {
-        let mut out = String::with_capacity(html.len());
-        let mut in_tag = false;
-        for ch in html.chars() {
-            match ch {
-                '<' => in_tag = true,
-                '>' => in_tag = false,
-                _ if !in_tag => out.push(ch),
-                _ => {}
+        Self::html_to_text(html)
+    }
+
+    /// Reset the reconnect backoff and report the channel healthy.
+    #[cfg_attr(not(feature = "channel-email"), allow(dead_code))]
+    fn record_connect_success(&self) {
+        let status = self
+            .reconnect
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .on_connected();
+        if let Some(ref registry) = self.health_registry {
+            registry.update(&self.base_config.name, status, None);
+        }
+    }
+
+    /// Advance the reconnect backoff after a failed session and report the
+    /// channel `Degraded` (or `Down` after prolonged failure).
+    ///
+    /// Returns the delay to wait before reconnecting.
+    #[cfg_attr(not(feature = "channel-email"), allow(dead_code))]
+    fn record_connect_failure(&self, error: &str, jitter_ms: u64) -> Duration {
+        let (delay, status) = self
+            .reconnect
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .on_failure(jitter_ms);
+        if let Some(ref registry) = self.health_registry {
+            let name = &self.base_config.name;
+            if status == HealthStatus::Down {
+                registry.set_error(name, error);
+            } else {
+                registry.update(
+                    name,
+                    status,
+                    Some(format!("IMAP reconnecting in {delay:?}: {error}")),
+                );
             }
         }
-        out.split_whitespace().collect::<Vec<_>>().join(" ")
+        delay
     }
 
     // ------------------------------------------------------------------
     // Feature-gated internals
     // ------------------------------------------------------------------
 
+    /// Build the outbound message: a plain single part (or plain + HTML
+    /// `multipart/alternative` when the body has Markdown), wrapped in
+    /// `multipart/mixed` with one part per attachment.
+    #[cfg(feature = "channel-email")]
+    fn build_email(&self, msg: &OutboundMessage) -> Result<lettre::Message> {
+        use lettre::message::{header::ContentType, Attachment, MultiPart, SinglePart};
+
+        let (subject, body) = split_subject(&msg.content);
+        let attachments = prepare_attachments(&msg.attachments, self.config.max_attachment_bytes)?;
+        let recipients = self.resolve_recipients(msg)?;
+
+        let from_addr = if let Some(ref name) = self.config.display_name {
+            format!("{name} <{}>", self.config.username)
+        } else {
+            self.config.username.clone()
+        };
+
+        let parse_mailbox = |addr: &str| -> Result<lettre::message::Mailbox> {
+            addr.parse()
+                .map_err(|e| ZeptoError::Channel(format!("Invalid address '{addr}': {e}")))
+        };
+
+        let mut builder = lettre::Message::builder()
+            .from(
+                from_addr
+                    .parse()
+                    .map_err(|e| ZeptoError::Channel(format!("Invalid from address: {e}")))?,
+            )
+            .subject(subject);
+        for addr in &recipients.to {
+            builder = builder.to(parse_mailbox(addr)?);
+        }
+        for addr in &recipients.cc {
+            builder = builder.cc(parse_mailbox(addr)?);
+        }
+        for addr in &recipients.bcc {
+            builder = builder.bcc(parse_mailbox(addr)?);
+        }
+
+        let html = html_body(&body);
+        let email = if attachments.is_empty() {
+            match html {
+                Some(html) => builder.multipart(MultiPart::alternative_plain_html(body, html)),
+                None => builder.singlepart(SinglePart::plain(body)),
+            }
+        } else {
+            let mut multipart = match html {
+                Some(html) => {
+                    MultiPart::mixed().multipart(MultiPart::alternative_plain_html(body, html))
+                }
+                None => MultiPart::mixed().singlepart(SinglePart::plain(body)),
+            };
+            for att in attachments {
+                let content_type = ContentType::parse(&att.mime_type).map_err(|e| {
+                    ZeptoError::Channel(format!(
+                        "Invalid MIME type '{}' for attachment '{}': {e}",
+                        att.mime_type, att.filename
+                    ))
+                })?;
+                multipart = multipart
+                    .singlepart(Attachment::new(att.filename).body(att.data, content_type));
+            }
+            builder.multipart(multipart)
+        };
+
+        email.map_err(|e| ZeptoError::Channel(format!("Failed to build email: {e}")))
+    }
+
     /// Connect to the IMAP server with implicit TLS (port 993) and authenticate.
     #[cfg(feature = "channel-email")]
     async fn connect_imap(
@@ -208,7 +779,6 @@ impl EmailChannel {
     #[cfg(feature = "channel-email")]
     async fn run_idle_session(&self) -> std::result::Result<(), ZeptoError> {
         use async_imap::extensions::idle::IdleResponse;
-        use std::time::Duration;
 
         let mut session = self.connect_imap().await?;
 
@@ -217,6 +787,8 @@ impl EmailChannel {
             .await
             .map_err(|e| ZeptoError::Channel(format!("IMAP SELECT failed: {e}")))?;
 
+        self.record_connect_success();
+
         info!(
             "Email IMAP IDLE listening on {} / {}",
             self.config.imap_host, self.config.imap_folder
@@ -330,11 +902,7 @@ impl EmailChannel {
 
             // Dedup BEFORE allowlist check so blocked messages are not
             // re-warned on every reconnect.
-            let is_new = {
-                let mut seen = self.seen_ids.lock().await;
-                seen.insert(msg_id.clone())
-            };
-            if !is_new {
+            if !self.seen_ids.insert(&msg_id) {
                 continue;
             }
 
@@ -344,13 +912,34 @@ impl EmailChannel {
             }
 
             let subject = parsed.subject().unwrap_or("(no subject)").to_string();
-            let body_text = Self::extract_plain_text(&parsed);
+            let body_text = Self::normalize_inbound_body(
+                &Self::extract_plain_text(&parsed),
+                self.config.strip_quoted_replies,
+            );
             let content = format!("Subject: {subject}\n\n{body_text}");
 
             let mut inbound = InboundMessage::new("email", &from, &from, &content)
                 .with_metadata("message_id", &msg_id)
                 .with_metadata("subject", &subject);
 
+            // Remember the other thread participants so replies can CC them
+            // when `reply_all` is enabled (filtered again at send time).
+            let participants: Vec<String> = parsed
+                .to()
+                .into_iter()
+                .chain(parsed.cc())
+                .flat_map(|a| a.iter())
+                .filter_map(|a| a.address())
+                .filter(|a| {
+                    !a.eq_ignore_ascii_case(&from) && !a.eq_ignore_ascii_case(&self.config.username)
+                })
+                .map(str::to_string)
+                .collect();
+            if !participants.is_empty() {
+                inbound =
+                    inbound.with_metadata(EMAIL_REPLY_ALL_METADATA_KEY, &participants.join(","));
+            }
+
             // Extract image attachments
             use mail_parser::MimeHeaders;
             for part in parsed.attachments() {
@@ -382,6 +971,7 @@ impl EmailChannel {
         if !raw_messages.is_empty() {
             let _ = session.uid_store(&uid_set, "+FLAGS (\\Seen)").await;
         }
+        self.seen_ids.maybe_flush();
 
         Ok(())
     }
@@ -415,9 +1005,15 @@ impl Channel for EmailChannel {
                 return Ok(());
             }
 
+            if let Err(e) = self.seen_ids.persist_to(SeenIds::default_path("email")) {
+                warn!("Failed to load seen email IDs: {e}");
+            }
+
             let config = self.config.clone();
             let bus = Arc::clone(&self.bus);
             let seen_ids = Arc::clone(&self.seen_ids);
+            let reconnect = Arc::clone(&self.reconnect);
+            let health_registry = self.health_registry.clone();
             let this_running = Arc::clone(&self.running);
 
             tokio::spawn(async move {
@@ -429,20 +1025,22 @@ impl Channel for EmailChannel {
                         bus,
                         running: Arc::clone(&loop_running),
                         seen_ids,
+                        reconnect,
+                        health_registry,
                     };
 
-                    let mut backoff = std::time::Duration::from_secs(1);
-                    let max_backoff = std::time::Duration::from_secs(60);
-
                     while loop_running.load(Ordering::SeqCst) {
                         match channel.run_idle_session().await {
                             Ok(()) => break,
                             Err(e) => {
+                                let backoff = channel.record_connect_failure(
+                                    &e.to_string(),
+                                    imap_backoff_jitter_ms(),
+                                );
                                 error!(
                                     "Email IMAP session error: {e}. Reconnecting in {backoff:?}…"
                                 );
                                 tokio::time::sleep(backoff).await;
-                                backoff = std::cmp::min(backoff * 2, max_backoff);
                             }
                         }
                     }
@@ -476,6 +1074,9 @@ impl Channel for EmailChannel {
         // The IDLE loop re-checks `running` on each timeout (≤ idle_timeout_secs),
         // so shutdown latency is bounded without needing extra plumbing.
         self.running.store(false, Ordering::SeqCst);
+        if let Err(e) = self.seen_ids.flush() {
+            tracing::warn!("Failed to persist seen email IDs: {e}");
+        }
         Ok(())
     }
 
@@ -494,47 +1095,12 @@ impl Channel for EmailChannel {
         {
             // Fix 1: use async SMTP transport so we don't block the Tokio thread.
             use lettre::{
-                message::SinglePart, transport::smtp::authentication::Credentials,
-                AsyncSmtpTransport, AsyncTransport, Message as LettreMessage, Tokio1Executor,
-            };
-
-            let (subject, body) = if msg.content.starts_with("Subject: ") {
-                if let Some(pos) = msg.content.find("\n\n") {
-                    (
-                        msg.content[9..pos].to_string(),
-                        msg.content[pos + 2..].to_string(),
-                    )
-                } else if let Some(pos) = msg.content.find('\n') {
-                    (
-                        msg.content[9..pos].to_string(),
-                        msg.content[pos + 1..].to_string(),
-                    )
-                } else {
-                    ("ZeptoClaw Message".to_string(), msg.content.clone())
-                }
-            } else {
-                ("ZeptoClaw Message".to_string(), msg.content.clone())
-            };
-
-            let from_addr = if let Some(ref name) = self.config.display_name {
-                format!("{name} <{}>", self.config.username)
-            } else {
-                self.config.username.clone()
+                transport::smtp::authentication::Credentials, AsyncSmtpTransport, AsyncTransport,
+                Tokio1Executor,
             };
 
-            let email = LettreMessage::builder()
-                .from(
-                    from_addr
-                        .parse()
-                        .map_err(|e| ZeptoError::Channel(format!("Invalid from address: {e}")))?,
-                )
-                .to(msg
-                    .chat_id
-                    .parse()
-                    .map_err(|e| ZeptoError::Channel(format!("Invalid to address: {e}")))?)
-                .subject(subject)
-                .singlepart(SinglePart::plain(body))
-                .map_err(|e| ZeptoError::Channel(format!("Failed to build email: {e}")))?;
+            let email = self.build_email(&msg)?;
+            let recipient_count = email.envelope().to().len();
 
             let creds =
                 Credentials::new(self.config.username.clone(), self.config.password.clone());
@@ -551,11 +1117,20 @@ impl Channel for EmailChannel {
                 .await
                 .map_err(|e| ZeptoError::Channel(format!("SMTP send failed: {e}")))?;
 
-            info!("Email sent to {}", msg.chat_id);
+            info!(
+                "Email sent to {} (+{} other recipient(s), {} attachment(s))",
+                msg.chat_id,
+                recipient_count.saturating_sub(1),
+                msg.attachments.len()
+            );
             Ok(())
         }
     }
 
+    fn markdown_dialect(&self) -> MarkdownDialect {
+        MarkdownDialect::Html
+    }
+
     fn is_running(&self) -> bool {
         self.running.load(Ordering::SeqCst)
     }
@@ -563,6 +1138,10 @@ impl Channel for EmailChannel {
     fn is_allowed(&self, user_id: &str) -> bool {
         self.base_config.is_allowed(user_id)
     }
+
+    fn set_health_registry(&mut self, registry: HealthRegistry) {
+        self.health_registry = Some(registry);
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -586,6 +1165,10 @@ mod tests {
             allowed_senders: vec![],
             deny_by_default: false,
             idle_timeout_secs: 1740,
+            max_attachment_bytes: 1024,
+            strip_quoted_replies: true,
+            reply_all: false,
+            allowed_recipients: vec![],
             enabled: false,
         }
     }
@@ -656,16 +1239,14 @@ mod tests {
     #[tokio::test]
     async fn test_dedup_new_id_accepted() {
         let ch = make_channel(make_config());
-        let mut seen = ch.seen_ids.lock().await;
-        assert!(seen.insert("msg-001".to_string()));
+        assert!(ch.seen_ids.insert("msg-001"));
     }
 
     #[tokio::test]
     async fn test_dedup_duplicate_rejected() {
         let ch = make_channel(make_config());
-        let mut seen = ch.seen_ids.lock().await;
-        seen.insert("msg-dup".to_string());
-        assert!(!seen.insert("msg-dup".to_string()));
+        ch.seen_ids.insert("msg-dup");
+        assert!(!ch.seen_ids.insert("msg-dup"));
     }
 
     // ---- name test ----
@@ -752,22 +1333,115 @@ mod tests {
         assert_eq!(cfg.imap_folder, "INBOX");
         assert!(!cfg.deny_by_default);
         assert!(cfg.allowed_senders.is_empty());
+        assert_eq!(cfg.max_attachment_bytes, 10 * 1024 * 1024);
+        assert!(cfg.strip_quoted_replies);
     }
 
     // ---- strip_html helper ----
 
     #[test]
+    #[allow(deprecated)]
     fn test_strip_html_basic() {
         assert_eq!(EmailChannel::strip_html("<p>Hello</p>"), "Hello");
         assert_eq!(EmailChannel::strip_html("<b>World</b>"), "World");
     }
 
     #[test]
+    #[allow(deprecated)]
     fn test_strip_html_no_tags() {
         assert_eq!(EmailChannel::strip_html("plain text"), "plain text");
         assert_eq!(EmailChannel::strip_html(""), "");
     }
 
+    // ---- inbound normalization ----
+
+    #[test]
+    fn test_html_to_text_blocks_and_entities() {
+        let html = "<html><head><style>p{color:red}</style></head><body>\
+            <p>Hi&nbsp;team,</p><p>Numbers: 1 &lt; 2 &amp; 3</p>\
+            <ul><li>First</li><li>Second</li></ul>Line<br>break\
+            <script>alert(1)</script></body></html>";
+        let text = EmailChannel::html_to_text(html);
+        assert_eq!(
+            text,
+            "Hi team,\nNumbers: 1 < 2 & 3\n- First\n- Second\nLine\nbreak"
+        );
+    }
+
+    #[test]
+    fn test_html_to_text_keeps_link_urls() {
+        let text = EmailChannel::html_to_text(
+            r#"<p>See <a href="https://example.com/x">the doc</a>.</p>"#,
+        );
+        assert_eq!(text, "See the doc (https://example.com/x).");
+    }
+
+    #[test]
+    fn test_html_to_text_marks_blockquotes() {
+        let html = "<div>Sounds good!</div><div class=\"gmail_quote\">\
+            <div>On Mon, Jan 1, 2024 at 9:00 AM Bob &lt;bob@x.com&gt; wrote:</div>\
+            <blockquote><p>Can we ship Friday?</p></blockquote></div>";
+        let text = EmailChannel::html_to_text(html);
+        assert!(text.contains("> Can we ship Friday?"));
+        assert_eq!(EmailChannel::strip_quoted_reply(&text), "Sounds good!");
+    }
+
+    #[test]
+    fn test_strip_quoted_reply_on_wrote() {
+        let body = "Yes, Friday works.\n\nOn Tue, Mar 5, 2024 at 10:12 AM Alice <alice@example.com> wrote:\n> Does Friday work?\n> Thanks";
+        assert_eq!(EmailChannel::strip_quoted_reply(body), "Yes, Friday works.");
+    }
+
+    #[test]
+    fn test_strip_quoted_reply_wrapped_attribution() {
+        let body = "Done.\nOn Tue, Mar 5, 2024 at 10:12 AM Alice Example\n<alice@example.com> wrote:\nold stuff";
+        assert_eq!(EmailChannel::strip_quoted_reply(body), "Done.");
+    }
+
+    #[test]
+    fn test_strip_quoted_reply_outlook_headers() {
+        let body =
+            "Approved.\n\n-----Original Message-----\nFrom: Bob\nSent: Monday\nPlease approve";
+        assert_eq!(EmailChannel::strip_quoted_reply(body), "Approved.");
+
+        let body = "Approved.\n________________________________\nFrom: Bob\nSent: Monday";
+        assert_eq!(EmailChannel::strip_quoted_reply(body), "Approved.");
+
+        let body = "Approved.\nFrom: Bob <bob@x.com>\nDate: Monday\nSubject: Re: budget";
+        assert_eq!(EmailChannel::strip_quoted_reply(body), "Approved.");
+    }
+
+    #[test]
+    fn test_strip_quoted_reply_drops_inline_quote_blocks() {
+        let body = "> what about tests?\nAdded them.\n> and docs?\nAlso done.";
+        assert_eq!(
+            EmailChannel::strip_quoted_reply(body),
+            "Added them.\nAlso done."
+        );
+    }
+
+    #[test]
+    fn test_strip_quoted_reply_keeps_plain_text() {
+        let body = "On second thought, let's wait.\nThanks";
+        assert_eq!(EmailChannel::strip_quoted_reply(body), body);
+    }
+
+    #[test]
+    fn test_strip_quoted_reply_never_empties_message() {
+        let body = "> only quoted\n> content";
+        assert_eq!(EmailChannel::strip_quoted_reply(body), body);
+    }
+
+    #[test]
+    fn test_normalize_inbound_body_toggle() {
+        let body = "New text\r\n\r\nOn Mon, Bob wrote:\r\n> old";
+        assert_eq!(EmailChannel::normalize_inbound_body(body, true), "New text");
+        assert_eq!(
+            EmailChannel::normalize_inbound_body(body, false),
+            "New text\n\nOn Mon, Bob wrote:\n> old"
+        );
+    }
+
     // ---- is_running default ----
 
     #[test]
@@ -796,4 +1470,278 @@ mod tests {
         let cfg: EmailConfig = serde_json::from_value(json).unwrap();
         assert!(cfg.enabled);
     }
+
+    // ---- reconnect backoff tests ----
+
+    #[test]
+    fn test_backoff_schedule_doubles_and_caps() {
+        let delays: Vec<u64> = (0..9).map(|n| imap_backoff_delay(n, 0).as_secs()).collect();
+        assert_eq!(delays, vec![1, 2, 4, 8, 16, 32, 60, 60, 60]);
+    }
+
+    #[test]
+    fn test_backoff_jitter_added_but_capped() {
+        assert_eq!(imap_backoff_delay(0, 250), Duration::from_millis(1_250));
+        assert_eq!(imap_backoff_delay(10, 999), Duration::from_secs(60));
+        assert!(imap_backoff_jitter_ms() < IMAP_RECONNECT_BASE_MS);
+    }
+
+    #[test]
+    fn test_reconnect_state_resets_on_success() {
+        let mut state = ImapReconnectState::default();
+        state.on_failure(0);
+        state.on_failure(0);
+        let (delay, _) = state.on_failure(0);
+        assert_eq!(delay, Duration::from_secs(4));
+
+        assert_eq!(state.on_connected(), HealthStatus::Ok);
+        let (delay, status) = state.on_failure(0);
+        assert_eq!(delay, Duration::from_secs(1));
+        assert_eq!(status, HealthStatus::Degraded);
+    }
+
+    #[test]
+    fn test_health_transitions_from_connect_outcomes() {
+        let registry = HealthRegistry::new();
+        registry.register(crate::health::HealthCheck {
+            name: "email".into(),
+            ..Default::default()
+        });
+        let mut ch = make_channel(make_config());
+        ch.set_health_registry(registry.clone());
+
+        let status = |r: &HealthRegistry| {
+            r.all_checks()
+                .into_iter()
+                .find(|c| c.name == "email")
+                .unwrap()
+                .status
+        };
+
+        ch.record_connect_failure("connection refused", 0);
+        assert_eq!(status(&registry), HealthStatus::Degraded);
+
+        for _ in 1..IMAP_DOWN_AFTER_FAILURES - 1 {
+            ch.record_connect_failure("connection refused", 0);
+        }
+        assert_eq!(status(&registry), HealthStatus::Degraded);
+
+        ch.record_connect_failure("connection refused", 0);
+        assert_eq!(status(&registry), HealthStatus::Down);
+        assert!(!registry.is_ready());
+
+        ch.record_connect_success();
+        assert_eq!(status(&registry), HealthStatus::Ok);
+        assert_eq!(
+            ch.record_connect_failure("timeout", 0),
+            Duration::from_secs(1)
+        );
+    }
+
+    #[test]
+    fn test_connect_outcomes_without_registry() {
+        let ch = make_channel(make_config());
+        assert_eq!(ch.record_connect_failure("x", 0), Duration::from_secs(1));
+        assert_eq!(ch.record_connect_failure("x", 0), Duration::from_secs(2));
+        ch.record_connect_success();
+        assert_eq!(ch.record_connect_failure("x", 0), Duration::from_secs(1));
+    }
+
+    // ---- outbound attachment tests ----
+
+    fn doc(filename: &str, mime: &str, data: &[u8]) -> MediaAttachment {
+        MediaAttachment::new(crate::bus::MediaType::Document)
+            .with_filename(filename)
+            .with_mime_type(mime)
+            .with_data(data.to_vec())
+    }
+
+    #[test]
+    fn test_html_body_only_for_markup() {
+        assert_eq!(html_body("hello\nthere"), None);
+        assert_eq!(
+            html_body("Use **care** & `x < y`").as_deref(),
+            Some("Use <b>care</b> &amp; <code>x &lt; y</code>")
+        );
+    }
+
+    #[test]
+    fn test_split_subject() {
+        assert_eq!(
+            split_subject("Subject: Weekly report\n\nAll green."),
+            ("Weekly report".into(), "All green.".into())
+        );
+        assert_eq!(
+            split_subject("Subject: Hi\nBody"),
+            ("Hi".into(), "Body".into())
+        );
+        assert_eq!(
+            split_subject("Just text"),
+            (DEFAULT_EMAIL_SUBJECT.into(), "Just text".into())
+        );
+    }
+
+    #[test]
+    fn test_prepare_attachments_fills_defaults() {
+        let bare = MediaAttachment::new(crate::bus::MediaType::Document).with_data(vec![0; 4]);
+        let prepared =
+            prepare_attachments(&[doc("report.pdf", "application/pdf", b"%PDF"), bare], 1024)
+                .unwrap();
+        assert_eq!(prepared[0].filename, "report.pdf");
+        assert_eq!(prepared[0].mime_type, "application/pdf");
+        assert_eq!(prepared[1].filename, "attachment-2");
+        assert_eq!(prepared[1].mime_type, "application/octet-stream");
+    }
+
+    #[test]
+    fn test_prepare_attachments_rejects_oversize() {
+        let big = doc("big.bin", "application/octet-stream", &[0u8; 600]);
+        assert!(prepare_attachments(std::slice::from_ref(&big), 1024).is_ok());
+
+        let err = prepare_attachments(&[big.clone(), big], 1024).unwrap_err();
+        assert!(err.to_string().contains("exceed size limit"));
+    }
+
+    #[test]
+    fn test_prepare_attachments_requires_inline_data() {
+        let remote = MediaAttachment::new(crate::bus::MediaType::Image)
+            .with_url("https://example.com/cat.png")
+            .with_filename("cat.png");
+        let err = prepare_attachments(&[remote], 1024).unwrap_err();
+        assert!(err.to_string().contains("no inline data"));
+    }
+
+    #[cfg(feature = "channel-email")]
+    #[test]
+    fn test_build_email_multipart_with_attachment() {
+        let ch = make_channel(make_config());
+        let msg = OutboundMessage::new(
+            "email",
+            "user@example.com",
+            "Subject: Report\n\nSee attached.",
+        )
+        .with_attachment(doc("report.pdf", "application/pdf", b"%PDF-1.7"));
+
+        let raw = String::from_utf8(ch.build_email(&msg).unwrap().formatted()).unwrap();
+        assert!(raw.contains("Subject: Report"));
+        assert!(raw.contains("multipart/mixed"));
+        assert!(raw.contains("See attached."));
+        assert!(raw.contains("Content-Type: application/pdf"));
+        assert!(raw.contains("filename=\"report.pdf\""));
+    }
+
+    #[cfg(feature = "channel-email")]
+    #[test]
+    fn test_build_email_plain_without_attachments() {
+        let ch = make_channel(make_config());
+        let msg = OutboundMessage::new("email", "user@example.com", "hello");
+        let raw = String::from_utf8(ch.build_email(&msg).unwrap().formatted()).unwrap();
+        assert!(!raw.contains("multipart"));
+        assert!(raw.contains("Subject: ZeptoClaw Message"));
+    }
+
+    #[cfg(feature = "channel-email")]
+    #[test]
+    fn test_build_email_adds_html_alternative_for_markdown() {
+        let ch = make_channel(make_config());
+        let msg = OutboundMessage::new("email", "user@example.com", "See **this**.");
+        let raw = String::from_utf8(ch.build_email(&msg).unwrap().formatted()).unwrap();
+        assert!(raw.contains("multipart/alternative"));
+        assert!(raw.contains("text/html"));
+        assert!(raw.contains("<b>this</b>"));
+    }
+
+    #[cfg(feature = "channel-email")]
+    #[test]
+    fn test_build_email_rejects_oversize_attachments() {
+        let ch = make_channel(make_config());
+        let msg = OutboundMessage::new("email", "user@example.com", "big").with_attachment(doc(
+            "big.bin",
+            "application/octet-stream",
+            &[0u8; 2048],
+        ));
+        assert!(ch.build_email(&msg).is_err());
+    }
+
+    // ---- recipient resolution tests ----
+
+    #[test]
+    fn test_is_valid_email_address() {
+        assert!(is_valid_email_address("user@example.com"));
+        assert!(is_valid_email_address("first.last+tag@sub.example.org"));
+        assert!(!is_valid_email_address("no-at-sign"));
+        assert!(!is_valid_email_address("@example.com"));
+        assert!(!is_valid_email_address("user@localhost"));
+        assert!(!is_valid_email_address("user @example.com"));
+        assert!(!is_valid_email_address("Name <user@example.com>"));
+    }
+
+    #[test]
+    fn test_resolve_recipients_multi_to_cc_bcc() {
+        let ch = make_channel(make_config());
+        let msg = OutboundMessage::new("email", "a@example.com", "hi")
+            .with_metadata(EMAIL_TO_METADATA_KEY, "b@example.com, c@example.com")
+            .with_metadata(EMAIL_CC_METADATA_KEY, "d@example.com;A@example.com")
+            .with_metadata(EMAIL_BCC_METADATA_KEY, "e@example.com");
+
+        let r = ch.resolve_recipients(&msg).unwrap();
+        assert_eq!(
+            r.to,
+            vec!["a@example.com", "b@example.com", "c@example.com"]
+        );
+        // Duplicate of a `To` address (case-insensitive) is dropped from Cc.
+        assert_eq!(r.cc, vec!["d@example.com"]);
+        assert_eq!(r.bcc, vec!["e@example.com"]);
+    }
+
+    #[test]
+    fn test_resolve_recipients_rejects_invalid_address() {
+        let ch = make_channel(make_config());
+        let msg = OutboundMessage::new("email", "a@example.com", "hi")
+            .with_metadata(EMAIL_CC_METADATA_KEY, "not-an-address");
+        assert!(ch.resolve_recipients(&msg).is_err());
+    }
+
+    #[test]
+    fn test_resolve_recipients_reply_all_disabled_ignores_participants() {
+        let ch = make_channel(make_config());
+        let msg = OutboundMessage::new("email", "a@example.com", "hi")
+            .with_metadata(EMAIL_REPLY_ALL_METADATA_KEY, "b@example.com");
+        let r = ch.resolve_recipients(&msg).unwrap();
+        assert!(r.cc.is_empty());
+    }
+
+    #[test]
+    fn test_resolve_recipients_reply_all_filters_by_allowlist() {
+        let mut cfg = make_config();
+        cfg.reply_all = true;
+        cfg.allowed_senders = vec!["@example.com".into()];
+        let ch = make_channel(cfg);
+        let msg = OutboundMessage::new("email", "a@example.com", "hi").with_metadata(
+            EMAIL_REPLY_ALL_METADATA_KEY,
+            "b@example.com,outsider@evil.test,bot@example.com,garbage",
+        );
+
+        let r = ch.resolve_recipients(&msg).unwrap();
+        assert_eq!(r.to, vec!["a@example.com"]);
+        // Disallowed domain, our own address and malformed entries are skipped.
+        assert_eq!(r.cc, vec!["b@example.com"]);
+    }
+
+    #[cfg(feature = "channel-email")]
+    #[test]
+    fn test_build_email_includes_cc_and_hides_bcc() {
+        let ch = make_channel(make_config());
+        let msg = OutboundMessage::new("email", "a@example.com", "hi")
+            .with_metadata(EMAIL_TO_METADATA_KEY, "b@example.com")
+            .with_metadata(EMAIL_CC_METADATA_KEY, "c@example.com")
+            .with_metadata(EMAIL_BCC_METADATA_KEY, "d@example.com");
+
+        let email = ch.build_email(&msg).unwrap();
+        assert_eq!(email.envelope().to().len(), 4);
+        let raw = String::from_utf8(email.formatted()).unwrap();
+        assert!(raw.contains("b@example.com"));
+        assert!(raw.contains("Cc: c@example.com"));
+        assert!(!raw.contains("d@example.com"));
+    }
 }
@@ -0,0 +1,464 @@
+//! Outbound Markdown formatting per channel.
+//!
+//! The agent replies in canonical Markdown, but every platform renders a
+//! different dialect: Telegram wants HTML or MarkdownV2, Slack uses
+//! `mrkdwn`, email needs HTML and Discord understands Markdown as-is. Each
+//! channel declares its dialect via [`Channel::markdown_dialect`] and passes
+//! outgoing text through [`format_markdown`], which also escapes the
+//! characters each dialect reserves so sends are not rejected as malformed.
+//!
+//! [`Channel::markdown_dialect`]: super::Channel::markdown_dialect
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// Markup dialect a channel renders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MarkdownDialect {
+    /// Standard Markdown, sent unchanged (Discord, CLI, webhooks).
+    #[default]
+    Markdown,
+    /// Telegram `parse_mode=HTML`.
+    TelegramHtml,
+    /// Telegram `parse_mode=MarkdownV2`.
+    TelegramMarkdownV2,
+    /// Slack `mrkdwn`.
+    SlackMrkdwn,
+    /// HTML fragment with `<br>` line breaks (email).
+    Html,
+}
+
+/// Convert canonical Markdown into `dialect`.
+pub fn format_markdown(content: &str, dialect: MarkdownDialect) -> String {
+    match dialect {
+        MarkdownDialect::Markdown => content.to_string(),
+        MarkdownDialect::TelegramHtml => render_telegram_html(content),
+        MarkdownDialect::TelegramMarkdownV2 => render_markdown_v2(content),
+        MarkdownDialect::SlackMrkdwn => render_slack_mrkdwn(content),
+        MarkdownDialect::Html => render_email_html(content),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Shared Markdown patterns
+// ---------------------------------------------------------------------------
+//
+// Strategy for every dialect: extract code regions into NUL-byte
+// placeholders first (so their content is never touched by Markdown regex),
+// process everything else, then reinsert code with its own escaping.
+
+static RE_FENCED_CODE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?s)```[^\n]*\n(.*?)```").unwrap());
+static RE_INLINE_CODE: Lazy<Regex> = Lazy::new(|| Regex::new(r"`([^`\n]+)`").unwrap());
+// Bold+italic combined (***text***) must be matched before bold and italic.
+static RE_BOLD_ITALIC: Lazy<Regex> = Lazy::new(|| Regex::new(r"\*\*\*(.+?)\*\*\*").unwrap());
+static RE_BOLD: Lazy<Regex> = Lazy::new(|| Regex::new(r"\*\*(.+?)\*\*").unwrap());
+// Italic is applied after bold conversion has consumed all ** pairs, so
+// remaining single * delimiters are safe to match without lookbehind.
+static RE_ITALIC: Lazy<Regex> = Lazy::new(|| Regex::new(r"\*([^\*\n]+?)\*").unwrap());
+// Underscore-style italic: _text_ — matched at word boundaries.
+// Uses `(?:^|[\s])` as a pseudo-lookbehind to avoid snake_case.
+static RE_ITALIC_UNDERSCORE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?:^|(?P<pre>\s))_(?P<body>[^_\n]+?)_(?P<suf>[\s.,;:!?]|$)"#).unwrap()
+});
+static RE_STRIKETHROUGH: Lazy<Regex> = Lazy::new(|| Regex::new(r"~~(.+?)~~").unwrap());
+static RE_LINK: Lazy<Regex> = Lazy::new(|| Regex::new(r"\[([^\]]+)\]\(([^)]+)\)").unwrap());
+static RE_SPOILER: Lazy<Regex> = Lazy::new(|| Regex::new(r"\|\|(.+?)\|\|").unwrap());
+static RE_HEADER: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?m)^#{1,6}\s+(.+)$").unwrap());
+static RE_BULLET: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?m)^[ \t]*[-*]\s+").unwrap());
+static RE_NUMBERED_LIST: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?m)^[ \t]*\d+\.\s+").unwrap());
+static RE_BLOCKQUOTE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?m)^&gt;\s?(.*)$").unwrap());
+static RE_HR: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?m)^-{3,}\s*$").unwrap());
+// Blockquote before any escaping (the HTML path matches the escaped form).
+static RE_QUOTE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?m)^>\s?").unwrap());
+
+// ---------------------------------------------------------------------------
+// HTML (Telegram and email)
+// ---------------------------------------------------------------------------
+//
+// Telegram's HTML mode supports a small subset of tags: <b>, <i>, <code>,
+// <pre>, <a href="">, <tg-spoiler>.  Email gets the same tags plus <br> line
+// breaks.
+
+pub(crate) fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Validate that HTML tags are properly nested (no crossing tags).
+/// Returns `true` when the tag structure is well-formed. `<br>` is void and
+/// needs no closing tag.
+pub(crate) fn html_tags_valid(html: &str) -> bool {
+    static RE_TAG: Lazy<Regex> = Lazy::new(|| Regex::new(r"<(/?)(\w[\w-]*)(?:\s[^>]*)?>").unwrap());
+    let mut stack: Vec<String> = Vec::new();
+    for caps in RE_TAG.captures_iter(html) {
+        let closing = &caps[1] == "/";
+        let tag = caps[2].to_lowercase();
+        if tag == "br" {
+            continue;
+        }
+        if closing {
+            if stack.last().map(|s| s.as_str()) != Some(tag.as_str()) {
+                return false;
+            }
+            stack.pop();
+        } else {
+            stack.push(tag);
+        }
+    }
+    stack.is_empty()
+}
+
+/// Strip all HTML tags, restoring a plain-text representation.
+pub(crate) fn strip_html_tags(html: &str) -> String {
+    static RE_STRIP: Lazy<Regex> = Lazy::new(|| Regex::new(r"<[^>]+>").unwrap());
+    let text = RE_STRIP.replace_all(html, "");
+    // Unescape entities we added so the user sees normal characters.
+    text.replace("&amp;", "&")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+}
+
+/// Convert Markdown to Telegram's HTML parse mode.
+pub(crate) fn render_telegram_html(content: &str) -> String {
+    render_html(content, true)
+}
+
+/// Convert Markdown to an HTML fragment for email bodies.
+pub(crate) fn render_email_html(content: &str) -> String {
+    render_html(content, false)
+}
+
+fn render_html(content: &str, telegram: bool) -> String {
+    // Phase 1: Extract fenced code blocks into placeholders.
+    let mut code_blocks: Vec<String> = Vec::new();
+    let mut text = RE_FENCED_CODE
+        .replace_all(content, |caps: &regex::Captures| {
+            let idx = code_blocks.len();
+            let body = caps.get(1).map_or("", |m| m.as_str());
+            code_blocks.push(body.to_string());
+            format!("\x00CODEBLOCK{idx}\x00")
+        })
+        .into_owned();
+
+    // Phase 2: Extract inline code into placeholders.
+    let mut inline_codes: Vec<String> = Vec::new();
+    text = RE_INLINE_CODE
+        .replace_all(&text, |caps: &regex::Captures| {
+            let idx = inline_codes.len();
+            inline_codes.push(caps[1].to_string());
+            format!("\x00INLINE{idx}\x00")
+        })
+        .into_owned();
+
+    // Phase 3: Escape HTML entities in the remaining text.
+    text = escape_html(&text);
+
+    // Phase 3b: Restore Telegram-native HTML tags that Claude may emit
+    // directly (there is no markdown equivalent for these).
+    text = text
+        .replace("&lt;u&gt;", "<u>")
+        .replace("&lt;/u&gt;", "</u>")
+        .replace("&lt;ins&gt;", "<u>")
+        .replace("&lt;/ins&gt;", "</u>");
+
+    // Phase 4: Block-level conversions.
+    text = RE_HR.replace_all(&text, "").into_owned();
+    text = RE_HEADER.replace_all(&text, "<b>$1</b>\n").into_owned();
+    text = RE_BLOCKQUOTE
+        .replace_all(&text, "<blockquote>$1</blockquote>")
+        .into_owned();
+    text = RE_BULLET.replace_all(&text, "• ").into_owned();
+    text = RE_NUMBERED_LIST
+        .replace_all(&text, |caps: &regex::Captures| {
+            // Preserve the original number prefix but strip the markdown indent.
+            let m = caps.get(0).unwrap().as_str().trim_start();
+            m.to_string()
+        })
+        .into_owned();
+
+    // Phase 5: Inline conversions (bold+italic before bold before italic).
+    text = RE_BOLD_ITALIC
+        .replace_all(&text, "<b><i>$1</i></b>")
+        .into_owned();
+    text = RE_BOLD.replace_all(&text, "<b>$1</b>").into_owned();
+    text = RE_ITALIC.replace_all(&text, "<i>$1</i>").into_owned();
+    text = RE_ITALIC_UNDERSCORE
+        .replace_all(&text, |caps: &regex::Captures| {
+            let pre = caps.name("pre").map_or("", |m| m.as_str());
+            let body = &caps["body"];
+            let suf = caps.name("suf").map_or("", |m| m.as_str());
+            format!("{pre}<i>{body}</i>{suf}")
+        })
+        .into_owned();
+    text = RE_STRIKETHROUGH
+        .replace_all(&text, "<s>$1</s>")
+        .into_owned();
+    text = RE_LINK
+        .replace_all(&text, |caps: &regex::Captures| {
+            format!("<a href=\"{}\">{}</a>", &caps[2], &caps[1])
+        })
+        .into_owned();
+
+    // Phase 6: Spoilers (Telegram only) or explicit line breaks (email).
+    // Code placeholders contain no newlines, so `<pre>` bodies keep theirs.
+    if telegram {
+        text = RE_SPOILER
+            .replace_all(&text, "<tg-spoiler>$1</tg-spoiler>")
+            .into_owned();
+    } else {
+        text = text.replace('\n', "<br>\n");
+    }
+
+    // Phase 7: Reinsert code blocks with their own HTML escaping.
+    for (idx, block) in code_blocks.iter().enumerate() {
+        let tag = format!("<pre>{}</pre>", escape_html(block.trim_end()));
+        text = text.replace(&format!("\x00CODEBLOCK{idx}\x00"), &tag);
+    }
+    for (idx, code) in inline_codes.iter().enumerate() {
+        let tag = format!("<code>{}</code>", escape_html(code));
+        text = text.replace(&format!("\x00INLINE{idx}\x00"), &tag);
+    }
+
+    // Safety net: if regex substitutions produced crossing tags (e.g. bold
+    // wrapping an italic that extends beyond it), fall back to plain text so
+    // Telegram doesn't reject the message outright.
+    if !html_tags_valid(&text) {
+        let plain = strip_html_tags(&text);
+        if telegram {
+            return plain;
+        }
+        return escape_html(&plain).replace('\n', "<br>\n");
+    }
+
+    text
+}
+
+// ---------------------------------------------------------------------------
+// Telegram MarkdownV2 and Slack mrkdwn
+// ---------------------------------------------------------------------------
+//
+// Both dialects mark up text with characters that must otherwise be escaped,
+// so styles are first rewritten to private-use marker characters, the text
+// is escaped, and the markers are then replaced with the dialect's syntax.
+
+const MARK_BOLD: char = '\u{E000}';
+const MARK_ITALIC: char = '\u{E001}';
+const MARK_STRIKE: char = '\u{E002}';
+const MARK_QUOTE: char = '\u{E003}';
+const MARK_SPOILER: char = '\u{E004}';
+
+/// Characters Telegram MarkdownV2 requires to be backslash-escaped.
+const MARKDOWN_V2_RESERVED: &[char] = &[
+    '_', '*', '[', ']', '(', ')', '~', '`', '>', '#', '+', '-', '=', '|', '{', '}', '.', '!', '\\',
+];
+
+/// Escape text for Telegram MarkdownV2 outside code and link URLs.
+pub fn escape_markdown_v2(s: &str) -> String {
+    escape_with(s, MARKDOWN_V2_RESERVED)
+}
+
+/// Backslash-escape every character in `reserved`.
+fn escape_with(s: &str, reserved: &[char]) -> String {
+    let mut out = String::with_capacity(s.len());
+    for ch in s.chars() {
+        if reserved.contains(&ch) {
+            out.push('\\');
+        }
+        out.push(ch);
+    }
+    out
+}
+
+/// Store an already-rendered fragment and return its placeholder.
+fn stash(slots: &mut Vec<String>, rendered: String) -> String {
+    let idx = slots.len();
+    slots.push(rendered);
+    format!("\x00{idx}\x00")
+}
+
+fn unstash(mut text: String, slots: &[String]) -> String {
+    for (idx, rendered) in slots.iter().enumerate() {
+        text = text.replace(&format!("\x00{idx}\x00"), rendered);
+    }
+    text
+}
+
+/// Rewrite block and inline styles to marker characters.
+fn mark_styles(text: &str, italic: &str) -> String {
+    let bold = MARK_BOLD.to_string();
+    let mut text = RE_HR.replace_all(text, "").into_owned();
+    text = RE_HEADER
+        .replace_all(&text, format!("{bold}$1{bold}").as_str())
+        .into_owned();
+    text = RE_QUOTE
+        .replace_all(&text, MARK_QUOTE.to_string().as_str())
+        .into_owned();
+    text = RE_BULLET.replace_all(&text, "• ").into_owned();
+    text = RE_BOLD_ITALIC
+        .replace_all(&text, format!("{bold}{italic}$1{italic}{bold}").as_str())
+        .into_owned();
+    text = RE_BOLD
+        .replace_all(&text, format!("{bold}$1{bold}").as_str())
+        .into_owned();
+    text = RE_ITALIC
+        .replace_all(&text, format!("{italic}$1{italic}").as_str())
+        .into_owned();
+    text = RE_ITALIC_UNDERSCORE
+        .replace_all(&text, |caps: &regex::Captures| {
+            let pre = caps.name("pre").map_or("", |m| m.as_str());
+            let suf = caps.name("suf").map_or("", |m| m.as_str());
+            format!("{pre}{italic}{}{italic}{suf}", &caps["body"])
+        })
+        .into_owned();
+    RE_STRIKETHROUGH
+        .replace_all(&text, format!("{MARK_STRIKE}$1{MARK_STRIKE}").as_str())
+        .into_owned()
+}
+
+/// Convert Markdown to Telegram MarkdownV2, escaping every reserved
+/// character outside of markup.
+fn render_markdown_v2(content: &str) -> String {
+    let mut slots = Vec::new();
+    let mut text = RE_FENCED_CODE
+        .replace_all(content, |caps: &regex::Captures| {
+            let body = escape_with(caps[1].trim_end(), &['`', '\\']);
+            stash(&mut slots, format!("```\n{body}\n```"))
+        })
+        .into_owned();
+    text = RE_INLINE_CODE
+        .replace_all(&text, |caps: &regex::Captures| {
+            let code = escape_with(&caps[1], &['`', '\\']);
+            stash(&mut slots, format!("`{code}`"))
+        })
+        .into_owned();
+    text = RE_LINK
+        .replace_all(&text, |caps: &regex::Captures| {
+            let label = escape_markdown_v2(&caps[1]);
+            let url = escape_with(&caps[2], &[')', '\\']);
+            stash(&mut slots, format!("[{label}]({url})"))
+        })
+        .into_owned();
+
+    text = mark_styles(&text, &MARK_ITALIC.to_string());
+    text = RE_SPOILER
+        .replace_all(&text, format!("{MARK_SPOILER}$1{MARK_SPOILER}").as_str())
+        .into_owned();
+
+    let text: String = escape_markdown_v2(&text)
+        .chars()
+        .map(|ch| match ch {
+            MARK_BOLD => "*".to_string(),
+            MARK_ITALIC => "_".to_string(),
+            MARK_STRIKE => "~".to_string(),
+            MARK_QUOTE => ">".to_string(),
+            MARK_SPOILER => "||".to_string(),
+            other => other.to_string(),
+        })
+        .collect();
+    unstash(text, &slots)
+}
+
+/// Convert Markdown to Slack `mrkdwn`.
+fn render_slack_mrkdwn(content: &str) -> String {
+    let mut slots = Vec::new();
+    let mut text = RE_FENCED_CODE
+        .replace_all(content, |caps: &regex::Captures| {
+            let body = escape_html(caps[1].trim_end());
+            stash(&mut slots, format!("```\n{body}\n```"))
+        })
+        .into_owned();
+    text = RE_INLINE_CODE
+        .replace_all(&text, |caps: &regex::Captures| {
+            let code = escape_html(&caps[1]);
+            stash(&mut slots, format!("`{code}`"))
+        })
+        .into_owned();
+    text = RE_LINK
+        .replace_all(&text, |caps: &regex::Captures| {
+            let label = escape_html(&caps[1]).replace('|', "¦");
+            stash(&mut slots, format!("<{}|{}>", escape_html(&caps[2]), label))
+        })
+        .into_owned();
+
+    // Slack reserves only `&`, `<` and `>`.
+    let text: String = escape_html(&mark_styles(&text, "_"))
+        .chars()
+        .map(|ch| match ch {
+            MARK_BOLD => '*',
+            MARK_STRIKE => '~',
+            MARK_QUOTE => '>',
+            other => other,
+        })
+        .collect();
+    unstash(text, &slots)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "## Result\n\
+Run **cargo build** then see [the docs](https://example.com/a_b?x=1&y=2).\n\
+- use `a < b` *carefully*\n\
+```rust\nlet v = vec![1, 2];\n```";
+
+    #[test]
+    fn test_escape_markdown_v2_reserved_characters() {
+        assert_eq!(
+            escape_markdown_v2("1.5 + (2-1) = 2.5! #tag_x"),
+            "1\\.5 \\+ \\(2\\-1\\) \\= 2\\.5\\! \\#tag\\_x"
+        );
+        assert_eq!(escape_markdown_v2("a\\b"), "a\\\\b");
+    }
+
+    #[test]
+    fn test_markdown_v2_sample_message() {
+        assert_eq!(
+            format_markdown(SAMPLE, MarkdownDialect::TelegramMarkdownV2),
+            "*Result*\n\
+Run *cargo build* then see [the docs](https://example.com/a_b?x=1&y=2)\\.\n\
+• use `a < b` _carefully_\n\
+```\nlet v = vec![1, 2];\n```"
+        );
+    }
+
+    #[test]
+    fn test_markdown_v2_escapes_code_and_links() {
+        assert_eq!(
+            format_markdown(
+                "`a\\b` [a_b](https://e.com/x_y) ~~old~~ ||secret||",
+                MarkdownDialect::TelegramMarkdownV2
+            ),
+            "`a\\\\b` [a\\_b](https://e.com/x_y) ~old~ ||secret||"
+        );
+    }
+
+    #[test]
+    fn test_slack_mrkdwn_sample_message() {
+        assert_eq!(
+            format_markdown(SAMPLE, MarkdownDialect::SlackMrkdwn),
+            "*Result*\n\
+Run *cargo build* then see <https://example.com/a_b?x=1&amp;y=2|the docs>.\n\
+• use `a &lt; b` _carefully_\n\
+```\nlet v = vec![1, 2];\n```"
+        );
+    }
+
+    #[test]
+    fn test_email_html_line_breaks() {
+        assert_eq!(
+            format_markdown("Hi **Bob**\nLine 2 & more", MarkdownDialect::Html),
+            "Hi <b>Bob</b><br>\nLine 2 &amp; more"
+        );
+        assert_eq!(
+            format_markdown("```\na\nb\n```", MarkdownDialect::Html),
+            "<pre>a\nb</pre>"
+        );
+    }
+
+    #[test]
+    fn test_markdown_dialect_passthrough() {
+        assert_eq!(format_markdown(SAMPLE, MarkdownDialect::Markdown), SAMPLE);
+    }
+}
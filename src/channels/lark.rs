@@ -35,10 +35,14 @@ use tokio_tungstenite::connect_async;
 use tokio_tungstenite::tungstenite::Message as WsMsg;
 use tracing::{debug, error, info, warn};
 
-use crate::bus::{InboundMessage, MediaAttachment, MediaType, MessageBus, OutboundMessage};
+use crate::bus::{
+    ActionStyle, InboundMessage, MediaAttachment, MediaType, MessageBus, OutboundMessage,
+    ACTION_ID_METADATA_KEY,
+};
 use crate::config::LarkConfig;
 use crate::error::{Result, ZeptoError};
 
+use super::seen::SeenIds;
 use super::{BaseChannelConfig, Channel};
 
 // ---------------------------------------------------------------------------
@@ -283,6 +287,137 @@ fn should_respond_in_group(mentions: &[serde_json::Value]) -> bool {
     !mentions.is_empty()
 }
 
+/// Build the interactive card for an outbound message.
+///
+/// The message text is always rendered as markdown.  When the message
+/// carries a [`StructuredOutput`](crate::bus::StructuredOutput), its title
+/// becomes the card header, fields are laid out as short two-column entries
+/// and actions become buttons whose callback value carries the action ID.
+fn build_card(msg: &OutboundMessage) -> serde_json::Value {
+    let mut elements = Vec::new();
+    if msg.structured.is_none() || !msg.content.trim().is_empty() {
+        elements.push(serde_json::json!({
+            "tag": "markdown",
+            "content": msg.content,
+        }));
+    }
+
+    let Some(structured) = &msg.structured else {
+        return serde_json::json!({
+            "config": { "wide_screen_mode": true },
+            "elements": elements,
+        });
+    };
+
+    if !structured.fields.is_empty() {
+        let fields: Vec<serde_json::Value> = structured
+            .fields
+            .iter()
+            .map(|f| {
+                serde_json::json!({
+                    "is_short": true,
+                    "text": {
+                        "tag": "lark_md",
+                        "content": format!("**{}**\n{}", f.label, f.value),
+                    }
+                })
+            })
+            .collect();
+        elements.push(serde_json::json!({ "tag": "div", "fields": fields }));
+    }
+
+    if !structured.actions.is_empty() {
+        let buttons: Vec<serde_json::Value> = structured
+            .actions
+            .iter()
+            .map(|a| {
+                let kind = match a.style {
+                    ActionStyle::Default => "default",
+                    ActionStyle::Primary => "primary",
+                    ActionStyle::Danger => "danger",
+                };
+                serde_json::json!({
+                    "tag": "button",
+                    "text": { "tag": "plain_text", "content": a.label },
+                    "type": kind,
+                    "value": { "action_id": a.id, "value": a.value },
+                })
+            })
+            .collect();
+        elements.push(serde_json::json!({ "tag": "action", "actions": buttons }));
+    }
+
+    let mut card = serde_json::json!({
+        "config": { "wide_screen_mode": true },
+        "elements": elements,
+    });
+    if let Some(title) = &structured.title {
+        card["header"] = serde_json::json!({
+            "title": { "tag": "plain_text", "content": title },
+        });
+    }
+    card
+}
+
+/// A card button click delivered by Lark.
+#[derive(Debug, Clone, PartialEq)]
+struct CardAction {
+    sender_open_id: String,
+    chat_id: String,
+    action_id: String,
+    value: String,
+}
+
+impl CardAction {
+    /// Convert the click into the user's reply on the bus.
+    ///
+    /// The content is the button's value (e.g. "approve"), so text-based
+    /// flows such as approvals handle clicks and typed replies alike.
+    fn into_inbound(self, channel: &str) -> InboundMessage {
+        let content = if self.value.is_empty() {
+            self.action_id.as_str()
+        } else {
+            self.value.as_str()
+        };
+        InboundMessage::new(channel, &self.sender_open_id, &self.chat_id, content)
+            .with_metadata(ACTION_ID_METADATA_KEY, &self.action_id)
+    }
+}
+
+/// Parse a card button callback.
+///
+/// Handles both the v2 `card.action.trigger` event (operator and context
+/// under `event`) and the legacy v1 callback (top-level `open_id` and
+/// `open_chat_id`).  Returns `None` when the payload is not a button click
+/// on a card built by [`build_card`].
+fn parse_card_action(payload: &serde_json::Value) -> Option<CardAction> {
+    let (body, sender, chat) = match payload["header"]["event_type"].as_str() {
+        Some("card.action.trigger") => {
+            let event = &payload["event"];
+            (
+                event,
+                event["operator"]["open_id"].as_str(),
+                event["context"]["open_chat_id"].as_str(),
+            )
+        }
+        Some(_) => return None,
+        None => (
+            payload,
+            payload["open_id"].as_str(),
+            payload["open_chat_id"].as_str(),
+        ),
+    };
+    let value = &body["action"]["value"];
+    let action_id = value["action_id"].as_str().filter(|s| !s.is_empty())?;
+    let sender = sender.filter(|s| !s.is_empty())?;
+    Some(CardAction {
+        sender_open_id: sender.to_string(),
+        chat_id: chat.filter(|s| !s.is_empty()).unwrap_or(sender).to_string(),
+        action_id: action_id.to_string(),
+        value: value["value"].as_str().unwrap_or("").to_string(),
+    })
+}
+
 /// Fetch and cache a Lark tenant access token.
 ///
 /// Checks the cache first; only hits the network when the token is missing
@@ -389,8 +524,8 @@ pub struct LarkChannel {
     running: Arc<AtomicBool>,
     /// Cached tenant access token with proactive refresh metadata.
     tenant_token: Arc<RwLock<Option<CachedToken>>>,
-    /// Dedup set: WS message_ids seen in the last ~30 min.
-    ws_seen_ids: Arc<RwLock<HashMap<String, Instant>>>,
+    /// Dedup set: recently seen WS message_ids, bounded and persisted.
+    ws_seen_ids: Arc<SeenIds>,
 }
 
 impl LarkChannel {
@@ -411,7 +546,7 @@ impl LarkChannel {
             bus,
             running: Arc::new(AtomicBool::new(false)),
             tenant_token: Arc::new(RwLock::new(None)),
-            ws_seen_ids: Arc::new(RwLock::new(HashMap::new())),
+            ws_seen_ids: Arc::new(SeenIds::default()),
         }
     }
 
@@ -714,7 +849,7 @@ impl LarkChannel {
                         }
                     };
 
-                    if msg_type != "event" {
+                    if msg_type != "event" && msg_type != "card" {
                         continue;
                     }
 
@@ -724,6 +859,27 @@ impl LarkChannel {
                         Err(e) => { error!("Lark: event JSON parse error: {e}"); continue; }
                     };
 
+                    // Card button clicks are routed back as the user's reply
+                    if let Some(action) = parse_card_action(&event) {
+                        if !self.is_sender_allowed(&action.sender_open_id) {
+                            warn!(
+                                "Lark WS: ignoring card action from {} (not in allowed list)",
+                                action.sender_open_id
+                            );
+                            continue;
+                        }
+                        debug!(
+                            "Lark WS: card action '{}' from {}",
+                            action.action_id, action.sender_open_id
+                        );
+                        let inbound = action.into_inbound(self.base_config.name.as_str());
+                        if self.bus.publish_inbound(inbound).await.is_err() {
+                            warn!("Lark: message bus closed — stopping WS loop");
+                            break;
+                        }
+                        continue;
+                    }
+
                     let event_type = event["header"]["event_type"].as_str().unwrap_or("");
                     if event_type != "im.message.receive_v1" {
                         continue;
@@ -771,14 +927,12 @@ impl LarkChannel {
 
                     // Dedup
                     if !message_id.is_empty() {
-                        let now = Instant::now();
-                        let mut seen = self.ws_seen_ids.write().await;
-                        seen.retain(|_, t| now.duration_since(*t) < Duration::from_secs(30 * 60));
-                        if seen.contains_key(&message_id) {
+                        let is_new = self.ws_seen_ids.insert(&message_id);
+                        self.ws_seen_ids.maybe_flush();
+                        if !is_new {
                             debug!("Lark WS: duplicate message_id {message_id}, skipping");
                             continue;
                         }
-                        seen.insert(message_id.clone(), now);
                     }
 
                     // Decode message text (image messages use a synthetic description)
@@ -1004,6 +1158,11 @@ impl Channel for LarkChannel {
             self.config.feishu
         );
 
+        let seen_path = SeenIds::default_path(&self.base_config.name);
+        if let Err(e) = self.ws_seen_ids.persist_to(seen_path) {
+            warn!("Lark: failed to load seen message IDs: {e}");
+        }
+
         let running = Arc::clone(&self.running);
         let tenant_token = Arc::clone(&self.tenant_token);
         let ws_seen_ids = Arc::clone(&self.ws_seen_ids);
@@ -1065,6 +1224,9 @@ impl Channel for LarkChannel {
     async fn stop(&mut self) -> Result<()> {
         info!("Lark channel stopping");
         self.running.store(false, Ordering::SeqCst);
+        if let Err(e) = self.ws_seen_ids.flush() {
+            warn!("Lark: failed to persist seen message IDs: {e}");
+        }
         Ok(())
     }
 
@@ -1087,13 +1249,7 @@ impl Channel for LarkChannel {
             self.api_base(),
             receive_id_type
         );
-        let card = serde_json::json!({
-            "config": { "wide_screen_mode": true },
-            "elements": [{
-                "tag": "markdown",
-                "content": msg.content,
-            }]
-        });
+        let card = build_card(&msg);
         let body = serde_json::json!({
             "receive_id": msg.chat_id,
             "msg_type":   "interactive",
@@ -1473,4 +1629,115 @@ mod tests {
         assert_eq!(parsed_card["elements"][0]["content"], content);
         assert_eq!(parsed_card["config"]["wide_screen_mode"], true);
     }
+
+    #[test]
+    fn test_build_card_plain_text_fallback() {
+        let msg = OutboundMessage::new("lark", "oc_test", "Just **text**");
+        let card = build_card(&msg);
+        assert_eq!(card["elements"].as_array().unwrap().len(), 1);
+        assert_eq!(card["elements"][0]["tag"], "markdown");
+        assert_eq!(card["elements"][0]["content"], "Just **text**");
+        assert!(card.get("header").is_none());
+    }
+
+    #[test]
+    fn test_build_card_from_structured_tool_output() {
+        use crate::bus::{OutputAction, StructuredOutput};
+        use crate::tools::ToolOutput;
+
+        let output = ToolOutput::user_visible("Deploy `api` to production?").with_structured(
+            StructuredOutput::new()
+                .with_title("Approval required")
+                .with_field("Tool", "shell")
+                .with_field("Command", "make deploy")
+                .with_action(
+                    OutputAction::new("approve", "Approve", "approve")
+                        .with_style(ActionStyle::Primary),
+                )
+                .with_action(
+                    OutputAction::new("reject", "Reject", "reject").with_style(ActionStyle::Danger),
+                ),
+        );
+        let mut msg = OutboundMessage::new("lark", "oc_test", output.for_user.as_deref().unwrap());
+        msg.structured = output.structured.clone();
+
+        let card = build_card(&msg);
+        assert_eq!(card["header"]["title"]["content"], "Approval required");
+        assert_eq!(card["elements"][0]["tag"], "markdown");
+
+        let fields = &card["elements"][1];
+        assert_eq!(fields["tag"], "div");
+        assert_eq!(fields["fields"][1]["is_short"], true);
+        assert_eq!(
+            fields["fields"][1]["text"]["content"],
+            "**Command**\nmake deploy"
+        );
+
+        let actions = &card["elements"][2];
+        assert_eq!(actions["tag"], "action");
+        assert_eq!(actions["actions"][0]["text"]["content"], "Approve");
+        assert_eq!(actions["actions"][0]["type"], "primary");
+        assert_eq!(actions["actions"][1]["type"], "danger");
+        assert_eq!(actions["actions"][1]["value"]["action_id"], "reject");
+        assert_eq!(actions["actions"][1]["value"]["value"], "reject");
+    }
+
+    #[test]
+    fn test_card_action_v2_maps_to_inbound_action() {
+        let payload = serde_json::json!({
+            "schema": "2.0",
+            "header": { "event_type": "card.action.trigger" },
+            "event": {
+                "operator": { "open_id": "ou_user1" },
+                "action": {
+                    "tag": "button",
+                    "value": { "action_id": "approve", "value": "approve" }
+                },
+                "context": { "open_chat_id": "oc_chat1", "open_message_id": "om_1" }
+            }
+        });
+        let action = parse_card_action(&payload).unwrap();
+        assert_eq!(action.sender_open_id, "ou_user1");
+        assert_eq!(action.chat_id, "oc_chat1");
+
+        let inbound = action.into_inbound("lark");
+        assert_eq!(inbound.channel, "lark");
+        assert_eq!(inbound.sender_id, "ou_user1");
+        assert_eq!(inbound.chat_id, "oc_chat1");
+        assert_eq!(inbound.content, "approve");
+        assert_eq!(
+            inbound
+                .metadata
+                .get(ACTION_ID_METADATA_KEY)
+                .map(String::as_str),
+            Some("approve")
+        );
+    }
+
+    #[test]
+    fn test_card_action_v1_without_value_uses_action_id() {
+        let payload = serde_json::json!({
+            "open_id": "ou_user2",
+            "open_message_id": "om_2",
+            "action": { "tag": "button", "value": { "action_id": "refresh" } }
+        });
+        let inbound = parse_card_action(&payload).unwrap().into_inbound("feishu");
+        // No chat ID in the callback: reply in the user's p2p chat
+        assert_eq!(inbound.chat_id, "ou_user2");
+        assert_eq!(inbound.content, "refresh");
+    }
+
+    #[test]
+    fn test_parse_card_action_ignores_other_events() {
+        let message_event = serde_json::json!({
+            "header": { "event_type": "im.message.receive_v1" },
+            "event": { "action": { "value": { "action_id": "x" } } }
+        });
+        assert!(parse_card_action(&message_event).is_none());
+        let foreign_card = serde_json::json!({
+            "open_id": "ou_user",
+            "action": { "value": { "key": "not ours" } }
+        });
+        assert!(parse_card_action(&foreign_card).is_none());
+    }
 }
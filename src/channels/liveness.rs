@@ -0,0 +1,131 @@
+//! Channel liveness tracking.
+//!
+//! Long-poll and websocket channels can keep reporting `is_running()` after
+//! their connection has silently died, so the bot looks up but receives
+//! nothing. The channel manager periodically calls [`Channel::ping`] on
+//! channels that support it and feeds the outcomes to a [`LivenessMonitor`],
+//! which decides when the connection is stale enough to reconnect.
+//!
+//! [`Channel::ping`]: super::Channel::ping
+
+use std::time::{Duration, Instant};
+
+/// What the supervisor should do after a ping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum LivenessDecision {
+    /// The ping succeeded.
+    Alive,
+    /// The ping failed, but the last success is recent enough to wait.
+    Degraded,
+    /// No ping has succeeded within the stale window; reconnect the channel.
+    Reconnect,
+}
+
+/// Ping history for one channel.
+#[derive(Debug, Clone)]
+pub(crate) struct LivenessMonitor {
+    stale_after: Duration,
+    last_ok: Instant,
+    consecutive_failures: u32,
+}
+
+impl LivenessMonitor {
+    /// Start tracking a channel that is known to be connected at `now`.
+    pub(crate) fn new(stale_after: Duration, now: Instant) -> Self {
+        Self {
+            stale_after,
+            last_ok: now,
+            consecutive_failures: 0,
+        }
+    }
+
+    /// Record a ping outcome at `now` and decide what to do.
+    pub(crate) fn record(&mut self, ok: bool, now: Instant) -> LivenessDecision {
+        if ok {
+            self.last_ok = now;
+            self.consecutive_failures = 0;
+            return LivenessDecision::Alive;
+        }
+        self.consecutive_failures += 1;
+        if now.saturating_duration_since(self.last_ok) >= self.stale_after {
+            LivenessDecision::Reconnect
+        } else {
+            LivenessDecision::Degraded
+        }
+    }
+
+    /// Forget failures after the channel has been reconnected at `now`.
+    pub(crate) fn reset(&mut self, now: Instant) {
+        self.last_ok = now;
+        self.consecutive_failures = 0;
+    }
+
+    /// Number of failed pings since the last success.
+    pub(crate) fn consecutive_failures(&self) -> u32 {
+        self.consecutive_failures
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STALE: Duration = Duration::from_secs(180);
+
+    #[test]
+    fn test_failures_within_stale_window_only_degrade() {
+        let start = Instant::now();
+        let mut monitor = LivenessMonitor::new(STALE, start);
+
+        assert_eq!(
+            monitor.record(false, start + Duration::from_secs(60)),
+            LivenessDecision::Degraded
+        );
+        assert_eq!(
+            monitor.record(false, start + Duration::from_secs(120)),
+            LivenessDecision::Degraded
+        );
+        assert_eq!(monitor.consecutive_failures(), 2);
+
+        // A success in between restarts the stale window.
+        assert_eq!(
+            monitor.record(true, start + Duration::from_secs(150)),
+            LivenessDecision::Alive
+        );
+        assert_eq!(monitor.consecutive_failures(), 0);
+        assert_eq!(
+            monitor.record(false, start + Duration::from_secs(300)),
+            LivenessDecision::Degraded
+        );
+    }
+
+    #[test]
+    fn test_stale_heartbeat_triggers_reconnect() {
+        let start = Instant::now();
+        let mut monitor = LivenessMonitor::new(STALE, start);
+
+        for secs in [60, 120] {
+            assert_eq!(
+                monitor.record(false, start + Duration::from_secs(secs)),
+                LivenessDecision::Degraded
+            );
+        }
+        assert_eq!(
+            monitor.record(false, start + Duration::from_secs(180)),
+            LivenessDecision::Reconnect
+        );
+
+        // After reconnecting, the channel gets a fresh window.
+        let reconnected = start + Duration::from_secs(181);
+        monitor.reset(reconnected);
+        assert_eq!(monitor.consecutive_failures(), 0);
+        assert_eq!(
+            monitor.record(false, reconnected + Duration::from_secs(60)),
+            LivenessDecision::Degraded
+        );
+        assert_eq!(
+            monitor.record(false, reconnected + Duration::from_secs(180)),
+            LivenessDecision::Reconnect
+        );
+    }
+}
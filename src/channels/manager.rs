@@ -3,21 +3,24 @@
 //! This module provides the `ChannelManager` which is responsible for:
 //! - Registering and managing multiple communication channels
 //! - Starting and stopping all channels
-//! - Dispatching outbound messages to the appropriate channels
+//! - Dispatching outbound messages to the appropriate channels, through the
+//!   optional content filter (`safety.output_filter`)
 //! - Supervising channel health and restarting dead channels
 
 use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tokio::sync::{watch, Mutex, RwLock};
 use tokio::task::JoinHandle;
 use tracing::{debug, error, info, warn};
 
-use crate::bus::{MessageBus, OutboundMessage};
+use crate::bus::{MessageBus, OutboundMessage, STREAM_FINAL_METADATA_KEY, STREAM_ID_METADATA_KEY};
 use crate::config::Config;
 use crate::error::Result;
 use crate::health::{HealthCheck, HealthRegistry, HealthStatus};
+use crate::safety::output_filter::OutputFilter;
 
+use super::liveness::{LivenessDecision, LivenessMonitor};
 use super::Channel;
 
 type SharedChannel = Arc<Mutex<Box<dyn Channel>>>;
@@ -28,12 +31,23 @@ const SUPERVISOR_POLL_SECS: u64 = 15;
 const SUPERVISOR_COOLDOWN_SECS: u64 = 60;
 /// Maximum number of restart attempts before giving up on a channel.
 const SUPERVISOR_MAX_RESTARTS: u32 = 5;
+/// Interval between liveness pings for channels that support them.
+const LIVENESS_PING_SECS: u64 = 60;
+/// A channel with no successful ping for this long is reconnected.
+const LIVENESS_STALE_SECS: u64 = 180;
+/// Pings that take longer than this count as failed.
+const LIVENESS_PING_TIMEOUT_SECS: u64 = 10;
+
+/// Streams without a final update are forgotten after this long.
+const STREAM_STATE_TTL: Duration = Duration::from_secs(15 * 60);
 
 /// Per-channel supervisor state.
 struct SupervisorEntry {
     restart_count: u32,
     last_restart: Option<Instant>,
     started: bool,
+    liveness: LivenessMonitor,
+    last_ping: Instant,
 }
 
 /// The `ChannelManager` manages the lifecycle of all communication channels.
@@ -101,6 +115,8 @@ pub struct ChannelManager {
     health_registry: Option<HealthRegistry>,
     /// Handle to the supervisor task (if running)
     supervisor_handle: Arc<RwLock<Option<JoinHandle<()>>>>,
+    /// Content filter applied to outbound messages, if enabled
+    output_filter: Option<Arc<OutputFilter>>,
 }
 
 impl ChannelManager {
@@ -125,6 +141,11 @@ impl ChannelManager {
     /// ```
     pub fn new(bus: Arc<MessageBus>, config: Config) -> Self {
         let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let output_filter = if config.safety.enabled {
+            OutputFilter::from_config(&config.safety.output_filter).map(Arc::new)
+        } else {
+            None
+        };
         Self {
             channels: Arc::new(RwLock::new(HashMap::new())),
             bus,
@@ -134,9 +155,16 @@ impl ChannelManager {
             dispatcher_handle: Arc::new(RwLock::new(None)),
             health_registry: None,
             supervisor_handle: Arc::new(RwLock::new(None)),
+            output_filter,
         }
     }
 
+    /// Replaces the outbound content filter built from config, e.g. with one
+    /// that also has a model classifier attached.
+    pub fn set_output_filter(&mut self, filter: Option<OutputFilter>) {
+        self.output_filter = filter.map(Arc::new);
+    }
+
     /// Sets the health registry for channel status reporting.
     pub fn set_health_registry(&mut self, registry: HealthRegistry) {
         self.health_registry = Some(registry);
@@ -249,6 +277,9 @@ impl ChannelManager {
         for (name, channel) in channels_to_start {
             info!("Starting channel: {}", name);
             let mut channel = channel.lock().await;
+            if let Some(ref registry) = self.health_registry {
+                channel.set_health_registry(registry.clone());
+            }
             if let Err(e) = channel.start().await {
                 error!("Failed to start channel {}: {}", name, e);
             } else {
@@ -274,8 +305,9 @@ impl ChannelManager {
         let bus = self.bus.clone();
         let channels_ref = self.channels.clone();
         let shutdown_rx = self.shutdown_rx.clone();
+        let output_filter = self.output_filter.clone();
         let handle = tokio::spawn(async move {
-            dispatch_outbound(bus, channels_ref, shutdown_rx).await;
+            dispatch_outbound(bus, channels_ref, output_filter, shutdown_rx).await;
         });
 
         // Store the handle so we can wait for it to stop
@@ -310,6 +342,11 @@ impl ChannelManager {
                             restart_count: 0,
                             last_restart: None,
                             started: true,
+                            liveness: LivenessMonitor::new(
+                                Duration::from_secs(LIVENESS_STALE_SECS),
+                                Instant::now(),
+                            ),
+                            last_ping: Instant::now(),
                         },
                     )
                 })
@@ -359,11 +396,13 @@ impl ChannelManager {
                         ch.is_running()
                     };
 
-                    if is_running {
+                    if is_running
+                        && !check_liveness(&name, &channel, entry, health_registry.as_ref()).await
+                    {
                         continue;
                     }
 
-                    // Channel is dead — check if we should restart
+                    // Channel is dead or stale — check if we should restart
                     if entry.restart_count >= SUPERVISOR_MAX_RESTARTS {
                         // Already gave up on this channel
                         continue;
@@ -378,8 +417,9 @@ impl ChannelManager {
 
                     // Attempt restart
                     warn!(
-                        "Supervisor: channel '{}' is dead (restart {}/{}), restarting",
+                        "Supervisor: channel '{}' is {} (restart {}/{}), restarting",
                         name,
+                        if is_running { "stale" } else { "dead" },
                         entry.restart_count + 1,
                         SUPERVISOR_MAX_RESTARTS
                     );
@@ -393,6 +433,8 @@ impl ChannelManager {
 
                     entry.restart_count += 1;
                     entry.last_restart = Some(Instant::now());
+                    entry.liveness.reset(Instant::now());
+                    entry.last_ping = Instant::now();
 
                     if restart_ok {
                         info!("Supervisor: channel '{}' restarted successfully", name);
@@ -498,6 +540,7 @@ impl ChannelManager {
     ///
     /// Returns an error if the channel fails to send the message.
     /// If the channel is not found, a warning is logged and `Ok(())` is returned.
+    /// A message withheld by the content filter is not sent and returns `Ok(())`.
     ///
     /// # Example
     ///
@@ -519,6 +562,13 @@ impl ChannelManager {
         };
 
         if let Some(channel) = channel {
+            let msg = match self.output_filter.as_deref() {
+                Some(filter) => match filter.apply(msg, true).await {
+                    Some(msg) => msg,
+                    None => return Ok(()),
+                },
+                None => msg,
+            };
             let channel = channel.lock().await;
             channel.send(msg).await
         } else {
@@ -537,6 +587,167 @@ impl ChannelManager {
     }
 }
 
+/// A streamed reply being edited in place.
+struct StreamedMessage {
+    /// Reference of the placeholder message, if the channel returned one.
+    message_ref: Option<String>,
+    /// When the placeholder was last sent or edited.
+    last_edit: Instant,
+}
+
+/// Tracks streamed replies so successive updates edit a single message.
+///
+/// The first update of a stream is sent as a placeholder; later updates edit
+/// it, skipping any that arrive within the channel's
+/// [`edit_interval`](Channel::edit_interval). The final update is always
+/// applied, so skipped intermediate text is never lost. Channels that cannot
+/// edit only receive the final update.
+#[derive(Default)]
+struct StreamEdits {
+    active: HashMap<String, StreamedMessage>,
+}
+
+impl StreamEdits {
+    async fn deliver(
+        &mut self,
+        channel: &dyn Channel,
+        msg: OutboundMessage,
+        stream_id: &str,
+    ) -> Result<()> {
+        let is_final = msg
+            .metadata
+            .get(STREAM_FINAL_METADATA_KEY)
+            .is_some_and(|v| v == "true");
+        if !channel.supports_edit() {
+            return if is_final {
+                channel.send(msg).await
+            } else {
+                Ok(())
+            };
+        }
+
+        let key = format!("{}:{}:{}", msg.channel, msg.chat_id, stream_id);
+        if is_final {
+            let Some(message_ref) = self.active.remove(&key).and_then(|s| s.message_ref) else {
+                return channel.send(msg).await;
+            };
+            if let Err(e) = channel
+                .edit_message(&msg.chat_id, &message_ref, &msg.content)
+                .await
+            {
+                // e.g. the final text outgrew the platform's edit limit
+                warn!("Final stream edit failed, sending as new message: {}", e);
+                return channel.send(msg).await;
+            }
+            return Ok(());
+        }
+
+        match self.active.get_mut(&key) {
+            None => {
+                self.active
+                    .retain(|_, s| s.last_edit.elapsed() < STREAM_STATE_TTL);
+                let chat_id = msg.chat_id.clone();
+                let message_ref = channel.send_with_ref(msg).await?;
+                debug!(chat_id = %chat_id, "Stream placeholder sent");
+                self.active.insert(
+                    key,
+                    StreamedMessage {
+                        message_ref,
+                        last_edit: Instant::now(),
+                    },
+                );
+                Ok(())
+            }
+            Some(state) => {
+                let Some(message_ref) = state.message_ref.clone() else {
+                    return Ok(());
+                };
+                if state.last_edit.elapsed() < channel.edit_interval() {
+                    return Ok(());
+                }
+                state.last_edit = Instant::now();
+                channel
+                    .edit_message(&msg.chat_id, &message_ref, &msg.content)
+                    .await
+            }
+        }
+    }
+}
+
+/// Pings a running channel when its ping interval has elapsed and reports
+/// the outcome to the health registry.
+///
+/// Returns `true` when the connection is stale and should be reconnected.
+async fn check_liveness(
+    name: &str,
+    channel: &SharedChannel,
+    entry: &mut SupervisorEntry,
+    health_registry: Option<&HealthRegistry>,
+) -> bool {
+    if entry.last_ping.elapsed() < Duration::from_secs(LIVENESS_PING_SECS) {
+        return false;
+    }
+    entry.last_ping = Instant::now();
+
+    let outcome = {
+        let ch = channel.lock().await;
+        if !ch.supports_ping() {
+            return false;
+        }
+        tokio::time::timeout(Duration::from_secs(LIVENESS_PING_TIMEOUT_SECS), ch.ping()).await
+    };
+    let ok = match outcome {
+        Ok(Ok(())) => true,
+        Ok(Err(e)) => {
+            warn!(
+                "Supervisor: liveness ping for channel '{}' failed: {}",
+                name, e
+            );
+            false
+        }
+        Err(_) => {
+            warn!("Supervisor: liveness ping for channel '{}' timed out", name);
+            false
+        }
+    };
+
+    let was_failing = entry.liveness.consecutive_failures() > 0;
+    match entry.liveness.record(ok, Instant::now()) {
+        LivenessDecision::Alive => {
+            if let Some(registry) = health_registry {
+                registry.heartbeat(name);
+                if was_failing {
+                    registry.update(name, HealthStatus::Ok, None);
+                }
+            }
+            if was_failing {
+                info!("Supervisor: channel '{}' liveness ping recovered", name);
+            }
+            false
+        }
+        LivenessDecision::Degraded => {
+            if let Some(registry) = health_registry {
+                registry.update(
+                    name,
+                    HealthStatus::Degraded,
+                    Some(format!(
+                        "liveness ping failed ({} in a row)",
+                        entry.liveness.consecutive_failures()
+                    )),
+                );
+            }
+            false
+        }
+        LivenessDecision::Reconnect => {
+            warn!(
+                "Supervisor: channel '{}' has not answered a liveness ping in {}s",
+                name, LIVENESS_STALE_SECS
+            );
+            true
+        }
+    }
+}
+
 /// Background task that dispatches outbound messages from the bus to channels.
 ///
 /// This function runs in a loop, consuming outbound messages from the bus
@@ -547,13 +758,16 @@ impl ChannelManager {
 ///
 /// * `bus` - The message bus to consume from
 /// * `channels` - The shared map of channels
+/// * `output_filter` - Content filter screening messages before they are sent
 /// * `shutdown_rx` - Receiver for shutdown signals
 async fn dispatch_outbound(
     bus: Arc<MessageBus>,
     channels: Arc<RwLock<HashMap<String, SharedChannel>>>,
+    output_filter: Option<Arc<OutputFilter>>,
     mut shutdown_rx: watch::Receiver<bool>,
 ) {
     info!("Outbound dispatcher started");
+    let mut streams = StreamEdits::default();
     loop {
         tokio::select! {
             // Check for shutdown signal
@@ -573,8 +787,23 @@ async fn dispatch_outbound(
                     };
 
                     if let Some(channel) = channel {
+                        let msg = match output_filter.as_deref() {
+                            Some(filter) => {
+                                let complete = !msg.metadata.contains_key(STREAM_ID_METADATA_KEY)
+                                    || msg.metadata.get(STREAM_FINAL_METADATA_KEY).is_some_and(|v| v == "true");
+                                match filter.apply(msg, complete).await {
+                                    Some(msg) => msg,
+                                    None => continue,
+                                }
+                            }
+                            None => msg,
+                        };
                         let channel = channel.lock().await;
-                        if let Err(e) = channel.send(msg).await {
+                        let result = match msg.metadata.get(STREAM_ID_METADATA_KEY).cloned() {
+                            Some(stream_id) => streams.deliver(&**channel, msg, &stream_id).await,
+                            None => channel.send(msg).await,
+                        };
+                        if let Err(e) = result {
                             error!("Failed to send message to {}: {}", channel_name, e);
                         }
                     } else {
@@ -969,4 +1198,212 @@ mod tests {
             assert!(handle.is_none()); // Taken by stop_all
         }
     }
+
+    /// A mock channel that records sends and in-place edits.
+    #[derive(Default)]
+    struct EditingChannel {
+        editable: bool,
+        edit_interval: Duration,
+        /// Message texts by ref, in send order.
+        messages: Arc<std::sync::Mutex<Vec<String>>>,
+        edits: Arc<AtomicU32>,
+    }
+
+    impl EditingChannel {
+        fn editable(edit_interval: Duration) -> Self {
+            Self {
+                editable: true,
+                edit_interval,
+                ..Default::default()
+            }
+        }
+
+        fn texts(&self) -> Vec<String> {
+            self.messages.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl Channel for EditingChannel {
+        fn name(&self) -> &str {
+            "editing"
+        }
+
+        async fn start(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn stop(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn send(&self, msg: OutboundMessage) -> Result<()> {
+            self.send_with_ref(msg).await.map(|_| ())
+        }
+
+        async fn send_with_ref(&self, msg: OutboundMessage) -> Result<Option<String>> {
+            let mut messages = self.messages.lock().unwrap();
+            messages.push(msg.content);
+            Ok(self.editable.then(|| (messages.len() - 1).to_string()))
+        }
+
+        async fn edit_message(
+            &self,
+            _chat_id: &str,
+            message_ref: &str,
+            new_text: &str,
+        ) -> Result<()> {
+            let index: usize = message_ref.parse().unwrap();
+            self.messages.lock().unwrap()[index] = new_text.to_string();
+            self.edits.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+
+        fn supports_edit(&self) -> bool {
+            self.editable
+        }
+
+        fn edit_interval(&self) -> Duration {
+            self.edit_interval
+        }
+
+        fn is_running(&self) -> bool {
+            true
+        }
+
+        fn is_allowed(&self, _user_id: &str) -> bool {
+            true
+        }
+    }
+
+    #[tokio::test]
+    async fn test_output_filter_redacts_and_blocks_before_send() {
+        let mut config = Config::default();
+        config.safety.output_filter = serde_json::from_value(serde_json::json!({
+            "enabled": true,
+            "blocked_message": "",
+            "rules": [
+                { "name": "profanity", "pattern": "(?i)darn", "action": "redact" },
+                { "name": "secret", "pattern": "launch code", "action": "block" }
+            ]
+        }))
+        .unwrap();
+        let manager = ChannelManager::new(Arc::new(MessageBus::new()), config);
+        let channel = EditingChannel::default();
+        let messages = Arc::clone(&channel.messages);
+        manager.register(Box::new(channel)).await;
+
+        let send = |text: &str| OutboundMessage::new("editing", "chat1", text);
+        manager.send("editing", send("Darn it")).await.unwrap();
+        manager
+            .send("editing", send("The launch code is 0000"))
+            .await
+            .unwrap();
+        assert_eq!(*messages.lock().unwrap(), ["[filtered] it"]);
+    }
+
+    fn stream_update(text: &str, is_final: bool) -> OutboundMessage {
+        let msg = OutboundMessage::new("editing", "chat1", text)
+            .with_metadata(STREAM_ID_METADATA_KEY, "s1");
+        if is_final {
+            msg.with_metadata(STREAM_FINAL_METADATA_KEY, "true")
+        } else {
+            msg
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stream_edits_replace_content_on_existing_ref() {
+        let channel = EditingChannel::editable(Duration::ZERO);
+        let mut streams = StreamEdits::default();
+
+        for (text, is_final) in [("Hel", false), ("Hello wor", false), ("Hello world!", true)] {
+            streams
+                .deliver(&channel, stream_update(text, is_final), "s1")
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(channel.texts(), vec!["Hello world!".to_string()]);
+        assert_eq!(channel.edits.load(Ordering::SeqCst), 2);
+        assert!(streams.active.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_stream_edits_respect_edit_interval() {
+        let channel = EditingChannel::editable(Duration::from_secs(60));
+        let mut streams = StreamEdits::default();
+
+        for text in ["a", "ab", "abc"] {
+            streams
+                .deliver(&channel, stream_update(text, false), "s1")
+                .await
+                .unwrap();
+        }
+        // Intermediate edits are rate limited...
+        assert_eq!(channel.texts(), vec!["a".to_string()]);
+        assert_eq!(channel.edits.load(Ordering::SeqCst), 0);
+
+        // ...but the final update always lands.
+        streams
+            .deliver(&channel, stream_update("abcd", true), "s1")
+            .await
+            .unwrap();
+        assert_eq!(channel.texts(), vec!["abcd".to_string()]);
+        assert_eq!(channel.edits.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_stream_to_non_editing_channel_sends_final_only() {
+        let channel = EditingChannel::default();
+        let mut streams = StreamEdits::default();
+
+        streams
+            .deliver(&channel, stream_update("partial", false), "s1")
+            .await
+            .unwrap();
+        streams
+            .deliver(&channel, stream_update("complete", true), "s1")
+            .await
+            .unwrap();
+
+        assert_eq!(channel.texts(), vec!["complete".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_default_edit_message_sends_new_message() {
+        let channel = MockChannel::new("test");
+        assert!(!channel.supports_edit());
+        assert!(channel
+            .edit_message("chat1", "ref", "updated")
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_dispatcher_routes_stream_updates_through_edits() {
+        let bus = Arc::new(MessageBus::new());
+        let manager = ChannelManager::new(bus.clone(), Config::default());
+        let channel = EditingChannel::editable(Duration::ZERO);
+        let messages = Arc::clone(&channel.messages);
+        manager.register(Box::new(channel)).await;
+        manager.start_all().await.unwrap();
+
+        bus.publish_outbound(stream_update("Thinking", false))
+            .await
+            .unwrap();
+        bus.publish_outbound(stream_update("Done.", true))
+            .await
+            .unwrap();
+        bus.publish_outbound(OutboundMessage::new("editing", "chat1", "separate"))
+            .await
+            .unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+
+        assert_eq!(
+            *messages.lock().unwrap(),
+            vec!["Done.".to_string(), "separate".to_string()]
+        );
+        manager.stop_all().await.unwrap();
+    }
 }
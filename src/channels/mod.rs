@@ -114,13 +114,16 @@ mod acp_protocol;
 pub mod discord;
 pub mod email_channel;
 mod factory;
+pub mod format;
 pub mod lark;
+mod liveness;
 mod manager;
 pub mod model_switch;
 #[cfg(feature = "mqtt")]
 pub mod mqtt;
 pub mod persona_switch;
 pub mod plugin;
+pub mod seen;
 #[cfg(feature = "hardware")]
 pub mod serial;
 pub mod slack;
@@ -136,6 +139,7 @@ pub use acp_http::AcpHttpChannel;
 pub use discord::DiscordChannel;
 pub use email_channel::EmailChannel;
 pub use factory::register_configured_channels;
+pub use format::{format_markdown, MarkdownDialect};
 pub use lark::LarkChannel;
 pub use manager::ChannelManager;
 #[cfg(feature = "mqtt")]
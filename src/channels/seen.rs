@@ -0,0 +1,218 @@
+//! Bounded, persisted tracking of already-processed inbound message IDs.
+//!
+//! Channels that can be redelivered the same message (IMAP reconnects,
+//! WebSocket retries) remember the IDs they handled in a [`SeenIds`]. It
+//! keeps at most `capacity` IDs, evicting the least recently seen, and can
+//! be backed by a JSON file so a restart does not reprocess recent messages.
+//! Writes are batched: [`SeenIds::maybe_flush`] only touches disk once
+//! [`FLUSH_INTERVAL`] has passed since the last write.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use tracing::warn;
+
+use crate::config::Config;
+use crate::error::Result;
+use crate::utils::fs::write_atomic;
+
+/// Default number of IDs remembered per channel.
+pub const DEFAULT_SEEN_CAPACITY: usize = 10_000;
+
+/// Minimum time between two writes by [`SeenIds::maybe_flush`].
+pub const FLUSH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// An LRU set of message IDs, optionally persisted to disk.
+pub struct SeenIds {
+    inner: Mutex<Inner>,
+}
+
+struct Inner {
+    capacity: usize,
+    /// ID → recency stamp.
+    stamps: HashMap<String, u64>,
+    /// Recency stamp → ID, oldest first.
+    order: BTreeMap<u64, String>,
+    next_stamp: u64,
+    path: Option<PathBuf>,
+    dirty: bool,
+    last_flush: Instant,
+}
+
+impl Inner {
+    fn touch(&mut self, id: &str) -> bool {
+        let stamp = self.next_stamp;
+        self.next_stamp += 1;
+        let is_new = match self.stamps.insert(id.to_string(), stamp) {
+            Some(old) => {
+                self.order.remove(&old);
+                false
+            }
+            None => true,
+        };
+        self.order.insert(stamp, id.to_string());
+        while self.stamps.len() > self.capacity {
+            let Some((_, oldest)) = self.order.pop_first() else {
+                break;
+            };
+            self.stamps.remove(&oldest);
+        }
+        self.dirty = true;
+        is_new
+    }
+
+    fn write(&mut self) -> Result<()> {
+        let Some(path) = self.path.as_ref() else {
+            return Ok(());
+        };
+        let ids: Vec<&String> = self.order.values().collect();
+        write_atomic(path, serde_json::to_string(&ids)?)?;
+        self.dirty = false;
+        self.last_flush = Instant::now();
+        Ok(())
+    }
+}
+
+impl SeenIds {
+    /// Create an in-memory set holding at most `capacity` IDs (minimum 1).
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Mutex::new(Inner {
+                capacity: capacity.max(1),
+                stamps: HashMap::new(),
+                order: BTreeMap::new(),
+                next_stamp: 0,
+                path: None,
+                dirty: false,
+                last_flush: Instant::now(),
+            }),
+        }
+    }
+
+    /// Default file for a channel: `~/.zeptoclaw/channels/<channel>_seen.json`.
+    pub fn default_path(channel: &str) -> PathBuf {
+        Config::dir()
+            .join("channels")
+            .join(format!("{}_seen.json", channel))
+    }
+
+    /// Back the set with `path`: IDs stored there are loaded (as older than
+    /// any already in memory) and later flushes write to it.
+    pub fn persist_to(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref().to_path_buf();
+        let stored: Vec<String> = match std::fs::read_to_string(&path) {
+            Ok(raw) => serde_json::from_str(&raw)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e.into()),
+        };
+        let mut inner = self.inner.lock().unwrap();
+        let current: Vec<String> = inner.order.values().cloned().collect();
+        inner.stamps.clear();
+        inner.order.clear();
+        for id in stored.iter().chain(current.iter()) {
+            inner.touch(id);
+        }
+        inner.dirty = !current.is_empty();
+        inner.path = Some(path);
+        Ok(())
+    }
+
+    /// Record `id` as seen. Returns `true` if it was not already tracked.
+    pub fn insert(&self, id: &str) -> bool {
+        self.inner.lock().unwrap().touch(id)
+    }
+
+    /// Whether `id` is tracked.
+    pub fn contains(&self, id: &str) -> bool {
+        self.inner.lock().unwrap().stamps.contains_key(id)
+    }
+
+    /// Number of tracked IDs.
+    pub fn len(&self) -> usize {
+        self.inner.lock().unwrap().stamps.len()
+    }
+
+    /// Whether no IDs are tracked.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Write pending changes to the backing file, if any.
+    pub fn flush(&self) -> Result<()> {
+        let mut inner = self.inner.lock().unwrap();
+        if !inner.dirty {
+            return Ok(());
+        }
+        inner.write()
+    }
+
+    /// Flush if there are changes and [`FLUSH_INTERVAL`] has passed since the
+    /// last write. Errors are logged, not returned.
+    pub fn maybe_flush(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.dirty && inner.path.is_some() && inner.last_flush.elapsed() >= FLUSH_INTERVAL {
+            if let Err(e) = inner.write() {
+                warn!(error = %e, "Failed to persist seen message IDs");
+            }
+        }
+    }
+}
+
+impl Default for SeenIds {
+    fn default() -> Self {
+        Self::new(DEFAULT_SEEN_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_oldest_ids_are_evicted_past_capacity() {
+        let seen = SeenIds::new(3);
+        for id in ["a", "b", "c"] {
+            assert!(seen.insert(id));
+        }
+        // Seeing "a" again makes it the most recent, so "b" goes first.
+        assert!(!seen.insert("a"));
+        assert!(seen.insert("d"));
+        assert_eq!(seen.len(), 3);
+        assert!(!seen.contains("b"));
+        assert!(seen.contains("a"));
+        assert!(seen.insert("e"));
+        assert!(!seen.contains("c"));
+        assert!(seen.insert("b"));
+    }
+
+    #[test]
+    fn test_persistence_round_trip_keeps_recency_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("channels").join("email_seen.json");
+
+        let seen = SeenIds::new(3);
+        seen.insert("early");
+        seen.persist_to(&path).unwrap();
+        seen.insert("msg-1");
+        seen.insert("msg-2");
+        seen.flush().unwrap();
+
+        // A restart does not reprocess what was already handled.
+        let restored = SeenIds::new(3);
+        restored.persist_to(&path).unwrap();
+        assert_eq!(restored.len(), 3);
+        assert!(!restored.insert("msg-2"));
+        // Loaded IDs keep their order: the oldest is evicted first.
+        assert!(restored.insert("msg-3"));
+        assert!(!restored.contains("early"));
+        assert!(restored.contains("msg-1"));
+
+        // A file from a larger-capacity run is trimmed to the newest IDs.
+        let small = SeenIds::new(1);
+        small.persist_to(&path).unwrap();
+        assert!(small.contains("msg-2"));
+        assert_eq!(small.len(), 1);
+    }
+}
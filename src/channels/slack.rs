@@ -20,9 +20,14 @@ use crate::bus::{InboundMessage, MediaAttachment, MediaType, MessageBus, Outboun
 use crate::config::SlackConfig;
 use crate::error::{Result, ZeptoError};
 
+use super::format::{format_markdown, MarkdownDialect};
 use super::{BaseChannelConfig, Channel};
 
 const SLACK_CHAT_POST_MESSAGE_URL: &str = "https://slack.com/api/chat.postMessage";
+const SLACK_CHAT_UPDATE_URL: &str = "https://slack.com/api/chat.update";
+const SLACK_AUTH_TEST_URL: &str = "https://slack.com/api/auth.test";
+/// `chat.update` is a Tier 3 method (~50 calls per minute).
+const SLACK_EDIT_INTERVAL: Duration = Duration::from_millis(1500);
 const SLACK_SOCKET_OPEN_URL: &str = "https://slack.com/api/apps.connections.open";
 const SLACK_RECONNECT_DELAY_SECS: u64 = 2;
 
@@ -135,7 +140,7 @@ impl SlackChannel {
         self.config.enabled
     }
 
-    fn build_payload(msg: &OutboundMessage) -> Result<Value> {
+    fn build_payload(&self, msg: &OutboundMessage) -> Result<Value> {
         let channel = msg.chat_id.trim();
         if channel.is_empty() {
             return Err(ZeptoError::Channel(
@@ -145,7 +150,7 @@ impl SlackChannel {
 
         let mut payload = json!({
             "channel": channel,
-            "text": msg.content,
+            "text": format_markdown(&msg.content, self.markdown_dialect()),
         });
 
         if let Some(ref reply_to) = msg.reply_to {
@@ -157,6 +162,50 @@ impl SlackChannel {
         Ok(payload)
     }
 
+    /// POST a Web API method and return the response body once `ok` is true.
+    async fn call_api(&self, url: &str, payload: &Value) -> Result<Value> {
+        let response = self
+            .client
+            .post(url)
+            .bearer_auth(&self.config.bot_token)
+            .json(payload)
+            .send()
+            .await
+            .map_err(|e| ZeptoError::Channel(format!("Failed to call Slack API: {}", e)))?;
+
+        let status = response.status();
+        let body = response.text().await.map_err(|e| {
+            ZeptoError::Channel(format!("Failed to read Slack API response: {}", e))
+        })?;
+
+        if !status.is_success() {
+            return Err(ZeptoError::Channel(format!(
+                "Slack API returned HTTP {}: {}",
+                status, body
+            )));
+        }
+
+        let body_json: Value = serde_json::from_str(&body)
+            .map_err(|e| ZeptoError::Channel(format!("Invalid Slack API response JSON: {}", e)))?;
+
+        if !body_json
+            .get("ok")
+            .and_then(Value::as_bool)
+            .unwrap_or(false)
+        {
+            let api_error = body_json
+                .get("error")
+                .and_then(Value::as_str)
+                .unwrap_or("unknown_error");
+            return Err(ZeptoError::Channel(format!(
+                "Slack API returned error: {}",
+                api_error
+            )));
+        }
+
+        Ok(body_json)
+    }
+
     async fn open_socket_mode_url(client: &reqwest::Client, app_token: &str) -> Result<String> {
         let response = client
             .post(SLACK_SOCKET_OPEN_URL)
@@ -529,6 +578,10 @@ impl Channel for SlackChannel {
     }
 
     async fn send(&self, msg: OutboundMessage) -> Result<()> {
+        self.send_with_ref(msg).await.map(|_| ())
+    }
+
+    async fn send_with_ref(&self, msg: OutboundMessage) -> Result<Option<String>> {
         if !self.running.load(Ordering::SeqCst) {
             return Err(ZeptoError::Channel("Slack channel not running".to_string()));
         }
@@ -537,51 +590,52 @@ impl Channel for SlackChannel {
             return Err(ZeptoError::Config("Slack bot token is empty".to_string()));
         }
 
-        let payload = Self::build_payload(&msg)?;
-
-        let response = self
-            .client
-            .post(SLACK_CHAT_POST_MESSAGE_URL)
-            .bearer_auth(&self.config.bot_token)
-            .json(&payload)
-            .send()
-            .await
-            .map_err(|e| ZeptoError::Channel(format!("Failed to call Slack API: {}", e)))?;
+        let payload = self.build_payload(&msg)?;
+        let body_json = self.call_api(SLACK_CHAT_POST_MESSAGE_URL, &payload).await?;
 
-        let status = response.status();
-        let body = response.text().await.map_err(|e| {
-            ZeptoError::Channel(format!("Failed to read Slack API response: {}", e))
-        })?;
+        info!("Slack: Message sent successfully");
+        Ok(body_json
+            .get("ts")
+            .and_then(Value::as_str)
+            .map(str::to_string))
+    }
 
-        if !status.is_success() {
-            return Err(ZeptoError::Channel(format!(
-                "Slack API returned HTTP {}: {}",
-                status, body
-            )));
+    async fn edit_message(&self, chat_id: &str, message_ref: &str, new_text: &str) -> Result<()> {
+        if self.config.bot_token.trim().is_empty() {
+            return Err(ZeptoError::Config("Slack bot token is empty".to_string()));
         }
 
-        let body_json: Value = serde_json::from_str(&body)
-            .map_err(|e| ZeptoError::Channel(format!("Invalid Slack API response JSON: {}", e)))?;
+        let payload = json!({
+            "channel": chat_id.trim(),
+            "ts": message_ref,
+            "text": format_markdown(new_text, self.markdown_dialect()),
+        });
+        self.call_api(SLACK_CHAT_UPDATE_URL, &payload).await?;
+        Ok(())
+    }
 
-        if !body_json
-            .get("ok")
-            .and_then(Value::as_bool)
-            .unwrap_or(false)
-        {
-            let api_error = body_json
-                .get("error")
-                .and_then(Value::as_str)
-                .unwrap_or("unknown_error");
-            return Err(ZeptoError::Channel(format!(
-                "Slack API returned error: {}",
-                api_error
-            )));
-        }
+    fn supports_edit(&self) -> bool {
+        true
+    }
 
-        info!("Slack: Message sent successfully");
+    fn edit_interval(&self) -> Duration {
+        SLACK_EDIT_INTERVAL
+    }
+
+    /// Calls `auth.test`, which fails when the token or connection is dead.
+    async fn ping(&self) -> Result<()> {
+        self.call_api(SLACK_AUTH_TEST_URL, &json!({})).await?;
         Ok(())
     }
 
+    fn supports_ping(&self) -> bool {
+        true
+    }
+
+    fn markdown_dialect(&self) -> MarkdownDialect {
+        MarkdownDialect::SlackMrkdwn
+    }
+
     fn is_running(&self) -> bool {
         self.running.load(Ordering::SeqCst)
     }
@@ -729,7 +783,8 @@ mod tests {
     #[test]
     fn test_slack_payload_with_reply() {
         let msg = OutboundMessage::new("slack", "C123", "hello").with_reply("173401.000200");
-        let payload = SlackChannel::build_payload(&msg).expect("payload should build");
+        let channel = SlackChannel::new(SlackConfig::default(), test_bus());
+        let payload = channel.build_payload(&msg).expect("payload should build");
 
         assert_eq!(payload["channel"], "C123");
         assert_eq!(payload["text"], "hello");
@@ -45,10 +45,10 @@ use tokio::sync::{mpsc, Mutex};
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, warn};
 
-use once_cell::sync::Lazy;
-use regex::Regex;
-
-use crate::bus::{InboundMessage, MediaAttachment, MediaType, MessageBus, OutboundMessage};
+use crate::bus::{
+    InboundMessage, MediaAttachment, MediaType, MessageBus, OutboundMessage, OutputAction,
+    StructuredOutput, ACTION_ID_METADATA_KEY,
+};
 use crate::config::Config;
 use crate::config::TelegramConfig;
 use crate::error::{Result, ZeptoError};
@@ -57,6 +57,8 @@ use crate::memory::longterm::LongTermMemory;
 
 /// Synthetic text used when a photo is sent without a caption.
 const BARE_PHOTO_PLACEHOLDER: &str = "Please analyze this image.";
+/// Synthetic text used when a non-image file is sent without a caption.
+const BARE_FILE_PLACEHOLDER: &str = "Please look at the attached file.";
 /// Maximum number of startup connectivity retries before giving up.
 const MAX_STARTUP_RETRIES: u32 = 10;
 /// Base delay (in seconds) for exponential backoff on startup retries.
@@ -64,6 +66,7 @@ const BASE_RETRY_DELAY_SECS: u64 = 2;
 /// Maximum delay (in seconds) for exponential backoff on startup retries.
 const MAX_RETRY_DELAY_SECS: u64 = 120;
 
+use super::format::{format_markdown, MarkdownDialect};
 use super::model_switch::{
     format_current_model, format_model_list, hydrate_overrides, new_override_store,
     parse_model_command, persist_single, remove_single, ModelCommand, ModelOverrideStore,
@@ -103,78 +106,14 @@ struct OverridesDep {
     typing_gen: Arc<std::sync::atomic::AtomicU64>,
 }
 
-// ---------------------------------------------------------------------------
-// Markdown → Telegram HTML conversion
-// ---------------------------------------------------------------------------
-//
-// Telegram's HTML mode supports a small subset of tags: <b>, <i>, <code>,
-// <pre>, <a href="">, <tg-spoiler>.  Claude emits standard Markdown, so we
-// convert the most common constructs before sending.
-//
-// Strategy: extract code regions into NUL-byte placeholders first (so their
-// content is never touched by Markdown regex), process everything else, then
-// reinsert code with its own HTML escaping.
-
-static RE_FENCED_CODE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?s)```[^\n]*\n(.*?)```").unwrap());
-static RE_INLINE_CODE: Lazy<Regex> = Lazy::new(|| Regex::new(r"`([^`\n]+)`").unwrap());
-// Bold+italic combined (***text***) must be matched before bold and italic.
-static RE_BOLD_ITALIC: Lazy<Regex> = Lazy::new(|| Regex::new(r"\*\*\*(.+?)\*\*\*").unwrap());
-static RE_BOLD: Lazy<Regex> = Lazy::new(|| Regex::new(r"\*\*(.+?)\*\*").unwrap());
-// Italic is applied after bold conversion has consumed all ** pairs, so
-// remaining single * delimiters are safe to match without lookbehind.
-static RE_ITALIC: Lazy<Regex> = Lazy::new(|| Regex::new(r"\*([^\*\n]+?)\*").unwrap());
-// Underscore-style italic: _text_ — matched at word boundaries.
-// Uses `(?:^|[\s])` as a pseudo-lookbehind to avoid snake_case.
-static RE_ITALIC_UNDERSCORE: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r#"(?:^|(?P<pre>\s))_(?P<body>[^_\n]+?)_(?P<suf>[\s.,;:!?]|$)"#).unwrap()
-});
-static RE_STRIKETHROUGH: Lazy<Regex> = Lazy::new(|| Regex::new(r"~~(.+?)~~").unwrap());
-static RE_LINK: Lazy<Regex> = Lazy::new(|| Regex::new(r"\[([^\]]+)\]\(([^)]+)\)").unwrap());
-static RE_SPOILER: Lazy<Regex> = Lazy::new(|| Regex::new(r"\|\|(.+?)\|\|").unwrap());
-static RE_HEADER: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?m)^#{1,6}\s+(.+)$").unwrap());
-static RE_BULLET: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?m)^[ \t]*[-*]\s+").unwrap());
-static RE_NUMBERED_LIST: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?m)^[ \t]*\d+\.\s+").unwrap());
-static RE_BLOCKQUOTE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?m)^&gt;\s?(.*)$").unwrap());
-static RE_HR: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?m)^-{3,}\s*$").unwrap());
-
-fn escape_html(s: &str) -> String {
-    s.replace('&', "&amp;")
-        .replace('<', "&lt;")
-        .replace('>', "&gt;")
-}
-
-/// Validate that HTML tags are properly nested (no crossing tags).
-/// Returns `true` when the tag structure is well-formed.
-fn html_tags_valid(html: &str) -> bool {
-    static RE_TAG: Lazy<Regex> = Lazy::new(|| Regex::new(r"<(/?)(\w[\w-]*)(?:\s[^>]*)?>").unwrap());
-    let mut stack: Vec<String> = Vec::new();
-    for caps in RE_TAG.captures_iter(html) {
-        let closing = &caps[1] == "/";
-        let tag = caps[2].to_lowercase();
-        if closing {
-            if stack.last().map(|s| s.as_str()) != Some(tag.as_str()) {
-                return false;
-            }
-            stack.pop();
-        } else {
-            stack.push(tag);
-        }
-    }
-    stack.is_empty()
-}
-
-/// Strip all HTML tags, restoring a plain-text representation.
-fn strip_html_tags(html: &str) -> String {
-    static RE_STRIP: Lazy<Regex> = Lazy::new(|| Regex::new(r"<[^>]+>").unwrap());
-    let text = RE_STRIP.replace_all(html, "");
-    // Unescape entities we added so the user sees normal characters.
-    text.replace("&amp;", "&")
-        .replace("&lt;", "<")
-        .replace("&gt;", ">")
-}
-
 /// Telegram's maximum message length in UTF-16 code units.
 const TELEGRAM_MAX_MESSAGE_LEN: usize = 4096;
+/// Telegram's maximum `callback_data` size for inline keyboard buttons, in bytes.
+const TELEGRAM_MAX_CALLBACK_DATA_LEN: usize = 64;
+/// Buttons per inline keyboard row.
+const INLINE_KEYBOARD_ROW_LEN: usize = 3;
+/// Separates the action ID from its value in `callback_data`.
+const CALLBACK_DATA_SEPARATOR: char = '\u{1f}';
 
 /// Split a message into chunks that fit within `max_len` UTF-16 code units.
 /// Tries to break at paragraph boundaries (`\n\n`), then line boundaries (`\n`),
@@ -222,100 +161,95 @@ fn chunk_message(text: &str, max_len: usize) -> Vec<String> {
     chunks
 }
 
-fn render_telegram_html(content: &str) -> String {
-    // Phase 1: Extract fenced code blocks into placeholders.
-    let mut code_blocks: Vec<String> = Vec::new();
-    let mut text = RE_FENCED_CODE
-        .replace_all(content, |caps: &regex::Captures| {
-            let idx = code_blocks.len();
-            let body = caps.get(1).map_or("", |m| m.as_str());
-            code_blocks.push(body.to_string());
-            format!("\x00CODEBLOCK{idx}\x00")
-        })
-        .into_owned();
-
-    // Phase 2: Extract inline code into placeholders.
-    let mut inline_codes: Vec<String> = Vec::new();
-    text = RE_INLINE_CODE
-        .replace_all(&text, |caps: &regex::Captures| {
-            let idx = inline_codes.len();
-            inline_codes.push(caps[1].to_string());
-            format!("\x00INLINE{idx}\x00")
-        })
-        .into_owned();
-
-    // Phase 3: Escape HTML entities in the remaining text.
-    text = escape_html(&text);
-
-    // Phase 3b: Restore Telegram-native HTML tags that Claude may emit
-    // directly (there is no markdown equivalent for these).
-    text = text
-        .replace("&lt;u&gt;", "<u>")
-        .replace("&lt;/u&gt;", "</u>")
-        .replace("&lt;ins&gt;", "<u>")
-        .replace("&lt;/ins&gt;", "</u>");
-
-    // Phase 4: Block-level conversions.
-    text = RE_HR.replace_all(&text, "").into_owned();
-    text = RE_HEADER.replace_all(&text, "<b>$1</b>\n").into_owned();
-    text = RE_BLOCKQUOTE
-        .replace_all(&text, "<blockquote>$1</blockquote>")
-        .into_owned();
-    text = RE_BULLET.replace_all(&text, "• ").into_owned();
-    text = RE_NUMBERED_LIST
-        .replace_all(&text, |caps: &regex::Captures| {
-            // Preserve the original number prefix but strip the markdown indent.
-            let m = caps.get(0).unwrap().as_str().trim_start();
-            m.to_string()
-        })
-        .into_owned();
-
-    // Phase 5: Inline conversions (bold+italic before bold before italic).
-    text = RE_BOLD_ITALIC
-        .replace_all(&text, "<b><i>$1</i></b>")
-        .into_owned();
-    text = RE_BOLD.replace_all(&text, "<b>$1</b>").into_owned();
-    text = RE_ITALIC.replace_all(&text, "<i>$1</i>").into_owned();
-    text = RE_ITALIC_UNDERSCORE
-        .replace_all(&text, |caps: &regex::Captures| {
-            let pre = caps.name("pre").map_or("", |m| m.as_str());
-            let body = &caps["body"];
-            let suf = caps.name("suf").map_or("", |m| m.as_str());
-            format!("{pre}<i>{body}</i>{suf}")
-        })
-        .into_owned();
-    text = RE_STRIKETHROUGH
-        .replace_all(&text, "<s>$1</s>")
-        .into_owned();
-    text = RE_LINK
-        .replace_all(&text, |caps: &regex::Captures| {
-            format!("<a href=\"{}\">{}</a>", &caps[2], &caps[1])
-        })
-        .into_owned();
+/// Markdown for an outbound message: the structured title (if any) in bold,
+/// then the content, then structured fields as `**Label:** value` lines.
+fn structured_markdown(msg: &OutboundMessage) -> String {
+    let Some(structured) = &msg.structured else {
+        return msg.content.clone();
+    };
+    let mut parts = Vec::new();
+    if let Some(title) = &structured.title {
+        parts.push(format!("**{}**", title));
+    }
+    if !msg.content.trim().is_empty() {
+        parts.push(msg.content.clone());
+    }
+    if !structured.fields.is_empty() {
+        let fields: Vec<String> = structured
+            .fields
+            .iter()
+            .map(|f| format!("**{}:** {}", f.label, f.value))
+            .collect();
+        parts.push(fields.join("\n"));
+    }
+    parts.join("\n\n")
+}
 
-    // Phase 6: Spoilers.
-    text = RE_SPOILER
-        .replace_all(&text, "<tg-spoiler>$1</tg-spoiler>")
-        .into_owned();
+/// Encode an action as button `callback_data`: `id`, separator, `value`.
+///
+/// Telegram caps callback data at 64 bytes; when both do not fit, only the
+/// (truncated) action ID is sent and the click is reported with the ID as
+/// its value.
+fn encode_callback_data(action: &OutputAction) -> String {
+    let full = format!("{}{}{}", action.id, CALLBACK_DATA_SEPARATOR, action.value);
+    if full.len() <= TELEGRAM_MAX_CALLBACK_DATA_LEN {
+        return full;
+    }
+    let mut end = action.id.len().min(TELEGRAM_MAX_CALLBACK_DATA_LEN);
+    while !action.id.is_char_boundary(end) {
+        end -= 1;
+    }
+    action.id[..end].to_string()
+}
 
-    // Phase 7: Reinsert code blocks with their own HTML escaping.
-    for (idx, block) in code_blocks.iter().enumerate() {
-        let tag = format!("<pre>{}</pre>", escape_html(block.trim_end()));
-        text = text.replace(&format!("\x00CODEBLOCK{idx}\x00"), &tag);
-    }
-    for (idx, code) in inline_codes.iter().enumerate() {
-        let tag = format!("<code>{}</code>", escape_html(code));
-        text = text.replace(&format!("\x00INLINE{idx}\x00"), &tag);
-    }
+/// Build the inline keyboard for a structured output's actions, or `None`
+/// when it has no actions.
+fn build_inline_keyboard(
+    structured: &StructuredOutput,
+) -> Option<teloxide::types::InlineKeyboardMarkup> {
+    use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup};
 
-    // Safety net: if regex substitutions produced crossing tags (e.g. bold
-    // wrapping an italic that extends beyond it), fall back to plain text so
-    // Telegram doesn't reject the message outright.
-    if !html_tags_valid(&text) {
-        return strip_html_tags(&text);
+    if structured.actions.is_empty() {
+        return None;
     }
+    let rows: Vec<Vec<InlineKeyboardButton>> = structured
+        .actions
+        .chunks(INLINE_KEYBOARD_ROW_LEN)
+        .map(|row| {
+            row.iter()
+                .map(|a| InlineKeyboardButton::callback(a.label.clone(), encode_callback_data(a)))
+                .collect()
+        })
+        .collect();
+    Some(InlineKeyboardMarkup::new(rows))
+}
 
-    text
+/// Convert a pressed inline keyboard button into the user's reply on the bus.
+///
+/// The content is the button's value (e.g. "approve"), so text-based flows
+/// such as approvals handle clicks and typed replies alike. Forum topic
+/// clicks keep the per-topic session like regular messages.
+fn callback_to_inbound(
+    user_id: &str,
+    chat_id: &str,
+    thread_id: Option<&str>,
+    data: &str,
+) -> Option<InboundMessage> {
+    let (action_id, value) = match data.split_once(CALLBACK_DATA_SEPARATOR) {
+        Some((id, value)) if !value.is_empty() => (id, value),
+        Some((id, _)) => (id, id),
+        None => (data, data),
+    };
+    if action_id.is_empty() {
+        return None;
+    }
+    let mut inbound = InboundMessage::new("telegram", user_id, chat_id, value)
+        .with_metadata(ACTION_ID_METADATA_KEY, action_id);
+    if let Some(tid) = thread_id {
+        inbound.session_key = format!("telegram:{}:{}", chat_id, tid);
+        inbound = inbound.with_metadata("telegram_thread_id", tid);
+    }
+    Some(inbound)
 }
 
 fn is_numeric_allowlist_entry(entry: &str) -> bool {
@@ -417,6 +351,86 @@ async fn download_telegram_photo(
     )
 }
 
+/// Downloads a non-image document from Telegram's file API.
+///
+/// Returns `None` (with a `warn!` log) on any failure, including files over
+/// the upload size limit. Telegram reports the size up front, so oversized
+/// files are rejected before downloading.
+async fn download_telegram_document(
+    bot: &teloxide::Bot,
+    doc: &teloxide::types::Document,
+    http_client: &reqwest::Client,
+) -> Option<MediaAttachment> {
+    use crate::session::uploads::MAX_UPLOAD_SIZE;
+    use teloxide::prelude::Requester;
+
+    let name = doc.file_name.as_deref().unwrap_or("file");
+    if doc.file.size as usize > MAX_UPLOAD_SIZE {
+        warn!(
+            "Telegram document {} too large: {} bytes",
+            name, doc.file.size
+        );
+        return None;
+    }
+
+    let file = match tokio::time::timeout(
+        Duration::from_secs(15),
+        bot.get_file(doc.file.id.clone()),
+    )
+    .await
+    {
+        Ok(Ok(f)) => f,
+        Ok(Err(e)) => {
+            warn!("Failed to get Telegram file info for {}: {}", name, e);
+            return None;
+        }
+        Err(_) => {
+            warn!("Telegram get_file timed out after 15s for {}", name);
+            return None;
+        }
+    };
+
+    if file.path.is_empty() {
+        warn!("Telegram file path is empty for document {}", name);
+        return None;
+    }
+
+    let download_url = format!(
+        "https://api.telegram.org/file/bot{}/{}",
+        bot.token(),
+        file.path
+    );
+    let bytes = match http_client.get(&download_url).send().await {
+        Ok(resp) => match resp.bytes().await {
+            Ok(b) => b,
+            Err(e) => {
+                warn!("Failed to read Telegram document {}: {}", name, e);
+                return None;
+            }
+        },
+        Err(e) => {
+            warn!("Failed to download Telegram document {}: {}", name, e);
+            return None;
+        }
+    };
+    if bytes.len() > MAX_UPLOAD_SIZE {
+        warn!(
+            "Telegram document {} too large: {} bytes",
+            name,
+            bytes.len()
+        );
+        return None;
+    }
+
+    let mut media = MediaAttachment::new(MediaType::Document)
+        .with_data(bytes.to_vec())
+        .with_filename(name);
+    if let Some(mime) = doc.mime_type.as_ref() {
+        media = media.with_mime_type(mime.as_ref());
+    }
+    Some(media)
+}
+
 /// Telegram channel implementation using teloxide.
 ///
 /// This channel connects to Telegram's Bot API to receive and send messages.
@@ -731,7 +745,7 @@ impl Channel for TelegramChannel {
 
                 // Create the handler for incoming messages
                 // Note: dptree injects dependencies separately, not as tuples
-                let handler =
+                let message_handler =
                     Update::filter_message().endpoint(
                         |bot: Bot,
                          msg: Message,
@@ -857,10 +871,17 @@ impl Channel for TelegramChannel {
                                 .map(|m| m.as_ref().starts_with("image/"))
                                 .unwrap_or(false);
                             let has_image = has_photo || has_image_doc;
+                            let has_file = msg.document().is_some() && !has_image_doc;
 
                             if let Some(text) = msg.text()
                                 .or_else(|| msg.caption())
-                                .or(if has_image { Some(BARE_PHOTO_PLACEHOLDER) } else { None })
+                                .or(if has_image {
+                                    Some(BARE_PHOTO_PLACEHOLDER)
+                                } else if has_file {
+                                    Some(BARE_FILE_PLACEHOLDER)
+                                } else {
+                                    None
+                                })
                             {
                                 let chat_id = msg.chat.id.0.to_string();
                                 let chat_id_num = msg.chat.id.0;
@@ -1139,6 +1160,16 @@ impl Channel for TelegramChannel {
                                         .with_metadata("persona_override", &persona_value);
                                 }
 
+                                // Telegram reports the client's UI language; the
+                                // agent uses it as the conversation locale.
+                                if let Some(lang) = user.and_then(|u| u.language_code.as_deref())
+                                {
+                                    inbound = inbound.with_metadata(
+                                        crate::agent::locale::LOCALE_METADATA_KEY,
+                                        lang,
+                                    );
+                                }
+
                                 // Download image attachment (photo or image document)
                                 let mut image_ok = !has_image;
                                 if let Some(photos) = msg.photo() {
@@ -1163,6 +1194,23 @@ impl Channel for TelegramChannel {
                                     }
                                 }
 
+                                // Download other documents so the agent can save
+                                // them into the workspace for file tools.
+                                if has_file {
+                                    if let Some(doc) = msg.document() {
+                                        match download_telegram_document(&bot, doc, &http_client).await {
+                                            Some(media) => inbound = inbound.with_media(media),
+                                            None => {
+                                                let name = doc.file_name.as_deref().unwrap_or("file");
+                                                inbound.content.push_str(&format!(
+                                                    "\n\n[Attachment: {} (download failed)]",
+                                                    name
+                                                ));
+                                            }
+                                        }
+                                    }
+                                }
+
                                 if !image_ok && has_image {
                                     let req = bot.send_message(
                                         teloxide::types::ChatId(chat_id_num),
@@ -1196,6 +1244,71 @@ impl Channel for TelegramChannel {
                         },
                     );
 
+                // Inline keyboard button presses arrive as callback queries.
+                let callback_handler = Update::filter_callback_query().endpoint(
+                    |bot: Bot,
+                     query: CallbackQuery,
+                     bus: Arc<MessageBus>,
+                     Allowlist(allowlist): Allowlist,
+                     AllowUsernames(allow_usernames): AllowUsernames,
+                     deny_by_default: bool| async move {
+                        // Answer right away so the client clears the loading spinner.
+                        if let Err(e) = bot.answer_callback_query(query.id.clone()).await {
+                            debug!("Failed to answer Telegram callback query: {}", e);
+                        }
+
+                        let user_id = query.from.id.0.to_string();
+                        let username = query.from.username.clone().unwrap_or_default();
+                        let allowed = if allowlist.is_empty() {
+                            !deny_by_default
+                        } else {
+                            telegram_allowlist_allows(
+                                &allowlist,
+                                &user_id,
+                                &username,
+                                allow_usernames,
+                            )
+                        };
+                        if !allowed {
+                            info!("Telegram: Ignoring button press from user {}", user_id);
+                            return Ok(());
+                        }
+
+                        let (Some(message), Some(data)) =
+                            (query.message.as_ref(), query.data.as_deref())
+                        else {
+                            return Ok(());
+                        };
+                        let chat_id = message.chat().id.0.to_string();
+                        let thread_id = message
+                            .regular_message()
+                            .and_then(|m| m.thread_id)
+                            .map(|t| t.0 .0.to_string());
+                        if let Some(inbound) =
+                            callback_to_inbound(&user_id, &chat_id, thread_id.as_deref(), data)
+                        {
+                            info!(
+                                "Telegram: Button {} pressed by user {} in chat {}",
+                                inbound
+                                    .metadata
+                                    .get(ACTION_ID_METADATA_KEY)
+                                    .map(String::as_str)
+                                    .unwrap_or_default(),
+                                user_id,
+                                chat_id
+                            );
+                            if let Err(e) = bus.publish_inbound(inbound).await {
+                                error!("Failed to publish button press to bus: {}", e);
+                            }
+                        }
+                        Ok::<(), Box<dyn std::error::Error + Send + Sync>>(())
+                    },
+                );
+
+                let handler = dptree::entry()
+                    .branch(message_handler)
+                    .branch(callback_handler);
+
                 // Build the dispatcher with dependencies
                 let mut dispatcher = Dispatcher::builder(bot, handler)
                     .dependencies(dptree::deps![
@@ -1282,6 +1395,10 @@ impl Channel for TelegramChannel {
     /// - The chat_id cannot be parsed as an integer
     /// - The Telegram API request fails
     async fn send(&self, msg: OutboundMessage) -> Result<()> {
+        self.send_with_ref(msg).await.map(|_| ())
+    }
+
+    async fn send_with_ref(&self, msg: OutboundMessage) -> Result<Option<String>> {
         use teloxide::prelude::*;
         use teloxide::types::{ChatId, MessageId, ParseMode, ReactionType, ReplyParameters};
 
@@ -1316,8 +1433,9 @@ impl Channel for TelegramChannel {
             .as_ref()
             .ok_or_else(|| ZeptoError::Channel("Telegram bot not initialized".to_string()))?;
 
-        let rendered = render_telegram_html(&msg.content);
+        let rendered = format_markdown(&structured_markdown(&msg), self.markdown_dialect());
         let chunks = chunk_message(&rendered, TELEGRAM_MAX_MESSAGE_LEN);
+        let keyboard = msg.structured.as_ref().and_then(build_inline_keyboard);
 
         let thread_id: Option<i32> = msg
             .metadata
@@ -1330,6 +1448,7 @@ impl Channel for TelegramChannel {
             .or(msg.metadata.get("telegram_message_id").map(|s| s.as_str()))
             .and_then(|s| s.parse().ok());
 
+        let mut sent_ids = Vec::with_capacity(chunks.len());
         for (i, chunk) in chunks.iter().enumerate() {
             let mut req = bot
                 .send_message(ChatId(chat_id), chunk.clone())
@@ -1349,31 +1468,42 @@ impl Channel for TelegramChannel {
                 }
             }
 
-            if let Err(e) = req.await {
-                error!(
-                    "Failed to send Telegram chunk {}/{}: {}",
-                    i + 1,
-                    chunks.len(),
-                    e
-                );
-                // Send a plain-text error fallback so the user knows something went wrong.
-                let fallback = format!(
-                    "[Error: message could not be delivered (part {}/{}). Try asking for a shorter response.]",
-                    i + 1,
-                    chunks.len()
-                );
-                let mut fallback_req = bot.send_message(ChatId(chat_id), fallback);
-                if let Some(tid) = thread_id {
-                    fallback_req = fallback_req.message_thread_id(teloxide::types::ThreadId(
-                        teloxide::types::MessageId(tid),
-                    ));
+            // Buttons go under the last chunk, after the full text.
+            if i + 1 == chunks.len() {
+                if let Some(keyboard) = &keyboard {
+                    req = req.reply_markup(keyboard.clone());
                 }
-                let _ = fallback_req.await;
-                return Err(ZeptoError::Channel(format!(
-                    "Failed to send Telegram message: {}",
-                    e
-                )));
             }
+
+            let e = match req.await {
+                Ok(sent) => {
+                    sent_ids.push(sent.id.0);
+                    continue;
+                }
+                Err(e) => e,
+            };
+            error!(
+                "Failed to send Telegram chunk {}/{}: {}",
+                i + 1,
+                chunks.len(),
+                e
+            );
+            // Send a plain-text error fallback so the user knows something went wrong.
+            let fallback = format!(
+                "[Error: message could not be delivered (part {}/{}). Try asking for a shorter response.]",
+                i + 1,
+                chunks.len()
+            );
+            let mut fallback_req = bot.send_message(ChatId(chat_id), fallback);
+            if let Some(tid) = thread_id {
+                fallback_req = fallback_req
+                    .message_thread_id(teloxide::types::ThreadId(teloxide::types::MessageId(tid)));
+            }
+            let _ = fallback_req.await;
+            return Err(ZeptoError::Channel(format!(
+                "Failed to send Telegram message: {}",
+                e
+            )));
         }
 
         // Replace 👀 with ✅ now that the reply was sent successfully.
@@ -1394,7 +1524,88 @@ impl Channel for TelegramChannel {
         }
 
         info!("Telegram: Message sent successfully to chat {}", chat_id);
-        Ok(())
+        // A reply split over several messages cannot be edited as one.
+        Ok(match sent_ids.as_slice() {
+            [id] => Some(id.to_string()),
+            _ => None,
+        })
+    }
+
+    async fn edit_message(&self, chat_id: &str, message_ref: &str, new_text: &str) -> Result<()> {
+        use teloxide::prelude::*;
+        use teloxide::types::{ChatId, MessageId, ParseMode};
+
+        let chat: i64 = chat_id
+            .parse()
+            .map_err(|_| ZeptoError::Channel(format!("Invalid Telegram chat ID: {}", chat_id)))?;
+        let message_id: i32 = message_ref.parse().map_err(|_| {
+            ZeptoError::Channel(format!("Invalid Telegram message ID: {}", message_ref))
+        })?;
+        let bot = self
+            .bot
+            .as_ref()
+            .ok_or_else(|| ZeptoError::Channel("Telegram bot not initialized".to_string()))?;
+
+        let rendered = format_markdown(new_text, self.markdown_dialect());
+        if rendered.chars().count() > TELEGRAM_MAX_MESSAGE_LEN {
+            return Err(ZeptoError::Channel(
+                "Telegram edit exceeds the message length limit".to_string(),
+            ));
+        }
+
+        // Visible text replaces the typing indicator.
+        let thread_prefix = format!("{}:", chat);
+        self.typing_indicators.retain(|key, (_, token)| {
+            let same_chat = key == chat_id || key.starts_with(&thread_prefix);
+            if same_chat {
+                token.cancel();
+            }
+            !same_chat
+        });
+
+        match bot
+            .edit_message_text(ChatId(chat), MessageId(message_id), rendered)
+            .parse_mode(ParseMode::Html)
+            .await
+        {
+            Ok(_) => Ok(()),
+            // Editing to identical text is a no-op, not a failure.
+            Err(e) if e.to_string().contains("message is not modified") => Ok(()),
+            Err(e) => Err(ZeptoError::Channel(format!(
+                "Failed to edit Telegram message: {}",
+                e
+            ))),
+        }
+    }
+
+    /// Sent with `parse_mode=HTML`. Telegram stays on HTML rather than
+    /// [`MarkdownDialect::TelegramMarkdownV2`] because an unescaped reserved
+    /// character fails the whole MarkdownV2 request, while HTML only needs
+    /// `<`, `>` and `&` escaped.
+    fn markdown_dialect(&self) -> MarkdownDialect {
+        MarkdownDialect::TelegramHtml
+    }
+
+    fn supports_edit(&self) -> bool {
+        true
+    }
+
+    /// Calls `getMe`, which fails when the token or connection is dead.
+    async fn ping(&self) -> Result<()> {
+        use teloxide::prelude::*;
+
+        let bot = self
+            .bot
+            .as_ref()
+            .ok_or_else(|| ZeptoError::Channel("Telegram bot not initialized".to_string()))?;
+        bot.get_me()
+            .await
+            .map(|_| ())
+            .map_err(|e| ZeptoError::Channel(format!("Telegram getMe failed: {}", e)))
+    }
+
+    fn supports_ping(&self) -> bool {
+        true
     }
 
     /// Returns whether the channel is currently running.
@@ -1413,6 +1624,7 @@ impl Channel for TelegramChannel {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::channels::format::{html_tags_valid, render_telegram_html, strip_html_tags};
 
     #[test]
     fn test_telegram_channel_creation() {
@@ -2122,4 +2334,92 @@ mod tests {
         assert_eq!(handler_key_threaded, send_key_threaded);
         assert_eq!(handler_key_plain, send_key_plain);
     }
+
+    #[test]
+    fn test_inline_keyboard_from_structured_tool_output() {
+        use crate::bus::ActionStyle;
+        use crate::tools::ToolOutput;
+
+        let output = ToolOutput::user_visible("Deploy `api` to production?").with_structured(
+            StructuredOutput::new()
+                .with_title("Approval required")
+                .with_field("Command", "make deploy")
+                .with_action(
+                    OutputAction::new("approve", "Approve", "approve")
+                        .with_style(ActionStyle::Primary),
+                )
+                .with_action(
+                    OutputAction::new("reject", "Reject", "reject").with_style(ActionStyle::Danger),
+                ),
+        );
+        let mut msg = OutboundMessage::new("telegram", "42", output.for_user.as_deref().unwrap());
+        msg.structured = output.structured.clone();
+
+        assert_eq!(
+            structured_markdown(&msg),
+            "**Approval required**\n\nDeploy `api` to production?\n\n**Command:** make deploy"
+        );
+
+        let keyboard = build_inline_keyboard(msg.structured.as_ref().unwrap()).unwrap();
+        let json = serde_json::to_value(&keyboard).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "inline_keyboard": [[
+                    { "text": "Approve", "callback_data": "approve\u{1f}approve" },
+                    { "text": "Reject", "callback_data": "reject\u{1f}reject" }
+                ]]
+            })
+        );
+        assert!(build_inline_keyboard(&StructuredOutput::new()).is_none());
+    }
+
+    #[test]
+    fn test_inline_keyboard_wraps_rows_and_caps_callback_data() {
+        let mut structured = StructuredOutput::new();
+        for i in 0..4 {
+            structured = structured.with_action(OutputAction::new(
+                format!("opt{}", i),
+                format!("Option {}", i),
+                "x".repeat(80),
+            ));
+        }
+        let json = serde_json::to_value(build_inline_keyboard(&structured).unwrap()).unwrap();
+        let rows = json["inline_keyboard"].as_array().unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].as_array().unwrap().len(), INLINE_KEYBOARD_ROW_LEN);
+        // Value too long for the 64-byte limit: only the action ID is sent.
+        assert_eq!(rows[1][0]["callback_data"], "opt3");
+    }
+
+    #[test]
+    fn test_callback_maps_to_inbound_action() {
+        let inbound = callback_to_inbound("7", "-100123", None, "approve\u{1f}yes").unwrap();
+        assert_eq!(inbound.channel, "telegram");
+        assert_eq!(inbound.sender_id, "7");
+        assert_eq!(inbound.chat_id, "-100123");
+        assert_eq!(inbound.session_key, "telegram:-100123");
+        assert_eq!(inbound.content, "yes");
+        assert_eq!(
+            inbound
+                .metadata
+                .get(ACTION_ID_METADATA_KEY)
+                .map(String::as_str),
+            Some("approve")
+        );
+
+        // ID-only data (value did not fit) and forum topics
+        let inbound = callback_to_inbound("7", "-100123", Some("9"), "refresh").unwrap();
+        assert_eq!(inbound.content, "refresh");
+        assert_eq!(inbound.session_key, "telegram:-100123:9");
+        assert_eq!(
+            inbound
+                .metadata
+                .get("telegram_thread_id")
+                .map(String::as_str),
+            Some("9")
+        );
+
+        assert!(callback_to_inbound("7", "-100123", None, "").is_none());
+    }
 }
@@ -3,10 +3,14 @@
 //! This module defines the `Channel` trait that all communication channels
 //! (Telegram, Discord, Slack, etc.) must implement, along with supporting types.
 
+use std::time::Duration;
+
 use async_trait::async_trait;
 
+use super::format::MarkdownDialect;
 use crate::bus::OutboundMessage;
 use crate::error::Result;
+use crate::health::HealthRegistry;
 
 /// The `Channel` trait defines the interface for all communication channels.
 ///
@@ -94,9 +98,73 @@ pub trait Channel: Send + Sync {
     /// invalid chat ID, rate limiting, etc.).
     async fn send(&self, msg: OutboundMessage) -> Result<()>;
 
+    /// Sends a message and returns a reference to it for later
+    /// [`edit_message`](Channel::edit_message) calls.
+    ///
+    /// Returns `None` when the platform gave no editable reference (e.g. the
+    /// text was split across several messages). The default implementation
+    /// delegates to [`send`](Channel::send) and returns `None`.
+    async fn send_with_ref(&self, msg: OutboundMessage) -> Result<Option<String>> {
+        self.send(msg).await?;
+        Ok(None)
+    }
+
+    /// Replaces the text of a previously sent message.
+    ///
+    /// # Arguments
+    ///
+    /// * `chat_id` - The conversation the message was sent to
+    /// * `message_ref` - Reference returned by [`send_with_ref`](Channel::send_with_ref)
+    /// * `new_text` - The full replacement text
+    ///
+    /// The default implementation cannot edit and sends `new_text` as a new
+    /// message instead.
+    async fn edit_message(&self, chat_id: &str, message_ref: &str, new_text: &str) -> Result<()> {
+        let _ = message_ref;
+        self.send(OutboundMessage::new(self.name(), chat_id, new_text))
+            .await
+    }
+
+    /// Whether [`edit_message`](Channel::edit_message) edits in place.
+    ///
+    /// Streamed replies are only delivered progressively to channels that
+    /// return `true`; others receive the final text once.
+    fn supports_edit(&self) -> bool {
+        false
+    }
+
+    /// Minimum time between edits of the same message, to stay within the
+    /// platform's edit rate limit.
+    fn edit_interval(&self) -> Duration {
+        Duration::from_secs(1)
+    }
+
+    /// Markup dialect outgoing text is converted to with
+    /// [`format_markdown`](super::format::format_markdown).
+    ///
+    /// Defaults to standard Markdown, which is sent unchanged.
+    fn markdown_dialect(&self) -> MarkdownDialect {
+        MarkdownDialect::Markdown
+    }
+
     /// Returns whether the channel is currently running and accepting messages.
     fn is_running(&self) -> bool;
 
+    /// Verifies the platform connection with a lightweight API call.
+    ///
+    /// The channel manager calls this periodically on channels that return
+    /// `true` from [`supports_ping`](Channel::supports_ping) and reconnects
+    /// the channel when pings keep failing, catching connections that died
+    /// while [`is_running`](Channel::is_running) still reports `true`.
+    async fn ping(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Whether [`ping`](Channel::ping) performs a real liveness check.
+    fn supports_ping(&self) -> bool {
+        false
+    }
+
     /// Checks if a user is allowed to use this channel.
     ///
     /// # Arguments
@@ -107,6 +175,12 @@ pub trait Channel: Send + Sync {
     ///
     /// `true` if the user is allowed, `false` otherwise.
     fn is_allowed(&self, user_id: &str) -> bool;
+
+    /// Hands the channel the shared health registry before it is started.
+    ///
+    /// Channels that manage their own reconnect loops can use it to report
+    /// `Degraded`/`Down` states. The default implementation ignores it.
+    fn set_health_registry(&mut self, _registry: HealthRegistry) {}
 }
 
 /// Base configuration shared by all channels.
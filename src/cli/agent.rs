@@ -293,7 +293,7 @@ pub(crate) async fn cmd_agent(
                                 eprintln!("{}", format_cli_error(&e));
                                 std::process::exit(1);
                             }
-                            StreamEvent::ToolCalls(_) => {}
+                            StreamEvent::ToolCalls(_) | StreamEvent::ToolCallReady(_) => {}
                         }
                     }
                     println!(); // newline after streaming
@@ -660,7 +660,7 @@ pub(crate) async fn cmd_agent(
                                 StreamEvent::Error(e) => {
                                     eprintln!("{}", format_cli_error(&e));
                                 }
-                                StreamEvent::ToolCalls(_) => {}
+                                StreamEvent::ToolCalls(_) | StreamEvent::ToolCallReady(_) => {}
                             }
                         }
                         println!();
@@ -768,7 +768,7 @@ pub(crate) async fn cmd_agent_stdin() -> Result<()> {
 }
 
 /// Format agent errors with actionable guidance for CLI users.
-fn format_cli_error(e: &dyn std::fmt::Display) -> String {
+pub(crate) fn format_cli_error(e: &dyn std::fmt::Display) -> String {
     let msg = e.to_string();
 
     if msg.contains("Authentication error") {
@@ -0,0 +1,416 @@
+//! One-shot `ask` command handler.
+//!
+//! Sends a single prompt straight to the configured provider chain (no tools,
+//! no session history) and prints the answer. Text deltas are streamed to
+//! stdout as they arrive when the provider supports streaming; otherwise the
+//! command falls back to a regular buffered `chat()` call.
+
+use std::io::{self, Write};
+
+use anyhow::{Context, Result};
+use serde_json::json;
+
+use zeptoclaw::config::Config;
+use zeptoclaw::providers::{ChatOptions, LLMProvider, StreamEvent, Usage};
+use zeptoclaw::session::Message;
+
+use super::agent::format_cli_error;
+
+/// Final answer assembled from a provider call.
+#[derive(Debug)]
+pub(crate) struct AskAnswer {
+    /// Full response text.
+    pub content: String,
+    /// Token usage reported by the provider, if any.
+    pub usage: Option<Usage>,
+    /// Whether the answer was produced via `chat_stream()`.
+    pub streamed: bool,
+}
+
+/// Decide whether to stream: the user must not have opted out (flag or
+/// config) and the provider must advertise real streaming support.
+pub(crate) fn should_stream(
+    provider: &dyn LLMProvider,
+    no_stream: bool,
+    config_streaming: bool,
+) -> bool {
+    !no_stream && config_streaming && provider.supports_streaming()
+}
+
+/// Ask a single question and print the answer.
+pub(crate) async fn cmd_ask(prompt: String, json_output: bool, no_stream: bool) -> Result<()> {
+    let config = Config::load().with_context(|| "Failed to load configuration")?;
+
+    let Some((provider, _names)) = zeptoclaw::kernel::build_provider_chain(&config).await else {
+        anyhow::bail!(
+            "No AI provider configured. Run 'zeptoclaw onboard' or set ZEPTOCLAW_PROVIDERS_ANTHROPIC_API_KEY"
+        );
+    };
+
+    let mut messages = Vec::with_capacity(2);
+    if let Some(sp) = &config.agents.defaults.system_prompt {
+        messages.push(Message::system(sp));
+    }
+    messages.push(Message::user(&prompt));
+
+    let options = ChatOptions::new()
+        .with_max_tokens(config.agents.defaults.max_tokens)
+        .with_temperature(config.agents.defaults.temperature);
+    let model = config.agents.defaults.model.as_str();
+    let streaming = should_stream(
+        provider.as_ref(),
+        no_stream,
+        config.agents.defaults.streaming,
+    );
+
+    let stdout = io::stdout();
+    let result = if json_output {
+        // JSON mode buffers everything so stdout carries exactly one object.
+        ask_provider(provider.as_ref(), messages, model, options, streaming, None).await
+    } else {
+        let mut out = stdout.lock();
+        ask_provider(
+            provider.as_ref(),
+            messages,
+            model,
+            options,
+            streaming,
+            Some(&mut out),
+        )
+        .await
+    };
+
+    let answer = match result {
+        Ok(answer) => answer,
+        Err(e) => {
+            if json_output {
+                println!("{}", json!({ "model": model, "error": e.to_string() }));
+            } else {
+                eprintln!("{}", format_cli_error(&e));
+            }
+            std::process::exit(1);
+        }
+    };
+
+    if json_output {
+        println!("{}", render_json(&answer, model));
+    } else if needs_trailing_newline(&answer.content) {
+        println!();
+    }
+
+    Ok(())
+}
+
+/// Run the prompt against `provider`, writing text to `out` incrementally
+/// when one is supplied.
+pub(crate) async fn ask_provider(
+    provider: &dyn LLMProvider,
+    messages: Vec<Message>,
+    model: &str,
+    options: ChatOptions,
+    streaming: bool,
+    mut out: Option<&mut dyn Write>,
+) -> Result<AskAnswer> {
+    if streaming {
+        let rx = provider
+            .chat_stream(messages, Vec::new(), Some(model), options)
+            .await?;
+        let (content, usage) = collect_stream(rx, out).await?;
+        return Ok(AskAnswer {
+            content,
+            usage,
+            streamed: true,
+        });
+    }
+
+    let response = provider
+        .chat(messages, Vec::new(), Some(model), options)
+        .await?;
+    if let Some(w) = out.as_mut() {
+        w.write_all(response.content.as_bytes())?;
+        w.flush()?;
+    }
+    Ok(AskAnswer {
+        content: response.content,
+        usage: response.usage,
+        streamed: false,
+    })
+}
+
+/// Drain a stream, echoing each text delta to `out` and accumulating the
+/// full response.
+///
+/// If the provider emitted no deltas but `Done` carries content, that content
+/// is used (and echoed) instead.
+pub(crate) async fn collect_stream(
+    mut rx: tokio::sync::mpsc::Receiver<StreamEvent>,
+    mut out: Option<&mut dyn Write>,
+) -> Result<(String, Option<Usage>)> {
+    let mut content = String::new();
+    let mut usage = None;
+
+    while let Some(event) = rx.recv().await {
+        match event {
+            StreamEvent::Delta(text) => {
+                if let Some(w) = out.as_mut() {
+                    w.write_all(text.as_bytes())?;
+                    w.flush()?;
+                }
+                content.push_str(&text);
+            }
+            StreamEvent::Done {
+                content: full,
+                usage: done_usage,
+            } => {
+                if content.is_empty() && !full.is_empty() {
+                    if let Some(w) = out.as_mut() {
+                        w.write_all(full.as_bytes())?;
+                        w.flush()?;
+                    }
+                    content = full;
+                }
+                usage = done_usage;
+                break;
+            }
+            StreamEvent::Error(e) => return Err(e.into()),
+            StreamEvent::ToolCalls(_) | StreamEvent::ToolCallReady(_) => {}
+        }
+    }
+
+    Ok((content, usage))
+}
+
+/// Whether a newline must be printed after the answer to leave the terminal
+/// on a clean line.
+fn needs_trailing_newline(content: &str) -> bool {
+    !content.ends_with('\n')
+}
+
+/// Render the buffered answer as a single JSON object.
+fn render_json(answer: &AskAnswer, model: &str) -> serde_json::Value {
+    json!({
+        "model": model,
+        "content": answer.content,
+        "streamed": answer.streamed,
+        "usage": answer.usage.as_ref().map(|u| json!({
+            "prompt_tokens": u.prompt_tokens,
+            "completion_tokens": u.completion_tokens,
+            "total_tokens": u.total_tokens,
+        })),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use zeptoclaw::providers::{LLMResponse, ToolDefinition};
+
+    struct StreamingProvider;
+
+    #[async_trait]
+    impl LLMProvider for StreamingProvider {
+        async fn chat(
+            &self,
+            _messages: Vec<Message>,
+            _tools: Vec<ToolDefinition>,
+            _model: Option<&str>,
+            _options: ChatOptions,
+        ) -> zeptoclaw::error::Result<LLMResponse> {
+            panic!("streaming provider should not be called via chat()");
+        }
+
+        fn default_model(&self) -> &str {
+            "stream-model"
+        }
+
+        fn name(&self) -> &str {
+            "streaming"
+        }
+
+        fn supports_streaming(&self) -> bool {
+            true
+        }
+
+        async fn chat_stream(
+            &self,
+            _messages: Vec<Message>,
+            _tools: Vec<ToolDefinition>,
+            _model: Option<&str>,
+            _options: ChatOptions,
+        ) -> zeptoclaw::error::Result<tokio::sync::mpsc::Receiver<StreamEvent>> {
+            let (tx, rx) = tokio::sync::mpsc::channel(8);
+            for chunk in ["Hel", "lo, ", "world"] {
+                tx.send(StreamEvent::Delta(chunk.to_string()))
+                    .await
+                    .unwrap();
+            }
+            tx.send(StreamEvent::Done {
+                content: "Hello, world".to_string(),
+                usage: Some(Usage::new(5, 3)),
+            })
+            .await
+            .unwrap();
+            Ok(rx)
+        }
+    }
+
+    struct BufferedProvider;
+
+    #[async_trait]
+    impl LLMProvider for BufferedProvider {
+        async fn chat(
+            &self,
+            _messages: Vec<Message>,
+            _tools: Vec<ToolDefinition>,
+            _model: Option<&str>,
+            _options: ChatOptions,
+        ) -> zeptoclaw::error::Result<LLMResponse> {
+            Ok(LLMResponse::text("buffered answer"))
+        }
+
+        fn default_model(&self) -> &str {
+            "buffered-model"
+        }
+
+        fn name(&self) -> &str {
+            "buffered"
+        }
+    }
+
+    #[test]
+    fn test_should_stream_follows_provider_capability() {
+        assert!(should_stream(&StreamingProvider, false, true));
+        assert!(!should_stream(&BufferedProvider, false, true));
+    }
+
+    #[test]
+    fn test_should_stream_respects_opt_outs() {
+        assert!(!should_stream(&StreamingProvider, true, true));
+        assert!(!should_stream(&StreamingProvider, false, false));
+    }
+
+    #[tokio::test]
+    async fn test_collect_stream_accumulates_deltas() {
+        let (tx, rx) = tokio::sync::mpsc::channel(8);
+        tx.send(StreamEvent::Delta("foo".into())).await.unwrap();
+        tx.send(StreamEvent::Delta("bar".into())).await.unwrap();
+        tx.send(StreamEvent::Done {
+            content: "foobar".into(),
+            usage: None,
+        })
+        .await
+        .unwrap();
+        drop(tx);
+
+        let mut buf: Vec<u8> = Vec::new();
+        let (content, usage) = collect_stream(rx, Some(&mut buf)).await.unwrap();
+        assert_eq!(content, "foobar");
+        assert_eq!(String::from_utf8(buf).unwrap(), "foobar");
+        assert!(usage.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_collect_stream_uses_done_content_without_deltas() {
+        let (tx, rx) = tokio::sync::mpsc::channel(2);
+        tx.send(StreamEvent::Done {
+            content: "all at once".into(),
+            usage: Some(Usage::new(1, 2)),
+        })
+        .await
+        .unwrap();
+        drop(tx);
+
+        let mut buf: Vec<u8> = Vec::new();
+        let (content, usage) = collect_stream(rx, Some(&mut buf)).await.unwrap();
+        assert_eq!(content, "all at once");
+        assert_eq!(String::from_utf8(buf).unwrap(), "all at once");
+        assert_eq!(usage.unwrap().total_tokens, 3);
+    }
+
+    #[tokio::test]
+    async fn test_collect_stream_propagates_error() {
+        let (tx, rx) = tokio::sync::mpsc::channel(2);
+        tx.send(StreamEvent::Delta("partial".into())).await.unwrap();
+        tx.send(StreamEvent::Error(zeptoclaw::error::ZeptoError::Provider(
+            "boom".into(),
+        )))
+        .await
+        .unwrap();
+        drop(tx);
+
+        let err = collect_stream(rx, None).await.unwrap_err();
+        assert!(err.to_string().contains("boom"));
+    }
+
+    #[tokio::test]
+    async fn test_ask_provider_streams_when_supported() {
+        let provider = StreamingProvider;
+        let streaming = should_stream(&provider, false, true);
+        let mut buf: Vec<u8> = Vec::new();
+        let answer = ask_provider(
+            &provider,
+            vec![Message::user("hi")],
+            "stream-model",
+            ChatOptions::new(),
+            streaming,
+            Some(&mut buf),
+        )
+        .await
+        .unwrap();
+
+        assert!(answer.streamed);
+        assert_eq!(answer.content, "Hello, world");
+        assert_eq!(String::from_utf8(buf).unwrap(), "Hello, world");
+    }
+
+    #[tokio::test]
+    async fn test_ask_provider_falls_back_to_chat() {
+        let provider = BufferedProvider;
+        let streaming = should_stream(&provider, false, true);
+        let mut buf: Vec<u8> = Vec::new();
+        let answer = ask_provider(
+            &provider,
+            vec![Message::user("hi")],
+            "buffered-model",
+            ChatOptions::new(),
+            streaming,
+            Some(&mut buf),
+        )
+        .await
+        .unwrap();
+
+        assert!(!answer.streamed);
+        assert_eq!(answer.content, "buffered answer");
+        assert_eq!(String::from_utf8(buf).unwrap(), "buffered answer");
+    }
+
+    #[tokio::test]
+    async fn test_json_mode_buffers_single_object() {
+        let provider = StreamingProvider;
+        let answer = ask_provider(
+            &provider,
+            vec![Message::user("hi")],
+            "stream-model",
+            ChatOptions::new(),
+            true,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let rendered = render_json(&answer, "stream-model").to_string();
+        let parsed: serde_json::Value = serde_json::from_str(&rendered).unwrap();
+        assert_eq!(parsed["content"], "Hello, world");
+        assert_eq!(parsed["streamed"], true);
+        assert_eq!(parsed["usage"]["total_tokens"], 8);
+        assert!(!rendered.contains('\n'));
+    }
+
+    #[test]
+    fn test_needs_trailing_newline() {
+        assert!(needs_trailing_newline("no newline"));
+        assert!(needs_trailing_newline(""));
+        assert!(!needs_trailing_newline("ends with newline\n"));
+    }
+}
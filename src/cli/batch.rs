@@ -121,7 +121,7 @@ async fn process_streaming(
             StreamEvent::Delta(chunk) => response.push_str(&chunk),
             StreamEvent::Done { .. } => break,
             StreamEvent::Error(err) => anyhow::bail!("stream error: {}", err),
-            StreamEvent::ToolCalls(_) => {}
+            StreamEvent::ToolCalls(_) | StreamEvent::ToolCallReady(_) => {}
         }
     }
     Ok(response)
@@ -0,0 +1,94 @@
+//! Response cache inspection and purge command handler.
+
+use anyhow::{bail, Result};
+
+use zeptoclaw::cache::ResponseCache;
+use zeptoclaw::config::Config;
+
+use super::CacheAction;
+
+/// Handle `zeptoclaw cache` subcommands.
+pub(crate) fn cmd_cache(action: CacheAction) -> Result<()> {
+    let config = Config::load().unwrap_or_default();
+    let mut cache = ResponseCache::with_compression(
+        config.cache.ttl_secs,
+        config.cache.max_entries,
+        config.cache.compress,
+    )
+    .with_max_bytes(config.cache.max_bytes);
+
+    match action {
+        CacheAction::Stats => {
+            let stats = cache.stats();
+            println!("Entries:      {}", stats.total_entries);
+            println!("Size:         {} bytes", stats.total_bytes);
+            println!("Hits:         {}", stats.total_hits);
+            println!("Misses:       {}", stats.misses);
+            match stats.hit_rate() {
+                Some(rate) => println!("Hit rate:     {:.1}%", rate * 100.0),
+                None => println!("Hit rate:     n/a"),
+            }
+            println!("Tokens saved: {}", stats.total_tokens_saved);
+            println!("Evictions:    {}", stats.evictions);
+        }
+        CacheAction::Purge {
+            model,
+            older_than,
+            all,
+        } => {
+            let removed = if all {
+                cache.clear()
+            } else {
+                let max_age = older_than.as_deref().map(parse_age).transpose()?;
+                if model.is_none() && max_age.is_none() {
+                    bail!("Specify --model, --older-than, or --all");
+                }
+                let now = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs();
+                cache.invalidate_matching(|_, entry| {
+                    model
+                        .as_deref()
+                        .is_none_or(|m| entry.model.as_deref() == Some(m))
+                        && max_age.is_none_or(|age| now.saturating_sub(entry.created_at) > age)
+                })
+            };
+            println!("Removed {} cached response(s).", removed);
+        }
+    }
+
+    Ok(())
+}
+
+/// Parse an age like `90`, `90s`, `30m`, `12h` or `7d` into seconds.
+fn parse_age(input: &str) -> Result<u64> {
+    match zeptoclaw::cache::parse_age(input) {
+        Some(secs) => Ok(secs),
+        None => bail!(
+            "Invalid age '{}': expected a number with optional s/m/h/d suffix",
+            input.trim()
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_age;
+
+    #[test]
+    fn test_parse_age_units() {
+        assert_eq!(parse_age("90").unwrap(), 90);
+        assert_eq!(parse_age("90s").unwrap(), 90);
+        assert_eq!(parse_age("30m").unwrap(), 1800);
+        assert_eq!(parse_age("12h").unwrap(), 43_200);
+        assert_eq!(parse_age("7d").unwrap(), 604_800);
+    }
+
+    #[test]
+    fn test_parse_age_invalid() {
+        assert!(parse_age("").is_err());
+        assert!(parse_age("abc").is_err());
+        assert!(parse_age("5w").is_err());
+    }
+}
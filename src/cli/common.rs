@@ -7,19 +7,30 @@ use std::sync::Arc;
 use anyhow::{Context, Result};
 use tracing::{info, warn};
 
-use zeptoclaw::agent::{AgentLoop, ContextBuilder, RuntimeContext};
+use zeptoclaw::agent::{AgentLoop, ContextBuilder, PromptTemplate, RuntimeContext};
 use zeptoclaw::bus::MessageBus;
 use zeptoclaw::config::templates::{AgentTemplate, TemplateRegistry};
 use zeptoclaw::config::{Config, MemoryBackend, MemoryCitationsMode};
 use zeptoclaw::hands::resolve_hand;
+use zeptoclaw::memory::preferences::PreferencesStore;
+use zeptoclaw::memory::workspace_index::WorkspaceIndex;
 use zeptoclaw::providers::{
     resolve_runtime_providers, FallbackProvider, LLMProvider, ProviderPlugin,
 };
+use zeptoclaw::session::uploads::UploadStore;
 use zeptoclaw::session::SessionManager;
 use zeptoclaw::skills::SkillsLoader;
 use zeptoclaw::tools::approval::ApprovalPolicyConfig;
+use zeptoclaw::tools::approval_store::PendingApprovals;
+use zeptoclaw::tools::cache::CacheTool;
+use zeptoclaw::tools::compact_session::CompactSessionTool;
 use zeptoclaw::tools::delegate::DelegateTool;
+use zeptoclaw::tools::kv::KvTool;
+use zeptoclaw::tools::pin::PinTool;
+use zeptoclaw::tools::preferences::PreferencesTool;
+use zeptoclaw::tools::rag::{IndexWorkspaceTool, RagQueryTool};
 use zeptoclaw::tools::spawn::SpawnTool;
+use zeptoclaw::tools::UseHandTool;
 
 /// Read a line from stdin, trimming whitespace.
 pub(crate) fn read_line() -> Result<String> {
@@ -112,7 +123,9 @@ pub(crate) fn resolve_template(name: &str) -> Result<AgentTemplate> {
 
 // Provider functions extracted to zeptoclaw::kernel::provider.
 // Re-import for use within this module.
-use zeptoclaw::kernel::provider::{apply_retry_wrapper, provider_from_runtime_selection};
+use zeptoclaw::kernel::provider::{
+    apply_prompt_log_wrapper, apply_retry_wrapper, provider_from_runtime_selection,
+};
 
 fn build_skills_prompt(config: &Config) -> String {
     if !config.skills.enabled {
@@ -224,6 +237,8 @@ pub(crate) async fn create_agent_with_template(
                     config.approval.require_for.push(pattern.clone());
                 }
             }
+            config.approval.hand_require_for =
+                hand.manifest.guardrails.require_approval_for.clone();
         }
     }
 
@@ -265,10 +280,12 @@ pub(crate) async fn create_agent_with_template(
     .await?;
 
     // --- Per-session state: context builder, agent loop ---
-    let session_manager = SessionManager::new().unwrap_or_else(|_| {
-        warn!("Failed to create persistent session manager, using in-memory");
-        SessionManager::new_memory()
-    });
+    let session_manager = SessionManager::new()
+        .unwrap_or_else(|_| {
+            warn!("Failed to create persistent session manager, using in-memory");
+            SessionManager::new_memory()
+        })
+        .with_uploads(UploadStore::new(config.workspace_path()));
 
     let skills_prompt = build_skills_prompt(&config);
     let mut context_builder = ContextBuilder::new();
@@ -293,7 +310,29 @@ pub(crate) async fn create_agent_with_template(
     } else if let Some(tpl) = &template {
         context_builder = context_builder.with_system_prompt(&tpl.system_prompt);
     } else if let Some(hand) = active_hand.as_ref() {
-        context_builder = context_builder.with_system_prompt(&hand.manifest.system_prompt);
+        context_builder = context_builder
+            .with_system_prompt(&hand.manifest.system_prompt)
+            .with_active_hand(&hand.manifest.name, &hand.manifest.system_prompt);
+    }
+    for (channel, prompt) in &config.channels.system_prompts {
+        context_builder = context_builder.with_channel_prompt(channel, prompt.clone());
+    }
+    if let (None, Some(path)) = (
+        &config.agents.defaults.system_prompt,
+        &config.agents.defaults.system_prompt_template,
+    ) {
+        match PromptTemplate::load(&expand_tilde(path)) {
+            Ok(mut prompt_template) => {
+                prompt_template =
+                    prompt_template.with_workspace(config.workspace_path().display().to_string());
+                if let Some(hand) = active_hand.as_ref() {
+                    prompt_template = prompt_template.with_hand(&hand.manifest.name);
+                }
+                context_builder = context_builder.with_prompt_template(prompt_template);
+                info!("Loaded system prompt template from {}", path);
+            }
+            Err(e) => warn!("{}; using the built-in system prompt", e),
+        }
     }
     if !skills_prompt.is_empty() {
         context_builder = context_builder.with_skills(&skills_prompt);
@@ -321,6 +360,9 @@ pub(crate) async fn create_agent_with_template(
         agent_loop.set_taint(Arc::clone(taint));
         info!("Wired shared taint engine into agent loop");
     }
+    let preferences = Arc::new(PreferencesStore::new(PreferencesStore::default_dir()));
+    agent_loop.set_preferences_store(Arc::clone(&preferences));
+    agent_loop.set_pending_approvals(Arc::new(PendingApprovals::from_config(&config)));
     let agent = Arc::new(agent_loop);
 
     // Transfer kernel tools + MCP clients into agent
@@ -350,11 +392,18 @@ pub(crate) async fn create_agent_with_template(
         let google_token = resolve_google_token(&config).await;
         if let Some(token) = google_token {
             agent
-                .register_tool(Box::new(zeptoclaw::tools::GoogleTool::new(
-                    &token,
-                    &config.tools.google.default_calendar,
-                    config.tools.google.max_search_results,
-                )))
+                .register_tool(Box::new(
+                    zeptoclaw::tools::GoogleTool::new(
+                        &token,
+                        &config.tools.google.default_calendar,
+                        config.tools.google.max_search_results,
+                    )
+                    .with_display_format(zeptoclaw::utils::format::DisplayFormat::from_names(
+                        &config.agents.defaults.timezone,
+                        &config.locale.default,
+                    ))
+                    .with_preferences(Arc::clone(&preferences)),
+                ))
                 .await;
             info!("Registered google tool");
         }
@@ -417,6 +466,7 @@ pub(crate) async fn create_agent_with_template(
             let chain_label = chain_names.join(" -> ");
             let plugin_count = chain_names.len();
             let chain = apply_retry_wrapper(chain, &config);
+            let chain = apply_prompt_log_wrapper(chain, &config);
             agent.set_provider(chain).await;
 
             if plugin_count > 1 {
@@ -455,6 +505,83 @@ pub(crate) async fn create_agent_with_template(
         }
     }
 
+    // Register the per-user preferences tool (shares the agent's store)
+    if filter.is_enabled("preferences") {
+        agent
+            .register_tool(Box::new(PreferencesTool::new(preferences)))
+            .await;
+    }
+
+    // Register key-value state (counters, flags, last-seen values)
+    if filter.is_enabled("kv") {
+        agent
+            .register_tool(Box::new(KvTool::new(KvTool::default_path())))
+            .await;
+    }
+
+    // Register agent-triggered session compaction (summaries need a provider)
+    if filter.is_enabled("compact_session") {
+        if let Some(provider) = agent.provider().await {
+            agent
+                .register_tool(Box::new(
+                    CompactSessionTool::new(Arc::clone(agent.session_manager()), provider)
+                        .with_model(config.agents.defaults.model.clone()),
+                ))
+                .await;
+        }
+    }
+
+    // Register context pinning (notes kept across truncation/compaction)
+    if filter.is_enabled("pin") {
+        agent
+            .register_tool(Box::new(
+                PinTool::new(Arc::clone(agent.session_manager()))
+                    .with_max_notes(config.session.max_pinned_notes),
+            ))
+            .await;
+    }
+
+    // Register per-conversation hand switching
+    if filter.is_enabled("use_hand") {
+        agent
+            .register_tool(Box::new(UseHandTool::new(Arc::clone(
+                agent.session_manager(),
+            ))))
+            .await;
+    }
+
+    // Register response cache management (clear is gated as `cache:clear`)
+    if filter.is_enabled("cache") {
+        if let Some(cache) = agent.response_cache() {
+            agent
+                .register_tool(Box::new(CacheTool::new(Arc::clone(cache))))
+                .await;
+        }
+    }
+
+    // Register workspace RAG tools (opt-in coding tools; embeddings need a provider)
+    let coding_tools_on = config.tools.coding_tools
+        || filter.has_explicit_profile()
+        || template
+            .as_ref()
+            .is_some_and(|t| t.tags.iter().any(|tag| tag == "coding"));
+    if coding_tools_on && (filter.is_enabled("index_workspace") || filter.is_enabled("rag_query")) {
+        if let Some(provider) = agent.provider().await {
+            let index = Arc::new(WorkspaceIndex::new(provider, Config::dir().join("rag")));
+            if filter.is_enabled("index_workspace") {
+                agent
+                    .register_tool(Box::new(IndexWorkspaceTool::new(Arc::clone(&index))))
+                    .await;
+            }
+            if filter.is_enabled("rag_query") {
+                agent
+                    .register_tool(Box::new(RagQueryTool::new(index)))
+                    .await;
+            }
+            info!("Registered workspace RAG tools");
+        }
+    }
+
     Ok(agent)
 }
 
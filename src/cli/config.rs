@@ -94,6 +94,13 @@ async fn cmd_config_check() -> Result<()> {
         .filter(|d| d.level == zeptoclaw::config::validate::DiagnosticLevel::Warn)
         .count();
 
+    // Quota warning thresholds (out-of-range values fall back to the default).
+    let quota_diags = zeptoclaw::config::validate::validate_quota_thresholds(&config);
+    for diag in &quota_diags {
+        println!("{}", diag);
+    }
+    warnings += quota_diags.len();
+
     // Hint: workspace configured but coding tools disabled
     let workspace = config.workspace_path();
     if workspace.exists() && !config.tools.coding_tools {
@@ -35,6 +35,7 @@ pub fn run_diagnostics(config: &Config, online: bool) -> Vec<DiagItem> {
 
     check_config(config, &mut diags);
     check_workspace_writable(&config.workspace_path(), &mut diags);
+    check_workspace_bootstrap(config, &mut diags);
     check_environment(&mut diags);
     check_providers(config, &mut diags);
     check_channels(config, &mut diags);
@@ -98,6 +99,30 @@ fn check_workspace_writable(workspace: &Path, diags: &mut Vec<DiagItem>) {
     }
 }
 
+/// Explain what happens to a missing workspace on the next start.
+fn check_workspace_bootstrap(config: &Config, diags: &mut Vec<DiagItem>) {
+    let workspace = config.workspace_path();
+    if workspace.exists() {
+        return;
+    }
+    let message = if config.agents.defaults.bootstrap_workspace {
+        format!(
+            "Workspace will be created at {} on next start (set `agents.defaults.bootstrap_workspace: false` to opt out)",
+            workspace.display()
+        )
+    } else {
+        format!(
+            "Workspace bootstrap is disabled; create {} or run `zeptoclaw onboard`",
+            workspace.display()
+        )
+    };
+    diags.push(DiagItem {
+        severity: Severity::Warn,
+        category: "workspace",
+        message,
+    });
+}
+
 fn check_environment(diags: &mut Vec<DiagItem>) {
     // sh is required for the shell tool.
     check_binary("sh", diags);
@@ -367,6 +392,23 @@ mod tests {
         assert!(diags.iter().any(|d| d.severity == Severity::Err));
     }
 
+    #[test]
+    fn test_check_workspace_bootstrap_mentions_missing_workspace() {
+        let mut config = Config::default();
+        config.agents.defaults.workspace = "/nonexistent/path/12345".into();
+
+        let mut diags = Vec::new();
+        check_workspace_bootstrap(&config, &mut diags);
+        assert!(diags[0]
+            .message
+            .contains("will be created at /nonexistent/path/12345"));
+
+        config.agents.defaults.bootstrap_workspace = false;
+        let mut diags = Vec::new();
+        check_workspace_bootstrap(&config, &mut diags);
+        assert!(diags[0].message.contains("bootstrap is disabled"));
+    }
+
     #[test]
     fn test_check_binary_present() {
         let mut diags = Vec::new();
@@ -5,20 +5,26 @@ use std::time::Duration;
 
 use anyhow::{Context, Result};
 use tokio::sync::{mpsc, watch};
-use tracing::{error, info, warn};
+use tracing::{debug, error, info, warn};
 
-use zeptoclaw::bus::MessageBus;
+use zeptoclaw::agent::AgentLoop;
+use zeptoclaw::bus::{InboundMessage, MessageBus};
 use zeptoclaw::channels::{register_configured_channels, ChannelManager};
-use zeptoclaw::config::watcher::ConfigWatcher;
+use zeptoclaw::config::watcher::{changed_sections, ConfigWatcher, HOT_RELOAD_SECTIONS};
 use zeptoclaw::config::{Config, ContainerAgentBackend};
+use zeptoclaw::hands::monitor::MonitorService;
+use zeptoclaw::hardware::hotplug::{HotplugWatcher, DEFAULT_POLL_INTERVAL};
 use zeptoclaw::health::{
-    health_port, start_health_server, start_health_server_legacy, start_periodic_usage_flush,
-    HealthRegistry, UsageMetrics,
+    health_port, start_health_server, start_health_server_legacy, start_periodic_disk_check,
+    start_periodic_usage_flush, HealthRegistry, UsageMetrics,
 };
 use zeptoclaw::heartbeat::{ensure_heartbeat_file, HeartbeatService};
 use zeptoclaw::providers::{
     configured_provider_names, resolve_runtime_provider, RUNTIME_SUPPORTED_PROVIDERS,
 };
+use zeptoclaw::safety::output_filter::{LlmOutputClassifier, OutputFilter};
+use zeptoclaw::scheduler::Scheduler;
+use zeptoclaw::session::start_periodic_session_gc;
 
 use super::common::create_agent;
 use super::heartbeat::heartbeat_file_path;
@@ -106,6 +112,10 @@ pub(crate) async fn cmd_gateway(
 
     // Create usage metrics tracker
     let metrics = Arc::new(UsageMetrics::new());
+    #[cfg(feature = "otel")]
+    if config.telemetry.otlp.enabled {
+        zeptoclaw::utils::otel::register_usage_metrics(Arc::clone(&metrics));
+    }
 
     // Start legacy health check server (liveness + readiness via UsageMetrics)
     let hp = health_port();
@@ -126,6 +136,8 @@ pub(crate) async fn cmd_gateway(
     // Create HealthRegistry (shared between health server and channel supervisor)
     let health_registry = HealthRegistry::new();
     health_registry.set_metrics(Arc::clone(&metrics));
+    zeptoclaw::providers::LatencyTracker::global()
+        .attach_health(health_registry.clone(), config.health.provider_slow_p95_ms);
 
     // Start HealthRegistry-based server if config.health.enabled
     if config.health.enabled {
@@ -155,6 +167,28 @@ pub(crate) async fn cmd_gateway(
 
     // Create shutdown watch channel for periodic usage flush
     let (usage_shutdown_tx, usage_shutdown_rx) = tokio::sync::watch::channel(false);
+    // Free space under the workspace (or config root), reported as `disk`.
+    // Stops on the same shutdown signal as the usage flush.
+    let workspace = config.workspace_path();
+    let disk_path = if workspace.exists() {
+        workspace
+    } else {
+        Config::dir()
+    };
+    if start_periodic_disk_check(
+        health_registry.clone(),
+        disk_path,
+        config.health.disk_warn_free_mb.saturating_mul(1024 * 1024),
+        config
+            .health
+            .disk_critical_free_mb
+            .saturating_mul(1024 * 1024),
+        usage_shutdown_rx.clone(),
+    )
+    .is_none()
+    {
+        debug!("Disk space check unavailable on this platform");
+    }
     let usage_flush_handle = start_periodic_usage_flush(Arc::clone(&metrics), usage_shutdown_rx);
 
     // Determine agent backend: containerized or in-process
@@ -293,6 +327,7 @@ pub(crate) async fn cmd_gateway(
     let mut agent = if !containerized {
         let agent = create_agent(config.clone(), bus.clone()).await?;
         agent.set_usage_metrics(Arc::clone(&metrics)).await;
+        health_registry.set_run_limiter(Arc::clone(agent.run_limiter()));
         Some(agent)
     } else {
         None
@@ -301,6 +336,7 @@ pub(crate) async fn cmd_gateway(
     // Create channel manager with health supervision
     let mut channel_manager = ChannelManager::new(bus.clone(), config.clone());
     channel_manager.set_health_registry(health_registry.clone());
+    attach_output_classifier(&mut channel_manager, agent.as_ref(), &config).await;
 
     // Register channels via factory.
     let channel_count = register_configured_channels(&channel_manager, bus.clone(), &config).await;
@@ -358,6 +394,20 @@ pub(crate) async fn cmd_gateway(
         None
     };
 
+    // In-process scheduler for Rust jobs; last-run times persist across restarts.
+    let scheduler = Scheduler::from_config(&config);
+
+    // Schedule monitor watch list
+    if config.monitor.enabled && !config.monitor.targets.is_empty() {
+        let service = Arc::new(MonitorService::new(
+            config.monitor.clone(),
+            Config::dir().join("monitor"),
+            bus.clone(),
+        ));
+        let registered = service.register_with(&scheduler);
+        info!("Scheduled {} monitor target(s)", registered);
+    }
+
     // Start memory hygiene scheduler
     let _hygiene_handle = match zeptoclaw::memory::longterm::LongTermMemory::new() {
         Ok(ltm) => {
@@ -373,18 +423,30 @@ pub(crate) async fn cmd_gateway(
         }
     };
 
-    // Start device service if configured
-    // TODO: publish to MessageBus for channel delivery once InboundMessage wrapping is settled
-    let _device_handle =
-        zeptoclaw::devices::DeviceService::new(config.devices.enabled, config.devices.monitor_usb)
-            .start()
-            .map(|mut rx| {
-                tokio::spawn(async move {
-                    while let Some(event) = rx.recv().await {
-                        tracing::info!("Device event: {}", event.format_message());
-                    }
-                })
-            });
+    // Start USB hotplug watcher if configured; events reach the agent via the bus.
+    let hotplug_shutdown_tx = if config.devices.enabled && config.devices.monitor_usb {
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        tokio::spawn(HotplugWatcher::new(DEFAULT_POLL_INTERVAL).watch(event_tx, shutdown_rx));
+        let bus = bus.clone();
+        tokio::spawn(async move {
+            while let Some(event) = event_rx.recv().await {
+                let text = event.format_message();
+                info!("Device event: {}", text);
+                let mut msg = InboundMessage::new("devices", "system", "hotplug", &text);
+                if let Ok(json) = serde_json::to_string(&event) {
+                    msg = msg.with_metadata("hotplug_event", &json);
+                }
+                if let Err(e) = bus.publish_inbound(msg).await {
+                    warn!("Failed to publish device event: {}", e);
+                }
+            }
+        });
+        info!("USB hotplug watcher started");
+        Some(shutdown_tx)
+    } else {
+        None
+    };
 
     // Start agent loop in background (only for in-process mode)
     let mut agent_handle = if let Some(ref agent) = agent {
@@ -410,6 +472,11 @@ pub(crate) async fn cmd_gateway(
         None
     };
 
+    // Sweep stale sessions in the background when a TTL is configured
+    let mut session_gc = agent
+        .as_ref()
+        .and_then(|agent| spawn_session_gc(agent, &config));
+
     // Mark gateway as ready for /readyz
     metrics.set_ready(true);
 
@@ -476,6 +543,8 @@ pub(crate) async fn cmd_gateway(
                     match create_agent(config.clone(), bus.clone()).await {
                         Ok(new_agent) => {
                             new_agent.set_usage_metrics(Arc::clone(&metrics)).await;
+                            health_registry
+                                .set_run_limiter(Arc::clone(new_agent.run_limiter()));
                             let agent_clone = Arc::clone(&new_agent);
                             let agent_metrics = Arc::clone(&metrics);
                             let agent_guard = guard.clone();
@@ -494,6 +563,8 @@ pub(crate) async fn cmd_gateway(
                                     Ok(()) => warn!("Agent loop stopped"),
                                 }
                             }));
+                            stop_session_gc(session_gc.take()).await;
+                            session_gc = spawn_session_gc(&new_agent, &config);
                             agent = Some(new_agent);
                         }
                         Err(e) => {
@@ -513,6 +584,7 @@ pub(crate) async fn cmd_gateway(
                     }
                     let mut new_manager = ChannelManager::new(bus.clone(), config.clone());
                     new_manager.set_health_registry(health_registry.clone());
+                    attach_output_classifier(&mut new_manager, agent.as_ref(), &config).await;
                     let count = register_configured_channels(&new_manager, bus.clone(), &config).await;
                     if count == 0 {
                         warn!("No channels configured after hot-reload");
@@ -537,10 +609,12 @@ pub(crate) async fn cmd_gateway(
     // Signal usage flush to emit final summary
     let _ = usage_shutdown_tx.send(true);
     let _ = tokio::time::timeout(std::time::Duration::from_secs(2), usage_flush_handle).await;
+    stop_session_gc(session_gc.take()).await;
 
     if let Some(service) = &heartbeat_service {
         service.stop().await;
     }
+    scheduler.stop();
 
     // Stop agent or proxy
     if let Some(ref agent) = agent {
@@ -557,6 +631,10 @@ pub(crate) async fn cmd_gateway(
         .await
         .with_context(|| "Failed to stop channels")?;
 
+    if let Some(tx) = &hotplug_shutdown_tx {
+        let _ = tx.send(true);
+    }
+
     // Stop config watcher
     let _ = reload_shutdown_tx.send(true);
     let _ = tokio::time::timeout(Duration::from_secs(2), watcher_handle).await;
@@ -620,25 +698,87 @@ fn parse_deliver_to(s: &str) -> Option<(String, String)> {
     }
 }
 
-fn diff_hot_reload_sections(old: &Config, new: &Config) -> Vec<&'static str> {
-    let mut changed = Vec::new();
-    if section_changed(&old.providers, &new.providers) {
-        changed.push("providers");
-    }
-    if section_changed(&old.channels, &new.channels) {
-        changed.push("channels");
+/// Attach the model classifier of `safety.output_filter` to the channel
+/// manager's outbound filter, using the agent's provider.
+async fn attach_output_classifier(
+    manager: &mut ChannelManager,
+    agent: Option<&Arc<AgentLoop>>,
+    config: &Config,
+) {
+    let filter_config = &config.safety.output_filter;
+    let classifier = &filter_config.classifier;
+    if !config.safety.enabled || !filter_config.enabled || !classifier.enabled {
+        return;
     }
-    if section_changed(&old.safety, &new.safety) {
-        changed.push("safety");
+    let provider = match agent {
+        Some(agent) => agent.provider().await,
+        None => None,
+    };
+    let Some(provider) = provider else {
+        warn!("Output filter classifier needs an in-process provider; applying rules only");
+        return;
+    };
+    let model = classifier
+        .model
+        .clone()
+        .unwrap_or_else(|| config.agents.defaults.model.clone());
+    let filter = OutputFilter::from_config(filter_config).map(|filter| {
+        filter.with_classifier(
+            Arc::new(LlmOutputClassifier::new(
+                provider,
+                Some(model),
+                classifier.categories.clone(),
+            )),
+            classifier.action,
+        )
+    });
+    manager.set_output_filter(filter);
+}
+
+/// Shutdown sender and task of a running session garbage collector.
+type SessionGcHandle = (watch::Sender<bool>, tokio::task::JoinHandle<()>);
+
+/// Start the session garbage collector for `agent` if `session.session_ttl_days`
+/// is set. Sessions the agent is processing are never collected.
+fn spawn_session_gc(agent: &Arc<AgentLoop>, config: &Config) -> Option<SessionGcHandle> {
+    let ttl_days = config.session.session_ttl_days;
+    if ttl_days == 0 {
+        return None;
     }
-    if section_changed(&old.agents, &new.agents) {
-        changed.push("agents");
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+    let gc_agent = Arc::clone(agent);
+    let handle = start_periodic_session_gc(
+        Arc::clone(agent.session_manager()),
+        ttl_days,
+        config.session.gc_dry_run,
+        move || {
+            let agent = Arc::clone(&gc_agent);
+            async move { agent.active_session_keys().await }
+        },
+        shutdown_rx,
+    );
+    info!(
+        ttl_days,
+        dry_run = config.session.gc_dry_run,
+        "Session garbage collector started"
+    );
+    Some((shutdown_tx, handle))
+}
+
+async fn stop_session_gc(gc: Option<SessionGcHandle>) {
+    if let Some((shutdown_tx, handle)) = gc {
+        let _ = shutdown_tx.send(true);
+        let _ = tokio::time::timeout(Duration::from_secs(2), handle).await;
     }
-    changed
 }
 
-fn section_changed<T: serde::Serialize>(old: &T, new: &T) -> bool {
-    serde_json::to_value(old).ok() != serde_json::to_value(new).ok()
+fn diff_hot_reload_sections(old: &Config, new: &Config) -> Vec<&'static str> {
+    let changed = changed_sections(old, new);
+    HOT_RELOAD_SECTIONS
+        .iter()
+        .copied()
+        .filter(|section| changed.iter().any(|c| c == section))
+        .collect()
 }
 
 #[cfg(test)]
@@ -3,7 +3,10 @@
 use anyhow::{Context, Result};
 
 use zeptoclaw::config::Config;
-use zeptoclaw::hands::{built_in_hands, load_hands_from_dir, resolve_hand, HandSource};
+use zeptoclaw::hands::{
+    built_in_hands, load_hands_from_dir, resolve_hand, validate_hands_dir, HandSource,
+};
+use zeptoclaw::kernel::BUILTIN_TOOL_NAMES;
 
 use super::HandAction;
 
@@ -73,6 +76,52 @@ pub(crate) async fn cmd_hand(action: HandAction) -> Result<()> {
                 );
             }
         }
+        HandAction::Validate { dir } => {
+            let dir = dir.unwrap_or_else(|| Config::dir().join("hands"));
+            if !dir.exists() {
+                println!("No hands found in {}", dir.display());
+                return Ok(());
+            }
+            let config = Config::load().unwrap_or_default();
+            let mut known: Vec<&str> = BUILTIN_TOOL_NAMES.to_vec();
+            known.extend(config.custom_tools.iter().map(|t| t.name.as_str()));
+
+            let reports = validate_hands_dir(&dir, &known)
+                .with_context(|| format!("Failed to read {}", dir.display()))?;
+            if reports.is_empty() {
+                println!("No hands found in {}", dir.display());
+                return Ok(());
+            }
+
+            let mut failed = 0;
+            for report in &reports {
+                let label = report
+                    .name
+                    .clone()
+                    .unwrap_or_else(|| report.dir.display().to_string());
+                if report.is_valid() {
+                    println!("[pass] {}", label);
+                } else {
+                    failed += 1;
+                    println!("[FAIL] {} ({})", label, report.dir.display());
+                }
+                for error in &report.errors {
+                    println!("    error: {}", error);
+                }
+                for warning in &report.warnings {
+                    println!("    warning: {}", warning);
+                }
+            }
+            println!();
+            println!(
+                "{} of {} hand(s) passed",
+                reports.len() - failed,
+                reports.len()
+            );
+            if failed > 0 {
+                anyhow::bail!("{} hand(s) failed validation", failed);
+            }
+        }
     }
 
     Ok(())
@@ -3,7 +3,9 @@
 //! All CLI logic lives here. `main.rs` calls `cli::run()`.
 
 pub mod agent;
+pub mod ask;
 pub mod batch;
+pub mod cache;
 pub mod channel;
 pub mod common;
 pub mod config;
@@ -24,6 +26,7 @@ pub mod quota;
 pub mod secrets;
 #[cfg(feature = "panel")]
 pub mod serve;
+pub mod sessions;
 pub(crate) mod shimmer;
 pub mod skills;
 pub mod slash;
@@ -32,6 +35,8 @@ pub mod template;
 pub mod tools;
 pub mod uninstall;
 pub mod update;
+pub mod usage;
+pub mod verify;
 pub mod watch;
 
 use anyhow::Result;
@@ -78,6 +83,17 @@ enum Commands {
         #[arg(long)]
         mode: Option<String>,
     },
+    /// Ask a one-shot question (no tools, no session) and stream the answer
+    Ask {
+        /// Prompt to send to the model
+        prompt: String,
+        /// Print the full answer as a single JSON object
+        #[arg(long)]
+        json: bool,
+        /// Disable streaming (streaming is on by default)
+        #[arg(long)]
+        no_stream: bool,
+    },
     /// Process prompts from a file
     Batch {
         /// Input file (.txt, .json, or .jsonl)
@@ -124,6 +140,11 @@ enum Commands {
         #[command(subcommand)]
         action: HistoryAction,
     },
+    /// Manage stored sessions
+    Sessions {
+        #[command(subcommand)]
+        action: SessionsAction,
+    },
     /// Manage long-term memory
     Memory {
         #[command(subcommand)]
@@ -140,6 +161,7 @@ enum Commands {
         action: SkillsAction,
     },
     /// Manage hands-lite packages
+    #[command(alias = "hands")]
     Hand {
         #[command(subcommand)]
         action: HandAction,
@@ -189,16 +211,32 @@ enum Commands {
         #[command(subcommand)]
         action: PairAction,
     },
+    /// Inspect or purge the LLM response cache
+    Cache {
+        #[command(subcommand)]
+        action: CacheAction,
+    },
     /// Show or reset per-provider quota usage
     Quota {
         #[command(subcommand)]
         action: QuotaSubcommand,
     },
+    /// Show per-provider spend for the current quota period
+    Usage {
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
     /// Inspect provider chain configuration
     Provider {
         #[command(subcommand)]
         action: ProviderSubcommand,
     },
+    /// Re-send a provider's last failed request (captured redacted on failure)
+    ReplayLast {
+        /// Runtime provider name (e.g. anthropic, openai, gemini)
+        provider: String,
+    },
     /// Start the control panel (API server + dashboard)
     Panel {
         /// Panel subcommand (install, auth, uninstall). Omit to start.
@@ -256,6 +294,20 @@ enum Commands {
         #[arg(long)]
         force: bool,
     },
+    /// Verify a downloaded release binary against its checksum or signature
+    Verify {
+        /// File to verify
+        file: std::path::PathBuf,
+        /// Expected SHA-256 digest (hex, or the contents of a .sha256 file)
+        #[arg(long)]
+        sha256: Option<String>,
+        /// Minisign signature file (.minisig)
+        #[arg(long)]
+        minisign_sig: Option<std::path::PathBuf>,
+        /// Minisign public key (base64) or path to the .pub file
+        #[arg(long)]
+        minisign_key: Option<String>,
+    },
     /// Remove ZeptoClaw state and optionally the current binary
     Uninstall {
         /// Also remove the current zeptoclaw binary for direct file installs
@@ -429,6 +481,11 @@ pub enum HandAction {
     Deactivate,
     /// Show currently active hand
     Status,
+    /// Check custom hands for manifest errors before deploying them
+    Validate {
+        /// Hands directory or single hand directory (default: ~/.zeptoclaw/hands)
+        dir: Option<std::path::PathBuf>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -514,6 +571,19 @@ pub enum HistoryAction {
     },
 }
 
+#[derive(Subcommand)]
+pub enum SessionsAction {
+    /// Delete sessions not updated within the TTL
+    Gc {
+        /// TTL in days (defaults to session.session_ttl_days)
+        #[arg(long)]
+        ttl_days: Option<u32>,
+        /// Only list the sessions that would be deleted
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
 #[derive(Subcommand)]
 pub enum TemplateAction {
     /// List available templates (built-in + user-defined)
@@ -548,6 +618,24 @@ pub enum PairAction {
     },
 }
 
+#[derive(Subcommand)]
+pub enum CacheAction {
+    /// Show response cache statistics
+    Stats,
+    /// Remove cached responses by model, by age, or entirely
+    Purge {
+        /// Only remove entries produced by this model
+        #[arg(long)]
+        model: Option<String>,
+        /// Only remove entries older than this age (e.g. 90s, 30m, 12h, 7d)
+        #[arg(long)]
+        older_than: Option<String>,
+        /// Remove every cached response
+        #[arg(long, conflicts_with_all = ["model", "older_than"])]
+        all: bool,
+    },
+}
+
 #[derive(Subcommand)]
 pub enum QuotaSubcommand {
     /// Show current quota usage for all providers
@@ -555,7 +643,11 @@ pub enum QuotaSubcommand {
     /// Reset quota usage for a specific provider, or all providers if omitted
     Reset {
         /// Provider name to reset (e.g., "anthropic"). Omit to reset all.
+        #[arg(long)]
         provider: Option<String>,
+        /// Positional form of `--provider`, kept for backward compatibility.
+        #[arg(hide = true, conflicts_with = "provider")]
+        name: Option<String>,
     },
 }
 
@@ -589,16 +681,25 @@ pub async fn run() -> Result<()> {
     // defaults if the config file is missing or unreadable.
     let cli = Cli::parse();
 
-    let mut logging_cfg = zeptoclaw::config::Config::load()
-        .map(|c| c.logging)
+    let loaded = zeptoclaw::config::Config::load().ok();
+    // Outbound HTTP settings (proxy, timeouts, CA certs) must be in place
+    // before any provider or tool builds its client.
+    if let Some(config) = &loaded {
+        zeptoclaw::utils::http::configure(&config.http);
+        zeptoclaw::providers::debug_log::configure(&config.providers.debug_log);
+    }
+    let otlp_cfg = loaded
+        .as_ref()
+        .map(|c| c.telemetry.otlp.clone())
         .unwrap_or_default();
+    let mut logging_cfg = loaded.map(|c| c.logging).unwrap_or_default();
 
     // CLI agent mode defaults to warn-level logging to keep output clean.
     // Gateway and other long-running modes keep info-level for operational visibility.
     // Users can still override with RUST_LOG=info.
     if matches!(
         cli.command,
-        Some(Commands::Agent { .. } | Commands::Batch { .. })
+        Some(Commands::Agent { .. } | Commands::Ask { .. } | Commands::Batch { .. })
     ) && std::env::var("RUST_LOG").is_err()
     {
         logging_cfg.level = "warn".to_string();
@@ -617,7 +718,8 @@ pub async fn run() -> Result<()> {
         logging_cfg.level = "off".to_string();
     }
 
-    zeptoclaw::utils::logging::init_logging(&logging_cfg);
+    // Held until `run` returns so pending OpenTelemetry exports are flushed.
+    let _logging_guard = zeptoclaw::utils::logging::init_logging(&logging_cfg, &otlp_cfg);
 
     match cli.command {
         None => {
@@ -643,6 +745,13 @@ pub async fn run() -> Result<()> {
         }) => {
             agent::cmd_agent(message, template, no_stream, dry_run, mode).await?;
         }
+        Some(Commands::Ask {
+            prompt,
+            json,
+            no_stream,
+        }) => {
+            ask::cmd_ask(prompt, json, no_stream).await?;
+        }
         Some(Commands::Batch {
             input,
             output,
@@ -668,6 +777,9 @@ pub async fn run() -> Result<()> {
         Some(Commands::History { action }) => {
             history::cmd_history(action).await?;
         }
+        Some(Commands::Sessions { action }) => {
+            sessions::cmd_sessions(action).await?;
+        }
         Some(Commands::Memory { action }) => {
             memory::cmd_memory(action).await?;
         }
@@ -708,12 +820,21 @@ pub async fn run() -> Result<()> {
         Some(Commands::Pair { action }) => {
             pair::cmd_pair(action).await?;
         }
+        Some(Commands::Cache { action }) => {
+            cache::cmd_cache(action)?;
+        }
         Some(Commands::Quota { action }) => {
             quota::cmd_quota(action)?;
         }
+        Some(Commands::Usage { json }) => {
+            usage::cmd_usage(json)?;
+        }
         Some(Commands::Provider { action }) => {
             provider::cmd_provider(action)?;
         }
+        Some(Commands::ReplayLast { provider }) => {
+            provider::cmd_replay_last(&provider).await?;
+        }
         Some(Commands::Panel {
             action,
             dev,
@@ -750,6 +871,14 @@ pub async fn run() -> Result<()> {
         }) => {
             update::cmd_update(check, version, force).await?;
         }
+        Some(Commands::Verify {
+            file,
+            sha256,
+            minisign_sig,
+            minisign_key,
+        }) => {
+            verify::cmd_verify(file, sha256, minisign_sig, minisign_key).await?;
+        }
         Some(Commands::Uninstall { remove_binary, yes }) => {
             uninstall::cmd_uninstall(remove_binary, yes).await?;
         }
@@ -985,6 +1114,33 @@ async fn cmd_mcp_server(http: Option<String>) -> Result<()> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn quota_reset_accepts_flag_and_positional_provider() {
+        let cli = Cli::try_parse_from(["zeptoclaw", "quota", "reset", "--provider", "openai"])
+            .expect("parse --provider");
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Quota {
+                action: QuotaSubcommand::Reset {
+                    provider: Some(ref p),
+                    name: None,
+                }
+            }) if p == "openai"
+        ));
+
+        let cli = Cli::try_parse_from(["zeptoclaw", "quota", "reset", "openai"])
+            .expect("parse positional provider");
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Quota {
+                action: QuotaSubcommand::Reset {
+                    provider: None,
+                    name: Some(ref p),
+                }
+            }) if p == "openai"
+        ));
+    }
+
     #[cfg(not(feature = "panel"))]
     #[test]
     fn panel_subcommand_is_recognized_without_panel_feature() {
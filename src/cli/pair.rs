@@ -19,7 +19,11 @@ pub(crate) async fn cmd_pair(action: PairAction) -> Result<()> {
 
 /// Generate a new pairing code and display it.
 async fn cmd_pair_new(config: &Config) -> Result<()> {
-    let mut mgr = PairingManager::new(config.pairing.max_attempts, config.pairing.lockout_secs);
+    let mut mgr = PairingManager::with_storage(
+        config.pairing.max_attempts,
+        config.pairing.lockout_secs,
+        &config.storage,
+    );
 
     let code = mgr.generate_pairing_code();
     println!("Pairing code: {}", code);
@@ -48,7 +52,11 @@ async fn cmd_pair_new(config: &Config) -> Result<()> {
 
 /// List all paired devices.
 async fn cmd_pair_list(config: &Config) -> Result<()> {
-    let mgr = PairingManager::new(config.pairing.max_attempts, config.pairing.lockout_secs);
+    let mgr = PairingManager::with_storage(
+        config.pairing.max_attempts,
+        config.pairing.lockout_secs,
+        &config.storage,
+    );
 
     let devices = mgr.list_devices();
     if devices.is_empty() {
@@ -71,7 +79,11 @@ async fn cmd_pair_list(config: &Config) -> Result<()> {
 
 /// Revoke a paired device by name.
 async fn cmd_pair_revoke(config: &Config, device_name: &str) -> Result<()> {
-    let mut mgr = PairingManager::new(config.pairing.max_attempts, config.pairing.lockout_secs);
+    let mut mgr = PairingManager::with_storage(
+        config.pairing.max_attempts,
+        config.pairing.lockout_secs,
+        &config.storage,
+    );
 
     if mgr.revoke(device_name) {
         println!("Device '{}' has been revoked.", device_name);
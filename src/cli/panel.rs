@@ -9,6 +9,7 @@ use zeptoclaw::api::events::EventBus;
 use zeptoclaw::api::server::{start_server, AppState};
 use zeptoclaw::config::Config;
 use zeptoclaw::providers::openai::OpenAIProvider;
+use zeptoclaw::security::LockoutTracker;
 
 /// Panel subcommands.
 #[derive(clap::Subcommand, Debug)]
@@ -184,6 +185,11 @@ async fn cmd_start(
 
     let event_bus = EventBus::new(256);
     let mut state = AppState::new(api_token.clone(), event_bus);
+    state.config_path = Some(Config::path());
+    state.login_lockout = Arc::new(std::sync::Mutex::new(LockoutTracker::new(
+        panel_config.login_max_attempts,
+        panel_config.login_lockout_secs,
+    )));
 
     // Wire in the LLM provider so /v1/chat/completions works.
     if let Some(ref openai_cfg) = config.providers.openai {
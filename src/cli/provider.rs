@@ -1,7 +1,9 @@
 //! Provider chain status command handler.
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use zeptoclaw::config::Config;
+use zeptoclaw::kernel::provider::provider_from_runtime_selection;
+use zeptoclaw::providers::replay::{replay, FailureStore};
 use zeptoclaw::providers::{resolve_runtime_providers, QuotaStore};
 
 use super::ProviderSubcommand;
@@ -77,7 +79,7 @@ fn print_provider_status() -> Result<()> {
             .unwrap_or_default(),
     );
 
-    let store = QuotaStore::load_or_default();
+    let store = QuotaStore::open(&config.storage);
     let snapshot = store.snapshot();
     if !snapshot.is_empty() {
         println!("\nQuota Usage:");
@@ -100,6 +102,55 @@ fn print_provider_status() -> Result<()> {
     Ok(())
 }
 
+/// Handle `zeptoclaw replay-last <provider>`.
+pub(crate) async fn cmd_replay_last(name: &str) -> Result<()> {
+    let store = FailureStore::new(FailureStore::default_dir());
+    let Some(failed) = store.load(name)? else {
+        println!("No failed request captured for '{}'.", name);
+        return Ok(());
+    };
+
+    let config = Config::load()?;
+    let Some(selection) = resolve_runtime_providers(&config)
+        .into_iter()
+        .find(|s| s.name == name)
+    else {
+        bail!("Provider '{}' is not configured", name);
+    };
+    let Some(provider) =
+        provider_from_runtime_selection(&selection, &config.agents.defaults.model).await
+    else {
+        bail!("Provider '{}' could not be built from config", name);
+    };
+
+    println!(
+        "Last failure of {} at {}:",
+        failed.provider, failed.failed_at
+    );
+    println!("  error:    {}", failed.error);
+    println!(
+        "  model:    {}",
+        failed.model.as_deref().unwrap_or(provider.default_model())
+    );
+    println!(
+        "  request:  {} message(s), {} tool(s) (PII and secrets were redacted)",
+        failed.messages.len(),
+        failed.tools.len()
+    );
+    println!("\nReplaying...");
+
+    match replay(provider.as_ref(), &failed).await {
+        Ok(response) => {
+            println!("Succeeded. The failure no longer reproduces.");
+            if !response.content.is_empty() {
+                println!("\n{}", response.content);
+            }
+            Ok(())
+        }
+        Err(e) => bail!("Replay failed again: {}", e),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,15 +2,17 @@
 
 use anyhow::Result;
 
+use zeptoclaw::config::Config;
 use zeptoclaw::providers::QuotaStore;
 
 use super::QuotaSubcommand;
 
 /// Handle `zeptoclaw quota` subcommands.
 pub(crate) fn cmd_quota(action: QuotaSubcommand) -> Result<()> {
+    let storage = Config::load().unwrap_or_default().storage;
     match action {
         QuotaSubcommand::Status => {
-            let store = QuotaStore::load_or_default();
+            let store = QuotaStore::open(&storage);
             let snapshot = store.snapshot();
 
             if snapshot.is_empty() {
@@ -39,17 +41,23 @@ pub(crate) fn cmd_quota(action: QuotaSubcommand) -> Result<()> {
                 );
             }
         }
-        QuotaSubcommand::Reset { provider } => {
-            let store = QuotaStore::load_or_default();
-            match provider {
+        QuotaSubcommand::Reset { provider, name } => {
+            let store = QuotaStore::open(&storage);
+            match provider.or(name) {
                 Some(name) => {
                     let normalized = name.trim().to_lowercase();
-                    store.reset_provider(&normalized);
-                    println!("Reset quota usage for: {}", normalized);
+                    if store.reset(&normalized) {
+                        println!("Reset quota usage for: {}", normalized);
+                    } else {
+                        println!(
+                            "No quota usage recorded for '{}'; nothing to reset.",
+                            normalized
+                        );
+                    }
                 }
                 None => {
-                    store.reset_all();
-                    println!("Reset all quota usage.");
+                    let cleared = store.reset_all();
+                    println!("Reset {} quota counter(s).", cleared);
                 }
             }
         }
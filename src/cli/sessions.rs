@@ -0,0 +1,58 @@
+//! Session maintenance command handler.
+
+use std::collections::HashSet;
+
+use anyhow::{Context, Result};
+
+use zeptoclaw::config::Config;
+use zeptoclaw::session::uploads::UploadStore;
+use zeptoclaw::session::{ConversationHistory, SessionManager};
+
+use super::SessionsAction;
+
+/// Manage stored sessions.
+pub(crate) async fn cmd_sessions(action: SessionsAction) -> Result<()> {
+    match action {
+        SessionsAction::Gc { ttl_days, dry_run } => {
+            let config = Config::load().with_context(|| "Failed to load configuration")?;
+            let ttl_days = ttl_days.unwrap_or(config.session.session_ttl_days);
+            if ttl_days == 0 {
+                anyhow::bail!(
+                    "No session TTL set. Pass --ttl-days or set session.session_ttl_days"
+                );
+            }
+            let dry_run = dry_run || config.session.gc_dry_run;
+
+            // The most recent CLI conversation is the one `zeptoclaw agent`
+            // resumes, so treat it as active.
+            let mut active = HashSet::new();
+            if let Some(entry) = ConversationHistory::new()
+                .ok()
+                .and_then(|history| history.latest_conversation().ok().flatten())
+            {
+                active.insert(entry.session_key);
+            }
+
+            let manager = SessionManager::new()
+                .with_context(|| "Failed to open session store")?
+                .with_uploads(UploadStore::new(config.workspace_path()));
+            let stale = manager.collect_garbage(ttl_days, dry_run, &active).await?;
+            if stale.is_empty() {
+                println!("No sessions older than {} day(s).", ttl_days);
+                return Ok(());
+            }
+            let verb = if dry_run { "Would delete" } else { "Deleted" };
+            println!(
+                "{} {} session(s) older than {} day(s):",
+                verb,
+                stale.len(),
+                ttl_days
+            );
+            for key in stale {
+                println!("- {}", key);
+            }
+        }
+    }
+
+    Ok(())
+}
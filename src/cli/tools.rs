@@ -92,6 +92,41 @@ const TOOLS: &[ToolInfo] = &[
         config_hint: "",
         opt_in: false,
     },
+    ToolInfo {
+        name: "kv",
+        description: "Per-conversation or global key-value state (counters, flags)",
+        requires_config: false,
+        config_hint: "",
+        opt_in: false,
+    },
+    ToolInfo {
+        name: "preferences",
+        description: "Per-user persistent preferences (tone, units, language, hand)",
+        requires_config: false,
+        config_hint: "",
+        opt_in: false,
+    },
+    ToolInfo {
+        name: "compact_session",
+        description: "Summarize old conversation turns to reduce context size",
+        requires_config: false,
+        config_hint: "",
+        opt_in: false,
+    },
+    ToolInfo {
+        name: "pin",
+        description: "Pin conversation context that survives truncation",
+        requires_config: false,
+        config_hint: "",
+        opt_in: false,
+    },
+    ToolInfo {
+        name: "cache",
+        description: "Inspect and purge the LLM response cache",
+        requires_config: true,
+        config_hint: "Set cache.enabled = true",
+        opt_in: false,
+    },
     ToolInfo {
         name: "message",
         description: "Send proactive messages to channels",
@@ -177,6 +212,20 @@ const TOOLS: &[ToolInfo] = &[
         config_hint: "",
         opt_in: true,
     },
+    ToolInfo {
+        name: "index_workspace",
+        description: "Embed workspace files into a local semantic index",
+        requires_config: false,
+        config_hint: "",
+        opt_in: true,
+    },
+    ToolInfo {
+        name: "rag_query",
+        description: "Semantic search over indexed workspace files with citations",
+        requires_config: false,
+        config_hint: "",
+        opt_in: true,
+    },
 ];
 
 pub(crate) async fn cmd_tools(action: ToolsAction) -> Result<()> {
@@ -330,6 +379,7 @@ fn is_tool_configured(config: &Config, name: &str) -> bool {
         }
         "r8r" => std::env::var("R8R_API_URL").is_ok(),
         "browser" => config.tools.browser.enabled,
+        "cache" => config.cache.enabled,
         _ => true,
     }
 }
@@ -371,7 +421,7 @@ mod tests {
 
     #[test]
     fn test_tools_list_count() {
-        assert_eq!(TOOLS.len(), 23);
+        assert_eq!(TOOLS.len(), 30);
     }
 
     #[test]
@@ -0,0 +1,342 @@
+//! Usage report command handler.
+//!
+//! Reads `~/.zeptoclaw/quota/usage.json` and prints per-provider spend for the
+//! current quota period alongside the configured limits. Models with their
+//! own `per_model` quota are listed under their provider.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use serde::Serialize;
+
+use zeptoclaw::config::Config;
+use zeptoclaw::providers::quota::{QuotaUsage, AGGREGATE_QUOTA_NAME};
+use zeptoclaw::providers::{provider_config_by_name, QuotaPeriod, QuotaStore};
+use zeptoclaw::utils::format::DisplayFormat;
+
+/// One provider's usage for its current quota period.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct UsageRow {
+    provider: String,
+    /// Set for a per-model counter of `provider`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    model: Option<String>,
+    period_key: String,
+    cost_usd: f64,
+    tokens: u64,
+    max_cost_usd: Option<f64>,
+    max_tokens: Option<u64>,
+    /// Percentage of `max_cost_usd` used (`None` when no cost limit).
+    cost_pct: Option<f64>,
+    /// Percentage of `max_tokens` used (`None` when no token limit).
+    tokens_pct: Option<f64>,
+}
+
+/// Handle `zeptoclaw usage`.
+pub(crate) fn cmd_usage(json: bool) -> Result<()> {
+    let config = Config::load().unwrap_or_default();
+    let snapshot = QuotaStore::open(&config.storage).snapshot();
+    let rows = build_report(&snapshot, &config);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&rows)?);
+    } else {
+        let display =
+            DisplayFormat::from_names(&config.agents.defaults.timezone, &config.locale.default);
+        print!("{}", format_table(&rows, &display));
+    }
+    Ok(())
+}
+
+/// Build report rows sorted by provider name, each provider followed by its
+/// per-model rows.
+///
+/// Counters left over from an earlier period are reported as zero usage for
+/// the current period, matching how the quota check treats them. Per-model
+/// rows use the model's `per_model` entry for their period and limits, and
+/// the `aggregate` row uses `providers.aggregate_quota`.
+fn build_report(snapshot: &HashMap<String, QuotaUsage>, config: &Config) -> Vec<UsageRow> {
+    let mut rows: Vec<UsageRow> = snapshot
+        .iter()
+        .map(|(key, usage)| {
+            let (provider, model) = QuotaStore::split_model_key(key);
+            let provider_quota = if provider == AGGREGATE_QUOTA_NAME {
+                config.providers.aggregate_quota.as_ref()
+            } else {
+                provider_config_by_name(config, provider).and_then(|p| p.quota.as_ref())
+            };
+            let quota = match model {
+                Some(model) => provider_quota.and_then(|q| q.per_model.get(model)),
+                None => provider_quota,
+            };
+            let period = quota.map_or(QuotaPeriod::Monthly, |q| q.period.clone());
+            let period_key = QuotaStore::current_period_key(&period);
+            let (cost_usd, tokens) = if usage.period_key == period_key {
+                (usage.cost_usd, usage.tokens)
+            } else {
+                (0.0, 0)
+            };
+            let max_cost_usd = quota.and_then(|q| q.max_cost_usd);
+            let max_tokens = quota.and_then(|q| q.max_tokens);
+            UsageRow {
+                provider: provider.to_string(),
+                model: model.map(str::to_string),
+                period_key,
+                cost_usd,
+                tokens,
+                max_cost_usd,
+                max_tokens,
+                cost_pct: max_cost_usd.map(|max| percent(cost_usd, max)),
+                tokens_pct: max_tokens.map(|max| percent(tokens as f64, max as f64)),
+            }
+        })
+        .collect();
+    rows.sort_by(|a, b| (&a.provider, &a.model).cmp(&(&b.provider, &b.model)));
+    rows
+}
+
+fn percent(used: f64, limit: f64) -> f64 {
+    if limit > 0.0 {
+        used / limit * 100.0
+    } else {
+        0.0
+    }
+}
+
+/// Human-readable table; `--json` keeps the raw numbers.
+fn format_table(rows: &[UsageRow], display: &DisplayFormat) -> String {
+    if rows.is_empty() {
+        return "No usage recorded yet.\n".to_string();
+    }
+
+    let mut out = format!(
+        "{:<16} {:<12} {:<22} {:<22}\n",
+        "Provider", "Period", "Cost", "Tokens"
+    );
+    out.push_str(&"-".repeat(74));
+    out.push('\n');
+    for row in rows {
+        let cost = match (row.max_cost_usd, row.cost_pct) {
+            (Some(max), Some(pct)) => format!(
+                "{} / {} ({:.0}%)",
+                display.usd(row.cost_usd),
+                display.usd(max),
+                pct
+            ),
+            _ => display.usd(row.cost_usd),
+        };
+        let tokens = match (row.max_tokens, row.tokens_pct) {
+            (Some(max), Some(pct)) => format!(
+                "{} / {} ({:.0}%)",
+                display.count(row.tokens),
+                display.count(max),
+                pct
+            ),
+            _ => display.count(row.tokens),
+        };
+        let name = match &row.model {
+            Some(model) => format!("  {model}"),
+            None => row.provider.clone(),
+        };
+        out.push_str(&format!(
+            "{:<16} {:<12} {:<22} {:<22}\n",
+            name, row.period_key, cost, tokens
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zeptoclaw::config::ProviderConfig;
+    use zeptoclaw::providers::QuotaConfig;
+
+    fn fixture() -> (HashMap<String, QuotaUsage>, Config) {
+        let monthly = QuotaStore::current_period_key(&QuotaPeriod::Monthly);
+        let mut snapshot = HashMap::new();
+        snapshot.insert(
+            "openai".to_string(),
+            QuotaUsage {
+                period_key: monthly.clone(),
+                cost_usd: 2.5,
+                tokens: 1_000,
+            },
+        );
+        snapshot.insert(
+            "anthropic".to_string(),
+            QuotaUsage {
+                period_key: monthly,
+                cost_usd: 12.5,
+                tokens: 40_000,
+            },
+        );
+        snapshot.insert(
+            "groq".to_string(),
+            QuotaUsage {
+                period_key: "1999-01".to_string(),
+                cost_usd: 99.0,
+                tokens: 99,
+            },
+        );
+
+        let mut config = Config::default();
+        config.providers.anthropic = Some(ProviderConfig {
+            quota: Some(QuotaConfig {
+                max_cost_usd: Some(50.0),
+                max_tokens: Some(100_000),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        (snapshot, config)
+    }
+
+    #[test]
+    fn test_report_rows_sorted_with_limits() {
+        let (snapshot, config) = fixture();
+        let rows = build_report(&snapshot, &config);
+        let names: Vec<_> = rows.iter().map(|r| r.provider.as_str()).collect();
+        assert_eq!(names, ["anthropic", "groq", "openai"]);
+
+        let anthropic = &rows[0];
+        assert_eq!(anthropic.cost_pct, Some(25.0));
+        assert_eq!(anthropic.tokens_pct, Some(40.0));
+
+        let openai = &rows[2];
+        assert_eq!(openai.max_cost_usd, None);
+        assert_eq!(openai.cost_pct, None);
+    }
+
+    #[test]
+    fn test_report_zeroes_stale_period() {
+        let (snapshot, config) = fixture();
+        let rows = build_report(&snapshot, &config);
+        let groq = rows.iter().find(|r| r.provider == "groq").unwrap();
+        assert_eq!(groq.cost_usd, 0.0);
+        assert_eq!(groq.tokens, 0);
+        assert_eq!(
+            groq.period_key,
+            QuotaStore::current_period_key(&QuotaPeriod::Monthly)
+        );
+    }
+
+    #[test]
+    fn test_format_table() {
+        let (snapshot, config) = fixture();
+        let rows = build_report(&snapshot, &config);
+        let table = format_table(&rows, &DisplayFormat::default());
+        assert!(table.starts_with("Provider"));
+        assert!(table.contains("$12.50 / $50.00 (25%)"));
+        assert!(table.contains("40,000 / 100,000 (40%)"));
+        assert!(table.contains("$2.50"));
+        assert_eq!(table.lines().count(), 2 + 3);
+
+        let german = format_table(&rows, &DisplayFormat::from_names("UTC", "de"));
+        assert!(german.contains("40.000 / 100.000 (40%)"));
+        assert!(german.contains("$12,50 / $50,00 (25%)"));
+    }
+
+    #[test]
+    fn test_report_per_model_rows_use_model_quota() {
+        let (mut snapshot, mut config) = fixture();
+        let daily = QuotaStore::current_period_key(&QuotaPeriod::Daily);
+        snapshot.insert(
+            QuotaStore::model_key("anthropic", "opus"),
+            QuotaUsage {
+                period_key: daily.clone(),
+                cost_usd: 5.0,
+                tokens: 2_000,
+            },
+        );
+        snapshot.insert(
+            "anthropic-proxy".to_string(),
+            QuotaUsage {
+                period_key: "1999-01".to_string(),
+                cost_usd: 0.0,
+                tokens: 0,
+            },
+        );
+        let quota = config
+            .providers
+            .anthropic
+            .as_mut()
+            .unwrap()
+            .quota
+            .as_mut()
+            .unwrap();
+        quota.per_model.insert(
+            "opus".to_string(),
+            QuotaConfig {
+                max_cost_usd: Some(10.0),
+                period: QuotaPeriod::Daily,
+                ..Default::default()
+            },
+        );
+
+        let rows = build_report(&snapshot, &config);
+        let keys: Vec<_> = rows
+            .iter()
+            .map(|r| (r.provider.as_str(), r.model.as_deref()))
+            .collect();
+        assert_eq!(
+            keys[..3],
+            [
+                ("anthropic", None),
+                ("anthropic", Some("opus")),
+                ("anthropic-proxy", None)
+            ]
+        );
+        let opus = &rows[1];
+        assert_eq!(opus.period_key, daily);
+        assert_eq!(opus.cost_usd, 5.0);
+        assert_eq!(opus.max_cost_usd, Some(10.0));
+        assert_eq!(opus.cost_pct, Some(50.0));
+        assert_eq!(opus.max_tokens, None);
+
+        let table = format_table(&rows, &DisplayFormat::default());
+        assert!(table.contains("  opus"));
+        assert!(table.contains("$5.00 / $10.00 (50%)"));
+    }
+
+    #[test]
+    fn test_report_aggregate_row_uses_aggregate_quota() {
+        let (mut snapshot, mut config) = fixture();
+        snapshot.insert(
+            AGGREGATE_QUOTA_NAME.to_string(),
+            QuotaUsage {
+                period_key: QuotaStore::current_period_key(&QuotaPeriod::Monthly),
+                cost_usd: 15.0,
+                tokens: 41_000,
+            },
+        );
+        config.providers.aggregate_quota = Some(QuotaConfig {
+            max_cost_usd: Some(100.0),
+            ..Default::default()
+        });
+
+        let rows = build_report(&snapshot, &config);
+        let aggregate = rows
+            .iter()
+            .find(|r| r.provider == AGGREGATE_QUOTA_NAME)
+            .unwrap();
+        assert_eq!(aggregate.cost_pct, Some(15.0));
+    }
+
+    #[test]
+    fn test_format_table_empty() {
+        assert_eq!(
+            format_table(&[], &DisplayFormat::default()),
+            "No usage recorded yet.\n"
+        );
+    }
+
+    #[test]
+    fn test_report_json_shape() {
+        let (snapshot, config) = fixture();
+        let json = serde_json::to_value(build_report(&snapshot, &config)).unwrap();
+        assert_eq!(json[0]["provider"], "anthropic");
+        assert_eq!(json[0]["cost_pct"], 25.0);
+        assert!(json[2]["max_tokens"].is_null());
+    }
+}
@@ -0,0 +1,291 @@
+//! Release artifact verification.
+//!
+//! `zeptoclaw verify <file> --sha256 <hash>` compares the file's SHA-256
+//! digest with the published one, and `--minisign-sig` / `--minisign-key`
+//! additionally check a minisign signature, so install scripts can gate on
+//! the exit code. Both minisign signature kinds are verified: legacy (`Ed`,
+//! Ed25519 over the file) and prehashed (`ED`, Ed25519 over the file's
+//! BLAKE2b-512 digest, the default since minisign 0.10). Other signature
+//! formats, such as cosign/sigstore bundles, are not supported; verify those
+//! with `cosign verify-blob`.
+
+use std::borrow::Cow;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use base64::Engine as _;
+use ring::signature::{UnparsedPublicKey, ED25519};
+use sha2::{Digest, Sha256};
+
+/// Parse an expected SHA-256 digest.
+///
+/// Accepts a bare hex digest, the `<hex>  <filename>` line of a `.sha256`
+/// file, or a `sha256:<hex>` reference, in any case.
+pub(crate) fn parse_sha256(input: &str) -> Result<String> {
+    let token = input.split_whitespace().next().unwrap_or_default();
+    let hex_digest = token.strip_prefix("sha256:").unwrap_or(token);
+    if hex_digest.len() != 64 || !hex_digest.chars().all(|c| c.is_ascii_hexdigit()) {
+        bail!(
+            "malformed SHA-256 digest '{}': expected 64 hex characters",
+            input.trim()
+        );
+    }
+    Ok(hex_digest.to_ascii_lowercase())
+}
+
+/// SHA-256 digest of the file at `path`, as lowercase hex.
+pub(crate) fn sha256_file(path: &Path) -> Result<String> {
+    let mut file =
+        std::fs::File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+    let mut hasher = Sha256::new();
+    let mut buf = [0u8; 64 * 1024];
+    loop {
+        let n = file
+            .read(&mut buf)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Check `actual` against an expected digest as given on the command line.
+pub(crate) fn verify_sha256(actual: &str, expected: &str) -> Result<()> {
+    let expected = parse_sha256(expected)?;
+    if actual != expected {
+        bail!("SHA-256 mismatch!\n  expected: {expected}\n  actual:   {actual}");
+    }
+    Ok(())
+}
+
+/// A minisign public key: key ID and Ed25519 key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct MinisignPublicKey {
+    key_id: [u8; 8],
+    key: [u8; 32],
+}
+
+/// Parse a minisign public key from its base64 form or the contents of a
+/// `.pub` file (comment lines are skipped).
+pub(crate) fn parse_minisign_public_key(input: &str) -> Result<MinisignPublicKey> {
+    let encoded = input
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with("untrusted comment:"))
+        .unwrap_or_default();
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(encoded)
+        .context("malformed minisign public key: not base64")?;
+    if bytes.len() != 42 || &bytes[..2] != b"Ed" {
+        bail!("malformed minisign public key: expected an Ed25519 key");
+    }
+    let mut key_id = [0u8; 8];
+    key_id.copy_from_slice(&bytes[2..10]);
+    let mut key = [0u8; 32];
+    key.copy_from_slice(&bytes[10..42]);
+    Ok(MinisignPublicKey { key_id, key })
+}
+
+/// Verify a minisign signature file over `data`, including the signature
+/// on its trusted comment. Returns the trusted comment.
+pub(crate) fn verify_minisign(
+    data: &[u8],
+    signature_file: &str,
+    public_key: &MinisignPublicKey,
+) -> Result<String> {
+    let mut lines = signature_file.lines().map(str::trim_end);
+    let _untrusted = lines.next();
+    let sig_line = lines.next().unwrap_or_default();
+    let trusted_comment = lines
+        .next()
+        .and_then(|line| line.strip_prefix("trusted comment: "))
+        .context("malformed minisign signature: missing trusted comment")?;
+    let global_line = lines.next().unwrap_or_default();
+
+    let engine = base64::engine::general_purpose::STANDARD;
+    let sig = engine
+        .decode(sig_line)
+        .context("malformed minisign signature: not base64")?;
+    if sig.len() != 74 {
+        bail!("malformed minisign signature: unexpected length");
+    }
+    let message = match &sig[..2] {
+        b"Ed" => Cow::Borrowed(data),
+        b"ED" => Cow::Owned(blake2b512(data)),
+        _ => bail!("malformed minisign signature: unknown algorithm"),
+    };
+    if sig[2..10] != public_key.key_id {
+        bail!("minisign signature was made with a different key");
+    }
+
+    let key = UnparsedPublicKey::new(&ED25519, &public_key.key);
+    key.verify(&message, &sig[10..74])
+        .map_err(|_| anyhow::anyhow!("minisign signature verification failed"))?;
+
+    let global = engine
+        .decode(global_line)
+        .context("malformed minisign signature: global signature not base64")?;
+    let mut signed_comment = sig[10..74].to_vec();
+    signed_comment.extend_from_slice(trusted_comment.as_bytes());
+    key.verify(&signed_comment, &global)
+        .map_err(|_| anyhow::anyhow!("minisign trusted comment verification failed"))?;
+
+    Ok(trusted_comment.to_string())
+}
+
+/// BLAKE2b-512 digest of `data`, the message a prehashed (`ED`) minisign
+/// signature covers.
+fn blake2b512(data: &[u8]) -> Vec<u8> {
+    use blake2::Digest as _;
+    blake2::Blake2b512::digest(data).to_vec()
+}
+
+/// Read a value that may be given inline or as a path to a file.
+fn inline_or_file(value: &str) -> Result<String> {
+    let path = Path::new(value);
+    if path.is_file() {
+        std::fs::read_to_string(path).with_context(|| format!("failed to read {}", value))
+    } else {
+        Ok(value.to_string())
+    }
+}
+
+pub(crate) async fn cmd_verify(
+    file: PathBuf,
+    sha256: Option<String>,
+    minisign_sig: Option<PathBuf>,
+    minisign_key: Option<String>,
+) -> Result<()> {
+    if sha256.is_none() && minisign_sig.is_none() {
+        bail!("nothing to verify: pass --sha256 and/or --minisign-sig");
+    }
+
+    if let Some(expected) = sha256 {
+        let actual = sha256_file(&file)?;
+        verify_sha256(&actual, &expected)?;
+        println!("SHA-256 OK: {}", file.display());
+    }
+
+    if let Some(sig_path) = minisign_sig {
+        let key = minisign_key.context("--minisign-sig requires --minisign-key")?;
+        let public_key = parse_minisign_public_key(&inline_or_file(&key)?)?;
+        let signature = std::fs::read_to_string(&sig_path)
+            .with_context(|| format!("failed to read {}", sig_path.display()))?;
+        let data =
+            std::fs::read(&file).with_context(|| format!("failed to read {}", file.display()))?;
+        let comment = verify_minisign(&data, &signature, &public_key)?;
+        println!("Signature OK: {} ({})", file.display(), comment);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ring::rand::SystemRandom;
+    use ring::signature::{Ed25519KeyPair, KeyPair};
+
+    const HELLO_SHA256: &str = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+
+    #[test]
+    fn test_sha256_comparison_passes_and_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("zeptoclaw-linux-aarch64");
+        std::fs::write(&path, b"hello").unwrap();
+        let actual = sha256_file(&path).unwrap();
+
+        assert!(verify_sha256(&actual, HELLO_SHA256).is_ok());
+        assert!(verify_sha256(
+            &actual,
+            &format!("{}  zeptoclaw-linux-aarch64\n", HELLO_SHA256.to_uppercase())
+        )
+        .is_ok());
+        assert!(verify_sha256(&actual, &format!("sha256:{}", HELLO_SHA256)).is_ok());
+
+        let other = "0".repeat(64);
+        let err = verify_sha256(&actual, &other).unwrap_err().to_string();
+        assert!(err.contains("SHA-256 mismatch"));
+    }
+
+    #[test]
+    fn test_malformed_hash_input_is_rejected() {
+        let not_hex = "g".repeat(64);
+        let too_long = "a".repeat(65);
+        for bad in ["", "abc123", not_hex.as_str(), too_long.as_str()] {
+            let err = parse_sha256(bad).unwrap_err().to_string();
+            assert!(err.contains("malformed SHA-256 digest"), "{bad}: {err}");
+        }
+    }
+
+    /// A minisign key pair with its parsed public key.
+    fn minisign_key() -> (Ed25519KeyPair, MinisignPublicKey) {
+        let engine = base64::engine::general_purpose::STANDARD;
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+        let pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+
+        let mut pk = b"Ed".to_vec();
+        pk.extend_from_slice(b"zeptokey");
+        pk.extend_from_slice(pair.public_key().as_ref());
+        let public_key = parse_minisign_public_key(&format!(
+            "untrusted comment: minisign public key\n{}\n",
+            engine.encode(&pk)
+        ))
+        .unwrap();
+        (pair, public_key)
+    }
+
+    /// A minisign signature file over `message` with algorithm tag `alg`.
+    fn minisign_sig(pair: &Ed25519KeyPair, alg: &[u8; 2], message: &[u8], comment: &str) -> String {
+        let engine = base64::engine::general_purpose::STANDARD;
+        let sig = pair.sign(message);
+        let mut global_msg = sig.as_ref().to_vec();
+        global_msg.extend_from_slice(comment.as_bytes());
+        let mut sig_bytes = alg.to_vec();
+        sig_bytes.extend_from_slice(b"zeptokey");
+        sig_bytes.extend_from_slice(sig.as_ref());
+        format!(
+            "untrusted comment: signature\n{}\ntrusted comment: {}\n{}\n",
+            engine.encode(&sig_bytes),
+            comment,
+            engine.encode(pair.sign(&global_msg).as_ref())
+        )
+    }
+
+    #[test]
+    fn test_minisign_legacy_signature() {
+        let (pair, public_key) = minisign_key();
+        let data = b"release binary";
+        let comment = "timestamp:1700000000\tfile:zeptoclaw";
+        let sig_file = minisign_sig(&pair, b"Ed", data, comment);
+
+        assert_eq!(
+            verify_minisign(data, &sig_file, &public_key).unwrap(),
+            comment
+        );
+        assert!(verify_minisign(b"tampered binary", &sig_file, &public_key).is_err());
+        let forged = sig_file.replace("file:zeptoclaw", "file:other");
+        assert!(verify_minisign(data, &forged, &public_key).is_err());
+    }
+
+    #[test]
+    fn test_minisign_prehashed_signature() {
+        let (pair, public_key) = minisign_key();
+        let data = b"release binary";
+        let comment = "timestamp:1700000000\tfile:zeptoclaw\thashed";
+        let sig_file = minisign_sig(&pair, b"ED", &blake2b512(data), comment);
+
+        assert_eq!(
+            verify_minisign(data, &sig_file, &public_key).unwrap(),
+            comment
+        );
+        assert!(verify_minisign(b"tampered binary", &sig_file, &public_key).is_err());
+
+        // A prehashed signature is not accepted as a legacy one.
+        let legacy = minisign_sig(&pair, b"Ed", &blake2b512(data), comment);
+        assert!(verify_minisign(data, &legacy, &public_key).is_err());
+    }
+}
@@ -2,10 +2,14 @@
 
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::{bail, Context, Result};
+use futures::future::BoxFuture;
 use reqwest::Url;
+use zeptoclaw::cron::{CronSchedule, OnMiss};
+use zeptoclaw::scheduler::Scheduler;
+use zeptoclaw::utils::duration::parse_duration_secs;
 
 /// Maximum bytes to read from a watched URL response (800KB, same as web_fetch).
 const MAX_WATCH_BYTES: usize = 800_000;
@@ -15,19 +19,11 @@ const MIN_INTERVAL_SECS: u64 = 10;
 
 /// Parse interval string like "1h", "30m", "15m", "60s" into seconds.
 pub fn parse_interval(s: &str) -> Result<u64> {
-    let s = s.trim().to_lowercase();
-    let secs = if let Some(hours) = s.strip_suffix('h') {
-        let n: u64 = hours.parse().with_context(|| "Invalid hours value")?;
-        n * 3600
-    } else if let Some(mins) = s.strip_suffix('m') {
-        let n: u64 = mins.parse().with_context(|| "Invalid minutes value")?;
-        n * 60
-    } else if let Some(sec_str) = s.strip_suffix('s') {
-        let n: u64 = sec_str.parse().with_context(|| "Invalid seconds value")?;
-        n
-    } else {
-        s.parse::<u64>()
-            .with_context(|| "Invalid interval. Use formats like 1h, 30m, or 60s")?
+    let Some(secs) = parse_duration_secs(s) else {
+        bail!(
+            "Invalid interval '{}'. Use formats like 1h, 30m, or 60s",
+            s.trim()
+        );
     };
 
     if secs < MIN_INTERVAL_SECS {
@@ -118,81 +114,125 @@ pub(crate) async fn cmd_watch(url: String, interval: String, notify: Option<Stri
         .timeout(std::time::Duration::from_secs(30))
         .build()?;
 
-    let mut ticker = tokio::time::interval(std::time::Duration::from_secs(interval_secs));
-
-    loop {
-        ticker.tick().await;
-
-        match client.get(&url).send().await {
-            Ok(resp) => {
-                // Post-redirect SSRF check
-                if zeptoclaw::tools::is_blocked_host(resp.url()) {
-                    eprintln!(
-                        "[{}] Blocked: redirect to local/private host {}",
-                        chrono::Local::now().format("%H:%M"),
-                        resp.url()
-                    );
-                    continue;
-                }
+    // Baseline check now, then every interval on an in-memory scheduler
+    // (an ad-hoc watch has nothing worth persisting across runs).
+    run_check(&client, &url, &snap_path, notify.as_deref()).await;
+    let scheduler = Scheduler::in_memory(OnMiss::Skip);
+    let job_name = format!("watch:{}", url);
+    let job = move || -> BoxFuture<'static, zeptoclaw::error::Result<()>> {
+        let (client, url, snap_path, notify) = (
+            client.clone(),
+            url.clone(),
+            snap_path.clone(),
+            notify.clone(),
+        );
+        Box::pin(async move {
+            run_check(&client, &url, &snap_path, notify.as_deref()).await;
+            Ok(())
+        })
+    };
+    scheduler.register(
+        &job_name,
+        CronSchedule::Every {
+            every_ms: interval_secs as i64 * 1000,
+        },
+        job,
+    )?;
+
+    tokio::signal::ctrl_c().await?;
+    scheduler.stop();
+    Ok(())
+}
 
-                let status = resp.status();
-                if !status.is_success() {
-                    eprintln!(
-                        "[{}] HTTP {} for {}",
-                        chrono::Local::now().format("%H:%M"),
-                        status,
-                        url
-                    );
-                    continue;
-                }
+/// Run [`check_once`], reporting a failure instead of ending the watch.
+async fn run_check(client: &reqwest::Client, url: &str, snap_path: &Path, notify: Option<&str>) {
+    if let Err(e) = check_once(client, url, snap_path, notify).await {
+        eprintln!(
+            "[{}] Check failed: {:#}",
+            chrono::Local::now().format("%H:%M"),
+            e
+        );
+    }
+}
 
-                let body = read_body_limited(resp, MAX_WATCH_BYTES).await?;
-                let previous = std::fs::read_to_string(&snap_path).unwrap_or_default();
-
-                if previous.is_empty() {
-                    // First fetch — save baseline
-                    std::fs::write(&snap_path, &body)?;
-                    println!(
-                        "[{}] Baseline saved ({} bytes)",
-                        chrono::Local::now().format("%H:%M"),
-                        body.len()
-                    );
-                } else if body != previous {
-                    std::fs::write(&snap_path, &body)?;
-                    println!(
-                        "[{}] Change detected! (was {} bytes, now {} bytes)",
-                        chrono::Local::now().format("%H:%M"),
-                        previous.len(),
-                        body.len()
-                    );
-
-                    // Notification message
-                    let message = format!(
-                        "URL changed: {}\nPrevious: {} bytes -> New: {} bytes",
-                        url,
-                        previous.len(),
-                        body.len()
-                    );
-                    if let Some(ref channel) = notify {
-                        println!("  Notification ({}): {}", channel, message);
-                    } else {
-                        println!("  {}", message);
-                    }
-
-                    // TODO: Wire to actual channel send via ChannelManager
-                } else {
-                    eprintln!("[{}] No change", chrono::Local::now().format("%H:%M"));
-                }
+/// Fetch `url` once and compare it with the snapshot at `snap_path`, saving
+/// the new body and printing a notification when it changed.
+async fn check_once(
+    client: &reqwest::Client,
+    url: &str,
+    snap_path: &Path,
+    notify: Option<&str>,
+) -> Result<()> {
+    match client.get(url).send().await {
+        Ok(resp) => {
+            // Post-redirect SSRF check
+            if zeptoclaw::tools::is_blocked_host(resp.url()) {
+                eprintln!(
+                    "[{}] Blocked: redirect to local/private host {}",
+                    chrono::Local::now().format("%H:%M"),
+                    resp.url()
+                );
+                return Ok(());
             }
-            Err(e) => {
+
+            let status = resp.status();
+            if !status.is_success() {
                 eprintln!(
-                    "[{}] Fetch error: {}",
+                    "[{}] HTTP {} for {}",
                     chrono::Local::now().format("%H:%M"),
-                    e
+                    status,
+                    url
                 );
+                return Ok(());
             }
+
+            let body = read_body_limited(resp, MAX_WATCH_BYTES).await?;
+            let previous = std::fs::read_to_string(snap_path).unwrap_or_default();
+
+            if previous.is_empty() {
+                // First fetch — save baseline
+                std::fs::write(snap_path, &body)?;
+                println!(
+                    "[{}] Baseline saved ({} bytes)",
+                    chrono::Local::now().format("%H:%M"),
+                    body.len()
+                );
+            } else if body != previous {
+                std::fs::write(snap_path, &body)?;
+                println!(
+                    "[{}] Change detected! (was {} bytes, now {} bytes)",
+                    chrono::Local::now().format("%H:%M"),
+                    previous.len(),
+                    body.len()
+                );
+
+                // Notification message
+                let message = format!(
+                    "URL changed: {}\nPrevious: {} bytes -> New: {} bytes",
+                    url,
+                    previous.len(),
+                    body.len()
+                );
+                if let Some(channel) = notify {
+                    println!("  Notification ({}): {}", channel, message);
+                } else {
+                    println!("  {}", message);
+                }
+
+                // TODO: Wire to actual channel send via ChannelManager
+            } else {
+                eprintln!("[{}] No change", chrono::Local::now().format("%H:%M"));
+            }
+        }
+        Err(e) => {
+            eprintln!(
+                "[{}] Fetch error: {}",
+                chrono::Local::now().format("%H:%M"),
+                e
+            );
         }
     }
+    Ok(())
 }
 
 #[cfg(test)]
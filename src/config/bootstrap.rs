@@ -0,0 +1,98 @@
+//! First-run workspace bootstrap.
+//!
+//! When `agents.defaults.bootstrap_workspace` is on (the default) and the
+//! configured workspace does not exist, it is created with a short README
+//! and a `.zeptoignore` that keeps common secret files out of workspace
+//! indexing. An existing workspace is never touched.
+
+use std::path::Path;
+
+use tracing::info;
+
+use crate::error::Result;
+use crate::memory::workspace_index::IGNORE_FILE;
+
+/// README written into a freshly created workspace.
+pub const WORKSPACE_README: &str = "\
+# ZeptoClaw workspace
+
+This directory is the agent's workspace. File tools read and write here,
+memory notes and the optional `SOUL.md` persona live here, and the
+semantic index is built from the files in it.
+
+Paths listed in `.zeptoignore` are skipped when indexing the workspace.
+";
+
+/// Ignore file written into a freshly created workspace.
+pub const WORKSPACE_IGNORE: &str = "\
+# Paths the agent skips when indexing this workspace (gitignore-like globs).
+.env
+.env.*
+*.pem
+*.key
+*.p12
+*.pfx
+id_rsa*
+id_ed25519*
+credentials.json
+secrets/
+";
+
+/// Create `workspace` with the default layout if it does not exist.
+///
+/// Returns `true` when the workspace was created and `false` when it already
+/// existed, in which case nothing is written.
+///
+/// # Example
+///
+/// ```rust
+/// use zeptoclaw::config::bootstrap::bootstrap_workspace;
+///
+/// let dir = tempfile::tempdir().unwrap();
+/// let workspace = dir.path().join("workspace");
+/// assert!(bootstrap_workspace(&workspace).unwrap());
+/// assert!(workspace.join("README.md").is_file());
+/// assert!(!bootstrap_workspace(&workspace).unwrap());
+/// ```
+pub fn bootstrap_workspace(workspace: &Path) -> Result<bool> {
+    if workspace.exists() {
+        return Ok(false);
+    }
+    std::fs::create_dir_all(workspace)?;
+    std::fs::write(workspace.join("README.md"), WORKSPACE_README)?;
+    std::fs::write(workspace.join(IGNORE_FILE), WORKSPACE_IGNORE)?;
+    info!(workspace = %workspace.display(), "Created workspace");
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bootstrap_creates_default_layout() {
+        let dir = tempfile::tempdir().unwrap();
+        let workspace = dir.path().join("nested").join("workspace");
+
+        assert!(bootstrap_workspace(&workspace).unwrap());
+        let readme = std::fs::read_to_string(workspace.join("README.md")).unwrap();
+        assert!(readme.starts_with("# ZeptoClaw workspace"));
+        let ignore = std::fs::read_to_string(workspace.join(".zeptoignore")).unwrap();
+        assert!(ignore.lines().any(|line| line == ".env"));
+        assert!(ignore.lines().any(|line| line == "secrets/"));
+    }
+
+    #[test]
+    fn test_bootstrap_leaves_existing_workspace_alone() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("notes.md"), "mine").unwrap();
+
+        assert!(!bootstrap_workspace(dir.path()).unwrap());
+        assert!(!dir.path().join("README.md").exists());
+        assert!(!dir.path().join(".zeptoignore").exists());
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("notes.md")).unwrap(),
+            "mine"
+        );
+    }
+}
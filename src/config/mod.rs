@@ -3,6 +3,7 @@
 //! This module provides configuration loading, saving, and global state management.
 //! Configuration is loaded from `~/.zeptoclaw/config.json` with environment variable overrides.
 
+pub mod bootstrap;
 pub mod templates;
 mod types;
 pub mod validate;
@@ -121,6 +122,25 @@ impl Config {
             )));
         }
 
+        // Out-of-range thresholds fall back to the default at check time.
+        for diag in crate::config::validate::validate_quota_thresholds(&config) {
+            warn!(
+                path = %diag.path,
+                message = %diag.message,
+                "Config quota validation warning"
+            );
+        }
+
+        if let Some(diag) = crate::config::validate::validate_timezone(&config)
+            .into_iter()
+            .next()
+        {
+            return Err(ZeptoError::Config(format!(
+                "Invalid timezone configuration at {}: {}",
+                diag.path, diag.message
+            )));
+        }
+
         Ok(config)
     }
 
@@ -184,6 +204,35 @@ impl Config {
                 _ => {}
             }
         }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_AGENTS_DEFAULTS_MAX_CONCURRENT_RUNS") {
+            if let Ok(v) = val.parse() {
+                self.agents.defaults.max_concurrent_runs = v;
+            }
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_AGENTS_DEFAULTS_RUN_OVERFLOW") {
+            match val.trim().to_ascii_lowercase().as_str() {
+                "queue" => self.agents.defaults.run_overflow = RunOverflowMode::Queue,
+                "reject" => self.agents.defaults.run_overflow = RunOverflowMode::Reject,
+                _ => {}
+            }
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_AGENTS_DEFAULTS_BOOTSTRAP_WORKSPACE") {
+            self.agents.defaults.bootstrap_workspace = val == "true" || val == "1";
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_AGENTS_DEFAULTS_STREAM_CHANNELS") {
+            self.agents.defaults.stream_channels = val == "true" || val == "1";
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_AGENTS_DEFAULTS_USAGE_FOOTER") {
+            self.agents.defaults.usage_footer = val == "true" || val == "1";
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_AGENTS_DEFAULTS_PLAN_MODE") {
+            self.agents.defaults.plan_mode = val == "true" || val == "1";
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_AGENTS_DEFAULTS_PLAN_APPROVAL_TIMEOUT_SECS") {
+            if let Ok(v) = val.parse() {
+                self.agents.defaults.plan_approval_timeout_secs = v;
+            }
+        }
         if let Ok(val) = std::env::var("ZEPTOCLAW_AGENTS_DEFAULTS_COMPACT_TOOLS") {
             self.agents.defaults.compact_tools = val == "true" || val == "1";
         }
@@ -196,6 +245,10 @@ impl Config {
         if let Ok(val) = std::env::var("ZEPTOCLAW_AGENTS_DEFAULTS_SYSTEM_PROMPT") {
             self.agents.defaults.system_prompt = if val.is_empty() { None } else { Some(val) };
         }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_AGENTS_DEFAULTS_SYSTEM_PROMPT_TEMPLATE") {
+            self.agents.defaults.system_prompt_template =
+                if val.is_empty() { None } else { Some(val) };
+        }
         if let Ok(val) = std::env::var("ZEPTOCLAW_AGENTS_DEFAULTS_TIMEZONE") {
             self.agents.defaults.timezone = val;
         }
@@ -254,6 +307,21 @@ impl Config {
                 self.agents.defaults.max_tool_result_bytes = v;
             }
         }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_AGENTS_DEFAULTS_MAX_SESSION_UPLOADS") {
+            if let Ok(v) = val.parse() {
+                self.agents.defaults.max_session_uploads = v;
+            }
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_AGENTS_DEFAULTS_MAX_SESSION_UPLOAD_BYTES") {
+            if let Ok(v) = val.parse() {
+                self.agents.defaults.max_session_upload_bytes = v;
+            }
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_AGENTS_DEFAULTS_MAX_MESSAGE_CHARS") {
+            if let Ok(v) = val.parse() {
+                self.agents.defaults.max_message_chars = v;
+            }
+        }
         if let Ok(val) = std::env::var("ZEPTOCLAW_AGENTS_DEFAULTS_MAX_TOOL_CALLS") {
             if let Ok(v) = val.parse::<u32>() {
                 self.agents.defaults.max_tool_calls = Some(v);
@@ -334,6 +402,14 @@ impl Config {
         // Cache
         self.apply_cache_env_overrides();
 
+        // OpenTelemetry export
+        if let Ok(val) = std::env::var("ZEPTOCLAW_TELEMETRY_OTLP_ENABLED") {
+            self.telemetry.otlp.enabled = val.eq_ignore_ascii_case("true") || val == "1";
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_TELEMETRY_OTLP_ENDPOINT") {
+            self.telemetry.otlp.endpoint = val;
+        }
+
         // Agent mode
         if let Ok(val) = std::env::var("ZEPTOCLAW_SECURITY_AGENT_MODE") {
             self.agent_mode.mode = val;
@@ -346,6 +422,42 @@ impl Config {
         if let Ok(val) = std::env::var("ZEPTOCLAW_SESSION_AUTO_REPAIR") {
             self.session.auto_repair = val.eq_ignore_ascii_case("true") || val == "1";
         }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_SESSION_SESSION_TTL_DAYS") {
+            if let Ok(v) = val.parse() {
+                self.session.session_ttl_days = v;
+            }
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_SESSION_GC_DRY_RUN") {
+            self.session.gc_dry_run = val.eq_ignore_ascii_case("true") || val == "1";
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_SESSION_MAX_PINNED_NOTES") {
+            if let Ok(v) = val.parse() {
+                self.session.max_pinned_notes = v;
+            }
+        }
+
+        // Storage
+        if let Ok(val) = std::env::var("ZEPTOCLAW_STORAGE_BACKEND") {
+            match val.to_ascii_lowercase().as_str() {
+                "json" => self.storage.backend = StorageBackend::Json,
+                "sqlite" => self.storage.backend = StorageBackend::Sqlite,
+                _ => {}
+            }
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_STORAGE_SQLITE_PATH") {
+            self.storage.sqlite_path = Some(val);
+        }
+
+        // Locale
+        if let Ok(val) = std::env::var("ZEPTOCLAW_LOCALE_ENABLED") {
+            self.locale.enabled = val == "true" || val == "1";
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_LOCALE_DEFAULT") {
+            self.locale.default = val;
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_LOCALE_DETECT") {
+            self.locale.detect = val == "true" || val == "1";
+        }
 
         // Transcription
         if let Ok(val) = std::env::var("ZEPTOCLAW_TRANSCRIPTION_MODEL") {
@@ -811,6 +923,18 @@ impl Config {
             }
         }
 
+        // Prompt/response logging
+        if let Ok(val) = std::env::var("ZEPTOCLAW_PROVIDERS_PROMPT_LOG_ENABLED") {
+            if let Ok(enabled) = val.parse() {
+                self.providers.prompt_log.enabled = enabled;
+            }
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_PROVIDERS_PROMPT_LOG_SAMPLE_RATE") {
+            if let Ok(v) = val.parse() {
+                self.providers.prompt_log.sample_rate = v;
+            }
+        }
+
         // Provider fallback behavior
         if let Ok(val) = std::env::var("ZEPTOCLAW_PROVIDERS_FALLBACK_ENABLED") {
             if let Ok(enabled) = val.parse() {
@@ -870,6 +994,7 @@ impl Config {
             let maybe_period = match val.trim().to_ascii_lowercase().as_str() {
                 "daily" => Some(QuotaPeriod::Daily),
                 "monthly" => Some(QuotaPeriod::Monthly),
+                "weekly" => Some(QuotaPeriod::Weekly),
                 _ => None, // unknown value — skip, don't override config file
             };
             if let Some(period) = maybe_period {
@@ -933,6 +1058,7 @@ impl Config {
             let maybe_period = match val.trim().to_ascii_lowercase().as_str() {
                 "daily" => Some(QuotaPeriod::Daily),
                 "monthly" => Some(QuotaPeriod::Monthly),
+                "weekly" => Some(QuotaPeriod::Weekly),
                 _ => None, // unknown value — skip, don't override config file
             };
             if let Some(period) = maybe_period {
@@ -1215,6 +1341,9 @@ impl Config {
         if let Ok(v) = std::env::var("ZEPTOCLAW_TOOLS_CODING_TOOLS") {
             self.tools.coding_tools = v == "true" || v == "1";
         }
+        if let Ok(v) = std::env::var("ZEPTOCLAW_TOOLS_VALIDATE_ARGS") {
+            self.tools.validate_args = v == "true" || v == "1";
+        }
     }
 
     /// Apply memory-specific environment variable overrides.
@@ -1396,6 +1525,14 @@ impl Config {
                 .filter(|s| !s.is_empty())
                 .collect();
         }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_SAFETY_SECRET_GUARD_ENABLED") {
+            self.safety.secret_guard.enabled = val.eq_ignore_ascii_case("true") || val == "1";
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_SAFETY_SECRET_GUARD_ACTION") {
+            if let Ok(action) = val.parse() {
+                self.safety.secret_guard.action = action;
+            }
+        }
     }
 
     /// Apply context-compaction environment variable overrides.
@@ -1493,6 +1630,35 @@ impl Config {
                 self.health.port = port;
             }
         }
+        if let Ok(v) = std::env::var("ZEPTOCLAW_HEALTH_DISK_WARN_FREE_MB") {
+            if let Ok(n) = v.parse::<u64>() {
+                self.health.disk_warn_free_mb = n;
+            }
+        }
+        if let Ok(v) = std::env::var("ZEPTOCLAW_HEALTH_DISK_CRITICAL_FREE_MB") {
+            if let Ok(n) = v.parse::<u64>() {
+                self.health.disk_critical_free_mb = n;
+            }
+        }
+        if let Ok(v) = std::env::var("ZEPTOCLAW_HEALTH_PROVIDER_SLOW_P95_MS") {
+            if let Ok(n) = v.parse::<u64>() {
+                self.health.provider_slow_p95_ms = n;
+            }
+        }
+        if let Ok(v) = std::env::var("ZEPTOCLAW_HTTP_PROXY") {
+            let v = v.trim().to_string();
+            self.http.proxy = if v.is_empty() { None } else { Some(v) };
+        }
+        if let Ok(v) = std::env::var("ZEPTOCLAW_HTTP_CONNECT_TIMEOUT_SECS") {
+            if let Ok(n) = v.parse::<u64>() {
+                self.http.connect_timeout_secs = Some(n);
+            }
+        }
+        if let Ok(v) = std::env::var("ZEPTOCLAW_HTTP_READ_TIMEOUT_SECS") {
+            if let Ok(n) = v.parse::<u64>() {
+                self.http.read_timeout_secs = Some(n);
+            }
+        }
     }
 
     /// Apply Stripe environment variable overrides.
@@ -1528,6 +1694,14 @@ impl Config {
                 self.cache.max_entries = n;
             }
         }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_CACHE_MAX_BYTES") {
+            if let Ok(n) = val.parse::<usize>() {
+                self.cache.max_bytes = n;
+            }
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_CACHE_COMPRESS") {
+            self.cache.compress = val.eq_ignore_ascii_case("true") || val == "1";
+        }
     }
 
     /// Apply device pairing environment variable overrides.
@@ -2129,6 +2303,23 @@ mod tests {
         assert_eq!(config.memory.extra_paths.len(), 2);
     }
 
+    #[test]
+    fn test_storage_config_from_json() {
+        assert_eq!(Config::default().storage.backend, StorageBackend::Json);
+        assert_eq!(
+            Config::default().storage.sqlite_path(),
+            Config::dir().join("state.db")
+        );
+
+        let json = r#"{"storage": {"backend": "sqlite", "sqlite_path": "/var/lib/zc/state.db"}}"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert_eq!(config.storage.backend, StorageBackend::Sqlite);
+        assert_eq!(
+            config.storage.sqlite_path(),
+            PathBuf::from("/var/lib/zc/state.db")
+        );
+    }
+
     #[test]
     fn test_tools_config() {
         let json = r#"{
@@ -2252,6 +2443,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_load_from_path_falls_back_on_out_of_range_quota_threshold() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config_path = temp_dir.path().join("config.json");
+        std::fs::write(
+            &config_path,
+            r#"{
+                "providers": {
+                    "aggregate_quota": {
+                        "max_cost_usd": 10.0,
+                        "warning_threshold": 1.5
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let config = Config::load_from_path(&config_path).unwrap();
+        let quota = config.providers.aggregate_quota.unwrap();
+        assert_eq!(quota.warning_threshold, Some(1.5));
+        assert_eq!(quota.warning_threshold(), 0.8);
+    }
+
     #[test]
     fn test_load_nonexistent() {
         let path = PathBuf::from("/nonexistent/path/config.json");
@@ -70,6 +70,8 @@ pub struct Config {
     pub memory: MemoryConfig,
     /// Heartbeat background task configuration
     pub heartbeat: HeartbeatConfig,
+    /// Monitor hand watch list (scheduled URL change detection)
+    pub monitor: MonitorConfig,
     /// Skills system configuration
     pub skills: SkillsConfig,
     /// Runtime configuration for container isolation
@@ -98,6 +100,8 @@ pub struct Config {
     pub mcp: McpConfig,
     /// Routines (event/webhook/cron triggers) configuration
     pub routines: RoutinesConfig,
+    /// In-process job scheduler (monitor checks and other internal jobs)
+    pub scheduler: SchedulerConfig,
     /// Tunnel configuration for exposing local ports publicly
     pub tunnel: TunnelConfig,
     /// Stripe payment integration configuration.
@@ -110,6 +114,12 @@ pub struct Config {
     pub pairing: PairingConfig,
     /// Session validation and repair behavior.
     pub session: SessionConfig,
+    /// Backend for quota usage and paired-device state.
+    #[serde(default)]
+    pub storage: StorageConfig,
+    /// Per-conversation reply language.
+    #[serde(default)]
+    pub locale: LocaleConfig,
     /// Custom CLI-defined tools (shell commands as agent tools).
     #[serde(default)]
     pub custom_tools: Vec<CustomToolDef>,
@@ -136,6 +146,9 @@ pub struct Config {
     /// r8r workflow-engine bridge configuration.
     #[serde(default)]
     pub r8r_bridge: R8rBridgeConfig,
+    /// Outbound HTTP client settings (proxy, timeouts, extra CA certificates).
+    #[serde(default)]
+    pub http: HttpClientConfig,
 }
 
 // ============================================================================
@@ -232,6 +245,10 @@ pub struct PanelConfig {
     pub auth_mode: AuthMode,
     /// Bind address (default: 127.0.0.1).
     pub bind: String,
+    /// Maximum failed login attempts per client before lockout.
+    pub login_max_attempts: u32,
+    /// Duration in seconds to lock a client out after too many failed logins.
+    pub login_lockout_secs: u64,
 }
 
 impl Default for PanelConfig {
@@ -242,6 +259,8 @@ impl Default for PanelConfig {
             api_port: 9091,
             auth_mode: AuthMode::Token,
             bind: "127.0.0.1".to_string(),
+            login_max_attempts: 5,
+            login_lockout_secs: 300,
         }
     }
 }
@@ -323,7 +342,8 @@ impl Default for R8rBridgeConfig {
 ///
 /// When enabled, caches LLM responses keyed by SHA-256 of
 /// `(model, system_prompt, user_prompt)`. Supports TTL expiry and LRU eviction.
-/// Persists to `~/.zeptoclaw/cache/responses.json`.
+/// Persists to `~/.zeptoclaw/cache/responses.json` (or `responses.json.gz`
+/// when `compress` is set).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct CacheConfig {
@@ -333,6 +353,10 @@ pub struct CacheConfig {
     pub ttl_secs: u64,
     /// Maximum number of cached entries before LRU eviction.
     pub max_entries: usize,
+    /// Maximum total bytes of cached responses before LRU eviction (`0` = unlimited).
+    pub max_bytes: usize,
+    /// Gzip-compress the persisted cache file (`responses.json.gz`).
+    pub compress: bool,
 }
 
 impl Default for CacheConfig {
@@ -341,6 +365,8 @@ impl Default for CacheConfig {
             enabled: false,
             ttl_secs: 3600,
             max_entries: 500,
+            max_bytes: 16 * 1024 * 1024,
+            compress: false,
         }
     }
 }
@@ -381,11 +407,98 @@ impl Default for PairingConfig {
 pub struct SessionConfig {
     /// Automatically repair malformed conversation histories when loaded.
     pub auto_repair: bool,
+    /// Delete sessions not updated for this many days (0 = keep forever).
+    pub session_ttl_days: u32,
+    /// Only log the sessions the garbage collector would delete.
+    pub gc_dry_run: bool,
+    /// Maximum notes the `pin` tool keeps per session.
+    pub max_pinned_notes: usize,
 }
 
 impl Default for SessionConfig {
     fn default() -> Self {
-        Self { auto_repair: true }
+        Self {
+            auto_repair: true,
+            session_ttl_days: 0,
+            gc_dry_run: false,
+            max_pinned_notes: crate::tools::pin::DEFAULT_MAX_PINNED_NOTES,
+        }
+    }
+}
+
+// ============================================================================
+// Storage Configuration
+// ============================================================================
+
+/// Backend for persisted quota usage and paired devices.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageBackend {
+    /// One JSON file per store, rewritten on every change (default).
+    #[default]
+    Json,
+    /// Shared SQLite database with per-row updates (feature: sqlite-store).
+    Sqlite,
+}
+
+/// Storage backend configuration for quota and pairing state.
+///
+/// Switching to `sqlite` imports existing `usage.json` and
+/// `paired_devices.json` data on first start.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct StorageConfig {
+    /// Backend to use (default: json).
+    pub backend: StorageBackend,
+    /// SQLite database path (default: `~/.zeptoclaw/state.db`).
+    pub sqlite_path: Option<String>,
+}
+
+impl StorageConfig {
+    /// Resolved SQLite database path, expanding a leading `~`.
+    pub fn sqlite_path(&self) -> std::path::PathBuf {
+        match &self.sqlite_path {
+            Some(path) => super::expand_home(path),
+            None => Config::dir().join("state.db"),
+        }
+    }
+}
+
+// ============================================================================
+// Locale Configuration
+// ============================================================================
+
+fn default_locale() -> String {
+    "en".to_string()
+}
+
+/// Per-conversation reply language.
+///
+/// The locale comes from an explicit override, the channel's user metadata,
+/// or a guess on the incoming text; a non-default locale adds a "respond in
+/// <language>" hint to the system prompt.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LocaleConfig {
+    /// Enable locale hints (default: true).
+    pub enabled: bool,
+    /// Locale that needs no hint (default: "en").
+    pub default: String,
+    /// Guess the language of incoming text when the channel reports none
+    /// (default: true).
+    pub detect: bool,
+    /// Explicit per-user locales, keyed by `channel:sender_id` or sender id.
+    pub overrides: HashMap<String, String>,
+}
+
+impl Default for LocaleConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            default: default_locale(),
+            detect: true,
+            overrides: HashMap::new(),
+        }
     }
 }
 
@@ -401,6 +514,14 @@ fn default_health_port() -> u16 {
     9090
 }
 
+fn default_disk_warn_free_mb() -> u64 {
+    1024
+}
+
+fn default_disk_critical_free_mb() -> u64 {
+    100
+}
+
 /// HTTP health server configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HealthConfig {
@@ -413,6 +534,16 @@ pub struct HealthConfig {
     /// Port to bind the health server (default: 9090).
     #[serde(default = "default_health_port")]
     pub port: u16,
+    /// Report the `disk` check as degraded below this much free space, in MiB (default: 1024).
+    #[serde(default = "default_disk_warn_free_mb")]
+    pub disk_warn_free_mb: u64,
+    /// Report the `disk` check as down below this much free space, in MiB (default: 100).
+    #[serde(default = "default_disk_critical_free_mb")]
+    pub disk_critical_free_mb: u64,
+    /// Report a provider as degraded when its p95 response time exceeds this
+    /// many milliseconds (default: 0 = never).
+    #[serde(default)]
+    pub provider_slow_p95_ms: u64,
 }
 
 impl Default for HealthConfig {
@@ -421,10 +552,34 @@ impl Default for HealthConfig {
             enabled: false,
             host: default_health_host(),
             port: default_health_port(),
+            disk_warn_free_mb: default_disk_warn_free_mb(),
+            disk_critical_free_mb: default_disk_critical_free_mb(),
+            provider_slow_p95_ms: 0,
         }
     }
 }
 
+// ============================================================================
+// HTTP Client Configuration
+// ============================================================================
+
+/// Outbound HTTP client settings shared by providers and HTTP tools.
+///
+/// Every field is optional; unset fields keep each client's built-in
+/// defaults.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct HttpClientConfig {
+    /// Proxy URL for all outbound requests (e.g. `http://proxy.corp:3128`).
+    pub proxy: Option<String>,
+    /// TCP connect timeout in seconds.
+    pub connect_timeout_secs: Option<u64>,
+    /// Timeout in seconds for each read from the connection.
+    pub read_timeout_secs: Option<u64>,
+    /// Paths to PEM files with extra root certificates to trust.
+    pub ca_certs: Vec<String>,
+}
+
 // ============================================================================
 // Compaction Configuration
 // ============================================================================
@@ -557,6 +712,18 @@ impl Default for RoutinesConfig {
     }
 }
 
+// ============================================================================
+// Scheduler Configuration
+// ============================================================================
+
+/// In-process job scheduler configuration.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SchedulerConfig {
+    /// What to do with a run missed while the process was down.
+    pub on_miss: crate::cron::OnMiss,
+}
+
 // ============================================================================
 // Stripe Configuration
 // ============================================================================
@@ -726,6 +893,10 @@ impl Default for LoopGuardConfig {
 pub struct AgentDefaults {
     /// Workspace directory path
     pub workspace: String,
+    /// Create the workspace with a README and a `.zeptoignore` on startup
+    /// when it does not exist yet. Default: true.
+    #[serde(default = "default_true")]
+    pub bootstrap_workspace: bool,
     /// Default model to use
     pub model: String,
     /// Maximum tokens for responses
@@ -740,10 +911,32 @@ pub struct AgentDefaults {
     pub tool_timeout_secs: u64,
     /// How to handle messages arriving during an active run.
     pub message_queue_mode: MessageQueueMode,
+    /// Maximum agent runs processed at once across all sessions. 0 = unlimited.
+    #[serde(default = "default_max_concurrent_runs")]
+    pub max_concurrent_runs: usize,
+    /// What to do with a run beyond `max_concurrent_runs`.
+    #[serde(default)]
+    pub run_overflow: RunOverflowMode,
     /// Whether to stream the final LLM response token-by-token in CLI mode.
     pub streaming: bool,
+    /// Stream replies to chat channels by editing a single message as text
+    /// arrives, showing tool progress output (e.g. shell lines) while tools
+    /// run. Channels that cannot edit receive the final reply only.
+    #[serde(default)]
+    pub stream_channels: bool,
     /// Per-session token budget (input + output). 0 = unlimited.
     pub token_budget: u64,
+    /// Append a token/cost/model footer to each chat reply. Users can
+    /// override this with the `usage_footer` preference.
+    #[serde(default)]
+    pub usage_footer: bool,
+    /// Propose a numbered plan for each new request and only execute it
+    /// once the user approves.
+    #[serde(default)]
+    pub plan_mode: bool,
+    /// Seconds a proposed plan waits for approval before it expires. 0 = never.
+    #[serde(default = "default_plan_approval_timeout_secs")]
+    pub plan_approval_timeout_secs: u64,
     /// Use compact (shorter) tool descriptions to save tokens.
     #[serde(default)]
     pub compact_tools: bool,
@@ -767,19 +960,39 @@ pub struct AgentDefaults {
     /// Maximum total tool calls allowed per agent run. None = unlimited.
     #[serde(default)]
     pub max_tool_calls: Option<u32>,
+    /// Maximum uploaded files kept per session. 0 = unlimited.
+    #[serde(default = "default_max_session_uploads")]
+    pub max_session_uploads: usize,
+    /// Maximum total bytes of uploaded files kept per session. 0 = unlimited.
+    #[serde(default = "default_max_session_upload_bytes")]
+    pub max_session_upload_bytes: u64,
+    /// Longest inbound message text accepted, in characters. 0 = unlimited.
+    #[serde(default = "default_max_message_chars")]
+    pub max_message_chars: usize,
+    /// What to do with a message longer than `max_message_chars`.
+    #[serde(default)]
+    pub oversized_message: OversizedMessageMode,
     /// Custom system prompt injected into ContextBuilder. Takes priority over
     /// template and hand system prompts when set. Useful for gateway/headless
     /// mode where the system prompt must come from config, not CLI flags.
     #[serde(default)]
     pub system_prompt: Option<String>,
+    /// Path to a system-prompt template file with `{{date}}`, `{{workspace}}`,
+    /// `{{tools}}`, `{{user}}` and `{{hand}}` variables. Replaces the built-in,
+    /// template and hand prompts; `system_prompt` still takes priority.
+    #[serde(default)]
+    pub system_prompt_template: Option<String>,
 }
 
 /// Detect the system's IANA timezone.
 ///
 /// Priority: `TZ` env → `/etc/localtime` symlink → `"UTC"`.
+/// System timezone from `TZ` or `/etc/localtime`, if it is a known IANA
+/// name; otherwise `UTC`.
 fn default_timezone() -> String {
+    let valid = |name: &str| name.parse::<chrono_tz::Tz>().is_ok();
     if let Ok(tz) = std::env::var("TZ") {
-        if !tz.is_empty() {
+        if valid(&tz) {
             return tz;
         }
     }
@@ -788,13 +1001,35 @@ fn default_timezone() -> String {
         if let Ok(target) = std::fs::read_link("/etc/localtime") {
             let path = target.to_string_lossy();
             if let Some(pos) = path.find("zoneinfo/") {
-                return path[pos + 9..].to_string();
+                if valid(&path[pos + 9..]) {
+                    return path[pos + 9..].to_string();
+                }
             }
         }
     }
     "UTC".to_string()
 }
 
+fn default_max_concurrent_runs() -> usize {
+    8
+}
+
+fn default_plan_approval_timeout_secs() -> u64 {
+    600
+}
+
+fn default_max_session_uploads() -> usize {
+    50
+}
+
+fn default_max_session_upload_bytes() -> u64 {
+    100 * 1024 * 1024
+}
+
+fn default_max_message_chars() -> usize {
+    100_000
+}
+
 fn default_max_tool_result_bytes() -> usize {
     crate::utils::sanitize::DEFAULT_MAX_RESULT_BYTES
 }
@@ -810,6 +1045,7 @@ impl Default for AgentDefaults {
     fn default() -> Self {
         Self {
             workspace: "~/.zeptoclaw/workspace".to_string(),
+            bootstrap_workspace: true,
             model: COMPILE_TIME_DEFAULT_MODEL.to_string(),
             max_tokens: 8192,
             temperature: 0.7,
@@ -817,8 +1053,14 @@ impl Default for AgentDefaults {
             agent_timeout_secs: 300,
             tool_timeout_secs: 0,
             message_queue_mode: MessageQueueMode::default(),
+            max_concurrent_runs: default_max_concurrent_runs(),
+            run_overflow: RunOverflowMode::default(),
             streaming: true,
+            stream_channels: false,
             token_budget: 0,
+            usage_footer: false,
+            plan_mode: false,
+            plan_approval_timeout_secs: default_plan_approval_timeout_secs(),
             compact_tools: false,
             tool_profile: None,
             active_hand: None,
@@ -826,7 +1068,12 @@ impl Default for AgentDefaults {
             loop_guard: LoopGuardConfig::default(),
             max_tool_result_bytes: default_max_tool_result_bytes(),
             max_tool_calls: None,
+            max_session_uploads: default_max_session_uploads(),
+            max_session_upload_bytes: default_max_session_upload_bytes(),
+            max_message_chars: default_max_message_chars(),
+            oversized_message: OversizedMessageMode::default(),
             system_prompt: None,
+            system_prompt_template: None,
         }
     }
 }
@@ -842,6 +1089,29 @@ pub enum MessageQueueMode {
     Followup,
 }
 
+/// What happens to an agent run when `max_concurrent_runs` are already active.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RunOverflowMode {
+    /// Wait for a running conversation to finish.
+    #[default]
+    Queue,
+    /// Reply that the agent is busy and drop the run.
+    Reject,
+}
+
+/// What happens to an inbound message longer than `max_message_chars`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum OversizedMessageMode {
+    /// Save the full text as an upload and send the model a preview plus
+    /// the file path, so it can read the parts it needs.
+    #[default]
+    Attach,
+    /// Refuse the message and ask the user to summarize or attach it.
+    Reject,
+}
+
 // ============================================================================
 // Channel Configurations
 // ============================================================================
@@ -884,6 +1154,32 @@ pub struct ChannelsConfig {
     /// Directory for channel plugins (default: ~/.zeptoclaw/channels/)
     #[serde(default)]
     pub channel_plugins_dir: Option<String>,
+    /// Per-channel system prompt overrides, keyed by channel name
+    /// (`telegram`, `slack`, ...).
+    #[serde(default)]
+    pub system_prompts: HashMap<String, ChannelPromptConfig>,
+}
+
+/// How a channel's prompt combines with the agent's system prompt.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChannelPromptMode {
+    /// Put the channel prompt before the system prompt.
+    #[default]
+    Prepend,
+    /// Use the channel prompt instead of the system prompt. An active
+    /// hand's instructions are kept.
+    Replace,
+}
+
+/// System prompt override for conversations on one channel.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChannelPromptConfig {
+    /// Prompt text for this channel's audience.
+    pub prompt: String,
+    /// Whether `prompt` is prepended to or replaces the system prompt.
+    pub mode: ChannelPromptMode,
 }
 
 /// Serial (UART) channel configuration.
@@ -1585,9 +1881,21 @@ pub struct ProvidersConfig {
     pub fallback: FallbackConfig,
     /// Provider rotation configuration for 3+ health-aware providers
     pub rotation: RotationConfig,
+    /// Org-wide quota checked against the combined usage of all providers,
+    /// in addition to each provider's own `quota`.
+    #[serde(default)]
+    pub aggregate_quota: Option<crate::providers::quota::QuotaConfig>,
+    /// Chat to notify when a quota first crosses its warning threshold or is
+    /// exceeded in a period.
+    #[serde(default)]
+    pub quota_notify: Option<crate::providers::quota::QuotaNotifyConfig>,
     /// External binary provider plugins (JSON-RPC 2.0 over stdin/stdout)
     #[serde(default)]
     pub plugins: Vec<ProviderPluginConfig>,
+    /// Sampled prompt/response logging for quality monitoring
+    pub prompt_log: PromptLogConfig,
+    /// Redaction applied to provider request/response debug logs
+    pub debug_log: ProviderDebugLogConfig,
 }
 
 /// Generic provider configuration
@@ -1616,6 +1924,14 @@ pub struct ProviderConfig {
     /// API version query param, e.g. "2024-08-01-preview" for Azure.
     #[serde(default)]
     pub api_version: Option<String>,
+    /// Gemini 2.5 thinking budget in tokens (`0` disables thinking, `-1`
+    /// lets the model decide). Unset keeps the model default. Gemini only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thinking_budget: Option<i32>,
+    /// Return the model's summarized thought trace in `LLMResponse::thoughts`
+    /// instead of discarding it, for debugging. Gemini only. Default: false.
+    #[serde(default)]
+    pub include_thoughts: bool,
 }
 
 impl ProviderConfig {
@@ -1679,6 +1995,79 @@ impl Default for RetryConfig {
     }
 }
 
+/// Sampled prompt/response logging.
+///
+/// When enabled, `sample_rate` of all LLM calls are written in full (after
+/// redaction) to a size-rotated JSONL file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PromptLogConfig {
+    /// Enable prompt/response logging.
+    pub enabled: bool,
+    /// Fraction of calls to log, from 0.0 to 1.0.
+    pub sample_rate: f64,
+    /// Log file path. Defaults to `~/.zeptoclaw/logs/prompts.jsonl`.
+    pub path: Option<String>,
+    /// Rotate the log once it would exceed this many bytes.
+    pub max_file_bytes: u64,
+    /// Number of rotated files to keep.
+    pub max_files: u32,
+    /// Mask emails, phone numbers, card numbers and secrets before writing.
+    pub redact: bool,
+}
+
+impl Default for PromptLogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            sample_rate: 0.05,
+            path: None,
+            max_file_bytes: 10 * 1024 * 1024,
+            max_files: 5,
+            redact: true,
+        }
+    }
+}
+
+/// Redaction rules for provider request/response lines logged at `debug`.
+///
+/// Applied by every built-in provider, so turning on debug logging for
+/// troubleshooting does not write credentials or whole conversations to the
+/// log.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProviderDebugLogConfig {
+    /// Header and query parameter names (case-insensitive) whose values are
+    /// replaced with `[REDACTED]`.
+    pub redact_headers: Vec<String>,
+    /// Longest prompt or response text logged, in characters; longer text is
+    /// truncated. `0` omits the text entirely.
+    pub max_body_chars: usize,
+    /// Log a short SHA-256 of each prompt text instead of the text itself,
+    /// so identical prompts can still be correlated.
+    pub hash_prompts: bool,
+}
+
+impl Default for ProviderDebugLogConfig {
+    fn default() -> Self {
+        Self {
+            redact_headers: [
+                "authorization",
+                "proxy-authorization",
+                "x-api-key",
+                "api-key",
+                "x-goog-api-key",
+                "key",
+            ]
+            .iter()
+            .map(|s| s.to_string())
+            .collect(),
+            max_body_chars: 200,
+            hash_prompts: false,
+        }
+    }
+}
+
 /// Fallback behavior across multiple configured runtime providers.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(default)]
@@ -1816,7 +2205,7 @@ fn default_transcribe_model() -> String {
 }
 
 /// Tools configuration
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct ToolsConfig {
     /// Web tools configuration
@@ -1852,6 +2241,34 @@ pub struct ToolsConfig {
     /// Tools to deny (disable). Set by startup guard in degraded mode.
     #[serde(default)]
     pub deny: Vec<String>,
+    /// Check tool-call arguments against each tool's parameter schema before
+    /// running it. Mismatches (missing required fields, wrong types) are
+    /// returned to the model as a tool error it can correct. Default: true.
+    #[serde(default = "default_true")]
+    pub validate_args: bool,
+    /// Per-tool / per-category control over whether tools' user-visible
+    /// output is sent to the channel. Default: forward everything.
+    #[serde(default)]
+    pub user_output: crate::tools::user_output::UserOutputConfig,
+}
+
+impl Default for ToolsConfig {
+    fn default() -> Self {
+        Self {
+            web: WebToolsConfig::default(),
+            browser: BrowserConfig::default(),
+            whatsapp: WhatsAppToolConfig::default(),
+            google_sheets: GoogleSheetsToolConfig::default(),
+            google: GoogleToolConfig::default(),
+            http_request: None,
+            transcribe: TranscribeConfig::default(),
+            skills: SkillsMarketplaceConfig::default(),
+            coding_tools: false,
+            deny: Vec::new(),
+            validate_args: true,
+            user_output: Default::default(),
+        }
+    }
 }
 
 /// Configuration for the HTTP request tool.
@@ -2143,6 +2560,90 @@ impl Default for HeartbeatConfig {
 
 // ============================================================================
 
+// ============================================================================
+// Monitor Hand Configuration
+// ============================================================================
+
+/// Severity attached to a monitor change notification.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum MonitorSeverity {
+    /// Routine content change.
+    #[default]
+    Info,
+    /// Change worth a look.
+    Warning,
+    /// Change that needs immediate attention.
+    Critical,
+}
+
+/// Rule that raises a notification's severity when changed lines match.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MonitorSeverityRule {
+    /// Case-insensitive substring matched against added/removed lines.
+    pub pattern: String,
+    /// Severity assigned when the pattern matches.
+    pub severity: MonitorSeverity,
+}
+
+/// A single URL watched by the monitor hand.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MonitorTarget {
+    /// Unique target name (used for snapshots and job labels).
+    pub name: String,
+    /// URL to fetch (must be http/https and publicly routable).
+    pub url: String,
+    /// Check interval ("30s", "15m", "1h") or a 5-field cron expression (UTC).
+    #[serde(default = "default_monitor_interval")]
+    pub interval: String,
+    /// Channel to notify on change (e.g. "telegram").
+    pub channel: String,
+    /// Chat ID within the channel to notify.
+    pub chat_id: String,
+    /// Rules mapping changed content to a severity.
+    #[serde(default)]
+    pub severity_rules: Vec<MonitorSeverityRule>,
+    /// Minimum severity required before a notification is sent.
+    #[serde(default)]
+    pub min_severity: MonitorSeverity,
+    /// Minimum number of added/removed lines for a change to count as meaningful.
+    #[serde(default = "default_monitor_min_changed_lines")]
+    pub min_changed_lines: usize,
+}
+
+fn default_monitor_interval() -> String {
+    "1h".to_string()
+}
+
+fn default_monitor_min_changed_lines() -> usize {
+    1
+}
+
+/// Monitor hand watch-list configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MonitorConfig {
+    /// Enable scheduled target checks in the gateway.
+    pub enabled: bool,
+    /// URLs to watch.
+    pub targets: Vec<MonitorTarget>,
+    /// Per-request timeout in seconds.
+    pub timeout_secs: u64,
+    /// Maximum response body size in bytes kept per snapshot.
+    pub max_response_bytes: usize,
+}
+
+impl Default for MonitorConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            targets: Vec::new(),
+            timeout_secs: 30,
+            max_response_bytes: 512 * 1024,
+        }
+    }
+}
+
 // ============================================================================
 // Skills Marketplace (ClawHub) Configuration
 // ============================================================================
@@ -3119,6 +3620,9 @@ mod tests {
                 max_tokens: None,
                 period: QuotaPeriod::Monthly,
                 action: QuotaAction::Reject,
+                warning_threshold: None,
+                downgrade_model: None,
+                per_model: Default::default(),
             }),
             ..Default::default()
         };
@@ -3305,6 +3809,9 @@ fn default_email_imap_folder() -> String {
 fn default_email_idle_timeout_secs() -> u64 {
     1740
 }
+fn default_email_max_attachment_bytes() -> usize {
+    10 * 1024 * 1024
+}
 
 /// Email channel configuration (IMAP IDLE inbound + SMTP outbound).
 ///
@@ -3345,6 +3852,22 @@ pub struct EmailConfig {
     /// Seconds before restarting IDLE (RFC 2177 recommends < 30 min). Default: 1740.
     #[serde(default = "default_email_idle_timeout_secs")]
     pub idle_timeout_secs: u64,
+    /// Maximum combined size of outbound attachments in bytes. Default: 10 MiB.
+    #[serde(default = "default_email_max_attachment_bytes")]
+    pub max_attachment_bytes: usize,
+    /// Strip quoted reply history (`On ... wrote:`, `>` blocks) from inbound
+    /// mail so only the new content reaches the agent. Default: `true`.
+    #[serde(default = "default_true")]
+    pub strip_quoted_replies: bool,
+    /// CC the other `To`/`Cc` participants of the inbound thread on replies.
+    /// Participants not matching `allowed_senders` are never added. Default: `false`.
+    #[serde(default)]
+    pub reply_all: bool,
+    /// Addresses or domains (same syntax as `allowed_senders`) the `message`
+    /// tool may email besides the sender of the current conversation.
+    /// Default: empty, so the tool can only reply to that sender.
+    #[serde(default)]
+    pub allowed_recipients: Vec<String>,
     /// When `true`, the channel is active. Default: `false`.
     #[serde(default)]
     pub enabled: bool,
@@ -3364,6 +3887,10 @@ impl Default for EmailConfig {
             allowed_senders: Vec::new(),
             deny_by_default: false,
             idle_timeout_secs: default_email_idle_timeout_secs(),
+            max_attachment_bytes: default_email_max_attachment_bytes(),
+            strip_quoted_replies: true,
+            reply_all: false,
+            allowed_recipients: Vec::new(),
             enabled: false,
         }
     }
@@ -3383,6 +3910,9 @@ impl std::fmt::Debug for EmailConfig {
             .field("allowed_senders", &self.allowed_senders)
             .field("deny_by_default", &self.deny_by_default)
             .field("idle_timeout_secs", &self.idle_timeout_secs)
+            .field("max_attachment_bytes", &self.max_attachment_bytes)
+            .field("strip_quoted_replies", &self.strip_quoted_replies)
+            .field("reply_all", &self.reply_all)
             .field("enabled", &self.enabled)
             .finish()
     }
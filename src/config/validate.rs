@@ -16,6 +16,7 @@ const KNOWN_TOP_LEVEL: &[&str] = &[
     "tools",
     "memory",
     "heartbeat",
+    "monitor",
     "skills",
     "runtime",
     "container_agent",
@@ -40,16 +41,20 @@ const KNOWN_TOP_LEVEL: &[&str] = &[
     "agent_mode",
     "pairing",
     "session",
+    "storage",
+    "locale",
     "panel",
     "health",
     "devices",
     "logging",
     "r8r_bridge",
+    "http",
 ];
 
 /// Known fields for each section. Nested as section.field.
 const KNOWN_AGENTS_DEFAULTS: &[&str] = &[
     "workspace",
+    "bootstrap_workspace",
     "model",
     "max_tokens",
     "temperature",
@@ -57,8 +62,14 @@ const KNOWN_AGENTS_DEFAULTS: &[&str] = &[
     "agent_timeout_secs",
     "tool_timeout_secs",
     "message_queue_mode",
+    "max_concurrent_runs",
+    "run_overflow",
     "streaming",
+    "stream_channels",
     "token_budget",
+    "usage_footer",
+    "plan_mode",
+    "plan_approval_timeout_secs",
     "compact_tools",
     "tool_profile",
     "active_hand",
@@ -66,7 +77,10 @@ const KNOWN_AGENTS_DEFAULTS: &[&str] = &[
     "loop_guard",
     "max_tool_result_bytes",
     "max_tool_calls",
+    "max_session_uploads",
+    "max_session_upload_bytes",
     "system_prompt",
+    "system_prompt_template",
 ];
 
 const KNOWN_LOOP_GUARD: &[&str] = &[
@@ -440,6 +454,70 @@ pub fn validate_provider_api_bases(config: &crate::config::Config) -> Vec<Diagno
     diagnostics
 }
 
+/// Validate quota warning thresholds from a typed config.
+///
+/// Each `warning_threshold` (per-provider `quota`, its `per_model` entries
+/// and `aggregate_quota`) should lie strictly between 0 and 1; other values
+/// produce warnings, as the quota check falls back to the default.
+pub fn validate_quota_thresholds(config: &crate::config::Config) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut check = |path: String, quota: Option<&crate::providers::quota::QuotaConfig>| {
+        if let Some(threshold) = quota.and_then(|q| q.warning_threshold) {
+            if !(threshold > 0.0 && threshold < 1.0) {
+                diagnostics.push(Diagnostic {
+                    level: DiagnosticLevel::Warn,
+                    path,
+                    message: format!(
+                        "warning_threshold must be between 0 and 1 (exclusive), got {}; using the default {}",
+                        threshold,
+                        crate::providers::quota::DEFAULT_WARNING_THRESHOLD
+                    ),
+                });
+            }
+        }
+    };
+
+    for spec in crate::providers::PROVIDER_REGISTRY {
+        let quota = crate::providers::provider_config_by_name(config, spec.name)
+            .and_then(|p| p.quota.as_ref());
+        check(
+            format!("providers.{}.quota.warning_threshold", spec.name),
+            quota,
+        );
+        for (model, model_quota) in quota.iter().flat_map(|q| &q.per_model) {
+            check(
+                format!(
+                    "providers.{}.quota.per_model.{}.warning_threshold",
+                    spec.name, model
+                ),
+                Some(model_quota),
+            );
+        }
+    }
+    check(
+        "providers.aggregate_quota.warning_threshold".to_string(),
+        config.providers.aggregate_quota.as_ref(),
+    );
+
+    diagnostics
+}
+
+/// Validate `agents.defaults.timezone` as an IANA timezone name.
+pub fn validate_timezone(config: &crate::config::Config) -> Vec<Diagnostic> {
+    let timezone = &config.agents.defaults.timezone;
+    if timezone.parse::<chrono_tz::Tz>().is_ok() {
+        return Vec::new();
+    }
+    vec![Diagnostic {
+        level: DiagnosticLevel::Error,
+        path: "agents.defaults.timezone".to_string(),
+        message: format!(
+            "unknown timezone '{}'; use an IANA name such as 'Europe/Berlin' or 'UTC'",
+            timezone
+        ),
+    }]
+}
+
 /// Check if a model name looks compatible with a provider backend.
 ///
 /// Returns `None` when the combination is fine, or `Some(message)` describing
@@ -1157,4 +1235,54 @@ mod tests {
         let diags = validate_provider_api_bases(&config);
         assert!(diags.is_empty());
     }
+
+    #[test]
+    fn test_validate_quota_thresholds() {
+        use crate::providers::quota::QuotaConfig;
+
+        let mut config = Config::default();
+        config.providers.openai = Some(crate::config::ProviderConfig {
+            quota: Some(QuotaConfig {
+                warning_threshold: Some(0.5),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        assert!(validate_quota_thresholds(&config).is_empty());
+
+        config.providers.aggregate_quota = Some(QuotaConfig {
+            warning_threshold: Some(1.0),
+            ..Default::default()
+        });
+        config.providers.anthropic = Some(crate::config::ProviderConfig {
+            quota: Some(QuotaConfig {
+                warning_threshold: Some(0.0),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        let diags = validate_quota_thresholds(&config);
+        assert!(diags.iter().all(|d| d.level == DiagnosticLevel::Warn));
+        let paths: Vec<_> = diags.into_iter().map(|d| d.path).collect();
+        assert_eq!(
+            paths,
+            [
+                "providers.anthropic.quota.warning_threshold",
+                "providers.aggregate_quota.warning_threshold",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_timezone() {
+        let mut config = Config::default();
+        config.agents.defaults.timezone = "Asia/Kuala_Lumpur".to_string();
+        assert!(validate_timezone(&config).is_empty());
+
+        config.agents.defaults.timezone = "Mars/Olympus".to_string();
+        let diags = validate_timezone(&config);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].path, "agents.defaults.timezone");
+        assert!(diags[0].message.contains("Mars/Olympus"));
+    }
 }
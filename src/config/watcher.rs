@@ -80,6 +80,25 @@ impl ConfigWatcher {
     }
 }
 
+/// Top-level config sections the gateway applies live when the file changes.
+///
+/// Changes to any other section only take effect after a restart.
+pub const HOT_RELOAD_SECTIONS: &[&str] = &["providers", "channels", "safety", "agents"];
+
+/// Returns the top-level config sections whose values differ between `old`
+/// and `new`, in serialization order.
+pub fn changed_sections(old: &Config, new: &Config) -> Vec<String> {
+    let old = serde_json::to_value(old).unwrap_or_default();
+    let new = serde_json::to_value(new).unwrap_or_default();
+    let (Some(old), Some(new)) = (old.as_object(), new.as_object()) else {
+        return Vec::new();
+    };
+    new.iter()
+        .filter(|(key, value)| old.get(key.as_str()) != Some(*value))
+        .map(|(key, _)| key.clone())
+        .collect()
+}
+
 fn read_mtime(path: &PathBuf) -> Option<SystemTime> {
     std::fs::metadata(path).ok().and_then(|m| m.modified().ok())
 }
@@ -89,6 +108,20 @@ mod tests {
     use super::*;
     use tempfile::TempDir;
 
+    #[test]
+    fn changed_sections_reports_only_differing_sections() {
+        let old = Config::default();
+        let mut new = old.clone();
+        new.gateway.port += 1;
+        new.safety.enabled = !new.safety.enabled;
+
+        let changed = changed_sections(&old, &new);
+        assert_eq!(changed.len(), 2);
+        assert!(changed.contains(&"gateway".to_string()));
+        assert!(changed.contains(&"safety".to_string()));
+        assert!(changed_sections(&old, &old).is_empty());
+    }
+
     #[tokio::test]
     async fn watcher_emits_on_change() {
         let tmp = TempDir::new().unwrap();
@@ -4,7 +4,8 @@ use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
-use chrono::{DateTime, Datelike, Duration, Timelike, Utc};
+use chrono::{DateTime, Datelike, Duration, TimeZone, Timelike, Utc};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 use tokio::task::JoinHandle;
@@ -17,9 +18,18 @@ use crate::error::{Result, ZeptoError};
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "kind", rename_all = "snake_case")]
 pub enum CronSchedule {
-    At { at_ms: i64 },
-    Every { every_ms: i64 },
-    Cron { expr: String },
+    At {
+        at_ms: i64,
+    },
+    Every {
+        every_ms: i64,
+    },
+    Cron {
+        expr: String,
+        /// IANA timezone the expression is evaluated in (UTC when unset).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        tz: Option<String>,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -117,26 +127,34 @@ fn error_backoff_ms(consecutive_errors: u32) -> i64 {
     ERROR_BACKOFF_SCHEDULE_MS[idx]
 }
 
+/// Parse one cron field: `*`, values, `a-b` ranges and `/n` steps on either,
+/// combined with commas (e.g. `*/15`, `1-5`, `0-30/10,45`).
 fn parse_cron_field(field: &str, min: u32, max: u32) -> Option<Vec<u32>> {
-    if field == "*" {
-        return Some((min..=max).collect());
-    }
-    if let Some(step_str) = field.strip_prefix("*/") {
-        let step = step_str.parse::<u32>().ok()?;
-        if step == 0 {
-            return None;
-        }
-        return Some((min..=max).step_by(step as usize).collect());
-    }
-
     let mut values = Vec::new();
     for part in field.split(',') {
-        let value = part.parse::<u32>().ok()?;
-        if !(min..=max).contains(&value) {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, Some(step.parse::<u32>().ok()?)),
+            None => (part, None),
+        };
+        if step == Some(0) {
+            return None;
+        }
+        let (lo, hi) = if range == "*" {
+            (min, max)
+        } else if let Some((lo, hi)) = range.split_once('-') {
+            (lo.parse::<u32>().ok()?, hi.parse::<u32>().ok()?)
+        } else {
+            let value = range.parse::<u32>().ok()?;
+            // `5/15` means "from 5 to the end, every 15".
+            (value, if step.is_some() { max } else { value })
+        };
+        if lo < min || hi > max || lo > hi {
             return None;
         }
-        values.push(value);
+        values.extend((lo..=hi).step_by(step.unwrap_or(1) as usize));
     }
+    values.sort_unstable();
+    values.dedup();
     if values.is_empty() {
         None
     } else {
@@ -144,7 +162,9 @@ fn parse_cron_field(field: &str, min: u32, max: u32) -> Option<Vec<u32>> {
     }
 }
 
-fn next_run_from_cron_expr(expr: &str, now: i64) -> Option<i64> {
+/// Next time (unix ms) after `now` matching `expr`, with fields read as
+/// wall-clock time in `tz`.
+fn next_run_from_cron_expr(expr: &str, now: i64, tz: Tz) -> Option<i64> {
     let fields: Vec<&str> = expr.split_whitespace().collect();
     if fields.len() != 5 {
         return None;
@@ -154,7 +174,11 @@ fn next_run_from_cron_expr(expr: &str, now: i64) -> Option<i64> {
     let hours = parse_cron_field(fields[1], 0, 23)?;
     let dom = parse_cron_field(fields[2], 1, 31)?;
     let month = parse_cron_field(fields[3], 1, 12)?;
-    let dow = parse_cron_field(fields[4], 0, 6)?;
+    // Both 0 and 7 mean Sunday.
+    let dow: Vec<u32> = parse_cron_field(fields[4], 0, 7)?
+        .into_iter()
+        .map(|d| d % 7)
+        .collect();
 
     let mut candidate = DateTime::from_timestamp_millis(now)?
         .with_second(0)?
@@ -163,11 +187,12 @@ fn next_run_from_cron_expr(expr: &str, now: i64) -> Option<i64> {
     let limit = candidate + Duration::days(366);
 
     while candidate <= limit {
-        let m = candidate.minute();
-        let h = candidate.hour();
-        let d = candidate.day();
-        let mon = candidate.month();
-        let wd = candidate.weekday().num_days_from_sunday();
+        let local = candidate.with_timezone(&tz);
+        let m = local.minute();
+        let h = local.hour();
+        let d = local.day();
+        let mon = local.month();
+        let wd = local.weekday().num_days_from_sunday();
         if minutes.contains(&m)
             && hours.contains(&h)
             && dom.contains(&d)
@@ -184,10 +209,14 @@ fn next_run_from_cron_expr(expr: &str, now: i64) -> Option<i64> {
 
 /// Returns true if the cron expression is valid and has a future run time.
 pub fn is_valid_cron_expr(expr: &str) -> bool {
-    next_run_from_cron_expr(expr, now_ms()).is_some()
+    next_run_from_cron_expr(expr, now_ms(), Tz::UTC).is_some()
 }
 
-fn next_run_at(schedule: &CronSchedule, now: i64) -> Option<i64> {
+/// Compute the next run time (unix ms) for `schedule` after `now`.
+///
+/// Returns `None` for one-shot schedules in the past, non-positive intervals,
+/// or cron expressions with no match within a year.
+pub fn next_run_at(schedule: &CronSchedule, now: i64) -> Option<i64> {
     match schedule {
         CronSchedule::At { at_ms } => {
             if *at_ms > now {
@@ -203,7 +232,13 @@ fn next_run_at(schedule: &CronSchedule, now: i64) -> Option<i64> {
                 None
             }
         }
-        CronSchedule::Cron { expr } => next_run_from_cron_expr(expr, now),
+        CronSchedule::Cron { expr, tz } => {
+            let tz = tz
+                .as_deref()
+                .and_then(|tz| tz.parse().ok())
+                .unwrap_or(Tz::UTC);
+            next_run_from_cron_expr(expr, now, tz)
+        }
     }
 }
 
@@ -579,12 +614,22 @@ async fn tick(
 }
 
 /// Parse ISO datetime string into unix milliseconds.
-pub fn parse_at_datetime_ms(input: &str) -> Result<i64> {
+///
+/// RFC3339 input carries its own offset; `YYYY-MM-DDTHH:MM:SS` without one
+/// is read as local time in `tz`.
+pub fn parse_at_datetime_ms(input: &str, tz: Tz) -> Result<i64> {
     if let Ok(dt) = DateTime::parse_from_rfc3339(input) {
         return Ok(dt.timestamp_millis());
     }
     if let Ok(naive) = chrono::NaiveDateTime::parse_from_str(input, "%Y-%m-%dT%H:%M:%S") {
-        return Ok(naive.and_utc().timestamp_millis());
+        // Naive times are wall-clock times in `tz`; in a DST gap there is
+        // no such time, so use the instant just after it.
+        if let Some(local) = tz.from_local_datetime(&naive).earliest().or_else(|| {
+            tz.from_local_datetime(&(naive + Duration::hours(1)))
+                .earliest()
+        }) {
+            return Ok(local.timestamp_millis());
+        }
     }
     Err(ZeptoError::Tool(format!(
         "Invalid 'at' datetime '{}'. Use RFC3339 or YYYY-MM-DDTHH:MM:SS",
@@ -605,9 +650,59 @@ mod tests {
         assert_eq!(next, 1_500);
     }
 
+    #[test]
+    fn test_cron_next_run_honors_timezone() {
+        // 2026-03-03T12:00:00Z, a Tuesday.
+        let now = Utc
+            .with_ymd_and_hms(2026, 3, 3, 12, 0, 0)
+            .unwrap()
+            .timestamp_millis();
+        let at_nine = |tz: Option<&str>| CronSchedule::Cron {
+            expr: "0 9 * * *".to_string(),
+            tz: tz.map(str::to_string),
+        };
+
+        // UTC stays the default for jobs without a timezone.
+        let utc = next_run_at(&at_nine(None), now).unwrap();
+        assert_eq!(
+            DateTime::from_timestamp_millis(utc).unwrap().to_rfc3339(),
+            "2026-03-04T09:00:00+00:00"
+        );
+
+        // 09:00 in Kuala Lumpur (UTC+8) is 01:00 UTC.
+        let kl = next_run_at(&at_nine(Some("Asia/Kuala_Lumpur")), now).unwrap();
+        assert_eq!(
+            DateTime::from_timestamp_millis(kl).unwrap().to_rfc3339(),
+            "2026-03-04T01:00:00+00:00"
+        );
+
+        // 09:00 in New York (UTC-5 before DST) is 14:00 UTC, still today.
+        let ny = next_run_at(&at_nine(Some("America/New_York")), now).unwrap();
+        assert_eq!(
+            DateTime::from_timestamp_millis(ny).unwrap().to_rfc3339(),
+            "2026-03-03T14:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn test_parse_at_naive_datetime_in_timezone() {
+        let tz: Tz = "Asia/Kuala_Lumpur".parse().unwrap();
+        let ms = parse_at_datetime_ms("2026-03-04T09:00:00", tz).unwrap();
+        assert_eq!(
+            DateTime::from_timestamp_millis(ms).unwrap().to_rfc3339(),
+            "2026-03-04T01:00:00+00:00"
+        );
+        // Explicit offsets win over the timezone.
+        let ms = parse_at_datetime_ms("2026-03-04T09:00:00Z", tz).unwrap();
+        assert_eq!(
+            DateTime::from_timestamp_millis(ms).unwrap().to_rfc3339(),
+            "2026-03-04T09:00:00+00:00"
+        );
+    }
+
     #[test]
     fn test_parse_at_datetime_ms_rfc3339() {
-        let ms = parse_at_datetime_ms("2026-02-12T12:34:56Z").unwrap();
+        let ms = parse_at_datetime_ms("2026-02-12T12:34:56Z", Tz::UTC).unwrap();
         assert!(ms > 0);
     }
 
@@ -120,6 +120,100 @@ impl From<ProviderError> for ZeptoError {
     }
 }
 
+// ============================================================================
+// Tool Error Classification
+// ============================================================================
+
+/// Kind of tool failure, reported to the model with the error text.
+///
+/// Lets the model tell "fix your input" from "try again later" from "this
+/// does not exist" without parsing free-form messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToolErrorKind {
+    /// Arguments are missing, malformed, or out of range
+    InvalidArgument,
+    /// The referenced resource (message, file, device, ...) does not exist
+    NotFound,
+    /// Credentials are missing, expired, or lack permission
+    Unauthorized,
+    /// The upstream service is throttling requests
+    RateLimited,
+    /// A temporary failure (timeout, connection drop, 5xx) that may succeed on retry
+    Transient,
+    /// A bug or unexpected failure inside the tool
+    Internal,
+}
+
+impl fmt::Display for ToolErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl ToolErrorKind {
+    /// Stable snake_case name shown to the model (`invalid_argument`, ...).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ToolErrorKind::InvalidArgument => "invalid_argument",
+            ToolErrorKind::NotFound => "not_found",
+            ToolErrorKind::Unauthorized => "unauthorized",
+            ToolErrorKind::RateLimited => "rate_limited",
+            ToolErrorKind::Transient => "transient",
+            ToolErrorKind::Internal => "internal",
+        }
+    }
+
+    /// Returns `true` if repeating the same call may succeed.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, ToolErrorKind::RateLimited | ToolErrorKind::Transient)
+    }
+
+    /// What the model should do next.
+    fn guidance(&self) -> &'static str {
+        match self {
+            ToolErrorKind::InvalidArgument => "Fix the arguments before calling again.",
+            ToolErrorKind::NotFound => "Check the identifier, or search for it first.",
+            ToolErrorKind::Unauthorized => {
+                "Ask the user to grant access or re-authenticate; do not retry."
+            }
+            ToolErrorKind::RateLimited => "Wait before retrying.",
+            ToolErrorKind::Transient => "Retrying the same call may succeed.",
+            ToolErrorKind::Internal => "Do not retry; tell the user the tool failed.",
+        }
+    }
+
+    /// Classify the error text of a failed upstream API call.
+    pub fn from_api_message(msg: &str) -> Self {
+        let lower = msg.to_lowercase();
+        if lower.contains("404") || lower.contains("not found") || lower.contains("notfound") {
+            return ToolErrorKind::NotFound;
+        }
+        match crate::providers::error_classifier::classify_error_message(msg) {
+            ProviderError::Auth(_) | ProviderError::Billing(_) => ToolErrorKind::Unauthorized,
+            ProviderError::RateLimit(_) => ToolErrorKind::RateLimited,
+            ProviderError::ServerError(_)
+            | ProviderError::Timeout(_)
+            | ProviderError::Overloaded(_) => ToolErrorKind::Transient,
+            ProviderError::ModelNotFound(_) => ToolErrorKind::NotFound,
+            ProviderError::InvalidRequest(_)
+            | ProviderError::Format(_)
+            | ProviderError::ContextOverflow(_) => ToolErrorKind::InvalidArgument,
+            ProviderError::Unknown(_) => {
+                if ["500", "502", "503", "504", "connection", "unavailable"]
+                    .iter()
+                    .any(|p| lower.contains(p))
+                {
+                    ToolErrorKind::Transient
+                } else if lower.contains("400") || lower.contains("invalid") {
+                    ToolErrorKind::InvalidArgument
+                } else {
+                    ToolErrorKind::Internal
+                }
+            }
+        }
+    }
+}
+
 // ============================================================================
 // Primary Error Type
 // ============================================================================
@@ -148,6 +242,13 @@ pub enum ZeptoError {
     #[error("Tool error: {0}")]
     Tool(String),
 
+    /// Tool execution error with a classified [`ToolErrorKind`].
+    #[error("Tool error: {message}")]
+    ToolTyped {
+        kind: ToolErrorKind,
+        message: String,
+    },
+
     /// Session management errors (invalid state, persistence failures, etc.)
     #[error("Session error: {0}")]
     Session(String),
@@ -195,6 +296,52 @@ pub enum ZeptoError {
     /// Provider quota exceeded and the configured action is "reject" (no fallback).
     #[error("Quota rejected: {0}")]
     QuotaRejected(String),
+
+    /// The agent is at its concurrent run limit and rejected the request.
+    #[error("Busy: {0}")]
+    Busy(String),
+}
+
+impl ZeptoError {
+    /// Create a classified tool error.
+    pub fn tool(kind: ToolErrorKind, message: impl Into<String>) -> Self {
+        ZeptoError::ToolTyped {
+            kind,
+            message: message.into(),
+        }
+    }
+
+    /// The tool error kind, when the error carries one.
+    pub fn tool_error_kind(&self) -> Option<ToolErrorKind> {
+        self.tool_error_parts().map(|(kind, _)| kind)
+    }
+
+    fn tool_error_parts(&self) -> Option<(ToolErrorKind, &str)> {
+        match self {
+            ZeptoError::ToolTyped { kind, message } => Some((*kind, message)),
+            ZeptoError::NotFound(message) => Some((ToolErrorKind::NotFound, message)),
+            ZeptoError::Unauthorized(message) => Some((ToolErrorKind::Unauthorized, message)),
+            _ => None,
+        }
+    }
+
+    /// Text of a failed tool call as shown to the model.
+    ///
+    /// Classified errors render as
+    /// `Error [<kind>]: <message>` followed by a `Retryable: yes|no.` line
+    /// with guidance; anything else keeps the plain `Error: <error>` form.
+    pub fn to_tool_result(&self) -> String {
+        match self.tool_error_parts() {
+            Some((kind, message)) => format!(
+                "Error [{}]: {}\nRetryable: {}. {}",
+                kind,
+                message,
+                if kind.is_retryable() { "yes" } else { "no" },
+                kind.guidance()
+            ),
+            None => format!("Error: {}", self),
+        }
+    }
 }
 
 /// A specialized `Result` type for ZeptoClaw operations.
@@ -253,6 +400,66 @@ mod tests {
         );
     }
 
+    // ====================================================================
+    // ToolErrorKind tests
+    // ====================================================================
+
+    #[test]
+    fn test_tool_error_kinds_format_distinctly() {
+        let kinds = [
+            ToolErrorKind::InvalidArgument,
+            ToolErrorKind::NotFound,
+            ToolErrorKind::Unauthorized,
+            ToolErrorKind::RateLimited,
+            ToolErrorKind::Transient,
+            ToolErrorKind::Internal,
+        ];
+        let rendered: Vec<String> = kinds
+            .iter()
+            .map(|kind| ZeptoError::tool(*kind, "boom").to_tool_result())
+            .collect();
+        for (kind, text) in kinds.iter().zip(&rendered) {
+            assert!(text.starts_with(&format!("Error [{}]: boom\nRetryable: ", kind)));
+        }
+        let distinct: std::collections::HashSet<_> = rendered.iter().collect();
+        assert_eq!(distinct.len(), kinds.len());
+
+        assert!(rendered[3].contains("Retryable: yes."));
+        assert!(rendered[0].contains("Retryable: no."));
+        assert_eq!(
+            ZeptoError::tool(ToolErrorKind::NotFound, "gone").to_string(),
+            "Tool error: gone"
+        );
+        // Unclassified errors keep the plain form.
+        assert_eq!(
+            ZeptoError::Tool("boom".into()).to_tool_result(),
+            "Error: Tool error: boom"
+        );
+        assert_eq!(
+            ZeptoError::NotFound("session".into()).tool_error_kind(),
+            Some(ToolErrorKind::NotFound)
+        );
+    }
+
+    #[test]
+    fn test_tool_error_kind_from_api_message() {
+        let cases = [
+            (
+                "HTTP 404: Requested entity was not found",
+                ToolErrorKind::NotFound,
+            ),
+            ("HTTP 401 Unauthorized", ToolErrorKind::Unauthorized),
+            ("HTTP 429: Too many requests", ToolErrorKind::RateLimited),
+            ("HTTP 503 Service Unavailable", ToolErrorKind::Transient),
+            ("request timed out", ToolErrorKind::Transient),
+            ("HTTP 400: Invalid label", ToolErrorKind::InvalidArgument),
+            ("something odd happened", ToolErrorKind::Internal),
+        ];
+        for (msg, kind) in cases {
+            assert_eq!(ToolErrorKind::from_api_message(msg), kind, "{}", msg);
+        }
+    }
+
     // ====================================================================
     // ProviderError tests
     // ====================================================================
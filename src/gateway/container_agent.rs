@@ -105,9 +105,10 @@ impl ContainerAgentProxy {
         };
 
         let pairing = if config.pairing.enabled {
-            Some(std::sync::Mutex::new(PairingManager::new(
+            Some(std::sync::Mutex::new(PairingManager::with_storage(
                 config.pairing.max_attempts,
                 config.pairing.lockout_secs,
+                &config.storage,
             )))
         } else {
             None
@@ -199,9 +200,8 @@ impl ContainerAgentProxy {
                                                 ip = %ip,
                                                 "Rate limited inbound message"
                                             );
-                                            let rejection = OutboundMessage::new(
-                                                &inbound.channel,
-                                                &inbound.chat_id,
+                                            let rejection = OutboundMessage::reply_to(
+                                                &inbound,
                                                 "Rate limit exceeded. Please wait before sending another message.",
                                             );
                                             if let Err(e) = self.bus.publish_outbound(rejection).await {
@@ -245,9 +245,8 @@ impl ContainerAgentProxy {
                                         channel = %inbound.channel,
                                         "Rejected unpaired device (pairing enabled)"
                                     );
-                                    let rejection = OutboundMessage::new(
-                                        &inbound.channel,
-                                        &inbound.chat_id,
+                                    let rejection = OutboundMessage::reply_to(
+                                        &inbound,
                                         "Access denied: device not paired. Use `zeptoclaw pair new` to generate a pairing code.",
                                     );
                                     if let Err(e) = self.bus.publish_outbound(rejection).await {
@@ -338,7 +337,7 @@ impl ContainerAgentProxy {
                     AgentResult::Success { content, session } => {
                         self.persist_session_snapshot(&message.session_key, session)
                             .await;
-                        OutboundMessage::new(&message.channel, &message.chat_id, &content)
+                        OutboundMessage::reply_to(message, &content)
                     }
                     AgentResult::Error { message: err, .. } => {
                         // Only record a request-level error when the usage
@@ -349,10 +348,9 @@ impl ContainerAgentProxy {
                                 metrics.record_error();
                             }
                         }
-                        OutboundMessage::new(
-                            &message.channel,
-                            &message.chat_id,
-                            &format!("Error: {}", err),
+                        OutboundMessage::reply_to(
+                            message,
+                            &format!("Error: {} (trace: {})", err, message.trace_id),
                         )
                     }
                 }
@@ -362,10 +360,9 @@ impl ContainerAgentProxy {
                 if let Some(metrics) = usage_metrics.as_ref() {
                     metrics.record_error();
                 }
-                OutboundMessage::new(
-                    &message.channel,
-                    &message.chat_id,
-                    &format!("Container error: {}", e),
+                OutboundMessage::reply_to(
+                    message,
+                    &format!("Container error: {} (trace: {})", e, message.trace_id),
                 )
             }
         }
@@ -444,6 +441,7 @@ impl ContainerAgentProxy {
 
         debug!(
             request_id = %request.request_id,
+            trace_id = %request.message.trace_id,
             backend = %self.resolved_backend,
             image = %self.container_config.image,
             args_len = invocation.args.len(),
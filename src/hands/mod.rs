@@ -1,7 +1,10 @@
 //! Hands-lite registry and manifest parsing.
 
-use std::collections::HashMap;
-use std::path::Path;
+pub mod monitor;
+pub mod pipeline;
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 
 use serde::Deserialize;
 
@@ -31,6 +34,30 @@ pub struct HandManifest {
     pub settings: HashMap<String, String>,
     #[serde(default)]
     pub guardrails: HandGuardrails,
+    /// Sub-hands run in order when this is a pipeline hand; each stage's
+    /// output feeds the next (see [`pipeline`]).
+    #[serde(default)]
+    pub stages: Vec<HandStage>,
+    /// Cap on model calls across all stages of a pipeline.
+    #[serde(default)]
+    pub max_iterations: Option<u32>,
+}
+
+impl HandManifest {
+    /// Whether this hand chains other hands instead of acting itself.
+    pub fn is_pipeline(&self) -> bool {
+        !self.stages.is_empty()
+    }
+}
+
+/// One `[[stages]]` entry of a pipeline hand.
+#[derive(Debug, Clone, Deserialize)]
+pub struct HandStage {
+    /// Name of the hand that runs this stage.
+    pub hand: String,
+    /// Extra instructions for this stage, added to the task it receives.
+    #[serde(default)]
+    pub instructions: Option<String>,
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -73,12 +100,7 @@ pub fn load_hands_from_dir(dir: &Path) -> Result<Vec<Hand>> {
     }
 
     let mut hands = Vec::new();
-    for entry in std::fs::read_dir(dir)? {
-        let entry = entry?;
-        let path = entry.path();
-        if !path.is_dir() {
-            continue;
-        }
+    for path in hand_dirs(dir)? {
         if let Some(hand) = load_hand_dir(&path)? {
             hands.push(hand);
         }
@@ -86,6 +108,19 @@ pub fn load_hands_from_dir(dir: &Path) -> Result<Vec<Hand>> {
     Ok(hands)
 }
 
+/// Subdirectories of `dir`, sorted by path.
+fn hand_dirs(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut dirs = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            dirs.push(path);
+        }
+    }
+    dirs.sort();
+    Ok(dirs)
+}
+
 fn load_hand_dir(path: &Path) -> Result<Option<Hand>> {
     let manifest_path = path.join("HAND.toml");
     if !manifest_path.is_file() {
@@ -106,6 +141,185 @@ fn load_hand_dir(path: &Path) -> Result<Option<Hand>> {
     }))
 }
 
+/// Result of validating one hand directory.
+#[derive(Debug, Clone)]
+pub struct HandReport {
+    /// Directory that was checked.
+    pub dir: PathBuf,
+    /// Manifest name, when the manifest parsed.
+    pub name: Option<String>,
+    /// Problems that stop the hand from loading or working.
+    pub errors: Vec<String>,
+    /// Likely mistakes that do not stop the hand from loading.
+    pub warnings: Vec<String>,
+}
+
+impl HandReport {
+    /// Whether the hand passed validation (warnings allowed).
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// Check a parsed manifest. Returns `(errors, warnings)`.
+///
+/// `known_tools` are the tool names that can be registered; `required_tools`
+/// entries outside it produce a warning, since they may still come from an
+/// MCP server or plugin.
+pub fn validate_manifest(
+    manifest: &HandManifest,
+    known_tools: &[&str],
+) -> (Vec<String>, Vec<String>) {
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+
+    let name = manifest.name.trim();
+    if name.is_empty() {
+        errors.push("`name` is empty".to_string());
+    } else if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        errors.push(format!(
+            "`name` '{}' may only contain letters, digits, '-' and '_'",
+            name
+        ));
+    } else if built_in_hands()
+        .iter()
+        .any(|h| h.manifest.name.eq_ignore_ascii_case(name))
+    {
+        warnings.push(format!(
+            "`name` '{}' is a built-in hand, which takes precedence over this one",
+            name
+        ));
+    }
+    if manifest.description.trim().is_empty() {
+        errors.push("`description` is empty".to_string());
+    }
+    if manifest.system_prompt.trim().is_empty() {
+        errors.push("`system_prompt` is empty".to_string());
+    }
+
+    for pattern in &manifest.guardrails.require_approval_for {
+        if let Err(reason) = check_tool_glob(pattern) {
+            errors.push(format!(
+                "guardrails.require_approval_for: '{}' {}",
+                pattern, reason
+            ));
+        }
+    }
+
+    for (i, stage) in manifest.stages.iter().enumerate() {
+        let hand = stage.hand.trim();
+        if hand.is_empty() {
+            errors.push(format!("stages[{}]: `hand` is empty", i));
+        } else if !name.is_empty() && hand.eq_ignore_ascii_case(name) {
+            errors.push(format!("stages[{}]: a pipeline cannot run itself", i));
+        }
+    }
+
+    if manifest.required_tools.is_empty() && !manifest.is_pipeline() {
+        warnings.push(
+            "`required_tools` is empty, so no tools are available while this hand is active"
+                .to_string(),
+        );
+    }
+    let mut seen = HashSet::new();
+    for tool in &manifest.required_tools {
+        let key = tool.to_ascii_lowercase();
+        if !seen.insert(key.clone()) {
+            warnings.push(format!("required_tools: '{}' is listed twice", tool));
+        } else if !known_tools.iter().any(|t| t.eq_ignore_ascii_case(&key)) {
+            let similar = crate::tools::suggest::similar_tools(&key, known_tools);
+            let hint = match similar.first() {
+                Some(s) => format!(" (did you mean '{}'?)", s),
+                None => " (fine if it comes from an MCP server or plugin)".to_string(),
+            };
+            warnings.push(format!(
+                "required_tools: '{}' is not a registered tool{}",
+                tool, hint
+            ));
+        }
+    }
+
+    (errors, warnings)
+}
+
+/// Validate a single hand directory, or every hand under a hands directory.
+///
+/// `dir` may hold a `HAND.toml` itself; otherwise each subdirectory is
+/// treated as a hand, and subdirectories without a `HAND.toml` fail.
+/// Unlike [`load_hands_from_dir`], a broken hand does not stop the others
+/// from being checked.
+pub fn validate_hands_dir(dir: &Path, known_tools: &[&str]) -> Result<Vec<HandReport>> {
+    if !dir.is_dir() {
+        return Err(ZeptoError::Config(format!(
+            "Hands path is not a directory: {}",
+            dir.display()
+        )));
+    }
+    let dirs = if dir.join("HAND.toml").is_file() {
+        vec![dir.to_path_buf()]
+    } else {
+        hand_dirs(dir)?
+    };
+
+    let mut names: HashMap<String, PathBuf> = HashMap::new();
+    let mut reports = Vec::new();
+    for path in dirs {
+        let mut report = HandReport {
+            dir: path.clone(),
+            name: None,
+            errors: Vec::new(),
+            warnings: Vec::new(),
+        };
+        match load_hand_dir(&path) {
+            Ok(Some(hand)) => {
+                let (errors, warnings) = validate_manifest(&hand.manifest, known_tools);
+                report.errors = errors;
+                report.warnings = warnings;
+                let key = hand.manifest.name.trim().to_ascii_lowercase();
+                if let Some(first) = names.get(&key) {
+                    report.errors.push(format!(
+                        "`name` '{}' is already used by {}",
+                        hand.manifest.name,
+                        first.display()
+                    ));
+                } else {
+                    names.insert(key, path.clone());
+                }
+                if hand.skill_md.trim().is_empty() {
+                    report
+                        .warnings
+                        .push("no SKILL.md (or it is empty)".to_string());
+                }
+                report.name = Some(hand.manifest.name);
+            }
+            Ok(None) => report.errors.push("missing HAND.toml".to_string()),
+            Err(e) => report.errors.push(e.to_string()),
+        }
+        reports.push(report);
+    }
+    Ok(reports)
+}
+
+/// Check a tool pattern against the globs approval supports: `*`,
+/// `prefix*`, `*suffix` or an exact name.
+fn check_tool_glob(pattern: &str) -> std::result::Result<(), &'static str> {
+    if pattern.trim().is_empty() {
+        return Err("is empty");
+    }
+    if pattern.contains(['?', '[', ']', '{', '}']) {
+        return Err("uses unsupported glob syntax (only '*' is supported)");
+    }
+    let stars = pattern.matches('*').count();
+    let edge_star = pattern == "*" || pattern.starts_with('*') || pattern.ends_with('*');
+    if stars > 1 || (stars == 1 && !edge_star) {
+        return Err("may only use a single '*' at the start or end");
+    }
+    Ok(())
+}
+
 fn built_in_researcher() -> Hand {
     Hand {
         manifest: HandManifest {
@@ -118,11 +332,15 @@ fn built_in_researcher() -> Hand {
                 "web_fetch".to_string(),
                 "memory_search".to_string(),
                 "memory_get".to_string(),
+                "index_workspace".to_string(),
+                "rag_query".to_string(),
             ],
             settings: HashMap::new(),
             guardrails: HandGuardrails {
                 require_approval_for: vec!["shell*".to_string(), "write_*".to_string()],
             },
+            stages: Vec::new(),
+            max_iterations: None,
         },
         skill_md: "# Researcher Skill\nPrioritize primary sources and include citations.".to_string(),
         source: HandSource::BuiltIn,
@@ -142,11 +360,15 @@ fn built_in_coder() -> Hand {
                 "edit_file".to_string(),
                 "list_dir".to_string(),
                 "git".to_string(),
+                "index_workspace".to_string(),
+                "rag_query".to_string(),
             ],
             settings: HashMap::new(),
             guardrails: HandGuardrails {
                 require_approval_for: vec!["shell*".to_string()],
             },
+            stages: Vec::new(),
+            max_iterations: None,
         },
         skill_md: "# Coder Skill\nKeep patches small, test-backed, and reversible.".to_string(),
         source: HandSource::BuiltIn,
@@ -170,6 +392,8 @@ fn built_in_monitor() -> Hand {
             guardrails: HandGuardrails {
                 require_approval_for: vec!["shell*".to_string(), "write_*".to_string()],
             },
+            stages: Vec::new(),
+            max_iterations: None,
         },
         skill_md: "# Monitor Skill\nOnly notify on meaningful changes.".to_string(),
         source: HandSource::BuiltIn,
@@ -196,4 +420,97 @@ require_approval_for = ["shell*"]
         assert_eq!(hand.required_tools.len(), 2);
         assert_eq!(hand.guardrails.require_approval_for, vec!["shell*"]);
     }
+
+    fn write_hand(root: &Path, dir: &str, manifest: &str) {
+        let path = root.join(dir);
+        std::fs::create_dir_all(&path).unwrap();
+        std::fs::write(path.join("HAND.toml"), manifest).unwrap();
+        std::fs::write(path.join("SKILL.md"), "# Skill").unwrap();
+    }
+
+    #[test]
+    fn validate_reports_mixed_hand_dirs() {
+        let root = tempfile::tempdir().unwrap();
+        let known = ["read_file", "web_fetch", "shell"];
+        write_hand(
+            root.path(),
+            "good",
+            r#"
+name = "good"
+description = "Works"
+system_prompt = "Do things"
+required_tools = ["read_file", "web_fech", "jira_search"]
+
+[guardrails]
+require_approval_for = ["shell*", "*_send", "*"]
+"#,
+        );
+        write_hand(
+            root.path(),
+            "bad_fields",
+            r#"
+name = "bad name"
+description = ""
+system_prompt = "x"
+required_tools = ["read_file"]
+
+[guardrails]
+require_approval_for = ["sh*ll", "web_?"]
+"#,
+        );
+        write_hand(root.path(), "unparseable", "name = \"broken\"\n");
+        write_hand(
+            root.path(),
+            "zz_dupe",
+            "name = \"GOOD\"\ndescription = \"d\"\nsystem_prompt = \"p\"\nrequired_tools = [\"shell\"]\n",
+        );
+        std::fs::create_dir_all(root.path().join("not_a_hand")).unwrap();
+
+        let reports = validate_hands_dir(root.path(), &known).unwrap();
+        let by_dir = |name: &str| {
+            reports
+                .iter()
+                .find(|r| r.dir.ends_with(name))
+                .unwrap()
+                .clone()
+        };
+        assert_eq!(reports.len(), 5);
+
+        let good = by_dir("good");
+        assert!(good.is_valid(), "{:?}", good.errors);
+        assert_eq!(good.warnings.len(), 2);
+        assert!(good.warnings[0].contains("'web_fech'") && good.warnings[0].contains("web_fetch"));
+        assert!(good.warnings[1].contains("'jira_search'") && good.warnings[1].contains("MCP"));
+
+        let bad = by_dir("bad_fields");
+        assert_eq!(bad.errors.len(), 4, "{:?}", bad.errors);
+        assert!(bad.errors[0].contains("may only contain"));
+        assert!(bad.errors[1].contains("`description` is empty"));
+        assert!(bad.errors[2].contains("'sh*ll'"));
+        assert!(bad.errors[3].contains("unsupported glob"));
+
+        let unparseable = by_dir("unparseable");
+        assert!(unparseable.name.is_none());
+        assert!(unparseable.errors[0].contains("Invalid HAND.toml"));
+
+        let dupe = by_dir("zz_dupe");
+        assert!(dupe.errors[0].contains("already used"));
+        assert_eq!(by_dir("not_a_hand").errors, ["missing HAND.toml"]);
+
+        // A single hand directory validates on its own.
+        let single = validate_hands_dir(&root.path().join("good"), &known).unwrap();
+        assert_eq!(single.len(), 1);
+        assert_eq!(single[0].name.as_deref(), Some("good"));
+    }
+
+    #[test]
+    fn built_in_hands_pass_validation() {
+        for hand in built_in_hands() {
+            let (errors, warnings) =
+                validate_manifest(&hand.manifest, crate::kernel::BUILTIN_TOOL_NAMES);
+            assert!(errors.is_empty(), "{}: {:?}", hand.manifest.name, errors);
+            // Only the shadowing notice about its own name is expected.
+            assert_eq!(warnings.len(), 1, "{}: {:?}", hand.manifest.name, warnings);
+        }
+    }
 }
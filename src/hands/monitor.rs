@@ -0,0 +1,749 @@
+//! Monitor hand watch list — scheduled URL checks with change detection.
+//!
+//! Targets come from `config.monitor.targets`. Each target is scheduled with
+//! the cron module's schedule math, fetched through the hardened
+//! [`HttpRequestTool`], diffed against the last snapshot stored on disk, and
+//! a notification is published to the configured channel only when the change
+//! is meaningful and has not already been reported. The stored snapshot only
+//! moves when a change is reported, so small changes accumulate against it
+//! until together they cross the target's threshold.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures::future::BoxFuture;
+use reqwest::Url;
+use tracing::{debug, info, warn};
+
+use crate::bus::{MessageBus, OutboundMessage};
+use crate::config::{MonitorConfig, MonitorSeverity, MonitorSeverityRule, MonitorTarget};
+use crate::cron::{is_valid_cron_expr, next_run_at, CronSchedule};
+use crate::error::{Result, ZeptoError};
+use crate::r8r_bridge::Deduplicator;
+use crate::scheduler::Scheduler;
+use crate::tools::HttpRequestTool;
+
+/// Minimum allowed check interval in seconds (prevents hammering targets).
+const MIN_INTERVAL_SECS: u64 = 10;
+
+/// Maximum diff lines included in a notification.
+const MAX_NOTIFY_DIFF_LINES: usize = 10;
+
+/// How long a reported snapshot hash suppresses repeat notifications.
+const NOTIFY_DEDUP_TTL_SECS: u64 = 24 * 3600;
+
+/// Maximum number of reported snapshot hashes remembered.
+const NOTIFY_DEDUP_MAX_ENTRIES: usize = 500;
+
+/// Line-level difference between two snapshots.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SnapshotDiff {
+    /// Lines present in the new snapshot but not the old one.
+    pub added: Vec<String>,
+    /// Lines present in the old snapshot but not the new one.
+    pub removed: Vec<String>,
+}
+
+impl SnapshotDiff {
+    /// Total number of added and removed lines.
+    pub fn changed_lines(&self) -> usize {
+        self.added.len() + self.removed.len()
+    }
+
+    /// Whether the snapshots are line-for-line identical.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// Outcome of comparing a fresh fetch against the stored snapshot.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChangeDecision {
+    /// No previous snapshot — the fetch becomes the baseline.
+    Baseline,
+    /// Content is unchanged after normalization.
+    Unchanged,
+    /// Content changed, but too little or at too low a severity to report.
+    BelowThreshold {
+        severity: MonitorSeverity,
+        diff: SnapshotDiff,
+    },
+    /// Content changed meaningfully and should be reported.
+    Notify {
+        severity: MonitorSeverity,
+        diff: SnapshotDiff,
+    },
+}
+
+/// Normalize a fetched body so whitespace-only edits do not count as change.
+///
+/// Trailing whitespace is stripped from each line and blank lines are dropped.
+pub fn normalize_snapshot(body: &str) -> String {
+    body.lines()
+        .map(str::trim_end)
+        .filter(|line| !line.trim().is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Compute added/removed lines between two normalized snapshots.
+///
+/// Uses multiset semantics: a line repeated twice in `current` but once in
+/// `previous` counts as one addition. Order within each list follows the
+/// source snapshot.
+pub fn diff_snapshots(previous: &str, current: &str) -> SnapshotDiff {
+    fn counts(text: &str) -> HashMap<&str, usize> {
+        let mut map = HashMap::new();
+        for line in text.lines() {
+            *map.entry(line).or_insert(0) += 1;
+        }
+        map
+    }
+
+    let mut prev_counts = counts(previous);
+    let mut curr_counts = counts(current);
+
+    let mut added = Vec::new();
+    for line in current.lines() {
+        match prev_counts.get_mut(line) {
+            Some(n) if *n > 0 => *n -= 1,
+            _ => added.push(line.to_string()),
+        }
+    }
+
+    let mut removed = Vec::new();
+    for line in previous.lines() {
+        match curr_counts.get_mut(line) {
+            Some(n) if *n > 0 => *n -= 1,
+            _ => removed.push(line.to_string()),
+        }
+    }
+
+    SnapshotDiff { added, removed }
+}
+
+/// Highest severity among rules whose pattern matches a changed line.
+///
+/// Returns [`MonitorSeverity::Info`] when no rule matches.
+pub fn classify_severity(rules: &[MonitorSeverityRule], diff: &SnapshotDiff) -> MonitorSeverity {
+    let changed: Vec<String> = diff
+        .added
+        .iter()
+        .chain(diff.removed.iter())
+        .map(|line| line.to_lowercase())
+        .collect();
+
+    rules
+        .iter()
+        .filter(|rule| !rule.pattern.is_empty())
+        .filter(|rule| {
+            let pattern = rule.pattern.to_lowercase();
+            changed.iter().any(|line| line.contains(&pattern))
+        })
+        .map(|rule| rule.severity)
+        .max()
+        .unwrap_or_default()
+}
+
+/// Decide whether the change between `previous` and `current` is meaningful
+/// for `target`. Both inputs must already be normalized.
+pub fn evaluate_change(
+    target: &MonitorTarget,
+    previous: Option<&str>,
+    current: &str,
+) -> ChangeDecision {
+    let Some(previous) = previous else {
+        return ChangeDecision::Baseline;
+    };
+
+    let diff = diff_snapshots(previous, current);
+    if diff.is_empty() {
+        return ChangeDecision::Unchanged;
+    }
+
+    let severity = classify_severity(&target.severity_rules, &diff);
+    if diff.changed_lines() < target.min_changed_lines.max(1) || severity < target.min_severity {
+        ChangeDecision::BelowThreshold { severity, diff }
+    } else {
+        ChangeDecision::Notify { severity, diff }
+    }
+}
+
+/// The "only notify on change" gate.
+///
+/// Returns `true` only for [`ChangeDecision::Notify`] decisions whose snapshot
+/// has not already been reported recently. This suppresses repeat alerts when
+/// a target flaps between the same states.
+pub fn should_notify(
+    dedup: &mut Deduplicator,
+    target_name: &str,
+    current: &str,
+    decision: &ChangeDecision,
+) -> bool {
+    match decision {
+        ChangeDecision::Notify { .. } => {
+            dedup.is_new(&format!("{}:{:016x}", target_name, content_hash(current)))
+        }
+        _ => false,
+    }
+}
+
+/// Render a terse change notification.
+pub fn format_notification(
+    target: &MonitorTarget,
+    severity: MonitorSeverity,
+    diff: &SnapshotDiff,
+) -> String {
+    let label = match severity {
+        MonitorSeverity::Info => "INFO",
+        MonitorSeverity::Warning => "WARNING",
+        MonitorSeverity::Critical => "CRITICAL",
+    };
+    let mut out = format!(
+        "[{}] {} changed (+{} / -{})\n{}",
+        label,
+        target.name,
+        diff.added.len(),
+        diff.removed.len(),
+        target.url
+    );
+
+    let lines: Vec<String> = diff
+        .added
+        .iter()
+        .map(|l| format!("+ {}", l))
+        .chain(diff.removed.iter().map(|l| format!("- {}", l)))
+        .collect();
+    for line in lines.iter().take(MAX_NOTIFY_DIFF_LINES) {
+        out.push('\n');
+        out.push_str(line);
+    }
+    if lines.len() > MAX_NOTIFY_DIFF_LINES {
+        out.push_str(&format!(
+            "\n... and {} more line(s)",
+            lines.len() - MAX_NOTIFY_DIFF_LINES
+        ));
+    }
+    out
+}
+
+/// Parse a duration like "30s", "15m", "1h", "1d" (or bare seconds).
+pub fn parse_interval_secs(s: &str) -> Option<u64> {
+    crate::utils::duration::parse_duration_secs(s)
+}
+
+/// Convert a target interval into a cron schedule.
+///
+/// Strings containing whitespace are treated as 5-field cron expressions;
+/// everything else is parsed as a duration.
+pub fn schedule_for_interval(interval: &str) -> Result<CronSchedule> {
+    let interval = interval.trim();
+    if interval.contains(char::is_whitespace) {
+        if is_valid_cron_expr(interval) {
+            return Ok(CronSchedule::Cron {
+                expr: interval.to_string(),
+                tz: None,
+            });
+        }
+        return Err(ZeptoError::Config(format!(
+            "Invalid monitor cron expression '{}'",
+            interval
+        )));
+    }
+
+    let secs = parse_interval_secs(interval).ok_or_else(|| {
+        ZeptoError::Config(format!(
+            "Invalid monitor interval '{}'. Use formats like 30s, 15m, 1h",
+            interval
+        ))
+    })?;
+    if secs < MIN_INTERVAL_SECS {
+        return Err(ZeptoError::Config(format!(
+            "Monitor interval too small ({}s). Minimum is {}s",
+            secs, MIN_INTERVAL_SECS
+        )));
+    }
+    Ok(CronSchedule::Every {
+        every_ms: (secs * 1000) as i64,
+    })
+}
+
+fn content_hash(content: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn snapshot_file(dir: &Path, target_name: &str) -> PathBuf {
+    let safe: String = target_name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    dir.join(format!(
+        "{}-{:08x}.txt",
+        safe,
+        content_hash(target_name) as u32
+    ))
+}
+
+fn now_ms() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+/// Background service that checks monitor targets on their schedules.
+pub struct MonitorService {
+    config: MonitorConfig,
+    snapshot_dir: PathBuf,
+    bus: Arc<MessageBus>,
+    running: Arc<AtomicBool>,
+    dedup: Mutex<Deduplicator>,
+}
+
+impl MonitorService {
+    /// Create a new monitor service storing snapshots in `snapshot_dir`.
+    pub fn new(config: MonitorConfig, snapshot_dir: PathBuf, bus: Arc<MessageBus>) -> Self {
+        Self {
+            config,
+            snapshot_dir,
+            bus,
+            running: Arc::new(AtomicBool::new(false)),
+            dedup: Mutex::new(Deduplicator::new(
+                NOTIFY_DEDUP_MAX_ENTRIES,
+                NOTIFY_DEDUP_TTL_SECS,
+            )),
+        }
+    }
+
+    /// Start the scheduling loop in the background (idempotent).
+    ///
+    /// Targets with invalid intervals are skipped with a warning. Each valid
+    /// target is checked once immediately to establish a baseline.
+    pub async fn start(self: &Arc<Self>) -> Result<()> {
+        if self.running.swap(true, Ordering::SeqCst) {
+            warn!("Monitor service already running");
+            return Ok(());
+        }
+
+        tokio::fs::create_dir_all(&self.snapshot_dir).await?;
+
+        let mut scheduled: Vec<(MonitorTarget, CronSchedule)> = Vec::new();
+        for target in &self.config.targets {
+            match schedule_for_interval(&target.interval) {
+                Ok(schedule) => scheduled.push((target.clone(), schedule)),
+                Err(e) => warn!(target = %target.name, "Skipping monitor target: {}", e),
+            }
+        }
+
+        info!("Monitor service started ({} target(s))", scheduled.len());
+
+        let service = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut next_due: HashMap<String, i64> = HashMap::new();
+            let mut ticker = tokio::time::interval(Duration::from_secs(1));
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+            while service.running.load(Ordering::SeqCst) {
+                ticker.tick().await;
+                for (target, schedule) in &scheduled {
+                    let now = now_ms();
+                    if next_due.get(&target.name).is_some_and(|due| *due > now) {
+                        continue;
+                    }
+                    if let Err(e) = service.check_target(target).await {
+                        warn!(target = %target.name, "Monitor check failed: {}", e);
+                    }
+                    let next = next_run_at(schedule, now_ms()).unwrap_or(i64::MAX);
+                    next_due.insert(target.name.clone(), next);
+                }
+            }
+            info!("Monitor service stopped");
+        });
+
+        Ok(())
+    }
+
+    /// Register each target with `scheduler` as job `monitor:<name>`, as an
+    /// alternative to [`MonitorService::start`] whose last-check times survive
+    /// restarts. Invalid targets are skipped. Returns the number registered.
+    pub fn register_with(self: &Arc<Self>, scheduler: &Scheduler) -> usize {
+        let mut registered = 0;
+        for target in &self.config.targets {
+            let schedule = match schedule_for_interval(&target.interval) {
+                Ok(schedule) => schedule,
+                Err(e) => {
+                    warn!(target = %target.name, "Skipping monitor target: {}", e);
+                    continue;
+                }
+            };
+            let service = Arc::clone(self);
+            let target = target.clone();
+            let job_name = format!("monitor:{}", target.name);
+            let job = move || -> BoxFuture<'static, Result<()>> {
+                let service = Arc::clone(&service);
+                let target = target.clone();
+                Box::pin(async move { service.check_target(&target).await.map(|_| ()) })
+            };
+            match scheduler.register(&job_name, schedule, job) {
+                Ok(_) => registered += 1,
+                Err(e) => warn!(job = %job_name, "Failed to schedule monitor target: {}", e),
+            }
+        }
+        registered
+    }
+
+    /// Stop the scheduling loop.
+    pub fn stop(&self) {
+        self.running.store(false, Ordering::SeqCst);
+    }
+
+    /// Returns whether the service is running.
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+
+    /// Fetch a target, compare it with its snapshot, and notify on change.
+    pub async fn check_target(&self, target: &MonitorTarget) -> Result<ChangeDecision> {
+        let body = self.fetch(target).await?;
+        let (decision, notification) = self.process_body(target, &body).await?;
+        if let Some(content) = notification {
+            let msg = OutboundMessage::new(&target.channel, &target.chat_id, &content);
+            self.bus.publish_outbound(msg).await?;
+            info!(target = %target.name, "Monitor change notification sent");
+        }
+        Ok(decision)
+    }
+
+    /// Fetch the target body through the hardened HTTP request tool, scoped
+    /// to the target's own host.
+    async fn fetch(&self, target: &MonitorTarget) -> Result<String> {
+        let parsed = Url::parse(&target.url)
+            .map_err(|e| ZeptoError::Config(format!("Invalid monitor URL: {e}")))?;
+        let host = parsed.host_str().unwrap_or_default().to_lowercase();
+        let fetcher = HttpRequestTool::new(
+            vec![host],
+            self.config.timeout_secs,
+            self.config.max_response_bytes,
+        );
+        let (status, body) = fetcher.fetch("GET", &target.url, Vec::new(), None).await?;
+        if !(200..300).contains(&status) {
+            return Err(ZeptoError::Tool(format!(
+                "HTTP {} for {}",
+                status, target.url
+            )));
+        }
+        Ok(body)
+    }
+
+    /// Compare `body` with the stored snapshot and return the decision plus
+    /// the notification text if one should be sent.
+    ///
+    /// The fetch replaces the stored snapshot only when it is the first one
+    /// or a reportable change; below-threshold changes leave it in place.
+    pub async fn process_body(
+        &self,
+        target: &MonitorTarget,
+        body: &str,
+    ) -> Result<(ChangeDecision, Option<String>)> {
+        let path = snapshot_file(&self.snapshot_dir, &target.name);
+        let previous = match tokio::fs::read_to_string(&path).await {
+            Ok(content) => Some(content),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e.into()),
+        };
+
+        let current = normalize_snapshot(body);
+        let decision = evaluate_change(target, previous.as_deref(), &current);
+
+        if matches!(
+            decision,
+            ChangeDecision::Baseline | ChangeDecision::Notify { .. }
+        ) {
+            if let Some(parent) = path.parent() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+            tokio::fs::write(&path, &current).await?;
+        }
+
+        let notify = {
+            let mut dedup = self.dedup.lock().unwrap_or_else(|e| e.into_inner());
+            should_notify(&mut dedup, &target.name, &current, &decision)
+        };
+
+        let notification = match (&decision, notify) {
+            (ChangeDecision::Notify { severity, diff }, true) => {
+                Some(format_notification(target, *severity, diff))
+            }
+            (ChangeDecision::Notify { .. }, false) => {
+                debug!(target = %target.name, "Monitor change already reported, suppressing");
+                None
+            }
+            _ => None,
+        };
+
+        Ok((decision, notification))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn target() -> MonitorTarget {
+        MonitorTarget {
+            name: "status-page".to_string(),
+            url: "https://status.example.com".to_string(),
+            interval: "15m".to_string(),
+            channel: "telegram".to_string(),
+            chat_id: "42".to_string(),
+            severity_rules: vec![
+                MonitorSeverityRule {
+                    pattern: "degraded".to_string(),
+                    severity: MonitorSeverity::Warning,
+                },
+                MonitorSeverityRule {
+                    pattern: "outage".to_string(),
+                    severity: MonitorSeverity::Critical,
+                },
+            ],
+            min_severity: MonitorSeverity::Info,
+            min_changed_lines: 1,
+        }
+    }
+
+    fn service(dir: &TempDir) -> MonitorService {
+        MonitorService::new(
+            MonitorConfig::default(),
+            dir.path().to_path_buf(),
+            Arc::new(MessageBus::new()),
+        )
+    }
+
+    #[test]
+    fn test_normalize_ignores_whitespace_only_edits() {
+        assert_eq!(
+            normalize_snapshot("a  \n\n  \nb\t\n"),
+            normalize_snapshot("a\nb")
+        );
+    }
+
+    #[test]
+    fn test_diff_snapshots_added_and_removed() {
+        let diff = diff_snapshots("a\nb\nc", "a\nc\nd");
+        assert_eq!(diff.added, vec!["d"]);
+        assert_eq!(diff.removed, vec!["b"]);
+        assert_eq!(diff.changed_lines(), 2);
+    }
+
+    #[test]
+    fn test_diff_snapshots_counts_duplicates() {
+        let diff = diff_snapshots("x", "x\nx");
+        assert_eq!(diff.added, vec!["x"]);
+        assert!(diff.removed.is_empty());
+    }
+
+    #[test]
+    fn test_diff_snapshots_identical_is_empty() {
+        assert!(diff_snapshots("a\nb", "a\nb").is_empty());
+    }
+
+    #[test]
+    fn test_classify_severity_takes_highest_match() {
+        let diff = SnapshotDiff {
+            added: vec!["API degraded".into(), "Full OUTAGE in eu-west".into()],
+            removed: vec![],
+        };
+        assert_eq!(
+            classify_severity(&target().severity_rules, &diff),
+            MonitorSeverity::Critical
+        );
+    }
+
+    #[test]
+    fn test_classify_severity_defaults_to_info() {
+        let diff = SnapshotDiff {
+            added: vec!["all systems operational".into()],
+            removed: vec![],
+        };
+        assert_eq!(
+            classify_severity(&target().severity_rules, &diff),
+            MonitorSeverity::Info
+        );
+    }
+
+    #[test]
+    fn test_evaluate_change_baseline_and_unchanged() {
+        let t = target();
+        assert_eq!(evaluate_change(&t, None, "a"), ChangeDecision::Baseline);
+        assert_eq!(
+            evaluate_change(&t, Some("a"), "a"),
+            ChangeDecision::Unchanged
+        );
+    }
+
+    #[test]
+    fn test_evaluate_change_respects_min_changed_lines() {
+        let mut t = target();
+        t.min_changed_lines = 3;
+        let decision = evaluate_change(&t, Some("a\nb"), "a\nc");
+        assert!(matches!(decision, ChangeDecision::BelowThreshold { .. }));
+    }
+
+    #[test]
+    fn test_evaluate_change_respects_min_severity() {
+        let mut t = target();
+        t.min_severity = MonitorSeverity::Warning;
+        let minor = evaluate_change(&t, Some("ok"), "still ok");
+        assert!(matches!(minor, ChangeDecision::BelowThreshold { .. }));
+
+        let major = evaluate_change(&t, Some("ok"), "service degraded");
+        assert_eq!(
+            major,
+            ChangeDecision::Notify {
+                severity: MonitorSeverity::Warning,
+                diff: SnapshotDiff {
+                    added: vec!["service degraded".into()],
+                    removed: vec!["ok".into()],
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn test_should_notify_only_for_notify_decisions() {
+        let mut dedup = Deduplicator::new(10, 600);
+        assert!(!should_notify(
+            &mut dedup,
+            "t",
+            "a",
+            &ChangeDecision::Baseline
+        ));
+        assert!(!should_notify(
+            &mut dedup,
+            "t",
+            "a",
+            &ChangeDecision::Unchanged
+        ));
+        let notify = ChangeDecision::Notify {
+            severity: MonitorSeverity::Info,
+            diff: SnapshotDiff::default(),
+        };
+        assert!(should_notify(&mut dedup, "t", "a", &notify));
+        assert!(!should_notify(&mut dedup, "t", "a", &notify));
+        assert!(should_notify(&mut dedup, "other", "a", &notify));
+    }
+
+    #[test]
+    fn test_format_notification_is_terse() {
+        let diff = SnapshotDiff {
+            added: (0..15).map(|i| format!("line {i}")).collect(),
+            removed: vec![],
+        };
+        let text = format_notification(&target(), MonitorSeverity::Critical, &diff);
+        assert!(text.starts_with("[CRITICAL] status-page changed (+15 / -0)"));
+        assert!(text.contains("+ line 0"));
+        assert!(!text.contains("+ line 12"));
+        assert!(text.ends_with("... and 5 more line(s)"));
+    }
+
+    #[test]
+    fn test_schedule_for_interval() {
+        assert!(matches!(
+            schedule_for_interval("15m").unwrap(),
+            CronSchedule::Every { every_ms: 900_000 }
+        ));
+        assert!(matches!(
+            schedule_for_interval("*/5 * * * *").unwrap(),
+            CronSchedule::Cron { .. }
+        ));
+        assert!(schedule_for_interval("5s").is_err());
+        assert!(schedule_for_interval("soon").is_err());
+        assert!(schedule_for_interval("61 * * * *").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_process_body_only_notifies_on_change() {
+        let dir = TempDir::new().unwrap();
+        let svc = service(&dir);
+        let t = target();
+
+        let (decision, note) = svc.process_body(&t, "all good").await.unwrap();
+        assert_eq!(decision, ChangeDecision::Baseline);
+        assert!(note.is_none());
+
+        let (decision, note) = svc.process_body(&t, "all good\n").await.unwrap();
+        assert_eq!(decision, ChangeDecision::Unchanged);
+        assert!(note.is_none());
+
+        let (_, note) = svc.process_body(&t, "partial outage").await.unwrap();
+        let note = note.expect("change should notify");
+        assert!(note.starts_with("[CRITICAL]"));
+        assert!(note.contains("+ partial outage"));
+        assert!(note.contains("- all good"));
+    }
+
+    #[tokio::test]
+    async fn test_process_body_suppresses_flapping_repeats() {
+        let dir = TempDir::new().unwrap();
+        let svc = service(&dir);
+        let t = target();
+
+        svc.process_body(&t, "state A").await.unwrap();
+        let (_, first) = svc.process_body(&t, "state B").await.unwrap();
+        let (_, back) = svc.process_body(&t, "state A").await.unwrap();
+        let (_, again) = svc.process_body(&t, "state B").await.unwrap();
+
+        assert!(first.is_some());
+        assert!(back.is_some());
+        assert!(again.is_none(), "state B was already reported");
+    }
+
+    #[tokio::test]
+    async fn test_process_body_persists_snapshot() {
+        let dir = TempDir::new().unwrap();
+        let svc = service(&dir);
+        let t = target();
+
+        svc.process_body(&t, "line one\n\nline two  ")
+            .await
+            .unwrap();
+        let stored =
+            std::fs::read_to_string(snapshot_file(dir.path(), &t.name)).expect("snapshot written");
+        assert_eq!(stored, "line one\nline two");
+    }
+
+    #[tokio::test]
+    async fn test_process_body_accumulates_small_changes() {
+        let dir = TempDir::new().unwrap();
+        let svc = service(&dir);
+        let mut t = target();
+        t.min_changed_lines = 3;
+
+        svc.process_body(&t, "a").await.unwrap();
+        let (decision, note) = svc.process_body(&t, "a\nb").await.unwrap();
+        assert!(matches!(decision, ChangeDecision::BelowThreshold { .. }));
+        assert!(note.is_none());
+        let (_, note) = svc.process_body(&t, "a\nb\nc").await.unwrap();
+        assert!(note.is_none());
+        let stored = std::fs::read_to_string(snapshot_file(dir.path(), &t.name)).unwrap();
+        assert_eq!(stored, "a", "baseline kept while below threshold");
+
+        // Three lines added since the baseline: together they notify.
+        let (decision, note) = svc.process_body(&t, "a\nb\nc\nd").await.unwrap();
+        assert!(matches!(decision, ChangeDecision::Notify { .. }));
+        assert!(note.is_some());
+        let stored = std::fs::read_to_string(snapshot_file(dir.path(), &t.name)).unwrap();
+        assert_eq!(stored, "a\nb\nc\nd");
+    }
+}
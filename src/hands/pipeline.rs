@@ -0,0 +1,365 @@
+//! Pipeline hands: chain other hands, feeding each stage's output forward.
+//!
+//! A hand whose `HAND.toml` lists `[[stages]]` does not act itself. Each
+//! stage runs a sub-hand as a scoped sub-agent — its system prompt and
+//! `required_tools` allowlist — on the original task plus the previous
+//! stage's output:
+//!
+//! ```toml
+//! name = "report"
+//! description = "Research a topic, then write it up"
+//! system_prompt = "Produce a sourced report."
+//! max_iterations = 30
+//!
+//! [[stages]]
+//! hand = "researcher"
+//!
+//! [[stages]]
+//! hand = "writer"
+//! instructions = "Turn the findings into a one-page report."
+//! ```
+//!
+//! `max_iterations` caps model calls across all stages (default: the
+//! agent's `max_tool_iterations`); a stage only gets what earlier stages left.
+//! Stages are run by a [`StageRunner`], in production the `delegate` tool.
+
+use std::path::Path;
+
+use async_trait::async_trait;
+
+use super::{resolve_hand, Hand, HandManifest};
+use crate::error::{Result, ZeptoError};
+
+/// A stage ready to run.
+#[derive(Debug, Clone)]
+pub struct PipelineStage {
+    /// The sub-hand that runs this stage.
+    pub hand: Hand,
+    /// Extra instructions from the pipeline manifest.
+    pub instructions: Option<String>,
+}
+
+/// What a stage produced.
+#[derive(Debug, Clone)]
+pub struct StageOutput {
+    /// Final reply of the stage's sub-agent.
+    pub text: String,
+    /// Model calls the stage used.
+    pub iterations: u32,
+}
+
+/// Runs one stage as a scoped sub-agent.
+#[async_trait]
+pub trait StageRunner: Send + Sync {
+    /// Run `stage` on `input` using at most `max_iterations` model calls.
+    async fn run_stage(
+        &self,
+        stage: &PipelineStage,
+        input: &str,
+        max_iterations: u32,
+    ) -> Result<StageOutput>;
+}
+
+/// Output of one completed stage.
+#[derive(Debug, Clone)]
+pub struct StageResult {
+    /// Name of the hand that ran the stage.
+    pub hand: String,
+    /// The stage's output.
+    pub output: String,
+    /// Model calls the stage used.
+    pub iterations: u32,
+}
+
+/// Result of running a pipeline.
+#[derive(Debug, Clone, Default)]
+pub struct PipelineOutcome {
+    /// Completed stages, in order.
+    pub stages: Vec<StageResult>,
+    /// Why the pipeline stopped early, if it did.
+    pub stopped: Option<String>,
+}
+
+impl PipelineOutcome {
+    /// Output of the last completed stage.
+    pub fn final_output(&self) -> Option<&str> {
+        self.stages.last().map(|s| s.output.as_str())
+    }
+
+    /// Model calls used across all stages.
+    pub fn iterations(&self) -> u32 {
+        self.stages.iter().map(|s| s.iterations).sum()
+    }
+
+    /// Text reply for the user: the final output, with a note when the
+    /// pipeline stopped early.
+    pub fn render(&self) -> String {
+        let mut out = self.final_output().unwrap_or_default().to_string();
+        if let Some(reason) = &self.stopped {
+            let completed: Vec<&str> = self.stages.iter().map(|s| s.hand.as_str()).collect();
+            let note = if completed.is_empty() {
+                format!("Pipeline stopped: {}.", reason)
+            } else {
+                format!(
+                    "Pipeline stopped after {}: {}.",
+                    completed.join(" → "),
+                    reason
+                )
+            };
+            out = if out.is_empty() {
+                note
+            } else {
+                format!("{}\n\n[{}]", out, note)
+            };
+        }
+        out
+    }
+}
+
+/// Resolve the stages of a pipeline manifest.
+///
+/// # Errors
+///
+/// Fails if the manifest has no stages, a stage names an unknown hand, or a
+/// stage is itself a pipeline (pipelines do not nest).
+pub fn resolve_stages(manifest: &HandManifest, hands_dir: &Path) -> Result<Vec<PipelineStage>> {
+    if !manifest.is_pipeline() {
+        return Err(ZeptoError::Config(format!(
+            "Hand '{}' has no stages",
+            manifest.name
+        )));
+    }
+    manifest
+        .stages
+        .iter()
+        .map(|stage| {
+            let hand = resolve_hand(&stage.hand, hands_dir)?.ok_or_else(|| {
+                ZeptoError::Config(format!(
+                    "Pipeline '{}': unknown stage hand '{}'",
+                    manifest.name, stage.hand
+                ))
+            })?;
+            if hand.manifest.is_pipeline() {
+                return Err(ZeptoError::Config(format!(
+                    "Pipeline '{}': stage '{}' is itself a pipeline",
+                    manifest.name, stage.hand
+                )));
+            }
+            Ok(PipelineStage {
+                hand,
+                instructions: stage.instructions.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Input for a stage: the task, the stage's instructions, and the previous
+/// stage's output.
+fn stage_input(task: &str, stage: &PipelineStage, previous: Option<&StageResult>) -> String {
+    let mut input = task.to_string();
+    if let Some(instructions) = stage
+        .instructions
+        .as_deref()
+        .filter(|i| !i.trim().is_empty())
+    {
+        input.push_str(&format!("\n\n## Your stage\n{}", instructions.trim()));
+    }
+    if let Some(previous) = previous {
+        input.push_str(&format!(
+            "\n\n## Output from the {} stage\n{}",
+            previous.hand, previous.output
+        ));
+    }
+    input
+}
+
+/// Run `stages` in order on `task`, threading each output into the next.
+///
+/// Stops early — keeping the completed stages — when a stage fails or the
+/// `max_iterations` budget is spent.
+pub async fn run_pipeline(
+    stages: &[PipelineStage],
+    task: &str,
+    max_iterations: u32,
+    runner: &dyn StageRunner,
+) -> PipelineOutcome {
+    let mut outcome = PipelineOutcome::default();
+    let mut remaining = max_iterations;
+    for stage in stages {
+        let name = stage.hand.manifest.name.clone();
+        if remaining == 0 {
+            outcome.stopped = Some(format!(
+                "iteration budget of {} used up before the {} stage",
+                max_iterations, name
+            ));
+            break;
+        }
+        let input = stage_input(task, stage, outcome.stages.last());
+        match runner.run_stage(stage, &input, remaining).await {
+            Ok(output) => {
+                let iterations = output.iterations.min(remaining);
+                remaining -= iterations;
+                outcome.stages.push(StageResult {
+                    hand: name,
+                    output: output.text,
+                    iterations,
+                });
+            }
+            Err(e) => {
+                outcome.stopped = Some(format!("the {} stage failed: {}", name, e));
+                break;
+            }
+        }
+    }
+    outcome
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hands::{parse_hand_toml, HandSource};
+    use std::sync::Mutex;
+
+    /// Records each stage's input and answers `"<hand> done"`, spending a
+    /// fixed number of iterations.
+    struct MockRunner {
+        cost: u32,
+        fail_on: Option<&'static str>,
+        calls: Mutex<Vec<(String, String, u32)>>,
+    }
+
+    impl MockRunner {
+        fn new(cost: u32) -> Self {
+            Self {
+                cost,
+                fail_on: None,
+                calls: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl StageRunner for MockRunner {
+        async fn run_stage(
+            &self,
+            stage: &PipelineStage,
+            input: &str,
+            max_iterations: u32,
+        ) -> Result<StageOutput> {
+            let name = stage.hand.manifest.name.clone();
+            self.calls
+                .lock()
+                .unwrap()
+                .push((name.clone(), input.to_string(), max_iterations));
+            if self.fail_on == Some(name.as_str()) {
+                return Err(ZeptoError::Tool("provider down".into()));
+            }
+            Ok(StageOutput {
+                text: format!("{} done", name),
+                iterations: self.cost,
+            })
+        }
+    }
+
+    fn stage(name: &str, instructions: Option<&str>) -> PipelineStage {
+        let raw = format!(
+            "name = \"{}\"\ndescription = \"d\"\nsystem_prompt = \"p\"\n",
+            name
+        );
+        PipelineStage {
+            hand: Hand {
+                manifest: parse_hand_toml(&raw).unwrap(),
+                skill_md: String::new(),
+                source: HandSource::User,
+            },
+            instructions: instructions.map(str::to_string),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stages_run_in_order_and_thread_output() {
+        let stages = [
+            stage("researcher", None),
+            stage("summarizer", Some("Condense to five bullets.")),
+            stage("coder", None),
+        ];
+        let runner = MockRunner::new(2);
+        let outcome = run_pipeline(&stages, "Compare Rust web frameworks", 20, &runner).await;
+
+        assert!(outcome.stopped.is_none());
+        assert_eq!(outcome.final_output(), Some("coder done"));
+        assert_eq!(outcome.iterations(), 6);
+
+        let calls = runner.calls.lock().unwrap();
+        let order: Vec<&str> = calls.iter().map(|c| c.0.as_str()).collect();
+        assert_eq!(order, ["researcher", "summarizer", "coder"]);
+        assert_eq!(calls[0].1, "Compare Rust web frameworks");
+        assert_eq!(
+            calls[1].1,
+            "Compare Rust web frameworks\n\n## Your stage\nCondense to five bullets.\n\n\
+             ## Output from the researcher stage\nresearcher done"
+        );
+        assert!(calls[2]
+            .1
+            .ends_with("## Output from the summarizer stage\nsummarizer done"));
+        // Each stage gets only the budget earlier stages left.
+        let budgets: Vec<u32> = calls.iter().map(|c| c.2).collect();
+        assert_eq!(budgets, [20, 18, 16]);
+    }
+
+    #[tokio::test]
+    async fn test_iteration_budget_and_failures_stop_pipeline() {
+        let stages = [stage("researcher", None), stage("coder", None)];
+
+        let runner = MockRunner::new(5);
+        let outcome = run_pipeline(&stages, "task", 5, &runner).await;
+        assert_eq!(outcome.stages.len(), 1);
+        assert_eq!(runner.calls.lock().unwrap().len(), 1);
+        assert_eq!(
+            outcome.render(),
+            "researcher done\n\n[Pipeline stopped after researcher: iteration budget of 5 \
+             used up before the coder stage.]"
+        );
+
+        let mut runner = MockRunner::new(1);
+        runner.fail_on = Some("researcher");
+        let outcome = run_pipeline(&stages, "task", 10, &runner).await;
+        assert!(outcome.stages.is_empty());
+        assert!(outcome
+            .render()
+            .starts_with("Pipeline stopped: the researcher stage failed"));
+    }
+
+    #[test]
+    fn test_resolve_stages_rejects_unknown_and_nested() {
+        let dir = tempfile::tempdir().unwrap();
+        let raw = r#"
+name = "report"
+description = "Research then code"
+system_prompt = "p"
+
+[[stages]]
+hand = "researcher"
+
+[[stages]]
+hand = "coder"
+instructions = "Write the script"
+"#;
+        let manifest = parse_hand_toml(raw).unwrap();
+        let stages = resolve_stages(&manifest, dir.path()).unwrap();
+        assert_eq!(stages.len(), 2);
+        assert_eq!(stages[1].hand.manifest.name, "coder");
+        assert_eq!(stages[1].instructions.as_deref(), Some("Write the script"));
+
+        let unknown = parse_hand_toml(&raw.replace("\"coder\"", "\"juggler\"")).unwrap();
+        let err = resolve_stages(&unknown, dir.path()).unwrap_err();
+        assert!(err.to_string().contains("unknown stage hand 'juggler'"));
+
+        let nested_dir = dir.path().join("report");
+        std::fs::create_dir_all(&nested_dir).unwrap();
+        std::fs::write(nested_dir.join("HAND.toml"), raw).unwrap();
+        let outer = parse_hand_toml(&raw.replace("\"coder\"", "\"report\"")).unwrap();
+        let err = resolve_stages(&outer, dir.path()).unwrap_err();
+        assert!(err.to_string().contains("is itself a pipeline"));
+    }
+}
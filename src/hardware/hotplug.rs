@@ -0,0 +1,313 @@
+//! USB hotplug watcher -- emits `DeviceAdded` / `DeviceRemoved` events.
+//!
+//! With the `hardware` feature on a platform `nusb` supports, the watcher
+//! listens for native hotplug notifications and rescans on each one. Otherwise
+//! (or if the native watch cannot be opened) it falls back to polling
+//! [`HardwareManager::discover_devices`] every `poll_interval`. Either way,
+//! events are derived by diffing consecutive scans, so they carry the same
+//! registry-enriched [`DiscoveredDevice`] data as `discover_devices`. Scans
+//! enumerate USB synchronously and run on the blocking thread pool.
+//!
+//! The gateway starts a watcher when `devices.enabled` and
+//! `devices.monitor_usb` are set and publishes each event to the bus.
+
+use std::time::Duration;
+
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use serde::Serialize;
+use tokio::sync::{mpsc, watch};
+use tracing::{debug, info, warn};
+
+use super::{DiscoveredDevice, HardwareManager};
+
+/// Delay after a native hotplug notification before rescanning, giving the OS
+/// time to create serial device nodes for the new board.
+const HOTPLUG_SETTLE: Duration = Duration::from_millis(250);
+
+/// Default interval between scans when native notifications are unavailable.
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// A device arrival or departure.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(tag = "event", content = "device")]
+pub enum HotplugEvent {
+    /// A device appeared since the previous scan.
+    DeviceAdded(DiscoveredDevice),
+    /// A device disappeared since the previous scan.
+    DeviceRemoved(DiscoveredDevice),
+}
+
+impl HotplugEvent {
+    /// The device this event refers to.
+    pub fn device(&self) -> &DiscoveredDevice {
+        match self {
+            Self::DeviceAdded(d) | Self::DeviceRemoved(d) => d,
+        }
+    }
+
+    /// Human-readable summary, e.g.
+    /// `USB device connected: esp32 (1a86:7523) at /dev/ttyUSB0`.
+    pub fn format_message(&self) -> String {
+        let action = match self {
+            Self::DeviceAdded(_) => "connected",
+            Self::DeviceRemoved(_) => "disconnected",
+        };
+        let dev = self.device();
+        let mut msg = format!(
+            "USB device {}: {} ({:04x}:{:04x})",
+            action, dev.name, dev.vid, dev.pid
+        );
+        if let Some(path) = &dev.device_path {
+            msg.push_str(&format!(" at {}", path));
+        }
+        msg
+    }
+}
+
+/// Compute hotplug events between two scans.
+///
+/// Devices are compared by VID, PID, serial path and product string, counting
+/// duplicates so that plugging in a second identical board is still reported.
+/// Removals are emitted before additions.
+pub fn diff_scans(
+    previous: &[DiscoveredDevice],
+    current: &[DiscoveredDevice],
+) -> Vec<HotplugEvent> {
+    let mut unmatched: Vec<&DiscoveredDevice> = previous.iter().collect();
+    let mut added = Vec::new();
+
+    for dev in current {
+        match unmatched.iter().position(|p| same_device(p, dev)) {
+            Some(pos) => {
+                unmatched.swap_remove(pos);
+            }
+            None => added.push(HotplugEvent::DeviceAdded(dev.clone())),
+        }
+    }
+
+    let mut events: Vec<HotplugEvent> = unmatched
+        .into_iter()
+        .map(|d| HotplugEvent::DeviceRemoved(d.clone()))
+        .collect();
+    events.extend(added);
+    events
+}
+
+fn same_device(a: &DiscoveredDevice, b: &DiscoveredDevice) -> bool {
+    a.vid == b.vid && a.pid == b.pid && a.device_path == b.device_path && a.detail == b.detail
+}
+
+/// Hotplug watcher using native USB notifications with a polling fallback.
+pub struct HotplugWatcher {
+    poll_interval: Duration,
+}
+
+impl HotplugWatcher {
+    pub fn new(poll_interval: Duration) -> Self {
+        Self { poll_interval }
+    }
+
+    /// Run until shutdown is signalled or the receiver is dropped.
+    ///
+    /// The initial scan is the baseline; devices already connected at start
+    /// do not produce `DeviceAdded` events.
+    pub async fn watch(
+        self,
+        tx: mpsc::UnboundedSender<HotplugEvent>,
+        mut shutdown_rx: watch::Receiver<bool>,
+    ) {
+        let mut known = scan().await.unwrap_or_default();
+        let mut native = native_hotplug_stream();
+        if native.is_none() {
+            debug!(
+                interval_ms = self.poll_interval.as_millis() as u64,
+                "USB hotplug using polling"
+            );
+        }
+
+        loop {
+            tokio::select! {
+                _ = shutdown_rx.changed() => {
+                    if *shutdown_rx.borrow() {
+                        info!("Hotplug watcher shutting down");
+                        return;
+                    }
+                    continue;
+                }
+                _ = wait_for_change(&mut native, self.poll_interval) => {}
+            }
+
+            if *shutdown_rx.borrow() {
+                return;
+            }
+
+            let Some(current) = scan().await else {
+                continue;
+            };
+            for event in diff_scans(&known, &current) {
+                debug!(?event, "USB hotplug event");
+                if tx.send(event).is_err() {
+                    warn!("Hotplug watcher receiver dropped, stopping watcher");
+                    return;
+                }
+            }
+            known = current;
+        }
+    }
+}
+
+/// Enumerate devices on the blocking pool. `None` if the scan task failed,
+/// so a failed scan is not mistaken for every device being removed.
+async fn scan() -> Option<Vec<DiscoveredDevice>> {
+    match tokio::task::spawn_blocking(|| HardwareManager::new().discover_devices()).await {
+        Ok(devices) => Some(devices),
+        Err(e) => {
+            warn!("USB device scan failed: {e}");
+            None
+        }
+    }
+}
+
+/// Wait for the next native notification, or one poll interval.
+///
+/// If the native stream ends, it is dropped and polling takes over.
+async fn wait_for_change(native: &mut Option<BoxStream<'static, ()>>, poll_interval: Duration) {
+    if let Some(stream) = native.as_mut() {
+        if stream.next().await.is_some() {
+            tokio::time::sleep(HOTPLUG_SETTLE).await;
+            return;
+        }
+        warn!("USB hotplug stream ended; falling back to polling");
+        *native = None;
+    }
+    tokio::time::sleep(poll_interval).await;
+}
+
+#[cfg(all(
+    feature = "hardware",
+    any(target_os = "linux", target_os = "macos", target_os = "windows")
+))]
+fn native_hotplug_stream() -> Option<BoxStream<'static, ()>> {
+    match nusb::watch_devices() {
+        Ok(stream) => Some(stream.map(|_| ()).boxed()),
+        Err(e) => {
+            warn!("USB hotplug notifications unavailable ({e}); falling back to polling");
+            None
+        }
+    }
+}
+
+#[cfg(not(all(
+    feature = "hardware",
+    any(target_os = "linux", target_os = "macos", target_os = "windows")
+)))]
+fn native_hotplug_stream() -> Option<BoxStream<'static, ()>> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn device(name: &str, vid: u16, pid: u16, path: Option<&str>) -> DiscoveredDevice {
+        DiscoveredDevice {
+            name: name.to_string(),
+            detail: None,
+            device_path: path.map(String::from),
+            vid,
+            pid,
+            architecture: None,
+        }
+    }
+
+    #[test]
+    fn test_event_device_accessor() {
+        let dev = device("nucleo-f401re", 0x0483, 0x374b, Some("/dev/ttyACM0"));
+        assert_eq!(HotplugEvent::DeviceAdded(dev.clone()).device(), &dev);
+        assert_eq!(HotplugEvent::DeviceRemoved(dev.clone()).device(), &dev);
+    }
+
+    #[test]
+    fn test_event_serialize() {
+        let event = HotplugEvent::DeviceAdded(device("arduino-uno", 0x2341, 0x0043, None));
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["event"], "DeviceAdded");
+        assert_eq!(json["device"]["name"], "arduino-uno");
+        assert_eq!(json["device"]["vid"], 0x2341);
+    }
+
+    #[test]
+    fn test_event_format_message() {
+        let dev = device("esp32", 0x1a86, 0x7523, Some("/dev/ttyUSB0"));
+        assert_eq!(
+            HotplugEvent::DeviceAdded(dev).format_message(),
+            "USB device connected: esp32 (1a86:7523) at /dev/ttyUSB0"
+        );
+        let dev = device("arduino-uno", 0x2341, 0x0043, None);
+        assert_eq!(
+            HotplugEvent::DeviceRemoved(dev).format_message(),
+            "USB device disconnected: arduino-uno (2341:0043)"
+        );
+    }
+
+    #[test]
+    fn test_diff_scans_identical_is_empty() {
+        let scan = vec![device("esp32", 0x1a86, 0x7523, Some("/dev/ttyUSB0"))];
+        assert!(diff_scans(&scan, &scan).is_empty());
+        assert!(diff_scans(&[], &[]).is_empty());
+    }
+
+    #[test]
+    fn test_diff_scans_added_and_removed() {
+        let uno = device("arduino-uno", 0x2341, 0x0043, Some("/dev/ttyACM0"));
+        let esp = device("esp32", 0x1a86, 0x7523, Some("/dev/ttyUSB0"));
+        let nucleo = device("nucleo-f401re", 0x0483, 0x374b, Some("/dev/ttyACM1"));
+
+        let events = diff_scans(&[uno.clone(), esp.clone()], &[esp, nucleo.clone()]);
+        assert_eq!(
+            events,
+            vec![
+                HotplugEvent::DeviceRemoved(uno),
+                HotplugEvent::DeviceAdded(nucleo),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_scans_counts_identical_boards() {
+        let a = device("esp32", 0x1a86, 0x7523, None);
+        let events = diff_scans(&[a.clone()], &[a.clone(), a.clone()]);
+        assert_eq!(events, vec![HotplugEvent::DeviceAdded(a.clone())]);
+
+        let events = diff_scans(&[a.clone(), a.clone()], &[a.clone()]);
+        assert_eq!(events, vec![HotplugEvent::DeviceRemoved(a)]);
+    }
+
+    #[test]
+    fn test_diff_scans_path_change_is_replug() {
+        let before = device("esp32", 0x1a86, 0x7523, Some("/dev/ttyUSB0"));
+        let after = device("esp32", 0x1a86, 0x7523, Some("/dev/ttyUSB1"));
+        let events = diff_scans(&[before.clone()], &[after.clone()]);
+        assert_eq!(
+            events,
+            vec![
+                HotplugEvent::DeviceRemoved(before),
+                HotplugEvent::DeviceAdded(after),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_watcher_stops_on_shutdown() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let handle =
+            tokio::spawn(HotplugWatcher::new(Duration::from_millis(10)).watch(tx, shutdown_rx));
+        shutdown_tx.send(true).unwrap();
+        tokio::time::timeout(Duration::from_secs(2), handle)
+            .await
+            .expect("watcher should stop")
+            .unwrap();
+    }
+}
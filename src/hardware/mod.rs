@@ -2,12 +2,16 @@
 //!
 //! This module provides hardware discovery capabilities for ZeptoClaw:
 //!
-//! - **Board registry** (`registry`): Static VID/PID to board name mapping (always compiled)
+//! - **Board registry** (`registry`): VID/PID to board name mapping, built-in plus
+//!   `~/.zeptoclaw/hardware/boards.toml` overrides (always compiled)
 //! - **USB discovery** (`discover`): Enumerate connected USB devices (feature-gated: `hardware`)
 //! - **Introspection** (`introspect`): Correlate serial paths with USB devices (feature-gated: `hardware`)
+//! - **Hotplug** (`hotplug`): `DeviceAdded`/`DeviceRemoved` events via native USB
+//!   notifications (feature-gated: `hardware`) or polling fallback (always compiled)
 //!
 //! The `HardwareManager` orchestrator ties these together for the agent tool and CLI.
 
+pub mod hotplug;
 pub mod registry;
 
 #[cfg(all(
@@ -28,7 +32,7 @@ use serde::Serialize;
 ///
 /// This is the unified device representation used by the CLI and agent tool.
 /// It is always available (no feature gate) so that stub code can reference it.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, PartialEq)]
 pub struct DiscoveredDevice {
     /// Human-readable device name (board name or "VID:PID" fallback)
     pub name: String,
@@ -68,6 +72,7 @@ impl HardwareManager {
             any(target_os = "linux", target_os = "macos", target_os = "windows")
         ))]
         {
+            let mut serial_ports = usb_serial_ports();
             match discover::list_usb_devices() {
                 Ok(devices) => devices
                     .into_iter()
@@ -76,7 +81,7 @@ impl HardwareManager {
                             .board_name
                             .unwrap_or_else(|| format!("{:04x}:{:04x}", d.vid, d.pid)),
                         detail: d.product_string,
-                        device_path: None,
+                        device_path: take_serial_port(&mut serial_ports, d.vid, d.pid),
                         vid: d.vid,
                         pid: d.pid,
                         architecture: d.architecture,
@@ -138,6 +143,37 @@ impl HardwareManager {
     }
 }
 
+/// List USB serial ports as `(vid, pid, path)`, best-effort.
+#[cfg(all(
+    feature = "hardware",
+    any(target_os = "linux", target_os = "macos", target_os = "windows")
+))]
+fn usb_serial_ports() -> Vec<(u16, u16, String)> {
+    tokio_serial::available_ports()
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|p| match p.port_type {
+            tokio_serial::SerialPortType::UsbPort(usb) => Some((usb.vid, usb.pid, p.port_name)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Claim the first unassigned serial port matching `vid`/`pid`.
+///
+/// Ports are consumed so that two identical boards get distinct paths.
+#[cfg_attr(
+    not(all(
+        feature = "hardware",
+        any(target_os = "linux", target_os = "macos", target_os = "windows")
+    )),
+    allow(dead_code)
+)]
+fn take_serial_port(ports: &mut Vec<(u16, u16, String)>, vid: u16, pid: u16) -> Option<String> {
+    let pos = ports.iter().position(|(v, p, _)| *v == vid && *p == pid)?;
+    Some(ports.remove(pos).2)
+}
+
 impl Default for HardwareManager {
     fn default() -> Self {
         Self::new()
@@ -214,6 +250,25 @@ mod tests {
         assert!(info.is_none());
     }
 
+    #[test]
+    fn test_take_serial_port_assigns_distinct_paths() {
+        let mut ports = vec![
+            (0x1a86, 0x7523, "/dev/ttyUSB0".to_string()),
+            (0x2341, 0x0043, "/dev/ttyACM0".to_string()),
+            (0x1a86, 0x7523, "/dev/ttyUSB1".to_string()),
+        ];
+        assert_eq!(
+            take_serial_port(&mut ports, 0x1a86, 0x7523).as_deref(),
+            Some("/dev/ttyUSB0")
+        );
+        assert_eq!(
+            take_serial_port(&mut ports, 0x1a86, 0x7523).as_deref(),
+            Some("/dev/ttyUSB1")
+        );
+        assert_eq!(take_serial_port(&mut ports, 0x1a86, 0x7523), None);
+        assert_eq!(ports.len(), 1);
+    }
+
     #[test]
     fn test_hardware_manager_device_info_invalid_vid_pid() {
         let mgr = HardwareManager::new();
@@ -1,11 +1,30 @@
 //! Board registry -- maps USB VID/PID to known board names and architectures.
 //!
-//! This module provides a static lookup table of known development boards,
-//! mapping their USB Vendor ID (VID) and Product ID (PID) to human-readable
-//! board names and architecture descriptions. The registry is always compiled
-//! (no feature gate) so that board lookups work in all builds.
+//! This module provides a lookup table of known development boards, mapping
+//! their USB Vendor ID (VID) and Product ID (PID) to human-readable board names
+//! and architecture descriptions. The registry is always compiled (no feature
+//! gate) so that board lookups work in all builds.
+//!
+//! The built-in list can be extended without recompiling by dropping a TOML
+//! file at `~/.zeptoclaw/hardware/boards.toml`:
+//!
+//! ```toml
+//! [[board]]
+//! vid = 0x0483
+//! pid = 0x374e
+//! name = "nucleo-f303k8"
+//! architecture = "ARM Cortex-M4"
+//! ```
+//!
+//! User entries are keyed by VID:PID and override built-in entries with the
+//! same key. Malformed entries are skipped with a warning.
 
+use once_cell::sync::Lazy;
 use serde::Serialize;
+use std::path::Path;
+use tracing::warn;
+
+use crate::config::Config;
 
 /// Information about a known development board.
 #[derive(Debug, Clone, Serialize)]
@@ -83,19 +102,139 @@ const KNOWN_BOARDS: &[BoardInfo] = &[
     },
 ];
 
+/// Merged registry: built-in boards plus user definitions, loaded once.
+static REGISTRY: Lazy<Vec<BoardInfo>> = Lazy::new(|| {
+    let path = user_boards_path();
+    merge_boards(KNOWN_BOARDS, load_boards_file(&path))
+});
+
+/// Path of the user board definitions file (`~/.zeptoclaw/hardware/boards.toml`).
+pub fn user_boards_path() -> std::path::PathBuf {
+    Config::dir().join("hardware").join("boards.toml")
+}
+
 /// Look up a board by USB Vendor ID and Product ID.
 ///
 /// Returns `Some(&BoardInfo)` if the VID/PID pair matches a known board,
 /// or `None` if the device is not recognized.
 pub fn lookup_board(vid: u16, pid: u16) -> Option<&'static BoardInfo> {
-    KNOWN_BOARDS.iter().find(|b| b.vid == vid && b.pid == pid)
+    REGISTRY.iter().find(|b| b.vid == vid && b.pid == pid)
 }
 
-/// Return all known board entries in the registry.
+/// Return all known board entries in the registry (built-in and user-defined).
 pub fn known_boards() -> &'static [BoardInfo] {
+    &REGISTRY
+}
+
+/// Return only the compiled-in board entries.
+pub fn builtin_boards() -> &'static [BoardInfo] {
     KNOWN_BOARDS
 }
 
+/// Load board definitions from a TOML file.
+///
+/// A missing file yields no entries; an unreadable or unparsable file is
+/// logged and ignored so a bad user file never breaks discovery.
+pub fn load_boards_file(path: &Path) -> Vec<BoardInfo> {
+    if !path.exists() {
+        return Vec::new();
+    }
+    match std::fs::read_to_string(path) {
+        Ok(content) => parse_boards_toml(&content),
+        Err(e) => {
+            warn!("Failed to read board registry {}: {e}", path.display());
+            Vec::new()
+        }
+    }
+}
+
+/// Parse `[[board]]` entries from TOML, skipping malformed ones.
+///
+/// `vid`/`pid` may be integers (`0x0483`) or hex strings (`"0483"`,
+/// `"0x0483"`); `name` is required and `architecture` is optional.
+pub fn parse_boards_toml(content: &str) -> Vec<BoardInfo> {
+    let table: toml::Table = match content.parse() {
+        Ok(t) => t,
+        Err(e) => {
+            warn!("Invalid board registry TOML: {e}");
+            return Vec::new();
+        }
+    };
+    let Some(entries) = table.get("board").and_then(|v| v.as_array()) else {
+        return Vec::new();
+    };
+
+    entries
+        .iter()
+        .enumerate()
+        .filter_map(|(i, entry)| match parse_board_entry(entry) {
+            Ok(board) => Some(board),
+            Err(reason) => {
+                warn!("Skipping board registry entry #{}: {reason}", i + 1);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Overlay `extra` entries on `builtin`, keyed by VID:PID.
+///
+/// An extra entry replaces the built-in entry with the same VID:PID in place;
+/// new VID:PID pairs are appended. Later extra entries win over earlier ones.
+pub fn merge_boards(builtin: &[BoardInfo], extra: Vec<BoardInfo>) -> Vec<BoardInfo> {
+    let mut merged = builtin.to_vec();
+    for board in extra {
+        match merged
+            .iter_mut()
+            .find(|b| b.vid == board.vid && b.pid == board.pid)
+        {
+            Some(existing) => *existing = board,
+            None => merged.push(board),
+        }
+    }
+    merged
+}
+
+fn parse_board_entry(entry: &toml::Value) -> std::result::Result<BoardInfo, String> {
+    let table = entry.as_table().ok_or("entry is not a table")?;
+    let vid = parse_usb_id(table.get("vid")).ok_or("missing or invalid 'vid'")?;
+    let pid = parse_usb_id(table.get("pid")).ok_or("missing or invalid 'pid'")?;
+    let name = table
+        .get("name")
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|n| !n.is_empty())
+        .ok_or("missing or empty 'name'")?;
+    let architecture = match table.get("architecture") {
+        None => None,
+        Some(v) => Some(v.as_str().ok_or("'architecture' must be a string")?),
+    };
+
+    // Registry entries are `'static`; user boards are loaded once per process,
+    // so leaking their strings is bounded.
+    Ok(BoardInfo {
+        vid,
+        pid,
+        name: Box::leak(name.to_string().into_boxed_str()),
+        architecture: architecture.map(|a| &*Box::leak(a.to_string().into_boxed_str())),
+    })
+}
+
+fn parse_usb_id(value: Option<&toml::Value>) -> Option<u16> {
+    match value? {
+        toml::Value::Integer(n) => u16::try_from(*n).ok(),
+        toml::Value::String(s) => {
+            let s = s.trim();
+            let hex = s
+                .strip_prefix("0x")
+                .or_else(|| s.strip_prefix("0X"))
+                .unwrap_or(s);
+            u16::from_str_radix(hex, 16).ok()
+        }
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -159,8 +298,9 @@ mod tests {
     }
 
     #[test]
-    fn test_known_boards_count() {
-        assert_eq!(known_boards().len(), 9);
+    fn test_builtin_boards_count() {
+        assert_eq!(builtin_boards().len(), 9);
+        assert!(known_boards().len() >= builtin_boards().len());
     }
 
     #[test]
@@ -182,4 +322,149 @@ mod tests {
         assert_eq!(json["vid"], 0x0483);
         assert_eq!(json["pid"], 0x374b);
     }
+
+    // ---- user registry tests ----
+
+    #[test]
+    fn test_parse_boards_toml_int_and_hex_ids() {
+        let boards = parse_boards_toml(
+            r#"
+[[board]]
+vid = 0x0483
+pid = 0x374e
+name = "nucleo-f303k8"
+architecture = "ARM Cortex-M4"
+
+[[board]]
+vid = "2e8a"
+pid = "0x000a"
+name = "rp2040"
+"#,
+        );
+        assert_eq!(boards.len(), 2);
+        assert_eq!((boards[0].vid, boards[0].pid), (0x0483, 0x374e));
+        assert_eq!(boards[0].name, "nucleo-f303k8");
+        assert_eq!(boards[0].architecture, Some("ARM Cortex-M4"));
+        assert_eq!((boards[1].vid, boards[1].pid), (0x2e8a, 0x000a));
+        assert_eq!(boards[1].architecture, None);
+    }
+
+    #[test]
+    fn test_parse_boards_toml_skips_malformed_entries() {
+        let boards = parse_boards_toml(
+            r#"
+[[board]]
+vid = 0x1234
+pid = 0x5678
+name = "good"
+
+[[board]]
+pid = 0x0001
+name = "missing-vid"
+
+[[board]]
+vid = 70000
+pid = 0x0001
+name = "vid-out-of-range"
+
+[[board]]
+vid = "zz"
+pid = 0x0001
+name = "bad-hex"
+
+[[board]]
+vid = 0x1234
+pid = 0x0002
+name = "  "
+
+[[board]]
+vid = 0x1234
+pid = 0x0003
+name = "bad-arch"
+architecture = 42
+"#,
+        );
+        assert_eq!(boards.len(), 1);
+        assert_eq!(boards[0].name, "good");
+    }
+
+    #[test]
+    fn test_parse_boards_toml_invalid_document() {
+        assert!(parse_boards_toml("[[board]\nvid =").is_empty());
+        assert!(parse_boards_toml("").is_empty());
+    }
+
+    #[test]
+    fn test_merge_boards_user_overrides_builtin() {
+        let extra = parse_boards_toml(
+            r#"
+[[board]]
+vid = 0x0483
+pid = 0x374b
+name = "my-nucleo"
+
+[[board]]
+vid = 0xaaaa
+pid = 0xbbbb
+name = "custom"
+"#,
+        );
+        let merged = merge_boards(builtin_boards(), extra);
+        assert_eq!(merged.len(), builtin_boards().len() + 1);
+
+        let overridden = merged
+            .iter()
+            .find(|b| b.vid == 0x0483 && b.pid == 0x374b)
+            .unwrap();
+        assert_eq!(overridden.name, "my-nucleo");
+        assert_eq!(overridden.architecture, None);
+        assert_eq!(
+            merged
+                .iter()
+                .filter(|b| b.vid == 0x0483 && b.pid == 0x374b)
+                .count(),
+            1
+        );
+        assert!(merged.iter().any(|b| b.name == "custom"));
+    }
+
+    #[test]
+    fn test_merge_boards_last_user_entry_wins() {
+        let extra = parse_boards_toml(
+            r#"
+[[board]]
+vid = 0xaaaa
+pid = 0xbbbb
+name = "first"
+
+[[board]]
+vid = 0xaaaa
+pid = 0xbbbb
+name = "second"
+"#,
+        );
+        let merged = merge_boards(&[], extra);
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0].name, "second");
+    }
+
+    #[test]
+    fn test_load_boards_file_missing_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(load_boards_file(&dir.path().join("boards.toml")).is_empty());
+    }
+
+    #[test]
+    fn test_load_boards_file_reads_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("boards.toml");
+        std::fs::write(
+            &path,
+            "[[board]]\nvid = 0x1111\npid = 0x2222\nname = \"from-file\"\n",
+        )
+        .unwrap();
+        let boards = load_boards_file(&path);
+        assert_eq!(boards.len(), 1);
+        assert_eq!(boards[0].name, "from-file");
+    }
 }
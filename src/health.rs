@@ -1,17 +1,21 @@
 //! HTTP health server for ZeptoClaw.
 //!
-//! Exposes `/health` (liveness) and `/ready` (readiness) endpoints.
+//! Exposes `/health` (liveness), `/ready` (readiness) and `/metrics`
+//! (usage counters in Prometheus text format; `?format=json` adds provider
+//! response times) endpoints.
 //! Components register named checks via [`HealthRegistry`].
 //!
 //! Also provides:
 //! - [`UsageMetrics`] for lock-free per-request counters
 //! - [`start_periodic_usage_flush`] for periodic metric emission
+//! - [`start_periodic_disk_check`] for the `disk` free-space check
 //! - [`health_port`] helper for legacy env-only port resolution
 //!
 //! Uses raw TCP + manual HTTP to avoid adding a web framework dependency,
 //! preserving the ultra-light binary footprint (4MB design goal).
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, RwLock};
 use std::time::{Duration, Instant};
@@ -20,12 +24,22 @@ use tokio::io::AsyncWriteExt;
 use tokio::net::TcpListener;
 use tracing::{info, warn};
 
+use crate::agent::RunLimiter;
+use crate::providers::latency::{LatencyStats, LatencyTracker};
+
 // ============================================================================
 // Default health check port
 // ============================================================================
 
 const DEFAULT_HEALTH_PORT: u16 = 9090;
 const USAGE_FLUSH_INTERVAL_SECS: u64 = 60;
+const DISK_CHECK_INTERVAL_SECS: u64 = 60;
+
+/// Content type of the Prometheus text exposition format served on `/metrics`.
+const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Name of the free-space check registered by [`start_periodic_disk_check`].
+pub const DISK_CHECK_NAME: &str = "disk";
 
 // ============================================================================
 // Platform RSS helper
@@ -127,6 +141,94 @@ pub fn get_rss_bytes() -> Option<u64> {
     }
 }
 
+// ============================================================================
+// Platform disk-space helper
+// ============================================================================
+
+/// Free and total bytes of a filesystem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiskSpace {
+    /// Bytes available to unprivileged processes.
+    pub free_bytes: u64,
+    /// Total size of the filesystem in bytes.
+    pub total_bytes: u64,
+}
+
+/// Return free/total space of the filesystem containing `path`, or `None`
+/// on unsupported platforms or when `path` cannot be queried.
+///
+/// - **macOS / 64-bit Linux**: `statvfs()` FFI; free space is `f_bavail`
+///   (blocks available to non-root) times the fragment size.
+/// - **Other**: Returns `None`.
+///
+/// No new crate dependencies are added; all FFI is declared inline.
+pub fn get_disk_space(path: &Path) -> Option<DiskSpace> {
+    #[cfg(any(
+        target_os = "macos",
+        all(target_os = "linux", target_pointer_width = "64")
+    ))]
+    {
+        use std::ffi::CString;
+        use std::os::raw::{c_char, c_int, c_ulong};
+        use std::os::unix::ffi::OsStrExt;
+
+        // sys/statvfs.h: fsblkcnt_t/fsfilcnt_t are 32-bit on macOS.
+        #[cfg(target_os = "macos")]
+        type BlkCnt = u32;
+        #[cfg(target_os = "linux")]
+        type BlkCnt = u64;
+
+        #[repr(C)]
+        #[allow(dead_code)]
+        struct StatVfs {
+            f_bsize: c_ulong,
+            f_frsize: c_ulong,
+            f_blocks: BlkCnt,
+            f_bfree: BlkCnt,
+            f_bavail: BlkCnt,
+            f_files: BlkCnt,
+            f_ffree: BlkCnt,
+            f_favail: BlkCnt,
+            f_fsid: c_ulong,
+            f_flag: c_ulong,
+            f_namemax: c_ulong,
+            // glibc/musl trailing spare fields (absent on macOS).
+            spare: [c_int; 6],
+        }
+
+        extern "C" {
+            fn statvfs(path: *const c_char, buf: *mut StatVfs) -> c_int;
+        }
+
+        let c_path = CString::new(path.as_os_str().as_bytes()).ok()?;
+        // StatVfs is plain integers, so all-zero is a valid value.
+        let mut buf: StatVfs = unsafe { std::mem::zeroed() };
+        let ret = unsafe { statvfs(c_path.as_ptr(), &mut buf) };
+        if ret != 0 {
+            return None;
+        }
+
+        let block_size = if buf.f_frsize > 0 {
+            u64::from(buf.f_frsize)
+        } else {
+            u64::from(buf.f_bsize)
+        };
+        Some(DiskSpace {
+            free_bytes: u64::from(buf.f_bavail) * block_size,
+            total_bytes: u64::from(buf.f_blocks) * block_size,
+        })
+    }
+
+    #[cfg(not(any(
+        target_os = "macos",
+        all(target_os = "linux", target_pointer_width = "64")
+    )))]
+    {
+        let _ = path;
+        None
+    }
+}
+
 // ============================================================================
 // HealthStatus
 // ============================================================================
@@ -169,6 +271,8 @@ pub struct HealthCheck {
     pub restart_count: u64,
     /// Last error message, if any.
     pub last_error: Option<String>,
+    /// When the component last answered a liveness ping, if it is pinged.
+    pub last_heartbeat: Option<Instant>,
 }
 
 impl Default for HealthCheck {
@@ -179,6 +283,7 @@ impl Default for HealthCheck {
             message: None,
             restart_count: 0,
             last_error: None,
+            last_heartbeat: None,
         }
     }
 }
@@ -204,6 +309,7 @@ pub struct HealthRegistry {
     checks: Arc<RwLock<HashMap<String, HealthCheck>>>,
     start_time: Instant,
     metrics: Arc<RwLock<Option<Arc<UsageMetrics>>>>,
+    run_limiter: Arc<RwLock<Option<Arc<RunLimiter>>>>,
 }
 
 impl HealthRegistry {
@@ -213,6 +319,7 @@ impl HealthRegistry {
             checks: Arc::new(RwLock::new(HashMap::new())),
             start_time: Instant::now(),
             metrics: Arc::new(RwLock::new(None)),
+            run_limiter: Arc::new(RwLock::new(None)),
         }
     }
 
@@ -221,6 +328,11 @@ impl HealthRegistry {
         *self.metrics.write().unwrap() = Some(metrics);
     }
 
+    /// Attach the agent's [`RunLimiter`] to report active runs in health responses.
+    pub fn set_run_limiter(&self, limiter: Arc<RunLimiter>) {
+        *self.run_limiter.write().unwrap() = Some(limiter);
+    }
+
     /// Register a new named check. Replaces any existing check with the same name.
     pub fn register(&self, check: HealthCheck) {
         self.checks
@@ -269,6 +381,16 @@ impl HealthRegistry {
         }
     }
 
+    /// Record a successful liveness ping for a named component.
+    ///
+    /// No-op if no check with that name is registered.
+    pub fn heartbeat(&self, name: &str) {
+        let mut checks = self.checks.write().unwrap();
+        if let Some(check) = checks.get_mut(name) {
+            check.last_heartbeat = Some(Instant::now());
+        }
+    }
+
     /// Return a snapshot of all registered checks.
     pub fn all_checks(&self) -> Vec<HealthCheck> {
         self.checks.read().unwrap().values().cloned().collect()
@@ -298,6 +420,12 @@ impl HealthRegistry {
                 if let Some(ref err) = c.last_error {
                     fields.push_str(&format!(",\"last_error\":\"{}\"", err.replace('"', "\\\"")));
                 }
+                if let Some(at) = c.last_heartbeat {
+                    fields.push_str(&format!(
+                        ",\"heartbeat_age_secs\":{}",
+                        at.elapsed().as_secs()
+                    ));
+                }
                 format!("\"{}\":{{{}}}", c.name, fields)
             })
             .collect();
@@ -336,11 +464,131 @@ impl HealthRegistry {
                 ",\"usage\":{{\"requests\":{},\"tool_calls\":{},\"input_tokens\":{},\"output_tokens\":{},\"errors\":{}}}",
                 requests, tool_calls, input_tokens, output_tokens, errors
             ));
+            json.push_str(&format!(",\"latency_ms\":{}", m.latency.render_json()));
+            json.push_str(&format!(",\"by_provider\":{}", m.render_by_provider_json()));
+        }
+
+        // agent_runs section — only when a run limiter is attached
+        if let Some(ref limiter) = *self.run_limiter.read().unwrap() {
+            json.push_str(&format!(
+                ",\"agent_runs\":{{\"active\":{},\"max\":{}}}",
+                limiter.active(),
+                limiter.max()
+            ));
         }
 
         json.push_str(&format!(",\"checks\":{}}}", checks_json));
         json
     }
+
+    /// Render the `/metrics` response: usage counters (when metrics are
+    /// attached) and rolling response times per provider.
+    pub fn render_metrics_json(&self) -> String {
+        let mut parts = Vec::with_capacity(2);
+        if let Some(ref m) = *self.metrics.read().unwrap() {
+            parts.push(format!(
+                "\"usage\":{{\"requests\":{},\"tool_calls\":{},\"input_tokens\":{},\"output_tokens\":{},\"errors\":{}}}",
+                m.requests.load(Ordering::Relaxed),
+                m.tool_calls.load(Ordering::Relaxed),
+                m.input_tokens.load(Ordering::Relaxed),
+                m.output_tokens.load(Ordering::Relaxed),
+                m.errors.load(Ordering::Relaxed),
+            ));
+        }
+        parts.push(format!(
+            "\"provider_latency\":{}",
+            render_provider_latency_json(&LatencyTracker::global().snapshot())
+        ));
+        format!("{{{}}}", parts.join(","))
+    }
+
+    /// Render the usage counters in Prometheus text exposition format.
+    ///
+    /// Returns an empty string when no [`UsageMetrics`] is attached.
+    pub fn render_metrics_prometheus(&self) -> String {
+        let metrics = self.metrics.read().unwrap();
+        let Some(ref m) = *metrics else {
+            return String::new();
+        };
+        let mut out = String::new();
+        let counters = [
+            (
+                "zeptoclaw_requests_total",
+                "Total requests processed.",
+                m.requests.load(Ordering::Relaxed),
+            ),
+            (
+                "zeptoclaw_tool_calls_total",
+                "Total tool calls executed.",
+                m.tool_calls.load(Ordering::Relaxed),
+            ),
+            (
+                "zeptoclaw_input_tokens_total",
+                "Total input tokens consumed.",
+                m.input_tokens.load(Ordering::Relaxed),
+            ),
+            (
+                "zeptoclaw_output_tokens_total",
+                "Total output tokens produced.",
+                m.output_tokens.load(Ordering::Relaxed),
+            ),
+            (
+                "zeptoclaw_errors_total",
+                "Total errors encountered.",
+                m.errors.load(Ordering::Relaxed),
+            ),
+        ];
+        for (name, help, value) in counters {
+            push_prometheus_metric(&mut out, name, help, "counter", value);
+        }
+        push_prometheus_histogram(&mut out, &m.latency);
+        push_prometheus_metric(
+            &mut out,
+            "zeptoclaw_uptime_seconds",
+            "Seconds since the process started.",
+            "gauge",
+            self.uptime().as_secs(),
+        );
+        if let Some(rss) = get_rss_bytes() {
+            push_prometheus_metric(
+                &mut out,
+                "zeptoclaw_rss_bytes",
+                "Resident set size of the process in bytes.",
+                "gauge",
+                rss,
+            );
+        }
+        out
+    }
+}
+
+/// Append one metric with its `# HELP` and `# TYPE` lines.
+fn push_prometheus_metric(out: &mut String, name: &str, help: &str, kind: &str, value: u64) {
+    out.push_str(&format!(
+        "# HELP {name} {help}\n# TYPE {name} {kind}\n{name} {value}\n"
+    ));
+}
+
+/// Append the provider latency histogram, in seconds as Prometheus expects.
+fn push_prometheus_histogram(out: &mut String, histogram: &LatencyHistogram) {
+    let name = "zeptoclaw_chat_latency_seconds";
+    out.push_str(&format!(
+        "# HELP {name} Duration of provider chat calls.\n# TYPE {name} histogram\n"
+    ));
+    let cumulative = histogram.cumulative();
+    for (bound, count) in LATENCY_BUCKETS_MS.iter().zip(cumulative) {
+        let le = *bound as f64 / 1000.0;
+        out.push_str(&format!("{name}_bucket{{le=\"{le}\"}} {count}\n"));
+    }
+    out.push_str(&format!(
+        "{name}_bucket{{le=\"+Inf\"}} {}\n",
+        cumulative[LATENCY_BUCKETS_MS.len()]
+    ));
+    out.push_str(&format!(
+        "{name}_sum {}\n{name}_count {}\n",
+        histogram.sum_ms() as f64 / 1000.0,
+        histogram.count()
+    ));
 }
 
 impl Default for HealthRegistry {
@@ -349,11 +597,105 @@ impl Default for HealthRegistry {
     }
 }
 
+/// Render per-provider latency summaries as a JSON object keyed by provider.
+pub(crate) fn render_provider_latency_json(stats: &BTreeMap<String, LatencyStats>) -> String {
+    let parts: Vec<String> = stats
+        .iter()
+        .map(|(name, s)| {
+            format!(
+                "\"{}\":{{\"samples\":{},\"p50_ms\":{},\"p95_ms\":{}}}",
+                name.replace('"', "\\\""),
+                s.samples,
+                s.p50_ms,
+                s.p95_ms
+            )
+        })
+        .collect();
+    format!("{{{}}}", parts.join(","))
+}
+
 // ============================================================================
 // UsageMetrics (retained from original for gateway wiring)
 // ============================================================================
 
+/// Upper bounds, in milliseconds, of the [`LatencyHistogram`] buckets. A final
+/// `+Inf` bucket catches everything slower.
+pub const LATENCY_BUCKETS_MS: [u64; 8] = [50, 100, 250, 500, 1_000, 2_000, 5_000, 10_000];
+
+/// Lock-free fixed-bucket histogram of provider call durations.
+///
+/// Recording is two relaxed `fetch_add`s plus the bucket increment; reads
+/// happen only when the histogram is rendered.
+#[derive(Debug, Default)]
+pub struct LatencyHistogram {
+    /// Per-bucket counts; index `LATENCY_BUCKETS_MS.len()` is `+Inf`.
+    buckets: [AtomicU64; LATENCY_BUCKETS_MS.len() + 1],
+    count: AtomicU64,
+    sum_ms: AtomicU64,
+}
+
+impl LatencyHistogram {
+    /// Record one observation.
+    pub fn record(&self, elapsed: Duration) {
+        let ms = u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX);
+        let index = LATENCY_BUCKETS_MS.partition_point(|&bound| bound < ms);
+        self.buckets[index].fetch_add(1, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.sum_ms.fetch_add(ms, Ordering::Relaxed);
+    }
+
+    /// Cumulative counts per bucket (`<= bound`), ending with the `+Inf`
+    /// bucket, which equals the total count.
+    pub fn cumulative(&self) -> [u64; LATENCY_BUCKETS_MS.len() + 1] {
+        let mut total = 0;
+        std::array::from_fn(|i| {
+            total += self.buckets[i].load(Ordering::Relaxed);
+            total
+        })
+    }
+
+    /// Number of observations.
+    pub fn count(&self) -> u64 {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    /// Sum of all observations in milliseconds.
+    pub fn sum_ms(&self) -> u64 {
+        self.sum_ms.load(Ordering::Relaxed)
+    }
+
+    /// Render as a JSON object of cumulative bucket counts, e.g.
+    /// `{"50":3,"100":5,...,"+Inf":9}`.
+    pub fn render_json(&self) -> String {
+        let cumulative = self.cumulative();
+        let parts: Vec<String> = LATENCY_BUCKETS_MS
+            .iter()
+            .map(|bound| bound.to_string())
+            .chain(std::iter::once("+Inf".to_string()))
+            .zip(cumulative)
+            .map(|(bound, count)| format!("\"{}\":{}", bound, count))
+            .collect();
+        format!("{{{}}}", parts.join(","))
+    }
+}
+
+/// Counters for one provider in [`UsageMetrics::by_provider`].
+#[derive(Debug, Default)]
+pub struct ProviderUsage {
+    /// Requests routed to this provider.
+    pub requests: AtomicU64,
+    /// Input tokens consumed.
+    pub input_tokens: AtomicU64,
+    /// Output tokens produced.
+    pub output_tokens: AtomicU64,
+    /// Failed requests.
+    pub errors: AtomicU64,
+}
+
 /// Lock-free per-request counters for gateway usage tracking.
+///
+/// The flat counters are totals across all providers. The `*_for` recorders
+/// also attribute the event to a provider in [`by_provider`](Self::by_provider).
 #[derive(Debug)]
 pub struct UsageMetrics {
     /// Total requests processed.
@@ -368,6 +710,10 @@ pub struct UsageMetrics {
     pub errors: AtomicU64,
     /// Whether the gateway is ready to accept requests.
     pub ready: AtomicBool,
+    /// Duration of provider `chat()` calls.
+    pub latency: LatencyHistogram,
+    /// Counters keyed by provider name. Only the map insert takes the lock.
+    by_provider: RwLock<BTreeMap<String, Arc<ProviderUsage>>>,
 }
 
 impl UsageMetrics {
@@ -380,12 +726,43 @@ impl UsageMetrics {
             output_tokens: AtomicU64::new(0),
             errors: AtomicU64::new(0),
             ready: AtomicBool::new(false),
+            latency: LatencyHistogram::default(),
+            by_provider: RwLock::new(BTreeMap::new()),
+        }
+    }
+
+    /// Counters for `provider`, created on first use.
+    fn provider(&self, provider: &str) -> Arc<ProviderUsage> {
+        if let Some(usage) = self.by_provider.read().unwrap().get(provider) {
+            return Arc::clone(usage);
         }
+        let mut by_provider = self.by_provider.write().unwrap();
+        Arc::clone(by_provider.entry(provider.to_string()).or_default())
+    }
+
+    /// Snapshot of the per-provider counters, sorted by provider name.
+    pub fn by_provider(&self) -> Vec<(String, Arc<ProviderUsage>)> {
+        self.by_provider
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(name, usage)| (name.clone(), Arc::clone(usage)))
+            .collect()
     }
 
     /// Increment the request counter.
     pub fn record_request(&self) {
+        self.record_request_for(None);
+    }
+
+    /// Increment the request counter, attributing it to `provider` if known.
+    pub fn record_request_for(&self, provider: Option<&str>) {
         self.requests.fetch_add(1, Ordering::Relaxed);
+        if let Some(provider) = provider {
+            self.provider(provider)
+                .requests
+                .fetch_add(1, Ordering::Relaxed);
+        }
     }
 
     /// Increment the tool call counter.
@@ -395,13 +772,57 @@ impl UsageMetrics {
 
     /// Record token usage from an LLM response.
     pub fn record_tokens(&self, input: u64, output: u64) {
+        self.record_tokens_for(None, input, output);
+    }
+
+    /// Record token usage, attributing it to `provider` if known.
+    pub fn record_tokens_for(&self, provider: Option<&str>, input: u64, output: u64) {
         self.input_tokens.fetch_add(input, Ordering::Relaxed);
         self.output_tokens.fetch_add(output, Ordering::Relaxed);
+        if let Some(provider) = provider {
+            let usage = self.provider(provider);
+            usage.input_tokens.fetch_add(input, Ordering::Relaxed);
+            usage.output_tokens.fetch_add(output, Ordering::Relaxed);
+        }
     }
 
     /// Increment the error counter.
     pub fn record_error(&self) {
+        self.record_error_for(None);
+    }
+
+    /// Increment the error counter, attributing it to `provider` if known.
+    pub fn record_error_for(&self, provider: Option<&str>) {
         self.errors.fetch_add(1, Ordering::Relaxed);
+        if let Some(provider) = provider {
+            self.provider(provider)
+                .errors
+                .fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Render the per-provider counters as a JSON object keyed by provider.
+    pub fn render_by_provider_json(&self) -> String {
+        let parts: Vec<String> = self
+            .by_provider()
+            .iter()
+            .map(|(name, u)| {
+                format!(
+                    "\"{}\":{{\"requests\":{},\"input_tokens\":{},\"output_tokens\":{},\"errors\":{}}}",
+                    name.replace('"', "\\\""),
+                    u.requests.load(Ordering::Relaxed),
+                    u.input_tokens.load(Ordering::Relaxed),
+                    u.output_tokens.load(Ordering::Relaxed),
+                    u.errors.load(Ordering::Relaxed),
+                )
+            })
+            .collect();
+        format!("{{{}}}", parts.join(","))
+    }
+
+    /// Record the duration of one provider call.
+    pub fn record_latency(&self, elapsed: Duration) {
+        self.latency.record(elapsed);
     }
 
     /// Set the ready flag.
@@ -439,6 +860,8 @@ impl Default for UsageMetrics {
 /// Serves:
 /// - `GET /health` → 200 with JSON body `{"status":"ok","uptime_secs":N,"checks":{...}}`
 /// - `GET /ready`  → 200 if all checks are not Down, 503 otherwise
+/// - `GET /metrics` → 200 with usage counters in Prometheus text format
+/// - `GET /metrics?format=json` → 200 with usage counters and per-provider p50/p95 latency
 /// - `GET /healthz` → 200 OK (liveness alias, retained for backward compat)
 /// - `GET /readyz`  → delegates to the same readiness logic (backward compat)
 /// - Anything else → 404
@@ -475,13 +898,21 @@ pub async fn start_health_server(
                         let mut parts = request_line.split_whitespace();
                         let method = parts.next().unwrap_or_default();
                         let raw_path = parts.next().unwrap_or_default();
-                        let path = raw_path.split('?').next().unwrap_or(raw_path);
+                        let (path, query) = raw_path.split_once('?').unwrap_or((raw_path, ""));
+                        let mut content_type = "application/json";
 
                         let (status_line, body) = match (method, path) {
                             ("GET", "/health") | ("GET", "/healthz") => {
                                 let body = registry.render_health_json();
                                 ("200 OK", body)
                             }
+                            ("GET", "/metrics") if query.split('&').any(|p| p == "format=json") => {
+                                ("200 OK", registry.render_metrics_json())
+                            }
+                            ("GET", "/metrics") => {
+                                content_type = PROMETHEUS_CONTENT_TYPE;
+                                ("200 OK", registry.render_metrics_prometheus())
+                            }
                             ("GET", "/ready") | ("GET", "/readyz") => {
                                 if registry.is_ready() {
                                     ("200 OK", "{\"status\":\"ready\"}".to_string())
@@ -496,8 +927,9 @@ pub async fn start_health_server(
                         };
 
                         let response = format!(
-                            "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                            "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
                             status_line,
+                            content_type,
                             body.len(),
                             body
                         );
@@ -647,6 +1079,92 @@ pub fn start_periodic_usage_flush(
     })
 }
 
+// ============================================================================
+// Disk space check
+// ============================================================================
+
+/// Map free space to a health status and message.
+///
+/// `Down` below `critical_free_bytes`, `Degraded` below `warn_free_bytes`,
+/// `Ok` otherwise. The message carries the free and total byte counts.
+pub fn disk_status(
+    space: DiskSpace,
+    warn_free_bytes: u64,
+    critical_free_bytes: u64,
+) -> (HealthStatus, String) {
+    let status = if space.free_bytes < critical_free_bytes {
+        HealthStatus::Down
+    } else if space.free_bytes < warn_free_bytes {
+        HealthStatus::Degraded
+    } else {
+        HealthStatus::Ok
+    };
+    let percent = if space.total_bytes > 0 {
+        space.free_bytes as f64 * 100.0 / space.total_bytes as f64
+    } else {
+        0.0
+    };
+    let message = format!(
+        "{} of {} bytes free ({:.1}%)",
+        space.free_bytes, space.total_bytes, percent
+    );
+    (status, message)
+}
+
+/// Register the `disk` check and refresh it every 60 seconds until
+/// `shutdown_rx` flips to `true`.
+///
+/// Returns `None` without registering anything when free space under `path`
+/// cannot be read (unsupported platform or missing path).
+pub fn start_periodic_disk_check(
+    registry: HealthRegistry,
+    path: PathBuf,
+    warn_free_bytes: u64,
+    critical_free_bytes: u64,
+    mut shutdown_rx: tokio::sync::watch::Receiver<bool>,
+) -> Option<tokio::task::JoinHandle<()>> {
+    let space = get_disk_space(&path)?;
+    let (status, message) = disk_status(space, warn_free_bytes, critical_free_bytes);
+    registry.register(HealthCheck {
+        name: DISK_CHECK_NAME.into(),
+        status,
+        message: Some(message),
+        ..Default::default()
+    });
+
+    Some(tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(DISK_CHECK_INTERVAL_SECS));
+        interval.tick().await; // first reading taken above
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {
+                    match get_disk_space(&path) {
+                        Some(space) => {
+                            let (status, message) =
+                                disk_status(space, warn_free_bytes, critical_free_bytes);
+                            if status != HealthStatus::Ok {
+                                warn!(path = %path.display(), %message, "Low disk space");
+                            }
+                            registry.update(DISK_CHECK_NAME, status, Some(message));
+                        }
+                        None => registry.update(
+                            DISK_CHECK_NAME,
+                            HealthStatus::Degraded,
+                            Some(format!("cannot read free space for {}", path.display())),
+                        ),
+                    }
+                }
+                _ = shutdown_rx.changed() => {
+                    if *shutdown_rx.borrow() {
+                        break;
+                    }
+                }
+            }
+        }
+    }))
+}
+
 // ============================================================================
 // Legacy port helper
 // ============================================================================
@@ -1231,6 +1749,43 @@ mod tests {
         assert!(json.contains("\"errors\":1"));
     }
 
+    #[test]
+    fn test_registry_reports_usage_by_provider() {
+        let reg = HealthRegistry::new();
+        let metrics = Arc::new(UsageMetrics::new());
+        metrics.record_request_for(Some("gemini"));
+        metrics.record_tokens_for(Some("gemini"), 100, 20);
+        metrics.record_request_for(Some("anthropic"));
+        metrics.record_request_for(Some("anthropic"));
+        metrics.record_tokens_for(Some("anthropic"), 300, 40);
+        metrics.record_error_for(Some("anthropic"));
+        // Unlabelled events only count towards the totals.
+        metrics.record_request();
+        metrics.record_tokens(5, 5);
+        reg.set_metrics(Arc::clone(&metrics));
+
+        let json = reg.render_health_json();
+        assert!(json.contains(
+            "\"usage\":{\"requests\":4,\"tool_calls\":0,\"input_tokens\":405,\"output_tokens\":65,\"errors\":1"
+        ));
+        assert!(json.contains(
+            "\"by_provider\":{\"anthropic\":{\"requests\":2,\"input_tokens\":300,\"output_tokens\":40,\"errors\":1},\
+             \"gemini\":{\"requests\":1,\"input_tokens\":100,\"output_tokens\":20,\"errors\":0}}"
+        ), "{}", json);
+    }
+
+    #[tokio::test]
+    async fn test_registry_reports_active_runs() {
+        let reg = HealthRegistry::new();
+        assert!(!reg.render_health_json().contains("\"agent_runs\""));
+
+        let limiter = Arc::new(RunLimiter::new(4, crate::config::RunOverflowMode::Reject));
+        reg.set_run_limiter(Arc::clone(&limiter));
+        let _permit = limiter.admit().await.unwrap();
+        let json = reg.render_health_json();
+        assert!(json.contains("\"agent_runs\":{\"active\":1,\"max\":4}"));
+    }
+
     #[test]
     fn test_registry_without_metrics_omits_usage() {
         let reg = HealthRegistry::new();
@@ -1238,6 +1793,164 @@ mod tests {
         assert!(!json.contains("\"usage\""));
     }
 
+    #[test]
+    fn test_render_provider_latency_json() {
+        let mut stats = BTreeMap::new();
+        assert_eq!(render_provider_latency_json(&stats), "{}");
+        stats.insert(
+            "anthropic".to_string(),
+            LatencyStats {
+                samples: 12,
+                p50_ms: 800,
+                p95_ms: 2_400,
+            },
+        );
+        assert_eq!(
+            render_provider_latency_json(&stats),
+            r#"{"anthropic":{"samples":12,"p50_ms":800,"p95_ms":2400}}"#
+        );
+
+        let reg = HealthRegistry::new();
+        reg.set_metrics(Arc::new(UsageMetrics::new()));
+        let json = reg.render_metrics_json();
+        assert!(json.starts_with("{\"usage\":{\"requests\":0"));
+        assert!(json.contains("\"provider_latency\":{"));
+    }
+
+    #[test]
+    fn test_render_metrics_prometheus() {
+        let reg = HealthRegistry::new();
+        assert_eq!(reg.render_metrics_prometheus(), "");
+
+        let metrics = Arc::new(UsageMetrics::new());
+        metrics.record_request();
+        metrics.record_request();
+        metrics.record_tool_calls(3);
+        metrics.record_tokens(1000, 250);
+        reg.set_metrics(metrics);
+
+        let text = reg.render_metrics_prometheus();
+        assert!(text.contains(
+            "# HELP zeptoclaw_requests_total Total requests processed.\n\
+             # TYPE zeptoclaw_requests_total counter\n\
+             zeptoclaw_requests_total 2\n"
+        ));
+        assert!(text.contains("zeptoclaw_tool_calls_total 3\n"));
+        assert!(text.contains("zeptoclaw_input_tokens_total 1000\n"));
+        assert!(text.contains("zeptoclaw_errors_total 0\n"));
+        assert!(text.contains("# TYPE zeptoclaw_uptime_seconds gauge\n"));
+        if get_rss_bytes().is_some() {
+            assert!(text.contains("# TYPE zeptoclaw_rss_bytes gauge\n"));
+        }
+        // Every metric family has exactly one HELP and one TYPE line.
+        let families: std::collections::BTreeSet<&str> = text
+            .lines()
+            .filter(|l| !l.starts_with('#'))
+            .map(|l| l.split(['{', ' ']).next().unwrap())
+            .map(|name| {
+                ["_bucket", "_sum", "_count"]
+                    .iter()
+                    .find_map(|suffix| name.strip_suffix(suffix))
+                    .unwrap_or(name)
+            })
+            .collect();
+        assert_eq!(text.matches("# HELP ").count(), families.len());
+        assert_eq!(text.matches("# TYPE ").count(), families.len());
+    }
+
+    #[test]
+    fn test_latency_histogram_buckets() {
+        let metrics = UsageMetrics::new();
+        for ms in [10, 50, 51, 700, 3_000, 60_000] {
+            metrics.record_latency(Duration::from_millis(ms));
+        }
+        assert_eq!(metrics.latency.count(), 6);
+        assert_eq!(metrics.latency.sum_ms(), 63_811);
+        assert_eq!(metrics.latency.cumulative(), [2, 3, 3, 3, 4, 4, 5, 5, 6]);
+        assert_eq!(
+            metrics.latency.render_json(),
+            r#"{"50":2,"100":3,"250":3,"500":3,"1000":4,"2000":4,"5000":5,"10000":5,"+Inf":6}"#
+        );
+
+        let reg = HealthRegistry::new();
+        reg.set_metrics(Arc::new(metrics));
+        assert!(reg
+            .render_health_json()
+            .contains(r#""latency_ms":{"50":2,"#));
+        let text = reg.render_metrics_prometheus();
+        assert!(text.contains("# TYPE zeptoclaw_chat_latency_seconds histogram\n"));
+        assert!(text.contains("zeptoclaw_chat_latency_seconds_bucket{le=\"0.05\"} 2\n"));
+        assert!(text.contains("zeptoclaw_chat_latency_seconds_bucket{le=\"10\"} 5\n"));
+        assert!(text.contains("zeptoclaw_chat_latency_seconds_bucket{le=\"+Inf\"} 6\n"));
+        assert!(text.contains("zeptoclaw_chat_latency_seconds_sum 63.811\n"));
+        assert!(text.contains("zeptoclaw_chat_latency_seconds_count 6\n"));
+    }
+
+    #[test]
+    fn test_latency_histogram_concurrent_records() {
+        let metrics = Arc::new(UsageMetrics::new());
+        let threads: Vec<_> = (0..8)
+            .map(|_| {
+                let metrics = Arc::clone(&metrics);
+                std::thread::spawn(move || {
+                    for _ in 0..1_000 {
+                        metrics.record_latency(Duration::from_millis(120));
+                    }
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+        assert_eq!(metrics.latency.count(), 8_000);
+        assert_eq!(metrics.latency.cumulative()[2], 8_000);
+        assert_eq!(metrics.latency.cumulative()[1], 0);
+    }
+
+    #[tokio::test]
+    async fn test_metrics_endpoint_serves_prometheus_and_json() {
+        let registry = HealthRegistry::new();
+        registry.set_metrics(Arc::new(UsageMetrics::new()));
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        drop(listener);
+
+        let handle = start_health_server("127.0.0.1", port, registry)
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        async fn get(port: u16, path: &str) -> String {
+            let mut stream = tokio::net::TcpStream::connect(format!("127.0.0.1:{}", port))
+                .await
+                .unwrap();
+            let request = format!("GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path);
+            tokio::io::AsyncWriteExt::write_all(&mut stream, request.as_bytes())
+                .await
+                .unwrap();
+            let mut response = Vec::new();
+            tokio::io::AsyncReadExt::read_to_end(&mut stream, &mut response)
+                .await
+                .unwrap();
+            String::from_utf8_lossy(&response).into_owned()
+        }
+
+        let text = get(port, "/metrics").await;
+        assert!(
+            text.contains("Content-Type: text/plain; version=0.0.4"),
+            "{}",
+            text
+        );
+        assert!(text.contains("zeptoclaw_requests_total 0"), "{}", text);
+
+        let json = get(port, "/metrics?format=json").await;
+        assert!(json.contains("Content-Type: application/json"), "{}", json);
+        assert!(json.contains("\"provider_latency\":{"), "{}", json);
+
+        handle.abort();
+    }
+
     #[test]
     fn test_render_health_json_has_version() {
         let reg = HealthRegistry::new();
@@ -1269,6 +1982,7 @@ mod tests {
             message: None,
             restart_count: 3,
             last_error: Some("timeout".into()),
+            last_heartbeat: None,
         });
         let json = reg.render_checks_json();
         assert!(json.contains("\"restart_count\":3"));
@@ -1277,6 +1991,48 @@ mod tests {
 
     // --- get_rss_bytes tests ---
 
+    #[test]
+    fn test_disk_status_thresholds() {
+        const MIB: u64 = 1024 * 1024;
+        let space = |free_mb: u64| DiskSpace {
+            free_bytes: free_mb * MIB,
+            total_bytes: 10_000 * MIB,
+        };
+
+        let (status, message) = disk_status(space(5_000), 1024 * MIB, 100 * MIB);
+        assert_eq!(status, HealthStatus::Ok);
+        assert!(message.contains(&format!("{} of {} bytes free", 5_000 * MIB, 10_000 * MIB)));
+        assert!(message.contains("50.0%"));
+
+        let (status, _) = disk_status(space(500), 1024 * MIB, 100 * MIB);
+        assert_eq!(status, HealthStatus::Degraded);
+
+        let (status, _) = disk_status(space(50), 1024 * MIB, 100 * MIB);
+        assert_eq!(status, HealthStatus::Down);
+
+        let (status, message) = disk_status(
+            DiskSpace {
+                free_bytes: 0,
+                total_bytes: 0,
+            },
+            1,
+            0,
+        );
+        assert_eq!(status, HealthStatus::Degraded);
+        assert!(message.contains("0.0%"));
+    }
+
+    #[test]
+    #[cfg(any(
+        target_os = "macos",
+        all(target_os = "linux", target_pointer_width = "64")
+    ))]
+    fn test_get_disk_space_reads_temp_dir() {
+        let space = get_disk_space(&std::env::temp_dir()).unwrap();
+        assert!(space.total_bytes > 0);
+        assert!(space.free_bytes <= space.total_bytes);
+    }
+
     #[test]
     #[cfg(any(target_os = "macos", target_os = "linux"))]
     fn test_get_rss_bytes_returns_some() {
@@ -5,6 +5,8 @@
 //! - `before_tool` — before tool execution (can log or block)
 //! - `after_tool` — after tool execution (can log)
 //! - `on_error` — when a tool fails (can log)
+//! - `post_process` — transforms a tool's output before the model sees it
+//!   (see [`post_process`])
 //!
 //! # Configuration
 //!
@@ -47,11 +49,16 @@
 //! assert!(matches!(result, HookResult::Block(_)));
 //! ```
 
+pub mod post_process;
+
 use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
 
 use crate::bus::{MessageBus, OutboundMessage};
+use crate::providers::LLMProvider;
+
+pub use post_process::{PostProcessRule, ToolTransform};
 
 // ---------------------------------------------------------------------------
 // Hook action enum
@@ -148,6 +155,8 @@ pub struct HooksConfig {
     pub after_tool: Vec<HookRule>,
     /// Rules evaluated when a tool returns an error.
     pub on_error: Vec<HookRule>,
+    /// Output transforms applied to successful tool results.
+    pub post_process: Vec<PostProcessRule>,
 }
 
 // ---------------------------------------------------------------------------
@@ -176,12 +185,17 @@ pub enum HookResult {
 pub struct HookEngine {
     config: HooksConfig,
     bus: Option<Arc<MessageBus>>,
+    summarizer: Option<post_process::Summarizer>,
 }
 
 impl HookEngine {
     /// Create a new HookEngine from configuration.
     pub fn new(config: HooksConfig) -> Self {
-        Self { config, bus: None }
+        Self {
+            config,
+            bus: None,
+            summarizer: None,
+        }
     }
 
     /// Attach a message bus for `notify` actions.
@@ -190,6 +204,12 @@ impl HookEngine {
         self
     }
 
+    /// Attach the model used by `summarize` post-process transforms.
+    pub fn with_summarizer(mut self, provider: Arc<dyn LLMProvider>, model: &str) -> Self {
+        self.summarizer = Some(post_process::Summarizer::new(provider, model));
+        self
+    }
+
     fn resolve_notify_target(
         rule: &HookRule,
         current_channel: &str,
@@ -451,6 +471,22 @@ impl HookEngine {
         }
     }
 
+    /// Run the `post_process` transforms matching `tool_name` over a
+    /// successful tool result. Returns the output unchanged when hooks are
+    /// disabled or no rule matches.
+    pub async fn post_process(&self, tool_name: &str, output: String) -> String {
+        if !self.config.enabled || self.config.post_process.is_empty() {
+            return output;
+        }
+        post_process::apply(
+            &self.config.post_process,
+            tool_name,
+            output,
+            self.summarizer.as_ref(),
+        )
+        .await
+    }
+
     /// Whether hooks are enabled.
     pub fn is_enabled(&self) -> bool {
         self.config.enabled
@@ -474,6 +510,7 @@ mod tests {
         assert!(config.before_tool.is_empty());
         assert!(config.after_tool.is_empty());
         assert!(config.on_error.is_empty());
+        assert!(config.post_process.is_empty());
     }
 
     #[test]
@@ -507,6 +544,7 @@ mod tests {
                 ..Default::default()
             }],
             on_error: vec![],
+            post_process: vec![],
         };
         let json = serde_json::to_string(&config).unwrap();
         let deserialized: HooksConfig = serde_json::from_str(&json).unwrap();
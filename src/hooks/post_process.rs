@@ -0,0 +1,398 @@
+//! Tool result post-processing.
+//!
+//! `hooks.post_process` rules transform a tool's `for_llm` output before the
+//! model sees it. Each rule names the tools it applies to and a chain of
+//! transforms that run in order; every matching rule contributes its chain.
+//!
+//! ```json
+//! {
+//!     "hooks": {
+//!         "enabled": true,
+//!         "post_process": [
+//!             { "tools": ["web_fetch"], "transforms": [
+//!                 { "type": "summarize", "min_chars": 4000 },
+//!                 { "type": "truncate", "max_chars": 2000 }
+//!             ] },
+//!             { "tools": ["http_request"], "transforms": [
+//!                 { "type": "json_extract", "path": ".data.items[].name" }
+//!             ] }
+//!         ]
+//!     }
+//! }
+//! ```
+//!
+//! A transform that cannot apply (non-JSON output, missing path, no
+//! summarizer) is skipped and the output passes through unchanged.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::providers::{ChatOptions, LLMProvider};
+use crate::session::Message;
+
+/// Outputs shorter than this are not summarized unless configured otherwise.
+const DEFAULT_SUMMARIZE_MIN_CHARS: usize = 4_000;
+/// Token cap for a summary unless configured otherwise.
+const DEFAULT_SUMMARY_MAX_TOKENS: u32 = 512;
+
+const SUMMARIZE_PROMPT: &str = "Summarize the following tool output for an AI agent. \
+Keep every fact, number, identifier, URL and error message the agent may need; drop \
+boilerplate, navigation text and repetition. Reply with the summary only.";
+
+/// Post-processing chain for a set of tools.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PostProcessRule {
+    /// Tool names to match. `["*"]` matches all tools.
+    pub tools: Vec<String>,
+    /// Transforms applied in order.
+    pub transforms: Vec<ToolTransform>,
+}
+
+impl PostProcessRule {
+    /// Check if this rule applies to the given tool name.
+    pub fn matches_tool(&self, tool_name: &str) -> bool {
+        self.tools.iter().any(|t| t == "*" || t == tool_name)
+    }
+}
+
+/// A single transform of a tool's output.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ToolTransform {
+    /// Keep at most `max_chars` characters, noting how much was cut.
+    Truncate { max_chars: usize },
+    /// Parse the output as JSON and keep only the value at a jq-style path
+    /// such as `.items[0].title` or `.items[].id`.
+    JsonExtract { path: String },
+    /// Summarize the output with the agent's model when it is longer than
+    /// `min_chars`.
+    Summarize {
+        #[serde(default)]
+        min_chars: Option<usize>,
+        #[serde(default)]
+        max_tokens: Option<u32>,
+        /// Extra instructions, e.g. what to focus on.
+        #[serde(default)]
+        instructions: Option<String>,
+    },
+}
+
+/// LLM used by the `summarize` transform.
+#[derive(Clone)]
+pub struct Summarizer {
+    provider: Arc<dyn LLMProvider>,
+    model: String,
+}
+
+impl Summarizer {
+    /// Summarize with `provider` using `model`.
+    pub fn new(provider: Arc<dyn LLMProvider>, model: &str) -> Self {
+        Self {
+            provider,
+            model: model.to_string(),
+        }
+    }
+}
+
+/// Run every matching rule's transforms over `output`, in order.
+pub async fn apply(
+    rules: &[PostProcessRule],
+    tool_name: &str,
+    output: String,
+    summarizer: Option<&Summarizer>,
+) -> String {
+    let mut output = output;
+    for transform in rules
+        .iter()
+        .filter(|rule| rule.matches_tool(tool_name))
+        .flat_map(|rule| &rule.transforms)
+    {
+        match apply_transform(transform, &output, summarizer).await {
+            Ok(Some(transformed)) => output = transformed,
+            Ok(None) => {}
+            Err(reason) => tracing::warn!(
+                hook = "post_process",
+                tool = tool_name,
+                transform = ?transform,
+                reason = %reason,
+                "Skipping tool output transform"
+            ),
+        }
+    }
+    output
+}
+
+/// Apply one transform. `Ok(None)` leaves the output as it is.
+async fn apply_transform(
+    transform: &ToolTransform,
+    output: &str,
+    summarizer: Option<&Summarizer>,
+) -> std::result::Result<Option<String>, String> {
+    match transform {
+        ToolTransform::Truncate { max_chars } => Ok(truncate(output, *max_chars)),
+        ToolTransform::JsonExtract { path } => {
+            let value: Value =
+                serde_json::from_str(output).map_err(|e| format!("output is not JSON: {}", e))?;
+            let extracted = json_extract(&value, path)?;
+            Ok(Some(match extracted {
+                Value::String(s) => s,
+                other => other.to_string(),
+            }))
+        }
+        ToolTransform::Summarize {
+            min_chars,
+            max_tokens,
+            instructions,
+        } => {
+            if output.chars().count() <= min_chars.unwrap_or(DEFAULT_SUMMARIZE_MIN_CHARS) {
+                return Ok(None);
+            }
+            let summarizer = summarizer.ok_or("no summarizer model available")?;
+            let system = match instructions {
+                Some(extra) => format!("{}\n\n{}", SUMMARIZE_PROMPT, extra),
+                None => SUMMARIZE_PROMPT.to_string(),
+            };
+            let options = ChatOptions::new()
+                .with_max_tokens(max_tokens.unwrap_or(DEFAULT_SUMMARY_MAX_TOKENS))
+                .with_temperature(0.0);
+            let response = summarizer
+                .provider
+                .chat(
+                    vec![Message::system(&system), Message::user(output)],
+                    Vec::new(),
+                    Some(summarizer.model.as_str()),
+                    options,
+                )
+                .await
+                .map_err(|e| format!("summarize failed: {}", e))?;
+            let summary = response.content.trim();
+            if summary.is_empty() {
+                return Err("summarizer returned an empty summary".to_string());
+            }
+            Ok(Some(format!(
+                "[Summarized from {} chars]\n{}",
+                output.chars().count(),
+                summary
+            )))
+        }
+    }
+}
+
+fn truncate(output: &str, max_chars: usize) -> Option<String> {
+    let total = output.chars().count();
+    if total <= max_chars {
+        return None;
+    }
+    let kept: String = output.chars().take(max_chars).collect();
+    Some(format!(
+        "{}\n...[truncated {} of {} chars]",
+        kept,
+        total - max_chars,
+        total
+    ))
+}
+
+/// Evaluate a jq-style path: `.field`, `["field"]`, `[n]` (negative counts
+/// from the end) and `[]` to map the rest of the path over an array.
+fn json_extract(value: &Value, path: &str) -> std::result::Result<Value, String> {
+    let segments = parse_path(path)?;
+    select(value, &segments).ok_or_else(|| format!("path '{}' not found", path))
+}
+
+#[derive(Debug, PartialEq)]
+enum PathSegment {
+    Field(String),
+    Index(i64),
+    Each,
+}
+
+fn parse_path(path: &str) -> std::result::Result<Vec<PathSegment>, String> {
+    let mut segments = Vec::new();
+    let mut rest = path.trim();
+    if rest == "." {
+        return Ok(segments);
+    }
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('[') {
+            let end = after
+                .find(']')
+                .ok_or_else(|| format!("unclosed '[' in path '{}'", path))?;
+            let inner = after[..end].trim();
+            segments.push(if inner.is_empty() {
+                PathSegment::Each
+            } else if let Some(quoted) = inner.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+                PathSegment::Field(quoted.to_string())
+            } else {
+                PathSegment::Index(
+                    inner
+                        .parse()
+                        .map_err(|_| format!("invalid index '{}' in path '{}'", inner, path))?,
+                )
+            });
+            rest = &after[end + 1..];
+        } else if let Some(after) = rest.strip_prefix('.') {
+            let end = after.find(['.', '[']).unwrap_or(after.len());
+            if end > 0 {
+                segments.push(PathSegment::Field(after[..end].to_string()));
+            }
+            rest = &after[end..];
+        } else {
+            return Err(format!("path '{}' must start with '.' or '['", path));
+        }
+    }
+    Ok(segments)
+}
+
+fn select(value: &Value, segments: &[PathSegment]) -> Option<Value> {
+    let Some((first, rest)) = segments.split_first() else {
+        return Some(value.clone());
+    };
+    match first {
+        PathSegment::Field(name) => select(value.get(name)?, rest),
+        PathSegment::Index(index) => {
+            let items = value.as_array()?;
+            let index = if *index < 0 {
+                items.len().checked_sub(index.unsigned_abs() as usize)?
+            } else {
+                *index as usize
+            };
+            select(items.get(index)?, rest)
+        }
+        PathSegment::Each => Some(Value::Array(
+            value
+                .as_array()?
+                .iter()
+                .filter_map(|item| select(item, rest))
+                .collect(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn rule(tools: &[&str], transforms: Vec<ToolTransform>) -> PostProcessRule {
+        PostProcessRule {
+            tools: tools.iter().map(|t| t.to_string()).collect(),
+            transforms,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_truncate_transform_limits_matching_tools_only() {
+        let rules = vec![rule(
+            &["web_fetch"],
+            vec![ToolTransform::Truncate { max_chars: 10 }],
+        )];
+        let page = "é".repeat(25);
+
+        let out = apply(&rules, "web_fetch", page.clone(), None).await;
+        assert_eq!(
+            out,
+            format!("{}\n...[truncated 15 of 25 chars]", "é".repeat(10))
+        );
+        assert_eq!(apply(&rules, "read_file", page.clone(), None).await, page);
+        assert_eq!(
+            apply(&rules, "web_fetch", "short".to_string(), None).await,
+            "short"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_json_extract_transform_selects_fields() {
+        let body = json!({
+            "data": {"items": [
+                {"name": "alpha", "id": 1},
+                {"name": "beta", "id": 2}
+            ]},
+            "meta": {"page": 1}
+        })
+        .to_string();
+        let extract = |path: &str| {
+            vec![rule(
+                &["*"],
+                vec![ToolTransform::JsonExtract {
+                    path: path.to_string(),
+                }],
+            )]
+        };
+
+        let out = apply(
+            &extract(".data.items[].name"),
+            "http_request",
+            body.clone(),
+            None,
+        )
+        .await;
+        assert_eq!(out, r#"["alpha","beta"]"#);
+        let out = apply(
+            &extract(".data.items[-1].name"),
+            "http_request",
+            body.clone(),
+            None,
+        )
+        .await;
+        assert_eq!(out, "beta");
+        let out = apply(&extract(".meta"), "http_request", body.clone(), None).await;
+        assert_eq!(out, r#"{"page":1}"#);
+
+        // Missing paths and non-JSON output pass through unchanged.
+        let out = apply(&extract(".missing"), "http_request", body.clone(), None).await;
+        assert_eq!(out, body);
+        let out = apply(&extract(".x"), "http_request", "not json".to_string(), None).await;
+        assert_eq!(out, "not json");
+    }
+
+    #[tokio::test]
+    async fn test_transforms_chain_in_order() {
+        let rules = vec![
+            rule(
+                &["http_request"],
+                vec![ToolTransform::JsonExtract {
+                    path: ".text".to_string(),
+                }],
+            ),
+            rule(&["*"], vec![ToolTransform::Truncate { max_chars: 5 }]),
+        ];
+        let body = json!({"text": "hello world"}).to_string();
+        let out = apply(&rules, "http_request", body, None).await;
+        assert_eq!(out, "hello\n...[truncated 6 of 11 chars]");
+
+        // Summarize without a summarizer is skipped.
+        let rules = vec![rule(
+            &["*"],
+            vec![ToolTransform::Summarize {
+                min_chars: Some(1),
+                max_tokens: None,
+                instructions: None,
+            }],
+        )];
+        assert_eq!(
+            apply(&rules, "web_fetch", "long text".to_string(), None).await,
+            "long text"
+        );
+    }
+
+    #[test]
+    fn test_rules_deserialize_from_config() {
+        let rule: PostProcessRule = serde_json::from_value(json!({
+            "tools": ["web_fetch"],
+            "transforms": [
+                {"type": "summarize", "min_chars": 2000},
+                {"type": "truncate", "max_chars": 500},
+                {"type": "json_extract", "path": ".a"}
+            ]
+        }))
+        .unwrap();
+        assert_eq!(rule.transforms.len(), 3);
+        assert_eq!(
+            rule.transforms[1],
+            ToolTransform::Truncate { max_chars: 500 }
+        );
+    }
+}
@@ -9,7 +9,7 @@ mod registrar;
 
 pub use gate::execute_tool;
 pub use provider::build_provider_chain;
-pub use registrar::ToolFilter;
+pub use registrar::{ToolFilter, BUILTIN_TOOL_NAMES};
 
 use std::sync::Arc;
 
@@ -70,23 +70,41 @@ impl ZeptoKernel {
         template: Option<&crate::config::templates::AgentTemplate>,
         hand: Option<&HandManifest>,
     ) -> anyhow::Result<Self> {
+        // 0. Create the workspace on first run so file tools have somewhere to work
+        if config.agents.defaults.bootstrap_workspace {
+            let workspace = config.workspace_path();
+            if let Err(e) = crate::config::bootstrap::bootstrap_workspace(&workspace) {
+                warn!(
+                    workspace = %workspace.display(),
+                    error = %e,
+                    "Failed to create workspace"
+                );
+            }
+        }
+
         // 1. Build tool filter from config/template/hand
         let filter = ToolFilter::from_config(&config, template, hand);
 
-        // 2. Build provider chain
-        let provider: Option<Arc<dyn LLMProvider>> =
-            if let Some((chain, names)) = provider::build_provider_chain(&config).await {
-                let chain_label = names.join(" -> ");
-                info!(
-                    provider_chain = %chain_label,
-                    "Assembled provider chain"
-                );
-                Some(chain)
-            } else {
-                // No runtime provider — plugin providers may be set later by
-                // `create_agent_with_template()`.
-                None
-            };
+        // 2. Build provider chain (quota notifications go to the configured chat)
+        let quota_notifier = config
+            .providers
+            .quota_notify
+            .clone()
+            .map(|target| crate::providers::quota::bus_notifier(target, bus.clone()));
+        let provider: Option<Arc<dyn LLMProvider>> = if let Some((chain, names)) =
+            provider::build_provider_chain_with_quota_notifier(&config, quota_notifier).await
+        {
+            let chain_label = names.join(" -> ");
+            info!(
+                provider_chain = %chain_label,
+                "Assembled provider chain"
+            );
+            Some(chain)
+        } else {
+            // No runtime provider — plugin providers may be set later by
+            // `create_agent_with_template()`.
+            None
+        };
 
         // 3. Safety layer
         let safety = if config.safety.enabled {
@@ -167,7 +185,7 @@ impl ZeptoKernel {
         cron_service.start(&config.routines.on_miss).await?;
 
         // 8. Register all tools
-        let mut tools = ToolRegistry::new();
+        let mut tools = ToolRegistry::new().with_arg_validation(config.tools.validate_args);
         let deps = registrar::ToolDeps {
             runtime,
             bus,
@@ -2,7 +2,7 @@
 //!
 //! Functions extracted (moved, not rewritten) from `cli/common.rs:139–384`.
 //! Handles provider resolution, fallback chain, retry wrapper, quota wrapper,
-//! and OAuth credential refresh.
+//! latency tracking, and OAuth credential refresh.
 
 use std::sync::Arc;
 
@@ -10,9 +10,11 @@ use tracing::warn;
 
 use crate::auth::{self, AuthMethod};
 use crate::config::Config;
+use crate::providers::replay::FailureStore;
 use crate::providers::{
-    provider_config_by_name, resolve_runtime_providers, ClaudeProvider, FallbackProvider,
-    GeminiProvider, LLMProvider, OpenAIProvider, RetryProvider, RuntimeProviderSelection,
+    provider_config_by_name, resolve_runtime_providers, ClaudeProvider, FailureCapture,
+    FallbackProvider, GeminiProvider, LLMProvider, LatencyProvider, OpenAIProvider, PromptLogger,
+    RetryProvider, RuntimeProviderSelection,
 };
 
 /// Build the complete provider chain from config.
@@ -22,10 +24,21 @@ use crate::providers::{
 /// Returns `None` if no providers are configured.
 pub async fn build_provider_chain(
     config: &Config,
+) -> Option<(Arc<dyn LLMProvider>, Vec<&'static str>)> {
+    build_provider_chain_with_quota_notifier(config, None).await
+}
+
+/// Like [`build_provider_chain`], additionally attaching `quota_notifier` to
+/// the shared quota store so threshold transitions are reported.
+pub async fn build_provider_chain_with_quota_notifier(
+    config: &Config,
+    quota_notifier: Option<crate::providers::quota::QuotaNotifier>,
 ) -> Option<(Arc<dyn LLMProvider>, Vec<&'static str>)> {
     refresh_oauth_credentials_if_needed(config).await;
-    let (chain, names) = build_runtime_provider_chain(config).await?;
+    let (chain, names) =
+        build_runtime_provider_chain_with_quota_notifier(config, quota_notifier).await?;
     let chain = apply_retry_wrapper(chain, config);
+    let chain = apply_prompt_log_wrapper(chain, config);
     Some((Arc::from(chain), names))
 }
 
@@ -71,8 +84,12 @@ pub async fn provider_from_runtime_selection(
                     Some(selection.api_key.as_str())
                 };
                 let prefer_oauth = selection.credential.is_bearer();
-                return GeminiProvider::from_config(api_key, model, prefer_oauth)
-                    .map(|p| Box::new(p) as Box<dyn LLMProvider>);
+                return GeminiProvider::from_config(api_key, model, prefer_oauth).map(|p| {
+                    Box::new(
+                        p.with_thinking_budget(selection.thinking_budget)
+                            .with_thoughts(selection.include_thoughts),
+                    ) as Box<dyn LLMProvider>
+                });
             }
             let api_base = match selection.api_base.as_deref() {
                 Some(base) => base,
@@ -144,20 +161,45 @@ struct RuntimeProviderCandidate {
 /// Moved from `cli/common.rs:251–315`.
 pub async fn build_runtime_provider_chain(
     config: &Config,
+) -> Option<(Box<dyn LLMProvider>, Vec<&'static str>)> {
+    build_runtime_provider_chain_with_quota_notifier(config, None).await
+}
+
+/// Like [`build_runtime_provider_chain`], with an optional quota notifier.
+pub async fn build_runtime_provider_chain_with_quota_notifier(
+    config: &Config,
+    quota_notifier: Option<crate::providers::quota::QuotaNotifier>,
 ) -> Option<(Box<dyn LLMProvider>, Vec<&'static str>)> {
     let mut candidates: Vec<RuntimeProviderCandidate> = Vec::new();
     let configured_model = &config.agents.defaults.model;
 
     // Create a single shared QuotaStore for all providers assembled in this call.
-    let quota_store = Arc::new(crate::providers::QuotaStore::load_or_default());
+    let quota_store = Arc::new(crate::providers::QuotaStore::open(&config.storage));
+    if let Some(notifier) = quota_notifier {
+        quota_store.set_notifier(notifier);
+    }
 
     for selection in resolve_runtime_providers(config) {
         if let Some(provider) = provider_from_runtime_selection(&selection, configured_model).await
         {
+            // Innermost, so the stored request is exactly what this provider rejected.
+            let provider: Box<dyn LLMProvider> = Box::new(FailureCapture::new(
+                provider,
+                selection.name,
+                FailureStore::new(FailureStore::default_dir()),
+            ));
             let quota =
                 provider_config_by_name(config, selection.name).and_then(|pc| pc.quota.clone());
-            let provider =
-                apply_quota_wrapper(provider, selection.name, quota, Arc::clone(&quota_store));
+            let provider = apply_quota_wrapper(
+                provider,
+                selection.name,
+                quota,
+                config.providers.aggregate_quota.clone(),
+                Arc::clone(&quota_store),
+            );
+            // Timed per provider, so a slow fallback target shows up under its own name.
+            let provider: Box<dyn LLMProvider> =
+                Box::new(LatencyProvider::new(provider, selection.name));
             candidates.push(RuntimeProviderCandidate {
                 name: selection.name,
                 provider,
@@ -251,21 +293,46 @@ pub fn apply_retry_wrapper(
     )
 }
 
-/// Wrap `provider` in a [`crate::providers::QuotaProvider`] when a quota
-/// configuration is present, otherwise return `provider` unchanged.
+/// Wrap `provider` with [`PromptLogger`] when `providers.prompt_log.enabled`.
+///
+/// Sits outside the retry wrapper so each logical request is sampled once.
+pub fn apply_prompt_log_wrapper(
+    provider: Box<dyn LLMProvider>,
+    config: &Config,
+) -> Box<dyn LLMProvider> {
+    if !config.providers.prompt_log.enabled {
+        return provider;
+    }
+
+    Box::new(PromptLogger::new(provider, &config.providers.prompt_log))
+}
+
+/// Wrap `provider` in a [`crate::providers::QuotaProvider`] when a per-provider
+/// or aggregate quota is configured, otherwise return `provider` unchanged.
+///
+/// With only an aggregate quota, the provider is still wrapped (with no limits
+/// of its own, on the aggregate's period) so its usage counts towards the total.
 ///
 /// Moved from `cli/common.rs:333–345`.
 fn apply_quota_wrapper(
     provider: Box<dyn LLMProvider>,
     name: &str,
     quota: Option<crate::providers::QuotaConfig>,
+    aggregate: Option<crate::providers::QuotaConfig>,
     store: Arc<crate::providers::QuotaStore>,
 ) -> Box<dyn LLMProvider> {
-    match quota {
-        Some(config) => Box::new(crate::providers::QuotaProvider::new(
-            provider, name, config, store,
-        )),
-        None => provider,
+    let config = match (quota, &aggregate) {
+        (Some(config), _) => config,
+        (None, Some(aggregate)) => crate::providers::QuotaConfig {
+            period: aggregate.period.clone(),
+            ..Default::default()
+        },
+        (None, None) => return provider,
+    };
+    let wrapped = crate::providers::QuotaProvider::new(provider, name, config, store);
+    match aggregate {
+        Some(aggregate) => Box::new(wrapped.with_aggregate(aggregate)),
+        None => Box::new(wrapped),
     }
 }
 
@@ -670,6 +737,7 @@ mod tests {
             }),
             "test",
             None, // no quota config
+            None, // no aggregate quota
             store,
         );
 
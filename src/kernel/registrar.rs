@@ -21,6 +21,7 @@ use crate::security::{ShellAllowlistMode, ShellSecurityConfig};
 use crate::tools::mcp::client::McpClient;
 use crate::tools::mcp::discovery::{discover_mcp_servers, DiscoveredMcpServer, McpTransportType};
 use crate::tools::mcp::wrapper::McpToolWrapper;
+use crate::tools::use_hand::USE_HAND_TOOL;
 use crate::tools::ToolRegistry;
 
 /// Build a [`ShellSecurityConfig`] from a template's `shell_allowlist` field.
@@ -39,6 +40,60 @@ pub fn build_shell_config(template: Option<&AgentTemplate>) -> ShellSecurityConf
     }
 }
 
+/// Names of every built-in tool that kernel boot or agent setup can register.
+///
+/// Whether a tool is actually registered still depends on config and the
+/// [`ToolFilter`]; MCP, plugin and custom tools are not listed.
+pub const BUILTIN_TOOL_NAMES: &[&str] = &[
+    "android",
+    "ask_clarification",
+    "browser",
+    "cache",
+    "compact_session",
+    "create_tool",
+    "cron",
+    "delegate",
+    "docx_read",
+    "echo",
+    "edit_file",
+    "find",
+    "find_skills",
+    "git",
+    "google",
+    "google_sheets",
+    "grep",
+    "hardware",
+    "http_request",
+    "index_workspace",
+    "install_skill",
+    "kv",
+    "list_dir",
+    "longterm_memory",
+    "memory_get",
+    "memory_search",
+    "message",
+    "pdf_read",
+    "pin",
+    "preferences",
+    "project",
+    "r8r",
+    "rag_query",
+    "read_file",
+    "reminder",
+    "serial",
+    "shell",
+    "spawn",
+    "stripe",
+    "task",
+    "transcribe",
+    "use_hand",
+    "web_fetch",
+    "web_screenshot",
+    "web_search",
+    "whatsapp_send",
+    "write_file",
+];
+
 /// Encapsulates the 5 filtering dimensions that gate tool registration.
 ///
 /// Each dimension independently vetoes a tool name. A tool passes only if ALL
@@ -85,10 +140,12 @@ impl ToolFilter {
                     .collect::<HashSet<_>>()
             });
 
+        // `use_hand` stays available so the user can switch hands later.
         let hand_allowed = hand.map(|h| {
             h.required_tools
                 .iter()
                 .map(|n| n.to_ascii_lowercase())
+                .chain(std::iter::once(USE_HAND_TOOL.to_string()))
                 .collect::<HashSet<_>>()
         });
 
@@ -400,9 +457,16 @@ pub async fn register_all_tools(
 
     // --- Group 7: Channel/messaging tools ---
     if filter.is_enabled("message") {
-        registry.register(Box::new(crate::tools::MessageTool::new(Arc::clone(
-            &deps.bus,
-        ))));
+        let email_recipients = config
+            .channels
+            .email
+            .as_ref()
+            .map(|email| email.allowed_recipients.clone())
+            .unwrap_or_default();
+        registry.register(Box::new(
+            crate::tools::MessageTool::new(Arc::clone(&deps.bus))
+                .with_email_recipients(email_recipients),
+        ));
         info!("Registered message tool");
     }
     if filter.is_enabled("whatsapp_send") {
@@ -490,9 +554,19 @@ pub async fn register_all_tools(
 
     // --- Group 11: Scheduling/cron ---
     if filter.is_enabled("cron") {
-        registry.register(Box::new(crate::tools::cron::CronTool::new(Arc::clone(
-            &deps.cron_service,
-        ))));
+        let timezone = config
+            .agents
+            .defaults
+            .timezone
+            .parse()
+            .unwrap_or(chrono_tz::Tz::UTC);
+        registry.register(Box::new(
+            crate::tools::cron::CronTool::new(Arc::clone(&deps.cron_service))
+                .with_timezone(timezone)
+                .with_preferences(Arc::new(crate::memory::preferences::PreferencesStore::new(
+                    crate::memory::preferences::PreferencesStore::default_dir(),
+                ))),
+        ));
     }
     if filter.is_enabled("r8r") {
         registry.register(Box::new(crate::tools::R8rTool::default()));
@@ -599,6 +673,13 @@ pub async fn register_all_tools(
         info!("Registered android tool");
     }
 
+    // --- Group 15b: Serial devices (feature-gated) ---
+    #[cfg(feature = "hardware")]
+    if filter.is_enabled("serial") {
+        registry.register(Box::new(crate::tools::SerialTool::new()));
+        info!("Registered serial tool");
+    }
+
     // --- Group 16: Plugin tools ---
     if config.plugins.enabled {
         let plugin_dirs: Vec<PathBuf> = config
@@ -1099,11 +1180,14 @@ mod tests {
             system_prompt: String::new(),
             guardrails: HandGuardrails::default(),
             settings: HashMap::new(),
+            stages: Vec::new(),
+            max_iterations: None,
         };
         let filter = ToolFilter::from_config(&config, None, Some(&hand));
         assert!(filter.is_enabled("echo"));
         assert!(filter.is_enabled("git"));
         assert!(!filter.is_enabled("shell")); // not in hand required_tools
+        assert!(filter.is_enabled("use_hand")); // switching hands stays possible
     }
 
     #[test]
@@ -1135,6 +1219,8 @@ mod tests {
             system_prompt: String::new(),
             guardrails: HandGuardrails::default(),
             settings: HashMap::new(),
+            stages: Vec::new(),
+            max_iterations: None,
         };
         let filter = ToolFilter::from_config(&config, Some(&template), Some(&hand));
         // Only "echo" is in both template allowed AND hand required
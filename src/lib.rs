@@ -30,6 +30,7 @@ pub mod providers;
 pub mod routines;
 pub mod runtime;
 pub mod safety;
+pub mod scheduler;
 pub use agent::{CompactionStrategy, ContextMonitor};
 pub use config::CompactionConfig;
 pub use safety::taint::{TaintConfig, TaintEngine, TaintLabel, TaintViolation};
@@ -53,7 +54,7 @@ pub use channels::{
 };
 pub use config::Config;
 pub use cron::{CronJob, CronPayload, CronSchedule, CronService, OnMiss};
-pub use error::{ProviderError, Result, ZeptoError};
+pub use error::{ProviderError, Result, ToolErrorKind, ZeptoError};
 pub use heartbeat::{ensure_heartbeat_file, HeartbeatResult, HeartbeatService, HEARTBEAT_PROMPT};
 pub use providers::{
     ChatOptions, ClaudeProvider, LLMProvider, LLMResponse, LLMToolCall, OpenAIProvider,
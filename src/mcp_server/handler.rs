@@ -167,7 +167,7 @@ async fn handle_tools_call(
         Err(e) => {
             let result = CallToolResult {
                 content: vec![ContentBlock::Text {
-                    text: format!("Tool execution error: {e}"),
+                    text: e.to_tool_result(),
                 }],
                 is_error: true,
             };
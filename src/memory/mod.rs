@@ -10,8 +10,10 @@ pub mod factory;
 pub mod hnsw_searcher;
 pub mod hygiene;
 pub mod longterm;
+pub mod preferences;
 pub mod snapshot;
 pub mod traits;
+pub mod workspace_index;
 
 use std::collections::HashSet;
 use std::fs;
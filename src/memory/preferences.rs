@@ -0,0 +1,397 @@
+//! Per-user preferences.
+//!
+//! Each identity (`channel:sender_id`) gets a small JSON file under
+//! `~/.zeptoclaw/users/` holding preferences such as tone, unit system,
+//! reply language and a preferred hand.  The agent loop reads them when
+//! building the system prompt; the `preferences` tool lets users change them
+//! conversationally.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+use crate::error::{Result, ZeptoError};
+
+/// Maximum length of a single preference value.
+const MAX_VALUE_LEN: usize = 200;
+/// Maximum number of free-form preferences per user.
+const MAX_CUSTOM_ENTRIES: usize = 20;
+/// Preference keys with dedicated handling.
+pub const KNOWN_KEYS: &[&str] = &[
+    "tone",
+    "units",
+    "language",
+    "timezone",
+    "hand",
+    "usage_footer",
+];
+
+/// Measurement system for quantities in replies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UnitSystem {
+    Metric,
+    Imperial,
+}
+
+impl UnitSystem {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Metric => "metric",
+            Self::Imperial => "imperial",
+        }
+    }
+}
+
+/// Preferences stored for one user.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UserPreferences {
+    /// Preferred tone of replies (e.g. "concise", "friendly").
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tone: Option<String>,
+    /// Unit system for quantities.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub units: Option<UnitSystem>,
+    /// Reply language as a primary locale subtag (e.g. "es").
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// IANA timezone for times shown to the user (e.g. "Europe/Berlin").
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+    /// Hand activated for this user's conversations.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hand: Option<String>,
+    /// Append a token/cost footer to replies (overrides the config default).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usage_footer: Option<bool>,
+    /// Free-form preferences.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub custom: BTreeMap<String, String>,
+}
+
+impl UserPreferences {
+    /// Returns `true` if no preference is set.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Set a preference, validating known keys.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        let key = key.trim().to_ascii_lowercase();
+        let value = value.trim();
+        if value.is_empty() {
+            return Err(ZeptoError::Tool(format!("Empty value for '{}'", key)));
+        }
+        if value.chars().count() > MAX_VALUE_LEN {
+            return Err(ZeptoError::Tool(format!(
+                "Value for '{}' exceeds {} characters",
+                key, MAX_VALUE_LEN
+            )));
+        }
+
+        match key.as_str() {
+            "tone" => self.tone = Some(value.to_string()),
+            "units" => {
+                self.units = Some(match value.to_ascii_lowercase().as_str() {
+                    "metric" | "si" => UnitSystem::Metric,
+                    "imperial" | "us" => UnitSystem::Imperial,
+                    other => {
+                        return Err(ZeptoError::Tool(format!(
+                            "Unknown unit system '{}' (expected metric or imperial)",
+                            other
+                        )))
+                    }
+                })
+            }
+            "language" => {
+                let locale = crate::agent::locale::normalize_locale(value).ok_or_else(|| {
+                    ZeptoError::Tool(format!("Invalid language code '{}'", value))
+                })?;
+                self.language = Some(locale);
+            }
+            "timezone" => {
+                let tz: chrono_tz::Tz = value
+                    .parse()
+                    .map_err(|_| ZeptoError::Tool(format!("Unknown timezone '{}'", value)))?;
+                self.timezone = Some(tz.name().to_string());
+            }
+            "hand" => self.hand = Some(value.to_ascii_lowercase()),
+            "usage_footer" => {
+                self.usage_footer = Some(match value.to_ascii_lowercase().as_str() {
+                    "on" | "true" | "yes" | "1" => true,
+                    "off" | "false" | "no" | "0" => false,
+                    other => {
+                        return Err(ZeptoError::Tool(format!(
+                            "Invalid usage_footer value '{}' (expected on or off)",
+                            other
+                        )))
+                    }
+                })
+            }
+            _ => {
+                if key.is_empty()
+                    || key.len() > 32
+                    || !key
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+                {
+                    return Err(ZeptoError::Tool(format!(
+                        "Invalid preference key '{}'",
+                        key
+                    )));
+                }
+                if !self.custom.contains_key(&key) && self.custom.len() >= MAX_CUSTOM_ENTRIES {
+                    return Err(ZeptoError::Tool(format!(
+                        "At most {} custom preferences are allowed",
+                        MAX_CUSTOM_ENTRIES
+                    )));
+                }
+                self.custom.insert(key, value.to_string());
+            }
+        }
+        Ok(())
+    }
+
+    /// Remove a preference.  Returns `true` if it was set.
+    pub fn unset(&mut self, key: &str) -> bool {
+        let key = key.trim().to_ascii_lowercase();
+        match key.as_str() {
+            "tone" => self.tone.take().is_some(),
+            "units" => self.units.take().is_some(),
+            "language" => self.language.take().is_some(),
+            "timezone" => self.timezone.take().is_some(),
+            "hand" => self.hand.take().is_some(),
+            "usage_footer" => self.usage_footer.take().is_some(),
+            _ => self.custom.remove(&key).is_some(),
+        }
+    }
+
+    /// All set preferences as `(key, value)` pairs, known keys first.
+    pub fn entries(&self) -> Vec<(String, String)> {
+        let mut entries = Vec::new();
+        if let Some(tone) = &self.tone {
+            entries.push(("tone".to_string(), tone.clone()));
+        }
+        if let Some(units) = self.units {
+            entries.push(("units".to_string(), units.as_str().to_string()));
+        }
+        if let Some(language) = &self.language {
+            entries.push(("language".to_string(), language.clone()));
+        }
+        if let Some(timezone) = &self.timezone {
+            entries.push(("timezone".to_string(), timezone.clone()));
+        }
+        if let Some(hand) = &self.hand {
+            entries.push(("hand".to_string(), hand.clone()));
+        }
+        if let Some(footer) = self.usage_footer {
+            let value = if footer { "on" } else { "off" };
+            entries.push(("usage_footer".to_string(), value.to_string()));
+        }
+        entries.extend(self.custom.iter().map(|(k, v)| (k.clone(), v.clone())));
+        entries
+    }
+
+    /// System-prompt section describing these preferences.
+    ///
+    /// Language and hand are applied separately (locale hint and hand
+    /// prompt) and the usage footer is added to replies by the agent loop,
+    /// so they are not repeated here.
+    pub fn prompt_section(&self) -> Option<String> {
+        let mut lines = Vec::new();
+        if let Some(tone) = &self.tone {
+            lines.push(format!("- Tone: {}", tone));
+        }
+        match self.units {
+            Some(UnitSystem::Metric) => {
+                lines.push("- Units: metric (km, kg, °C); convert imperial values".to_string())
+            }
+            Some(UnitSystem::Imperial) => {
+                lines.push("- Units: imperial (miles, lb, °F); convert metric values".to_string())
+            }
+            None => {}
+        }
+        if let Some(timezone) = &self.timezone {
+            lines.push(format!("- Timezone: {}; give times in this zone", timezone));
+        }
+        for (key, value) in &self.custom {
+            lines.push(format!("- {}: {}", key, value));
+        }
+        if lines.is_empty() {
+            return None;
+        }
+        Some(format!(
+            "## User Preferences\n\nThe user has asked you to follow these preferences:\n{}",
+            lines.join("\n")
+        ))
+    }
+}
+
+/// Preferences identity for a sender on a channel.
+pub fn identity(channel: &str, sender_id: &str) -> String {
+    format!("{}:{}", channel, sender_id)
+}
+
+/// File-backed preferences store, one JSON file per identity.
+#[derive(Debug)]
+pub struct PreferencesStore {
+    dir: PathBuf,
+}
+
+impl PreferencesStore {
+    /// Create a store rooted at `dir`.
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Default store directory (`~/.zeptoclaw/users`).
+    pub fn default_dir() -> PathBuf {
+        Config::dir().join("users")
+    }
+
+    /// File holding the preferences for `id`.
+    ///
+    /// Characters outside `[A-Za-z0-9_.-]` are replaced so identities can
+    /// never escape the store directory.
+    pub fn path_for(&self, id: &str) -> PathBuf {
+        let file: String = id
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.') {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        let file = file.trim_start_matches('.');
+        self.dir.join(format!("{}.json", file))
+    }
+
+    /// Timezone preference of the sender in `ctx`, if set and valid.
+    pub fn timezone_for(&self, ctx: &crate::tools::ToolContext) -> Option<chrono_tz::Tz> {
+        let id = identity(ctx.channel.as_deref()?, ctx.sender_id.as_deref()?);
+        self.load(&id).ok()?.timezone?.parse().ok()
+    }
+
+    /// Load preferences for `id`; a missing file yields empty preferences.
+    pub fn load(&self, id: &str) -> Result<UserPreferences> {
+        let path = self.path_for(id);
+        if !path.exists() {
+            return Ok(UserPreferences::default());
+        }
+        let content = std::fs::read_to_string(&path).map_err(|e| {
+            ZeptoError::Config(format!(
+                "Failed to read preferences from {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        serde_json::from_str(&content)
+            .map_err(|e| ZeptoError::Config(format!("Failed to parse preferences JSON: {}", e)))
+    }
+
+    /// Persist preferences for `id`, removing the file once they are empty.
+    pub fn save(&self, id: &str, prefs: &UserPreferences) -> Result<()> {
+        let path = self.path_for(id);
+        if prefs.is_empty() {
+            if path.exists() {
+                std::fs::remove_file(&path)?;
+            }
+            return Ok(());
+        }
+        std::fs::create_dir_all(&self.dir).map_err(|e| {
+            ZeptoError::Config(format!(
+                "Failed to create preferences directory {}: {}",
+                self.dir.display(),
+                e
+            ))
+        })?;
+        let json = serde_json::to_string_pretty(prefs)?;
+        std::fs::write(&path, json).map_err(|e| {
+            ZeptoError::Config(format!(
+                "Failed to write preferences to {}: {}",
+                path.display(),
+                e
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn test_store_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = PreferencesStore::new(dir.path().to_path_buf());
+        let id = identity("telegram", "42");
+        assert!(store.load(&id).unwrap().is_empty());
+
+        let mut prefs = UserPreferences::default();
+        prefs.set("tone", "concise").unwrap();
+        prefs.set("units", "Imperial").unwrap();
+        prefs.set("language", "pt-BR").unwrap();
+        prefs.set("hand", "Coder").unwrap();
+        prefs.set("timezone", "Asia/Tokyo").unwrap();
+        store.save(&id, &prefs).unwrap();
+
+        let loaded = PreferencesStore::new(dir.path().to_path_buf())
+            .load(&id)
+            .unwrap();
+        assert_eq!(loaded, prefs);
+        assert_eq!(loaded.units, Some(UnitSystem::Imperial));
+        assert_eq!(loaded.language.as_deref(), Some("pt"));
+        assert_eq!(loaded.hand.as_deref(), Some("coder"));
+
+        let mut cleared = loaded;
+        for (key, _) in cleared.entries() {
+            assert!(cleared.unset(&key));
+        }
+        store.save(&id, &cleared).unwrap();
+        assert!(!store.path_for(&id).exists());
+    }
+
+    #[test]
+    fn test_path_for_stays_in_store() {
+        let store = PreferencesStore::new(PathBuf::from("/tmp/users"));
+        let path = store.path_for("../../etc/passwd");
+        assert_eq!(path.parent().unwrap(), Path::new("/tmp/users"));
+        assert_eq!(
+            store.path_for("telegram:42"),
+            PathBuf::from("/tmp/users/telegram_42.json")
+        );
+    }
+
+    #[test]
+    fn test_set_rejects_invalid_values() {
+        let mut prefs = UserPreferences::default();
+        assert!(prefs.set("units", "cubits").is_err());
+        assert!(prefs.set("language", "!!").is_err());
+        assert!(prefs.set("bad key", "x").is_err());
+        assert!(prefs.set("usage_footer", "maybe").is_err());
+        assert!(prefs.set("timezone", "Mars/Olympus").is_err());
+        assert!(prefs.set("tone", " ").is_err());
+        assert!(prefs.is_empty());
+    }
+
+    #[test]
+    fn test_prompt_section() {
+        let mut prefs = UserPreferences::default();
+        assert!(prefs.prompt_section().is_none());
+        prefs.set("language", "es").unwrap();
+        assert!(prefs.prompt_section().is_none());
+
+        prefs.set("units", "metric").unwrap();
+        prefs.set("tone", "formal").unwrap();
+        let section = prefs.prompt_section().unwrap();
+        assert!(section.starts_with("## User Preferences"));
+        assert!(section.contains("- Tone: formal"));
+        assert!(section.contains("Units: metric"));
+    }
+}
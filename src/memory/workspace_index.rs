@@ -0,0 +1,591 @@
+//! Embedding index over workspace files for retrieval-augmented answers.
+//!
+//! Text files under the workspace are split into overlapping line chunks,
+//! embedded with the provider's `embed()` method, and persisted to a JSON
+//! file keyed by the workspace path. Re-indexing is incremental: a file is
+//! re-embedded only when its modification time or size changed, and entries
+//! for deleted files are dropped.
+//!
+//! Paths matching patterns in the workspace's `.zeptoignore` file are
+//! skipped, along with `.git`, `node_modules`, and `target`. The ignore file
+//! uses a gitignore-like subset: one glob per line, `#` comments, a trailing
+//! `/` to match directories only, and patterns containing `/` matched
+//! against the workspace-relative path instead of the file name.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::UNIX_EPOCH;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::sync::Mutex;
+use tracing::{debug, warn};
+
+use crate::error::{Result, ZeptoError};
+use crate::providers::LLMProvider;
+
+/// Name of the per-workspace ignore file.
+pub const IGNORE_FILE: &str = ".zeptoignore";
+
+/// Lines per chunk.
+pub const CHUNK_LINES: usize = 40;
+/// Lines shared between consecutive chunks.
+pub const CHUNK_OVERLAP: usize = 8;
+
+/// Directories that are never indexed.
+const DEFAULT_IGNORES: &[&str] = &[".git/", "node_modules/", "target/"];
+/// Files larger than this are skipped.
+const MAX_FILE_BYTES: u64 = 512 * 1024;
+/// Maximum texts sent to `embed()` in a single call.
+const EMBED_BATCH: usize = 64;
+const MAX_DIR_DEPTH: usize = 16;
+
+/// A contiguous range of lines from a file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TextChunk {
+    /// First line of the chunk (1-based).
+    pub start_line: usize,
+    /// Last line of the chunk (1-based, inclusive).
+    pub end_line: usize,
+    /// Chunk content.
+    pub text: String,
+}
+
+/// Split `text` into chunks of `chunk_lines` lines, each sharing `overlap`
+/// lines with the previous one. Whitespace-only chunks are dropped.
+pub fn chunk_text(text: &str, chunk_lines: usize, overlap: usize) -> Vec<TextChunk> {
+    let lines: Vec<&str> = text.lines().collect();
+    let chunk_lines = chunk_lines.max(1);
+    let step = chunk_lines.saturating_sub(overlap).max(1);
+
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    while start < lines.len() {
+        let end = (start + chunk_lines).min(lines.len());
+        let body = lines[start..end].join("\n");
+        if !body.trim().is_empty() {
+            chunks.push(TextChunk {
+                start_line: start + 1,
+                end_line: end,
+                text: body,
+            });
+        }
+        if end == lines.len() {
+            break;
+        }
+        start += step;
+    }
+    chunks
+}
+
+/// Patterns loaded from the workspace ignore file plus built-in defaults.
+#[derive(Debug, Default)]
+pub struct IgnoreRules {
+    rules: Vec<IgnoreRule>,
+}
+
+#[derive(Debug)]
+struct IgnoreRule {
+    pattern: glob::Pattern,
+    dir_only: bool,
+    anchored: bool,
+}
+
+impl IgnoreRules {
+    /// Load `.zeptoignore` from `workspace` (if present) plus the defaults.
+    pub fn load(workspace: &Path) -> Self {
+        let content = fs::read_to_string(workspace.join(IGNORE_FILE)).unwrap_or_default();
+        Self::parse(&content)
+    }
+
+    /// Parse ignore patterns, one per line, on top of the defaults.
+    pub fn parse(content: &str) -> Self {
+        let rules = DEFAULT_IGNORES
+            .iter()
+            .copied()
+            .chain(content.lines())
+            .filter_map(|line| {
+                let line = line.trim();
+                if line.is_empty() || line.starts_with('#') {
+                    return None;
+                }
+                let dir_only = line.ends_with('/');
+                let line = line.trim_end_matches('/');
+                let anchored = line.contains('/');
+                match glob::Pattern::new(line.trim_start_matches('/')) {
+                    Ok(pattern) => Some(IgnoreRule {
+                        pattern,
+                        dir_only,
+                        anchored,
+                    }),
+                    Err(e) => {
+                        warn!(pattern = line, error = %e, "Invalid {} pattern", IGNORE_FILE);
+                        None
+                    }
+                }
+            })
+            .collect();
+        Self { rules }
+    }
+
+    /// Returns `true` if the workspace-relative path `rel` is ignored.
+    pub fn is_ignored(&self, rel: &str, is_dir: bool) -> bool {
+        let name = rel.rsplit('/').next().unwrap_or(rel);
+        self.rules.iter().any(|rule| {
+            if rule.dir_only && !is_dir {
+                return false;
+            }
+            if rule.anchored {
+                rule.pattern.matches(rel)
+            } else {
+                rule.pattern.matches(name)
+            }
+        })
+    }
+}
+
+/// Summary of an indexing pass.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct IndexStats {
+    /// Files (re-)embedded in this pass.
+    pub indexed: usize,
+    /// Files whose stored vectors were reused.
+    pub unchanged: usize,
+    /// Files dropped because they no longer exist or are now ignored.
+    pub removed: usize,
+    /// Total chunks in the index after the pass.
+    pub chunks: usize,
+}
+
+/// A retrieved chunk with its similarity to the query.
+#[derive(Debug, Clone, Serialize)]
+pub struct RagHit {
+    /// Workspace-relative file path.
+    pub path: String,
+    /// First line of the chunk (1-based).
+    pub start_line: usize,
+    /// Last line of the chunk (1-based).
+    pub end_line: usize,
+    /// Cosine similarity to the query.
+    pub score: f32,
+    /// Chunk content.
+    pub text: String,
+}
+
+impl RagHit {
+    /// Citation in `path#Lx-Ly` form.
+    pub fn citation(&self) -> String {
+        format!("{}#L{}-L{}", self.path, self.start_line, self.end_line)
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct IndexStore {
+    files: HashMap<String, IndexedFile>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct IndexedFile {
+    mtime_ms: u64,
+    size: u64,
+    chunks: Vec<IndexedChunk>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct IndexedChunk {
+    #[serde(flatten)]
+    chunk: TextChunk,
+    vector: Vec<f32>,
+}
+
+/// Embedding index over the files of one or more workspaces.
+///
+/// Each workspace gets its own store file under `index_dir`, named by a hash
+/// of the workspace path.
+pub struct WorkspaceIndex {
+    provider: Arc<dyn LLMProvider>,
+    index_dir: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl WorkspaceIndex {
+    /// Create an index that embeds with `provider` and stores vectors under
+    /// `index_dir`.
+    pub fn new(provider: Arc<dyn LLMProvider>, index_dir: PathBuf) -> Self {
+        Self {
+            provider,
+            index_dir,
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Bring the index for `workspace` up to date, embedding only files that
+    /// changed since the last pass.
+    pub async fn refresh(&self, workspace: &Path) -> Result<IndexStats> {
+        let _guard = self.lock.lock().await;
+        let store_path = self.store_path(workspace);
+        let mut store = load_store(&store_path);
+
+        let ignore = IgnoreRules::load(workspace);
+        let mut files = Vec::new();
+        collect_files(workspace, workspace, &ignore, &mut files, 0);
+
+        let mut stats = IndexStats::default();
+        let mut next = IndexStore::default();
+        let mut pending: Vec<(String, u64, u64, Vec<TextChunk>)> = Vec::new();
+
+        for (rel, mtime_ms, size) in files {
+            match store.files.remove(&rel) {
+                Some(entry) if entry.mtime_ms == mtime_ms && entry.size == size => {
+                    stats.unchanged += 1;
+                    next.files.insert(rel, entry);
+                }
+                _ => {
+                    let Ok(content) = fs::read_to_string(workspace.join(&rel)) else {
+                        debug!(path = %rel, "Skipping non-UTF-8 file");
+                        continue;
+                    };
+                    pending.push((
+                        rel,
+                        mtime_ms,
+                        size,
+                        chunk_text(&content, CHUNK_LINES, CHUNK_OVERLAP),
+                    ));
+                }
+            }
+        }
+        stats.removed = store.files.len();
+
+        let texts: Vec<String> = pending
+            .iter()
+            .flat_map(|(_, _, _, chunks)| chunks.iter().map(|c| c.text.clone()))
+            .collect();
+        let mut vectors = self.embed_all(&texts).await?.into_iter();
+
+        for (rel, mtime_ms, size, chunks) in pending {
+            let chunks = chunks
+                .into_iter()
+                .map(|chunk| IndexedChunk {
+                    chunk,
+                    vector: vectors.next().unwrap_or_default(),
+                })
+                .collect();
+            next.files.insert(
+                rel,
+                IndexedFile {
+                    mtime_ms,
+                    size,
+                    chunks,
+                },
+            );
+            stats.indexed += 1;
+        }
+        stats.chunks = next.files.values().map(|f| f.chunks.len()).sum();
+
+        save_store(&store_path, &next)?;
+        Ok(stats)
+    }
+
+    /// Return the `top_k` chunks most similar to `query`, best first.
+    ///
+    /// Uses the stored index as-is; call [`WorkspaceIndex::refresh`] first to
+    /// pick up changed files.
+    pub async fn query(&self, workspace: &Path, query: &str, top_k: usize) -> Result<Vec<RagHit>> {
+        let store = {
+            let _guard = self.lock.lock().await;
+            load_store(&self.store_path(workspace))
+        };
+        if store.files.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let query_vec = self
+            .provider
+            .embed(&[query.to_string()])
+            .await?
+            .into_iter()
+            .next()
+            .unwrap_or_default();
+
+        let mut hits: Vec<RagHit> = store
+            .files
+            .into_iter()
+            .flat_map(|(path, file)| {
+                let query_vec = &query_vec;
+                file.chunks.into_iter().map(move |c| RagHit {
+                    path: path.clone(),
+                    start_line: c.chunk.start_line,
+                    end_line: c.chunk.end_line,
+                    score: cosine_similarity(query_vec, &c.vector),
+                    text: c.chunk.text,
+                })
+            })
+            .collect();
+        hits.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.path.cmp(&b.path))
+                .then_with(|| a.start_line.cmp(&b.start_line))
+        });
+        hits.truncate(top_k);
+        Ok(hits)
+    }
+
+    /// Whether `workspace` has any indexed chunks.
+    pub async fn is_indexed(&self, workspace: &Path) -> bool {
+        let _guard = self.lock.lock().await;
+        !load_store(&self.store_path(workspace)).files.is_empty()
+    }
+
+    fn store_path(&self, workspace: &Path) -> PathBuf {
+        let mut hasher = Sha256::new();
+        hasher.update(workspace.to_string_lossy().as_bytes());
+        let digest = hex::encode(hasher.finalize());
+        self.index_dir.join(format!("{}.json", &digest[..16]))
+    }
+
+    async fn embed_all(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        let mut vectors = Vec::with_capacity(texts.len());
+        for batch in texts.chunks(EMBED_BATCH) {
+            let embedded = self.provider.embed(batch).await?;
+            if embedded.len() != batch.len() {
+                return Err(ZeptoError::Provider(format!(
+                    "embed() returned {} vectors for {} inputs",
+                    embedded.len(),
+                    batch.len()
+                )));
+            }
+            vectors.extend(embedded);
+        }
+        Ok(vectors)
+    }
+}
+
+/// Cosine similarity of two vectors; 0.0 for empty, mismatched, or zero
+/// vectors.
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let mag_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let mag_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if mag_a == 0.0 || mag_b == 0.0 {
+        return 0.0;
+    }
+    dot / (mag_a * mag_b)
+}
+
+/// Collect `(relative path, mtime ms, size)` for indexable files.
+fn collect_files(
+    root: &Path,
+    dir: &Path,
+    ignore: &IgnoreRules,
+    files: &mut Vec<(String, u64, u64)>,
+    depth: usize,
+) {
+    if depth > MAX_DIR_DEPTH {
+        return;
+    }
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.filter_map(|e| e.ok()) {
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_symlink() {
+            continue;
+        }
+        let path = entry.path();
+        let rel = path
+            .strip_prefix(root)
+            .unwrap_or(&path)
+            .to_string_lossy()
+            .replace('\\', "/");
+        if rel == IGNORE_FILE || ignore.is_ignored(&rel, file_type.is_dir()) {
+            continue;
+        }
+        if file_type.is_dir() {
+            collect_files(root, &path, ignore, files, depth + 1);
+            continue;
+        }
+        let Ok(meta) = entry.metadata() else {
+            continue;
+        };
+        if meta.len() == 0 || meta.len() > MAX_FILE_BYTES {
+            continue;
+        }
+        let mtime_ms = meta
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+        files.push((rel, mtime_ms, meta.len()));
+    }
+}
+
+fn load_store(path: &Path) -> IndexStore {
+    match fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            warn!(path = %path.display(), error = %e, "Corrupt workspace index, rebuilding");
+            IndexStore::default()
+        }),
+        Err(_) => IndexStore::default(),
+    }
+}
+
+fn save_store(path: &Path, store: &IndexStore) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string(store)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::{ChatOptions, LLMResponse, ToolDefinition};
+    use crate::session::Message;
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::{Duration, SystemTime};
+    use tempfile::TempDir;
+
+    /// Embeds text as keyword counts over a tiny fixed vocabulary and counts
+    /// how many texts it was asked to embed.
+    struct KeywordEmbedder {
+        embedded: AtomicUsize,
+    }
+
+    const VOCAB: &[&str] = &["rust", "python", "cooking", "garden"];
+
+    #[async_trait]
+    impl LLMProvider for KeywordEmbedder {
+        fn name(&self) -> &str {
+            "keyword"
+        }
+        fn default_model(&self) -> &str {
+            "keyword"
+        }
+        async fn chat(
+            &self,
+            _messages: Vec<Message>,
+            _tools: Vec<ToolDefinition>,
+            _model: Option<&str>,
+            _options: ChatOptions,
+        ) -> Result<LLMResponse> {
+            Ok(LLMResponse::text("ok"))
+        }
+        async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+            self.embedded.fetch_add(texts.len(), Ordering::SeqCst);
+            Ok(texts
+                .iter()
+                .map(|t| {
+                    let t = t.to_lowercase();
+                    VOCAB.iter().map(|w| t.matches(w).count() as f32).collect()
+                })
+                .collect())
+        }
+    }
+
+    fn index(dir: &TempDir) -> (Arc<KeywordEmbedder>, WorkspaceIndex) {
+        let provider = Arc::new(KeywordEmbedder {
+            embedded: AtomicUsize::new(0),
+        });
+        let index = WorkspaceIndex::new(provider.clone(), dir.path().join("index"));
+        (provider, index)
+    }
+
+    #[test]
+    fn test_chunk_text_overlaps_and_tracks_lines() {
+        let text: String = (1..=10).map(|i| format!("line {i}\n")).collect();
+        let chunks = chunk_text(&text, 4, 1);
+        let ranges: Vec<_> = chunks.iter().map(|c| (c.start_line, c.end_line)).collect();
+        assert_eq!(ranges, vec![(1, 4), (4, 7), (7, 10)]);
+        assert_eq!(chunks[1].text, "line 4\nline 5\nline 6\nline 7");
+    }
+
+    #[test]
+    fn test_chunk_text_skips_blank_chunks() {
+        let text = "a\n\n\n\n\n\n\nb";
+        let chunks = chunk_text(text, 3, 0);
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].start_line, 1);
+        assert_eq!(chunks[1].end_line, 8);
+        assert!(chunk_text("", 3, 0).is_empty());
+    }
+
+    #[test]
+    fn test_ignore_rules() {
+        let rules = IgnoreRules::parse("# comment\n*.log\nbuild/\ndocs/private/*.md\n");
+        assert!(rules.is_ignored("app.log", false));
+        assert!(rules.is_ignored("nested/app.log", false));
+        assert!(rules.is_ignored("build", true));
+        assert!(!rules.is_ignored("build", false));
+        assert!(rules.is_ignored("docs/private/notes.md", false));
+        assert!(!rules.is_ignored("docs/notes.md", false));
+        assert!(rules.is_ignored(".git", true));
+        assert!(!rules.is_ignored("src/main.rs", false));
+    }
+
+    #[tokio::test]
+    async fn test_refresh_only_reembeds_changed_files() {
+        let dir = TempDir::new().unwrap();
+        let ws = dir.path().join("ws");
+        fs::create_dir_all(ws.join("ignored")).unwrap();
+        fs::write(ws.join("a.md"), "rust notes").unwrap();
+        fs::write(ws.join("b.md"), "python notes").unwrap();
+        fs::write(ws.join("ignored/c.md"), "garden notes").unwrap();
+        fs::write(ws.join(IGNORE_FILE), "ignored/\n").unwrap();
+        let (provider, index) = index(&dir);
+
+        let stats = index.refresh(&ws).await.unwrap();
+        assert_eq!((stats.indexed, stats.unchanged, stats.chunks), (2, 0, 2));
+        assert_eq!(provider.embedded.load(Ordering::SeqCst), 2);
+
+        let stats = index.refresh(&ws).await.unwrap();
+        assert_eq!((stats.indexed, stats.unchanged), (0, 2));
+        assert_eq!(provider.embedded.load(Ordering::SeqCst), 2);
+
+        fs::write(ws.join("a.md"), "rust notes, revised").unwrap();
+        let later = SystemTime::now() + Duration::from_secs(5);
+        fs::File::options()
+            .write(true)
+            .open(ws.join("a.md"))
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+        fs::remove_file(ws.join("b.md")).unwrap();
+
+        let stats = index.refresh(&ws).await.unwrap();
+        assert_eq!(
+            (stats.indexed, stats.unchanged, stats.removed, stats.chunks),
+            (1, 0, 1, 1)
+        );
+        assert_eq!(provider.embedded.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_query_returns_top_k_by_cosine() {
+        let dir = TempDir::new().unwrap();
+        let ws = dir.path().join("ws");
+        fs::create_dir_all(&ws).unwrap();
+        fs::write(ws.join("rust.md"), "rust rust rust").unwrap();
+        fs::write(ws.join("mixed.md"), "rust python").unwrap();
+        fs::write(ws.join("food.md"), "cooking garden").unwrap();
+        let (_, index) = index(&dir);
+        assert!(!index.is_indexed(&ws).await);
+        index.refresh(&ws).await.unwrap();
+
+        let hits = index.query(&ws, "rust", 2).await.unwrap();
+        let paths: Vec<_> = hits.iter().map(|h| h.path.as_str()).collect();
+        assert_eq!(paths, vec!["rust.md", "mixed.md"]);
+        assert!(hits[0].score > hits[1].score);
+        assert_eq!(hits[0].citation(), "rust.md#L1-L1");
+    }
+}
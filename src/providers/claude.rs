@@ -29,18 +29,26 @@
 use async_trait::async_trait;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use tracing::warn;
+use tracing::{debug, info, warn};
 
 use crate::error::{Result, ZeptoError};
 use crate::session::{ContentPart, ImageSource, Message, Role, ToolCall};
 
+use super::debug_log;
 use super::{
-    parse_provider_error, ChatOptions, LLMProvider, LLMResponse, LLMToolCall, ToolDefinition, Usage,
+    parse_provider_error, BatchRequest, ChatOptions, LLMProvider, LLMResponse, LLMToolCall,
+    ToolDefinition, Usage,
 };
 
 /// The Claude API endpoint URL.
 const CLAUDE_API_URL: &str = "https://api.anthropic.com/v1/messages";
 
+/// The Message Batches API endpoint URL.
+const CLAUDE_BATCHES_URL: &str = "https://api.anthropic.com/v1/messages/batches";
+
+/// How often to poll a submitted batch for completion.
+const DEFAULT_BATCH_POLL_INTERVAL_SECS: u64 = 30;
+
 /// The default Claude model to use.
 /// Can be overridden at compile time with `ZEPTOCLAW_CLAUDE_DEFAULT_MODEL` env var.
 const DEFAULT_MODEL: &str = match option_env!("ZEPTOCLAW_CLAUDE_DEFAULT_MODEL") {
@@ -60,6 +68,8 @@ pub struct ClaudeProvider {
     credential: crate::auth::ResolvedCredential,
     /// HTTP client for making requests
     client: Client,
+    /// Delay between batch status polls
+    batch_poll_interval: std::time::Duration,
 }
 
 impl ClaudeProvider {
@@ -79,10 +89,8 @@ impl ClaudeProvider {
     pub fn new(api_key: &str) -> Self {
         Self {
             credential: crate::auth::ResolvedCredential::ApiKey(api_key.to_string()),
-            client: Client::builder()
-                .timeout(std::time::Duration::from_secs(120))
-                .build()
-                .unwrap_or_else(|_| Client::new()),
+            client: crate::utils::http::client(std::time::Duration::from_secs(120)),
+            batch_poll_interval: std::time::Duration::from_secs(DEFAULT_BATCH_POLL_INTERVAL_SECS),
         }
     }
 
@@ -92,10 +100,8 @@ impl ClaudeProvider {
     pub fn with_credential(credential: crate::auth::ResolvedCredential) -> Self {
         Self {
             credential,
-            client: Client::builder()
-                .timeout(std::time::Duration::from_secs(120))
-                .build()
-                .unwrap_or_else(|_| Client::new()),
+            client: crate::utils::http::client(std::time::Duration::from_secs(120)),
+            batch_poll_interval: std::time::Duration::from_secs(DEFAULT_BATCH_POLL_INTERVAL_SECS),
         }
     }
 
@@ -111,9 +117,16 @@ impl ClaudeProvider {
         Self {
             credential: crate::auth::ResolvedCredential::ApiKey(api_key.to_string()),
             client,
+            batch_poll_interval: std::time::Duration::from_secs(DEFAULT_BATCH_POLL_INTERVAL_SECS),
         }
     }
 
+    /// Set how often `batch_chat()` polls a submitted batch (default 30s).
+    pub fn with_batch_poll_interval(mut self, interval: std::time::Duration) -> Self {
+        self.batch_poll_interval = interval;
+        self
+    }
+
     /// Build auth headers based on the resolved credential type.
     ///
     /// - API key: sends `x-api-key` header
@@ -155,6 +168,55 @@ impl ClaudeProvider {
         }
         headers
     }
+
+    /// Submit a batch, wait for it to end and return the raw JSONL results.
+    async fn run_batch(&self, batch: &ClaudeBatchCreate) -> Result<String> {
+        let response = self
+            .client
+            .post(CLAUDE_BATCHES_URL)
+            .headers(self.auth_headers())
+            .header("anthropic-version", ANTHROPIC_VERSION)
+            .header("content-type", "application/json")
+            .json(batch)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(error_from_response(response).await);
+        }
+        let mut status: ClaudeBatch = response.json().await?;
+        info!(batch_id = %status.id, requests = batch.requests.len(), "Submitted Claude message batch");
+
+        while status.processing_status != "ended" {
+            tokio::time::sleep(self.batch_poll_interval).await;
+            let response = self
+                .client
+                .get(format!("{}/{}", CLAUDE_BATCHES_URL, status.id))
+                .headers(self.auth_headers())
+                .header("anthropic-version", ANTHROPIC_VERSION)
+                .send()
+                .await?;
+            if !response.status().is_success() {
+                return Err(error_from_response(response).await);
+            }
+            status = response.json().await?;
+            debug!(batch_id = %status.id, status = %status.processing_status, "Polled Claude message batch");
+        }
+
+        let results_url = status.results_url.ok_or_else(|| {
+            ZeptoError::Provider(format!("Claude batch {} ended without results", status.id))
+        })?;
+        let response = self
+            .client
+            .get(&results_url)
+            .headers(self.auth_headers())
+            .header("anthropic-version", ANTHROPIC_VERSION)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(error_from_response(response).await);
+        }
+        Ok(response.text().await?)
+    }
 }
 
 #[async_trait]
@@ -166,66 +228,28 @@ impl LLMProvider for ClaudeProvider {
         model: Option<&str>,
         options: ChatOptions,
     ) -> Result<LLMResponse> {
-        let model = model.unwrap_or(DEFAULT_MODEL);
-
-        // Convert messages to Claude format, extracting system message
-        let (mut system, claude_messages) = convert_messages(messages)?;
-
-        // Append structured output instructions to system prompt if needed
-        if let Some(suffix) = options.output_format.to_claude_system_suffix() {
-            let base = system.unwrap_or_default();
-            system = Some(format!("{}{}", base, suffix));
-        }
-
-        // Build request
-        let request = ClaudeRequest {
-            model: model.to_string(),
-            max_tokens: options.max_tokens.unwrap_or(8192),
-            messages: claude_messages,
-            system,
-            tools: if tools.is_empty() {
-                None
-            } else {
-                Some(convert_tools(tools))
-            },
-            temperature: options.temperature,
-            top_p: options.top_p,
-            stop_sequences: options.stop,
-            stream: None,
-        };
+        let request = build_request(messages, tools, model, options, None)?;
 
         // Send request
-        let response = self
+        let request = self
             .client
             .post(CLAUDE_API_URL)
             .headers(self.auth_headers())
             .header("anthropic-version", ANTHROPIC_VERSION)
             .header("content-type", "application/json")
-            .json(&request)
-            .send()
-            .await?;
+            .json(&request);
+        debug_log::log_request("claude", &request);
+        let response = request.send().await?;
 
         if !response.status().is_success() {
-            let status = response.status().as_u16();
-            let error_text = response.text().await.unwrap_or_default();
-
-            // Build a human-readable body for the typed error
-            let body = if let Ok(error_response) =
-                serde_json::from_str::<ClaudeErrorResponse>(&error_text)
-            {
-                format!(
-                    "Claude API error: {} - {}",
-                    error_response.error.r#type, error_response.error.message
-                )
-            } else {
-                format!("Claude API error: {}", error_text)
-            };
-
-            return Err(ZeptoError::from(parse_provider_error(status, &body)));
+            return Err(error_from_response(response).await);
         }
 
-        let claude_response: ClaudeResponse = response.json().await?;
-        Ok(convert_response(claude_response))
+        let status = response.status().as_u16();
+        let body = response.text().await?;
+        debug_log::log_response("claude", status, &body);
+        let claude_response: ClaudeResponse = serde_json::from_str(&body)?;
+        checked_response(claude_response)
     }
 
     async fn chat_stream(
@@ -238,55 +262,20 @@ impl LLMProvider for ClaudeProvider {
         use super::StreamEvent;
         use futures::StreamExt;
 
-        let model = model.unwrap_or(DEFAULT_MODEL);
-        let (mut system, claude_messages) = convert_messages(messages)?;
-
-        // Append structured output instructions to system prompt if needed
-        if let Some(suffix) = options.output_format.to_claude_system_suffix() {
-            let base = system.unwrap_or_default();
-            system = Some(format!("{}{}", base, suffix));
-        }
-
-        let request = ClaudeRequest {
-            model: model.to_string(),
-            max_tokens: options.max_tokens.unwrap_or(8192),
-            messages: claude_messages,
-            system,
-            tools: if tools.is_empty() {
-                None
-            } else {
-                Some(convert_tools(tools))
-            },
-            temperature: options.temperature,
-            top_p: options.top_p,
-            stop_sequences: options.stop,
-            stream: Some(true),
-        };
+        let request = build_request(messages, tools, model, options, Some(true))?;
 
-        let response = self
+        let request = self
             .client
             .post(CLAUDE_API_URL)
             .headers(self.auth_headers())
             .header("anthropic-version", ANTHROPIC_VERSION)
             .header("content-type", "application/json")
-            .json(&request)
-            .send()
-            .await?;
+            .json(&request);
+        debug_log::log_request("claude", &request);
+        let response = request.send().await?;
 
         if !response.status().is_success() {
-            let status = response.status().as_u16();
-            let error_text = response.text().await.unwrap_or_default();
-            let body = if let Ok(error_response) =
-                serde_json::from_str::<ClaudeErrorResponse>(&error_text)
-            {
-                format!(
-                    "Claude API error: {} - {}",
-                    error_response.error.r#type, error_response.error.message
-                )
-            } else {
-                format!("Claude API error: {}", error_text)
-            };
-            return Err(ZeptoError::from(parse_provider_error(status, &body)));
+            return Err(error_from_response(response).await);
         }
 
         let (tx, rx) = tokio::sync::mpsc::channel::<StreamEvent>(32);
@@ -448,6 +437,31 @@ impl LLMProvider for ClaudeProvider {
     fn name(&self) -> &str {
         "claude"
     }
+
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    /// Run the requests through the Message Batches API.
+    ///
+    /// The batch is submitted in one call, polled until it ends and its
+    /// results are matched back to the requests by `custom_id`. Batches can
+    /// take minutes to hours but cost about half as much as `chat()`.
+    async fn batch_chat(&self, requests: Vec<BatchRequest>) -> Vec<Result<LLMResponse>> {
+        if requests.is_empty() {
+            return Vec::new();
+        }
+        let count = requests.len();
+
+        let batch = match build_batch(requests) {
+            Ok(batch) => batch,
+            Err(e) => return fail_all(count, &e),
+        };
+        match self.run_batch(&batch).await {
+            Ok(results) => correlate_batch_results(count, &results),
+            Err(e) => fail_all(count, &e),
+        }
+    }
 }
 
 // ============================================================================
@@ -564,7 +578,6 @@ struct ClaudeResponse {
     /// Token usage
     usage: ClaudeUsage,
     /// Stop reason (e.g., "end_turn", "tool_use")
-    #[allow(dead_code)]
     stop_reason: Option<String>,
 }
 
@@ -590,6 +603,52 @@ struct ClaudeUsage {
     output_tokens: u32,
 }
 
+// ============================================================================
+// Claude Message Batches Types
+// ============================================================================
+
+/// Message Batches create request body.
+#[derive(Debug, Serialize)]
+struct ClaudeBatchCreate {
+    requests: Vec<ClaudeBatchItem>,
+}
+
+/// One request within a batch.
+#[derive(Debug, Serialize)]
+struct ClaudeBatchItem {
+    /// Caller-chosen ID used to match the result back to the request
+    custom_id: String,
+    /// Regular Messages API parameters
+    params: ClaudeRequest,
+}
+
+/// Batch status returned by create and retrieve.
+#[derive(Debug, Deserialize)]
+struct ClaudeBatch {
+    id: String,
+    /// "in_progress", "canceling" or "ended"
+    processing_status: String,
+    /// Where to download results once the batch has ended
+    results_url: Option<String>,
+}
+
+/// One line of the batch results JSONL file.
+#[derive(Debug, Deserialize)]
+struct ClaudeBatchResultLine {
+    custom_id: String,
+    result: ClaudeBatchResult,
+}
+
+/// Outcome of a single batch request.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ClaudeBatchResult {
+    Succeeded { message: ClaudeResponse },
+    Errored { error: ClaudeErrorResponse },
+    Canceled,
+    Expired,
+}
+
 // ============================================================================
 // Claude SSE Streaming Types
 // ============================================================================
@@ -656,6 +715,149 @@ struct SseMessage {
 // Conversion Functions
 // ============================================================================
 
+/// Build a Messages API request body.
+fn build_request(
+    messages: Vec<Message>,
+    tools: Vec<ToolDefinition>,
+    model: Option<&str>,
+    options: ChatOptions,
+    stream: Option<bool>,
+) -> Result<ClaudeRequest> {
+    let model = model.unwrap_or(DEFAULT_MODEL);
+
+    // The Messages API has no repetition penalties.
+    if options.presence_penalty.is_some() || options.frequency_penalty.is_some() {
+        debug!("Claude does not support presence/frequency penalties; ignoring them");
+    }
+
+    // Convert messages to Claude format, extracting system message
+    let (mut system, claude_messages) = convert_messages(messages)?;
+
+    // Append structured output instructions to system prompt if needed
+    if let Some(suffix) = options.output_format.to_claude_system_suffix() {
+        let base = system.unwrap_or_default();
+        system = Some(format!("{}{}", base, suffix));
+    }
+
+    Ok(ClaudeRequest {
+        model: model.to_string(),
+        max_tokens: options.max_tokens.unwrap_or(8192),
+        messages: claude_messages,
+        system,
+        tools: if tools.is_empty() {
+            None
+        } else {
+            Some(convert_tools(tools))
+        },
+        temperature: options.temperature,
+        top_p: options.top_p,
+        stop_sequences: options.stop,
+        stream,
+    })
+}
+
+/// Turn a failed API response into a typed provider error.
+async fn error_from_response(response: reqwest::Response) -> ZeptoError {
+    let status = response.status().as_u16();
+    let error_text = response.text().await.unwrap_or_default();
+
+    // Build a human-readable body for the typed error
+    let body = if let Ok(error_response) = serde_json::from_str::<ClaudeErrorResponse>(&error_text)
+    {
+        format!(
+            "Claude API error: {} - {}",
+            error_response.error.r#type, error_response.error.message
+        )
+    } else {
+        format!("Claude API error: {}", error_text)
+    };
+
+    ZeptoError::from(parse_provider_error(status, &body))
+}
+
+/// `custom_id` for the request at `index`.
+fn batch_custom_id(index: usize) -> String {
+    format!("request-{}", index)
+}
+
+/// Build a Message Batches body, tagging each request with its index.
+fn build_batch(requests: Vec<BatchRequest>) -> Result<ClaudeBatchCreate> {
+    let requests = requests
+        .into_iter()
+        .enumerate()
+        .map(|(index, request)| {
+            Ok(ClaudeBatchItem {
+                custom_id: batch_custom_id(index),
+                params: build_request(
+                    request.messages,
+                    request.tools,
+                    request.model.as_deref(),
+                    request.options,
+                    None,
+                )?,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(ClaudeBatchCreate { requests })
+}
+
+/// Match batch results (JSONL, in any order) back to request positions.
+fn correlate_batch_results(count: usize, jsonl: &str) -> Vec<Result<LLMResponse>> {
+    let mut results: Vec<Option<Result<LLMResponse>>> = (0..count).map(|_| None).collect();
+
+    for line in jsonl.lines().filter(|l| !l.trim().is_empty()) {
+        let parsed: ClaudeBatchResultLine = match serde_json::from_str(line) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                warn!(error = %e, "Skipping unparseable Claude batch result line");
+                continue;
+            }
+        };
+        let index = parsed
+            .custom_id
+            .strip_prefix("request-")
+            .and_then(|n| n.parse::<usize>().ok())
+            .filter(|&n| n < count);
+        let Some(index) = index else {
+            warn!(custom_id = %parsed.custom_id, "Ignoring Claude batch result with unknown custom_id");
+            continue;
+        };
+        results[index] = Some(match parsed.result {
+            ClaudeBatchResult::Succeeded { message } => checked_response(message),
+            ClaudeBatchResult::Errored { error } => Err(ZeptoError::Provider(format!(
+                "Claude API error: {} - {}",
+                error.error.r#type, error.error.message
+            ))),
+            ClaudeBatchResult::Canceled => Err(ZeptoError::Provider(
+                "Claude batch request was canceled".into(),
+            )),
+            ClaudeBatchResult::Expired => Err(ZeptoError::Provider(
+                "Claude batch request expired before processing".into(),
+            )),
+        });
+    }
+
+    results
+        .into_iter()
+        .enumerate()
+        .map(|(index, result)| {
+            result.unwrap_or_else(|| {
+                Err(ZeptoError::Provider(format!(
+                    "Claude batch returned no result for {}",
+                    batch_custom_id(index)
+                )))
+            })
+        })
+        .collect()
+}
+
+/// The same failure for every request in a batch.
+fn fail_all(count: usize, error: &ZeptoError) -> Vec<Result<LLMResponse>> {
+    (0..count)
+        .map(|_| Err(ZeptoError::Provider(error.to_string())))
+        .collect()
+}
+
 /// Convert ZeptoClaw messages to Claude API format.
 ///
 /// Extracts the system message (if present) and converts all other messages
@@ -804,6 +1006,12 @@ fn convert_tools(tools: Vec<ToolDefinition>) -> Vec<ClaudeTool> {
         .collect()
 }
 
+/// Convert a Claude API response, rejecting one with no text and no tool calls.
+fn checked_response(response: ClaudeResponse) -> Result<LLMResponse> {
+    let stop_reason = response.stop_reason.clone();
+    convert_response(response).reject_empty("claude", stop_reason.as_deref())
+}
+
 /// Convert Claude API response to ZeptoClaw LLMResponse.
 fn convert_response(response: ClaudeResponse) -> LLMResponse {
     let mut content = String::new();
@@ -837,6 +1045,7 @@ fn convert_response(response: ClaudeResponse) -> LLMResponse {
         content,
         tool_calls,
         usage: Some(usage),
+        thoughts: None,
     }
 }
 
@@ -1026,6 +1235,23 @@ mod tests {
         assert_eq!(usage.total_tokens, 15);
     }
 
+    #[test]
+    fn test_checked_response_rejects_empty_reply() {
+        let response = ClaudeResponse {
+            content: vec![],
+            usage: ClaudeUsage {
+                input_tokens: 10,
+                output_tokens: 0,
+            },
+            stop_reason: Some("refusal".to_string()),
+        };
+
+        let err = checked_response(response).unwrap_err();
+        assert!(matches!(err, ZeptoError::Provider(_)));
+        assert!(err.to_string().contains("empty response from claude"));
+        assert!(err.to_string().contains("refusal"));
+    }
+
     #[test]
     fn test_convert_response_with_tool_calls() {
         let response = ClaudeResponse {
@@ -1370,4 +1596,67 @@ mod tests {
             panic!("Expected Blocks content");
         }
     }
+
+    #[test]
+    fn test_batch_request_serialization() {
+        let batch = build_batch(vec![
+            BatchRequest::new(vec![Message::system("Be brief."), Message::user("Hi")]),
+            BatchRequest::new(vec![Message::user("Summarize")])
+                .with_model("claude-haiku-4-5")
+                .with_options(ChatOptions::new().with_max_tokens(256)),
+        ])
+        .unwrap();
+
+        let json = serde_json::to_value(&batch).unwrap();
+        let requests = json["requests"].as_array().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0]["custom_id"], "request-0");
+        assert_eq!(requests[0]["params"]["model"], DEFAULT_MODEL);
+        assert_eq!(requests[0]["params"]["system"], "Be brief.");
+        assert_eq!(requests[0]["params"]["messages"][0]["content"], "Hi");
+        assert!(requests[0]["params"].get("stream").is_none());
+        assert_eq!(requests[1]["custom_id"], "request-1");
+        assert_eq!(requests[1]["params"]["model"], "claude-haiku-4-5");
+        assert_eq!(requests[1]["params"]["max_tokens"], 256);
+    }
+
+    #[test]
+    fn test_correlate_batch_results_by_custom_id() {
+        // Results arrive out of order and one request has no result at all.
+        let jsonl = r#"{"custom_id":"request-2","result":{"type":"errored","error":{"type":"error","error":{"type":"invalid_request_error","message":"bad input"}}}}
+{"custom_id":"request-0","result":{"type":"succeeded","message":{"id":"msg_1","type":"message","role":"assistant","model":"claude","content":[{"type":"text","text":"first"}],"stop_reason":"end_turn","usage":{"input_tokens":5,"output_tokens":2}}}}
+{"custom_id":"request-3","result":{"type":"expired"}}
+{"custom_id":"other-9","result":{"type":"canceled"}}
+"#;
+
+        let results = correlate_batch_results(5, jsonl);
+        assert_eq!(results.len(), 5);
+
+        let first = results[0].as_ref().unwrap();
+        assert_eq!(first.content, "first");
+        assert_eq!(first.usage.as_ref().unwrap().prompt_tokens, 5);
+
+        let missing = results[1].as_ref().unwrap_err().to_string();
+        assert!(missing.contains("no result for request-1"), "{missing}");
+        let errored = results[2].as_ref().unwrap_err().to_string();
+        assert!(errored.contains("bad input"), "{errored}");
+        let expired = results[3].as_ref().unwrap_err().to_string();
+        assert!(expired.contains("expired"), "{expired}");
+        assert!(results[4].is_err());
+    }
+
+    #[test]
+    fn test_build_request_keeps_stop_and_drops_penalties() {
+        let options = ChatOptions::new()
+            .with_stop(vec!["END".to_string()])
+            .with_presence_penalty(0.5)
+            .with_frequency_penalty(0.5);
+        let request =
+            build_request(vec![Message::user("Hi")], vec![], None, options, None).unwrap();
+
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["stop_sequences"], serde_json::json!(["END"]));
+        assert!(json.get("presence_penalty").is_none());
+        assert!(json.get("frequency_penalty").is_none());
+    }
 }
@@ -0,0 +1,280 @@
+//! Redacted debug logging of provider HTTP traffic.
+//!
+//! Every built-in provider logs its outgoing requests (and non-streaming
+//! response bodies) through [`log_request`] / [`log_response`] at `debug`
+//! level. The lines are scrubbed with one process-wide rule set from
+//! `providers.debug_log`:
+//!
+//! - credential headers and query parameters (`authorization`, `x-api-key`,
+//!   Gemini's `key=`, ...) are replaced with `[REDACTED]`;
+//! - prompt and completion text is truncated to `max_body_chars`, or
+//!   replaced with a short hash when `hash_prompts` is set;
+//! - anything the [`LeakDetector`] recognises as a secret is masked.
+//!
+//! Call [`configure`] once after loading the config; until then the
+//! defaults apply. Nothing is formatted unless `debug` is enabled for this
+//! module.
+//!
+//! # Example (config.json)
+//! ```json
+//! {
+//!   "providers": {
+//!     "debug_log": { "max_body_chars": 80, "hash_prompts": true }
+//!   }
+//! }
+//! ```
+
+use std::sync::RwLock;
+
+use once_cell::sync::Lazy;
+use reqwest::RequestBuilder;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use tracing::{debug, Level};
+
+use crate::config::ProviderDebugLogConfig;
+use crate::safety::leak_detector::LeakDetector;
+
+/// JSON keys whose string values (directly or inside arrays) are prompt or
+/// completion text.
+const TEXT_KEYS: &[&str] = &[
+    "arguments",
+    "content",
+    "input",
+    "instructions",
+    "prompt",
+    "system",
+    "text",
+    "thinking",
+];
+
+const REDACTED: &str = "[REDACTED]";
+
+static SETTINGS: Lazy<RwLock<ProviderDebugLogConfig>> =
+    Lazy::new(|| RwLock::new(ProviderDebugLogConfig::default()));
+
+static LEAKS: Lazy<LeakDetector> = Lazy::new(LeakDetector::new);
+
+/// Install the process-wide redaction rules.
+pub fn configure(settings: &ProviderDebugLogConfig) {
+    match SETTINGS.write() {
+        Ok(mut guard) => *guard = settings.clone(),
+        Err(poisoned) => *poisoned.into_inner() = settings.clone(),
+    }
+}
+
+/// Current process-wide redaction rules.
+pub fn settings() -> ProviderDebugLogConfig {
+    match SETTINGS.read() {
+        Ok(guard) => guard.clone(),
+        Err(poisoned) => poisoned.into_inner().clone(),
+    }
+}
+
+/// Log the request `builder` would send, redacted. Call right before `send()`.
+pub fn log_request(provider: &str, builder: &RequestBuilder) {
+    if !tracing::enabled!(Level::DEBUG) {
+        return;
+    }
+    let Some(Ok(request)) = builder.try_clone().map(RequestBuilder::build) else {
+        return;
+    };
+    let headers: Vec<(&str, &str)> = request
+        .headers()
+        .iter()
+        .map(|(name, value)| (name.as_str(), value.to_str().unwrap_or("<binary>")))
+        .collect();
+    let body = request
+        .body()
+        .and_then(|body| body.as_bytes())
+        .map(String::from_utf8_lossy);
+    debug!(
+        provider,
+        "{}",
+        format_request(
+            &settings(),
+            request.method().as_str(),
+            request.url().as_str(),
+            &headers,
+            body.as_deref(),
+        )
+    );
+}
+
+/// Log a response body, redacted.
+pub fn log_response(provider: &str, status: u16, body: &str) {
+    if !tracing::enabled!(Level::DEBUG) {
+        return;
+    }
+    debug!(provider, "{}", format_response(&settings(), status, body));
+}
+
+/// The redacted log line for a request.
+pub fn format_request(
+    config: &ProviderDebugLogConfig,
+    method: &str,
+    url: &str,
+    headers: &[(&str, &str)],
+    body: Option<&str>,
+) -> String {
+    let headers: Vec<String> = headers
+        .iter()
+        .map(|(name, value)| {
+            let value = if is_secret_name(config, name) {
+                REDACTED
+            } else {
+                value
+            };
+            format!("{}: {}", name.to_ascii_lowercase(), value)
+        })
+        .collect();
+    let mut line = format!(
+        "{} {} [{}]",
+        method,
+        redact_url(config, url),
+        headers.join(", ")
+    );
+    if let Some(body) = body {
+        line.push(' ');
+        line.push_str(&scrub_body(config, body, config.hash_prompts));
+    }
+    line
+}
+
+/// The redacted log line for a response.
+pub fn format_response(config: &ProviderDebugLogConfig, status: u16, body: &str) -> String {
+    format!("HTTP {} {}", status, scrub_body(config, body, false))
+}
+
+fn is_secret_name(config: &ProviderDebugLogConfig, name: &str) -> bool {
+    config
+        .redact_headers
+        .iter()
+        .any(|secret| secret.eq_ignore_ascii_case(name))
+}
+
+fn redact_url(config: &ProviderDebugLogConfig, url: &str) -> String {
+    let Some((base, query)) = url.split_once('?') else {
+        return url.to_string();
+    };
+    let pairs: Vec<String> = query
+        .split('&')
+        .map(|pair| match pair.split_once('=') {
+            Some((name, _)) if is_secret_name(config, name) => format!("{}={}", name, REDACTED),
+            _ => pair.to_string(),
+        })
+        .collect();
+    format!("{}?{}", base, pairs.join("&"))
+}
+
+fn scrub_body(config: &ProviderDebugLogConfig, body: &str, hash: bool) -> String {
+    let scrubbed = match serde_json::from_str::<Value>(body) {
+        Ok(mut json) => {
+            scrub_value(config, &mut json, false, hash);
+            json.to_string()
+        }
+        Err(_) => shorten(config, body, hash),
+    };
+    LEAKS.redact(&scrubbed).0
+}
+
+fn scrub_value(config: &ProviderDebugLogConfig, value: &mut Value, is_text: bool, hash: bool) {
+    match value {
+        Value::String(text) if is_text => *text = shorten(config, text, hash),
+        Value::Array(items) => {
+            for item in items {
+                scrub_value(config, item, is_text, hash);
+            }
+        }
+        Value::Object(map) => {
+            for (key, item) in map.iter_mut() {
+                scrub_value(config, item, TEXT_KEYS.contains(&key.as_str()), hash);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn shorten(config: &ProviderDebugLogConfig, text: &str, hash: bool) -> String {
+    let chars = text.chars().count();
+    if hash {
+        let digest = hex::encode(Sha256::digest(text.as_bytes()));
+        return format!("[sha256:{}, {} chars]", &digest[..12], chars);
+    }
+    if chars <= config.max_body_chars {
+        return text.to_string();
+    }
+    if config.max_body_chars == 0 {
+        return format!("[{} chars]", chars);
+    }
+    let kept: String = text.chars().take(config.max_body_chars).collect();
+    format!("{}…[+{} chars]", kept, chars - config.max_body_chars)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn request_body() -> String {
+        json!({
+            "model": "gpt-4o",
+            "messages": [
+                {"role": "system", "content": "You are a helpful assistant for ACME."},
+                {"role": "user", "content": [{"type": "text", "text": "Summarise the Q3 board minutes please"}]}
+            ]
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_request_line_redacts_auth_and_truncates_body() {
+        let config = ProviderDebugLogConfig {
+            max_body_chars: 10,
+            ..Default::default()
+        };
+        let line = format_request(
+            &config,
+            "POST",
+            "https://generativelanguage.googleapis.com/v1beta/models/x:generateContent?key=AIzaSecret&alt=json",
+            &[
+                ("Authorization", "Bearer sk-live-123"),
+                ("content-type", "application/json"),
+            ],
+            Some(&request_body()),
+        );
+
+        assert!(!line.contains("sk-live-123"), "{line}");
+        assert!(!line.contains("AIzaSecret"), "{line}");
+        assert!(line.contains("authorization: [REDACTED]"), "{line}");
+        assert!(line.contains("key=[REDACTED]&alt=json"), "{line}");
+        assert!(line.contains("content-type: application/json"), "{line}");
+        // Prompt text is cut to 10 characters; structure is kept.
+        assert!(line.contains("You are a …[+27 chars]"), "{line}");
+        assert!(line.contains("Summarise …[+27 chars]"), "{line}");
+        assert!(!line.contains("board minutes"), "{line}");
+        assert!(line.contains(r#""role":"user""#), "{line}");
+        assert!(line.contains(r#""model":"gpt-4o""#), "{line}");
+    }
+
+    #[test]
+    fn test_prompts_hashed_and_response_truncated() {
+        let config = ProviderDebugLogConfig {
+            max_body_chars: 0,
+            hash_prompts: true,
+            ..Default::default()
+        };
+        let first = format_request(&config, "POST", "https://x", &[], Some(&request_body()));
+        let second = format_request(&config, "POST", "https://x", &[], Some(&request_body()));
+        assert_eq!(first, second, "hashes are stable for correlation");
+        assert!(first.contains("[sha256:"), "{first}");
+        assert!(!first.contains("ACME"), "{first}");
+
+        let response = json!({"content": [{"type": "text", "text": "The minutes say..."}]});
+        let line = format_response(&config, 200, &response.to_string());
+        assert_eq!(
+            line,
+            r#"HTTP 200 {"content":[{"text":"[18 chars]","type":"text"}]}"#
+        );
+    }
+}
@@ -0,0 +1,412 @@
+//! Ensemble provider for ZeptoClaw
+//!
+//! This module provides an [`EnsembleProvider`] that sends each chat request
+//! to several LLM providers at once and combines their answers with an
+//! [`EnsembleStrategy`]: take the first answer, take the answer most members
+//! agree on, or concatenate every answer with attribution. It is meant for
+//! high-stakes questions where cost matters less than confidence.
+//!
+//! Every member call is bounded by a per-call timeout, so a slow or hung
+//! provider never stalls the ensemble; members that time out or fail are
+//! left out of the result. Token usage is summed over the members whose
+//! answers were received.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use std::time::Duration;
+//! use zeptoclaw::providers::ensemble::{EnsembleProvider, EnsembleStrategy};
+//! use zeptoclaw::providers::claude::ClaudeProvider;
+//! use zeptoclaw::providers::openai::OpenAIProvider;
+//!
+//! let providers: Vec<Box<dyn LLMProvider>> = vec![
+//!     Box::new(ClaudeProvider::new("claude-key")),
+//!     Box::new(OpenAIProvider::new("openai-key")),
+//! ];
+//! let provider = EnsembleProvider::new(providers, EnsembleStrategy::Majority)
+//!     .with_member_models(vec![None, Some("gpt-5".to_string())])
+//!     .with_timeout(Duration::from_secs(30));
+//! ```
+
+use std::fmt;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures::stream::{FuturesUnordered, StreamExt};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::error::{Result, ZeptoError};
+use crate::session::Message;
+
+use super::{ChatOptions, LLMProvider, LLMResponse, ToolDefinition, Usage};
+
+/// Default per-call timeout for each ensemble member.
+pub const DEFAULT_ENSEMBLE_TIMEOUT: Duration = Duration::from_secs(60);
+
+// ============================================================================
+// Ensemble Strategy
+// ============================================================================
+
+/// How the answers of the ensemble members are combined.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EnsembleStrategy {
+    /// Return the first successful answer; the slower calls are cancelled.
+    #[default]
+    FirstToRespond,
+    /// Wait for every member and return the answer most of them gave,
+    /// compared after normalization (case, whitespace, trailing punctuation).
+    /// Ties go to the member listed first.
+    Majority,
+    /// Wait for every member and return all answers, each headed by the name
+    /// of the member that gave it, in member order.
+    Concatenate,
+}
+
+// ============================================================================
+// EnsembleProvider
+// ============================================================================
+
+/// A provider that fans each request out to several providers concurrently.
+///
+/// Tool calls in the combined response come from the chosen answer
+/// (first / majority) or, for [`EnsembleStrategy::Concatenate`], from the
+/// first member in order that returned any.
+pub struct EnsembleProvider {
+    /// Members with their optional model override.
+    members: Vec<(Box<dyn LLMProvider>, Option<String>)>,
+    strategy: EnsembleStrategy,
+    timeout: Duration,
+    /// Pre-computed composite name.
+    composite_name: String,
+}
+
+impl fmt::Debug for EnsembleProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<&str> = self.members.iter().map(|(p, _)| p.name()).collect();
+        f.debug_struct("EnsembleProvider")
+            .field("providers", &names)
+            .field("strategy", &self.strategy)
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+impl EnsembleProvider {
+    /// Create a new ensemble provider.
+    ///
+    /// # Arguments
+    /// * `providers` - The members to query (order breaks majority ties and
+    ///   orders concatenated answers).
+    /// * `strategy` - How the answers are combined.
+    ///
+    /// # Panics
+    /// Panics if `providers` is empty.
+    pub fn new(providers: Vec<Box<dyn LLMProvider>>, strategy: EnsembleStrategy) -> Self {
+        assert!(
+            !providers.is_empty(),
+            "EnsembleProvider requires at least one provider"
+        );
+
+        let names: Vec<&str> = providers.iter().map(|p| p.name()).collect();
+        let composite_name = format!("ensemble({})", names.join(", "));
+
+        Self {
+            members: providers.into_iter().map(|p| (p, None)).collect(),
+            strategy,
+            timeout: DEFAULT_ENSEMBLE_TIMEOUT,
+            composite_name,
+        }
+    }
+
+    /// Set per-member model overrides, in member order.
+    ///
+    /// A member with `Some(model)` uses it instead of the request's model,
+    /// since one model name rarely fits every provider. Missing entries
+    /// leave the member on the request's model.
+    pub fn with_member_models(mut self, models: Vec<Option<String>>) -> Self {
+        for ((_, slot), model) in self.members.iter_mut().zip(models) {
+            *slot = model;
+        }
+        self
+    }
+
+    /// Set the per-call timeout applied to each member.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Normalize an answer for majority voting.
+    fn normalize(content: &str) -> String {
+        content
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .trim_end_matches(|c: char| c.is_ascii_punctuation())
+            .to_lowercase()
+    }
+
+    /// Combine the members' answers, given as `(member index, response)`.
+    fn combine(&self, mut answers: Vec<(usize, LLMResponse)>) -> LLMResponse {
+        answers.sort_by_key(|(i, _)| *i);
+        let usage = answers.iter().filter_map(|(_, r)| r.usage.as_ref()).fold(
+            None,
+            |total: Option<Usage>, u| {
+                let (prompt, completion) = total
+                    .map(|t| (t.prompt_tokens, t.completion_tokens))
+                    .unwrap_or_default();
+                Some(Usage::new(
+                    prompt.saturating_add(u.prompt_tokens),
+                    completion.saturating_add(u.completion_tokens),
+                ))
+            },
+        );
+
+        let mut combined = match self.strategy {
+            EnsembleStrategy::FirstToRespond | EnsembleStrategy::Majority => {
+                let keys: Vec<String> = answers
+                    .iter()
+                    .map(|(_, r)| Self::normalize(&r.content))
+                    .collect();
+                // Most votes wins; `max_by_key` keeps the last maximum, so
+                // scan in reverse to let the earliest member win ties.
+                let winner = (0..answers.len())
+                    .rev()
+                    .max_by_key(|&i| keys.iter().filter(|k| **k == keys[i]).count())
+                    .unwrap_or(0);
+                answers.swap_remove(winner).1
+            }
+            EnsembleStrategy::Concatenate => {
+                let content = answers
+                    .iter()
+                    .map(|(i, r)| format!("[{}]\n{}", self.members[*i].0.name(), r.content.trim()))
+                    .collect::<Vec<_>>()
+                    .join("\n\n");
+                let tool_calls = answers
+                    .into_iter()
+                    .map(|(_, r)| r.tool_calls)
+                    .find(|calls| !calls.is_empty())
+                    .unwrap_or_default();
+                LLMResponse::with_tools(&content, tool_calls)
+            }
+        };
+        combined.usage = usage;
+        combined
+    }
+}
+
+#[async_trait]
+impl LLMProvider for EnsembleProvider {
+    fn name(&self) -> &str {
+        &self.composite_name
+    }
+
+    fn default_model(&self) -> &str {
+        self.members[0].0.default_model()
+    }
+
+    async fn chat(
+        &self,
+        messages: Vec<Message>,
+        tools: Vec<ToolDefinition>,
+        model: Option<&str>,
+        options: ChatOptions,
+    ) -> Result<LLMResponse> {
+        let mut pending: FuturesUnordered<_> = self
+            .members
+            .iter()
+            .enumerate()
+            .map(|(i, (provider, member_model))| {
+                let call = provider.chat(
+                    messages.clone(),
+                    tools.clone(),
+                    member_model.as_deref().or(model),
+                    options.clone(),
+                );
+                async move { (i, tokio::time::timeout(self.timeout, call).await) }
+            })
+            .collect();
+
+        let mut answers = Vec::new();
+        let mut last_err = None;
+        while let Some((i, outcome)) = pending.next().await {
+            let name = self.members[i].0.name();
+            match outcome {
+                Ok(Ok(response)) => {
+                    debug!(provider = name, "Ensemble: member answered");
+                    answers.push((i, response));
+                    if self.strategy == EnsembleStrategy::FirstToRespond {
+                        break;
+                    }
+                }
+                Ok(Err(err)) => {
+                    warn!(provider = name, error = %err, "Ensemble: member failed");
+                    last_err = Some(err);
+                }
+                Err(_) => {
+                    warn!(
+                        provider = name,
+                        timeout_ms = self.timeout.as_millis() as u64,
+                        "Ensemble: member timed out"
+                    );
+                }
+            }
+        }
+
+        if answers.is_empty() {
+            return Err(last_err.unwrap_or_else(|| {
+                ZeptoError::Provider("All ensemble providers failed or timed out".into())
+            }));
+        }
+        Ok(self.combine(answers))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ProviderError;
+
+    /// A provider that answers after a fixed delay.
+    struct DelayedProvider {
+        name: &'static str,
+        delay_ms: u64,
+        answer: &'static str,
+        tokens: u32,
+    }
+
+    impl DelayedProvider {
+        fn boxed(name: &'static str, delay_ms: u64, answer: &'static str) -> Box<dyn LLMProvider> {
+            Box::new(Self {
+                name,
+                delay_ms,
+                answer,
+                tokens: 10,
+            })
+        }
+    }
+
+    #[async_trait]
+    impl LLMProvider for DelayedProvider {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn default_model(&self) -> &str {
+            "delayed-model"
+        }
+
+        async fn chat(
+            &self,
+            _messages: Vec<Message>,
+            _tools: Vec<ToolDefinition>,
+            _model: Option<&str>,
+            _options: ChatOptions,
+        ) -> Result<LLMResponse> {
+            tokio::time::sleep(Duration::from_millis(self.delay_ms)).await;
+            Ok(LLMResponse::text(self.answer).with_usage(Usage::new(self.tokens, self.tokens)))
+        }
+    }
+
+    /// A provider that always fails.
+    struct FailProvider;
+
+    #[async_trait]
+    impl LLMProvider for FailProvider {
+        fn name(&self) -> &str {
+            "broken"
+        }
+
+        fn default_model(&self) -> &str {
+            "broken-model"
+        }
+
+        async fn chat(
+            &self,
+            _messages: Vec<Message>,
+            _tools: Vec<ToolDefinition>,
+            _model: Option<&str>,
+            _options: ChatOptions,
+        ) -> Result<LLMResponse> {
+            Err(ZeptoError::from(ProviderError::ServerError(
+                "boom".to_string(),
+            )))
+        }
+    }
+
+    async fn ask(provider: &EnsembleProvider) -> Result<LLMResponse> {
+        provider
+            .chat(
+                vec![Message::user("2+2?")],
+                vec![],
+                None,
+                ChatOptions::new(),
+            )
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_first_to_respond_returns_fastest_answer() {
+        let provider = EnsembleProvider::new(
+            vec![
+                DelayedProvider::boxed("slow", 2_000, "slow answer"),
+                Box::new(FailProvider),
+                DelayedProvider::boxed("fast", 50, "fast answer"),
+            ],
+            EnsembleStrategy::FirstToRespond,
+        );
+        let started = std::time::Instant::now();
+
+        let response = ask(&provider).await.unwrap();
+        assert_eq!(response.content, "fast answer");
+        assert_eq!(response.usage.unwrap().total_tokens, 20);
+        // The slow member was not waited for.
+        assert!(started.elapsed() < Duration::from_millis(1_000));
+        assert_eq!(provider.name(), "ensemble(slow, broken, fast)");
+    }
+
+    #[tokio::test]
+    async fn test_concatenate_attributes_answers_and_sums_usage() {
+        let provider = EnsembleProvider::new(
+            vec![
+                DelayedProvider::boxed("claude", 50, "Four."),
+                DelayedProvider::boxed("gpt", 10, "4"),
+                DelayedProvider::boxed("hung", 10_000, "too late"),
+            ],
+            EnsembleStrategy::Concatenate,
+        )
+        .with_timeout(Duration::from_millis(200));
+
+        let response = ask(&provider).await.unwrap();
+        // Member order, not arrival order; the hung member is dropped.
+        assert_eq!(response.content, "[claude]\nFour.\n\n[gpt]\n4");
+        let usage = response.usage.unwrap();
+        assert_eq!(usage.prompt_tokens, 20);
+        assert_eq!(usage.total_tokens, 40);
+    }
+
+    #[tokio::test]
+    async fn test_majority_and_all_failing() {
+        let provider = EnsembleProvider::new(
+            vec![
+                DelayedProvider::boxed("a", 10, "Paris"),
+                DelayedProvider::boxed("b", 20, "Lyon"),
+                DelayedProvider::boxed("c", 30, "  paris. "),
+            ],
+            EnsembleStrategy::Majority,
+        );
+        assert_eq!(ask(&provider).await.unwrap().content, "Paris");
+
+        let provider = EnsembleProvider::new(
+            vec![
+                Box::new(FailProvider),
+                DelayedProvider::boxed("hung", 10_000, "too late"),
+            ],
+            EnsembleStrategy::Majority,
+        )
+        .with_timeout(Duration::from_millis(100));
+        assert!(ask(&provider).await.is_err());
+    }
+}
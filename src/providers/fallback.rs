@@ -217,6 +217,10 @@ impl LLMProvider for FallbackProvider {
         self.primary.default_model()
     }
 
+    fn supports_streaming(&self) -> bool {
+        self.primary.supports_streaming()
+    }
+
     async fn chat(
         &self,
         messages: Vec<Message>,
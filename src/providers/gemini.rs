@@ -4,6 +4,9 @@
 //!
 //! Thinking model support: Gemini 2.5 models return parts tagged `thought: true`.
 //! This provider filters those out and only returns the final non-thought text.
+//! A thinking budget (`thinkingConfig.thinkingBudget`) can be set with
+//! [`GeminiProvider::with_thinking_budget`], and [`GeminiProvider::with_thoughts`]
+//! asks for the summarized thought trace, returned in `LLMResponse::thoughts`.
 
 use async_trait::async_trait;
 use reqwest::Client;
@@ -14,6 +17,7 @@ use tracing::{debug, warn};
 use crate::error::{Result, ZeptoError};
 use crate::session::{ContentPart, ImageSource, Message, Role};
 
+use super::debug_log;
 use super::{parse_provider_error, ChatOptions, LLMProvider, LLMResponse, ToolDefinition, Usage};
 
 /// Gemini v1beta REST API base.
@@ -148,6 +152,10 @@ pub struct GeminiProvider {
     auth: GeminiAuth,
     model: String,
     client: Client,
+    /// `thinkingConfig.thinkingBudget`; `None` keeps the model default.
+    thinking_budget: Option<i32>,
+    /// Request and return the summarized thought trace.
+    include_thoughts: bool,
 }
 
 impl std::fmt::Debug for GeminiProvider {
@@ -155,6 +163,8 @@ impl std::fmt::Debug for GeminiProvider {
         f.debug_struct("GeminiProvider")
             .field("auth", &self.auth)
             .field("model", &self.model)
+            .field("thinking_budget", &self.thinking_budget)
+            .field("include_thoughts", &self.include_thoughts)
             .finish()
     }
 }
@@ -166,6 +176,8 @@ impl GeminiProvider {
             auth: GeminiAuth::ApiKey(api_key.to_string()),
             model: model.to_string(),
             client: Self::build_client(),
+            thinking_budget: None,
+            include_thoughts: false,
         }
     }
 
@@ -175,6 +187,8 @@ impl GeminiProvider {
             auth: GeminiAuth::BearerToken(bearer_token.to_string()),
             model: model.to_string(),
             client: Self::build_client(),
+            thinking_budget: None,
+            include_thoughts: false,
         }
     }
 
@@ -202,14 +216,27 @@ impl GeminiProvider {
             auth,
             model: model.to_string(),
             client: Self::build_client(),
+            thinking_budget: None,
+            include_thoughts: false,
         })
     }
 
+    /// Set the thinking budget in tokens (`0` disables thinking, `-1` lets
+    /// the model decide). `None` leaves the model default.
+    pub fn with_thinking_budget(mut self, budget: Option<i32>) -> Self {
+        self.thinking_budget = budget;
+        self
+    }
+
+    /// Ask for the summarized thought trace and return it separately in
+    /// `LLMResponse::thoughts`. Thoughts never reach `content` either way.
+    pub fn with_thoughts(mut self, include: bool) -> Self {
+        self.include_thoughts = include;
+        self
+    }
+
     fn build_client() -> Client {
-        Client::builder()
-            .timeout(Duration::from_secs(120))
-            .build()
-            .expect("failed to build HTTP client")
+        crate::utils::http::client(Duration::from_secs(120))
     }
 
     /// Build a minimal Gemini `generateContent` request body for a single
@@ -292,6 +319,25 @@ impl GeminiProvider {
         if let Some(top_p) = options.top_p {
             generation_config["topP"] = json!(top_p);
         }
+        if let Some(stop) = options.stop.as_ref().filter(|s| !s.is_empty()) {
+            generation_config["stopSequences"] = json!(stop);
+        }
+        if let Some(penalty) = options.presence_penalty {
+            generation_config["presencePenalty"] = json!(penalty);
+        }
+        if let Some(penalty) = options.frequency_penalty {
+            generation_config["frequencyPenalty"] = json!(penalty);
+        }
+        if self.thinking_budget.is_some() || self.include_thoughts {
+            let mut thinking = json!({});
+            if let Some(budget) = self.thinking_budget {
+                thinking["thinkingBudget"] = json!(budget);
+            }
+            if self.include_thoughts {
+                thinking["includeThoughts"] = json!(true);
+            }
+            generation_config["thinkingConfig"] = thinking;
+        }
 
         let mut body = json!({
             "contents": contents,
@@ -335,6 +381,42 @@ impl GeminiProvider {
         }
     }
 
+    /// Extract the thought trace (parts tagged `"thought": true`), if any.
+    ///
+    /// Only present when the request asked for thoughts; [`Self::extract_text`]
+    /// keeps them out of the answer.
+    pub fn extract_thoughts(response: &Value) -> Option<String> {
+        let parts = response["candidates"][0]["content"]["parts"].as_array()?;
+        let thoughts: Vec<&str> = parts
+            .iter()
+            .filter(|p| p["thought"].as_bool().unwrap_or(false))
+            .filter_map(|p| p["text"].as_str())
+            .collect();
+        (!thoughts.is_empty()).then(|| thoughts.join(""))
+    }
+
+    /// Finish reason of the first candidate, or the prompt block reason when
+    /// the response carries no candidates.
+    pub fn extract_finish_reason(response: &Value) -> Option<String> {
+        response["candidates"][0]["finishReason"]
+            .as_str()
+            .or_else(|| response["promptFeedback"]["blockReason"].as_str())
+            .map(str::to_string)
+    }
+
+    /// Convert a successful `generateContent` body into an [`LLMResponse`].
+    ///
+    /// Safety blocks and malformed candidates carry no text; they become a
+    /// provider error naming `provider` and the finish reason.
+    pub fn response_from_json(provider: &str, response: &Value) -> Result<LLMResponse> {
+        let content = Self::extract_text(response).unwrap_or_default();
+        let mut llm_response = LLMResponse::text(&content);
+        if let Some(u) = Self::extract_usage(response) {
+            llm_response = llm_response.with_usage(u);
+        }
+        llm_response.reject_empty(provider, Self::extract_finish_reason(response).as_deref())
+    }
+
     /// Parse token usage from a Gemini response if available.
     pub fn extract_usage(response: &Value) -> Option<Usage> {
         let meta = response.get("usageMetadata")?;
@@ -380,29 +462,32 @@ impl LLMProvider for GeminiProvider {
             .json(&body);
 
         let request = self.apply_auth(request);
+        debug_log::log_request("gemini", &request);
 
         let response = request
             .send()
             .await
             .map_err(|e| ZeptoError::Provider(format!("Gemini request failed: {}", e)))?;
 
+        let status = response.status().as_u16();
         if response.status().is_success() {
-            let json: Value = response.json().await.map_err(|e| {
+            let body = response.text().await.map_err(|e| {
+                ZeptoError::Provider(format!("Failed to read Gemini response: {}", e))
+            })?;
+            debug_log::log_response("gemini", status, &body);
+            let json: Value = serde_json::from_str(&body).map_err(|e| {
                 ZeptoError::Provider(format!("Failed to parse Gemini response: {}", e))
             })?;
 
-            let content = Self::extract_text(&json).unwrap_or_default();
-            let usage = Self::extract_usage(&json);
-
-            let mut llm_response = LLMResponse::text(&content);
-            if let Some(u) = usage {
-                llm_response = llm_response.with_usage(u);
+            let mut llm_response = Self::response_from_json("gemini", &json)?;
+            if self.include_thoughts {
+                llm_response.thoughts = Self::extract_thoughts(&json);
             }
             return Ok(llm_response);
         }
 
-        let status = response.status().as_u16();
         let error_text = response.text().await.unwrap_or_default();
+        debug_log::log_response("gemini", status, &error_text);
 
         // Try to extract a useful message from the Gemini error body.
         let body_msg = serde_json::from_str::<Value>(&error_text)
@@ -537,6 +622,31 @@ mod tests {
         assert!(text.is_none());
     }
 
+    #[test]
+    fn test_response_from_json_rejects_empty_candidate() {
+        let response = serde_json::json!({
+            "candidates": [{ "finishReason": "SAFETY" }]
+        });
+        let err = GeminiProvider::response_from_json("gemini", &response).unwrap_err();
+        assert!(matches!(err, ZeptoError::Provider(_)));
+        assert!(err.to_string().contains("empty response from gemini"));
+        assert!(err.to_string().contains("SAFETY"));
+
+        let blocked = serde_json::json!({ "promptFeedback": { "blockReason": "OTHER" } });
+        let err = GeminiProvider::response_from_json("gemini", &blocked).unwrap_err();
+        assert!(err.to_string().contains("OTHER"));
+
+        let ok = serde_json::json!({
+            "candidates": [{ "content": { "parts": [{ "text": "Hi" }] } }]
+        });
+        assert_eq!(
+            GeminiProvider::response_from_json("gemini", &ok)
+                .unwrap()
+                .content,
+            "Hi"
+        );
+    }
+
     #[test]
     fn test_build_request_body_sets_model_and_contents() {
         let provider = GeminiProvider::new_with_key("test-key", "gemini-2.0-flash");
@@ -610,6 +720,79 @@ mod tests {
         assert!(usage.is_none());
     }
 
+    #[test]
+    fn test_build_messages_body_includes_stop_and_penalties() {
+        let provider = GeminiProvider::new_with_key("key", DEFAULT_GEMINI_MODEL);
+        let options = ChatOptions::new()
+            .with_stop(vec!["END".to_string()])
+            .with_presence_penalty(0.5)
+            .with_frequency_penalty(-0.25);
+        let body = provider.build_messages_body(&[Message::user("Hi")], &options);
+
+        let gen_config = &body["generationConfig"];
+        assert_eq!(gen_config["stopSequences"], serde_json::json!(["END"]));
+        assert_eq!(gen_config["presencePenalty"], 0.5);
+        assert_eq!(gen_config["frequencyPenalty"], -0.25);
+
+        let body = provider.build_messages_body(&[Message::user("Hi")], &ChatOptions::default());
+        assert!(body["generationConfig"].get("stopSequences").is_none());
+        assert!(body["generationConfig"].get("presencePenalty").is_none());
+    }
+
+    #[test]
+    fn test_build_messages_body_includes_thinking_config() {
+        let provider = GeminiProvider::new_with_key("key", "gemini-2.5-pro");
+        let body = provider.build_messages_body(&[Message::user("Hi")], &ChatOptions::default());
+        assert!(body["generationConfig"].get("thinkingConfig").is_none());
+
+        let provider = provider.with_thinking_budget(Some(1024));
+        let body = provider.build_messages_body(&[Message::user("Hi")], &ChatOptions::default());
+        assert_eq!(
+            body["generationConfig"]["thinkingConfig"],
+            serde_json::json!({ "thinkingBudget": 1024 })
+        );
+
+        let provider = provider.with_thinking_budget(Some(0)).with_thoughts(true);
+        let body = provider.build_messages_body(&[Message::user("Hi")], &ChatOptions::default());
+        assert_eq!(
+            body["generationConfig"]["thinkingConfig"],
+            serde_json::json!({ "thinkingBudget": 0, "includeThoughts": true })
+        );
+    }
+
+    #[test]
+    fn test_extract_thoughts_separately_from_answer() {
+        let response = serde_json::json!({
+            "candidates": [{
+                "content": {
+                    "parts": [
+                        { "text": "Compare the two numbers. ", "thought": true },
+                        { "text": "9.11 < 9.9.", "thought": true },
+                        { "text": "9.9 is larger." }
+                    ]
+                }
+            }]
+        });
+        assert_eq!(
+            GeminiProvider::extract_text(&response).as_deref(),
+            Some("9.9 is larger.")
+        );
+        assert_eq!(
+            GeminiProvider::extract_thoughts(&response).as_deref(),
+            Some("Compare the two numbers. 9.11 < 9.9.")
+        );
+
+        // By default the trace is dropped from the response.
+        let llm_response = GeminiProvider::response_from_json("gemini", &response).unwrap();
+        assert_eq!(llm_response.content, "9.9 is larger.");
+        assert!(llm_response.thoughts.is_none());
+
+        let plain = serde_json::json!({
+            "candidates": [{ "content": { "parts": [{ "text": "Hi" }] } }]
+        });
+        assert!(GeminiProvider::extract_thoughts(&plain).is_none());
+    }
+
     #[test]
     fn test_build_messages_body_filters_system_role() {
         let provider = GeminiProvider::new_with_key("key", DEFAULT_GEMINI_MODEL);
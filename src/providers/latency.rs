@@ -0,0 +1,384 @@
+//! Provider response-time tracking.
+//!
+//! [`LatencyProvider`] times every `chat` / `chat_stream` call of the
+//! provider it wraps (until the full response, or the stream's `Done` event)
+//! and records it in a [`LatencyTracker`], which keeps a rolling window of
+//! recent samples per provider and reports p50 / p95.
+//!
+//! With a health registry attached and `health.provider_slow_p95_ms` set, a
+//! provider whose p95 rises above the threshold is reported as a `Degraded`
+//! `provider_latency:<name>` check (with a warning log), and back to `Ok` once
+//! it recovers. Only successful calls are timed; failures are already
+//! covered by retries, cooldowns and fallback.
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::time::Duration;
+//! use zeptoclaw::providers::latency::LatencyTracker;
+//!
+//! let tracker = LatencyTracker::new();
+//! for ms in [100, 200, 300, 400] {
+//!     tracker.record("anthropic", Duration::from_millis(ms));
+//! }
+//! let stats = tracker.stats("anthropic").unwrap();
+//! assert_eq!(stats.p50_ms, 200);
+//! assert_eq!(stats.p95_ms, 400);
+//! ```
+
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use once_cell::sync::Lazy;
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+
+use crate::error::Result;
+use crate::health::{HealthCheck, HealthRegistry, HealthStatus};
+use crate::session::Message;
+
+use super::{BatchRequest, ChatOptions, LLMProvider, LLMResponse, StreamEvent, ToolDefinition};
+
+/// Samples kept per provider.
+pub const LATENCY_WINDOW_SIZE: usize = 100;
+
+/// Samples needed before a provider can be reported as slow.
+pub const MIN_SAMPLES_FOR_STATUS: usize = 10;
+
+static GLOBAL: Lazy<Arc<LatencyTracker>> = Lazy::new(|| Arc::new(LatencyTracker::new()));
+
+/// Rolling window of the most recent response times, in milliseconds.
+#[derive(Debug, Clone)]
+pub struct LatencyWindow {
+    samples: VecDeque<u64>,
+    capacity: usize,
+}
+
+impl LatencyWindow {
+    /// Create a window holding at most `capacity` samples.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            samples: VecDeque::with_capacity(capacity),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// Add a sample, evicting the oldest one when full.
+    pub fn record(&mut self, ms: u64) {
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(ms);
+    }
+
+    /// Number of samples in the window.
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Whether the window has no samples.
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Nearest-rank percentile (`0.0..=100.0`) of the window, or `None` when
+    /// empty.
+    pub fn percentile(&self, p: f64) -> Option<u64> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        let mut sorted: Vec<u64> = self.samples.iter().copied().collect();
+        sorted.sort_unstable();
+        let rank = ((p.clamp(0.0, 100.0) / 100.0) * sorted.len() as f64).ceil() as usize;
+        Some(sorted[rank.saturating_sub(1).min(sorted.len() - 1)])
+    }
+}
+
+/// Latency summary of one provider.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LatencyStats {
+    /// Samples in the window.
+    pub samples: usize,
+    /// Median response time.
+    pub p50_ms: u64,
+    /// 95th percentile response time.
+    pub p95_ms: u64,
+}
+
+/// Health status for a provider's latency: `Degraded` when its p95 exceeds
+/// `slow_p95_ms` over at least [`MIN_SAMPLES_FOR_STATUS`] samples. A
+/// threshold of `0` disables the check.
+pub fn latency_status(stats: &LatencyStats, slow_p95_ms: u64) -> HealthStatus {
+    if slow_p95_ms > 0 && stats.samples >= MIN_SAMPLES_FOR_STATUS && stats.p95_ms > slow_p95_ms {
+        HealthStatus::Degraded
+    } else {
+        HealthStatus::Ok
+    }
+}
+
+/// Per-provider rolling latency windows.
+#[derive(Default)]
+pub struct LatencyTracker {
+    windows: Mutex<HashMap<String, LatencyWindow>>,
+    health: Mutex<Option<HealthRegistry>>,
+    slow_p95_ms: AtomicU64,
+    /// Providers currently reported as slow, to log only on transitions.
+    slow: Mutex<HashSet<String>>,
+}
+
+impl LatencyTracker {
+    /// Create an empty tracker.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The tracker shared by every [`LatencyProvider`] built from config.
+    pub fn global() -> Arc<LatencyTracker> {
+        Arc::clone(&GLOBAL)
+    }
+
+    /// Report slow providers to `registry` once their p95 exceeds
+    /// `slow_p95_ms` (`0` = never).
+    pub fn attach_health(&self, registry: HealthRegistry, slow_p95_ms: u64) {
+        self.slow_p95_ms.store(slow_p95_ms, Ordering::Relaxed);
+        *self.health.lock().unwrap_or_else(|e| e.into_inner()) = Some(registry);
+    }
+
+    /// Record one response time for `provider`.
+    pub fn record(&self, provider: &str, elapsed: Duration) {
+        let stats = {
+            let mut windows = self.windows.lock().unwrap_or_else(|e| e.into_inner());
+            let window = windows
+                .entry(provider.to_string())
+                .or_insert_with(|| LatencyWindow::new(LATENCY_WINDOW_SIZE));
+            window.record(elapsed.as_millis() as u64);
+            Self::summarize(window)
+        };
+        if let Some(stats) = stats {
+            self.update_health(provider, &stats);
+        }
+    }
+
+    /// Latency summary of `provider`, if it has samples.
+    pub fn stats(&self, provider: &str) -> Option<LatencyStats> {
+        let windows = self.windows.lock().unwrap_or_else(|e| e.into_inner());
+        windows.get(provider).and_then(Self::summarize)
+    }
+
+    /// Latency summaries of every provider with samples, by name.
+    pub fn snapshot(&self) -> BTreeMap<String, LatencyStats> {
+        let windows = self.windows.lock().unwrap_or_else(|e| e.into_inner());
+        windows
+            .iter()
+            .filter_map(|(name, window)| Some((name.clone(), Self::summarize(window)?)))
+            .collect()
+    }
+
+    fn summarize(window: &LatencyWindow) -> Option<LatencyStats> {
+        Some(LatencyStats {
+            samples: window.len(),
+            p50_ms: window.percentile(50.0)?,
+            p95_ms: window.percentile(95.0)?,
+        })
+    }
+
+    fn update_health(&self, provider: &str, stats: &LatencyStats) {
+        let threshold = self.slow_p95_ms.load(Ordering::Relaxed);
+        let health = self.health.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(registry) = health.as_ref() else {
+            return;
+        };
+        let status = latency_status(stats, threshold);
+        let is_slow = status == HealthStatus::Degraded;
+        let was_slow = {
+            let mut slow = self.slow.lock().unwrap_or_else(|e| e.into_inner());
+            if is_slow {
+                !slow.insert(provider.to_string())
+            } else {
+                slow.remove(provider)
+            }
+        };
+        if !is_slow && !was_slow {
+            return;
+        }
+        match (was_slow, is_slow) {
+            (false, true) => warn!(
+                provider = provider,
+                p95_ms = stats.p95_ms,
+                threshold_ms = threshold,
+                "Provider is responding slowly"
+            ),
+            (true, false) => info!(
+                provider = provider,
+                p95_ms = stats.p95_ms,
+                "Provider response times recovered"
+            ),
+            _ => {}
+        }
+        registry.register(HealthCheck {
+            name: format!("provider_latency:{}", provider),
+            status,
+            message: Some(format!(
+                "p50 {}ms, p95 {}ms over {} calls (threshold {}ms)",
+                stats.p50_ms, stats.p95_ms, stats.samples, threshold
+            )),
+            ..Default::default()
+        });
+    }
+}
+
+/// Provider wrapper that records response times in a [`LatencyTracker`].
+pub struct LatencyProvider {
+    inner: Box<dyn LLMProvider>,
+    name: String,
+    tracker: Arc<LatencyTracker>,
+}
+
+impl LatencyProvider {
+    /// Time `inner` under `name`, recording into the global tracker.
+    pub fn new(inner: Box<dyn LLMProvider>, name: &str) -> Self {
+        Self {
+            inner,
+            name: name.to_string(),
+            tracker: LatencyTracker::global(),
+        }
+    }
+
+    /// Record into `tracker` instead of the global one.
+    pub fn with_tracker(mut self, tracker: Arc<LatencyTracker>) -> Self {
+        self.tracker = tracker;
+        self
+    }
+}
+
+#[async_trait]
+impl LLMProvider for LatencyProvider {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn default_model(&self) -> &str {
+        self.inner.default_model()
+    }
+
+    fn supports_streaming(&self) -> bool {
+        self.inner.supports_streaming()
+    }
+
+    async fn chat(
+        &self,
+        messages: Vec<Message>,
+        tools: Vec<ToolDefinition>,
+        model: Option<&str>,
+        options: ChatOptions,
+    ) -> Result<LLMResponse> {
+        let start = Instant::now();
+        let response = self.inner.chat(messages, tools, model, options).await?;
+        self.tracker.record(&self.name, start.elapsed());
+        Ok(response)
+    }
+
+    async fn chat_stream(
+        &self,
+        messages: Vec<Message>,
+        tools: Vec<ToolDefinition>,
+        model: Option<&str>,
+        options: ChatOptions,
+    ) -> Result<mpsc::Receiver<StreamEvent>> {
+        let start = Instant::now();
+        let mut inner_rx = self
+            .inner
+            .chat_stream(messages, tools, model, options)
+            .await?;
+
+        // Forward events untouched and time the stream until `Done`.
+        let (tx, rx) = mpsc::channel(32);
+        let tracker = Arc::clone(&self.tracker);
+        let name = self.name.clone();
+        tokio::spawn(async move {
+            while let Some(event) = inner_rx.recv().await {
+                if matches!(event, StreamEvent::Done { .. }) {
+                    tracker.record(&name, start.elapsed());
+                }
+                if tx.send(event).await.is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(rx)
+    }
+
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        self.inner.embed(texts).await
+    }
+
+    async fn batch_chat(&self, requests: Vec<BatchRequest>) -> Vec<Result<LLMResponse>> {
+        self.inner.batch_chat(requests).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rolling_percentiles() {
+        let mut window = LatencyWindow::new(20);
+        assert_eq!(window.percentile(50.0), None);
+
+        for ms in 1..=20 {
+            window.record(ms * 10);
+        }
+        assert_eq!(window.percentile(50.0), Some(100));
+        assert_eq!(window.percentile(95.0), Some(190));
+        assert_eq!(window.percentile(100.0), Some(200));
+
+        // Old samples roll out of the window.
+        for _ in 0..20 {
+            window.record(5);
+        }
+        assert_eq!(window.len(), 20);
+        assert_eq!(window.percentile(95.0), Some(5));
+    }
+
+    #[test]
+    fn test_slow_p95_marks_provider_degraded() {
+        let registry = HealthRegistry::new();
+        let tracker = LatencyTracker::new();
+        tracker.attach_health(registry.clone(), 1_000);
+        let status = |registry: &HealthRegistry| {
+            registry
+                .all_checks()
+                .into_iter()
+                .find(|c| c.name == "provider_latency:openai")
+                .map(|c| c.status)
+        };
+
+        // Too few samples to judge, and nothing reported while healthy.
+        for _ in 0..MIN_SAMPLES_FOR_STATUS - 1 {
+            tracker.record("openai", Duration::from_millis(3_000));
+        }
+        assert_eq!(status(&registry), None);
+
+        tracker.record("openai", Duration::from_millis(3_000));
+        assert_eq!(status(&registry), Some(HealthStatus::Degraded));
+        assert!(registry.is_ready());
+
+        for _ in 0..LATENCY_WINDOW_SIZE {
+            tracker.record("openai", Duration::from_millis(200));
+        }
+        assert_eq!(status(&registry), Some(HealthStatus::Ok));
+        assert_eq!(tracker.stats("openai").unwrap().p95_ms, 200);
+
+        // A zero threshold disables the check.
+        let stats = LatencyStats {
+            samples: 50,
+            p50_ms: 9_000,
+            p95_ms: 9_000,
+        };
+        assert_eq!(latency_status(&stats, 0), HealthStatus::Ok);
+    }
+}
@@ -23,13 +23,18 @@
 
 pub mod claude;
 pub mod cooldown;
+pub mod debug_log;
+pub mod ensemble;
 pub mod error_classifier;
 pub mod fallback;
 pub mod gemini;
+pub mod latency;
 pub mod openai;
 pub mod plugin;
+pub mod prompt_log;
 pub mod quota;
 mod registry;
+pub mod replay;
 pub mod retry;
 pub mod rotation;
 pub mod structured;
@@ -63,13 +68,17 @@ use crate::error::ProviderError;
 
 pub use claude::ClaudeProvider;
 pub use cooldown::{CooldownTracker, FailoverReason};
+pub use ensemble::{EnsembleProvider, EnsembleStrategy};
 pub use error_classifier::classify_error_message;
 pub use fallback::FallbackProvider;
 pub use gemini::GeminiProvider;
+pub use latency::{LatencyProvider, LatencyTracker};
 pub use openai::OpenAIProvider;
 pub use plugin::ProviderPlugin;
+pub use prompt_log::PromptLogger;
 pub use quota::{
-    QuotaAction, QuotaCheckResult, QuotaConfig, QuotaPeriod, QuotaProvider, QuotaStore,
+    QuotaAction, QuotaCheckResult, QuotaConfig, QuotaEvent, QuotaLevel, QuotaNotifier, QuotaPeriod,
+    QuotaProvider, QuotaStore,
 };
 pub use registry::{
     configured_provider_models, configured_provider_names, configured_unsupported_provider_names,
@@ -77,11 +86,13 @@ pub use registry::{
     resolve_runtime_provider, resolve_runtime_providers, ProviderSpec, RuntimeProviderSelection,
     PROVIDER_REGISTRY,
 };
+pub use replay::FailureCapture;
 pub use retry::RetryProvider;
 pub use rotation::{RotationProvider, RotationStrategy};
 pub use structured::{validate_json_response, OutputFormat};
 pub use types::{
-    ChatOptions, LLMProvider, LLMResponse, LLMToolCall, StreamEvent, ToolDefinition, Usage,
+    BatchRequest, ChatOptions, LLMProvider, LLMResponse, LLMToolCall, StreamEvent, ToolDefinition,
+    Usage,
 };
 #[cfg(feature = "provider-vertex")]
 pub use vertex::VertexProvider;
@@ -36,6 +36,7 @@ use tracing::{debug, info};
 use crate::error::{Result, ZeptoError};
 use crate::session::{ContentPart, ImageSource, Message, Role};
 
+use super::debug_log;
 use super::{
     parse_provider_error, ChatOptions, LLMProvider, LLMResponse, LLMToolCall, ToolDefinition, Usage,
 };
@@ -79,6 +80,12 @@ struct OpenAIRequest {
     /// Stop sequences
     #[serde(skip_serializing_if = "Option::is_none")]
     stop: Option<Vec<String>>,
+    /// Presence penalty
+    #[serde(skip_serializing_if = "Option::is_none")]
+    presence_penalty: Option<f32>,
+    /// Frequency penalty
+    #[serde(skip_serializing_if = "Option::is_none")]
+    frequency_penalty: Option<f32>,
     /// Whether to stream the response using SSE
     #[serde(skip_serializing_if = "Option::is_none")]
     stream: Option<bool>,
@@ -195,6 +202,9 @@ struct OpenAIResponse {
 struct OpenAIChoice {
     /// The message content
     message: OpenAIResponseMessage,
+    /// Why generation stopped (e.g. "stop", "length", "content_filter")
+    #[serde(default)]
+    finish_reason: Option<String>,
 }
 
 /// A message in the response.
@@ -296,6 +306,56 @@ struct PendingToolCall {
     id: String,
     name: String,
     arguments: String,
+    /// Tracks whether `arguments` already holds a complete JSON value.
+    scan: JsonScan,
+    /// Whether the call was already surfaced as `StreamEvent::ToolCallReady`.
+    surfaced: bool,
+}
+
+/// Incremental scanner telling when streamed JSON text forms one complete
+/// object or array, without re-parsing the text on every fragment.
+#[derive(Debug, Default)]
+struct JsonScan {
+    depth: usize,
+    started: bool,
+    in_string: bool,
+    escaped: bool,
+    /// Text continued after the value closed; it is not valid JSON.
+    trailing: bool,
+}
+
+impl JsonScan {
+    fn feed(&mut self, text: &str) {
+        for c in text.chars() {
+            if self.in_string {
+                match c {
+                    _ if self.escaped => self.escaped = false,
+                    '\\' => self.escaped = true,
+                    '"' => self.in_string = false,
+                    _ => {}
+                }
+                continue;
+            }
+            if self.started && self.depth == 0 {
+                self.trailing |= !c.is_whitespace();
+                continue;
+            }
+            match c {
+                '{' | '[' => {
+                    self.started = true;
+                    self.depth += 1;
+                }
+                '}' | ']' => self.depth = self.depth.saturating_sub(1),
+                '"' => self.in_string = true,
+                _ => {}
+            }
+        }
+    }
+
+    /// Whether the text fed so far is one closed object or array.
+    fn is_complete(&self) -> bool {
+        self.started && self.depth == 0 && !self.in_string && !self.trailing
+    }
 }
 
 /// Which token limit field to send to OpenAI.
@@ -373,10 +433,7 @@ impl OpenAIProvider {
         Self {
             api_key: api_key.to_string(),
             api_base: OPENAI_API_URL.to_string(),
-            client: Client::builder()
-                .timeout(std::time::Duration::from_secs(120))
-                .build()
-                .unwrap_or_else(|_| Client::new()),
+            client: crate::utils::http::client(std::time::Duration::from_secs(120)),
             model_token_fields: Mutex::new(HashMap::new()),
             auth_key_header: None,
             api_version: None,
@@ -401,10 +458,7 @@ impl OpenAIProvider {
         Self {
             api_key: api_key.to_string(),
             api_base: api_base.trim_end_matches('/').to_string(),
-            client: Client::builder()
-                .timeout(std::time::Duration::from_secs(120))
-                .build()
-                .unwrap_or_else(|_| Client::new()),
+            client: crate::utils::http::client(std::time::Duration::from_secs(120)),
             model_token_fields: Mutex::new(HashMap::new()),
             auth_key_header: None,
             api_version: None,
@@ -462,10 +516,7 @@ impl OpenAIProvider {
         Self {
             api_key: api_key.to_string(),
             api_base: api_base.trim_end_matches('/').to_string(),
-            client: Client::builder()
-                .timeout(std::time::Duration::from_secs(120))
-                .build()
-                .unwrap_or_else(|_| Client::new()),
+            client: crate::utils::http::client(std::time::Duration::from_secs(120)),
             model_token_fields: Mutex::new(HashMap::new()),
             auth_key_header,
             api_version,
@@ -645,6 +696,15 @@ fn convert_response(response: OpenAIResponse) -> LLMResponse {
     llm_response
 }
 
+/// Convert an OpenAI API response, rejecting one with no text and no tool calls.
+fn checked_response(response: OpenAIResponse) -> Result<LLMResponse> {
+    let finish_reason = response
+        .choices
+        .first()
+        .and_then(|c| c.finish_reason.clone());
+    convert_response(response).reject_empty("openai", finish_reason.as_deref())
+}
+
 /// Build an OpenAI request payload with the requested token field variant.
 fn build_request(
     model: &str,
@@ -671,6 +731,8 @@ fn build_request(
         temperature: options.temperature,
         top_p: options.top_p,
         stop: options.stop.clone(),
+        presence_penalty: options.presence_penalty,
+        frequency_penalty: options.frequency_penalty,
         stream: None,
         response_format: options.output_format.to_openai_response_format(),
     }
@@ -713,6 +775,7 @@ fn apply_stream_chunk(
                         pending.name = name;
                     }
                     if let Some(arguments) = function.arguments {
+                        pending.scan.feed(&arguments);
                         pending.arguments.push_str(&arguments);
                     }
                 }
@@ -723,6 +786,112 @@ fn apply_stream_chunk(
     deltas
 }
 
+/// Tool calls whose id, name and arguments are complete but which have not
+/// been surfaced yet. Each call is returned at most once.
+fn take_ready_tool_calls(pending_tool_calls: &mut [PendingToolCall]) -> Vec<LLMToolCall> {
+    pending_tool_calls
+        .iter_mut()
+        .filter(|p| !p.surfaced && !p.id.is_empty() && !p.name.is_empty() && p.scan.is_complete())
+        .map(|p| {
+            p.surfaced = true;
+            LLMToolCall::new(&p.id, &p.name, &p.arguments)
+        })
+        .collect()
+}
+
+/// Incremental parser for an OpenAI chat completions SSE stream.
+///
+/// Bytes are fed as they arrive. Text deltas are surfaced per chunk, and a
+/// tool call is surfaced as soon as its arguments form a complete JSON
+/// value, instead of after the whole response has been received.
+#[derive(Debug, Default)]
+struct OpenAISseParser {
+    /// Bytes of the current, not yet terminated line.
+    line_buffer: Vec<u8>,
+    assembled_content: String,
+    pending_tool_calls: Vec<PendingToolCall>,
+    usage: Option<Usage>,
+    done: bool,
+}
+
+impl OpenAISseParser {
+    /// Consume raw stream bytes and return the events they complete.
+    fn feed(&mut self, bytes: &[u8]) -> Vec<super::StreamEvent> {
+        use super::StreamEvent;
+
+        let mut events = Vec::new();
+        if self.done {
+            return events;
+        }
+        self.line_buffer.extend_from_slice(bytes);
+
+        while let Some(newline_pos) = self.line_buffer.iter().position(|b| *b == b'\n') {
+            let raw: Vec<u8> = self.line_buffer.drain(..=newline_pos).collect();
+            let line = String::from_utf8_lossy(&raw);
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with("event:") {
+                continue;
+            }
+
+            let data = if let Some(stripped) = line.strip_prefix("data: ") {
+                stripped
+            } else if let Some(stripped) = line.strip_prefix("data:") {
+                stripped
+            } else {
+                continue;
+            };
+
+            if data == "[DONE]" {
+                self.done = true;
+                self.line_buffer.clear();
+                break;
+            }
+
+            let stream_chunk: OpenAIStreamChunk = match serde_json::from_str(data) {
+                Ok(v) => v,
+                Err(_) => continue,
+            };
+
+            let deltas = apply_stream_chunk(
+                stream_chunk,
+                &mut self.assembled_content,
+                &mut self.pending_tool_calls,
+                &mut self.usage,
+            );
+            events.extend(deltas.into_iter().map(StreamEvent::Delta));
+            events.extend(
+                take_ready_tool_calls(&mut self.pending_tool_calls)
+                    .into_iter()
+                    .map(StreamEvent::ToolCallReady),
+            );
+        }
+
+        events
+    }
+
+    /// Whether the `[DONE]` marker was seen.
+    fn is_done(&self) -> bool {
+        self.done
+    }
+
+    /// Events closing the stream: every tool call, then `Done`.
+    fn finish(self) -> Vec<super::StreamEvent> {
+        use super::StreamEvent;
+
+        let mut events = Vec::new();
+        let tool_calls = finalize_tool_calls(self.pending_tool_calls);
+        if !tool_calls.is_empty() {
+            events.push(StreamEvent::ToolCalls(tool_calls));
+        }
+        events.push(StreamEvent::Done {
+            content: self.assembled_content,
+            usage: self.usage,
+        });
+        events
+    }
+}
+
 fn finalize_tool_calls(pending_tool_calls: Vec<PendingToolCall>) -> Vec<LLMToolCall> {
     pending_tool_calls
         .into_iter()
@@ -787,22 +956,29 @@ impl LLMProvider for OpenAIProvider {
             if !auth_header_name.is_empty() {
                 req = req.header(auth_header_name, auth_header_value);
             }
+            debug_log::log_request("openai", &req);
             let response = req
                 .send()
                 .await
                 .map_err(|e| ZeptoError::Provider(format!("OpenAI request failed: {}", e)))?;
 
             if response.status().is_success() {
-                let openai_response: OpenAIResponse = response.json().await.map_err(|e| {
+                let status = response.status().as_u16();
+                let body = response.text().await.map_err(|e| {
+                    ZeptoError::Provider(format!("Failed to read OpenAI response: {}", e))
+                })?;
+                debug_log::log_response("openai", status, &body);
+                let openai_response: OpenAIResponse = serde_json::from_str(&body).map_err(|e| {
                     ZeptoError::Provider(format!("Failed to parse OpenAI response: {}", e))
                 })?;
 
                 info!("OpenAI response received");
-                return Ok(convert_response(openai_response));
+                return checked_response(openai_response);
             }
 
             let status = response.status();
             let error_text = response.text().await.unwrap_or_default();
+            debug_log::log_response("openai", status.as_u16(), &error_text);
 
             // Retry once for models that require max_completion_tokens.
             if status == StatusCode::BAD_REQUEST
@@ -874,6 +1050,7 @@ impl LLMProvider for OpenAIProvider {
             if !auth_header_name.is_empty() {
                 req = req.header(auth_header_name, auth_header_value);
             }
+            debug_log::log_request("openai", &req);
             let response = req
                 .send()
                 .await
@@ -884,11 +1061,7 @@ impl LLMProvider for OpenAIProvider {
                 let byte_stream = response.bytes_stream();
 
                 tokio::spawn(async move {
-                    let mut assembled_content = String::new();
-                    let mut pending_tool_calls: Vec<PendingToolCall> = Vec::new();
-                    let mut usage: Option<Usage> = None;
-                    let mut line_buffer = String::new();
-                    let mut done_seen = false;
+                    let mut parser = OpenAISseParser::default();
 
                     tokio::pin!(byte_stream);
 
@@ -906,65 +1079,20 @@ impl LLMProvider for OpenAIProvider {
                             }
                         };
 
-                        let chunk_str = String::from_utf8_lossy(&chunk);
-                        line_buffer.push_str(&chunk_str);
-
-                        while let Some(newline_pos) = line_buffer.find('\n') {
-                            let line = line_buffer[..newline_pos].trim().to_string();
-                            line_buffer = line_buffer[newline_pos + 1..].to_string();
-
-                            if line.is_empty() || line.starts_with("event:") {
-                                continue;
-                            }
-
-                            let data = if let Some(stripped) = line.strip_prefix("data: ") {
-                                stripped
-                            } else if let Some(stripped) = line.strip_prefix("data:") {
-                                stripped
-                            } else {
-                                continue;
-                            };
-
-                            if data == "[DONE]" {
-                                done_seen = true;
-                                break;
-                            }
-
-                            let stream_chunk: OpenAIStreamChunk = match serde_json::from_str(data) {
-                                Ok(v) => v,
-                                Err(_) => continue,
-                            };
-
-                            let deltas = apply_stream_chunk(
-                                stream_chunk,
-                                &mut assembled_content,
-                                &mut pending_tool_calls,
-                                &mut usage,
-                            );
-
-                            for delta in deltas {
-                                if tx.send(StreamEvent::Delta(delta)).await.is_err() {
-                                    return;
-                                }
+                        for event in parser.feed(&chunk) {
+                            if tx.send(event).await.is_err() {
+                                return;
                             }
                         }
 
-                        if done_seen {
+                        if parser.is_done() {
                             break;
                         }
                     }
 
-                    let tool_calls = finalize_tool_calls(pending_tool_calls);
-                    if !tool_calls.is_empty() {
-                        let _ = tx.send(StreamEvent::ToolCalls(tool_calls)).await;
+                    for event in parser.finish() {
+                        let _ = tx.send(event).await;
                     }
-
-                    let _ = tx
-                        .send(StreamEvent::Done {
-                            content: assembled_content,
-                            usage,
-                        })
-                        .await;
                 });
 
                 return Ok(rx);
@@ -972,6 +1100,7 @@ impl LLMProvider for OpenAIProvider {
 
             let status = response.status();
             let error_text = response.text().await.unwrap_or_default();
+            debug_log::log_response("openai", status.as_u16(), &error_text);
 
             // Retry once for models that require max_completion_tokens.
             if status == StatusCode::BAD_REQUEST
@@ -1074,6 +1203,10 @@ impl LLMProvider for OpenAIProvider {
     fn name(&self) -> &str {
         "openai"
     }
+
+    fn supports_streaming(&self) -> bool {
+        true
+    }
 }
 
 // ============================================================================
@@ -1320,6 +1453,7 @@ mod tests {
                     content: Some("Hello!".to_string()),
                     tool_calls: None,
                 },
+                finish_reason: None,
             }],
             usage: Some(OpenAIUsage {
                 prompt_tokens: 10,
@@ -1352,6 +1486,7 @@ mod tests {
                         },
                     }]),
                 },
+                finish_reason: None,
             }],
             usage: None,
         };
@@ -1376,6 +1511,27 @@ mod tests {
         assert!(!converted.has_tool_calls());
     }
 
+    #[test]
+    fn test_checked_response_rejects_empty_reply() {
+        let response: OpenAIResponse = serde_json::from_value(json!({
+            "choices": [{
+                "message": { "content": null },
+                "finish_reason": "content_filter"
+            }]
+        }))
+        .unwrap();
+        let err = checked_response(response).unwrap_err();
+        assert!(matches!(err, ZeptoError::Provider(_)));
+        assert!(err.to_string().contains("empty response from openai"));
+        assert!(err.to_string().contains("content_filter"));
+
+        let no_choices = OpenAIResponse {
+            choices: vec![],
+            usage: None,
+        };
+        assert!(checked_response(no_choices).is_err());
+    }
+
     #[test]
     fn test_convert_response_null_content() {
         let response = OpenAIResponse {
@@ -1390,6 +1546,7 @@ mod tests {
                         },
                     }]),
                 },
+                finish_reason: None,
             }],
             usage: None,
         };
@@ -1416,6 +1573,8 @@ mod tests {
             temperature: Some(0.7),
             top_p: None,
             stop: None,
+            presence_penalty: None,
+            frequency_penalty: None,
             stream: None,
             response_format: None,
         };
@@ -1451,6 +1610,8 @@ mod tests {
             temperature: None,
             top_p: None,
             stop: None,
+            presence_penalty: None,
+            frequency_penalty: None,
             stream: None,
             response_format: None,
         };
@@ -1531,6 +1692,42 @@ mod tests {
         assert_eq!(request.max_completion_tokens, Some(123));
     }
 
+    #[test]
+    fn test_build_request_includes_stop_and_penalties() {
+        let options = ChatOptions::new()
+            .with_stop(vec!["###".to_string()])
+            .with_presence_penalty(0.5)
+            .with_frequency_penalty(1.25);
+
+        let request = build_request(
+            "gpt-5.1",
+            &[Message::user("List things")],
+            &[],
+            &options,
+            MaxTokenField::MaxTokens,
+        );
+        let json = serde_json::to_value(&request).unwrap();
+
+        assert_eq!(json["stop"], serde_json::json!(["###"]));
+        assert_eq!(json["presence_penalty"], 0.5);
+        assert_eq!(json["frequency_penalty"], 1.25);
+    }
+
+    #[test]
+    fn test_build_request_omits_unset_penalties() {
+        let request = build_request(
+            "gpt-5.1",
+            &[Message::user("Hi")],
+            &[],
+            &ChatOptions::new(),
+            MaxTokenField::MaxTokens,
+        );
+        let json = serde_json::to_string(&request).unwrap();
+
+        assert!(!json.contains("presence_penalty"));
+        assert!(!json.contains("frequency_penalty"));
+    }
+
     #[test]
     fn test_detect_max_tokens_unsupported_error() {
         let err = r#"{
@@ -1737,6 +1934,78 @@ mod tests {
         assert_eq!(tool_calls[0].arguments, r#"{"q":"rust"}"#);
     }
 
+    #[test]
+    fn test_sse_parser_surfaces_tool_call_before_stream_ends() {
+        use crate::providers::StreamEvent;
+
+        let sse = concat!(
+            "data: {\"choices\":[{\"delta\":{\"content\":\"Checking\"}}]}\n\n",
+            "data: {\"choices\":[{\"delta\":{\"tool_calls\":[{\"index\":0,\"id\":\"call_1\",",
+            "\"function\":{\"name\":\"search\",\"arguments\":\"{\\\"q\\\":\"}}]}}]}\n\n",
+            "data: {\"choices\":[{\"delta\":{\"tool_calls\":[{\"index\":0,",
+            "\"function\":{\"arguments\":\"\\\"a } b\\\"}\"}}]}}]}\n\n",
+            "data: {\"choices\":[{\"delta\":{\"tool_calls\":[{\"index\":1,\"id\":\"call_2\",",
+            "\"function\":{\"name\":\"read_file\",\"arguments\":\"{\\\"path\\\"\"}}]}}]}\n\n",
+        );
+        let tail = concat!(
+            "data: {\"choices\":[{\"delta\":{\"tool_calls\":[{\"index\":1,",
+            "\"function\":{\"arguments\":\":\\\"a.txt\\\"}\"}}]}}]}\n\n",
+            "data: [DONE]\n\n",
+        );
+
+        // Feed the first part in small, arbitrary pieces.
+        let mut parser = OpenAISseParser::default();
+        let mut events = Vec::new();
+        for piece in sse.as_bytes().chunks(7) {
+            events.extend(parser.feed(piece));
+        }
+        assert!(!parser.is_done());
+        let ready: Vec<_> = events
+            .iter()
+            .filter_map(|e| match e {
+                StreamEvent::ToolCallReady(call) => Some(call),
+                _ => None,
+            })
+            .collect();
+        // The first call is complete (a brace inside a string does not close
+        // it); the second is still streaming.
+        assert_eq!(ready.len(), 1);
+        assert_eq!(ready[0].id, "call_1");
+        assert_eq!(ready[0].arguments, r#"{"q":"a } b"}"#);
+        assert!(matches!(&events[0], StreamEvent::Delta(text) if text == "Checking"));
+
+        let events = parser.feed(tail.as_bytes());
+        assert!(parser.is_done());
+        assert!(matches!(
+            events.as_slice(),
+            [StreamEvent::ToolCallReady(call)] if call.id == "call_2"
+        ));
+
+        let closing = parser.finish();
+        match closing.as_slice() {
+            [StreamEvent::ToolCalls(calls), StreamEvent::Done { content, .. }] => {
+                assert_eq!(calls.len(), 2);
+                assert_eq!(calls[1].arguments, r#"{"path":"a.txt"}"#);
+                assert_eq!(content, "Checking");
+            }
+            other => panic!("unexpected closing events: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_json_scan_detects_complete_values() {
+        let mut scan = JsonScan::default();
+        scan.feed(r#"{"a": [1, {"b": "\"}\\"#);
+        assert!(!scan.is_complete());
+        scan.feed(r#""}]"#);
+        assert!(!scan.is_complete());
+        scan.feed("} ");
+        assert!(scan.is_complete());
+        scan.feed("x");
+        assert!(!scan.is_complete());
+        assert!(!JsonScan::default().is_complete());
+    }
+
     // ====================================================================
     // embed() tests
     // ====================================================================
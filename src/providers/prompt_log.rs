@@ -0,0 +1,548 @@
+//! Sampled prompt/response logging.
+//!
+//! [`PromptLogger`] wraps any [`LLMProvider`] and appends a sampled fraction
+//! of full request/response pairs to a JSONL file for quality monitoring.
+//! Records are redacted (emails, phone numbers, card numbers and anything the
+//! [`LeakDetector`] recognises as a secret) before they are written, and the
+//! file is rotated by size.
+//!
+//! Logging is best-effort: records are handed to a background writer through
+//! a bounded queue and dropped when the queue is full, so a slow disk never
+//! delays a request.
+//!
+//! # Example (config.json)
+//! ```json
+//! {
+//!   "providers": {
+//!     "prompt_log": { "enabled": true, "sample_rate": 0.05 }
+//!   }
+//! }
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::mpsc;
+use tracing::{debug, warn};
+
+use crate::config::PromptLogConfig;
+use crate::error::Result;
+use crate::safety::leak_detector::LeakDetector;
+use crate::session::Message;
+
+use super::{
+    BatchRequest, ChatOptions, LLMProvider, LLMResponse, StreamEvent, ToolDefinition, Usage,
+};
+
+/// Records waiting to be written before new ones are dropped.
+const PROMPT_LOG_QUEUE_CAPACITY: usize = 256;
+
+static EMAIL_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}").unwrap());
+static CARD_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b(?:\d[ -]?){12,18}\d\b").unwrap());
+static PHONE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?:\+\d{1,3}[ .-]?)?(?:\(\d{3}\)|\b\d{3})[ .-]?\d{3}[ .-]?\d{4}\b").unwrap()
+});
+
+/// Decide whether to sample one call at `rate` (0.0 = never, 1.0 = always).
+pub fn should_sample(rate: f64) -> bool {
+    if rate <= 0.0 {
+        return false;
+    }
+    if rate >= 1.0 {
+        return true;
+    }
+    // The first 32 bits of a v4 UUID are uniformly random.
+    let bits = (uuid::Uuid::new_v4().as_u128() >> 96) as u32;
+    (f64::from(bits) / f64::from(u32::MAX)) < rate
+}
+
+/// Masks personal data and secrets in logged text.
+pub struct PiiRedactor {
+    leaks: LeakDetector,
+}
+
+impl PiiRedactor {
+    /// Create a redactor with the built-in PII and secret patterns.
+    pub fn new() -> Self {
+        Self {
+            leaks: LeakDetector::new(),
+        }
+    }
+
+    /// Return `text` with PII replaced by placeholders and secrets masked.
+    pub fn redact(&self, text: &str) -> String {
+        let (text, _) = self.leaks.redact(text);
+        let text = EMAIL_RE.replace_all(&text, "[EMAIL]");
+        let text = CARD_RE.replace_all(&text, "[CARD]");
+        PHONE_RE.replace_all(&text, "[PHONE]").into_owned()
+    }
+}
+
+impl Default for PiiRedactor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// One logged message of the prompt.
+#[derive(Debug, Clone, Serialize)]
+pub struct LoggedMessage {
+    pub role: String,
+    pub content: String,
+}
+
+/// One logged tool call from the response.
+#[derive(Debug, Clone, Serialize)]
+pub struct LoggedToolCall {
+    pub name: String,
+    pub arguments: String,
+}
+
+/// A single JSONL line in the prompt log.
+#[derive(Debug, Clone, Serialize)]
+pub struct PromptLogRecord {
+    /// RFC 3339 time the response completed
+    pub timestamp: String,
+    /// Provider that served the request
+    pub provider: String,
+    /// Model requested, or the provider default
+    pub model: String,
+    /// Full prompt
+    pub messages: Vec<LoggedMessage>,
+    /// Response text
+    pub response: String,
+    /// Tool calls in the response
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tool_calls: Vec<LoggedToolCall>,
+    /// Token usage, when reported
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usage: Option<Usage>,
+}
+
+impl PromptLogRecord {
+    fn new(provider: &str, model: &str, messages: &[Message], response: &LLMResponse) -> Self {
+        Self {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            provider: provider.to_string(),
+            model: model.to_string(),
+            messages: messages
+                .iter()
+                .map(|m| LoggedMessage {
+                    role: m.role.to_string(),
+                    content: m.content.clone(),
+                })
+                .collect(),
+            response: response.content.clone(),
+            tool_calls: response
+                .tool_calls
+                .iter()
+                .map(|tc| LoggedToolCall {
+                    name: tc.name.clone(),
+                    arguments: tc.arguments.clone(),
+                })
+                .collect(),
+            usage: response.usage.clone(),
+        }
+    }
+
+    /// Redact every free-text field in place.
+    pub fn redact(&mut self, redactor: &PiiRedactor) {
+        for message in &mut self.messages {
+            message.content = redactor.redact(&message.content);
+        }
+        self.response = redactor.redact(&self.response);
+        for call in &mut self.tool_calls {
+            call.arguments = redactor.redact(&call.arguments);
+        }
+    }
+}
+
+/// Appends lines to a JSONL file, rotating it by size.
+///
+/// When the next line would push the file past `max_bytes`, `log.jsonl`
+/// becomes `log.jsonl.1`, `.1` becomes `.2` and so on; at most `max_files`
+/// rotated files are kept.
+pub struct RotatingWriter {
+    path: PathBuf,
+    max_bytes: u64,
+    max_files: u32,
+    file: Option<tokio::fs::File>,
+    size: u64,
+}
+
+impl RotatingWriter {
+    /// Create a writer for `path`. The file is opened on first write.
+    pub fn new(path: PathBuf, max_bytes: u64, max_files: u32) -> Self {
+        Self {
+            path,
+            max_bytes,
+            max_files,
+            file: None,
+            size: 0,
+        }
+    }
+
+    fn rotated_path(&self, index: u32) -> PathBuf {
+        let mut name = self.path.as_os_str().to_owned();
+        name.push(format!(".{}", index));
+        PathBuf::from(name)
+    }
+
+    async fn rotate(&mut self) -> std::io::Result<()> {
+        self.file = None;
+        if self.max_files == 0 {
+            tokio::fs::remove_file(&self.path).await?;
+        } else {
+            let _ = tokio::fs::remove_file(self.rotated_path(self.max_files)).await;
+            for index in (1..self.max_files).rev() {
+                let from = self.rotated_path(index);
+                if tokio::fs::try_exists(&from).await.unwrap_or(false) {
+                    tokio::fs::rename(&from, self.rotated_path(index + 1)).await?;
+                }
+            }
+            tokio::fs::rename(&self.path, self.rotated_path(1)).await?;
+        }
+        self.size = 0;
+        Ok(())
+    }
+
+    async fn open(&mut self) -> std::io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await?;
+        self.size = file.metadata().await?.len();
+        self.file = Some(file);
+        Ok(())
+    }
+
+    /// Append one line (a trailing newline is added).
+    pub async fn append(&mut self, line: &str) -> std::io::Result<()> {
+        if self.file.is_none() {
+            self.open().await?;
+        }
+        let len = line.len() as u64 + 1;
+        if self.size > 0 && self.size + len > self.max_bytes {
+            self.rotate().await?;
+            self.open().await?;
+        }
+
+        if let Some(file) = self.file.as_mut() {
+            file.write_all(line.as_bytes()).await?;
+            file.write_all(b"\n").await?;
+            file.flush().await?;
+        }
+        self.size += len;
+        Ok(())
+    }
+}
+
+/// Default log location: `~/.zeptoclaw/logs/prompts.jsonl`.
+pub fn default_prompt_log_path() -> PathBuf {
+    crate::config::Config::dir()
+        .join("logs")
+        .join("prompts.jsonl")
+}
+
+fn spawn_writer(path: &Path, config: &PromptLogConfig) -> mpsc::Sender<PromptLogRecord> {
+    let (tx, mut rx) = mpsc::channel::<PromptLogRecord>(PROMPT_LOG_QUEUE_CAPACITY);
+    let mut writer =
+        RotatingWriter::new(path.to_path_buf(), config.max_file_bytes, config.max_files);
+    let redactor = config.redact.then(PiiRedactor::new);
+
+    tokio::spawn(async move {
+        while let Some(mut record) = rx.recv().await {
+            if let Some(redactor) = &redactor {
+                record.redact(redactor);
+            }
+            let line = match serde_json::to_string(&record) {
+                Ok(line) => line,
+                Err(e) => {
+                    warn!(error = %e, "Failed to serialize prompt log record");
+                    continue;
+                }
+            };
+            if let Err(e) = writer.append(&line).await {
+                warn!(error = %e, "Failed to write prompt log");
+            }
+        }
+    });
+    tx
+}
+
+/// Provider wrapper that logs a sample of prompt/response pairs.
+pub struct PromptLogger {
+    inner: Box<dyn LLMProvider>,
+    sample_rate: f64,
+    tx: mpsc::Sender<PromptLogRecord>,
+}
+
+impl PromptLogger {
+    /// Wrap `inner`, writing to `config.path` (or [`default_prompt_log_path`]).
+    ///
+    /// Must be called within a Tokio runtime; the writer runs as a task.
+    pub fn new(inner: Box<dyn LLMProvider>, config: &PromptLogConfig) -> Self {
+        let path = config
+            .path
+            .as_deref()
+            .map(crate::config::expand_home)
+            .unwrap_or_else(default_prompt_log_path);
+        Self {
+            inner,
+            sample_rate: config.sample_rate,
+            tx: spawn_writer(&path, config),
+        }
+    }
+
+    /// Queue a record without waiting. Dropped if the writer is behind.
+    fn enqueue(&self, record: PromptLogRecord) {
+        if self.tx.try_send(record).is_err() {
+            debug!("Prompt log queue full; dropping record");
+        }
+    }
+}
+
+#[async_trait]
+impl LLMProvider for PromptLogger {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn default_model(&self) -> &str {
+        self.inner.default_model()
+    }
+
+    fn supports_streaming(&self) -> bool {
+        self.inner.supports_streaming()
+    }
+
+    async fn chat(
+        &self,
+        messages: Vec<Message>,
+        tools: Vec<ToolDefinition>,
+        model: Option<&str>,
+        options: ChatOptions,
+    ) -> Result<LLMResponse> {
+        if !should_sample(self.sample_rate) {
+            return self.inner.chat(messages, tools, model, options).await;
+        }
+
+        let prompt = messages.clone();
+        let response = self.inner.chat(messages, tools, model, options).await?;
+        let model = model.unwrap_or(self.inner.default_model());
+        self.enqueue(PromptLogRecord::new(
+            self.inner.name(),
+            model,
+            &prompt,
+            &response,
+        ));
+        Ok(response)
+    }
+
+    async fn chat_stream(
+        &self,
+        messages: Vec<Message>,
+        tools: Vec<ToolDefinition>,
+        model: Option<&str>,
+        options: ChatOptions,
+    ) -> Result<mpsc::Receiver<StreamEvent>> {
+        if !should_sample(self.sample_rate) {
+            return self
+                .inner
+                .chat_stream(messages, tools, model, options)
+                .await;
+        }
+
+        let prompt = messages.clone();
+        let provider = self.inner.name().to_string();
+        let logged_model = model.unwrap_or(self.inner.default_model()).to_string();
+        let mut inner_rx = self
+            .inner
+            .chat_stream(messages, tools, model, options)
+            .await?;
+
+        // Forward events untouched and log once the stream completes.
+        let (tx, rx) = mpsc::channel(32);
+        let log_tx = self.tx.clone();
+        tokio::spawn(async move {
+            let mut tool_calls = Vec::new();
+            while let Some(event) = inner_rx.recv().await {
+                let record = match &event {
+                    StreamEvent::ToolCalls(calls) => {
+                        tool_calls.extend(calls.iter().cloned());
+                        None
+                    }
+                    StreamEvent::Done { content, usage } => {
+                        let response = LLMResponse {
+                            content: content.clone(),
+                            tool_calls: tool_calls.clone(),
+                            usage: usage.clone(),
+                            thoughts: None,
+                        };
+                        Some(PromptLogRecord::new(
+                            &provider,
+                            &logged_model,
+                            &prompt,
+                            &response,
+                        ))
+                    }
+                    _ => None,
+                };
+                if let Some(record) = record {
+                    let _ = log_tx.try_send(record);
+                }
+                if tx.send(event).await.is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(rx)
+    }
+
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        self.inner.embed(texts).await
+    }
+
+    async fn batch_chat(&self, requests: Vec<BatchRequest>) -> Vec<Result<LLMResponse>> {
+        self.inner.batch_chat(requests).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    struct EchoProvider;
+
+    #[async_trait]
+    impl LLMProvider for EchoProvider {
+        async fn chat(
+            &self,
+            messages: Vec<Message>,
+            _tools: Vec<ToolDefinition>,
+            _model: Option<&str>,
+            _options: ChatOptions,
+        ) -> Result<LLMResponse> {
+            let last = messages
+                .last()
+                .map(|m| m.content.clone())
+                .unwrap_or_default();
+            Ok(LLMResponse::text(&format!("you said: {}", last)))
+        }
+
+        fn default_model(&self) -> &str {
+            "echo-1"
+        }
+
+        fn name(&self) -> &str {
+            "echo"
+        }
+    }
+
+    async fn read_when_written(path: &Path) -> String {
+        for _ in 0..100 {
+            if let Ok(text) = std::fs::read_to_string(path) {
+                if !text.is_empty() {
+                    return text;
+                }
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        panic!("prompt log was never written");
+    }
+
+    #[test]
+    fn test_sampling_rate_statistics() {
+        const CALLS: usize = 20_000;
+        for rate in [0.05, 0.25, 0.5] {
+            let hits = (0..CALLS).filter(|_| should_sample(rate)).count();
+            let observed = hits as f64 / CALLS as f64;
+            // ~9 standard deviations for the worst case (rate 0.5).
+            assert!(
+                (observed - rate).abs() < 0.035,
+                "rate {rate}: observed {observed}"
+            );
+        }
+        assert!((0..1000).all(|_| !should_sample(0.0)));
+        assert!((0..1000).all(|_| should_sample(1.0)));
+    }
+
+    #[test]
+    fn test_redactor_masks_pii_and_secrets() {
+        let redactor = PiiRedactor::new();
+        let text = "Mail alice@example.com or call +1 415-555-0134, card 4111 1111 1111 1111, \
+key sk-abc12345678901234567890";
+        let redacted = redactor.redact(text);
+        assert!(!redacted.contains("alice@example.com"), "{redacted}");
+        assert!(!redacted.contains("415-555-0134"), "{redacted}");
+        assert!(!redacted.contains("4111 1111 1111 1111"), "{redacted}");
+        assert!(
+            !redacted.contains("sk-abc12345678901234567890"),
+            "{redacted}"
+        );
+        assert!(redacted.contains("[EMAIL]"));
+        assert!(redacted.contains("[PHONE]"));
+        assert!(redacted.contains("[CARD]"));
+    }
+
+    #[tokio::test]
+    async fn test_logger_redacts_before_writing() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("prompts.jsonl");
+        let config = PromptLogConfig {
+            enabled: true,
+            sample_rate: 1.0,
+            path: Some(path.to_string_lossy().into_owned()),
+            ..Default::default()
+        };
+        let logger = PromptLogger::new(Box::new(EchoProvider), &config);
+
+        let response = logger
+            .chat(
+                vec![Message::user("I am bob@example.org")],
+                vec![],
+                None,
+                ChatOptions::default(),
+            )
+            .await
+            .unwrap();
+        // The caller still sees the unredacted response.
+        assert_eq!(response.content, "you said: I am bob@example.org");
+
+        let text = read_when_written(&path).await;
+        assert!(!text.contains("bob@example.org"), "{text}");
+        let record: serde_json::Value = serde_json::from_str(text.lines().next().unwrap()).unwrap();
+        assert_eq!(record["provider"], "echo");
+        assert_eq!(record["model"], "echo-1");
+        assert_eq!(record["messages"][0]["role"], "user");
+        assert_eq!(record["messages"][0]["content"], "I am [EMAIL]");
+        assert_eq!(record["response"], "you said: I am [EMAIL]");
+    }
+
+    #[tokio::test]
+    async fn test_rotating_writer_rotates_by_size() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("log.jsonl");
+        let mut writer = RotatingWriter::new(path.clone(), 20, 2);
+
+        for line in ["aaaaaaaaaa", "bbbbbbbbbb", "cccccccccc", "dddddddddd"] {
+            writer.append(line).await.unwrap();
+        }
+
+        let read = |p: PathBuf| std::fs::read_to_string(p).unwrap();
+        assert_eq!(read(path.clone()), "dddddddddd\n");
+        assert_eq!(read(dir.path().join("log.jsonl.1")), "cccccccccc\n");
+        assert_eq!(read(dir.path().join("log.jsonl.2")), "bbbbbbbbbb\n");
+        assert!(!dir.path().join("log.jsonl.3").exists());
+    }
+}
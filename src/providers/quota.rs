@@ -2,7 +2,19 @@
 //!
 //! Tracks cost (USD) and token consumption per provider across configurable
 //! time periods (monthly or daily). Provides quota checks that return `Ok`,
-//! `Warning`, or `Exceeded` based on configurable thresholds.
+//! `Warning`, or `Exceeded` based on a configurable warning threshold.
+//!
+//! An optional aggregate (org-wide) quota is checked against a dedicated
+//! counter that every provider's usage is added to, on the aggregate's own
+//! period, in addition to each provider's own limits.
+//!
+//! A [`QuotaNotifier`] attached to the store receives a [`QuotaEvent`] the
+//! first time a check moves into `Warning` or `Exceeded` within a period.
+//!
+//! Usage is persisted to `~/.zeptoclaw/quota/usage.json` by default. With the
+//! `sqlite-store` feature and `storage.backend = "sqlite"`, each provider's
+//! counter is a row in a shared SQLite database updated atomically, so the
+//! gateway and CLI can record usage concurrently without losing updates.
 //!
 //! # Example
 //!
@@ -15,6 +27,9 @@
 //!     max_tokens: None,
 //!     period: QuotaPeriod::Monthly,
 //!     action: zeptoclaw::providers::quota::QuotaAction::Reject,
+//!     warning_threshold: Some(0.9),
+//!     downgrade_model: None,
+//!     per_model: Default::default(),
 //! };
 //!
 //! // Record some usage
@@ -29,13 +44,17 @@
 //! ```
 
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use async_trait::async_trait;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::config::{StorageBackend, StorageConfig};
+
 /// Reset cadence for quota counters.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "lowercase")]
@@ -45,6 +64,15 @@ pub enum QuotaPeriod {
     Monthly,
     /// Counters reset at midnight UTC each day.
     Daily,
+    /// Counters reset Monday 00:00 UTC (ISO week).
+    Weekly,
+    /// Counters reset every `days` days, counted from 1970-01-01 UTC.
+    ///
+    /// Written as `{"custom": {"days": 14}}` in config.
+    Custom {
+        /// Length of each period in days (0 is treated as 1).
+        days: u32,
+    },
 }
 
 /// Action to take when a provider's quota is exceeded.
@@ -74,6 +102,46 @@ pub struct QuotaConfig {
     pub period: QuotaPeriod,
     /// What to do when the quota is exceeded.
     pub action: QuotaAction,
+    /// Utilisation fraction in `(0, 1)` at which checks start returning
+    /// `Warning`. `None` uses the default of 0.8.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warning_threshold: Option<f64>,
+    /// Cheaper model used for this provider's requests while usage is at or
+    /// above the warning threshold (e.g. a flash model instead of pro).
+    /// Requests return to the normal model when the period resets.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub downgrade_model: Option<String>,
+    /// Limits for individual models of this provider, keyed by model name.
+    /// Requests for a listed model must be within both its entry and the
+    /// provider-level limits, and are counted separately (as
+    /// `provider:model`) in addition to the provider total. `per_model`
+    /// inside an entry is ignored.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub per_model: HashMap<String, QuotaConfig>,
+}
+
+impl QuotaConfig {
+    /// The effective warning threshold (configured value or the default).
+    ///
+    /// A configured value outside `(0, 1)` falls back to the default, with
+    /// a warning logged once per process.
+    pub fn warning_threshold(&self) -> f64 {
+        match self.warning_threshold {
+            Some(threshold) if threshold > 0.0 && threshold < 1.0 => threshold,
+            Some(threshold) => {
+                static WARNED: AtomicBool = AtomicBool::new(false);
+                if !WARNED.swap(true, Ordering::Relaxed) {
+                    tracing::warn!(
+                        threshold,
+                        default = DEFAULT_WARNING_THRESHOLD,
+                        "quota: warning_threshold must be between 0 and 1 (exclusive); using default"
+                    );
+                }
+                DEFAULT_WARNING_THRESHOLD
+            }
+            None => DEFAULT_WARNING_THRESHOLD,
+        }
+    }
 }
 
 impl Default for QuotaConfig {
@@ -83,6 +151,9 @@ impl Default for QuotaConfig {
             max_tokens: None,
             period: QuotaPeriod::Monthly,
             action: QuotaAction::Reject,
+            warning_threshold: None,
+            downgrade_model: None,
+            per_model: HashMap::new(),
         }
     }
 }
@@ -90,9 +161,9 @@ impl Default for QuotaConfig {
 /// Persisted usage counter for a single provider within a period.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct QuotaUsage {
-    /// The period this counter belongs to.
-    ///
-    /// Format: `"2026-03"` for monthly, `"2026-03-01"` for daily.
+    /// The period this counter belongs to, as produced by
+    /// [`QuotaStore::period_key_at`]: `"2026-03"` (monthly), `"2026-03-01"`
+    /// (daily), `"2026-W10"` (weekly) or `"2026-03-01+14d"` (custom).
     pub period_key: String,
     /// Accumulated cost in USD for this period.
     pub cost_usd: f64,
@@ -103,69 +174,296 @@ pub struct QuotaUsage {
 /// Result of a quota check for a provider.
 #[derive(Debug, Clone, PartialEq)]
 pub enum QuotaCheckResult {
-    /// Usage is below the warning threshold (80%).
+    /// Usage is below the warning threshold (80% unless configured).
     Ok,
-    /// Usage is at or above 80% of the configured limit. Inner value is the
-    /// utilisation fraction (range: `0.80..1.0`).
+    /// Usage is at or above the warning threshold but below the limit. Inner
+    /// value is the utilisation fraction (range: `threshold..1.0`).
     Warning(f64),
     /// At least one limit is at or above 100%.
     Exceeded,
 }
 
+/// Severity of a quota notification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QuotaLevel {
+    /// Utilisation crossed the warning threshold.
+    Warning,
+    /// Utilisation reached 100% of a limit.
+    Exceeded,
+}
+
+/// Emitted when a quota check first transitions into `Warning` or `Exceeded`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct QuotaEvent {
+    /// Provider name, or [`AGGREGATE_QUOTA_NAME`] for the org-wide quota.
+    pub provider: String,
+    pub level: QuotaLevel,
+    /// Highest utilisation fraction across the configured limits.
+    pub utilisation: f64,
+    /// Period the event belongs to (e.g. `"2026-03"`).
+    pub period_key: String,
+}
+
+/// Store key of the aggregate (org-wide) usage counter, also the provider
+/// name used in [`QuotaEvent`]s for the aggregate quota.
+pub const AGGREGATE_QUOTA_NAME: &str = "aggregate";
+
+/// Callback invoked for each [`QuotaEvent`].
+pub type QuotaNotifier = Arc<dyn Fn(&QuotaEvent) + Send + Sync>;
+
+/// Where quota notifications are delivered (`providers.quota_notify`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuotaNotifyConfig {
+    /// Channel name to notify (e.g. "telegram").
+    pub channel: String,
+    /// Chat ID within the channel (e.g. the admin's DM).
+    pub chat_id: String,
+}
+
+/// Build a notifier that publishes quota events to a chat via the message bus.
+pub fn bus_notifier(target: QuotaNotifyConfig, bus: Arc<crate::bus::MessageBus>) -> QuotaNotifier {
+    Arc::new(move |event: &QuotaEvent| {
+        let subject = if event.provider == AGGREGATE_QUOTA_NAME {
+            "Aggregate quota".to_string()
+        } else {
+            format!("Quota for provider '{}'", event.provider)
+        };
+        let text = match event.level {
+            QuotaLevel::Warning => format!(
+                "{} is at {:.0}% for period {}.",
+                subject,
+                event.utilisation * 100.0,
+                event.period_key
+            ),
+            QuotaLevel::Exceeded => format!(
+                "{} exceeded ({:.0}%) for period {}.",
+                subject,
+                event.utilisation * 100.0,
+                event.period_key
+            ),
+        };
+        let outbound = crate::bus::OutboundMessage::new(&target.channel, &target.chat_id, &text);
+        if let Err(e) = bus.try_publish_outbound(outbound) {
+            tracing::warn!(error = %e, "quota: failed to publish notification");
+        }
+    })
+}
+
 /// Persistent store for per-provider quota usage.
 ///
-/// Thread-safe via an internal `Mutex`. Persists state to
-/// `~/.zeptoclaw/quota/usage.json` after every `record()` call (best-effort;
-/// write errors are silently ignored).
+/// Thread-safe via an internal `Mutex`. With the JSON backend, state is
+/// persisted to `~/.zeptoclaw/quota/usage.json` after every `record()` call
+/// (best-effort; write errors are logged). With the SQLite backend, each
+/// call reads or updates the database directly.
 pub struct QuotaStore {
-    /// In-memory state: provider name → usage for the current period.
+    /// In-memory state for the JSON backend: provider name → usage for the
+    /// current period.
     state: Mutex<HashMap<String, QuotaUsage>>,
-    /// Path of the JSON file used for persistence.
+    /// Path of the JSON file used for persistence (or imported from, on
+    /// first open of a SQLite database).
     path: PathBuf,
+    /// Period of the [`AGGREGATE_QUOTA_NAME`] counter, or `None` when no
+    /// aggregate quota is configured and the counter is not kept.
+    aggregate_period: Mutex<Option<QuotaPeriod>>,
+    /// SQLite connection; when set, the database replaces `state` and `path`.
+    #[cfg(feature = "sqlite-store")]
+    sqlite: Option<Mutex<rusqlite::Connection>>,
+    /// Optional callback for threshold transitions.
+    notifier: Mutex<Option<QuotaNotifier>>,
+    /// Last level notified per provider, with its period key, for debouncing.
+    notified: Mutex<HashMap<String, (String, QuotaLevel)>>,
+}
+
+/// Separates provider and model in per-model store keys.
+const MODEL_KEY_SEPARATOR: char = ':';
+
+/// How long a stream is still drained for its usage after the
+/// `chat_stream` receiver is dropped; past this the inner stream is dropped,
+/// cancelling the upstream request.
+const STREAM_DRAIN_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Default fraction of quota utilisation at or above which a warning is issued.
+pub const DEFAULT_WARNING_THRESHOLD: f64 = 0.8;
+
+/// Highest utilisation fraction across the limits in `config`.
+fn utilisation(cost_usd: f64, tokens: u64, config: &QuotaConfig) -> f64 {
+    let cost_pct = config
+        .max_cost_usd
+        .map(|max| if max > 0.0 { cost_usd / max } else { 0.0 })
+        .unwrap_or(0.0);
+
+    let token_pct = config
+        .max_tokens
+        .map(|max| {
+            if max > 0 {
+                tokens as f64 / max as f64
+            } else {
+                0.0
+            }
+        })
+        .unwrap_or(0.0);
+
+    cost_pct.max(token_pct)
 }
 
-/// Fraction of quota utilisation at or above which a warning is issued.
-const WARNING_THRESHOLD: f64 = 0.8;
+/// Compare accumulated usage against the limits in `config`.
+fn evaluate_limits(cost_usd: f64, tokens: u64, config: &QuotaConfig) -> QuotaCheckResult {
+    let max_pct = utilisation(cost_usd, tokens, config);
+
+    if max_pct >= 1.0 {
+        QuotaCheckResult::Exceeded
+    } else if max_pct >= config.warning_threshold() {
+        QuotaCheckResult::Warning(max_pct)
+    } else {
+        QuotaCheckResult::Ok
+    }
+}
 
 impl QuotaStore {
     /// Load usage state from `~/.zeptoclaw/quota/usage.json`.
     ///
     /// Returns an empty store if the file does not exist or cannot be parsed.
     pub fn load_or_default() -> Self {
-        let path = dirs_path();
-        let state = load_state(&path);
-        Self {
-            state: Mutex::new(state),
-            path,
-        }
+        Self::load_from_path(dirs_path())
     }
 
     /// Load quota state from a specific directory (used for testing).
     pub fn load_from_dir(dir: impl AsRef<std::path::Path>) -> Self {
-        let path = dir.as_ref().join("usage.json");
+        Self::load_from_path(dir.as_ref().join("usage.json"))
+    }
+
+    fn load_from_path(path: PathBuf) -> Self {
         let state = load_state(&path);
         Self {
             state: Mutex::new(state),
             path,
+            aggregate_period: Mutex::new(None),
+            #[cfg(feature = "sqlite-store")]
+            sqlite: None,
+            notifier: Mutex::new(None),
+            notified: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Open the store selected by the `storage` config section.
+    ///
+    /// If the SQLite backend is requested but the `sqlite-store` feature was
+    /// not compiled in, or the database cannot be opened, logs a warning and
+    /// falls back to the JSON file.
+    pub fn open(storage: &StorageConfig) -> Self {
+        if storage.backend == StorageBackend::Sqlite {
+            #[cfg(feature = "sqlite-store")]
+            {
+                let db_path = storage.sqlite_path();
+                match Self::open_sqlite(&db_path, &dirs_path()) {
+                    Ok(store) => return store,
+                    Err(e) => tracing::warn!(
+                        path = %db_path.display(),
+                        error = %e,
+                        "quota: failed to open SQLite store; falling back to JSON"
+                    ),
+                }
+            }
+            #[cfg(not(feature = "sqlite-store"))]
+            tracing::warn!("sqlite-store feature not compiled; falling back to JSON quota store. Rebuild with: cargo build --features sqlite-store");
         }
+        Self::load_or_default()
+    }
+
+    /// Open a SQLite-backed store at `db_path`.
+    ///
+    /// On the first open of a database, usage from the JSON file at
+    /// `json_path` (if any) is imported.
+    #[cfg(feature = "sqlite-store")]
+    pub fn open_sqlite(db_path: &Path, json_path: &Path) -> rusqlite::Result<Self> {
+        let mut conn = crate::utils::sqlite::open(db_path)?;
+        sqlite::init(&mut conn, json_path)?;
+        Ok(Self {
+            state: Mutex::new(HashMap::new()),
+            path: json_path.to_path_buf(),
+            sqlite: Some(Mutex::new(conn)),
+            notifier: Mutex::new(None),
+            notified: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Attach a callback fired when a check first enters `Warning` or
+    /// `Exceeded` for a provider within a period.
+    pub fn set_notifier(&self, notifier: QuotaNotifier) {
+        *lock_recover(&self.notifier) = Some(notifier);
     }
 
     /// Compute the period key for `now()` given a reset cadence.
     ///
-    /// Returns `"YYYY-MM"` for `Monthly` and `"YYYY-MM-DD"` for `Daily`.
+    /// See [`period_key_at`](Self::period_key_at) for the formats.
     pub fn current_period_key(period: &QuotaPeriod) -> String {
-        let now = Utc::now();
+        Self::period_key_at(period, Utc::now())
+    }
+
+    /// Compute the period key containing `now`.
+    ///
+    /// Returns `"YYYY-MM"` for `Monthly`, `"YYYY-MM-DD"` for `Daily`,
+    /// `"YYYY-Www"` (ISO week-numbering year and week) for `Weekly`, and
+    /// `"YYYY-MM-DD+Nd"` (first day of the period, then its length) for
+    /// `Custom`.
+    pub fn period_key_at(period: &QuotaPeriod, now: DateTime<Utc>) -> String {
         match period {
             QuotaPeriod::Monthly => now.format("%Y-%m").to_string(),
             QuotaPeriod::Daily => now.format("%Y-%m-%d").to_string(),
+            QuotaPeriod::Weekly => now.format("%G-W%V").to_string(),
+            QuotaPeriod::Custom { days } => {
+                let days = i64::from((*days).max(1));
+                let day_index = now.timestamp().div_euclid(86_400);
+                let start = DateTime::<Utc>::UNIX_EPOCH
+                    + chrono::Duration::days(day_index - day_index.rem_euclid(days));
+                format!("{}+{}d", start.format("%Y-%m-%d"), days)
+            }
+        }
+    }
+
+    /// Store key for one model of a provider: `"provider:model"`.
+    pub fn model_key(provider: &str, model: &str) -> String {
+        format!("{}{}{}", provider, MODEL_KEY_SEPARATOR, model)
+    }
+
+    /// Split a store key into its provider and, for per-model counters, the
+    /// model (the inverse of [`model_key`](Self::model_key)).
+    pub fn split_model_key(key: &str) -> (&str, Option<&str>) {
+        match key.split_once(MODEL_KEY_SEPARATOR) {
+            Some((provider, model)) => (provider, Some(model)),
+            None => (key, None),
         }
     }
 
+    /// Check one model of `provider` against its own quota `config`.
+    pub fn check_model(
+        &self,
+        provider: &str,
+        model: &str,
+        config: &QuotaConfig,
+    ) -> QuotaCheckResult {
+        self.check(&Self::model_key(provider, model), config)
+    }
+
+    /// Record usage for one model of `provider`, counted separately from the
+    /// provider total.
+    pub fn record_model(
+        &self,
+        provider: &str,
+        model: &str,
+        period: &QuotaPeriod,
+        cost_usd: f64,
+        tokens: u64,
+    ) {
+        self.record(&Self::model_key(provider, model), period, cost_usd, tokens);
+    }
+
     /// Check whether the named provider is within its configured quota.
     ///
     /// Returns:
-    /// - `Ok` when below the 80% warning threshold (or no limits configured).
-    /// - `Warning(pct)` when utilisation is ≥ 80% but < 100%.
+    /// - `Ok` when below the warning threshold (or no limits configured).
+    /// - `Warning(pct)` when utilisation is ≥ the warning threshold but < 100%.
     /// - `Exceeded` when any limit is ≥ 100%.
     pub fn check(&self, provider: &str, config: &QuotaConfig) -> QuotaCheckResult {
         // No limits configured — always Ok.
@@ -175,52 +473,89 @@ impl QuotaStore {
 
         let current_key = Self::current_period_key(&config.period);
 
-        let guard = match self.state.lock() {
-            Ok(g) => g,
-            Err(poisoned) => {
-                tracing::warn!("quota state lock poisoned, recovering");
-                poisoned.into_inner()
-            }
-        };
-        let usage = match guard.get(provider) {
-            Some(u) if u.period_key == current_key => u,
+        let (cost_usd, tokens) = match self.usage(provider) {
+            Some(u) if u.period_key == current_key => (u.cost_usd, u.tokens),
             // No entry or stale period → nothing recorded yet.
-            _ => return QuotaCheckResult::Ok,
+            _ => (0.0, 0),
         };
 
-        let cost_pct = config
-            .max_cost_usd
-            .map(|max| if max > 0.0 { usage.cost_usd / max } else { 0.0 })
-            .unwrap_or(0.0);
-
-        let token_pct = config
-            .max_tokens
-            .map(|max| {
-                if max > 0 {
-                    usage.tokens as f64 / max as f64
-                } else {
-                    0.0
-                }
-            })
-            .unwrap_or(0.0);
+        let result = evaluate_limits(cost_usd, tokens, config);
+        self.observe(
+            provider,
+            &current_key,
+            &result,
+            utilisation(cost_usd, tokens, config),
+        );
+        result
+    }
 
-        let max_pct = cost_pct.max(token_pct);
+    /// Keep the aggregate counter on `period` (the aggregate quota's period).
+    ///
+    /// Until this is called, usage is not added to the aggregate counter;
+    /// [`QuotaProvider::with_aggregate`] calls it.
+    pub fn set_aggregate_period(&self, period: QuotaPeriod) {
+        *lock_recover(&self.aggregate_period) = Some(period);
+    }
 
-        if max_pct >= 1.0 {
-            QuotaCheckResult::Exceeded
-        } else if max_pct >= WARNING_THRESHOLD {
-            QuotaCheckResult::Warning(max_pct)
-        } else {
-            QuotaCheckResult::Ok
+    /// Check the combined usage of all providers against an aggregate quota.
+    ///
+    /// Reads the [`AGGREGATE_QUOTA_NAME`] counter, which every provider-level
+    /// [`record`](Self::record) adds to on the aggregate period, so providers
+    /// tracked on a different cadence (e.g. daily counters under a monthly
+    /// aggregate) still count in full.
+    pub fn check_aggregate(&self, config: &QuotaConfig) -> QuotaCheckResult {
+        self.check(AGGREGATE_QUOTA_NAME, config)
+    }
+
+    /// Fire the notifier when `result` escalates past the last level notified
+    /// for `name` in this period. Dropping back to `Ok` (e.g. after a reset)
+    /// re-arms the notification.
+    fn observe(&self, name: &str, period_key: &str, result: &QuotaCheckResult, pct: f64) {
+        let level = match result {
+            QuotaCheckResult::Ok => {
+                lock_recover(&self.notified).remove(name);
+                return;
+            }
+            QuotaCheckResult::Warning(_) => QuotaLevel::Warning,
+            QuotaCheckResult::Exceeded => QuotaLevel::Exceeded,
+        };
+
+        {
+            let mut notified = lock_recover(&self.notified);
+            if let Some((key, last)) = notified.get(name) {
+                if key == period_key && *last >= level {
+                    return;
+                }
+            }
+            notified.insert(name.to_string(), (period_key.to_string(), level));
+        }
+
+        let notifier = lock_recover(&self.notifier).clone();
+        if let Some(notifier) = notifier {
+            notifier(&QuotaEvent {
+                provider: name.to_string(),
+                level,
+                utilisation: pct,
+                period_key: period_key.to_string(),
+            });
         }
     }
 
     /// Record usage for a provider, resetting the counter if the period rolled over.
     ///
-    /// Persists the updated state to disk (best-effort; errors are ignored).
+    /// When an aggregate period is set, provider-level usage is also added to
+    /// the aggregate counter (per-model counters are not, as their usage is
+    /// already in the provider total). Persists the updated state
+    /// (best-effort; errors are logged).
     pub fn record(&self, provider: &str, period: &QuotaPeriod, cost_usd: f64, tokens: u64) {
         let cost_usd = cost_usd.max(0.0);
-        let current_key = Self::current_period_key(period);
+        let updates = self.record_targets(provider, period);
+
+        #[cfg(feature = "sqlite-store")]
+        if let Some(conn) = &self.sqlite {
+            sqlite::record(&mut lock_recover(conn), &updates, cost_usd, tokens);
+            return;
+        }
 
         let mut guard = match self.state.lock() {
             Ok(g) => g,
@@ -230,23 +565,23 @@ impl QuotaStore {
             }
         };
 
-        let entry = guard
-            .entry(provider.to_string())
-            .or_insert_with(|| QuotaUsage {
+        for (name, current_key) in updates {
+            let entry = guard.entry(name.to_string()).or_insert_with(|| QuotaUsage {
                 period_key: current_key.clone(),
                 cost_usd: 0.0,
                 tokens: 0,
             });
 
-        // Reset if the period has rolled over.
-        if entry.period_key != current_key {
-            entry.period_key = current_key;
-            entry.cost_usd = 0.0;
-            entry.tokens = 0;
-        }
+            // Reset if the period has rolled over.
+            if entry.period_key != current_key {
+                entry.period_key = current_key;
+                entry.cost_usd = 0.0;
+                entry.tokens = 0;
+            }
 
-        entry.cost_usd += cost_usd;
-        entry.tokens += tokens;
+            entry.cost_usd += cost_usd;
+            entry.tokens += tokens;
+        }
 
         // Persist best-effort — drop the guard first to keep the critical
         // section as short as possible.
@@ -256,8 +591,41 @@ impl QuotaStore {
         persist_state(&self.path, &snapshot);
     }
 
+    /// Counters a [`record`](Self::record) for `provider` updates, with the
+    /// current period key of each.
+    fn record_targets<'a>(
+        &self,
+        provider: &'a str,
+        period: &QuotaPeriod,
+    ) -> Vec<(&'a str, String)> {
+        let mut targets = vec![(provider, Self::current_period_key(period))];
+        let aggregate_period = lock_recover(&self.aggregate_period).clone();
+        if let Some(aggregate_period) = aggregate_period {
+            if !provider.contains(MODEL_KEY_SEPARATOR) && provider != AGGREGATE_QUOTA_NAME {
+                targets.push((
+                    AGGREGATE_QUOTA_NAME,
+                    Self::current_period_key(&aggregate_period),
+                ));
+            }
+        }
+        targets
+    }
+
+    /// Current usage entry for a single provider, if any.
+    fn usage(&self, provider: &str) -> Option<QuotaUsage> {
+        #[cfg(feature = "sqlite-store")]
+        if let Some(conn) = &self.sqlite {
+            return sqlite::load(&lock_recover(conn), provider);
+        }
+        lock_recover(&self.state).get(provider).cloned()
+    }
+
     /// Return a point-in-time snapshot of all provider usage entries.
     pub fn snapshot(&self) -> HashMap<String, QuotaUsage> {
+        #[cfg(feature = "sqlite-store")]
+        if let Some(conn) = &self.sqlite {
+            return sqlite::load_all(&lock_recover(conn));
+        }
         let guard = match self.state.lock() {
             Ok(g) => g,
             Err(poisoned) => {
@@ -268,8 +636,19 @@ impl QuotaStore {
         guard.clone()
     }
 
-    /// Reset usage for a single provider and persist the change.
-    pub fn reset_provider(&self, name: &str) {
+    /// Reset usage for a single provider, including its per-model counters,
+    /// and persist the change.
+    ///
+    /// The aggregate counter keeps that usage; reset it by its own name
+    /// ([`AGGREGATE_QUOTA_NAME`]).
+    ///
+    /// Returns `false` (and leaves the file untouched) if the provider has no
+    /// recorded usage.
+    pub fn reset(&self, name: &str) -> bool {
+        #[cfg(feature = "sqlite-store")]
+        if let Some(conn) = &self.sqlite {
+            return sqlite::delete(&lock_recover(conn), name);
+        }
         let mut guard = match self.state.lock() {
             Ok(g) => g,
             Err(poisoned) => {
@@ -277,14 +656,27 @@ impl QuotaStore {
                 poisoned.into_inner()
             }
         };
-        guard.remove(name);
+        let model_prefix = format!("{}{}", name, MODEL_KEY_SEPARATOR);
+        let before = guard.len();
+        guard.retain(|key, _| key != name && !key.starts_with(&model_prefix));
+        if guard.len() == before {
+            return false;
+        }
         let snapshot: HashMap<String, QuotaUsage> = guard.clone();
         drop(guard);
         persist_state(&self.path, &snapshot);
+        true
     }
 
     /// Reset usage for all providers and persist the change.
-    pub fn reset_all(&self) {
+    ///
+    /// Returns the number of counters cleared, including per-model counters
+    /// and the aggregate counter.
+    pub fn reset_all(&self) -> usize {
+        #[cfg(feature = "sqlite-store")]
+        if let Some(conn) = &self.sqlite {
+            return sqlite::clear(&lock_recover(conn));
+        }
         let mut guard = match self.state.lock() {
             Ok(g) => g,
             Err(poisoned) => {
@@ -292,9 +684,11 @@ impl QuotaStore {
                 poisoned.into_inner()
             }
         };
+        let cleared = guard.len();
         guard.clear();
         drop(guard);
         persist_state(&self.path, &HashMap::new());
+        cleared
     }
 }
 
@@ -305,8 +699,9 @@ impl QuotaStore {
 /// A decorator [`LLMProvider`] that enforces per-provider usage quotas.
 ///
 /// `QuotaProvider` wraps an inner provider and checks the configured quota
-/// before forwarding each `chat()` / `chat_stream()` call. After a successful
-/// `chat()` call it records the token usage in the shared [`QuotaStore`].
+/// before forwarding each `chat()` / `chat_stream()` call. It records the
+/// token usage of each successful `chat()` response, and of each stream's
+/// final `Done` event, in the shared [`QuotaStore`].
 ///
 /// # Quota Actions
 /// - [`QuotaAction::Reject`] — return [`crate::error::ZeptoError::QuotaRejected`]
@@ -314,11 +709,23 @@ impl QuotaStore {
 /// - [`QuotaAction::Fallback`] — return [`crate::error::ZeptoError::QuotaExceeded`]
 ///   so that a surrounding `FallbackProvider` can catch it and route to the secondary.
 /// - [`QuotaAction::Warn`] — log a warning and allow the request through.
+///
+/// With [`QuotaConfig::downgrade_model`] set, requests made while the
+/// provider's usage is in the warning band use that model instead of the
+/// requested one.
+///
+/// An aggregate quota set with [`QuotaProvider::with_aggregate`] is checked
+/// first. Exceeding it rejects the request with `QuotaRejected` even when the
+/// provider has headroom of its own (unless its action is `Warn`); falling
+/// back would not help because every provider shares the same total.
 pub struct QuotaProvider {
     inner: Box<dyn crate::providers::LLMProvider>,
     provider_name: String,
     config: QuotaConfig,
+    aggregate: Option<QuotaConfig>,
     store: Arc<QuotaStore>,
+    /// Whether requests are currently sent to the downgrade model.
+    downgraded: AtomicBool,
 }
 
 impl QuotaProvider {
@@ -333,77 +740,184 @@ impl QuotaProvider {
             inner,
             provider_name: provider_name.to_string(),
             config,
+            aggregate: None,
             store,
+            downgraded: AtomicBool::new(false),
         }
     }
 
+    /// Also enforce an org-wide quota across all providers sharing the store.
+    pub fn with_aggregate(mut self, aggregate: QuotaConfig) -> Self {
+        self.store.set_aggregate_period(aggregate.period.clone());
+        self.aggregate = Some(aggregate);
+        self
+    }
+
     /// Check whether the current usage is within quota and enforce the
     /// configured action when it is exceeded.
-    fn check_and_enforce(&self) -> crate::error::Result<()> {
-        match self.store.check(&self.provider_name, &self.config) {
+    ///
+    /// `model` (or the provider default) selects a `per_model` entry; the
+    /// request must be within both that entry and the provider-level limits.
+    ///
+    /// Returns the downgrade model to use instead of the requested one, if
+    /// usage is in the warning band and one is configured.
+    fn check_and_enforce(&self, model: Option<&str>) -> crate::error::Result<Option<&str>> {
+        if let Some(aggregate) = &self.aggregate {
+            match self.store.check_aggregate(aggregate) {
+                QuotaCheckResult::Ok => {}
+                QuotaCheckResult::Warning(pct) => {
+                    tracing::warn!(
+                        utilisation = %format!("{:.0}%", pct * 100.0),
+                        "aggregate quota warning: approaching limit",
+                    );
+                }
+                QuotaCheckResult::Exceeded => {
+                    if aggregate.action == QuotaAction::Warn {
+                        tracing::warn!(
+                            provider = %self.provider_name,
+                            "aggregate quota exceeded (action=warn): allowing request through",
+                        );
+                    } else {
+                        return Err(crate::error::ZeptoError::QuotaRejected(format!(
+                            "aggregate {} quota exceeded across all providers",
+                            period_label(&aggregate.period)
+                        )));
+                    }
+                }
+            }
+        }
+
+        let model = model.unwrap_or(self.inner.default_model());
+        let mut checks = Vec::with_capacity(2);
+        if let Some(model_config) = self.config.per_model.get(model) {
+            checks.push((
+                QuotaStore::model_key(&self.provider_name, model),
+                model_config,
+            ));
+        }
+        checks.push((self.provider_name.clone(), &self.config));
+
+        let mut downgrade = None;
+        for (subject, config) in checks {
+            let subject_downgrade = self.enforce(&subject, config)?;
+            downgrade = downgrade.or(subject_downgrade);
+        }
+        self.note_downgrade(downgrade);
+        Ok(downgrade)
+    }
+
+    /// Check one quota subject (the provider or one of its models) and apply
+    /// its action when exceeded. Returns its downgrade model while usage is in
+    /// the warning band.
+    fn enforce<'a>(
+        &self,
+        subject: &str,
+        config: &'a QuotaConfig,
+    ) -> crate::error::Result<Option<&'a str>> {
+        let mut downgrade = None;
+        match self.store.check(subject, config) {
             QuotaCheckResult::Ok => {}
             QuotaCheckResult::Warning(pct) => {
                 tracing::warn!(
-                    provider = %self.provider_name,
+                    provider = %subject,
                     utilisation = %format!("{:.0}%", pct * 100.0),
                     "quota warning: approaching limit",
                 );
+                downgrade = config.downgrade_model.as_deref();
             }
-            QuotaCheckResult::Exceeded => match self.config.action {
+            QuotaCheckResult::Exceeded => match config.action {
                 QuotaAction::Warn => {
                     tracing::warn!(
-                        provider = %self.provider_name,
+                        provider = %subject,
                         "quota exceeded (action=warn): allowing request through",
                     );
                 }
                 QuotaAction::Reject => {
-                    let period = match self.config.period {
-                        QuotaPeriod::Monthly => "monthly",
-                        QuotaPeriod::Daily => "daily",
-                    };
                     return Err(crate::error::ZeptoError::QuotaRejected(format!(
                         "{} {} quota exceeded (hard reject)",
-                        self.provider_name, period
+                        subject,
+                        period_label(&config.period)
                     )));
                 }
                 // Fallback surfaces QuotaExceeded so a surrounding FallbackProvider
                 // can catch it and route to the secondary provider.
                 QuotaAction::Fallback => {
-                    let period = match self.config.period {
-                        QuotaPeriod::Monthly => "monthly",
-                        QuotaPeriod::Daily => "daily",
-                    };
                     return Err(crate::error::ZeptoError::QuotaExceeded(format!(
                         "{} {} quota exceeded",
-                        self.provider_name, period
+                        subject,
+                        period_label(&config.period)
                     )));
                 }
             },
         }
-        Ok(())
+        Ok(downgrade)
+    }
+
+    /// Log when requests switch to or back from the downgrade model.
+    fn note_downgrade(&self, downgrade: Option<&str>) {
+        let was_downgraded = self.downgraded.swap(downgrade.is_some(), Ordering::Relaxed);
+        match (was_downgraded, downgrade) {
+            (false, Some(model)) => tracing::info!(
+                provider = %self.provider_name,
+                model = %model,
+                "quota warning threshold reached: downgrading requests to cheaper model",
+            ),
+            (true, None) => tracing::info!(
+                provider = %self.provider_name,
+                "quota usage back below warning threshold: restoring requested model",
+            ),
+            _ => {}
+        }
+    }
+
+    /// The model a request is sent to, and the store counters (with their
+    /// periods) its usage counts towards: always the provider, plus the
+    /// model's own counter when it has a `per_model` entry.
+    fn usage_targets(&self, sent_model: Option<&str>) -> (String, Vec<(String, QuotaPeriod)>) {
+        let model = sent_model.unwrap_or(self.inner.default_model());
+        let mut targets = vec![(self.provider_name.clone(), self.config.period.clone())];
+        if let Some(model_config) = self.config.per_model.get(model) {
+            targets.push((
+                QuotaStore::model_key(&self.provider_name, model),
+                model_config.period.clone(),
+            ));
+        }
+        (model.to_string(), targets)
     }
 
     /// Record token usage from a successful `chat()` response.
-    ///
-    /// Uses [`crate::utils::cost::estimate_cost`] to convert token counts to a
-    /// USD cost estimate. Falls back to 0.0 for unknown models (still records
-    /// the token count).
-    fn record_usage(&self, response: &crate::providers::LLMResponse) {
+    fn record_usage(&self, response: &crate::providers::LLMResponse, sent_model: Option<&str>) {
         let Some(usage) = &response.usage else {
             return;
         };
-        let tokens = u64::from(usage.total_tokens);
-        let cost_usd = crate::utils::cost::estimate_cost(
-            self.inner.default_model(),
-            usage.prompt_tokens,
-            usage.completion_tokens,
-            &HashMap::new(),
-        )
-        .unwrap_or(0.0);
+        let (model, targets) = self.usage_targets(sent_model);
+        record_token_usage(&self.store, &targets, &model, usage);
+    }
+}
 
-        if cost_usd > 0.0 || tokens > 0 {
-            self.store
-                .record(&self.provider_name, &self.config.period, cost_usd, tokens);
+/// Record `usage` of `model` under each of the `targets` counters in `store`.
+///
+/// Uses [`crate::utils::cost::estimate_cost`] to convert token counts to a
+/// USD cost estimate. Falls back to 0.0 for unknown models (still records
+/// the token count).
+fn record_token_usage(
+    store: &QuotaStore,
+    targets: &[(String, QuotaPeriod)],
+    model: &str,
+    usage: &crate::providers::Usage,
+) {
+    let tokens = u64::from(usage.total_tokens);
+    let cost_usd = crate::utils::cost::estimate_cost(
+        model,
+        usage.prompt_tokens,
+        usage.completion_tokens,
+        &HashMap::new(),
+    )
+    .unwrap_or(0.0);
+
+    if cost_usd > 0.0 || tokens > 0 {
+        for (name, period) in targets {
+            store.record(name, period, cost_usd, tokens);
         }
     }
 }
@@ -427,6 +941,10 @@ impl crate::providers::LLMProvider for QuotaProvider {
         self.inner.default_model()
     }
 
+    fn supports_streaming(&self) -> bool {
+        self.inner.supports_streaming()
+    }
+
     async fn chat(
         &self,
         messages: Vec<crate::session::Message>,
@@ -434,9 +952,13 @@ impl crate::providers::LLMProvider for QuotaProvider {
         model: Option<&str>,
         options: crate::providers::ChatOptions,
     ) -> crate::error::Result<crate::providers::LLMResponse> {
-        self.check_and_enforce()?;
-        let response = self.inner.chat(messages, tools, model, options).await?;
-        self.record_usage(&response);
+        let downgrade = self.check_and_enforce(model)?;
+        let sent_model = downgrade.or(model);
+        let response = self
+            .inner
+            .chat(messages, tools, sent_model, options)
+            .await?;
+        self.record_usage(&response, sent_model);
         Ok(response)
     }
 
@@ -447,13 +969,55 @@ impl crate::providers::LLMProvider for QuotaProvider {
         model: Option<&str>,
         options: crate::providers::ChatOptions,
     ) -> crate::error::Result<tokio::sync::mpsc::Receiver<crate::providers::StreamEvent>> {
-        self.check_and_enforce()?;
-        // Stream: pass through without usage recording — the stream receiver
-        // is returned immediately, so there is no response to inspect here.
-        // Usage recording for streaming is deferred to future work.
-        self.inner
-            .chat_stream(messages, tools, model, options)
-            .await
+        use crate::providers::StreamEvent;
+
+        let downgrade = self.check_and_enforce(model)?;
+        let sent_model = downgrade.or(model);
+        let mut inner_rx = self
+            .inner
+            .chat_stream(messages, tools, sent_model, options)
+            .await?;
+
+        // Forward events as they arrive and record the usage carried by
+        // `Done`. After the receiver is dropped the inner stream is still
+        // drained (for up to `STREAM_DRAIN_TIMEOUT`), so a client disconnect
+        // does not skip accounting.
+        let (tx, rx) = tokio::sync::mpsc::channel(32);
+        let store = Arc::clone(&self.store);
+        let (cost_model, targets) = self.usage_targets(sent_model);
+        tokio::spawn(async move {
+            let mut drain_deadline = None;
+            loop {
+                let event = match drain_deadline {
+                    None => inner_rx.recv().await,
+                    Some(deadline) => {
+                        match tokio::time::timeout_at(deadline, inner_rx.recv()).await {
+                            Ok(event) => event,
+                            Err(_) => {
+                                tracing::warn!(
+                                    "quota: stream still running {}s after disconnect, usage not recorded",
+                                    STREAM_DRAIN_TIMEOUT.as_secs()
+                                );
+                                return;
+                            }
+                        }
+                    }
+                };
+                let Some(event) = event else {
+                    return;
+                };
+                if let StreamEvent::Done {
+                    usage: Some(usage), ..
+                } = &event
+                {
+                    record_token_usage(&store, &targets, &cost_model, usage);
+                }
+                if drain_deadline.is_none() && tx.send(event).await.is_err() {
+                    drain_deadline = Some(tokio::time::Instant::now() + STREAM_DRAIN_TIMEOUT);
+                }
+            }
+        });
+        Ok(rx)
     }
 
     async fn embed(&self, texts: &[String]) -> crate::error::Result<Vec<Vec<f32>>> {
@@ -465,6 +1029,23 @@ impl crate::providers::LLMProvider for QuotaProvider {
 // Internal helpers
 // ---------------------------------------------------------------------------
 
+fn period_label(period: &QuotaPeriod) -> String {
+    match period {
+        QuotaPeriod::Monthly => "monthly".to_string(),
+        QuotaPeriod::Daily => "daily".to_string(),
+        QuotaPeriod::Weekly => "weekly".to_string(),
+        QuotaPeriod::Custom { days } => format!("{}-day", days),
+    }
+}
+
+/// Lock a mutex, recovering the data if a previous holder panicked.
+fn lock_recover<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|poisoned| {
+        tracing::warn!("quota lock poisoned, recovering");
+        poisoned.into_inner()
+    })
+}
+
 /// Canonical path for the usage file: `~/.zeptoclaw/quota/usage.json`.
 fn dirs_path() -> PathBuf {
     let base = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
@@ -472,7 +1053,7 @@ fn dirs_path() -> PathBuf {
 }
 
 /// Load `HashMap<String, QuotaUsage>` from JSON; returns empty map on error.
-fn load_state(path: &std::path::Path) -> HashMap<String, QuotaUsage> {
+fn load_state(path: &Path) -> HashMap<String, QuotaUsage> {
     let data = match std::fs::read_to_string(path) {
         Ok(s) => s,
         Err(_) => return HashMap::new(),
@@ -481,7 +1062,7 @@ fn load_state(path: &std::path::Path) -> HashMap<String, QuotaUsage> {
 }
 
 /// Persist `state` to `path` (best-effort; logs warnings on failure).
-fn persist_state(path: &std::path::Path, state: &HashMap<String, QuotaUsage>) {
+fn persist_state(path: &Path, state: &HashMap<String, QuotaUsage>) {
     if let Some(parent) = path.parent() {
         if let Err(e) = std::fs::create_dir_all(parent) {
             tracing::warn!("quota: failed to create dir {}: {}", parent.display(), e);
@@ -502,6 +1083,150 @@ fn persist_state(path: &std::path::Path, state: &HashMap<String, QuotaUsage>) {
     }
 }
 
+// ---------------------------------------------------------------------------
+// SQLite backend
+// ---------------------------------------------------------------------------
+
+#[cfg(feature = "sqlite-store")]
+mod sqlite {
+    use std::collections::HashMap;
+    use std::path::Path;
+
+    use rusqlite::{params, Connection, OptionalExtension, Row};
+
+    use super::{load_state, QuotaUsage};
+
+    /// Create the usage table and import `usage.json` on first open.
+    pub(super) fn init(conn: &mut Connection, json_path: &Path) -> rusqlite::Result<()> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS quota_usage (
+                name       TEXT PRIMARY KEY,
+                period_key TEXT NOT NULL,
+                cost_usd   REAL NOT NULL,
+                tokens     INTEGER NOT NULL
+            )",
+            [],
+        )?;
+        let imported = crate::utils::sqlite::import_once(conn, "quota_usage_json", |tx| {
+            let mut rows = 0;
+            for (name, usage) in load_state(json_path) {
+                rows += tx.execute(
+                    "INSERT OR IGNORE INTO quota_usage (name, period_key, cost_usd, tokens)
+                     VALUES (?1, ?2, ?3, ?4)",
+                    params![
+                        name,
+                        usage.period_key,
+                        usage.cost_usd,
+                        to_sql_tokens(usage.tokens)
+                    ],
+                )?;
+            }
+            Ok(rows)
+        })?;
+        if let Some(rows) = imported.filter(|rows| *rows > 0) {
+            tracing::info!(rows, from = %json_path.display(), "quota: imported JSON usage into SQLite");
+        }
+        Ok(())
+    }
+
+    /// Add usage to each `(name, period_key)` counter in one transaction,
+    /// restarting a counter when its stored period differs from `period_key`.
+    pub(super) fn record(
+        conn: &mut Connection,
+        updates: &[(&str, String)],
+        cost_usd: f64,
+        tokens: u64,
+    ) {
+        let result = conn.transaction().and_then(|tx| {
+            for (name, period_key) in updates {
+                tx.execute(
+                    "INSERT INTO quota_usage (name, period_key, cost_usd, tokens)
+                     VALUES (?1, ?2, ?3, ?4)
+                     ON CONFLICT(name) DO UPDATE SET
+                         cost_usd = CASE WHEN period_key = excluded.period_key
+                                         THEN cost_usd + excluded.cost_usd
+                                         ELSE excluded.cost_usd END,
+                         tokens = CASE WHEN period_key = excluded.period_key
+                                       THEN tokens + excluded.tokens
+                                       ELSE excluded.tokens END,
+                         period_key = excluded.period_key",
+                    params![name, period_key, cost_usd, to_sql_tokens(tokens)],
+                )?;
+            }
+            tx.commit()
+        });
+        if let Err(e) = result {
+            let provider = updates.first().map_or("", |(name, _)| *name);
+            tracing::warn!(provider, error = %e, "quota: failed to record usage");
+        }
+    }
+
+    pub(super) fn load(conn: &Connection, name: &str) -> Option<QuotaUsage> {
+        conn.query_row(
+            "SELECT period_key, cost_usd, tokens FROM quota_usage WHERE name = ?1",
+            [name],
+            |row| usage_at(row, 0),
+        )
+        .optional()
+        .unwrap_or_else(|e| {
+            tracing::warn!(provider = name, error = %e, "quota: failed to read usage");
+            None
+        })
+    }
+
+    pub(super) fn load_all(conn: &Connection) -> HashMap<String, QuotaUsage> {
+        query_all(conn).unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "quota: failed to read usage");
+            HashMap::new()
+        })
+    }
+
+    /// Delete `name` and its `name:model` per-model rows.
+    pub(super) fn delete(conn: &Connection, name: &str) -> bool {
+        match conn.execute(
+            "DELETE FROM quota_usage
+             WHERE name = ?1 OR substr(name, 1, length(?1) + 1) = ?1 || ':'",
+            [name],
+        ) {
+            Ok(rows) => rows > 0,
+            Err(e) => {
+                tracing::warn!(provider = name, error = %e, "quota: failed to reset usage");
+                false
+            }
+        }
+    }
+
+    pub(super) fn clear(conn: &Connection) -> usize {
+        conn.execute("DELETE FROM quota_usage", [])
+            .unwrap_or_else(|e| {
+                tracing::warn!(error = %e, "quota: failed to reset usage");
+                0
+            })
+    }
+
+    fn query_all(conn: &Connection) -> rusqlite::Result<HashMap<String, QuotaUsage>> {
+        let mut stmt =
+            conn.prepare("SELECT name, period_key, cost_usd, tokens FROM quota_usage")?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, usage_at(row, 1)?)))?;
+        rows.collect()
+    }
+
+    /// Read `period_key, cost_usd, tokens` starting at column `offset`.
+    fn usage_at(row: &Row<'_>, offset: usize) -> rusqlite::Result<QuotaUsage> {
+        let tokens: i64 = row.get(offset + 2)?;
+        Ok(QuotaUsage {
+            period_key: row.get(offset)?,
+            cost_usd: row.get(offset + 1)?,
+            tokens: tokens.max(0) as u64,
+        })
+    }
+
+    /// SQLite integers are signed; clamp counts that do not fit.
+    fn to_sql_tokens(tokens: u64) -> i64 {
+        i64::try_from(tokens).unwrap_or(i64::MAX)
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -513,10 +1238,7 @@ mod tests {
 
     /// Build a `QuotaStore` rooted in a temporary directory.
     fn store_in_tmpdir(tmp: &TempDir) -> QuotaStore {
-        QuotaStore {
-            state: Mutex::new(HashMap::new()),
-            path: tmp.path().join("usage.json"),
-        }
+        QuotaStore::load_from_path(tmp.path().join("usage.json"))
     }
 
     // --- QuotaConfig defaults ---
@@ -568,6 +1290,74 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_weekly_period_key_rolls_over_on_monday() {
+        let at = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+        let weekly = QuotaPeriod::Weekly;
+        // Sunday 2026-10-18 is the last day of ISO week 42.
+        assert_eq!(
+            QuotaStore::period_key_at(&weekly, at("2026-10-18T23:59:59Z")),
+            "2026-W42"
+        );
+        assert_eq!(
+            QuotaStore::period_key_at(&weekly, at("2026-10-19T00:00:00Z")),
+            "2026-W43"
+        );
+        // ISO week-numbering year differs from the calendar year at the edges.
+        assert_eq!(
+            QuotaStore::period_key_at(&weekly, at("2027-01-01T12:00:00Z")),
+            "2026-W53"
+        );
+        assert_eq!(
+            QuotaStore::period_key_at(&weekly, at("2024-12-30T00:00:00Z")),
+            "2025-W01"
+        );
+    }
+
+    #[test]
+    fn test_custom_period_key_buckets_days_since_epoch() {
+        let at = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+        let fortnight = QuotaPeriod::Custom { days: 14 };
+        // Day 20_734 since the epoch (2026-10-08) starts a 14-day bucket.
+        assert_eq!(
+            QuotaStore::period_key_at(&fortnight, at("2026-10-08T00:00:00Z")),
+            "2026-10-08+14d"
+        );
+        assert_eq!(
+            QuotaStore::period_key_at(&fortnight, at("2026-10-21T23:59:59Z")),
+            "2026-10-08+14d"
+        );
+        assert_eq!(
+            QuotaStore::period_key_at(&fortnight, at("2026-10-22T00:00:00Z")),
+            "2026-10-22+14d"
+        );
+        // A length of 0 behaves like daily buckets.
+        assert_eq!(
+            QuotaStore::period_key_at(&QuotaPeriod::Custom { days: 0 }, at("2026-10-17T08:00:00Z")),
+            "2026-10-17+1d"
+        );
+    }
+
+    #[test]
+    fn test_usage_resets_when_period_key_changes() {
+        let tmp = TempDir::new().unwrap();
+        let store = store_in_tmpdir(&tmp);
+        let cfg = QuotaConfig {
+            max_tokens: Some(1_000),
+            period: QuotaPeriod::Weekly,
+            ..Default::default()
+        };
+        store.record("openai", &cfg.period, 0.0, 1_000);
+        assert_eq!(store.check("openai", &cfg), QuotaCheckResult::Exceeded);
+
+        // Counters from last week no longer count.
+        {
+            let mut state = store.state.lock().unwrap();
+            state.get_mut("openai").unwrap().period_key = "2000-W01".to_string();
+        }
+        assert_eq!(store.check("openai", &cfg), QuotaCheckResult::Ok);
+    }
+
     // --- check() logic ---
 
     #[test]
@@ -617,33 +1407,77 @@ mod tests {
     }
 
     #[test]
-    fn test_check_at_100pct_is_exceeded() {
+    fn test_check_custom_warning_threshold() {
         let tmp = TempDir::new().unwrap();
         let store = store_in_tmpdir(&tmp);
         let cfg = QuotaConfig {
             max_cost_usd: Some(100.0),
-            max_tokens: None,
+            warning_threshold: Some(0.5),
             ..Default::default()
         };
-        store.record("openai", &cfg.period, 100.0, 0);
-        assert_eq!(store.check("openai", &cfg), QuotaCheckResult::Exceeded);
+        store.record("openai", &cfg.period, 55.0, 0);
+        match store.check("openai", &cfg) {
+            QuotaCheckResult::Warning(pct) => assert!((pct - 0.55).abs() < 1e-9),
+            other => panic!("expected Warning at 55% with 0.5 threshold, got {other:?}"),
+        }
+
+        // The same usage is Ok under the default threshold.
+        let default_cfg = QuotaConfig {
+            warning_threshold: None,
+            ..cfg
+        };
+        assert_eq!(store.check("openai", &default_cfg), QuotaCheckResult::Ok);
     }
 
     #[test]
-    fn test_check_over_100pct_is_exceeded() {
+    fn test_default_warning_threshold_is_80pct() {
+        let cfg = QuotaConfig::default();
+        assert_eq!(cfg.warning_threshold, None);
+        assert_eq!(cfg.warning_threshold(), 0.8);
+
         let tmp = TempDir::new().unwrap();
         let store = store_in_tmpdir(&tmp);
         let cfg = QuotaConfig {
             max_cost_usd: Some(100.0),
-            max_tokens: None,
             ..Default::default()
         };
-        store.record("openai", &cfg.period, 150.0, 0);
-        assert_eq!(store.check("openai", &cfg), QuotaCheckResult::Exceeded);
+        store.record("openai", &cfg.period, 79.0, 0);
+        assert_eq!(store.check("openai", &cfg), QuotaCheckResult::Ok);
+        store.record("openai", &cfg.period, 1.0, 0);
+        assert!(matches!(
+            store.check("openai", &cfg),
+            QuotaCheckResult::Warning(_)
+        ));
     }
 
     #[test]
-    fn test_check_token_limit_exceeded() {
+    fn test_check_at_100pct_is_exceeded() {
+        let tmp = TempDir::new().unwrap();
+        let store = store_in_tmpdir(&tmp);
+        let cfg = QuotaConfig {
+            max_cost_usd: Some(100.0),
+            max_tokens: None,
+            ..Default::default()
+        };
+        store.record("openai", &cfg.period, 100.0, 0);
+        assert_eq!(store.check("openai", &cfg), QuotaCheckResult::Exceeded);
+    }
+
+    #[test]
+    fn test_check_over_100pct_is_exceeded() {
+        let tmp = TempDir::new().unwrap();
+        let store = store_in_tmpdir(&tmp);
+        let cfg = QuotaConfig {
+            max_cost_usd: Some(100.0),
+            max_tokens: None,
+            ..Default::default()
+        };
+        store.record("openai", &cfg.period, 150.0, 0);
+        assert_eq!(store.check("openai", &cfg), QuotaCheckResult::Exceeded);
+    }
+
+    #[test]
+    fn test_check_token_limit_exceeded() {
         let tmp = TempDir::new().unwrap();
         let store = store_in_tmpdir(&tmp);
         let cfg = QuotaConfig {
@@ -739,6 +1573,69 @@ mod tests {
         assert!(contents.contains("12.5"), "file should contain cost value");
     }
 
+    // --- reset() logic ---
+
+    #[test]
+    fn test_reset_clears_only_named_provider() {
+        let tmp = TempDir::new().unwrap();
+        let store = store_in_tmpdir(&tmp);
+        store.record("anthropic", &QuotaPeriod::Monthly, 10.0, 1_000);
+        store.record("openai", &QuotaPeriod::Monthly, 5.0, 500);
+
+        assert!(store.reset("anthropic"));
+        let snap = store.snapshot();
+        assert!(!snap.contains_key("anthropic"));
+        assert_eq!(snap["openai"].cost_usd, 5.0);
+        assert_eq!(snap["openai"].tokens, 500);
+
+        // Persisted: a fresh load sees the same state.
+        let reloaded = QuotaStore::load_from_dir(tmp.path()).snapshot();
+        assert!(!reloaded.contains_key("anthropic"));
+        assert!(reloaded.contains_key("openai"));
+
+        // Usage starts from zero again after a reset.
+        store.record("anthropic", &QuotaPeriod::Monthly, 1.0, 10);
+        assert_eq!(store.snapshot()["anthropic"].cost_usd, 1.0);
+        assert_eq!(store.snapshot()["anthropic"].tokens, 10);
+    }
+
+    #[test]
+    fn test_reset_clears_per_model_counters() {
+        let tmp = TempDir::new().unwrap();
+        let store = store_in_tmpdir(&tmp);
+        store.record("nvidia", &QuotaPeriod::Monthly, 1.0, 100);
+        store.record_model("nvidia", "big", &QuotaPeriod::Monthly, 1.0, 100);
+        store.record("nvidia-nim", &QuotaPeriod::Monthly, 1.0, 100);
+
+        assert!(store.reset("nvidia"));
+        let snap = store.snapshot();
+        assert!(!snap.contains_key("nvidia"));
+        assert!(!snap.contains_key("nvidia:big"));
+        assert!(snap.contains_key("nvidia-nim"));
+    }
+
+    #[test]
+    fn test_reset_unknown_provider_is_noop() {
+        let tmp = TempDir::new().unwrap();
+        let store = store_in_tmpdir(&tmp);
+        store.record("openai", &QuotaPeriod::Monthly, 5.0, 500);
+        assert!(!store.reset("nonexistent"));
+        assert_eq!(store.snapshot().len(), 1);
+    }
+
+    #[test]
+    fn test_reset_all_clears_everything() {
+        let tmp = TempDir::new().unwrap();
+        let store = store_in_tmpdir(&tmp);
+        store.record("anthropic", &QuotaPeriod::Monthly, 10.0, 1_000);
+        store.record("openai", &QuotaPeriod::Daily, 5.0, 500);
+
+        assert_eq!(store.reset_all(), 2);
+        assert!(store.snapshot().is_empty());
+        assert!(QuotaStore::load_from_dir(tmp.path()).snapshot().is_empty());
+        assert_eq!(store.reset_all(), 0);
+    }
+
     // --- Serde roundtrips ---
 
     #[test]
@@ -749,6 +1646,24 @@ mod tests {
         assert_eq!(decoded, QuotaPeriod::Daily);
     }
 
+    #[test]
+    fn test_quota_period_weekly_and_custom_serde() {
+        assert_eq!(
+            serde_json::to_string(&QuotaPeriod::Weekly).unwrap(),
+            "\"weekly\""
+        );
+        let custom = QuotaPeriod::Custom { days: 10 };
+        let encoded = serde_json::to_string(&custom).unwrap();
+        assert_eq!(encoded, r#"{"custom":{"days":10}}"#);
+        assert_eq!(
+            serde_json::from_str::<QuotaPeriod>(&encoded).unwrap(),
+            custom
+        );
+        let cfg: QuotaConfig =
+            serde_json::from_str(r#"{"period":"weekly","action":"reject"}"#).unwrap();
+        assert_eq!(cfg.period, QuotaPeriod::Weekly);
+    }
+
     #[test]
     fn test_quota_action_serde() {
         let encoded = serde_json::to_string(&QuotaAction::Fallback).unwrap();
@@ -764,12 +1679,68 @@ mod tests {
             max_tokens: Some(100_000),
             period: QuotaPeriod::Daily,
             action: QuotaAction::Warn,
+            warning_threshold: Some(0.6),
+            downgrade_model: None,
+            per_model: HashMap::from([(
+                "gpt-4o".to_string(),
+                QuotaConfig {
+                    max_cost_usd: Some(5.0),
+                    ..Default::default()
+                },
+            )]),
         };
         let json = serde_json::to_string(&original).unwrap();
         let decoded: QuotaConfig = serde_json::from_str(&json).unwrap();
         assert_eq!(decoded, original);
     }
 
+    #[test]
+    fn test_quota_config_warning_threshold_serde() {
+        // Configs written before the field existed still parse.
+        let legacy: QuotaConfig =
+            serde_json::from_str(r#"{"max_cost_usd":10.0,"period":"daily","action":"warn"}"#)
+                .unwrap();
+        assert_eq!(legacy.warning_threshold, None);
+        assert_eq!(legacy.warning_threshold(), DEFAULT_WARNING_THRESHOLD);
+
+        // `None` is omitted on output, so rewritten configs stay unchanged.
+        let json = serde_json::to_string(&legacy).unwrap();
+        assert!(!json.contains("warning_threshold"), "{json}");
+
+        let custom: QuotaConfig = serde_json::from_str(
+            r#"{"period":"monthly","action":"reject","warning_threshold":0.9}"#,
+        )
+        .unwrap();
+        let decoded: QuotaConfig =
+            serde_json::from_str(&serde_json::to_string(&custom).unwrap()).unwrap();
+        assert_eq!(decoded.warning_threshold, Some(0.9));
+    }
+
+    #[test]
+    fn test_out_of_range_warning_threshold_falls_back() {
+        for threshold in [0.0, 1.0, 1.5, -0.2, f64::NAN] {
+            let cfg = QuotaConfig {
+                warning_threshold: Some(threshold),
+                ..Default::default()
+            };
+            assert_eq!(cfg.warning_threshold(), DEFAULT_WARNING_THRESHOLD);
+        }
+
+        // 85% usage: Warning under the default, not silently Ok under 1.5.
+        let tmp = TempDir::new().unwrap();
+        let store = store_in_tmpdir(&tmp);
+        let cfg = QuotaConfig {
+            max_cost_usd: Some(100.0),
+            warning_threshold: Some(1.5),
+            ..Default::default()
+        };
+        store.record("openai", &cfg.period, 85.0, 0);
+        assert!(matches!(
+            store.check("openai", &cfg),
+            QuotaCheckResult::Warning(_)
+        ));
+    }
+
     // --- Additional robustness tests ---
 
     #[test]
@@ -832,13 +1803,201 @@ mod tests {
         }
     }
 
+    // --- check_aggregate() logic ---
+
+    #[test]
+    fn test_check_aggregate_sums_current_period_usage() {
+        let tmp = TempDir::new().unwrap();
+        let store = store_in_tmpdir(&tmp);
+        let aggregate = QuotaConfig {
+            max_cost_usd: Some(100.0),
+            ..Default::default()
+        };
+        store.set_aggregate_period(aggregate.period.clone());
+        store.record("anthropic", &QuotaPeriod::Monthly, 30.0, 0);
+        store.record("openai", &QuotaPeriod::Monthly, 30.0, 0);
+        assert_eq!(store.check_aggregate(&aggregate), QuotaCheckResult::Ok);
+
+        store.record("groq", &QuotaPeriod::Monthly, 45.0, 0);
+        assert_eq!(
+            store.check_aggregate(&aggregate),
+            QuotaCheckResult::Exceeded
+        );
+    }
+
+    #[test]
+    fn test_check_aggregate_ignores_stale_periods() {
+        let tmp = TempDir::new().unwrap();
+        let store = store_in_tmpdir(&tmp);
+        store.state.lock().unwrap().insert(
+            AGGREGATE_QUOTA_NAME.to_string(),
+            QuotaUsage {
+                period_key: "2000-01".to_string(),
+                cost_usd: 500.0,
+                tokens: 0,
+            },
+        );
+        let aggregate = QuotaConfig {
+            max_cost_usd: Some(100.0),
+            ..Default::default()
+        };
+        assert_eq!(store.check_aggregate(&aggregate), QuotaCheckResult::Ok);
+    }
+
+    #[test]
+    fn test_check_aggregate_counts_providers_on_other_periods() {
+        let tmp = TempDir::new().unwrap();
+        let store = store_in_tmpdir(&tmp);
+        let aggregate = QuotaConfig {
+            max_cost_usd: Some(100.0),
+            period: QuotaPeriod::Monthly,
+            ..Default::default()
+        };
+        store.set_aggregate_period(aggregate.period.clone());
+
+        // A daily provider whose counter rolled over yesterday still counts
+        // towards this month's aggregate.
+        store.record("groq", &QuotaPeriod::Daily, 70.0, 0);
+        store
+            .state
+            .lock()
+            .unwrap()
+            .get_mut("groq")
+            .unwrap()
+            .period_key = "2000-01-01".to_string();
+        store.record("groq", &QuotaPeriod::Daily, 20.0, 0);
+        store.record("anthropic", &QuotaPeriod::Monthly, 15.0, 0);
+
+        assert_eq!(store.snapshot()["groq"].cost_usd, 20.0);
+        assert_eq!(store.snapshot()[AGGREGATE_QUOTA_NAME].cost_usd, 105.0);
+        assert_eq!(
+            store.check_aggregate(&aggregate),
+            QuotaCheckResult::Exceeded
+        );
+    }
+
+    #[test]
+    fn test_aggregate_counter_only_kept_when_configured() {
+        let tmp = TempDir::new().unwrap();
+        let store = store_in_tmpdir(&tmp);
+        store.record("anthropic", &QuotaPeriod::Monthly, 1.0, 10);
+        assert!(!store.snapshot().contains_key(AGGREGATE_QUOTA_NAME));
+    }
+
+    #[test]
+    fn test_aggregate_counter_skips_per_model_records() {
+        let tmp = TempDir::new().unwrap();
+        let store = store_in_tmpdir(&tmp);
+        store.set_aggregate_period(QuotaPeriod::Monthly);
+        store.record("anthropic", &QuotaPeriod::Monthly, 1.0, 10);
+        store.record_model("anthropic", "opus", &QuotaPeriod::Monthly, 1.0, 10);
+        let aggregate = store.snapshot()[AGGREGATE_QUOTA_NAME].clone();
+        assert_eq!(aggregate.tokens, 10);
+    }
+
+    // --- Notifications ---
+
+    fn collecting_notifier(store: &QuotaStore) -> Arc<Mutex<Vec<QuotaEvent>>> {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&events);
+        store.set_notifier(Arc::new(move |e: &QuotaEvent| {
+            sink.lock().unwrap().push(e.clone());
+        }));
+        events
+    }
+
+    #[test]
+    fn test_notifier_fires_once_per_transition() {
+        let tmp = TempDir::new().unwrap();
+        let store = store_in_tmpdir(&tmp);
+        let events = collecting_notifier(&store);
+        let cfg = QuotaConfig {
+            max_cost_usd: Some(100.0),
+            ..Default::default()
+        };
+
+        store.record("openai", &cfg.period, 50.0, 0);
+        store.check("openai", &cfg);
+        assert!(events.lock().unwrap().is_empty(), "Ok must not notify");
+
+        store.record("openai", &cfg.period, 35.0, 0);
+        for _ in 0..5 {
+            store.check("openai", &cfg);
+        }
+        store.record("openai", &cfg.period, 5.0, 0);
+        store.check("openai", &cfg);
+        {
+            let events = events.lock().unwrap();
+            assert_eq!(events.len(), 1, "repeated Warning checks fire once");
+            assert_eq!(events[0].provider, "openai");
+            assert_eq!(events[0].level, QuotaLevel::Warning);
+            assert!((events[0].utilisation - 0.85).abs() < 1e-9);
+        }
+
+        store.record("openai", &cfg.period, 20.0, 0);
+        for _ in 0..3 {
+            store.check("openai", &cfg);
+        }
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 2, "escalation to Exceeded fires once");
+        assert_eq!(events[1].level, QuotaLevel::Exceeded);
+    }
+
+    #[test]
+    fn test_notifier_rearms_after_reset() {
+        let tmp = TempDir::new().unwrap();
+        let store = store_in_tmpdir(&tmp);
+        let events = collecting_notifier(&store);
+        let cfg = QuotaConfig {
+            max_cost_usd: Some(10.0),
+            ..Default::default()
+        };
+
+        store.record("openai", &cfg.period, 20.0, 0);
+        store.check("openai", &cfg);
+        store.reset("openai");
+        store.check("openai", &cfg);
+        store.record("openai", &cfg.period, 20.0, 0);
+        store.check("openai", &cfg);
+
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 2);
+        assert!(events.iter().all(|e| e.level == QuotaLevel::Exceeded));
+    }
+
+    #[test]
+    fn test_notifier_tracks_providers_and_aggregate_separately() {
+        let tmp = TempDir::new().unwrap();
+        let store = store_in_tmpdir(&tmp);
+        let events = collecting_notifier(&store);
+        let cfg = QuotaConfig {
+            max_cost_usd: Some(10.0),
+            ..Default::default()
+        };
+        store.set_aggregate_period(cfg.period.clone());
+
+        store.record("anthropic", &cfg.period, 9.0, 0);
+        store.record("openai", &cfg.period, 9.0, 0);
+        store.check("anthropic", &cfg);
+        store.check("openai", &cfg);
+        store.check_aggregate(&cfg);
+        store.check_aggregate(&cfg);
+
+        let events = events.lock().unwrap();
+        let names: Vec<_> = events.iter().map(|e| e.provider.as_str()).collect();
+        assert_eq!(names, ["anthropic", "openai", AGGREGATE_QUOTA_NAME]);
+        assert_eq!(events[2].level, QuotaLevel::Exceeded);
+    }
+
     // ---------------------------------------------------------------------------
     // QuotaProvider tests
     // ---------------------------------------------------------------------------
 
     use super::QuotaProvider;
     use crate::error::{Result, ZeptoError};
-    use crate::providers::{ChatOptions, LLMProvider, LLMResponse, ToolDefinition, Usage};
+    use crate::providers::{
+        ChatOptions, LLMProvider, LLMResponse, StreamEvent, ToolDefinition, Usage,
+    };
     use crate::session::Message;
     use async_trait::async_trait;
 
@@ -905,6 +2064,56 @@ mod tests {
         }
     }
 
+    /// A mock streaming provider: two deltas, then `Done` with usage, sent
+    /// with a short pause between events.
+    struct StreamingProvider;
+
+    #[async_trait]
+    impl LLMProvider for StreamingProvider {
+        fn name(&self) -> &str {
+            "mock-stream"
+        }
+
+        fn default_model(&self) -> &str {
+            "claude-sonnet-4-5-20250929"
+        }
+
+        async fn chat(
+            &self,
+            _messages: Vec<Message>,
+            _tools: Vec<ToolDefinition>,
+            _model: Option<&str>,
+            _options: ChatOptions,
+        ) -> Result<LLMResponse> {
+            Ok(LLMResponse::text("ok"))
+        }
+
+        async fn chat_stream(
+            &self,
+            _messages: Vec<Message>,
+            _tools: Vec<ToolDefinition>,
+            _model: Option<&str>,
+            _options: ChatOptions,
+        ) -> Result<tokio::sync::mpsc::Receiver<StreamEvent>> {
+            let (tx, rx) = tokio::sync::mpsc::channel(1);
+            tokio::spawn(async move {
+                let events = [
+                    StreamEvent::Delta("Hel".to_string()),
+                    StreamEvent::Delta("lo".to_string()),
+                    StreamEvent::Done {
+                        content: "Hello".to_string(),
+                        usage: Some(Usage::new(1_000, 500)),
+                    },
+                ];
+                for event in events {
+                    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+                    let _ = tx.send(event).await;
+                }
+            });
+            Ok(rx)
+        }
+    }
+
     /// Helper: build a `QuotaProvider` backed by a `QuotaStore` in a tmpdir.
     fn quota_provider_ok(
         tmp: &TempDir,
@@ -937,6 +2146,275 @@ mod tests {
         vec![Message::user("hi")]
     }
 
+    /// A mock provider that records the model each request was sent with.
+    #[derive(Default)]
+    struct ModelRecordingProvider {
+        models: Arc<Mutex<Vec<Option<String>>>>,
+    }
+
+    #[async_trait]
+    impl LLMProvider for ModelRecordingProvider {
+        fn name(&self) -> &str {
+            "mock-models"
+        }
+
+        fn default_model(&self) -> &str {
+            "gemini-2.5-pro"
+        }
+
+        async fn chat(
+            &self,
+            _messages: Vec<Message>,
+            _tools: Vec<ToolDefinition>,
+            model: Option<&str>,
+            _options: ChatOptions,
+        ) -> Result<LLMResponse> {
+            self.models.lock().unwrap().push(model.map(str::to_string));
+            Ok(LLMResponse::text("ok"))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_quota_provider_downgrades_model_past_warning_threshold() {
+        let tmp = TempDir::new().unwrap();
+        let cfg = QuotaConfig {
+            max_cost_usd: Some(100.0),
+            downgrade_model: Some("gemini-2.5-flash".to_string()),
+            ..Default::default()
+        };
+        let store = Arc::new(store_in_tmpdir(&tmp));
+        let inner = ModelRecordingProvider::default();
+        let models = Arc::clone(&inner.models);
+        let provider =
+            QuotaProvider::new(Box::new(inner), "gemini", cfg.clone(), Arc::clone(&store));
+
+        // Below the warning threshold the requested model is used as-is.
+        store.record("gemini", &cfg.period, 50.0, 0);
+        provider
+            .chat(
+                empty_messages(),
+                vec![],
+                Some("gemini-2.5-pro"),
+                ChatOptions::new(),
+            )
+            .await
+            .unwrap();
+        provider
+            .chat(empty_messages(), vec![], None, ChatOptions::new())
+            .await
+            .unwrap();
+
+        // Crossing the threshold swaps in the cheaper model.
+        store.record("gemini", &cfg.period, 35.0, 0);
+        provider
+            .chat(
+                empty_messages(),
+                vec![],
+                Some("gemini-2.5-pro"),
+                ChatOptions::new(),
+            )
+            .await
+            .unwrap();
+        provider
+            .chat(empty_messages(), vec![], None, ChatOptions::new())
+            .await
+            .unwrap();
+
+        // A reset (new period) restores the normal model.
+        store.reset("gemini");
+        provider
+            .chat(
+                empty_messages(),
+                vec![],
+                Some("gemini-2.5-pro"),
+                ChatOptions::new(),
+            )
+            .await
+            .unwrap();
+
+        let flash = Some("gemini-2.5-flash".to_string());
+        let pro = Some("gemini-2.5-pro".to_string());
+        assert_eq!(
+            *models.lock().unwrap(),
+            vec![pro.clone(), None, flash.clone(), flash, pro]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_quota_provider_records_streaming_usage() {
+        let tmp = TempDir::new().unwrap();
+        let store = Arc::new(store_in_tmpdir(&tmp));
+        let provider = QuotaProvider::new(
+            Box::new(StreamingProvider),
+            "anthropic",
+            QuotaConfig::default(),
+            Arc::clone(&store),
+        );
+
+        let mut rx = provider
+            .chat_stream(empty_messages(), vec![], None, ChatOptions::new())
+            .await
+            .unwrap();
+        let mut seen = Vec::new();
+        while let Some(event) = rx.recv().await {
+            seen.push(match event {
+                StreamEvent::Delta(text) => text,
+                StreamEvent::Done { content, .. } => format!("done:{content}"),
+                other => panic!("unexpected event {other:?}"),
+            });
+        }
+        assert_eq!(seen, ["Hel", "lo", "done:Hello"]);
+
+        let usage = store.snapshot().get("anthropic").cloned().unwrap();
+        assert_eq!(usage.tokens, 1_500);
+        assert!(usage.cost_usd > 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_quota_provider_records_streaming_usage_after_disconnect() {
+        let tmp = TempDir::new().unwrap();
+        let store = Arc::new(store_in_tmpdir(&tmp));
+        let provider = QuotaProvider::new(
+            Box::new(StreamingProvider),
+            "anthropic",
+            QuotaConfig::default(),
+            Arc::clone(&store),
+        );
+
+        let mut rx = provider
+            .chat_stream(empty_messages(), vec![], None, ChatOptions::new())
+            .await
+            .unwrap();
+        // The client reads the first token and goes away.
+        assert!(matches!(rx.recv().await, Some(StreamEvent::Delta(_))));
+        drop(rx);
+
+        let recorded = async {
+            loop {
+                if let Some(usage) = store.snapshot().get("anthropic") {
+                    return usage.tokens;
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+            }
+        };
+        let tokens = tokio::time::timeout(std::time::Duration::from_secs(2), recorded)
+            .await
+            .expect("usage was not recorded after the receiver was dropped");
+        assert_eq!(tokens, 1_500);
+    }
+
+    #[test]
+    fn test_store_tracks_models_independently() {
+        let tmp = TempDir::new().unwrap();
+        let store = store_in_tmpdir(&tmp);
+        let cfg = QuotaConfig {
+            max_tokens: Some(1_000),
+            ..Default::default()
+        };
+        store.record_model("nvidia", "big", &cfg.period, 0.0, 1_000);
+        store.record_model("nvidia", "small", &cfg.period, 0.0, 100);
+
+        assert_eq!(QuotaStore::model_key("nvidia", "big"), "nvidia:big");
+        assert_eq!(
+            store.check_model("nvidia", "big", &cfg),
+            QuotaCheckResult::Exceeded
+        );
+        assert_eq!(
+            store.check_model("nvidia", "small", &cfg),
+            QuotaCheckResult::Ok
+        );
+        // The provider-level counter is separate.
+        assert_eq!(store.check("nvidia", &cfg), QuotaCheckResult::Ok);
+    }
+
+    #[tokio::test]
+    async fn test_quota_provider_per_model_limits() {
+        let tmp = TempDir::new().unwrap();
+        let cfg = QuotaConfig {
+            max_tokens: Some(1_000_000),
+            per_model: HashMap::from([(
+                "big".to_string(),
+                QuotaConfig {
+                    max_tokens: Some(2_000),
+                    ..Default::default()
+                },
+            )]),
+            ..Default::default()
+        };
+        // Each call uses 1_500 tokens.
+        let (provider, store) = quota_provider_ok(&tmp, cfg, 1_000, 500);
+
+        for _ in 0..2 {
+            provider
+                .chat(empty_messages(), vec![], Some("big"), ChatOptions::new())
+                .await
+                .unwrap();
+        }
+        // The expensive model is over its own cap...
+        let err = provider
+            .chat(empty_messages(), vec![], Some("big"), ChatOptions::new())
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&err, ZeptoError::QuotaRejected(msg) if msg.contains("anthropic:big")),
+            "{err:?}"
+        );
+        // ...while the cheap one still runs under the provider-level limit.
+        provider
+            .chat(empty_messages(), vec![], Some("small"), ChatOptions::new())
+            .await
+            .unwrap();
+
+        let snapshot = store.snapshot();
+        assert_eq!(snapshot["anthropic"].tokens, 4_500);
+        assert_eq!(snapshot["anthropic:big"].tokens, 3_000);
+        assert!(!snapshot.contains_key("anthropic:small"));
+
+        // Per-model counters are not double-counted in the aggregate.
+        let aggregate = QuotaConfig {
+            max_tokens: Some(5_000),
+            ..Default::default()
+        };
+        assert!(matches!(
+            store.check_aggregate(&aggregate),
+            QuotaCheckResult::Warning(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_quota_provider_per_model_respects_provider_cap() {
+        let tmp = TempDir::new().unwrap();
+        let cfg = QuotaConfig {
+            max_tokens: Some(3_000),
+            per_model: HashMap::from([(
+                "big".to_string(),
+                QuotaConfig {
+                    max_tokens: Some(1_000_000),
+                    ..Default::default()
+                },
+            )]),
+            ..Default::default()
+        };
+        // Each call uses 1_500 tokens.
+        let (provider, _store) = quota_provider_ok(&tmp, cfg, 1_000, 500);
+
+        for _ in 0..2 {
+            provider
+                .chat(empty_messages(), vec![], Some("big"), ChatOptions::new())
+                .await
+                .unwrap();
+        }
+        // The model has headroom of its own, but the provider total is spent.
+        let err = provider
+            .chat(empty_messages(), vec![], Some("big"), ChatOptions::new())
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&err, ZeptoError::QuotaRejected(msg) if !msg.contains("anthropic:big")),
+            "{err:?}"
+        );
+    }
+
     #[tokio::test]
     async fn test_quota_provider_allows_under_limit() {
         let tmp = TempDir::new().unwrap();
@@ -950,6 +2428,84 @@ mod tests {
         assert_eq!(result.unwrap().content, "ok");
     }
 
+    #[tokio::test]
+    async fn test_quota_provider_aggregate_rejects_despite_provider_headroom() {
+        let tmp = TempDir::new().unwrap();
+        let per_provider = QuotaConfig {
+            max_cost_usd: Some(50.0),
+            action: QuotaAction::Fallback,
+            ..Default::default()
+        };
+        let aggregate = QuotaConfig {
+            max_cost_usd: Some(60.0),
+            ..Default::default()
+        };
+        let store = Arc::new(store_in_tmpdir(&tmp));
+        // Each provider is at 40% of its own limit, but together at 133%
+        // of the aggregate.
+        store.record("anthropic", &per_provider.period, 20.0, 0);
+        store.record("openai", &per_provider.period, 20.0, 0);
+        store.record("groq", &per_provider.period, 40.0, 0);
+        assert_eq!(
+            store.check("anthropic", &per_provider),
+            QuotaCheckResult::Ok
+        );
+
+        let provider = QuotaProvider::new(
+            Box::new(AlwaysOkProvider::new(100, 50)),
+            "anthropic",
+            per_provider.clone(),
+            Arc::clone(&store),
+        )
+        .with_aggregate(aggregate);
+
+        match provider
+            .chat(empty_messages(), vec![], None, ChatOptions::new())
+            .await
+        {
+            Err(ZeptoError::QuotaRejected(msg)) => {
+                assert!(msg.contains("aggregate"), "unexpected message: {msg}");
+            }
+            other => panic!("expected QuotaRejected, got {other:?}"),
+        }
+
+        // Without the aggregate the same provider is allowed through.
+        let unrestricted = QuotaProvider::new(
+            Box::new(AlwaysOkProvider::new(100, 50)),
+            "anthropic",
+            per_provider,
+            Arc::clone(&store),
+        );
+        assert!(unrestricted
+            .chat(empty_messages(), vec![], None, ChatOptions::new())
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_quota_provider_aggregate_warn_allows_request() {
+        let tmp = TempDir::new().unwrap();
+        let aggregate = QuotaConfig {
+            max_cost_usd: Some(1.0),
+            action: QuotaAction::Warn,
+            ..Default::default()
+        };
+        let store = Arc::new(store_in_tmpdir(&tmp));
+        store.record("openai", &aggregate.period, 10.0, 0);
+
+        let provider = QuotaProvider::new(
+            Box::new(AlwaysOkProvider::new(100, 50)),
+            "anthropic",
+            QuotaConfig::default(),
+            Arc::clone(&store),
+        )
+        .with_aggregate(aggregate);
+        assert!(provider
+            .chat(empty_messages(), vec![], None, ChatOptions::new())
+            .await
+            .is_ok());
+    }
+
     #[tokio::test]
     async fn test_quota_provider_rejects_when_exceeded_reject_action() {
         let tmp = TempDir::new().unwrap();
@@ -1099,4 +2655,95 @@ mod tests {
         assert!(debug_str.contains("QuotaProvider"), "{debug_str}");
         assert!(debug_str.contains("anthropic"), "{debug_str}");
     }
+
+    // --- SQLite backend ---
+
+    #[cfg(feature = "sqlite-store")]
+    #[test]
+    fn test_sqlite_concurrent_records_from_two_handles_do_not_lose_updates() {
+        let tmp = TempDir::new().unwrap();
+        let db = tmp.path().join("state.db");
+        let json = tmp.path().join("usage.json");
+        let a = Arc::new(QuotaStore::open_sqlite(&db, &json).unwrap());
+        let b = Arc::new(QuotaStore::open_sqlite(&db, &json).unwrap());
+
+        let handles: Vec<_> = [a.clone(), b.clone()]
+            .into_iter()
+            .map(|store| {
+                std::thread::spawn(move || {
+                    for _ in 0..200 {
+                        store.record("anthropic", &QuotaPeriod::Monthly, 0.5, 10);
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        for store in [&a, &b] {
+            let usage = &store.snapshot()["anthropic"];
+            assert_eq!(usage.tokens, 4_000);
+            assert!((usage.cost_usd - 200.0).abs() < 1e-9);
+        }
+    }
+
+    #[cfg(feature = "sqlite-store")]
+    #[test]
+    fn test_sqlite_imports_json_once() {
+        let tmp = TempDir::new().unwrap();
+        let json_store = store_in_tmpdir(&tmp);
+        json_store.record("openai", &QuotaPeriod::Monthly, 5.0, 500);
+        let db = tmp.path().join("state.db");
+        let json = tmp.path().join("usage.json");
+
+        let store = QuotaStore::open_sqlite(&db, &json).unwrap();
+        assert_eq!(store.snapshot()["openai"].tokens, 500);
+
+        // A reset must not be undone by re-importing on the next open.
+        assert_eq!(store.reset_all(), 1);
+        let reopened = QuotaStore::open_sqlite(&db, &json).unwrap();
+        assert!(reopened.snapshot().is_empty());
+    }
+
+    #[cfg(feature = "sqlite-store")]
+    #[test]
+    fn test_sqlite_record_resets_on_period_change() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join("usage.json"),
+            r#"{"anthropic":{"period_key":"2020-01","cost_usd":999.0,"tokens":999999}}"#,
+        )
+        .unwrap();
+        let store =
+            QuotaStore::open_sqlite(&tmp.path().join("state.db"), &tmp.path().join("usage.json"))
+                .unwrap();
+
+        store.record("anthropic", &QuotaPeriod::Monthly, 5.0, 500);
+        let usage = &store.snapshot()["anthropic"];
+        assert_eq!(
+            usage.period_key,
+            QuotaStore::current_period_key(&QuotaPeriod::Monthly)
+        );
+        assert_eq!(usage.tokens, 500);
+        assert!(store.reset("anthropic"));
+        assert!(!store.reset("anthropic"));
+    }
+
+    #[cfg(feature = "sqlite-store")]
+    #[test]
+    fn test_sqlite_reset_clears_per_model_counters() {
+        let tmp = TempDir::new().unwrap();
+        let store =
+            QuotaStore::open_sqlite(&tmp.path().join("state.db"), &tmp.path().join("usage.json"))
+                .unwrap();
+        store.record("nvidia", &QuotaPeriod::Monthly, 1.0, 100);
+        store.record_model("nvidia", "big", &QuotaPeriod::Monthly, 1.0, 100);
+        store.record("nvidia_nim", &QuotaPeriod::Monthly, 1.0, 100);
+
+        assert!(store.reset("nvidia"));
+        let snap = store.snapshot();
+        assert_eq!(snap.len(), 1);
+        assert!(snap.contains_key("nvidia_nim"));
+    }
 }
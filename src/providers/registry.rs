@@ -47,6 +47,10 @@ pub struct RuntimeProviderSelection {
     pub auth_header: Option<String>,
     /// Effective API version param for this provider.
     pub api_version: Option<String>,
+    /// Thinking budget for providers that support one (Gemini).
+    pub thinking_budget: Option<i32>,
+    /// Whether to return the model's thought trace (Gemini).
+    pub include_thoughts: bool,
 }
 
 /// Provider registry in priority order.
@@ -486,6 +490,8 @@ pub fn resolve_runtime_providers(config: &Config) -> Vec<RuntimeProviderSelectio
             model: provider.and_then(|p| p.model.clone()),
             auth_header: effective_auth_header,
             api_version: effective_api_version,
+            thinking_budget: provider.and_then(|p| p.thinking_budget),
+            include_thoughts: provider.is_some_and(|p| p.include_thoughts),
         });
     }
 
@@ -0,0 +1,371 @@
+//! Capture and replay of the last failed provider request.
+//!
+//! [`FailureCapture`] wraps a single provider. When a call fails it writes
+//! the request — messages, tools, model and options, with PII and secrets
+//! masked by [`PiiRedactor`] — together with the error to
+//! `~/.zeptoclaw/failures/<provider>.json`. Only the most recent failure per
+//! provider is kept.
+//!
+//! `zeptoclaw replay-last <provider>` loads that file and re-issues the
+//! request with [`replay`], to confirm whether a fix or key rotation resolved
+//! the failure.
+
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use tracing::warn;
+
+use crate::config::Config;
+use crate::error::Result;
+use crate::session::{ContentPart, Message};
+use crate::utils::fs::write_atomic;
+
+use super::prompt_log::PiiRedactor;
+use super::{BatchRequest, ChatOptions, LLMProvider, LLMResponse, StreamEvent, ToolDefinition};
+
+/// A failed request as stored on disk.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FailedRequest {
+    /// Provider the request was sent to
+    pub provider: String,
+    /// RFC 3339 time of the failure
+    pub failed_at: String,
+    /// Error the provider returned
+    pub error: String,
+    /// Model requested (`None` = provider default)
+    pub model: Option<String>,
+    /// Redacted prompt
+    pub messages: Vec<Message>,
+    /// Tools offered to the model
+    #[serde(default)]
+    pub tools: Vec<ToolDefinition>,
+    /// Request options
+    #[serde(default)]
+    pub options: ChatOptions,
+}
+
+impl FailedRequest {
+    /// Record a failed call, redacting free text.
+    pub fn capture(
+        provider: &str,
+        error: &str,
+        messages: Vec<Message>,
+        tools: Vec<ToolDefinition>,
+        model: Option<&str>,
+        options: ChatOptions,
+    ) -> Self {
+        let redactor = PiiRedactor::new();
+        let messages = messages
+            .into_iter()
+            .map(|mut message| {
+                message.content = redactor.redact(&message.content);
+                for part in &mut message.content_parts {
+                    if let ContentPart::Text { text } = part {
+                        *text = redactor.redact(text);
+                    }
+                }
+                for call in message.tool_calls.iter_mut().flatten() {
+                    call.arguments = redactor.redact(&call.arguments);
+                }
+                message
+            })
+            .collect();
+        Self {
+            provider: provider.to_string(),
+            failed_at: chrono::Utc::now().to_rfc3339(),
+            error: redactor.redact(error),
+            model: model.map(str::to_string),
+            messages,
+            tools,
+            options,
+        }
+    }
+}
+
+/// One file per provider holding its most recent failure.
+#[derive(Debug, Clone)]
+pub struct FailureStore {
+    dir: PathBuf,
+}
+
+impl FailureStore {
+    /// Store failures under `dir`.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Default location: `~/.zeptoclaw/failures/`.
+    pub fn default_dir() -> PathBuf {
+        Config::dir().join("failures")
+    }
+
+    /// File holding `provider`'s last failure.
+    pub fn path(&self, provider: &str) -> PathBuf {
+        let safe: String = provider
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        self.dir.join(format!("{}.json", safe))
+    }
+
+    /// Replace the stored failure of `failed.provider`.
+    pub fn save(&self, failed: &FailedRequest) -> Result<()> {
+        write_atomic(
+            &self.path(&failed.provider),
+            serde_json::to_string_pretty(failed)?,
+        )?;
+        Ok(())
+    }
+
+    /// The last failure of `provider`, if one was captured.
+    pub fn load(&self, provider: &str) -> Result<Option<FailedRequest>> {
+        match std::fs::read_to_string(self.path(provider)) {
+            Ok(raw) => Ok(Some(serde_json::from_str(&raw)?)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// Re-issue a captured request against `provider`.
+pub async fn replay(provider: &dyn LLMProvider, failed: &FailedRequest) -> Result<LLMResponse> {
+    provider
+        .chat(
+            failed.messages.clone(),
+            failed.tools.clone(),
+            failed.model.as_deref(),
+            failed.options.clone(),
+        )
+        .await
+}
+
+/// Copy of a request kept in case the call fails.
+type Request = (Vec<Message>, Vec<ToolDefinition>, ChatOptions);
+
+/// Provider wrapper that captures its last failed request.
+pub struct FailureCapture {
+    inner: Box<dyn LLMProvider>,
+    provider: String,
+    store: FailureStore,
+}
+
+impl FailureCapture {
+    /// Wrap `inner`, storing failures under the runtime name `provider`.
+    pub fn new(inner: Box<dyn LLMProvider>, provider: &str, store: FailureStore) -> Self {
+        Self {
+            inner,
+            provider: provider.to_string(),
+            store,
+        }
+    }
+
+    /// Redact and store a request that `error` ended.
+    fn record(&self, error: &crate::error::ZeptoError, request: Request, model: Option<&str>) {
+        let (messages, tools, options) = request;
+        let failed = FailedRequest::capture(
+            &self.provider,
+            &error.to_string(),
+            messages,
+            tools,
+            model,
+            options,
+        );
+        if let Err(e) = self.store.save(&failed) {
+            warn!(provider = %self.provider, error = %e, "Failed to capture failed request");
+        }
+    }
+}
+
+#[async_trait]
+impl LLMProvider for FailureCapture {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn default_model(&self) -> &str {
+        self.inner.default_model()
+    }
+
+    fn supports_streaming(&self) -> bool {
+        self.inner.supports_streaming()
+    }
+
+    async fn chat(
+        &self,
+        messages: Vec<Message>,
+        tools: Vec<ToolDefinition>,
+        model: Option<&str>,
+        options: ChatOptions,
+    ) -> Result<LLMResponse> {
+        // The inner provider takes the request by value, so keep a copy; it
+        // is only redacted and written out if the call fails.
+        let request = (messages.clone(), tools.clone(), options.clone());
+        let result = self.inner.chat(messages, tools, model, options).await;
+        if let Err(e) = &result {
+            self.record(e, request, model);
+        }
+        result
+    }
+
+    async fn chat_stream(
+        &self,
+        messages: Vec<Message>,
+        tools: Vec<ToolDefinition>,
+        model: Option<&str>,
+        options: ChatOptions,
+    ) -> Result<mpsc::Receiver<StreamEvent>> {
+        let request = (messages.clone(), tools.clone(), options.clone());
+        let result = self
+            .inner
+            .chat_stream(messages, tools, model, options)
+            .await;
+        if let Err(e) = &result {
+            self.record(e, request, model);
+        }
+        result
+    }
+
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        self.inner.embed(texts).await
+    }
+
+    async fn batch_chat(&self, requests: Vec<BatchRequest>) -> Vec<Result<LLMResponse>> {
+        self.inner.batch_chat(requests).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ZeptoError;
+    use std::sync::{Arc, Mutex};
+
+    type Seen = Arc<Mutex<Vec<(Vec<Message>, Option<String>, Option<u32>)>>>;
+
+    /// Fails while `failing` is set; records every request it sees.
+    struct FlakyProvider {
+        failing: bool,
+        seen: Seen,
+    }
+
+    #[async_trait]
+    impl LLMProvider for FlakyProvider {
+        async fn chat(
+            &self,
+            messages: Vec<Message>,
+            _tools: Vec<ToolDefinition>,
+            model: Option<&str>,
+            options: ChatOptions,
+        ) -> Result<LLMResponse> {
+            self.seen.lock().unwrap().push((
+                messages,
+                model.map(str::to_string),
+                options.max_tokens,
+            ));
+            if self.failing {
+                return Err(ZeptoError::Provider("401 invalid api key".to_string()));
+            }
+            Ok(LLMResponse::text("recovered"))
+        }
+
+        fn default_model(&self) -> &str {
+            "flaky-1"
+        }
+
+        fn name(&self) -> &str {
+            "flaky"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failure_is_captured_redacted() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FailureStore::new(dir.path());
+        let provider = FailureCapture::new(
+            Box::new(FlakyProvider {
+                failing: true,
+                seen: Seen::default(),
+            }),
+            "openai",
+            store.clone(),
+        );
+
+        assert!(store.load("openai").unwrap().is_none());
+        let messages = vec![
+            Message::system("Be brief."),
+            Message::user("Email alice@example.com the key sk-abc12345678901234567890"),
+        ];
+        provider
+            .chat(
+                messages,
+                vec![],
+                Some("gpt-4o"),
+                ChatOptions::new().with_max_tokens(64),
+            )
+            .await
+            .unwrap_err();
+
+        let failed = store.load("openai").unwrap().unwrap();
+        assert_eq!(failed.provider, "openai");
+        assert!(failed.error.contains("invalid api key"));
+        assert_eq!(failed.model.as_deref(), Some("gpt-4o"));
+        assert_eq!(failed.options.max_tokens, Some(64));
+        assert_eq!(failed.messages.len(), 2);
+        let raw = std::fs::read_to_string(store.path("openai")).unwrap();
+        assert!(!raw.contains("alice@example.com"), "{raw}");
+        assert!(!raw.contains("sk-abc12345678901234567890"), "{raw}");
+        assert!(raw.contains("[EMAIL]"), "{raw}");
+
+        // Only the latest failure is kept.
+        provider
+            .chat(
+                vec![Message::user("second")],
+                vec![],
+                None,
+                ChatOptions::new(),
+            )
+            .await
+            .unwrap_err();
+        let failed = store.load("openai").unwrap().unwrap();
+        assert_eq!(failed.messages[0].content, "second");
+        assert!(failed.model.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_replay_reissues_captured_request() {
+        let failed = FailedRequest::capture(
+            "anthropic",
+            "timeout",
+            vec![Message::system("Be brief."), Message::user("status?")],
+            Vec::new(),
+            Some("claude-sonnet-4-5"),
+            ChatOptions::new().with_max_tokens(128),
+        );
+        // Round-trip through the on-disk format.
+        let failed: FailedRequest =
+            serde_json::from_str(&serde_json::to_string(&failed).unwrap()).unwrap();
+
+        let seen = Seen::default();
+        let provider = FlakyProvider {
+            failing: false,
+            seen: Arc::clone(&seen),
+        };
+        let response = replay(&provider, &failed).await.unwrap();
+        assert_eq!(response.content, "recovered");
+
+        let seen = seen.lock().unwrap();
+        let (messages, model, max_tokens) = &seen[0];
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[1].content, "status?");
+        assert_eq!(model.as_deref(), Some("claude-sonnet-4-5"));
+        assert_eq!(*max_tokens, Some(128));
+    }
+}
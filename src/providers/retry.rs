@@ -23,7 +23,7 @@ use tracing::warn;
 use crate::error::{Result, ZeptoError};
 use crate::session::Message;
 
-use super::{ChatOptions, LLMProvider, LLMResponse, StreamEvent, ToolDefinition};
+use super::{BatchRequest, ChatOptions, LLMProvider, LLMResponse, StreamEvent, ToolDefinition};
 
 /// A decorator provider that retries transient LLM errors with exponential backoff.
 ///
@@ -197,6 +197,7 @@ pub fn is_retryable(err: &ZeptoError) -> bool {
                 || lower.contains("503")
                 || lower.contains("504")
                 || lower.contains("timeout")
+                || lower.contains("empty response")
         }
         _ => false,
     }
@@ -260,6 +261,10 @@ impl LLMProvider for RetryProvider {
         self.inner.default_model()
     }
 
+    fn supports_streaming(&self) -> bool {
+        self.inner.supports_streaming()
+    }
+
     async fn chat(
         &self,
         messages: Vec<Message>,
@@ -417,6 +422,12 @@ impl LLMProvider for RetryProvider {
     async fn embed(&self, texts: &[String]) -> crate::error::Result<Vec<Vec<f32>>> {
         self.inner.embed(texts).await
     }
+
+    async fn batch_chat(&self, requests: Vec<BatchRequest>) -> Vec<Result<LLMResponse>> {
+        // Batches report failures per request; retrying the whole batch would
+        // resubmit the requests that already succeeded.
+        self.inner.batch_chat(requests).await
+    }
 }
 
 #[cfg(test)]
@@ -532,6 +543,12 @@ mod tests {
         assert!(is_retryable(&err));
     }
 
+    #[test]
+    fn test_is_retryable_empty_response() {
+        let err = ZeptoError::Provider("empty response from gemini (finish reason: STOP)".into());
+        assert!(is_retryable(&err));
+    }
+
     #[test]
     fn test_is_retryable_400() {
         let err = ZeptoError::Provider("HTTP 400 Bad Request: invalid JSON".to_string());
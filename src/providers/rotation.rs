@@ -300,6 +300,12 @@ impl LLMProvider for RotationProvider {
         self.providers[0].0.default_model()
     }
 
+    /// Streaming is only advertised when every rotation member supports it,
+    /// since any member may be selected for a given request.
+    fn supports_streaming(&self) -> bool {
+        self.providers.iter().all(|(p, _)| p.supports_streaming())
+    }
+
     async fn chat(
         &self,
         messages: Vec<Message>,
@@ -17,6 +17,10 @@ pub enum StreamEvent {
     Delta(String),
     /// Tool calls detected mid-stream (triggers fallback to non-streaming tool loop).
     ToolCalls(Vec<LLMToolCall>),
+    /// One tool call whose arguments finished streaming, surfaced before the
+    /// stream ends so it can be dispatched early. Every such call is repeated
+    /// in the final `ToolCalls` event; consumers may ignore this one.
+    ToolCallReady(LLMToolCall),
     /// Stream complete — carries the full assembled content and usage stats.
     Done {
         content: String,
@@ -111,6 +115,16 @@ pub trait LLMProvider: Send + Sync {
     /// The provider name (e.g., "openai", "anthropic")
     fn name(&self) -> &str;
 
+    /// Whether this provider streams tokens incrementally from `chat_stream()`.
+    ///
+    /// Returns `false` by default, meaning `chat_stream()` falls back to the
+    /// buffered wrapper around `chat()`. Providers that override `chat_stream()`
+    /// with real SSE streaming should return `true`; wrapper providers should
+    /// delegate to their inner provider.
+    fn supports_streaming(&self) -> bool {
+        false
+    }
+
     /// Send a streaming chat completion request.
     ///
     /// Returns an `mpsc::Receiver` that yields `StreamEvent`s.
@@ -153,12 +167,80 @@ pub trait LLMProvider: Send + Sync {
             "Embedding not supported by this provider".into(),
         ))
     }
+
+    /// Run several independent chat requests as one batch.
+    ///
+    /// Returns one result per request, in request order. Batches trade latency
+    /// for cost, so use them for non-interactive workloads only.
+    ///
+    /// The default implementation sends the requests one by one through
+    /// `chat()`. Providers with a native batch API (e.g. Anthropic's Message
+    /// Batches) should override this.
+    async fn batch_chat(&self, requests: Vec<BatchRequest>) -> Vec<Result<LLMResponse>> {
+        let mut results = Vec::with_capacity(requests.len());
+        for request in requests {
+            results.push(
+                self.chat(
+                    request.messages,
+                    request.tools,
+                    request.model.as_deref(),
+                    request.options,
+                )
+                .await,
+            );
+        }
+        results
+    }
+}
+
+/// A single request within a [`LLMProvider::batch_chat`] call.
+#[derive(Debug, Clone)]
+pub struct BatchRequest {
+    /// The conversation to complete
+    pub messages: Vec<Message>,
+    /// Available tools the LLM can call
+    pub tools: Vec<ToolDefinition>,
+    /// Optional model override (uses the provider default if None)
+    pub model: Option<String>,
+    /// Additional options like temperature, max_tokens, etc.
+    pub options: ChatOptions,
+}
+
+impl BatchRequest {
+    /// Create a batch request for `messages` with no tools and default options.
+    pub fn new(messages: Vec<Message>) -> Self {
+        Self {
+            messages,
+            tools: Vec::new(),
+            model: None,
+            options: ChatOptions::default(),
+        }
+    }
+
+    /// Set the tools available to this request.
+    pub fn with_tools(mut self, tools: Vec<ToolDefinition>) -> Self {
+        self.tools = tools;
+        self
+    }
+
+    /// Override the model for this request.
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+
+    /// Set the chat options for this request.
+    pub fn with_options(mut self, options: ChatOptions) -> Self {
+        self.options = options;
+        self
+    }
 }
 
 /// Options for chat completion requests.
 ///
 /// Use the builder pattern to construct options.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ChatOptions {
     /// Maximum number of tokens to generate
     pub max_tokens: Option<u32>,
@@ -168,6 +250,10 @@ pub struct ChatOptions {
     pub top_p: Option<f32>,
     /// Stop sequences that halt generation
     pub stop: Option<Vec<String>>,
+    /// Penalty for tokens that already appeared at all (-2.0 to 2.0)
+    pub presence_penalty: Option<f32>,
+    /// Penalty scaled by how often a token already appeared (-2.0 to 2.0)
+    pub frequency_penalty: Option<f32>,
     /// Output format (text, JSON, or JSON schema)
     pub output_format: OutputFormat,
 }
@@ -257,6 +343,48 @@ impl ChatOptions {
         self
     }
 
+    /// Set the presence penalty.
+    ///
+    /// Positive values discourage reusing any token that has already
+    /// appeared, nudging the model towards new topics. Not every provider
+    /// supports this; those that don't ignore it.
+    ///
+    /// # Arguments
+    /// * `penalty` - Presence penalty (typically -2.0 to 2.0)
+    ///
+    /// # Example
+    /// ```
+    /// use zeptoclaw::providers::ChatOptions;
+    ///
+    /// let options = ChatOptions::new().with_presence_penalty(0.5);
+    /// assert_eq!(options.presence_penalty, Some(0.5));
+    /// ```
+    pub fn with_presence_penalty(mut self, penalty: f32) -> Self {
+        self.presence_penalty = Some(penalty);
+        self
+    }
+
+    /// Set the frequency penalty.
+    ///
+    /// Positive values discourage tokens in proportion to how often they have
+    /// already appeared, which curbs repetition and runaway lists. Not every
+    /// provider supports this; those that don't ignore it.
+    ///
+    /// # Arguments
+    /// * `penalty` - Frequency penalty (typically -2.0 to 2.0)
+    ///
+    /// # Example
+    /// ```
+    /// use zeptoclaw::providers::ChatOptions;
+    ///
+    /// let options = ChatOptions::new().with_frequency_penalty(0.5);
+    /// assert_eq!(options.frequency_penalty, Some(0.5));
+    /// ```
+    pub fn with_frequency_penalty(mut self, penalty: f32) -> Self {
+        self.frequency_penalty = Some(penalty);
+        self
+    }
+
     /// Set the output format for structured responses.
     ///
     /// # Arguments
@@ -285,6 +413,10 @@ pub struct LLMResponse {
     pub tool_calls: Vec<LLMToolCall>,
     /// Token usage information (if available)
     pub usage: Option<Usage>,
+    /// Summarized reasoning trace, when the provider was asked to return one
+    /// (e.g. Gemini `include_thoughts`). Never part of `content`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thoughts: Option<String>,
 }
 
 impl LLMResponse {
@@ -306,6 +438,7 @@ impl LLMResponse {
             content: content.to_string(),
             tool_calls: vec![],
             usage: None,
+            thoughts: None,
         }
     }
 
@@ -328,6 +461,7 @@ impl LLMResponse {
             content: content.to_string(),
             tool_calls,
             usage: None,
+            thoughts: None,
         }
     }
 
@@ -344,6 +478,39 @@ impl LLMResponse {
         !self.tool_calls.is_empty()
     }
 
+    /// Check if this response carries neither text nor tool calls.
+    pub fn is_empty(&self) -> bool {
+        self.content.trim().is_empty() && self.tool_calls.is_empty()
+    }
+
+    /// Turn an empty response into a provider error.
+    ///
+    /// Safety blocks and malformed replies otherwise reach the user as a
+    /// blank answer; an error lets the retry and fallback layers react.
+    /// `finish_reason` is included in the message when the API reported one.
+    ///
+    /// # Example
+    /// ```
+    /// use zeptoclaw::providers::LLMResponse;
+    ///
+    /// let err = LLMResponse::text("").reject_empty("gemini", Some("SAFETY"));
+    /// assert!(err.unwrap_err().to_string().contains("SAFETY"));
+    /// assert!(LLMResponse::text("hi").reject_empty("gemini", None).is_ok());
+    /// ```
+    pub fn reject_empty(self, provider: &str, finish_reason: Option<&str>) -> Result<Self> {
+        if !self.is_empty() {
+            return Ok(self);
+        }
+        let message = match finish_reason {
+            Some(reason) => format!(
+                "empty response from {} (finish reason: {})",
+                provider, reason
+            ),
+            None => format!("empty response from {}", provider),
+        };
+        Err(ZeptoError::Provider(message))
+    }
+
     /// Set usage information for this response.
     ///
     /// # Arguments
@@ -352,6 +519,12 @@ impl LLMResponse {
         self.usage = Some(usage);
         self
     }
+
+    /// Attach a reasoning trace kept separate from the answer.
+    pub fn with_thoughts(mut self, thoughts: impl Into<String>) -> Self {
+        self.thoughts = Some(thoughts.into());
+        self
+    }
 }
 
 /// A tool call made by the LLM.
@@ -459,6 +632,7 @@ mod tests {
             content: "Hello".to_string(),
             tool_calls: vec![],
             usage: None,
+            thoughts: None,
         };
         assert_eq!(response.content, "Hello");
         assert!(!response.has_tool_calls());
@@ -846,4 +1020,57 @@ mod tests {
         let result = provider.embed(&[]).await;
         assert!(result.is_err());
     }
+
+    // ====================================================================
+    // batch_chat() tests
+    // ====================================================================
+
+    /// Default impl of batch_chat() runs each request through chat() in order.
+    #[tokio::test]
+    async fn test_batch_chat_default_is_sequential() {
+        struct EchoProvider;
+
+        #[async_trait]
+        impl LLMProvider for EchoProvider {
+            async fn chat(
+                &self,
+                messages: Vec<Message>,
+                _tools: Vec<ToolDefinition>,
+                model: Option<&str>,
+                _options: ChatOptions,
+            ) -> Result<LLMResponse> {
+                let last = messages
+                    .last()
+                    .map(|m| m.content.clone())
+                    .unwrap_or_default();
+                if last == "fail" {
+                    return Err(ZeptoError::Provider("boom".into()));
+                }
+                Ok(LLMResponse::text(&format!(
+                    "{}:{}",
+                    model.unwrap_or("echo"),
+                    last
+                )))
+            }
+            fn default_model(&self) -> &str {
+                "echo"
+            }
+            fn name(&self) -> &str {
+                "echo"
+            }
+        }
+
+        let results = EchoProvider
+            .batch_chat(vec![
+                BatchRequest::new(vec![Message::user("one")]),
+                BatchRequest::new(vec![Message::user("fail")]),
+                BatchRequest::new(vec![Message::user("two")]).with_model("other"),
+            ])
+            .await;
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].as_ref().unwrap().content, "echo:one");
+        assert!(results[1].is_err());
+        assert_eq!(results[2].as_ref().unwrap().content, "other:two");
+    }
 }
@@ -22,6 +22,7 @@ use tracing::debug;
 use crate::error::{Result, ZeptoError};
 use crate::session::{ContentPart, ImageSource, Message, Role};
 
+use super::debug_log;
 use super::gemini::GeminiProvider;
 use super::{parse_provider_error, ChatOptions, LLMProvider, LLMResponse, ToolDefinition};
 
@@ -157,10 +158,7 @@ impl VertexProvider {
     }
 
     fn build_client() -> Client {
-        Client::builder()
-            .timeout(Duration::from_secs(120))
-            .build()
-            .expect("failed to build HTTP client")
+        crate::utils::http::client(Duration::from_secs(120))
     }
 
     /// Get a valid bearer token, refreshing if needed for ADC.
@@ -245,6 +243,15 @@ impl VertexProvider {
         if let Some(top_p) = options.top_p {
             generation_config["topP"] = json!(top_p);
         }
+        if let Some(stop) = options.stop.as_ref().filter(|s| !s.is_empty()) {
+            generation_config["stopSequences"] = json!(stop);
+        }
+        if let Some(penalty) = options.presence_penalty {
+            generation_config["presencePenalty"] = json!(penalty);
+        }
+        if let Some(penalty) = options.frequency_penalty {
+            generation_config["frequencyPenalty"] = json!(penalty);
+        }
 
         let mut body = json!({
             "contents": contents,
@@ -274,34 +281,35 @@ impl LLMProvider for VertexProvider {
 
         debug!("Vertex AI request to model {} in {}", model, self.location);
 
-        let response = self
+        let request = self
             .client
             .post(self.api_url(model))
             .header("Content-Type", "application/json")
             .header("Authorization", format!("Bearer {}", token))
-            .json(&body)
+            .json(&body);
+        debug_log::log_request("vertex", &request);
+
+        let response = request
             .send()
             .await
             .map_err(|e| ZeptoError::Provider(format!("Vertex AI request failed: {}", e)))?;
 
+        let status = response.status().as_u16();
         if response.status().is_success() {
-            let json: Value = response.json().await.map_err(|e| {
+            let body = response.text().await.map_err(|e| {
+                ZeptoError::Provider(format!("Failed to read Vertex AI response: {}", e))
+            })?;
+            debug_log::log_response("vertex", status, &body);
+            let json: Value = serde_json::from_str(&body).map_err(|e| {
                 ZeptoError::Provider(format!("Failed to parse Vertex AI response: {}", e))
             })?;
 
             // Reuse Gemini's response parsing — the format is identical.
-            let content = GeminiProvider::extract_text(&json).unwrap_or_default();
-            let usage = GeminiProvider::extract_usage(&json);
-
-            let mut llm_response = LLMResponse::text(&content);
-            if let Some(u) = usage {
-                llm_response = llm_response.with_usage(u);
-            }
-            return Ok(llm_response);
+            return GeminiProvider::response_from_json("vertex", &json);
         }
 
-        let status = response.status().as_u16();
         let error_text = response.text().await.unwrap_or_default();
+        debug_log::log_response("vertex", status, &error_text);
 
         // Try to extract a useful message from the Vertex AI error body.
         let body_msg = serde_json::from_str::<Value>(&error_text)
@@ -428,6 +436,21 @@ mod tests {
         assert_eq!(gen_config["topP"], 0.75);
     }
 
+    #[test]
+    fn test_build_messages_body_includes_stop_and_penalties() {
+        let provider = VertexProvider::new("p", "us-central1", "t", DEFAULT_VERTEX_MODEL);
+        let options = ChatOptions::new()
+            .with_stop(vec!["END".to_string()])
+            .with_presence_penalty(0.5)
+            .with_frequency_penalty(0.75);
+        let body = provider.build_messages_body(&[Message::user("Hi")], &options);
+
+        let gen_config = &body["generationConfig"];
+        assert_eq!(gen_config["stopSequences"], serde_json::json!(["END"]));
+        assert_eq!(gen_config["presencePenalty"], 0.5);
+        assert_eq!(gen_config["frequencyPenalty"], 0.75);
+    }
+
     #[test]
     fn test_extract_text_reuses_gemini_parser() {
         let response = serde_json::json!({
@@ -462,6 +485,17 @@ mod tests {
         assert_eq!(u.total_tokens, 52);
     }
 
+    #[test]
+    fn test_empty_candidate_is_provider_error() {
+        let response = serde_json::json!({
+            "candidates": [{ "content": { "parts": [] }, "finishReason": "RECITATION" }]
+        });
+        let err = GeminiProvider::response_from_json("vertex", &response).unwrap_err();
+        assert!(matches!(err, ZeptoError::Provider(_)));
+        assert!(err.to_string().contains("empty response from vertex"));
+        assert!(err.to_string().contains("RECITATION"));
+    }
+
     #[test]
     fn test_no_system_instruction_when_no_system_message() {
         let provider = VertexProvider::new("p", "us-central1", "t", DEFAULT_VERTEX_MODEL);
@@ -6,7 +6,9 @@
 use async_trait::async_trait;
 use std::process::Stdio;
 use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
 use tokio::process::Command;
+use tokio::sync::mpsc;
 
 use super::types::{CommandOutput, ContainerConfig, ContainerRuntime, RuntimeError, RuntimeResult};
 
@@ -19,6 +21,49 @@ impl NativeRuntime {
     pub fn new() -> Self {
         Self
     }
+
+    /// Build the `sh -c` command with workdir, env and piped output.
+    fn command(command: &str, config: &ContainerConfig) -> Command {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg(command);
+
+        // Set working directory if specified
+        if let Some(ref workdir) = config.workdir {
+            cmd.current_dir(workdir);
+        }
+
+        // Set environment variables
+        for (key, value) in &config.env {
+            cmd.env(key, value);
+        }
+
+        // Capture output
+        cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
+        cmd
+    }
+}
+
+/// Read `reader` line by line, forwarding each line to `lines` and returning
+/// everything read.
+async fn forward_lines<R: AsyncRead + Unpin>(reader: R, lines: &mpsc::Sender<String>) -> String {
+    let mut reader = BufReader::new(reader);
+    let mut collected = String::new();
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        match reader.read_until(b'\n', &mut buf).await {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {
+                let line = String::from_utf8_lossy(&buf);
+                collected.push_str(&line);
+                // The receiver may have gone away; keep collecting regardless.
+                let _ = lines
+                    .send(line.trim_end_matches(['\r', '\n']).to_string())
+                    .await;
+            }
+        }
+    }
+    collected
 }
 
 #[async_trait]
@@ -37,21 +82,7 @@ impl ContainerRuntime for NativeRuntime {
         command: &str,
         config: &ContainerConfig,
     ) -> RuntimeResult<CommandOutput> {
-        let mut cmd = Command::new("sh");
-        cmd.arg("-c").arg(command);
-
-        // Set working directory if specified
-        if let Some(ref workdir) = config.workdir {
-            cmd.current_dir(workdir);
-        }
-
-        // Set environment variables
-        for (key, value) in &config.env {
-            cmd.env(key, value);
-        }
-
-        // Capture output
-        cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
+        let mut cmd = Self::command(command, config);
 
         // Execute with timeout
         let output = tokio::time::timeout(Duration::from_secs(config.timeout_secs), cmd.output())
@@ -65,6 +96,48 @@ impl ContainerRuntime for NativeRuntime {
             output.status.code(),
         ))
     }
+
+    async fn execute_streaming(
+        &self,
+        command: &str,
+        config: &ContainerConfig,
+        lines: mpsc::Sender<String>,
+    ) -> RuntimeResult<CommandOutput> {
+        let mut cmd = Self::command(command, config);
+        cmd.kill_on_drop(true);
+        let mut child = cmd
+            .spawn()
+            .map_err(|e| RuntimeError::ExecutionFailed(e.to_string()))?;
+        let stdout = child.stdout.take();
+        let stderr = child.stderr.take();
+
+        let run = async {
+            let (stdout, stderr) = tokio::join!(
+                async {
+                    match stdout {
+                        Some(out) => forward_lines(out, &lines).await,
+                        None => String::new(),
+                    }
+                },
+                async {
+                    match stderr {
+                        Some(err) => forward_lines(err, &lines).await,
+                        None => String::new(),
+                    }
+                }
+            );
+            let status = child
+                .wait()
+                .await
+                .map_err(|e| RuntimeError::ExecutionFailed(e.to_string()))?;
+            Ok(CommandOutput::new(stdout, stderr, status.code()))
+        };
+
+        // On timeout the child is dropped and killed.
+        tokio::time::timeout(Duration::from_secs(config.timeout_secs), run)
+            .await
+            .map_err(|_| RuntimeError::Timeout(config.timeout_secs))?
+    }
 }
 
 #[cfg(test)]
@@ -142,4 +215,26 @@ mod tests {
         let result = runtime.execute("sleep 10", &config).await;
         assert!(matches!(result, Err(RuntimeError::Timeout(1))));
     }
+
+    #[tokio::test]
+    async fn test_native_runtime_execute_streaming_forwards_lines() {
+        let runtime = NativeRuntime::new();
+        let config = ContainerConfig::new();
+        let (tx, mut rx) = mpsc::channel(16);
+
+        let output = runtime
+            .execute_streaming("echo one; echo two >&2; echo three", &config, tx)
+            .await
+            .unwrap();
+        assert!(output.success());
+        assert_eq!(output.stdout, "one\nthree\n");
+        assert_eq!(output.stderr, "two\n");
+
+        let mut lines = Vec::new();
+        while let Some(line) = rx.recv().await {
+            lines.push(line);
+        }
+        lines.sort();
+        assert_eq!(lines, vec!["one", "three", "two"]);
+    }
 }
@@ -3,6 +3,7 @@
 use async_trait::async_trait;
 use std::path::PathBuf;
 use thiserror::Error;
+use tokio::sync::mpsc;
 
 /// Errors that can occur during runtime operations
 #[derive(Error, Debug)]
@@ -144,6 +145,22 @@ pub trait ContainerRuntime: Send + Sync {
         command: &str,
         config: &ContainerConfig,
     ) -> RuntimeResult<CommandOutput>;
+
+    /// Execute a command, sending each line of stdout/stderr to `lines` as it
+    /// is produced.
+    ///
+    /// Returns the same `CommandOutput` as [`execute`](Self::execute). The
+    /// default waits for `execute` and sends nothing; runtimes that can observe
+    /// output incrementally override it.
+    async fn execute_streaming(
+        &self,
+        command: &str,
+        config: &ContainerConfig,
+        lines: mpsc::Sender<String>,
+    ) -> RuntimeResult<CommandOutput> {
+        drop(lines);
+        self.execute(command, config).await
+    }
 }
 
 #[cfg(test)]
@@ -5,8 +5,10 @@
 
 pub mod chain_alert;
 pub mod leak_detector;
+pub mod output_filter;
 pub mod policy;
 pub mod sanitizer;
+pub mod secret_guard;
 pub mod taint;
 pub mod validator;
 
@@ -17,6 +19,7 @@ use crate::audit::{log_audit_event, AuditCategory, AuditSeverity};
 use leak_detector::{LeakAction, LeakDetector};
 use policy::{PolicyAction, PolicyEngine};
 use sanitizer::SanitizedOutput;
+use secret_guard::{SecretFinding, SecretGuardAction, SecretGuardConfig};
 use validator::ContentValidator;
 
 // ---------------------------------------------------------------------------
@@ -42,6 +45,10 @@ pub struct SafetyConfig {
     pub allow_private_endpoints: bool,
     /// Taint tracking configuration.
     pub taint: taint::TaintConfig,
+    /// Pre-execution scan of outbound tool arguments for secrets.
+    pub secret_guard: SecretGuardConfig,
+    /// Filter applied to replies before channels send them (off by default).
+    pub output_filter: output_filter::OutputFilterConfig,
 }
 
 impl Default for SafetyConfig {
@@ -53,6 +60,8 @@ impl Default for SafetyConfig {
             max_output_length: 100_000,
             allow_private_endpoints: false,
             taint: taint::TaintConfig::default(),
+            secret_guard: SecretGuardConfig::default(),
+            output_filter: output_filter::OutputFilterConfig::default(),
         }
     }
 }
@@ -143,6 +152,39 @@ impl SafetyLayer {
         self.scan_impl(text, direction, options)
     }
 
+    /// Check a guarded tool's arguments for secrets before it runs.
+    ///
+    /// Returns `None` when the tool is not covered by `safety.secret_guard` or
+    /// the arguments are clean.
+    pub fn check_tool_secrets(
+        &self,
+        tool: &str,
+        args: &serde_json::Value,
+    ) -> Option<SecretFinding> {
+        if !self.config.secret_guard.covers(tool) {
+            return None;
+        }
+        let finding = secret_guard::scan_args(&self.leak_detector, tool, args)?;
+        log_audit_event(
+            AuditCategory::LeakDetection,
+            AuditSeverity::Critical,
+            "secret_in_tool_args",
+            &format!("{} in arguments of '{}'", finding.patterns.join(", "), tool),
+            self.config.secret_guard.action != SecretGuardAction::Warn,
+        );
+        Some(finding)
+    }
+
+    /// Action configured for secrets found by [`Self::check_tool_secrets`].
+    pub fn secret_guard_action(&self) -> SecretGuardAction {
+        self.config.secret_guard.action
+    }
+
+    /// Tool arguments with secrets masked, for approval prompts.
+    pub fn redact_tool_args(&self, args: &serde_json::Value) -> serde_json::Value {
+        secret_guard::redact_args(&self.leak_detector, args)
+    }
+
     fn scan_impl(
         &self,
         text: &str,
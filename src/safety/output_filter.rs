@@ -0,0 +1,413 @@
+//! Outbound content filter for channel replies.
+//!
+//! Public-facing deployments can screen what the agent sends before it
+//! reaches a channel. `safety.output_filter` holds regex rules, each with an
+//! action, and an optional model-based classifier:
+//!
+//! ```json
+//! {
+//!     "safety": {
+//!         "output_filter": {
+//!             "enabled": true,
+//!             "rules": [
+//!                 { "name": "profanity", "pattern": "(?i)\\b(darn|heck)\\b", "action": "redact" },
+//!                 { "name": "competitors", "pattern": "(?i)acme corp", "action": "flag" },
+//!                 { "name": "medical", "pattern": "(?i)\\bdosage\\b", "action": "block" }
+//!             ],
+//!             "classifier": { "enabled": true, "categories": ["hate", "self-harm"] }
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! - `redact` replaces each match with `redaction`.
+//! - `block` withholds the message; `blocked_message` is sent in its place,
+//!   or nothing when it is empty.
+//! - `flag` sends the message unchanged and only records the match.
+//!
+//! Every action is written to the audit log. The classifier only sees
+//! complete messages; while it is attached, streamed partials are held back
+//! so a reply reaches the channel only once it has been classified. A
+//! message it flags is handled with its `action` (`redact` withholds the
+//! whole message, like `block`).
+//! Classification errors let the message through with a warning.
+//! The filter is off by default.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use tracing::{error, warn};
+
+use crate::audit::{log_audit_event, AuditCategory, AuditSeverity};
+use crate::bus::OutboundMessage;
+use crate::error::Result;
+use crate::providers::{ChatOptions, LLMProvider};
+use crate::session::Message;
+
+/// Default replacement for redacted matches.
+pub const DEFAULT_REDACTION: &str = "[filtered]";
+
+/// Default text sent in place of a blocked message.
+pub const DEFAULT_BLOCKED_MESSAGE: &str =
+    "This reply was withheld by the content filter. Please rephrase your request.";
+
+/// What a filter rule does with a matching message.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFilterAction {
+    /// Replace the matched text.
+    #[default]
+    Redact,
+    /// Withhold the whole message.
+    Block,
+    /// Send unchanged, but log the match.
+    Flag,
+}
+
+impl OutputFilterAction {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Redact => "redact",
+            Self::Block => "block",
+            Self::Flag => "flag",
+        }
+    }
+}
+
+/// A regex rule applied to outbound messages.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutputFilterRule {
+    /// Rule name used in logs.
+    pub name: String,
+    /// Regular expression matched against the message text.
+    pub pattern: String,
+    /// What to do on a match.
+    #[serde(default)]
+    pub action: OutputFilterAction,
+}
+
+/// Model-based classification of outbound messages.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OutputClassifierConfig {
+    /// Whether complete messages are sent to the classifier. Streamed replies
+    /// are then delivered whole instead of as live partials.
+    pub enabled: bool,
+    /// Model to classify with (default: the agent's model).
+    pub model: Option<String>,
+    /// Disallowed categories described to the classifier.
+    pub categories: Vec<String>,
+    /// What to do with a message the classifier flags.
+    pub action: OutputFilterAction,
+}
+
+impl Default for OutputClassifierConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            model: None,
+            categories: vec![
+                "hate or harassment".to_string(),
+                "sexual content".to_string(),
+                "violence or self-harm".to_string(),
+            ],
+            action: OutputFilterAction::Block,
+        }
+    }
+}
+
+/// Outbound content filter configuration (`safety.output_filter`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OutputFilterConfig {
+    /// Master switch (off by default).
+    pub enabled: bool,
+    /// Channels the filter applies to; empty means all.
+    pub channels: Vec<String>,
+    /// Regex rules, applied in order.
+    pub rules: Vec<OutputFilterRule>,
+    /// Replacement for redacted matches.
+    pub redaction: String,
+    /// Text sent in place of a blocked message; empty sends nothing.
+    pub blocked_message: String,
+    /// Optional model-based classification.
+    pub classifier: OutputClassifierConfig,
+}
+
+impl Default for OutputFilterConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            channels: Vec::new(),
+            rules: Vec::new(),
+            redaction: DEFAULT_REDACTION.to_string(),
+            blocked_message: DEFAULT_BLOCKED_MESSAGE.to_string(),
+            classifier: OutputClassifierConfig::default(),
+        }
+    }
+}
+
+/// Decides whether a complete message falls in a disallowed category.
+#[async_trait]
+pub trait OutputClassifier: Send + Sync {
+    /// Return the violated category, or `None` when the text is acceptable.
+    async fn classify(&self, text: &str) -> Result<Option<String>>;
+}
+
+/// [`OutputClassifier`] that asks a model.
+pub struct LlmOutputClassifier {
+    provider: Arc<dyn LLMProvider>,
+    model: Option<String>,
+    categories: Vec<String>,
+}
+
+impl LlmOutputClassifier {
+    /// Classify with `provider`, describing `categories` as disallowed.
+    pub fn new(
+        provider: Arc<dyn LLMProvider>,
+        model: Option<String>,
+        categories: Vec<String>,
+    ) -> Self {
+        Self {
+            provider,
+            model,
+            categories,
+        }
+    }
+
+    fn prompt(&self) -> String {
+        format!(
+            "You are a content moderator for a public chat assistant. Decide whether the \
+             message you are given falls into one of these disallowed categories: {}. \
+             Reply with exactly SAFE if it does not; otherwise reply with only the name of \
+             the category.",
+            self.categories.join(", ")
+        )
+    }
+}
+
+#[async_trait]
+impl OutputClassifier for LlmOutputClassifier {
+    async fn classify(&self, text: &str) -> Result<Option<String>> {
+        let options = ChatOptions::new().with_max_tokens(20).with_temperature(0.0);
+        let response = self
+            .provider
+            .chat(
+                vec![Message::system(&self.prompt()), Message::user(text)],
+                Vec::new(),
+                self.model.as_deref(),
+                options,
+            )
+            .await?;
+        let verdict = response.content.lines().next().unwrap_or("").trim();
+        let verdict = verdict.trim_matches(|c: char| !c.is_alphanumeric());
+        if verdict.is_empty() || verdict.eq_ignore_ascii_case("safe") {
+            Ok(None)
+        } else {
+            Ok(Some(verdict.to_string()))
+        }
+    }
+}
+
+/// Compiled outbound content filter.
+pub struct OutputFilter {
+    channels: Vec<String>,
+    rules: Vec<(OutputFilterRule, Regex)>,
+    redaction: String,
+    blocked_message: String,
+    classifier: Option<(Arc<dyn OutputClassifier>, OutputFilterAction)>,
+}
+
+impl OutputFilter {
+    /// Build the filter from config, or `None` when it is disabled.
+    ///
+    /// Rules with an invalid pattern are skipped and logged.
+    pub fn from_config(config: &OutputFilterConfig) -> Option<Self> {
+        if !config.enabled {
+            return None;
+        }
+        let rules = config
+            .rules
+            .iter()
+            .filter_map(|rule| match Regex::new(&rule.pattern) {
+                Ok(regex) => Some((rule.clone(), regex)),
+                Err(e) => {
+                    error!(rule = %rule.name, error = %e, "Invalid output filter pattern, rule skipped");
+                    None
+                }
+            })
+            .collect();
+        Some(Self {
+            channels: config.channels.clone(),
+            rules,
+            redaction: config.redaction.clone(),
+            blocked_message: config.blocked_message.clone(),
+            classifier: None,
+        })
+    }
+
+    /// Also classify complete messages with `classifier`, applying `action`
+    /// to those it flags.
+    pub fn with_classifier(
+        mut self,
+        classifier: Arc<dyn OutputClassifier>,
+        action: OutputFilterAction,
+    ) -> Self {
+        self.classifier = Some((classifier, action));
+        self
+    }
+
+    /// Whether the filter screens messages for `channel`.
+    pub fn applies_to(&self, channel: &str) -> bool {
+        self.channels.is_empty() || self.channels.iter().any(|c| c == channel)
+    }
+
+    /// Screen an outbound message.
+    ///
+    /// Returns the message to send (possibly redacted or replaced by the
+    /// blocked notice), or `None` when nothing should be sent. `complete` is
+    /// false for streamed partials, which are held back (`None`) while a
+    /// classifier is attached since it only screens complete messages.
+    pub async fn apply(&self, mut msg: OutboundMessage, complete: bool) -> Option<OutboundMessage> {
+        if !self.applies_to(&msg.channel) || msg.content.is_empty() {
+            return Some(msg);
+        }
+        if !complete && self.classifier.is_some() {
+            return None;
+        }
+
+        let mut blocked = false;
+        for (rule, regex) in &self.rules {
+            if !regex.is_match(&msg.content) {
+                continue;
+            }
+            self.record(&msg, &rule.name, rule.action);
+            match rule.action {
+                OutputFilterAction::Redact => {
+                    msg.content = regex
+                        .replace_all(&msg.content, self.redaction.as_str())
+                        .into_owned();
+                }
+                OutputFilterAction::Block => blocked = true,
+                OutputFilterAction::Flag => {}
+            }
+        }
+
+        if let (false, true, Some((classifier, action))) = (blocked, complete, &self.classifier) {
+            match classifier.classify(&msg.content).await {
+                Ok(Some(category)) => {
+                    self.record(&msg, &format!("classifier:{}", category), *action);
+                    blocked = *action != OutputFilterAction::Flag;
+                }
+                Ok(None) => {}
+                Err(e) => warn!(error = %e, "Output classifier failed; message sent unclassified"),
+            }
+        }
+
+        if !blocked {
+            return Some(msg);
+        }
+        if self.blocked_message.is_empty() {
+            return None;
+        }
+        msg.content = self.blocked_message.clone();
+        msg.attachments.clear();
+        msg.structured = None;
+        Some(msg)
+    }
+
+    fn record(&self, msg: &OutboundMessage, rule: &str, action: OutputFilterAction) {
+        warn!(
+            channel = %msg.channel,
+            chat_id = %msg.chat_id,
+            rule = %rule,
+            action = action.as_str(),
+            "Output filter matched outbound message"
+        );
+        log_audit_event(
+            AuditCategory::PolicyViolation,
+            AuditSeverity::Warning,
+            &format!("output_filter_{}", action.as_str()),
+            &format!(
+                "Channel: {}, chat: {}, rule: {}",
+                msg.channel, msg.chat_id, rule
+            ),
+            action == OutputFilterAction::Block,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(rules: serde_json::Value) -> OutputFilter {
+        let config: OutputFilterConfig =
+            serde_json::from_value(serde_json::json!({ "enabled": true, "rules": rules })).unwrap();
+        OutputFilter::from_config(&config).unwrap()
+    }
+
+    struct FixedClassifier(Option<&'static str>);
+
+    #[async_trait]
+    impl OutputClassifier for FixedClassifier {
+        async fn classify(&self, _text: &str) -> Result<Option<String>> {
+            Ok(self.0.map(str::to_string))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_redact_rule_replaces_matches() {
+        let filter = filter(serde_json::json!([
+            { "name": "profanity", "pattern": "(?i)\\bdarn\\b" },
+            { "name": "brand", "pattern": "acme", "action": "flag" }
+        ]));
+        let msg = OutboundMessage::new("telegram", "42", "Darn, the acme build is darn slow.");
+        let out = filter.apply(msg, true).await.unwrap();
+        assert_eq!(
+            out.content,
+            "[filtered], the acme build is [filtered] slow."
+        );
+
+        let config = OutputFilterConfig::default();
+        assert!(!config.enabled);
+        assert!(OutputFilter::from_config(&config).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_block_rule_prevents_send() {
+        let mut filter = filter(serde_json::json!([
+            { "name": "medical", "pattern": "(?i)dosage", "action": "block" }
+        ]));
+        let msg = || OutboundMessage::new("slack", "C1", "The usual dosage is 40mg.");
+
+        let out = filter.apply(msg(), true).await.unwrap();
+        assert_eq!(out.content, DEFAULT_BLOCKED_MESSAGE);
+
+        filter.blocked_message.clear();
+        assert!(filter.apply(msg(), true).await.is_none());
+        // Messages to unfiltered channels and clean messages pass untouched.
+        filter.channels = vec!["telegram".to_string()];
+        assert!(filter.apply(msg(), true).await.is_some());
+        let clean = OutboundMessage::new("telegram", "1", "Hello!");
+        assert_eq!(filter.apply(clean, true).await.unwrap().content, "Hello!");
+    }
+
+    #[tokio::test]
+    async fn test_classifier_holds_back_partials() {
+        let filter = filter(serde_json::json!([])).with_classifier(
+            Arc::new(FixedClassifier(Some("violence"))),
+            OutputFilterAction::Block,
+        );
+        let msg = || OutboundMessage::new("discord", "1", "partial text");
+        // Partials cannot be classified, so none reach the channel...
+        assert!(filter.apply(msg(), false).await.is_none());
+        // ...and the complete message is screened before it is sent.
+        assert_eq!(
+            filter.apply(msg(), true).await.unwrap().content,
+            DEFAULT_BLOCKED_MESSAGE
+        );
+    }
+}
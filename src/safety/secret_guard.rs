@@ -0,0 +1,199 @@
+//! Secret guardrail for outbound tool arguments.
+//!
+//! A user may paste a credential that the agent then writes into a file bound
+//! for a repository or sends in an email. Before an outbound tool runs, its
+//! string arguments are scanned with the [`LeakDetector`] patterns used for
+//! output redaction; on a hit the configured [`SecretGuardAction`] decides
+//! whether the call is only logged, blocked, or held for user confirmation.
+//!
+//! Only patterns that redact or block count as secrets — warn-level patterns
+//! such as long hex strings match hashes too often to gate tools on.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::leak_detector::{LeakAction, LeakDetector};
+
+/// What to do when a guarded tool's arguments contain a secret.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SecretGuardAction {
+    /// Log a warning and run the tool.
+    Warn,
+    /// Refuse to run the tool.
+    Block,
+    /// Ask the user through the approval handler; block when there is none.
+    #[default]
+    Confirm,
+}
+
+impl std::str::FromStr for SecretGuardAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "warn" => Ok(Self::Warn),
+            "block" => Ok(Self::Block),
+            "confirm" => Ok(Self::Confirm),
+            other => Err(format!("unknown secret guard action '{}'", other)),
+        }
+    }
+}
+
+/// Secret guardrail configuration (`safety.secret_guard`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SecretGuardConfig {
+    /// Whether tool arguments are scanned before execution.
+    pub enabled: bool,
+    /// Action taken when a secret is found.
+    pub action: SecretGuardAction,
+    /// Tools whose arguments are scanned: those that write or send data out.
+    pub tools: Vec<String>,
+}
+
+impl Default for SecretGuardConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            action: SecretGuardAction::default(),
+            tools: Self::default_tools(),
+        }
+    }
+}
+
+impl SecretGuardConfig {
+    /// Built-in tools that can carry data out of the agent.
+    pub fn default_tools() -> Vec<String> {
+        [
+            "write_file",
+            "edit_file",
+            "git",
+            "google",
+            "message",
+            "whatsapp_send",
+            "http_request",
+            "web_fetch",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+    }
+
+    /// Whether `tool` is guarded.
+    pub fn covers(&self, tool: &str) -> bool {
+        self.enabled && self.tools.iter().any(|t| t.eq_ignore_ascii_case(tool))
+    }
+}
+
+/// Secrets found in a tool call's arguments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecretFinding {
+    /// Tool the arguments were meant for.
+    pub tool: String,
+    /// Names of the matched patterns, deduplicated.
+    pub patterns: Vec<String>,
+}
+
+impl SecretFinding {
+    /// Tool result when the call is blocked.
+    pub fn blocked_message(&self) -> String {
+        format!(
+            "Tool '{}' blocked: its arguments contain a secret ({}). Remove or replace the \
+             secret (e.g. reference an environment variable) and try again.",
+            self.tool,
+            self.patterns.join(", ")
+        )
+    }
+
+    /// Tool result when confirmation is required but nobody can give it.
+    pub fn confirm_message(&self) -> String {
+        format!(
+            "Tool '{}' was not executed: its arguments contain a secret ({}). Ask the user \
+             to confirm they want to send it before retrying without the secret.",
+            self.tool,
+            self.patterns.join(", ")
+        )
+    }
+}
+
+/// Scan the string values in `args` for secrets.
+pub fn scan_args(detector: &LeakDetector, tool: &str, args: &Value) -> Option<SecretFinding> {
+    let mut patterns: Vec<String> = Vec::new();
+    visit_strings(args, &mut |text| {
+        for detection in detector.scan(text) {
+            if detection.action != LeakAction::Warn && !patterns.contains(&detection.pattern_name) {
+                patterns.push(detection.pattern_name);
+            }
+        }
+    });
+    (!patterns.is_empty()).then(|| SecretFinding {
+        tool: tool.to_string(),
+        patterns,
+    })
+}
+
+/// Copy of `args` with secrets masked, for showing in approval prompts.
+pub fn redact_args(detector: &LeakDetector, args: &Value) -> Value {
+    match args {
+        Value::String(s) => Value::String(detector.redact(s).0),
+        Value::Array(items) => {
+            Value::Array(items.iter().map(|v| redact_args(detector, v)).collect())
+        }
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| (k.clone(), redact_args(detector, v)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+fn visit_strings(value: &Value, f: &mut impl FnMut(&str)) {
+    match value {
+        Value::String(s) => f(s),
+        Value::Array(items) => items.iter().for_each(|v| visit_strings(v, f)),
+        Value::Object(map) => map.values().for_each(|v| visit_strings(v, f)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_scan_args_finds_secrets_and_passes_clean_args() {
+        let detector = LeakDetector::new();
+        let args = json!({
+            "path": "deploy/.env",
+            "content": "OPENAI_API_KEY=sk-abc12345678901234567890\nDEBUG=1"
+        });
+        let finding = scan_args(&detector, "write_file", &args).unwrap();
+        assert_eq!(finding.patterns, ["openai_api_key"]);
+        assert!(finding.blocked_message().contains("write_file"));
+
+        let clean = json!({"path": "notes.md", "content": "OPENAI_API_KEY=${OPENAI_API_KEY}"});
+        assert!(scan_args(&detector, "write_file", &clean).is_none());
+
+        // Warn-level patterns (e.g. a sha256 digest) do not count.
+        let digest = json!({"content": "a".repeat(64)});
+        assert!(scan_args(&detector, "write_file", &digest).is_none());
+
+        let redacted = redact_args(&detector, &args);
+        assert!(redacted["content"]
+            .as_str()
+            .unwrap()
+            .contains("sk-a***7890"));
+    }
+
+    #[test]
+    fn test_secret_guard_config_parses_action() {
+        let config: SecretGuardConfig = serde_json::from_str(r#"{"action": "block"}"#).unwrap();
+        assert_eq!(config.action, SecretGuardAction::Block);
+        assert!(config.covers("Write_File"));
+        assert!(!config.covers("read_file"));
+        assert_eq!("warn".parse(), Ok(SecretGuardAction::Warn));
+    }
+}
@@ -0,0 +1,416 @@
+//! In-process job scheduler.
+//!
+//! [`CronService`](crate::cron::CronService) schedules agent turns on the
+//! bus; this scheduler runs Rust callbacks instead — the monitor hand's
+//! target checks, for example. Jobs are registered under a stable name with
+//! a [`CronSchedule`] and fired from a tokio timer task each.
+//!
+//! The schedule math is shared with the cron module (standard 5-field
+//! expressions, evaluated in UTC unless the schedule names a timezone).
+//! Job definitions and last-run times are written to a JSON file
+//! (`~/.zeptoclaw/scheduler.json`), so re-registering a job after a restart
+//! picks up where it left off. A run missed while the process was down is
+//! skipped or run once immediately, per `scheduler.on_miss`.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::config::Config;
+use crate::cron::{is_valid_cron_expr, next_run_at, CronSchedule, OnMiss};
+use crate::error::{Result, ZeptoError};
+use crate::utils::fs::write_atomic;
+
+/// Longest single sleep before the wall clock is re-checked, so clock jumps
+/// (suspend, NTP steps) delay a run by at most this much.
+const MAX_SLEEP: Duration = Duration::from_secs(60);
+
+/// Callback run each time a job fires.
+pub type JobFn = Arc<dyn Fn() -> BoxFuture<'static, Result<()>> + Send + Sync>;
+
+/// Persisted state of a registered job.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobRecord {
+    /// When the job should fire.
+    pub schedule: CronSchedule,
+    /// When the job last fired (unix ms).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_run_at_ms: Option<i64>,
+}
+
+/// When a job should first fire after it is (re-)registered at `now`.
+///
+/// A job with no recorded run waits for its next slot. Otherwise the slot
+/// following its last run is kept if it is still ahead; if it has passed,
+/// [`OnMiss::Skip`] moves on to the next future slot and
+/// [`OnMiss::RunOnce`] fires immediately (once, however many were missed).
+pub fn first_run_at(
+    schedule: &CronSchedule,
+    last_run_at_ms: Option<i64>,
+    now: i64,
+    on_miss: &OnMiss,
+) -> Option<i64> {
+    let missed = last_run_at_ms.and_then(|last| next_run_at(schedule, last));
+    match missed {
+        Some(due) if due > now => Some(due),
+        Some(_) if *on_miss == OnMiss::RunOnce => Some(now),
+        _ => next_run_at(schedule, now),
+    }
+}
+
+fn now_ms() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+fn same_schedule(a: &CronSchedule, b: &CronSchedule) -> bool {
+    serde_json::to_value(a).ok() == serde_json::to_value(b).ok()
+}
+
+/// Job records shared with the timer tasks.
+struct Store {
+    path: Option<PathBuf>,
+    records: Mutex<BTreeMap<String, JobRecord>>,
+}
+
+impl Store {
+    fn load(path: &Path) -> Result<BTreeMap<String, JobRecord>> {
+        match std::fs::read_to_string(path) {
+            Ok(raw) => Ok(serde_json::from_str(&raw)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(BTreeMap::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn save(&self, records: &BTreeMap<String, JobRecord>) {
+        let Some(path) = self.path.as_ref() else {
+            return;
+        };
+        let write = || -> Result<()> {
+            write_atomic(path, serde_json::to_string_pretty(records)?)?;
+            Ok(())
+        };
+        if let Err(e) = write() {
+            warn!(error = %e, "Failed to persist scheduler state");
+        }
+    }
+
+    fn mark_run(&self, name: &str, at_ms: i64) {
+        let mut records = self.records.lock().unwrap();
+        if let Some(record) = records.get_mut(name) {
+            record.last_run_at_ms = Some(at_ms);
+            self.save(&records);
+        }
+    }
+}
+
+/// Runs registered callbacks on cron schedules.
+pub struct Scheduler {
+    store: Arc<Store>,
+    on_miss: OnMiss,
+    handles: Mutex<HashMap<String, JoinHandle<()>>>,
+}
+
+impl Scheduler {
+    /// Scheduler persisting to `path`, loading any records already there.
+    pub fn new(path: impl Into<PathBuf>, on_miss: OnMiss) -> Self {
+        let path = path.into();
+        let records = Store::load(&path).unwrap_or_else(|e| {
+            warn!(error = %e, "Failed to load scheduler state; starting empty");
+            BTreeMap::new()
+        });
+        Self::with_store(Some(path), records, on_miss)
+    }
+
+    /// Scheduler that keeps its records in memory only (tests, one-shot runs).
+    pub fn in_memory(on_miss: OnMiss) -> Self {
+        Self::with_store(None, BTreeMap::new(), on_miss)
+    }
+
+    /// Scheduler at [`Scheduler::default_path`] using `scheduler.on_miss`.
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(Self::default_path(), config.scheduler.on_miss.clone())
+    }
+
+    /// Default state file: `~/.zeptoclaw/scheduler.json`.
+    pub fn default_path() -> PathBuf {
+        Config::dir().join("scheduler.json")
+    }
+
+    fn with_store(
+        path: Option<PathBuf>,
+        records: BTreeMap<String, JobRecord>,
+        on_miss: OnMiss,
+    ) -> Self {
+        Self {
+            store: Arc::new(Store {
+                path,
+                records: Mutex::new(records),
+            }),
+            on_miss,
+            handles: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Register `job` under `name` and start its timer.
+    ///
+    /// Must be called from within a tokio runtime. Registering a name again
+    /// replaces the earlier job; its last-run time is kept unless the
+    /// schedule changed. Returns the first run time (unix ms), or `None` if
+    /// the schedule will never fire again.
+    pub fn register<F>(&self, name: &str, schedule: CronSchedule, job: F) -> Result<Option<i64>>
+    where
+        F: Fn() -> BoxFuture<'static, Result<()>> + Send + Sync + 'static,
+    {
+        if let CronSchedule::Cron { expr, .. } = &schedule {
+            if !is_valid_cron_expr(expr) {
+                return Err(ZeptoError::Config(format!(
+                    "Invalid cron expression '{}' for scheduled job '{}'",
+                    expr, name
+                )));
+            }
+        }
+
+        let last_run_at_ms = {
+            let mut records = self.store.records.lock().unwrap();
+            let last = records
+                .get(name)
+                .filter(|r| same_schedule(&r.schedule, &schedule))
+                .and_then(|r| r.last_run_at_ms);
+            records.insert(
+                name.to_string(),
+                JobRecord {
+                    schedule: schedule.clone(),
+                    last_run_at_ms: last,
+                },
+            );
+            self.store.save(&records);
+            last
+        };
+
+        let first = first_run_at(&schedule, last_run_at_ms, now_ms(), &self.on_miss);
+        let handle = first.map(|first| {
+            tokio::spawn(run_job(
+                name.to_string(),
+                schedule,
+                first,
+                Arc::new(job),
+                Arc::clone(&self.store),
+            ))
+        });
+        if let Some(old) = match handle {
+            Some(handle) => self
+                .handles
+                .lock()
+                .unwrap()
+                .insert(name.to_string(), handle),
+            None => self.handles.lock().unwrap().remove(name),
+        } {
+            old.abort();
+        }
+        info!(job = %name, next_run_at_ms = ?first, "Registered scheduled job");
+        Ok(first)
+    }
+
+    /// Stop a job's timer and forget its record. Returns whether it existed.
+    pub fn unregister(&self, name: &str) -> bool {
+        if let Some(handle) = self.handles.lock().unwrap().remove(name) {
+            handle.abort();
+        }
+        let mut records = self.store.records.lock().unwrap();
+        let existed = records.remove(name).is_some();
+        if existed {
+            self.store.save(&records);
+        }
+        existed
+    }
+
+    /// Persisted record of a job, if known.
+    pub fn record(&self, name: &str) -> Option<JobRecord> {
+        self.store.records.lock().unwrap().get(name).cloned()
+    }
+
+    /// Names of jobs with a running timer.
+    pub fn job_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.handles.lock().unwrap().keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Stop every job timer. Records stay on disk for the next start.
+    pub fn stop(&self) {
+        for (_, handle) in self.handles.lock().unwrap().drain() {
+            handle.abort();
+        }
+    }
+}
+
+impl Drop for Scheduler {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+async fn run_job(
+    name: String,
+    schedule: CronSchedule,
+    mut due: i64,
+    job: JobFn,
+    store: Arc<Store>,
+) {
+    loop {
+        loop {
+            let wait = due - now_ms();
+            if wait <= 0 {
+                break;
+            }
+            let wait = Duration::from_millis(wait as u64).min(MAX_SLEEP);
+            tokio::time::sleep(wait).await;
+        }
+
+        let started = now_ms();
+        if let Err(e) = job().await {
+            warn!(job = %name, error = %e, "Scheduled job failed");
+        }
+        store.mark_run(&name, started);
+
+        match next_run_at(&schedule, now_ms().max(due)) {
+            Some(next) => due = next,
+            None => {
+                info!(job = %name, "Scheduled job has no further runs");
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn ms(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> i64 {
+        Utc.with_ymd_and_hms(y, mo, d, h, mi, 0)
+            .unwrap()
+            .timestamp_millis()
+    }
+
+    fn cron(expr: &str) -> CronSchedule {
+        CronSchedule::Cron {
+            expr: expr.to_string(),
+            tz: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_register_validates_cron_expressions() {
+        assert!(is_valid_cron_expr("*/15 9-17 * * 1-5"));
+        assert!(is_valid_cron_expr("0 0 1,15 * *"));
+        assert!(!is_valid_cron_expr("60 * * * *"));
+        assert!(!is_valid_cron_expr("* * * *"));
+
+        let scheduler = Scheduler::in_memory(OnMiss::Skip);
+        let noop = || -> BoxFuture<'static, Result<()>> { Box::pin(async { Ok(()) }) };
+        assert!(scheduler.register("bad", cron("* 25 * * *"), noop).is_err());
+        assert!(scheduler.record("bad").is_none());
+
+        let first = scheduler.register("ok", cron("0 3 * * *"), noop).unwrap();
+        assert!(first.is_some_and(|at| at > now_ms()));
+        assert_eq!(scheduler.job_names(), ["ok"]);
+        assert!(scheduler.unregister("ok"));
+        assert!(scheduler.job_names().is_empty());
+    }
+
+    #[test]
+    fn test_next_run_in_utc() {
+        // Evaluated in UTC: the March DST change in Europe/US has no effect.
+        let now = ms(2026, 3, 29, 0, 30);
+        assert_eq!(
+            next_run_at(&cron("30 2 * * *"), now),
+            Some(ms(2026, 3, 29, 2, 30))
+        );
+        assert_eq!(
+            next_run_at(&cron("0 */6 * * *"), ms(2026, 11, 1, 6, 0)),
+            Some(ms(2026, 11, 1, 12, 0))
+        );
+        // Month and year rollover.
+        assert_eq!(
+            next_run_at(&cron("0 0 1 * *"), ms(2026, 12, 31, 23, 59)),
+            Some(ms(2027, 1, 1, 0, 0))
+        );
+    }
+
+    #[test]
+    fn test_missed_run_skip_vs_catch_up() {
+        let hourly = cron("0 * * * *");
+        let now = ms(2026, 5, 10, 12, 20);
+
+        // Never ran: wait for the next slot under either policy.
+        assert_eq!(
+            first_run_at(&hourly, None, now, &OnMiss::RunOnce),
+            Some(ms(2026, 5, 10, 13, 0))
+        );
+
+        // Last run within the current slot: nothing was missed.
+        let recent = Some(ms(2026, 5, 10, 12, 0));
+        assert_eq!(
+            first_run_at(&hourly, recent, now, &OnMiss::RunOnce),
+            Some(ms(2026, 5, 10, 13, 0))
+        );
+
+        // Down since 09:00, so 10:00-12:00 were missed.
+        let stale = Some(ms(2026, 5, 10, 9, 0));
+        assert_eq!(
+            first_run_at(&hourly, stale, now, &OnMiss::Skip),
+            Some(ms(2026, 5, 10, 13, 0))
+        );
+        assert_eq!(
+            first_run_at(&hourly, stale, now, &OnMiss::RunOnce),
+            Some(now)
+        );
+
+        // Intervals keep their cadence across a restart.
+        let every = CronSchedule::Every { every_ms: 600_000 };
+        assert_eq!(
+            first_run_at(&every, Some(now - 120_000), now, &OnMiss::Skip),
+            Some(now + 480_000)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_last_run_persists_across_restarts() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("scheduler.json");
+        let runs = Arc::new(AtomicUsize::new(0));
+
+        let scheduler = Scheduler::new(&path, OnMiss::Skip);
+        let counter = Arc::clone(&runs);
+        scheduler
+            .register(
+                "tick",
+                CronSchedule::Every { every_ms: 20 },
+                move || -> BoxFuture<'static, Result<()>> {
+                    let counter = Arc::clone(&counter);
+                    Box::pin(async move {
+                        counter.fetch_add(1, Ordering::SeqCst);
+                        Ok(())
+                    })
+                },
+            )
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        drop(scheduler);
+        assert!(runs.load(Ordering::SeqCst) >= 1);
+
+        let restored = Scheduler::new(&path, OnMiss::Skip);
+        let record = restored.record("tick").unwrap();
+        assert!(record.last_run_at_ms.is_some());
+        assert!(restored.job_names().is_empty());
+    }
+}
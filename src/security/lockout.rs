@@ -0,0 +1,199 @@
+//! Per-identifier brute-force lockout.
+//!
+//! Failed attempts are counted per identifier (an IP address, username, or
+//! device id); after `max_attempts` failures the identifier is locked out for
+//! `lockout_secs`.  A success clears the count.  Entries live in memory only
+//! and are pruned once the map grows, bounding memory use under attack from
+//! many distinct identifiers: expired lockouts go, and so do identifiers
+//! whose last failure is older than `lockout_secs` without reaching a lockout.
+//!
+//! Shared by device pairing and panel login.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+/// Map size above which expired entries are pruned on each recorded failure.
+const PRUNE_THRESHOLD: usize = 100;
+
+/// Lockout state for a single identifier.
+struct LockoutEntry {
+    attempts: u32,
+    locked_until: Option<Instant>,
+    last_failure: Instant,
+}
+
+/// Tracks failed attempts and lockouts per identifier.
+pub struct LockoutTracker {
+    entries: HashMap<String, LockoutEntry>,
+    max_attempts: u32,
+    lockout_duration: Duration,
+}
+
+impl LockoutTracker {
+    /// Create a tracker that locks an identifier out for `lockout_secs` after
+    /// `max_attempts` consecutive failures.
+    pub fn new(max_attempts: u32, lockout_secs: u64) -> Self {
+        Self {
+            entries: HashMap::new(),
+            max_attempts: max_attempts.max(1),
+            lockout_duration: Duration::from_secs(lockout_secs),
+        }
+    }
+
+    /// Check if an identifier is currently locked out.
+    pub fn is_locked_out(&self, identifier: &str) -> bool {
+        self.remaining(identifier).is_some()
+    }
+
+    /// Time left on an identifier's lockout, or `None` if it is not locked out.
+    pub fn remaining(&self, identifier: &str) -> Option<Duration> {
+        let locked_until = self.entries.get(identifier)?.locked_until?;
+        locked_until.checked_duration_since(Instant::now())
+    }
+
+    /// Get the number of failed attempts for an identifier.
+    pub fn failed_attempts(&self, identifier: &str) -> u32 {
+        self.entries
+            .get(identifier)
+            .map(|e| e.attempts)
+            .unwrap_or(0)
+    }
+
+    /// Record a failed attempt.  Returns `true` if this failure triggered a
+    /// lockout.
+    pub fn record_failure(&mut self, identifier: &str) -> bool {
+        // Prune expired lockouts periodically (every time we record a failure)
+        // to bound memory growth from diverse attacker IPs.
+        if self.entries.len() > PRUNE_THRESHOLD {
+            self.prune_expired();
+        }
+
+        let entry = self
+            .entries
+            .entry(identifier.to_string())
+            .or_insert(LockoutEntry {
+                attempts: 0,
+                locked_until: None,
+                last_failure: Instant::now(),
+            });
+
+        // If a previous lockout has expired, reset
+        if entry
+            .locked_until
+            .map(|t| Instant::now() >= t)
+            .unwrap_or(false)
+        {
+            entry.attempts = 0;
+            entry.locked_until = None;
+        }
+
+        entry.attempts += 1;
+        entry.last_failure = Instant::now();
+
+        if entry.attempts >= self.max_attempts {
+            entry.locked_until = Some(Instant::now() + self.lockout_duration);
+            warn!(
+                identifier,
+                attempts = entry.attempts,
+                lockout_secs = self.lockout_duration.as_secs(),
+                "Brute-force lockout triggered"
+            );
+            return true;
+        }
+        false
+    }
+
+    /// Clear the failure count for an identifier after a success.
+    pub fn clear(&mut self, identifier: &str) {
+        self.entries.remove(identifier);
+    }
+
+    /// Remove expired lockouts and idle entries to prevent unbounded map
+    /// growth.
+    ///
+    /// An entry that never reached a lockout is idle once its last failure is
+    /// older than the lockout duration. Called automatically during
+    /// `record_failure()`. Can also be called explicitly for periodic
+    /// maintenance.
+    pub fn prune_expired(&mut self) {
+        let now = Instant::now();
+        let window = self.lockout_duration;
+        let before = self.entries.len();
+        self.entries.retain(|_, entry| match entry.locked_until {
+            Some(t) => now < t,
+            None => now.duration_since(entry.last_failure) < window,
+        });
+        let pruned = before - self.entries.len();
+        if pruned > 0 {
+            debug!(
+                pruned,
+                remaining = self.entries.len(),
+                "Pruned expired lockout entries"
+            );
+        }
+    }
+
+    /// Number of identifiers currently tracked.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if no identifiers are tracked.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lockout_after_max_attempts() {
+        let mut tracker = LockoutTracker::new(3, 300);
+        assert!(!tracker.record_failure("ip"));
+        assert!(!tracker.record_failure("ip"));
+        assert!(!tracker.is_locked_out("ip"));
+        assert!(tracker.record_failure("ip"));
+        assert!(tracker.is_locked_out("ip"));
+        let remaining = tracker.remaining("ip").unwrap();
+        assert!(remaining > Duration::from_secs(290));
+        assert!(!tracker.is_locked_out("other"));
+    }
+
+    #[test]
+    fn test_clear_resets_attempts() {
+        let mut tracker = LockoutTracker::new(3, 300);
+        tracker.record_failure("ip");
+        tracker.record_failure("ip");
+        tracker.clear("ip");
+        assert_eq!(tracker.failed_attempts("ip"), 0);
+        assert!(!tracker.record_failure("ip"));
+    }
+
+    #[test]
+    fn test_expired_lockout_resets_on_next_failure() {
+        let mut tracker = LockoutTracker::new(1, 0);
+        tracker.record_failure("ip");
+        std::thread::sleep(Duration::from_millis(5));
+        assert!(!tracker.is_locked_out("ip"));
+        tracker.record_failure("ip");
+        assert_eq!(tracker.failed_attempts("ip"), 1);
+    }
+
+    #[test]
+    fn test_prune_removes_idle_entries() {
+        let mut tracker = LockoutTracker::new(3, 300);
+        tracker.record_failure("recent");
+        tracker.prune_expired();
+        assert_eq!(tracker.failed_attempts("recent"), 1);
+
+        // Failures older than the window are forgotten even below the limit.
+        let mut tracker = LockoutTracker::new(3, 0);
+        tracker.record_failure("idle");
+        std::thread::sleep(Duration::from_millis(5));
+        tracker.prune_expired();
+        assert!(tracker.is_empty());
+    }
+}
@@ -5,6 +5,7 @@
 
 pub mod agent_mode;
 pub mod encryption;
+pub mod lockout;
 pub mod mount;
 pub mod pairing;
 pub mod path;
@@ -12,6 +13,7 @@ pub mod shell;
 
 pub use agent_mode::{AgentMode, AgentModeConfig, CategoryPermission, ModePolicy};
 pub use encryption::{is_secret_field, resolve_master_key, SecretEncryption};
+pub use lockout::LockoutTracker;
 pub use mount::{validate_extra_mounts, validate_mount_not_blocked, DEFAULT_BLOCKED_PATTERNS};
 pub use pairing::{DeviceInfo, PairedDevice, PairingManager};
 pub use path::{
@@ -5,24 +5,31 @@
 //! Failed validation attempts are tracked per identifier; after `max_attempts`, the identifier
 //! is locked out for `lockout_secs`.
 //!
-//! Persists paired devices to `~/.zeptoclaw/security/paired_devices.json`.
+//! Persists paired devices to `~/.zeptoclaw/security/paired_devices.json`, or,
+//! with the `sqlite-store` feature and `storage.backend = "sqlite"`, to one row
+//! per device in the shared SQLite database so the gateway sees devices paired
+//! or revoked from the CLI without a restart.
 //!
 //! # Security notes
 //!
 //! - Token comparison uses **constant-time** equality via the `subtle` crate to prevent
 //!   timing side-channel attacks.
 //! - 6-digit codes are generated from CSPRNG-sourced bytes (UUID v4 uses `getrandom`).
-//! - The lockout map is periodically pruned to prevent unbounded memory growth.
+//! - Lockouts are tracked by [`LockoutTracker`], which prunes expired entries to prevent
+//!   unbounded memory growth.
 
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use std::collections::HashMap;
+use std::borrow::Cow;
 use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use subtle::ConstantTimeEq;
-use tracing::{debug, info, warn};
+use tracing::{info, warn};
 use uuid::Uuid;
 
+use super::lockout::LockoutTracker;
+use crate::config::{StorageBackend, StorageConfig};
+
 /// A paired device record (persisted to JSON).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PairedDevice {
@@ -58,41 +65,83 @@ struct PendingCode {
     expires_at: Instant,
 }
 
-/// Per-identifier brute-force lockout entry (not persisted).
-struct LockoutEntry {
-    attempts: u32,
-    locked_until: Option<Instant>,
-}
-
 /// Manages device pairing lifecycle: code generation, token validation, and lockout.
 pub struct PairingManager {
     store: PairingStore,
     path: PathBuf,
+    /// SQLite connection; when set, the database is the source of truth for
+    /// `store` and `path` is only used for the one-time JSON import.
+    #[cfg(feature = "sqlite-store")]
+    sqlite: Option<rusqlite::Connection>,
     pending_code: Option<PendingCode>,
-    lockouts: HashMap<String, LockoutEntry>,
-    max_attempts: u32,
-    lockout_duration: Duration,
+    lockout: LockoutTracker,
 }
 
 impl PairingManager {
     /// Create a new `PairingManager`, loading any existing paired devices from disk.
     pub fn new(max_attempts: u32, lockout_secs: u64) -> Self {
-        let path = dirs::home_dir()
-            .unwrap_or_else(|| PathBuf::from("."))
-            .join(".zeptoclaw")
-            .join("security")
-            .join("paired_devices.json");
+        let path = Self::default_path();
         let store = Self::load_from_disk(&path);
         Self {
             store,
             path,
+            #[cfg(feature = "sqlite-store")]
+            sqlite: None,
             pending_code: None,
-            lockouts: HashMap::new(),
-            max_attempts,
-            lockout_duration: Duration::from_secs(lockout_secs),
+            lockout: LockoutTracker::new(max_attempts, lockout_secs),
         }
     }
 
+    /// Create a `PairingManager` using the backend selected by the `storage`
+    /// config section.
+    ///
+    /// If the SQLite backend is requested but the `sqlite-store` feature was
+    /// not compiled in, or the database cannot be opened, logs a warning and
+    /// falls back to the JSON file.
+    pub fn with_storage(max_attempts: u32, lockout_secs: u64, storage: &StorageConfig) -> Self {
+        if storage.backend == StorageBackend::Sqlite {
+            #[cfg(feature = "sqlite-store")]
+            {
+                let db_path = storage.sqlite_path();
+                match Self::open_sqlite(&db_path, &Self::default_path(), max_attempts, lockout_secs)
+                {
+                    Ok(mgr) => return mgr,
+                    Err(e) => warn!(
+                        path = %db_path.display(),
+                        error = %e,
+                        "Failed to open SQLite pairing store; falling back to JSON"
+                    ),
+                }
+            }
+            #[cfg(not(feature = "sqlite-store"))]
+            warn!("sqlite-store feature not compiled; falling back to JSON pairing store. Rebuild with: cargo build --features sqlite-store");
+        }
+        Self::new(max_attempts, lockout_secs)
+    }
+
+    /// Create a SQLite-backed `PairingManager` at `db_path`.
+    ///
+    /// On the first open of a database, devices from the JSON file at
+    /// `json_path` (if any) are imported.
+    #[cfg(feature = "sqlite-store")]
+    pub fn open_sqlite(
+        db_path: &Path,
+        json_path: &Path,
+        max_attempts: u32,
+        lockout_secs: u64,
+    ) -> rusqlite::Result<Self> {
+        let mut conn = crate::utils::sqlite::open(db_path)?;
+        sqlite::init(&mut conn, &Self::load_from_disk(json_path).devices)?;
+        let devices = sqlite::load_all(&conn)?;
+        Ok(Self {
+            store: PairingStore { devices },
+            path: json_path.to_path_buf(),
+            sqlite: Some(conn),
+            pending_code: None,
+            lockout: LockoutTracker::new(max_attempts, lockout_secs),
+        })
+    }
+
     /// Create a `PairingManager` with a custom storage path (useful for testing).
     #[cfg(test)]
     fn with_path(path: PathBuf, max_attempts: u32, lockout_secs: u64) -> Self {
@@ -100,10 +149,10 @@ impl PairingManager {
         Self {
             store,
             path,
+            #[cfg(feature = "sqlite-store")]
+            sqlite: None,
             pending_code: None,
-            lockouts: HashMap::new(),
-            max_attempts,
-            lockout_duration: Duration::from_secs(lockout_secs),
+            lockout: LockoutTracker::new(max_attempts, lockout_secs),
         }
     }
 
@@ -169,12 +218,17 @@ impl PairingManager {
         // Remove any existing device with the same name
         self.store.devices.retain(|d| d.name != device_name);
 
-        self.store.devices.push(PairedDevice {
+        let device = PairedDevice {
             name: device_name.to_string(),
             token_hash,
             paired_at: now,
             last_seen: now,
-        });
+        };
+        #[cfg(feature = "sqlite-store")]
+        if let Some(conn) = &self.sqlite {
+            sqlite::upsert(conn, &device);
+        }
+        self.store.devices.push(device);
 
         self.save_to_disk();
         info!(device = device_name, "Device paired successfully");
@@ -186,9 +240,10 @@ impl PairingManager {
     ///
     /// Uses **constant-time** comparison to prevent timing side-channel attacks.
     /// On success, updates the device's `last_seen` timestamp in memory and returns
-    /// the device name. The updated timestamp is **not** flushed to disk here —
-    /// it will be persisted on the next `complete_pairing()`, `revoke()`, or explicit
-    /// flush call, avoiding O(n) disk writes per request.
+    /// the device name. With the JSON backend the updated timestamp is **not** flushed
+    /// to disk here — it will be persisted on the next `complete_pairing()`, `revoke()`,
+    /// or explicit flush call, avoiding O(n) disk writes per request. With the SQLite
+    /// backend, devices are reloaded first and the timestamp is a single-row update.
     ///
     /// On failure, records a failed attempt for the identifier.
     pub fn validate_token(&mut self, raw_token: &str, identifier: &str) -> Option<String> {
@@ -198,6 +253,14 @@ impl PairingManager {
             return None;
         }
 
+        #[cfg(feature = "sqlite-store")]
+        if let Some(conn) = &self.sqlite {
+            match sqlite::load_all(conn) {
+                Ok(devices) => self.store.devices = devices,
+                Err(e) => warn!("Failed to reload paired devices: {}", e),
+            }
+        }
+
         let hash = Self::hash_token(raw_token);
         let hash_bytes = hash.as_bytes();
         let now = Self::now_secs();
@@ -219,6 +282,10 @@ impl PairingManager {
         if let Some(idx) = matched_idx {
             self.store.devices[idx].last_seen = now;
             let name = self.store.devices[idx].name.clone();
+            #[cfg(feature = "sqlite-store")]
+            if let Some(conn) = &self.sqlite {
+                sqlite::touch(conn, &name, now);
+            }
             // Deferred disk write — flushed on next complete_pairing/revoke/clear
             self.clear_lockout(identifier);
             Some(name)
@@ -232,20 +299,18 @@ impl PairingManager {
     ///
     /// Returns `true` if a device was found and removed.
     pub fn revoke(&mut self, device_name: &str) -> bool {
-        let initial_len = self.store.devices.len();
-        self.store.devices.retain(|d| d.name != device_name);
-        let removed = self.store.devices.len() < initial_len;
-        if removed {
+        if self.remove_device(device_name) {
             self.save_to_disk();
             info!(device = device_name, "Device revoked");
+            true
+        } else {
+            false
         }
-        removed
     }
 
     /// List paired devices without exposing token hashes.
     pub fn list_devices(&self) -> Vec<DeviceInfo> {
-        self.store
-            .devices
+        self.devices()
             .iter()
             .map(|d| DeviceInfo {
                 name: d.name.clone(),
@@ -257,27 +322,17 @@ impl PairingManager {
 
     /// Returns `true` if there are any paired devices.
     pub fn has_devices(&self) -> bool {
-        !self.store.devices.is_empty()
+        !self.devices().is_empty()
     }
 
     /// Check if an identifier is currently locked out.
     pub fn is_locked_out(&self, identifier: &str) -> bool {
-        if let Some(entry) = self.lockouts.get(identifier) {
-            if let Some(locked_until) = entry.locked_until {
-                if Instant::now() < locked_until {
-                    return true;
-                }
-            }
-        }
-        false
+        self.lockout.is_locked_out(identifier)
     }
 
     /// Get the number of failed attempts for an identifier.
     pub fn failed_attempts(&self, identifier: &str) -> u32 {
-        self.lockouts
-            .get(identifier)
-            .map(|e| e.attempts)
-            .unwrap_or(0)
+        self.lockout.failed_attempts(identifier)
     }
 
     /// Remove expired lockout entries to prevent unbounded map growth.
@@ -285,63 +340,48 @@ impl PairingManager {
     /// Called automatically during `record_failed_attempt()`. Can also be called
     /// explicitly for periodic maintenance.
     pub fn prune_expired_lockouts(&mut self) {
-        let now = Instant::now();
-        let before = self.lockouts.len();
-        self.lockouts.retain(|_, entry| {
-            entry.locked_until.map(|t| now < t).unwrap_or(true) // keep entries without a lockout deadline (still accumulating)
-        });
-        let pruned = before - self.lockouts.len();
-        if pruned > 0 {
-            debug!(
-                pruned,
-                remaining = self.lockouts.len(),
-                "Pruned expired lockout entries"
-            );
-        }
+        self.lockout.prune_expired();
     }
 
     // ---- Internal helpers ----
 
-    fn record_failed_attempt(&mut self, identifier: &str) {
-        // Prune expired lockouts periodically (every time we record a failure)
-        // to bound memory growth from diverse attacker IPs.
-        if self.lockouts.len() > 100 {
-            self.prune_expired_lockouts();
-        }
+    fn default_path() -> PathBuf {
+        dirs::home_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join(".zeptoclaw")
+            .join("security")
+            .join("paired_devices.json")
+    }
 
-        let entry = self
-            .lockouts
-            .entry(identifier.to_string())
-            .or_insert(LockoutEntry {
-                attempts: 0,
-                locked_until: None,
-            });
-
-        // If a previous lockout has expired, reset
-        if entry
-            .locked_until
-            .map(|t| Instant::now() >= t)
-            .unwrap_or(false)
-        {
-            entry.attempts = 0;
-            entry.locked_until = None;
+    /// Current paired devices, read from the database with the SQLite backend.
+    fn devices(&self) -> Cow<'_, [PairedDevice]> {
+        #[cfg(feature = "sqlite-store")]
+        if let Some(conn) = &self.sqlite {
+            match sqlite::load_all(conn) {
+                Ok(devices) => return Cow::Owned(devices),
+                Err(e) => warn!("Failed to read paired devices: {}", e),
+            }
         }
+        Cow::Borrowed(&self.store.devices)
+    }
 
-        entry.attempts += 1;
-
-        if entry.attempts >= self.max_attempts {
-            entry.locked_until = Some(Instant::now() + self.lockout_duration);
-            warn!(
-                identifier,
-                attempts = entry.attempts,
-                lockout_secs = self.lockout_duration.as_secs(),
-                "Brute-force lockout triggered"
-            );
+    /// Remove a device by name, returning `true` if it existed.
+    fn remove_device(&mut self, device_name: &str) -> bool {
+        let initial_len = self.store.devices.len();
+        self.store.devices.retain(|d| d.name != device_name);
+        #[cfg(feature = "sqlite-store")]
+        if let Some(conn) = &self.sqlite {
+            return sqlite::delete(conn, device_name);
         }
+        self.store.devices.len() < initial_len
+    }
+
+    fn record_failed_attempt(&mut self, identifier: &str) {
+        self.lockout.record_failure(identifier);
     }
 
     fn clear_lockout(&mut self, identifier: &str) {
-        self.lockouts.remove(identifier);
+        self.lockout.clear(identifier);
     }
 
     /// SHA-256 hash a raw token to hex.
@@ -388,6 +428,11 @@ impl PairingManager {
     }
 
     fn save_to_disk(&self) {
+        // The SQLite backend writes rows as they change.
+        #[cfg(feature = "sqlite-store")]
+        if self.sqlite.is_some() {
+            return;
+        }
         if let Some(parent) = self.path.parent() {
             let _ = std::fs::create_dir_all(parent);
         }
@@ -399,6 +444,99 @@ impl PairingManager {
     }
 }
 
+#[cfg(feature = "sqlite-store")]
+mod sqlite {
+    use rusqlite::{params, Connection};
+    use tracing::{info, warn};
+
+    use super::PairedDevice;
+
+    /// Create the devices table and import `paired_devices.json` on first open.
+    pub(super) fn init(
+        conn: &mut Connection,
+        json_devices: &[PairedDevice],
+    ) -> rusqlite::Result<()> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS paired_devices (
+                name       TEXT PRIMARY KEY,
+                token_hash TEXT NOT NULL,
+                paired_at  INTEGER NOT NULL,
+                last_seen  INTEGER NOT NULL
+            )",
+            [],
+        )?;
+        let imported = crate::utils::sqlite::import_once(conn, "paired_devices_json", |tx| {
+            let mut rows = 0;
+            for d in json_devices {
+                rows += tx.execute(
+                    "INSERT OR IGNORE INTO paired_devices (name, token_hash, paired_at, last_seen)
+                     VALUES (?1, ?2, ?3, ?4)",
+                    params![d.name, d.token_hash, d.paired_at as i64, d.last_seen as i64],
+                )?;
+            }
+            Ok(rows)
+        })?;
+        if let Some(rows) = imported.filter(|rows| *rows > 0) {
+            info!(rows, "Imported paired devices from JSON into SQLite");
+        }
+        Ok(())
+    }
+
+    pub(super) fn load_all(conn: &Connection) -> rusqlite::Result<Vec<PairedDevice>> {
+        let mut stmt = conn.prepare(
+            "SELECT name, token_hash, paired_at, last_seen FROM paired_devices ORDER BY paired_at",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(PairedDevice {
+                name: row.get(0)?,
+                token_hash: row.get(1)?,
+                paired_at: row.get::<_, i64>(2)?.max(0) as u64,
+                last_seen: row.get::<_, i64>(3)?.max(0) as u64,
+            })
+        })?;
+        rows.collect()
+    }
+
+    pub(super) fn upsert(conn: &Connection, device: &PairedDevice) {
+        let result = conn.execute(
+            "INSERT INTO paired_devices (name, token_hash, paired_at, last_seen)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(name) DO UPDATE SET
+                 token_hash = excluded.token_hash,
+                 paired_at = excluded.paired_at,
+                 last_seen = excluded.last_seen",
+            params![
+                device.name,
+                device.token_hash,
+                device.paired_at as i64,
+                device.last_seen as i64
+            ],
+        );
+        if let Err(e) = result {
+            warn!("Failed to save paired device: {}", e);
+        }
+    }
+
+    pub(super) fn touch(conn: &Connection, name: &str, last_seen: u64) {
+        if let Err(e) = conn.execute(
+            "UPDATE paired_devices SET last_seen = ?2 WHERE name = ?1",
+            params![name, last_seen as i64],
+        ) {
+            warn!("Failed to update device last_seen: {}", e);
+        }
+    }
+
+    pub(super) fn delete(conn: &Connection, name: &str) -> bool {
+        match conn.execute("DELETE FROM paired_devices WHERE name = ?1", [name]) {
+            Ok(rows) => rows > 0,
+            Err(e) => {
+                warn!("Failed to revoke paired device: {}", e);
+                false
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -412,10 +550,10 @@ mod tests {
         PairingManager {
             store: PairingStore::default(),
             path: PathBuf::from(format!("/tmp/zeptoclaw-test-pairing-{tid:?}-{id}.json")),
+            #[cfg(feature = "sqlite-store")]
+            sqlite: None,
             pending_code: None,
-            lockouts: HashMap::new(),
-            max_attempts: 5,
-            lockout_duration: Duration::from_secs(300),
+            lockout: LockoutTracker::new(5, 300),
         }
     }
 
@@ -583,8 +721,7 @@ mod tests {
     #[test]
     fn test_brute_force_lockout() {
         let mut mgr = test_manager();
-        mgr.max_attempts = 3;
-        mgr.lockout_duration = Duration::from_secs(300);
+        mgr.lockout = LockoutTracker::new(3, 300);
 
         let _code = mgr.generate_pairing_code();
 
@@ -605,7 +742,7 @@ mod tests {
     #[test]
     fn test_lockout_does_not_affect_other_identifiers() {
         let mut mgr = test_manager();
-        mgr.max_attempts = 2;
+        mgr.lockout = LockoutTracker::new(2, 300);
 
         let _code = mgr.generate_pairing_code();
 
@@ -651,8 +788,7 @@ mod tests {
     #[test]
     fn test_prune_expired_lockouts() {
         let mut mgr = test_manager();
-        mgr.max_attempts = 1;
-        mgr.lockout_duration = Duration::from_secs(0); // expire immediately
+        mgr.lockout = LockoutTracker::new(1, 0); // expire immediately
 
         let _code = mgr.generate_pairing_code();
 
@@ -661,13 +797,13 @@ mod tests {
         mgr.complete_pairing("999999", "d", "ip-b");
         mgr.complete_pairing("999999", "d", "ip-c");
 
-        assert_eq!(mgr.lockouts.len(), 3);
+        assert_eq!(mgr.lockout.len(), 3);
 
         // All lockouts have 0s duration, so they're already expired
         std::thread::sleep(Duration::from_millis(10));
         mgr.prune_expired_lockouts();
         assert_eq!(
-            mgr.lockouts.len(),
+            mgr.lockout.len(),
             0,
             "All expired lockouts should be pruned"
         );
@@ -676,16 +812,15 @@ mod tests {
     #[test]
     fn test_prune_keeps_active_lockouts() {
         let mut mgr = test_manager();
-        mgr.max_attempts = 1;
-        mgr.lockout_duration = Duration::from_secs(3600); // 1 hour
+        mgr.lockout = LockoutTracker::new(1, 3600); // 1 hour
 
         let _code = mgr.generate_pairing_code();
         mgr.complete_pairing("999999", "d", "ip-locked");
 
-        assert_eq!(mgr.lockouts.len(), 1);
+        assert_eq!(mgr.lockout.len(), 1);
         mgr.prune_expired_lockouts();
         assert_eq!(
-            mgr.lockouts.len(),
+            mgr.lockout.len(),
             1,
             "Active lockout should survive pruning"
         );
@@ -777,4 +912,50 @@ mod tests {
         let mgr = PairingManager::with_path(path, 5, 300);
         assert!(mgr.store.devices.is_empty());
     }
+
+    #[cfg(feature = "sqlite-store")]
+    #[test]
+    fn test_sqlite_devices_shared_between_handles() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let db = dir.path().join("state.db");
+        let json = dir.path().join("paired_devices.json");
+        let mut gateway = PairingManager::open_sqlite(&db, &json, 5, 300).unwrap();
+        let mut cli = PairingManager::open_sqlite(&db, &json, 5, 300).unwrap();
+
+        let code = cli.generate_pairing_code();
+        let token = cli.complete_pairing(&code, "phone", "127.0.0.1").unwrap();
+        assert!(gateway.has_devices());
+        assert_eq!(
+            gateway.validate_token(&token, "127.0.0.1"),
+            Some("phone".to_string())
+        );
+
+        assert!(cli.revoke("phone"));
+        assert_eq!(gateway.validate_token(&token, "127.0.0.1"), None);
+        assert!(!gateway.revoke("phone"));
+    }
+
+    #[cfg(feature = "sqlite-store")]
+    #[test]
+    fn test_sqlite_imports_json_devices_once() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let db = dir.path().join("state.db");
+        let json = dir.path().join("paired_devices.json");
+        let mut json_mgr = PairingManager::with_path(json.clone(), 5, 300);
+        let code = json_mgr.generate_pairing_code();
+        let token = json_mgr
+            .complete_pairing(&code, "laptop", "127.0.0.1")
+            .unwrap();
+
+        let mut mgr = PairingManager::open_sqlite(&db, &json, 5, 300).unwrap();
+        assert_eq!(
+            mgr.validate_token(&token, "127.0.0.1"),
+            Some("laptop".to_string())
+        );
+        assert!(mgr.revoke("laptop"));
+
+        // Revoked devices stay revoked even though the JSON file still lists them.
+        let reopened = PairingManager::open_sqlite(&db, &json, 5, 300).unwrap();
+        assert!(!reopened.has_devices());
+    }
 }
@@ -0,0 +1,187 @@
+//! Stale-session garbage collection.
+//!
+//! Sessions are kept forever unless `session.session_ttl_days` is set. When
+//! it is, the gateway periodically deletes sessions whose last update is
+//! older than the TTL; `zeptoclaw sessions gc` runs the same sweep on demand.
+//! Sessions that are currently in use are never removed, and with
+//! `session.gc_dry_run` the sweep only logs what it would delete.
+
+use std::collections::HashSet;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use tracing::{info, warn};
+
+use super::SessionManager;
+use crate::error::Result;
+
+/// How often the background collector sweeps (1 hour).
+pub const SESSION_GC_INTERVAL_SECS: u64 = 3600;
+
+/// Keys of sessions last updated before `cutoff`, excluding `active` ones.
+///
+/// The result is sorted for stable logging and output.
+pub fn select_stale(
+    sessions: impl IntoIterator<Item = (String, DateTime<Utc>)>,
+    cutoff: DateTime<Utc>,
+    active: &HashSet<String>,
+) -> Vec<String> {
+    let mut stale: Vec<String> = sessions
+        .into_iter()
+        .filter(|(key, updated_at)| *updated_at < cutoff && !active.contains(key))
+        .map(|(key, _)| key)
+        .collect();
+    stale.sort();
+    stale
+}
+
+impl SessionManager {
+    /// Delete sessions untouched for more than `ttl_days`.
+    ///
+    /// Keys in `active` are never removed. With `dry_run`, nothing is deleted
+    /// and the stale keys are only logged. Returns the stale keys either way.
+    pub async fn collect_garbage(
+        &self,
+        ttl_days: u32,
+        dry_run: bool,
+        active: &HashSet<String>,
+    ) -> Result<Vec<String>> {
+        let cutoff = Utc::now() - chrono::Duration::days(i64::from(ttl_days));
+
+        let mut touched = Vec::new();
+        for key in self.list().await? {
+            // The cached copy may carry updates newer than the stored one.
+            let cached = self.sessions.read().await.get(&key).map(|s| s.updated_at);
+            let updated_at = match (cached, &self.store) {
+                (Some(updated_at), _) => updated_at,
+                (None, Some(store)) => match store.load(&key).await {
+                    Ok(Some(session)) => session.updated_at,
+                    Ok(None) => continue,
+                    Err(e) => {
+                        warn!(session_key = %key, error = %e, "Skipping unreadable session during GC");
+                        continue;
+                    }
+                },
+                (None, None) => continue,
+            };
+            touched.push((key, updated_at));
+        }
+
+        let stale = select_stale(touched, cutoff, active);
+        for key in &stale {
+            if dry_run {
+                info!(session_key = %key, ttl_days, "Session GC (dry run): would remove stale session");
+            } else {
+                self.delete(key).await?;
+                info!(session_key = %key, ttl_days, "Session GC: removed stale session");
+            }
+        }
+        Ok(stale)
+    }
+}
+
+/// Spawn a background task that sweeps stale sessions every
+/// [`SESSION_GC_INTERVAL_SECS`] until `shutdown_rx` flips to `true`.
+///
+/// `active` is called before each sweep and returns the keys of sessions in
+/// use at that moment.
+pub fn start_periodic_session_gc<F, Fut>(
+    sessions: Arc<SessionManager>,
+    ttl_days: u32,
+    dry_run: bool,
+    active: F,
+    mut shutdown_rx: tokio::sync::watch::Receiver<bool>,
+) -> tokio::task::JoinHandle<()>
+where
+    F: Fn() -> Fut + Send + 'static,
+    Fut: Future<Output = HashSet<String>> + Send,
+{
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(SESSION_GC_INTERVAL_SECS));
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {
+                    let active = active().await;
+                    match sessions.collect_garbage(ttl_days, dry_run, &active).await {
+                        Ok(stale) if !stale.is_empty() => {
+                            info!(count = stale.len(), dry_run, "Session GC sweep finished");
+                        }
+                        Ok(_) => {}
+                        Err(e) => warn!("Session GC sweep failed: {}", e),
+                    }
+                }
+                _ = shutdown_rx.changed() => {
+                    if *shutdown_rx.borrow() {
+                        break;
+                    }
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn days_ago(days: i64) -> DateTime<Utc> {
+        Utc::now() - chrono::Duration::days(days)
+    }
+
+    async fn seed(manager: &SessionManager, key: &str, age_days: i64) {
+        let mut session = manager.get_or_create(key).await.unwrap();
+        session.updated_at = days_ago(age_days);
+        manager.save(&session).await.unwrap();
+    }
+
+    #[test]
+    fn test_select_stale_by_ttl() {
+        let cutoff = days_ago(30);
+        let sessions = vec![
+            ("telegram:old".to_string(), days_ago(45)),
+            ("telegram:new".to_string(), days_ago(2)),
+            ("cli:older".to_string(), days_ago(400)),
+        ];
+        assert_eq!(
+            select_stale(sessions, cutoff, &HashSet::new()),
+            vec!["cli:older".to_string(), "telegram:old".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_select_stale_excludes_active() {
+        let active = HashSet::from(["telegram:old".to_string()]);
+        let sessions = vec![
+            ("telegram:old".to_string(), days_ago(45)),
+            ("slack:old".to_string(), days_ago(45)),
+        ];
+        assert_eq!(
+            select_stale(sessions, days_ago(30), &active),
+            vec!["slack:old".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_collect_garbage_deletes_and_dry_runs() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = SessionManager::with_path(dir.path().to_path_buf()).unwrap();
+        seed(&manager, "telegram:stale", 60).await;
+        seed(&manager, "telegram:fresh", 1).await;
+        seed(&manager, "cli:current", 90).await;
+        let active = HashSet::from(["cli:current".to_string()]);
+
+        let stale = manager.collect_garbage(30, true, &active).await.unwrap();
+        assert_eq!(stale, vec!["telegram:stale".to_string()]);
+        assert!(manager.exists("telegram:stale").await);
+
+        manager.clear_cache().await;
+        let stale = manager.collect_garbage(30, false, &active).await.unwrap();
+        assert_eq!(stale, vec!["telegram:stale".to_string()]);
+        assert!(!manager.exists("telegram:stale").await);
+        assert!(manager.exists("telegram:fresh").await);
+        assert!(manager.exists("cli:current").await);
+    }
+}
@@ -234,7 +234,7 @@ impl ConversationHistory {
         Ok(deleted)
     }
 
-    /// Sanitize a session key for use as a filename (matches `SessionManager::sanitize_key`).
+    /// Sanitize a session key for use as a filename (matches `FileSessionStore::sanitize_key`).
     fn sanitize_key(key: &str) -> String {
         let mut result = String::with_capacity(key.len() * 3);
         for c in key.chars() {
@@ -2,7 +2,7 @@
 //!
 //! This module provides session management for ZeptoClaw, including:
 //! - In-memory session storage with async access
-//! - File-based persistence for sessions
+//! - Pluggable persistence via [`SessionStore`] (JSON files by default)
 //! - Session creation, retrieval, and deletion
 //!
 //! # Example
@@ -26,14 +26,21 @@
 //! }
 //! ```
 
+pub mod gc;
 pub mod history;
 pub mod media;
 pub mod repair;
+pub mod store;
 pub mod types;
+pub mod uploads;
 
+pub use gc::start_periodic_session_gc;
 pub use history::ConversationHistory;
 pub use repair::{repair_messages, RepairStats};
-pub use types::{ContentPart, ImageSource, Message, Role, Session, ToolCall};
+pub use store::{FileSessionStore, SessionStore};
+pub use types::{
+    ContentPart, ImageSource, Message, PendingPlan, PinnedNote, Role, Session, ToolCall,
+};
 
 use crate::config::Config;
 use crate::error::Result;
@@ -46,7 +53,7 @@ use tracing::warn;
 /// Session manager for storing and retrieving conversation sessions.
 ///
 /// The `SessionManager` provides both in-memory caching and optional
-/// file-based persistence for sessions. Sessions are identified by
+/// persistence through a [`SessionStore`] backend. Sessions are identified by
 /// unique keys (e.g., "telegram:chat123").
 ///
 /// # Thread Safety
@@ -57,13 +64,16 @@ use tracing::warn;
 /// # Persistence
 ///
 /// When created with `new()`, sessions are persisted to disk in the
-/// `~/.zeptoclaw/sessions/` directory. Use `new_memory()` for testing
+/// `~/.zeptoclaw/sessions/` directory via [`FileSessionStore`]. Use
+/// `with_store()` to plug in another backend, or `new_memory()` for testing
 /// or when persistence is not needed.
 pub struct SessionManager {
     /// In-memory cache of sessions
     sessions: Arc<RwLock<HashMap<String, Session>>>,
-    /// Optional path for file-based persistence
-    storage_path: Option<PathBuf>,
+    /// Optional persistence backend
+    store: Option<Arc<dyn SessionStore>>,
+    /// Upload directories removed along with their sessions
+    uploads: Option<uploads::UploadStore>,
 }
 
 impl SessionManager {
@@ -83,12 +93,7 @@ impl SessionManager {
     /// let manager = SessionManager::new().unwrap();
     /// ```
     pub fn new() -> Result<Self> {
-        let storage_path = Config::dir().join("sessions");
-        std::fs::create_dir_all(&storage_path)?;
-        Ok(Self {
-            sessions: Arc::new(RwLock::new(HashMap::new())),
-            storage_path: Some(storage_path),
-        })
+        Self::with_path(Config::dir().join("sessions"))
     }
 
     /// Create an in-memory session manager without persistence.
@@ -105,7 +110,8 @@ impl SessionManager {
     pub fn new_memory() -> Self {
         Self {
             sessions: Arc::new(RwLock::new(HashMap::new())),
-            storage_path: None,
+            store: None,
+            uploads: None,
         }
     }
 
@@ -126,17 +132,38 @@ impl SessionManager {
     /// let manager = SessionManager::with_path(PathBuf::from("/tmp/sessions")).unwrap();
     /// ```
     pub fn with_path(path: PathBuf) -> Result<Self> {
-        std::fs::create_dir_all(&path)?;
-        Ok(Self {
+        Ok(Self::with_store(Arc::new(FileSessionStore::new(path)?)))
+    }
+
+    /// Create a session manager backed by a custom [`SessionStore`].
+    ///
+    /// # Example
+    /// ```no_run
+    /// use std::sync::Arc;
+    /// use zeptoclaw::session::{FileSessionStore, SessionManager};
+    ///
+    /// let store = FileSessionStore::new("/tmp/sessions".into()).unwrap();
+    /// let manager = SessionManager::with_store(Arc::new(store));
+    /// ```
+    pub fn with_store(store: Arc<dyn SessionStore>) -> Self {
+        Self {
             sessions: Arc::new(RwLock::new(HashMap::new())),
-            storage_path: Some(path),
-        })
+            store: Some(store),
+            uploads: None,
+        }
+    }
+
+    /// Remove a session's uploaded files when the session is deleted
+    /// (builder pattern).
+    pub fn with_uploads(mut self, uploads: uploads::UploadStore) -> Self {
+        self.uploads = Some(uploads);
+        self
     }
 
     /// Get an existing session or create a new one.
     ///
     /// If the session exists in memory, it is returned immediately.
-    /// If persistence is enabled and the session exists on disk, it
+    /// If persistence is enabled and the session exists in the store, it
     /// is loaded into memory. Otherwise, a new empty session is created.
     ///
     /// # Arguments
@@ -166,19 +193,9 @@ impl SessionManager {
             }
         }
 
-        // Try loading from disk if persistence is enabled
-        if let Some(ref storage_path) = self.storage_path {
-            let file_path = storage_path.join(format!("{}.json", Self::sanitize_key(key)));
-            if file_path.exists() {
-                let content = tokio::fs::read_to_string(&file_path).await?;
-                let mut session: Session = serde_json::from_str(&content)?;
-                self.maybe_repair_loaded_session(&mut session, "get_or_create");
-
-                // Cache it in memory
-                let mut sessions = self.sessions.write().await;
-                sessions.insert(key.to_string(), session.clone());
-                return Ok(session);
-            }
+        // Try loading from the store if persistence is enabled
+        if let Some(session) = self.load_from_store(key, "get_or_create").await? {
+            return Ok(session);
         }
 
         // Create new session
@@ -209,25 +226,11 @@ impl SessionManager {
             }
         }
 
-        // Try loading from disk if persistence is enabled
-        if let Some(ref storage_path) = self.storage_path {
-            let file_path = storage_path.join(format!("{}.json", Self::sanitize_key(key)));
-            if file_path.exists() {
-                let content = tokio::fs::read_to_string(&file_path).await?;
-                let mut session: Session = serde_json::from_str(&content)?;
-                self.maybe_repair_loaded_session(&mut session, "get");
-
-                // Cache it in memory
-                let mut sessions = self.sessions.write().await;
-                sessions.insert(key.to_string(), session.clone());
-                return Ok(Some(session));
-            }
-        }
-
-        Ok(None)
+        // Try loading from the store if persistence is enabled
+        self.load_from_store(key, "get").await
     }
 
-    /// Save a session to both memory and disk (if persistence is enabled).
+    /// Save a session to both memory and the store (if persistence is enabled).
     ///
     /// # Arguments
     /// * `session` - The session to save
@@ -255,24 +258,22 @@ impl SessionManager {
             sessions.insert(session.key.clone(), session.clone());
         }
 
-        // Write to disk if persistence is enabled
-        if let Some(ref storage_path) = self.storage_path {
-            let file_path = storage_path.join(format!("{}.json", Self::sanitize_key(&session.key)));
-            let content = serde_json::to_string_pretty(session)?;
-            tokio::fs::write(&file_path, content).await?;
+        // Persist if a store is configured
+        if let Some(ref store) = self.store {
+            store.save(session).await?;
         }
 
         Ok(())
     }
 
-    /// Delete a session from both memory and disk.
+    /// Delete a session from both memory and the store.
     ///
     /// # Arguments
     /// * `key` - Unique session identifier
     ///
     /// # Errors
     ///
-    /// Returns an error if deleting from disk fails.
+    /// Returns an error if deleting from the store fails.
     ///
     /// # Example
     /// ```
@@ -292,12 +293,13 @@ impl SessionManager {
             sessions.remove(key);
         }
 
-        // Remove from disk if persistence is enabled
-        if let Some(ref storage_path) = self.storage_path {
-            let file_path = storage_path.join(format!("{}.json", Self::sanitize_key(key)));
-            if file_path.exists() {
-                tokio::fs::remove_file(&file_path).await?;
-            }
+        // Remove from the store if persistence is enabled
+        if let Some(ref store) = self.store {
+            store.delete(key).await?;
+        }
+
+        if let Some(ref uploads) = self.uploads {
+            uploads.remove_session(key).await?;
         }
 
         Ok(())
@@ -305,12 +307,12 @@ impl SessionManager {
 
     /// List all session keys.
     ///
-    /// Returns session keys from both memory and disk (if persistence is enabled).
+    /// Returns session keys from both memory and the store (if persistence is enabled).
     /// Duplicate keys are not included.
     ///
     /// # Errors
     ///
-    /// Returns an error if reading the storage directory fails.
+    /// Returns an error if listing the store fails.
     ///
     /// # Example
     /// ```
@@ -335,21 +337,11 @@ impl SessionManager {
             keys.extend(sessions.keys().cloned());
         }
 
-        // Get keys from disk if persistence is enabled
-        // We read each session file to get the actual key (not the sanitized filename)
-        if let Some(ref storage_path) = self.storage_path {
-            let mut dir_entries = tokio::fs::read_dir(storage_path).await?;
-            while let Some(entry) = dir_entries.next_entry().await? {
-                let path = entry.path();
-                if path.extension().map(|e| e == "json").unwrap_or(false) {
-                    // Read the session file to get the actual key
-                    if let Ok(content) = tokio::fs::read_to_string(&path).await {
-                        if let Ok(session) = serde_json::from_str::<Session>(&content) {
-                            if !keys.contains(&session.key) {
-                                keys.push(session.key);
-                            }
-                        }
-                    }
+        // Get keys from the store if persistence is enabled
+        if let Some(ref store) = self.store {
+            for key in store.list().await? {
+                if !keys.contains(&key) {
+                    keys.push(key);
                 }
             }
         }
@@ -365,7 +357,7 @@ impl SessionManager {
     ///
     /// # Returns
     ///
-    /// `true` if the session exists in memory or on disk.
+    /// `true` if the session exists in memory or in the store.
     pub async fn exists(&self, key: &str) -> bool {
         // Check memory
         {
@@ -375,16 +367,14 @@ impl SessionManager {
             }
         }
 
-        // Check disk
-        if let Some(ref storage_path) = self.storage_path {
-            let file_path = storage_path.join(format!("{}.json", Self::sanitize_key(key)));
-            return file_path.exists();
+        // Check the store
+        match self.store {
+            Some(ref store) => store.exists(key).await.unwrap_or(false),
+            None => false,
         }
-
-        false
     }
 
-    /// Clear all sessions from memory (does not affect disk).
+    /// Clear all sessions from memory (does not affect the store).
     ///
     /// Use this to free memory while keeping persisted sessions.
     pub async fn clear_cache(&self) {
@@ -398,73 +388,30 @@ impl SessionManager {
         sessions.len()
     }
 
-    /// Return the on-disk sessions directory, if persistence is enabled.
+    /// Return the on-disk sessions directory, if the store is file-based.
     ///
-    /// Returns `None` for in-memory-only managers created with `new_memory()`.
+    /// Returns `None` for in-memory-only managers created with `new_memory()`
+    /// and for stores without a local directory.
     /// Used by the agent loop to resolve `ImageSource::FilePath` entries to
     /// absolute paths before forwarding messages to LLM providers.
     pub fn sessions_dir(&self) -> Option<&std::path::Path> {
-        self.storage_path.as_deref()
-    }
-
-    /// Sanitize a session key for use as a filename.
-    ///
-    /// Uses percent-encoding to ensure the mapping is bijective (one-to-one).
-    /// This prevents collisions where different keys would map to the same filename.
-    ///
-    /// For example:
-    /// - "telegram:chat123" → "telegram%3Achat123"
-    /// - "discord/server" → "discord%2Fserver"
-    ///
-    /// This is reversible via `unsanitize_key`, ensuring keys round-trip correctly.
-    fn sanitize_key(key: &str) -> String {
-        // Characters that are problematic in filenames across platforms
-        // We percent-encode them to make the mapping reversible
-        let mut result = String::with_capacity(key.len() * 3);
-        for c in key.chars() {
-            match c {
-                '/' => result.push_str("%2F"),
-                '\\' => result.push_str("%5C"),
-                ':' => result.push_str("%3A"),
-                '*' => result.push_str("%2A"),
-                '?' => result.push_str("%3F"),
-                '"' => result.push_str("%22"),
-                '<' => result.push_str("%3C"),
-                '>' => result.push_str("%3E"),
-                '|' => result.push_str("%7C"),
-                '%' => result.push_str("%25"), // Escape % itself to make it reversible
-                c => result.push(c),
-            }
-        }
-        result
-    }
-
-    /// Reverse the sanitization to recover the original key.
-    ///
-    /// This is the inverse of `sanitize_key`.
-    #[allow(dead_code)]
-    fn unsanitize_key(sanitized: &str) -> String {
-        let mut result = String::with_capacity(sanitized.len());
-        let mut chars = sanitized.chars().peekable();
-
-        while let Some(c) = chars.next() {
-            if c == '%' {
-                // Try to read two hex digits
-                let hex: String = chars.by_ref().take(2).collect();
-                if hex.len() == 2 {
-                    if let Ok(byte) = u8::from_str_radix(&hex, 16) {
-                        result.push(byte as char);
-                        continue;
-                    }
-                }
-                // If parsing failed, just keep the % and the hex chars
-                result.push('%');
-                result.push_str(&hex);
-            } else {
-                result.push(c);
-            }
-        }
-        result
+        self.store.as_ref().and_then(|store| store.local_dir())
+    }
+
+    /// Load a session from the store, repair it, and cache it in memory.
+    async fn load_from_store(&self, key: &str, source: &str) -> Result<Option<Session>> {
+        let Some(ref store) = self.store else {
+            return Ok(None);
+        };
+        let Some(mut session) = store.load(key).await? else {
+            return Ok(None);
+        };
+        self.maybe_repair_loaded_session(&mut session, source);
+
+        // Cache it in memory
+        let mut sessions = self.sessions.write().await;
+        sessions.insert(key.to_string(), session.clone());
+        Ok(Some(session))
     }
 
     fn maybe_repair_loaded_session(&self, session: &mut Session, source: &str) {
@@ -493,7 +440,8 @@ impl Clone for SessionManager {
     fn clone(&self) -> Self {
         Self {
             sessions: Arc::clone(&self.sessions),
-            storage_path: self.storage_path.clone(),
+            store: self.store.clone(),
+            uploads: self.uploads.clone(),
         }
     }
 }
@@ -676,71 +624,6 @@ mod tests {
         assert!(keys.contains(&"gamma".to_string()));
     }
 
-    #[test]
-    fn test_sanitize_key() {
-        // Simple keys pass through unchanged
-        assert_eq!(SessionManager::sanitize_key("simple"), "simple");
-        // Special characters are percent-encoded
-        assert_eq!(
-            SessionManager::sanitize_key("telegram:chat123"),
-            "telegram%3Achat123"
-        );
-        assert_eq!(
-            SessionManager::sanitize_key("path/to/session"),
-            "path%2Fto%2Fsession"
-        );
-        assert_eq!(
-            SessionManager::sanitize_key("a:b/c\\d*e?f\"g<h>i|j"),
-            "a%3Ab%2Fc%5Cd%2Ae%3Ff%22g%3Ch%3Ei%7Cj"
-        );
-        // Percent itself is escaped to make encoding reversible
-        assert_eq!(SessionManager::sanitize_key("100%done"), "100%25done");
-    }
-
-    #[test]
-    fn test_unsanitize_key() {
-        // Round-trip: sanitize then unsanitize should return original
-        let keys = [
-            "simple",
-            "telegram:chat123",
-            "path/to/session",
-            "a:b/c\\d*e?f\"g<h>i|j",
-            "100%done",
-            "multi%percent%%test",
-        ];
-        for key in &keys {
-            let sanitized = SessionManager::sanitize_key(key);
-            let unsanitized = SessionManager::unsanitize_key(&sanitized);
-            assert_eq!(
-                unsanitized, *key,
-                "Key '{}' should round-trip through sanitize/unsanitize",
-                key
-            );
-        }
-    }
-
-    #[test]
-    fn test_sanitize_key_no_collisions() {
-        // Keys that would collide with the old underscore-replacement approach
-        // should now produce different sanitized values
-        let key1 = "a:b";
-        let key2 = "a/b";
-        let key3 = "a_b"; // This one has an actual underscore
-
-        let sanitized1 = SessionManager::sanitize_key(key1);
-        let sanitized2 = SessionManager::sanitize_key(key2);
-        let sanitized3 = SessionManager::sanitize_key(key3);
-
-        assert_ne!(sanitized1, sanitized2, "a:b and a/b should not collide");
-        assert_ne!(sanitized1, sanitized3, "a:b and a_b should not collide");
-        assert_ne!(sanitized2, sanitized3, "a/b and a_b should not collide");
-
-        // Verify the actual values
-        assert_eq!(sanitized1, "a%3Ab");
-        assert_eq!(sanitized2, "a%2Fb");
-        assert_eq!(sanitized3, "a_b");
-    }
-
     #[tokio::test]
     async fn test_list_returns_original_keys_with_special_chars() {
         // Regression test: list() should return original keys, not sanitized filenames
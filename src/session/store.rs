@@ -0,0 +1,331 @@
+//! Pluggable persistence backends for sessions.
+//!
+//! [`SessionManager`](super::SessionManager) keeps an in-memory cache and
+//! delegates durable storage to a [`SessionStore`].  The default backend is
+//! [`FileSessionStore`], which writes one JSON file per session; a shared
+//! backend (Redis, SQLite, ...) can be dropped in via
+//! [`SessionManager::with_store`](super::SessionManager::with_store) to run
+//! several instances against the same sessions.
+
+use async_trait::async_trait;
+use std::path::{Path, PathBuf};
+
+use super::Session;
+use crate::error::Result;
+
+/// Durable storage for conversation sessions.
+#[async_trait]
+pub trait SessionStore: Send + Sync {
+    /// Load a session by key, or `None` if it has never been saved.
+    async fn load(&self, key: &str) -> Result<Option<Session>>;
+
+    /// Insert or replace a session.
+    async fn save(&self, session: &Session) -> Result<()>;
+
+    /// List the keys of all stored sessions.
+    async fn list(&self) -> Result<Vec<String>>;
+
+    /// Delete a session.  Deleting a missing key is not an error.
+    async fn delete(&self, key: &str) -> Result<()>;
+
+    /// Check whether a session is stored.
+    async fn exists(&self, key: &str) -> Result<bool> {
+        Ok(self.load(key).await?.is_some())
+    }
+
+    /// Local directory holding session data, if the backend is file-based.
+    ///
+    /// Used to resolve `ImageSource::FilePath` entries relative to the
+    /// session store.
+    fn local_dir(&self) -> Option<&Path> {
+        None
+    }
+}
+
+/// Session store that keeps each session as a JSON file in a directory.
+///
+/// Filenames are the percent-encoded session key plus `.json`; the original
+/// key is read back from the file contents when listing.
+pub struct FileSessionStore {
+    dir: PathBuf,
+}
+
+impl FileSessionStore {
+    /// Create a file store rooted at `dir`, creating the directory if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be created.
+    pub fn new(dir: PathBuf) -> Result<Self> {
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    /// The directory sessions are stored in.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn file_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", Self::sanitize_key(key)))
+    }
+
+    /// Sanitize a session key for use as a filename.
+    ///
+    /// Uses percent-encoding to ensure the mapping is bijective (one-to-one).
+    /// This prevents collisions where different keys would map to the same filename.
+    ///
+    /// For example:
+    /// - "telegram:chat123" → "telegram%3Achat123"
+    /// - "discord/server" → "discord%2Fserver"
+    ///
+    /// This is reversible via `unsanitize_key`, ensuring keys round-trip correctly.
+    fn sanitize_key(key: &str) -> String {
+        // Characters that are problematic in filenames across platforms
+        // We percent-encode them to make the mapping reversible
+        let mut result = String::with_capacity(key.len() * 3);
+        for c in key.chars() {
+            match c {
+                '/' => result.push_str("%2F"),
+                '\\' => result.push_str("%5C"),
+                ':' => result.push_str("%3A"),
+                '*' => result.push_str("%2A"),
+                '?' => result.push_str("%3F"),
+                '"' => result.push_str("%22"),
+                '<' => result.push_str("%3C"),
+                '>' => result.push_str("%3E"),
+                '|' => result.push_str("%7C"),
+                '%' => result.push_str("%25"), // Escape % itself to make it reversible
+                c => result.push(c),
+            }
+        }
+        result
+    }
+
+    /// Reverse the sanitization to recover the original key.
+    ///
+    /// This is the inverse of `sanitize_key`.
+    #[allow(dead_code)]
+    fn unsanitize_key(sanitized: &str) -> String {
+        let mut result = String::with_capacity(sanitized.len());
+        let mut chars = sanitized.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            if c == '%' {
+                // Try to read two hex digits
+                let hex: String = chars.by_ref().take(2).collect();
+                if hex.len() == 2 {
+                    if let Ok(byte) = u8::from_str_radix(&hex, 16) {
+                        result.push(byte as char);
+                        continue;
+                    }
+                }
+                // If parsing failed, just keep the % and the hex chars
+                result.push('%');
+                result.push_str(&hex);
+            } else {
+                result.push(c);
+            }
+        }
+        result
+    }
+}
+
+#[async_trait]
+impl SessionStore for FileSessionStore {
+    async fn load(&self, key: &str) -> Result<Option<Session>> {
+        let file_path = self.file_path(key);
+        if !file_path.exists() {
+            return Ok(None);
+        }
+        let content = tokio::fs::read_to_string(&file_path).await?;
+        Ok(Some(serde_json::from_str(&content)?))
+    }
+
+    async fn save(&self, session: &Session) -> Result<()> {
+        let content = serde_json::to_string_pretty(session)?;
+        tokio::fs::write(self.file_path(&session.key), content).await?;
+        Ok(())
+    }
+
+    async fn list(&self) -> Result<Vec<String>> {
+        // We read each session file to get the actual key (not the sanitized filename)
+        let mut keys = Vec::new();
+        let mut dir_entries = tokio::fs::read_dir(&self.dir).await?;
+        while let Some(entry) = dir_entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().map(|e| e == "json").unwrap_or(false) {
+                if let Ok(content) = tokio::fs::read_to_string(&path).await {
+                    if let Ok(session) = serde_json::from_str::<Session>(&content) {
+                        keys.push(session.key);
+                    }
+                }
+            }
+        }
+        keys.sort();
+        Ok(keys)
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        let file_path = self.file_path(key);
+        if file_path.exists() {
+            tokio::fs::remove_file(&file_path).await?;
+        }
+        Ok(())
+    }
+
+    async fn exists(&self, key: &str) -> Result<bool> {
+        Ok(self.file_path(key).exists())
+    }
+
+    fn local_dir(&self) -> Option<&Path> {
+        Some(&self.dir)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::Message;
+    use tempfile::TempDir;
+
+    fn store() -> (TempDir, FileSessionStore) {
+        let dir = TempDir::new().unwrap();
+        let store = FileSessionStore::new(dir.path().join("sessions")).unwrap();
+        (dir, store)
+    }
+
+    #[tokio::test]
+    async fn test_file_store_save_load_round_trip() {
+        let (_dir, store) = store();
+        assert!(store.load("telegram:1").await.unwrap().is_none());
+        assert!(!store.exists("telegram:1").await.unwrap());
+
+        let mut session = Session::new("telegram:1");
+        session.add_message(Message::user("hello"));
+        session.add_message(Message::assistant("hi"));
+        store.save(&session).await.unwrap();
+
+        assert!(store.exists("telegram:1").await.unwrap());
+        let loaded = store.load("telegram:1").await.unwrap().unwrap();
+        assert_eq!(loaded.key, "telegram:1");
+        assert_eq!(loaded.messages.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_file_store_save_replaces_existing() {
+        let (_dir, store) = store();
+        let mut session = Session::new("k");
+        store.save(&session).await.unwrap();
+        session.add_message(Message::user("later"));
+        store.save(&session).await.unwrap();
+
+        let loaded = store.load("k").await.unwrap().unwrap();
+        assert_eq!(loaded.messages.len(), 1);
+        assert_eq!(store.list().await.unwrap(), vec!["k".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_file_store_list_returns_original_keys_sorted() {
+        let (_dir, store) = store();
+        for key in ["slack:b/c", "discord:a", "telegram:100%"] {
+            store.save(&Session::new(key)).await.unwrap();
+        }
+        assert_eq!(
+            store.list().await.unwrap(),
+            vec![
+                "discord:a".to_string(),
+                "slack:b/c".to_string(),
+                "telegram:100%".to_string()
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_file_store_delete() {
+        let (_dir, store) = store();
+        store.save(&Session::new("a")).await.unwrap();
+        store.save(&Session::new("b")).await.unwrap();
+
+        store.delete("a").await.unwrap();
+        assert!(store.load("a").await.unwrap().is_none());
+        assert_eq!(store.list().await.unwrap(), vec!["b".to_string()]);
+
+        // Deleting a missing key is a no-op.
+        store.delete("a").await.unwrap();
+    }
+
+    #[test]
+    fn test_file_store_local_dir() {
+        let (dir, store) = store();
+        assert_eq!(
+            store.local_dir(),
+            Some(dir.path().join("sessions").as_path())
+        );
+    }
+
+    #[test]
+    fn test_sanitize_key() {
+        // Simple keys pass through unchanged
+        assert_eq!(FileSessionStore::sanitize_key("simple"), "simple");
+        // Special characters are percent-encoded
+        assert_eq!(
+            FileSessionStore::sanitize_key("telegram:chat123"),
+            "telegram%3Achat123"
+        );
+        assert_eq!(
+            FileSessionStore::sanitize_key("path/to/session"),
+            "path%2Fto%2Fsession"
+        );
+        assert_eq!(
+            FileSessionStore::sanitize_key("a:b/c\\d*e?f\"g<h>i|j"),
+            "a%3Ab%2Fc%5Cd%2Ae%3Ff%22g%3Ch%3Ei%7Cj"
+        );
+        // Percent itself is escaped to make encoding reversible
+        assert_eq!(FileSessionStore::sanitize_key("100%done"), "100%25done");
+    }
+
+    #[test]
+    fn test_unsanitize_key() {
+        // Round-trip: sanitize then unsanitize should return original
+        let keys = [
+            "simple",
+            "telegram:chat123",
+            "path/to/session",
+            "a:b/c\\d*e?f\"g<h>i|j",
+            "100%done",
+            "multi%percent%%test",
+        ];
+        for key in &keys {
+            let sanitized = FileSessionStore::sanitize_key(key);
+            let unsanitized = FileSessionStore::unsanitize_key(&sanitized);
+            assert_eq!(
+                unsanitized, *key,
+                "Key '{}' should round-trip through sanitize/unsanitize",
+                key
+            );
+        }
+    }
+
+    #[test]
+    fn test_sanitize_key_no_collisions() {
+        // Keys that would collide with the old underscore-replacement approach
+        // should now produce different sanitized values
+        let key1 = "a:b";
+        let key2 = "a/b";
+        let key3 = "a_b"; // This one has an actual underscore
+
+        let sanitized1 = FileSessionStore::sanitize_key(key1);
+        let sanitized2 = FileSessionStore::sanitize_key(key2);
+        let sanitized3 = FileSessionStore::sanitize_key(key3);
+
+        assert_ne!(sanitized1, sanitized2, "a:b and a/b should not collide");
+        assert_ne!(sanitized1, sanitized3, "a:b and a_b should not collide");
+        assert_ne!(sanitized2, sanitized3, "a/b and a_b should not collide");
+
+        // Verify the actual values
+        assert_eq!(sanitized1, "a%3Ab");
+        assert_eq!(sanitized2, "a%2Fb");
+        assert_eq!(sanitized3, "a_b");
+    }
+}
@@ -18,6 +18,20 @@ pub struct Session {
     pub messages: Vec<Message>,
     /// Optional summary of previous conversation context
     pub summary: Option<String>,
+    /// Reply language for this conversation (primary subtag, e.g. "es")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    /// Notes pinned to this conversation; kept outside `messages` so
+    /// truncation and compaction never drop them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pinned: Vec<PinnedNote>,
+    /// Plan drafted in plan mode that still awaits the user's approval
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pending_plan: Option<PendingPlan>,
+    /// Hand switched to for this conversation with `use_hand`, overriding
+    /// the configured active hand
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_hand: Option<String>,
     /// When this session was created
     pub created_at: DateTime<Utc>,
     /// When this session was last modified
@@ -43,6 +57,10 @@ impl Session {
             key: key.to_string(),
             messages: Vec::new(),
             summary: None,
+            locale: None,
+            pinned: Vec::new(),
+            pending_plan: None,
+            active_hand: None,
             created_at: now,
             updated_at: now,
         }
@@ -117,6 +135,100 @@ impl Session {
     pub fn messages_by_role(&self, role: Role) -> Vec<&Message> {
         self.messages.iter().filter(|m| m.role == role).collect()
     }
+
+    /// Pin a note to this session and return its ID.
+    ///
+    /// # Example
+    /// ```
+    /// use zeptoclaw::session::Session;
+    ///
+    /// let mut session = Session::new("test");
+    /// let id = session.pin("Deploys go through staging first");
+    /// assert!(session.pinned_context().unwrap().contains("staging"));
+    /// assert!(session.unpin(id));
+    /// assert!(session.pinned_context().is_none());
+    /// ```
+    pub fn pin(&mut self, text: &str) -> u32 {
+        let id = self.pinned.iter().map(|n| n.id).max().unwrap_or(0) + 1;
+        self.pinned.push(PinnedNote {
+            id,
+            text: text.trim().to_string(),
+            pinned_at: Utc::now(),
+        });
+        self.updated_at = Utc::now();
+        id
+    }
+
+    /// Remove a pinned note. Returns `false` if no note has that ID.
+    pub fn unpin(&mut self, id: u32) -> bool {
+        let before = self.pinned.len();
+        self.pinned.retain(|n| n.id != id);
+        let removed = self.pinned.len() != before;
+        if removed {
+            self.updated_at = Utc::now();
+        }
+        removed
+    }
+
+    /// Pinned notes rendered for injection after the system prompt, or
+    /// `None` when nothing is pinned.
+    pub fn pinned_context(&self) -> Option<String> {
+        if self.pinned.is_empty() {
+            return None;
+        }
+        let notes: Vec<String> = self
+            .pinned
+            .iter()
+            .map(|n| format!("- [{}] {}", n.id, n.text))
+            .collect();
+        Some(format!(
+            "## Pinned Context\nAlways keep these in mind for this conversation:\n{}",
+            notes.join("\n")
+        ))
+    }
+}
+
+/// A note pinned to a session.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PinnedNote {
+    /// Session-local ID used to unpin the note
+    pub id: u32,
+    /// Note text
+    pub text: String,
+    /// When the note was pinned
+    pub pinned_at: DateTime<Utc>,
+}
+
+/// A step plan drafted in plan mode, awaiting the user's approval.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PendingPlan {
+    /// The request the plan was drafted for
+    pub task: String,
+    /// Numbered steps, in order, without their numbers
+    pub steps: Vec<String>,
+    /// When the plan was proposed
+    pub created_at: DateTime<Utc>,
+}
+
+impl PendingPlan {
+    /// Create a plan proposed now.
+    pub fn new(task: &str, steps: Vec<String>) -> Self {
+        Self {
+            task: task.to_string(),
+            steps,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Whether the plan has waited longer than `timeout_secs` for approval
+    /// (`0` = never expires).
+    pub fn is_expired(&self, timeout_secs: u64) -> bool {
+        timeout_secs > 0
+            && Utc::now()
+                .signed_duration_since(self.created_at)
+                .num_seconds()
+                > timeout_secs as i64
+    }
 }
 
 /// A content part within a message — either text or an image.
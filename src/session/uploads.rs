@@ -0,0 +1,377 @@
+//! Workspace storage for files users upload through channels.
+//!
+//! Attachments that arrive with inbound messages (documents, images,
+//! archives) are saved under the workspace so file tools such as `read_file`
+//! and `pdf_read` can open them. The agent is told where each file landed by
+//! a note appended to the user message.
+//!
+//! # Layout
+//!
+//! ```text
+//! {workspace}/
+//! └── uploads/
+//!     └── telegram_12345/      # one directory per session
+//!         ├── report.pdf
+//!         └── report_2.pdf
+//! ```
+//!
+//! A session's directory is removed when the session is deleted (see
+//! [`SessionManager::with_uploads`](super::SessionManager::with_uploads)).
+//!
+//! Besides the per-file cap, a session can be limited in how many files and
+//! bytes it keeps in total ([`UploadStore::with_session_limits`]). Usage is
+//! read from the session directory, so it survives restarts and resets when
+//! the session — and with it the directory — is deleted.
+
+use std::path::{Path, PathBuf};
+
+use tokio::fs;
+
+use crate::bus::InboundMessage;
+use crate::error::{Result, ZeptoError};
+use crate::security::validate_path_in_workspace;
+
+/// Workspace subdirectory that holds uploads.
+pub const UPLOADS_DIR: &str = "uploads";
+
+/// Maximum size of a single uploaded file (20 MiB).
+pub const MAX_UPLOAD_SIZE: usize = 20 * 1024 * 1024;
+
+/// Longest file name kept after sanitizing.
+const MAX_FILENAME_LEN: usize = 100;
+
+/// Saves inbound attachments into per-session workspace directories.
+#[derive(Debug, Clone)]
+pub struct UploadStore {
+    workspace: PathBuf,
+    max_size: usize,
+    /// Files a session may keep in total. 0 = unlimited.
+    max_session_files: usize,
+    /// Bytes a session may keep in total. 0 = unlimited.
+    max_session_bytes: u64,
+}
+
+impl UploadStore {
+    /// Create a store writing under `{workspace}/uploads/`.
+    pub fn new(workspace: impl Into<PathBuf>) -> Self {
+        Self {
+            workspace: workspace.into(),
+            max_size: MAX_UPLOAD_SIZE,
+            max_session_files: 0,
+            max_session_bytes: 0,
+        }
+    }
+
+    /// Override the per-file size cap (builder pattern).
+    pub fn with_max_size(mut self, max_size: usize) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// Cap the files and bytes each session may keep in total; 0 means
+    /// unlimited (builder pattern).
+    pub fn with_session_limits(mut self, max_files: usize, max_bytes: u64) -> Self {
+        self.max_session_files = max_files;
+        self.max_session_bytes = max_bytes;
+        self
+    }
+
+    /// Number of files and total bytes stored for a session.
+    pub async fn session_usage(&self, session_key: &str) -> Result<(usize, u64)> {
+        let dir = self.workspace.join(self.session_dir(session_key));
+        let mut entries = match fs::read_dir(&dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok((0, 0)),
+            Err(e) => return Err(e.into()),
+        };
+        let (mut files, mut bytes) = (0, 0);
+        while let Some(entry) = entries.next_entry().await? {
+            let metadata = entry.metadata().await?;
+            if metadata.is_file() {
+                files += 1;
+                bytes += metadata.len();
+            }
+        }
+        Ok((files, bytes))
+    }
+
+    /// Workspace-relative directory for a session's uploads.
+    pub fn session_dir(&self, session_key: &str) -> PathBuf {
+        let key = session_key.replace(['/', '\\'], "_");
+        Path::new(UPLOADS_DIR).join(sanitize_component(&key, "session"))
+    }
+
+    /// Save `data` as `filename` in the session's upload directory.
+    ///
+    /// The name is reduced to a safe base name, and a numeric suffix is
+    /// added if a file with that name already exists. Returns the path
+    /// relative to the workspace, e.g. `./uploads/telegram_1/report.pdf`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exceeds the size cap or the session's
+    /// total limits, the target resolves outside the workspace, or the write
+    /// fails.
+    pub async fn save(
+        &self,
+        session_key: &str,
+        filename: Option<&str>,
+        data: &[u8],
+    ) -> Result<String> {
+        if data.len() > self.max_size {
+            return Err(ZeptoError::Tool(format!(
+                "Upload too large: {} bytes (limit {} bytes)",
+                data.len(),
+                self.max_size
+            )));
+        }
+        if self.max_session_files > 0 || self.max_session_bytes > 0 {
+            let (files, bytes) = self.session_usage(session_key).await?;
+            if self.max_session_files > 0 && files >= self.max_session_files {
+                return Err(ZeptoError::Tool(format!(
+                    "Upload limit reached: this conversation already has {} files (limit {}). \
+                     Start a new session to upload more.",
+                    files, self.max_session_files
+                )));
+            }
+            if self.max_session_bytes > 0 && bytes + data.len() as u64 > self.max_session_bytes {
+                return Err(ZeptoError::Tool(format!(
+                    "Upload limit reached: {} more bytes would exceed this conversation's {} \
+                     byte limit ({} bytes already stored). Start a new session to upload more.",
+                    data.len(),
+                    self.max_session_bytes,
+                    bytes
+                )));
+            }
+        }
+
+        let dir = self.session_dir(session_key);
+        let name = sanitize_component(filename.unwrap_or_default(), "attachment");
+        let workspace = self.workspace.to_string_lossy();
+
+        let mut candidate = name.clone();
+        let mut counter = 2;
+        let target = loop {
+            let relative = dir.join(&candidate);
+            let safe = validate_path_in_workspace(&relative.to_string_lossy(), &workspace)?;
+            if !fs::try_exists(safe.as_path()).await.unwrap_or(false) {
+                break relative;
+            }
+            candidate = with_suffix(&name, counter);
+            counter += 1;
+        };
+
+        let absolute = self.workspace.join(&target);
+        if let Some(parent) = absolute.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        fs::write(&absolute, data).await?;
+
+        Ok(format!("./{}", target.to_string_lossy().replace('\\', "/")))
+    }
+
+    /// Save every attachment carrying data and annotate the message with
+    /// where each one landed.
+    ///
+    /// Attachments that fail to save are noted in the message instead, so
+    /// the agent does not look for a file that is not there.
+    pub async fn store_attachments(&self, msg: &InboundMessage) -> InboundMessage {
+        let mut notes = Vec::new();
+        for media in &msg.media {
+            let Some(data) = media.data.as_deref() else {
+                continue;
+            };
+            let name = media.filename.as_deref().unwrap_or("attachment");
+            match self
+                .save(&msg.session_key, media.filename.as_deref(), data)
+                .await
+            {
+                Ok(path) => notes.push(format!("[attached: {} at {}]", name, path)),
+                Err(e) => {
+                    tracing::warn!(file = %name, error = %e, "Failed to save upload");
+                    notes.push(format!("[attachment {} could not be saved: {}]", name, e));
+                }
+            }
+        }
+
+        let mut annotated = msg.clone();
+        annotated.content = annotate(&msg.content, &notes);
+        annotated
+    }
+
+    /// Delete a session's upload directory, if any.
+    pub async fn remove_session(&self, session_key: &str) -> Result<()> {
+        let dir = self.workspace.join(self.session_dir(session_key));
+        match fs::remove_dir_all(&dir).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// Append upload notes to message content, one per line.
+pub fn annotate(content: &str, notes: &[String]) -> String {
+    if notes.is_empty() {
+        return content.to_string();
+    }
+    let notes = notes.join("\n");
+    if content.trim().is_empty() {
+        notes
+    } else {
+        format!("{}\n\n{}", content, notes)
+    }
+}
+
+/// Reduce `raw` to a single safe path component.
+///
+/// Only the final path segment is kept; characters outside
+/// `[A-Za-z0-9._-]` become `_`, and leading dots are dropped so the result
+/// is never hidden, `.` or `..`.
+fn sanitize_component(raw: &str, fallback: &str) -> String {
+    let base = raw.rsplit(['/', '\\']).next().unwrap_or_default();
+    let cleaned: String = base
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let cleaned = cleaned.trim_start_matches('.');
+    if cleaned.is_empty() {
+        return fallback.to_string();
+    }
+    cleaned.chars().take(MAX_FILENAME_LEN).collect()
+}
+
+/// `report.pdf` -> `report_2.pdf`.
+fn with_suffix(name: &str, n: u32) -> String {
+    match name.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => format!("{}_{}.{}", stem, n, ext),
+        _ => format!("{}_{}", name, n),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bus::{MediaAttachment, MediaType};
+
+    #[test]
+    fn test_sanitize_component_strips_traversal() {
+        assert_eq!(sanitize_component("../../etc/passwd", "x"), "passwd");
+        assert_eq!(sanitize_component("..\\..\\boot.ini", "x"), "boot.ini");
+        assert_eq!(sanitize_component("..", "x"), "x");
+        assert_eq!(sanitize_component(".env", "x"), "env");
+        assert_eq!(
+            sanitize_component("my report (1).pdf", "x"),
+            "my_report__1_.pdf"
+        );
+        assert_eq!(sanitize_component("telegram:123", "x"), "telegram_123");
+        assert_eq!(sanitize_component("", "attachment"), "attachment");
+    }
+
+    #[tokio::test]
+    async fn test_save_stays_in_session_dir() {
+        let workspace = tempfile::tempdir().unwrap();
+        let store = UploadStore::new(workspace.path()).with_max_size(16);
+
+        let path = store
+            .save("telegram:42", Some("../../outside.txt"), b"hello")
+            .await
+            .unwrap();
+        assert_eq!(path, "./uploads/telegram_42/outside.txt");
+        assert!(workspace
+            .path()
+            .join("uploads/telegram_42/outside.txt")
+            .is_file());
+        assert!(!workspace.path().join("outside.txt").exists());
+
+        // Same name again gets a suffix instead of overwriting.
+        let second = store
+            .save("telegram:42", Some("outside.txt"), b"again")
+            .await
+            .unwrap();
+        assert_eq!(second, "./uploads/telegram_42/outside_2.txt");
+
+        // Oversized uploads are rejected.
+        assert!(store
+            .save("telegram:42", Some("big.bin"), &[0u8; 17])
+            .await
+            .is_err());
+
+        store.remove_session("telegram:42").await.unwrap();
+        assert!(!workspace.path().join("uploads/telegram_42").exists());
+    }
+
+    #[tokio::test]
+    async fn test_session_limits_are_cumulative_and_reset_with_session() {
+        let workspace = tempfile::tempdir().unwrap();
+        let store = UploadStore::new(workspace.path()).with_session_limits(3, 10);
+
+        store
+            .save("telegram:42", Some("a.txt"), b"1234")
+            .await
+            .unwrap();
+        store
+            .save("telegram:42", Some("b.txt"), b"1234")
+            .await
+            .unwrap();
+        assert_eq!(store.session_usage("telegram:42").await.unwrap(), (2, 8));
+
+        // 8 + 4 bytes would exceed the 10-byte cap.
+        let err = store
+            .save("telegram:42", Some("c.txt"), b"1234")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Upload limit reached"));
+        assert!(!workspace.path().join("uploads/telegram_42/c.txt").exists());
+        store
+            .save("telegram:42", Some("c.txt"), b"12")
+            .await
+            .unwrap();
+
+        // The file cap applies even to small files.
+        assert!(store.save("telegram:42", Some("d.txt"), b"").await.is_err());
+
+        // Other sessions have their own allowance.
+        store
+            .save("telegram:7", Some("a.txt"), b"1234")
+            .await
+            .unwrap();
+
+        // Ending the session frees its allowance.
+        store.remove_session("telegram:42").await.unwrap();
+        assert_eq!(store.session_usage("telegram:42").await.unwrap(), (0, 0));
+        store
+            .save("telegram:42", Some("a.txt"), b"1234")
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_store_attachments_annotates_message() {
+        let workspace = tempfile::tempdir().unwrap();
+        let store = UploadStore::new(workspace.path());
+        let msg = InboundMessage::new("discord", "u1", "c1", "Summarize this")
+            .with_media(
+                MediaAttachment::new(MediaType::Document)
+                    .with_data(b"%PDF-1.4".to_vec())
+                    .with_filename("report.pdf"),
+            )
+            .with_media(MediaAttachment::new(MediaType::Image).with_url("https://x/y.png"));
+
+        let annotated = store.store_attachments(&msg).await;
+        assert_eq!(
+            annotated.content,
+            "Summarize this\n\n[attached: report.pdf at ./uploads/discord_c1/report.pdf]"
+        );
+        assert_eq!(annotated.media.len(), 2);
+
+        assert_eq!(annotate("", &["[attached: a]".into()]), "[attached: a]");
+        assert_eq!(annotate("hi", &[]), "hi");
+    }
+}
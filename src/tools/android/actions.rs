@@ -4,7 +4,7 @@
 //! app management, and device control. Includes text escaping for
 //! shell metacharacters and coordinate sanitization.
 
-use crate::error::{Result, ZeptoError};
+use crate::error::{Result, ToolErrorKind, ZeptoError};
 
 use super::adb::AdbExecutor;
 
@@ -51,14 +51,12 @@ pub fn parse_coordinates(
     if let Some(coords) = coords_val.and_then(|v| v.as_str()) {
         let parts: Vec<&str> = coords.split([',', ' ']).filter(|s| !s.is_empty()).collect();
         if parts.len() == 2 {
-            let x = parts[0]
-                .trim()
-                .parse::<i32>()
-                .map_err(|_| ZeptoError::Tool("Invalid x coordinate".into()))?;
-            let y = parts[1]
-                .trim()
-                .parse::<i32>()
-                .map_err(|_| ZeptoError::Tool("Invalid y coordinate".into()))?;
+            let x = parts[0].trim().parse::<i32>().map_err(|_| {
+                ZeptoError::tool(ToolErrorKind::InvalidArgument, "Invalid x coordinate")
+            })?;
+            let y = parts[1].trim().parse::<i32>().map_err(|_| {
+                ZeptoError::tool(ToolErrorKind::InvalidArgument, "Invalid y coordinate")
+            })?;
             return validate_coords(x, y);
         }
     }
@@ -96,31 +94,42 @@ pub fn parse_coordinates(
 
 pub(crate) fn value_to_i32(v: &serde_json::Value) -> Result<i32> {
     if let Some(n) = v.as_i64() {
-        i32::try_from(n).map_err(|_| ZeptoError::Tool(format!("Coordinate {} out of i32 range", n)))
+        i32::try_from(n).map_err(|_| {
+            ZeptoError::tool(
+                ToolErrorKind::InvalidArgument,
+                format!("Coordinate {} out of i32 range", n),
+            )
+        })
     } else if let Some(n) = v.as_f64() {
         let rounded = n.round();
         if rounded < i32::MIN as f64 || rounded > i32::MAX as f64 {
-            return Err(ZeptoError::Tool(format!(
-                "Coordinate {} out of i32 range",
-                n
-            )));
+            return Err(ZeptoError::tool(
+                ToolErrorKind::InvalidArgument,
+                format!("Coordinate {} out of i32 range", n),
+            ));
         }
         Ok(rounded as i32)
     } else if let Some(s) = v.as_str() {
-        s.trim()
-            .parse::<i32>()
-            .map_err(|_| ZeptoError::Tool(format!("Cannot parse '{}' as coordinate", s)))
+        s.trim().parse::<i32>().map_err(|_| {
+            ZeptoError::tool(
+                ToolErrorKind::InvalidArgument,
+                format!("Cannot parse '{}' as coordinate", s),
+            )
+        })
     } else {
-        Err(ZeptoError::Tool("Expected numeric coordinate".into()))
+        Err(ZeptoError::tool(
+            ToolErrorKind::InvalidArgument,
+            "Expected numeric coordinate",
+        ))
     }
 }
 
 fn validate_coords(x: i32, y: i32) -> Result<(i32, i32)> {
     if !(0..=10000).contains(&x) || !(0..=10000).contains(&y) {
-        return Err(ZeptoError::Tool(format!(
-            "Coordinates out of range: ({}, {}). Must be 0-10000.",
-            x, y
-        )));
+        return Err(ZeptoError::tool(
+            ToolErrorKind::InvalidArgument,
+            format!("Coordinates out of range: ({}, {}). Must be 0-10000.", x, y),
+        ));
     }
     Ok((x, y))
 }
@@ -179,10 +188,13 @@ pub async fn scroll(
         "left" => (screen_w * 3 / 4, screen_h / 2, screen_w / 4, screen_h / 2),
         "right" => (screen_w / 4, screen_h / 2, screen_w * 3 / 4, screen_h / 2),
         _ => {
-            return Err(ZeptoError::Tool(format!(
-                "Invalid scroll direction '{}'. Use: up, down, left, right",
-                direction
-            )));
+            return Err(ZeptoError::tool(
+                ToolErrorKind::InvalidArgument,
+                format!(
+                    "Invalid scroll direction '{}'. Use: up, down, left, right",
+                    direction
+                ),
+            ));
         }
     };
     adb.shell(&format!("input swipe {} {} {} {} 500", x1, y1, x2, y2))
@@ -235,10 +247,13 @@ pub async fn enter(adb: &AdbExecutor) -> Result<String> {
 /// Send a key event by code or name.
 pub async fn key_event(adb: &AdbExecutor, key: &str) -> Result<String> {
     if !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
-        return Err(ZeptoError::Tool(format!(
-            "Invalid key code '{}': must be alphanumeric/underscore (e.g., KEYCODE_BACK or 66)",
-            key
-        )));
+        return Err(ZeptoError::tool(
+            ToolErrorKind::InvalidArgument,
+            format!(
+                "Invalid key code '{}': must be alphanumeric/underscore (e.g., KEYCODE_BACK or 66)",
+                key
+            ),
+        ));
     }
     adb.shell(&format!("input keyevent {}", key)).await?;
     Ok(format!("Sent key event: {}", key))
@@ -317,10 +332,13 @@ pub async fn launch_app(adb: &AdbExecutor, package: &str) -> Result<String> {
         .chars()
         .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '_')
     {
-        return Err(ZeptoError::Tool(format!(
-            "Invalid package name '{}': must match [a-zA-Z0-9_.]+",
-            package
-        )));
+        return Err(ZeptoError::tool(
+            ToolErrorKind::InvalidArgument,
+            format!(
+                "Invalid package name '{}': must match [a-zA-Z0-9_.]+",
+                package
+            ),
+        ));
     }
     // Try monkey first (works without knowing activity name)
     let result = adb
@@ -359,10 +377,13 @@ const ALLOWED_URL_SCHEMES: &[&str] = &[
 pub async fn open_url(adb: &AdbExecutor, url: &str) -> Result<String> {
     let lower = url.to_lowercase();
     if !ALLOWED_URL_SCHEMES.iter().any(|s| lower.starts_with(s)) {
-        return Err(ZeptoError::Tool(format!(
+        return Err(ZeptoError::tool(
+            ToolErrorKind::InvalidArgument,
+            format!(
             "Invalid URL scheme in '{}'. Allowed: http, https, tel, mailto, market, geo, content",
             url
-        )));
+        ),
+        ));
     }
     let escaped = escape_adb_text(url);
     adb.shell(&format!(
@@ -462,6 +483,10 @@ fn is_rm_recursive_force(tokens: &[&str]) -> bool {
 }
 
 /// Run an arbitrary shell command on the device.
+///
+/// Whether the `shell` action may run at all is decided by the central risk
+/// policy (`android:shell`, `confirm` by default); this only rejects command
+/// content that is never allowed.
 pub async fn device_shell(adb: &AdbExecutor, cmd: &str) -> Result<String> {
     // Block shell metacharacters that enable command chaining.
     // Check this FIRST — before normalization — to prevent bypass via
@@ -5,10 +5,11 @@
 
 use std::time::Duration;
 
+use serde::Serialize;
 use tokio::process::Command;
 use tracing::{debug, warn};
 
-use crate::error::{Result, ZeptoError};
+use crate::error::{Result, ToolErrorKind, ZeptoError};
 
 /// Default ADB command timeout.
 const DEFAULT_TIMEOUT: Duration = Duration::from_secs(15);
@@ -16,6 +17,31 @@ const DEFAULT_TIMEOUT: Duration = Duration::from_secs(15);
 /// Max retry attempts for transient failures.
 const MAX_RETRIES: u32 = 3;
 
+/// A device entry from `adb devices -l`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AdbDevice {
+    /// Device serial (e.g. "emulator-5554", "192.168.1.5:5555").
+    pub serial: String,
+    /// Connection state ("device", "unauthorized", "offline", ...).
+    pub state: String,
+    /// Model name, if reported.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Product name, if reported.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub product: Option<String>,
+    /// ADB transport id, if reported.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transport_id: Option<String>,
+}
+
+impl AdbDevice {
+    /// Whether the device is online and authorized.
+    pub fn is_ready(&self) -> bool {
+        self.state == "device"
+    }
+}
+
 /// ADB command executor with device targeting and retry support.
 #[derive(Debug, Clone)]
 pub struct AdbExecutor {
@@ -46,6 +72,19 @@ impl AdbExecutor {
         }
     }
 
+    /// The targeted device serial (empty = adb's default device).
+    pub fn device_serial(&self) -> &str {
+        &self.device_serial
+    }
+
+    /// Copy of this executor targeting `serial`.
+    pub fn for_device(&self, serial: &str) -> Self {
+        Self {
+            device_serial: serial.to_string(),
+            ..self.clone()
+        }
+    }
+
     /// Run a raw ADB command with the given arguments.
     pub async fn run(&self, args: &[&str]) -> Result<String> {
         let mut cmd_args = Vec::new();
@@ -62,14 +101,19 @@ impl AdbExecutor {
             Command::new(&self.adb_path).args(&cmd_args).output(),
         )
         .await
-        .map_err(|_| ZeptoError::Tool("ADB command timed out".into()))?
-        .map_err(|e| ZeptoError::Tool(format!("Failed to run adb: {}", e)))?;
+        .map_err(|_| ZeptoError::tool(ToolErrorKind::Transient, "ADB command timed out"))?
+        .map_err(|e| {
+            ZeptoError::tool(ToolErrorKind::Internal, format!("Failed to run adb: {}", e))
+        })?;
 
         if output.status.success() {
             Ok(String::from_utf8_lossy(&output.stdout).to_string())
         } else {
             let stderr = String::from_utf8_lossy(&output.stderr);
-            Err(ZeptoError::Tool(format!("ADB error: {}", stderr.trim())))
+            Err(ZeptoError::tool(
+                classify_adb_error(&stderr),
+                format!("ADB error: {}", stderr.trim()),
+            ))
         }
     }
 
@@ -104,26 +148,12 @@ impl AdbExecutor {
         Err(last_err.unwrap_or_else(|| ZeptoError::Tool("ADB retry exhausted".into())))
     }
 
-    /// List connected devices (serial numbers).
-    pub async fn list_devices(&self) -> Result<Vec<String>> {
-        let output = self.run(&["devices"]).await?;
-        let devices: Vec<String> = output
-            .lines()
-            .skip(1) // skip "List of devices attached"
-            .filter_map(|line| {
-                let line = line.trim();
-                if line.is_empty() {
-                    return None;
-                }
-                let parts: Vec<&str> = line.split_whitespace().collect();
-                if parts.len() >= 2 && parts[1] == "device" {
-                    Some(parts[0].to_string())
-                } else {
-                    None
-                }
-            })
-            .collect();
-        Ok(devices)
+    /// List attached devices with their state and model (`adb devices -l`).
+    ///
+    /// Always queries the server as a whole, regardless of the targeted serial.
+    pub async fn list_devices(&self) -> Result<Vec<AdbDevice>> {
+        let output = self.for_device("").run(&["devices", "-l"]).await?;
+        Ok(parse_devices_long(&output))
     }
 
     /// Get screen dimensions `(width, height)`.
@@ -153,6 +183,115 @@ impl AdbExecutor {
     }
 }
 
+/// Parse `adb devices -l` output.
+///
+/// Skips the header and daemon status lines (`* daemon started ...`).
+pub fn parse_devices_long(output: &str) -> Vec<AdbDevice> {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| {
+            !line.is_empty() && !line.starts_with("List of devices") && !line.starts_with('*')
+        })
+        .filter_map(|line| {
+            let mut tokens = line.split_whitespace();
+            let serial = tokens.next()?.to_string();
+            let state = tokens.next()?.to_string();
+            let mut device = AdbDevice {
+                serial,
+                state,
+                model: None,
+                product: None,
+                transport_id: None,
+            };
+            for token in tokens {
+                if let Some((key, value)) = token.split_once(':') {
+                    match key {
+                        "model" => device.model = Some(value.to_string()),
+                        "product" => device.product = Some(value.to_string()),
+                        "transport_id" => device.transport_id = Some(value.to_string()),
+                        _ => {}
+                    }
+                }
+            }
+            Some(device)
+        })
+        .collect()
+}
+
+/// Pick the device serial an action should target.
+///
+/// With `requested`, it must be connected and ready. Without it, exactly one
+/// ready device must be connected; several is an error rather than letting
+/// adb pick one arbitrarily.
+pub fn select_device(requested: Option<&str>, devices: &[AdbDevice]) -> Result<String> {
+    let ready: Vec<&AdbDevice> = devices.iter().filter(|d| d.is_ready()).collect();
+    let describe = |list: &[&AdbDevice]| {
+        list.iter()
+            .map(|d| match &d.model {
+                Some(model) => format!("{} ({})", d.serial, model),
+                None => d.serial.clone(),
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    if let Some(serial) = requested {
+        return match devices.iter().find(|d| d.serial == serial) {
+            Some(d) if d.is_ready() => Ok(d.serial.clone()),
+            Some(d) => Err(ZeptoError::tool(
+                if d.state == "unauthorized" {
+                    ToolErrorKind::Unauthorized
+                } else {
+                    ToolErrorKind::Transient
+                },
+                format!("Device '{}' is {}, not ready", d.serial, d.state),
+            )),
+            None => Err(ZeptoError::tool(
+                ToolErrorKind::NotFound,
+                format!(
+                    "Device '{}' is not connected. Connected: {}",
+                    serial,
+                    if ready.is_empty() {
+                        "none".to_string()
+                    } else {
+                        describe(&ready)
+                    }
+                ),
+            )),
+        };
+    }
+
+    match ready.as_slice() {
+        [] => Err(ZeptoError::tool(
+            ToolErrorKind::NotFound,
+            "No devices connected. Connect a device via USB or start an emulator.",
+        )),
+        [only] => Ok(only.serial.clone()),
+        _ => Err(ZeptoError::tool(
+            ToolErrorKind::InvalidArgument,
+            format!(
+                "Multiple devices connected: {}. Specify one with the 'device' parameter.",
+                describe(&ready)
+            ),
+        )),
+    }
+}
+
+/// Kind of an `adb` failure, from its stderr.
+fn classify_adb_error(stderr: &str) -> ToolErrorKind {
+    let lower = stderr.to_lowercase();
+    if lower.contains("device offline") || lower.contains("closed") {
+        ToolErrorKind::Transient
+    } else if lower.contains("not found") || lower.contains("no devices") {
+        ToolErrorKind::NotFound
+    } else if lower.contains("unauthorized") {
+        ToolErrorKind::Unauthorized
+    } else {
+        ToolErrorKind::Internal
+    }
+}
+
 /// Parse `wm size` output into (width, height).
 fn parse_screen_size(output: &str) -> Result<(i32, i32)> {
     // Handle "Physical size: 1080x2400" or "Override size: 1080x2400"
@@ -286,4 +425,114 @@ mod tests {
         let pkg = parse_foreground_app("").unwrap();
         assert_eq!(pkg, "unknown");
     }
+
+    const DEVICES_LONG: &str = "List of devices attached\n\
+        emulator-5554          device product:sdk_gphone64_x86_64 model:sdk_gphone64_x86_64 device:emu64xa transport_id:1\n\
+        R58M123ABC             device usb:1-1 product:beyond1 model:SM_G973F device:beyond1 transport_id:3\n\
+        0123456789ABCDEF       unauthorized usb:1-2 transport_id:4\n\
+        \n";
+
+    #[test]
+    fn test_parse_devices_long() {
+        let devices = parse_devices_long(DEVICES_LONG);
+        assert_eq!(devices.len(), 3);
+        assert_eq!(devices[0].serial, "emulator-5554");
+        assert_eq!(devices[0].state, "device");
+        assert_eq!(devices[0].model.as_deref(), Some("sdk_gphone64_x86_64"));
+        assert_eq!(devices[0].transport_id.as_deref(), Some("1"));
+        assert_eq!(devices[1].model.as_deref(), Some("SM_G973F"));
+        assert_eq!(devices[1].product.as_deref(), Some("beyond1"));
+        assert_eq!(devices[2].state, "unauthorized");
+        assert!(devices[2].model.is_none());
+        assert!(!devices[2].is_ready());
+    }
+
+    #[test]
+    fn test_parse_devices_long_skips_daemon_lines() {
+        let output = "* daemon not running; starting now at tcp:5037\n\
+                      * daemon started successfully\n\
+                      List of devices attached\n\
+                      192.168.1.5:5555\toffline\n";
+        let devices = parse_devices_long(output);
+        assert_eq!(devices.len(), 1);
+        assert_eq!(devices[0].serial, "192.168.1.5:5555");
+        assert_eq!(devices[0].state, "offline");
+    }
+
+    #[test]
+    fn test_select_device_single_default() {
+        let devices = parse_devices_long(
+            "List of devices attached\nemulator-5554 device\nABC unauthorized\n",
+        );
+        assert_eq!(select_device(None, &devices).unwrap(), "emulator-5554");
+    }
+
+    #[test]
+    fn test_select_device_ambiguous_errors() {
+        let devices = parse_devices_long(DEVICES_LONG);
+        let err = select_device(None, &devices).unwrap_err().to_string();
+        assert!(err.contains("Multiple devices"));
+        assert!(err.contains("emulator-5554"));
+        assert!(err.contains("R58M123ABC (SM_G973F)"));
+        assert!(!err.contains("0123456789ABCDEF"));
+    }
+
+    #[test]
+    fn test_select_device_explicit() {
+        let devices = parse_devices_long(DEVICES_LONG);
+        assert_eq!(
+            select_device(Some("R58M123ABC"), &devices).unwrap(),
+            "R58M123ABC"
+        );
+        assert!(select_device(Some("0123456789ABCDEF"), &devices)
+            .unwrap_err()
+            .to_string()
+            .contains("unauthorized"));
+        assert!(select_device(Some("missing"), &devices)
+            .unwrap_err()
+            .to_string()
+            .contains("not connected"));
+    }
+
+    #[test]
+    fn test_select_device_none_connected() {
+        assert!(select_device(None, &[])
+            .unwrap_err()
+            .to_string()
+            .contains("No devices connected"));
+    }
+
+    #[test]
+    fn test_device_errors_are_classified() {
+        let devices = parse_devices_long(DEVICES_LONG);
+        let kind = |requested| {
+            select_device(requested, &devices)
+                .unwrap_err()
+                .tool_error_kind()
+        };
+        assert_eq!(kind(None), Some(ToolErrorKind::InvalidArgument));
+        assert_eq!(
+            kind(Some("0123456789ABCDEF")),
+            Some(ToolErrorKind::Unauthorized)
+        );
+        assert_eq!(kind(Some("missing")), Some(ToolErrorKind::NotFound));
+        assert_eq!(
+            classify_adb_error("error: device offline"),
+            ToolErrorKind::Transient
+        );
+        assert_eq!(
+            classify_adb_error("Exception occurred while executing"),
+            ToolErrorKind::Internal
+        );
+    }
+
+    #[test]
+    fn test_for_device_keeps_settings() {
+        let exec = AdbExecutor::default().for_device("emulator-5554");
+        assert_eq!(exec.device_serial(), "emulator-5554");
+        assert_eq!(
+            exec.build_args(&["shell"]),
+            vec!["-s", "emulator-5554", "shell"]
+        );
+    }
 }
@@ -0,0 +1,392 @@
+//! Android macro store.
+//!
+//! Macros are named sequences of android tool actions (e.g. unlock → launch
+//! → tap) persisted to `~/.zeptoclaw/android/macros.json`. String arguments
+//! may contain `{{param}}` placeholders that are filled in at play time, so a
+//! recorded flow can be replayed with different text, packages or coordinates.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::config::Config;
+use crate::error::{Result, ZeptoError};
+
+/// Actions that may appear as macro steps (everything the tool dispatches).
+pub const MACRO_STEP_ACTIONS: &[&str] = &[
+    "screen",
+    "wait_for",
+    "tap",
+    "long_press",
+    "swipe",
+    "scroll",
+    "type",
+    "clear_field",
+    "back",
+    "home",
+    "recent",
+    "enter",
+    "key_event",
+    "set_clipboard",
+    "get_clipboard",
+    "paste",
+    "launch",
+    "open_url",
+    "open_notifications",
+    "open_quick_settings",
+    "screenshot",
+    "wake_screen",
+    "shell",
+];
+
+/// Maximum number of steps in one macro.
+const MAX_MACRO_STEPS: usize = 100;
+
+/// One recorded action and its arguments (without the `action` key).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MacroStep {
+    pub action: String,
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub args: Map<String, Value>,
+}
+
+impl MacroStep {
+    /// Build a step from a tool-call style object: `{"action": "tap", "x": 1, ...}`.
+    pub fn from_value(value: &Value) -> Result<Self> {
+        let mut args = value
+            .as_object()
+            .cloned()
+            .ok_or_else(|| ZeptoError::Tool("Each macro step must be an object".into()))?;
+        let action = args
+            .remove("action")
+            .and_then(|v| v.as_str().map(str::to_string))
+            .ok_or_else(|| ZeptoError::Tool("Macro step is missing 'action'".into()))?;
+        if !MACRO_STEP_ACTIONS.contains(&action.as_str()) {
+            return Err(ZeptoError::Tool(format!(
+                "Action '{}' cannot be used in a macro",
+                action
+            )));
+        }
+        Ok(Self { action, args })
+    }
+
+    /// The step as tool-call arguments, including the `action` key.
+    pub fn to_args(&self) -> Value {
+        let mut obj = self.args.clone();
+        obj.insert("action".into(), Value::String(self.action.clone()));
+        Value::Object(obj)
+    }
+}
+
+/// A named, recorded action sequence.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AndroidMacro {
+    pub name: String,
+    pub steps: Vec<MacroStep>,
+    pub created_at: u64,
+}
+
+/// Persistent store for Android macros, backed by a JSON file.
+#[derive(Debug)]
+pub struct MacroStore {
+    macros: BTreeMap<String, AndroidMacro>,
+    storage_path: PathBuf,
+}
+
+impl MacroStore {
+    /// Default storage path (`~/.zeptoclaw/android/macros.json`).
+    pub fn default_path() -> PathBuf {
+        Config::dir().join("android").join("macros.json")
+    }
+
+    /// Load a store from `path` (missing file = empty store).
+    pub fn with_path(path: PathBuf) -> Result<Self> {
+        let macros = Self::load(&path)?;
+        Ok(Self {
+            macros,
+            storage_path: path,
+        })
+    }
+
+    /// Validate and save a macro, replacing any existing one with the same name.
+    pub fn record(&mut self, name: &str, steps: &[Value]) -> Result<&AndroidMacro> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(ZeptoError::Tool("Macro name must not be empty".into()));
+        }
+        if steps.is_empty() {
+            return Err(ZeptoError::Tool("Macro must have at least one step".into()));
+        }
+        if steps.len() > MAX_MACRO_STEPS {
+            return Err(ZeptoError::Tool(format!(
+                "Macro has {} steps (max {})",
+                steps.len(),
+                MAX_MACRO_STEPS
+            )));
+        }
+
+        let steps = steps
+            .iter()
+            .enumerate()
+            .map(|(i, s)| {
+                MacroStep::from_value(s)
+                    .map_err(|e| ZeptoError::Tool(format!("Step {}: {}", i + 1, e)))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let created_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        self.macros.insert(
+            name.to_string(),
+            AndroidMacro {
+                name: name.to_string(),
+                steps,
+                created_at,
+            },
+        );
+        self.save()?;
+        Ok(&self.macros[name])
+    }
+
+    /// Lookup a macro by name.
+    pub fn get(&self, name: &str) -> Option<&AndroidMacro> {
+        self.macros.get(name)
+    }
+
+    /// All macros, ordered by name.
+    pub fn list(&self) -> Vec<&AndroidMacro> {
+        self.macros.values().collect()
+    }
+
+    /// Delete a macro. Returns `false` if it did not exist.
+    pub fn delete(&mut self, name: &str) -> Result<bool> {
+        if self.macros.remove(name).is_none() {
+            return Ok(false);
+        }
+        self.save()?;
+        Ok(true)
+    }
+
+    /// Resolve a macro into concrete tool-call arguments, substituting
+    /// `{{param}}` placeholders from `params`.
+    ///
+    /// Fails without returning any steps if a placeholder has no value, so a
+    /// half-parameterised macro never starts running.
+    pub fn expand(&self, name: &str, params: &Map<String, Value>) -> Result<Vec<Value>> {
+        let mac = self
+            .get(name)
+            .ok_or_else(|| ZeptoError::Tool(format!("Macro '{}' not found", name)))?;
+        mac.steps
+            .iter()
+            .map(|step| substitute(&step.to_args(), params))
+            .collect()
+    }
+
+    fn save(&self) -> Result<()> {
+        if let Some(parent) = self.storage_path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                ZeptoError::Tool(format!(
+                    "Failed to create macros directory {}: {}",
+                    parent.display(),
+                    e
+                ))
+            })?;
+        }
+
+        let json = serde_json::to_string_pretty(&self.macros)
+            .map_err(|e| ZeptoError::Tool(format!("Failed to serialize macros: {}", e)))?;
+
+        std::fs::write(&self.storage_path, json).map_err(|e| {
+            ZeptoError::Tool(format!(
+                "Failed to write macros to {}: {}",
+                self.storage_path.display(),
+                e
+            ))
+        })
+    }
+
+    fn load(path: &PathBuf) -> Result<BTreeMap<String, AndroidMacro>> {
+        if !path.exists() {
+            return Ok(BTreeMap::new());
+        }
+
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            ZeptoError::Tool(format!(
+                "Failed to read macros from {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+
+        if content.trim().is_empty() {
+            return Ok(BTreeMap::new());
+        }
+
+        serde_json::from_str(&content)
+            .map_err(|e| ZeptoError::Tool(format!("Failed to parse macros JSON: {}", e)))
+    }
+}
+
+/// Replace `{{param}}` placeholders in every string of `value`.
+///
+/// A string that is exactly one placeholder takes the parameter's JSON value
+/// as-is (so `"{{x}}"` can become the integer `540`); placeholders embedded in
+/// longer strings are interpolated as text.
+fn substitute(value: &Value, params: &Map<String, Value>) -> Result<Value> {
+    match value {
+        Value::String(s) => substitute_str(s, params),
+        Value::Array(items) => items
+            .iter()
+            .map(|v| substitute(v, params))
+            .collect::<Result<Vec<_>>>()
+            .map(Value::Array),
+        Value::Object(obj) => obj
+            .iter()
+            .map(|(k, v)| substitute(v, params).map(|v| (k.clone(), v)))
+            .collect::<Result<Map<_, _>>>()
+            .map(Value::Object),
+        other => Ok(other.clone()),
+    }
+}
+
+fn substitute_str(s: &str, params: &Map<String, Value>) -> Result<Value> {
+    let lookup = |key: &str| {
+        params
+            .get(key.trim())
+            .ok_or_else(|| ZeptoError::Tool(format!("Missing macro parameter '{}'", key.trim())))
+    };
+
+    if let Some(key) = s
+        .strip_prefix("{{")
+        .and_then(|rest| rest.strip_suffix("}}"))
+        .filter(|k| !k.contains("{{") && !k.contains("}}"))
+    {
+        return lookup(key).cloned();
+    }
+
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        out.push_str(&rest[..start]);
+        let key = &rest[start + 2..start + 2 + len];
+        match lookup(key)? {
+            Value::String(v) => out.push_str(v),
+            v => out.push_str(&v.to_string()),
+        }
+        rest = &rest[start + 2 + len + 2..];
+    }
+    out.push_str(rest);
+    Ok(Value::String(out))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn store() -> (tempfile::TempDir, MacroStore) {
+        let dir = tempfile::tempdir().unwrap();
+        let store = MacroStore::with_path(dir.path().join("android").join("macros.json")).unwrap();
+        (dir, store)
+    }
+
+    fn params(value: Value) -> Map<String, Value> {
+        value.as_object().cloned().unwrap()
+    }
+
+    #[test]
+    fn test_record_and_play_round_trip() {
+        let (dir, mut store) = store();
+        let steps = vec![
+            json!({"action": "wake_screen"}),
+            json!({"action": "launch", "package": "com.example.app"}),
+            json!({"action": "tap", "x": 540, "y": 1200}),
+        ];
+        store.record("open_app", &steps).unwrap();
+
+        // Reload from disk to prove persistence.
+        let reloaded =
+            MacroStore::with_path(dir.path().join("android").join("macros.json")).unwrap();
+        assert_eq!(reloaded.list().len(), 1);
+        let expanded = reloaded.expand("open_app", &Map::new()).unwrap();
+        assert_eq!(expanded, steps);
+    }
+
+    #[test]
+    fn test_play_substitutes_parameters() {
+        let (_dir, mut store) = store();
+        store
+            .record(
+                "search",
+                &[
+                    json!({"action": "launch", "package": "{{app}}"}),
+                    json!({"action": "tap", "coords": ["{{x}}", "{{y}}"]}),
+                    json!({"action": "type", "text": "find {{query}} near me"}),
+                ],
+            )
+            .unwrap();
+
+        let expanded = store
+            .expand(
+                "search",
+                &params(json!({
+                    "app": "com.maps",
+                    "x": 100,
+                    "y": 200,
+                    "query": "coffee"
+                })),
+            )
+            .unwrap();
+        assert_eq!(
+            expanded[0],
+            json!({"action": "launch", "package": "com.maps"})
+        );
+        assert_eq!(expanded[1], json!({"action": "tap", "coords": [100, 200]}));
+        assert_eq!(
+            expanded[2],
+            json!({"action": "type", "text": "find coffee near me"})
+        );
+    }
+
+    #[test]
+    fn test_play_missing_parameter_fails() {
+        let (_dir, mut store) = store();
+        store
+            .record("greet", &[json!({"action": "type", "text": "hi {{name}}"})])
+            .unwrap();
+        let err = store.expand("greet", &Map::new()).unwrap_err();
+        assert!(err.to_string().contains("Missing macro parameter 'name'"));
+    }
+
+    #[test]
+    fn test_record_rejects_invalid_steps() {
+        let (_dir, mut store) = store();
+        assert!(store.record("", &[json!({"action": "home"})]).is_err());
+        assert!(store.record("empty", &[]).is_err());
+        let err = store
+            .record(
+                "bad",
+                &[json!({"action": "home"}), json!({"action": "play_macro"})],
+            )
+            .unwrap_err();
+        assert!(err.to_string().contains("Step 2"));
+        assert!(store.record("bad", &[json!("tap")]).is_err());
+        assert!(store.list().is_empty());
+    }
+
+    #[test]
+    fn test_delete_macro() {
+        let (_dir, mut store) = store();
+        store.record("m", &[json!({"action": "home"})]).unwrap();
+        assert!(store.delete("m").unwrap());
+        assert!(!store.delete("m").unwrap());
+        assert!(store.expand("m", &Map::new()).is_err());
+    }
+}
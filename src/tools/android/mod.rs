@@ -7,7 +7,8 @@
 //! # Actions
 //!
 //! - `screen` — Get parsed UI elements from the current screen
-//! - `list_devices` — List connected Android devices
+//! - `wait_for` — Poll the screen until an element (by text/resource ID) appears
+//! - `list_devices` — List connected Android devices (serial, state, model)
 //! - `tap` — Tap at coordinates
 //! - `long_press` — Long press at coordinates
 //! - `swipe` — Swipe between two points
@@ -23,27 +24,75 @@
 //! - `screenshot` — Take a screenshot (base64 PNG)
 //! - `wake_screen` — Wake up the device screen
 //! - `shell` — Run a shell command on the device
+//! - `record_macro` / `play_macro` / `list_macros` / `delete_macro` — Named,
+//!   replayable action sequences (see [`macros`])
+//!
+//! Every device action accepts an optional `device` serial. Without it, the
+//! tool targets the only connected device and errors if several are attached.
 
 pub mod actions;
 pub mod adb;
+pub mod macros;
 pub mod screen;
 pub mod stuck;
 pub mod types;
 
+use std::path::PathBuf;
 use std::sync::Arc;
 
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, OnceCell};
 
 use async_trait::async_trait;
 use serde_json::{json, Value};
 use tracing::debug;
 
-use crate::error::{Result, ZeptoError};
+use crate::error::{Result, ToolErrorKind, ZeptoError};
+use crate::tools::output::{offset_arg, offset_schema, Page, DEFAULT_PAGE_BYTES};
 use crate::tools::types::{Tool, ToolCategory, ToolContext, ToolOutput};
 
 use self::adb::AdbExecutor;
+use self::macros::MacroStore;
 use self::stuck::StuckDetector;
 
+/// Lazily resolves the device an action targets.
+///
+/// Resolution (which may shell out to `adb devices -l`) only happens when an
+/// action first needs the executor, so argument errors surface without adb.
+struct DeviceTarget<'a> {
+    base: &'a AdbExecutor,
+    requested: Option<String>,
+    resolved: OnceCell<AdbExecutor>,
+}
+
+impl<'a> DeviceTarget<'a> {
+    fn new(base: &'a AdbExecutor, args: &Value) -> Self {
+        Self {
+            base,
+            requested: args
+                .get("device")
+                .and_then(|v| v.as_str())
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::to_string),
+            resolved: OnceCell::new(),
+        }
+    }
+
+    async fn adb(&self) -> Result<&AdbExecutor> {
+        self.resolved
+            .get_or_try_init(|| async {
+                // A serial fixed at construction is trusted as-is unless overridden.
+                if self.requested.is_none() && !self.base.device_serial().is_empty() {
+                    return Ok(self.base.clone());
+                }
+                let devices = self.base.list_devices().await?;
+                let serial = adb::select_device(self.requested.as_deref(), &devices)?;
+                Ok(self.base.for_device(&serial))
+            })
+            .await
+    }
+}
+
 /// Android device control tool.
 ///
 /// Wraps ADB commands behind a single tool interface with action-based
@@ -51,6 +100,7 @@ use self::stuck::StuckDetector;
 pub struct AndroidTool {
     adb: AdbExecutor,
     stuck: Arc<Mutex<StuckDetector>>,
+    macros_path: PathBuf,
 }
 
 impl Default for AndroidTool {
@@ -65,6 +115,7 @@ impl AndroidTool {
         Self {
             adb: AdbExecutor::default(),
             stuck: Arc::new(Mutex::new(StuckDetector::default())),
+            macros_path: MacroStore::default_path(),
         }
     }
 
@@ -73,32 +124,120 @@ impl AndroidTool {
         Self {
             adb: AdbExecutor::with_device(serial),
             stuck: Arc::new(Mutex::new(StuckDetector::default())),
+            macros_path: MacroStore::default_path(),
+        }
+    }
+
+    /// Use a custom macro store path. Useful for testing.
+    pub fn with_macros_path(mut self, path: PathBuf) -> Self {
+        self.macros_path = path;
+        self
+    }
+
+    /// Handle the macro management actions.
+    ///
+    /// Kept outside `dispatch_action` so playback can dispatch steps without
+    /// recursing into macro actions.
+    async fn handle_macro(&self, action: &str, args: &Value) -> Result<String> {
+        let mut store = MacroStore::with_path(self.macros_path.clone())?;
+        let name = || {
+            args.get("name").and_then(|v| v.as_str()).ok_or_else(|| {
+                ZeptoError::tool(ToolErrorKind::InvalidArgument, "Missing 'name' parameter")
+            })
+        };
+
+        match action {
+            "record_macro" => {
+                let steps = args
+                    .get("steps")
+                    .and_then(|v| v.as_array())
+                    .ok_or_else(|| {
+                        ZeptoError::tool(ToolErrorKind::InvalidArgument, "Missing 'steps' array")
+                    })?;
+                let mac = store.record(name()?, steps)?;
+                Ok(format!(
+                    "Recorded macro '{}' ({} steps)",
+                    mac.name,
+                    mac.steps.len()
+                ))
+            }
+            "play_macro" => {
+                let name = name()?;
+                let params = args
+                    .get("params")
+                    .and_then(|v| v.as_object())
+                    .cloned()
+                    .unwrap_or_default();
+                let steps = store.expand(name, &params)?;
+                let target = DeviceTarget::new(&self.adb, args);
+
+                let mut log = Vec::with_capacity(steps.len());
+                for (i, step) in steps.iter().enumerate() {
+                    let step_action = step["action"].as_str().unwrap_or_default();
+                    let output = self
+                        .dispatch_action(step_action, step, &target)
+                        .await
+                        .map_err(|e| {
+                            ZeptoError::Tool(format!(
+                                "Macro '{}' failed at step {} ({}): {}. Completed: [{}]",
+                                name,
+                                i + 1,
+                                step_action,
+                                e,
+                                log.join("; ")
+                            ))
+                        })?;
+                    log.push(format!("{}. {}: {}", i + 1, step_action, output));
+                }
+                Ok(format!("Played macro '{}':\n{}", name, log.join("\n")))
+            }
+            "list_macros" => {
+                let list: Vec<Value> = store
+                    .list()
+                    .into_iter()
+                    .map(|m| {
+                        json!({
+                            "name": m.name,
+                            "steps": m.steps.iter().map(|s| s.action.as_str()).collect::<Vec<_>>(),
+                        })
+                    })
+                    .collect();
+                if list.is_empty() {
+                    Ok("No macros recorded.".into())
+                } else {
+                    serde_json::to_string(&list)
+                        .map_err(|e| ZeptoError::Tool(format!("Serialization failed: {}", e)))
+                }
+            }
+            "delete_macro" => {
+                let name = name()?;
+                if store.delete(name)? {
+                    Ok(format!("Deleted macro '{}'", name))
+                } else {
+                    Err(ZeptoError::tool(
+                        ToolErrorKind::NotFound,
+                        format!("Macro '{}' not found", name),
+                    ))
+                }
+            }
+            _ => unreachable!("handle_macro called with non-macro action"),
         }
     }
 
     /// Handle the `screen` action: dump UI, parse, score, return compact JSON.
-    async fn handle_screen(&self) -> Result<String> {
+    async fn handle_screen(&self, adb: &AdbExecutor) -> Result<String> {
         // Dump UI hierarchy
-        let dump = self
-            .adb
+        let dump = adb
             .shell_retry("uiautomator dump /dev/tty")
             .await
             .map_err(|e| ZeptoError::Tool(format!("UI dump failed: {}", e)))?;
 
         // Strip the "UI hierarchy dumped to:" line if present, but keep the full XML payload
-        let xml_start = dump.find("<?xml").or_else(|| dump.find("<hierarchy"));
-        let xml = match xml_start {
-            Some(idx) => &dump[idx..],
-            None => &dump,
-        };
-
-        // Parse XML
-        let elements = screen::parse_ui_dump(xml)?;
+        let elements = screen::parse_ui_dump(screen::extract_xml(&dump))?;
 
         // Get screen size and foreground app
-        let (screen_w, screen_h) = self.adb.get_screen_size().await.unwrap_or((1080, 2400));
-        let package = self
-            .adb
+        let (screen_w, screen_h) = adb.get_screen_size().await.unwrap_or((1080, 2400));
+        let package = adb
             .get_foreground_app()
             .await
             .unwrap_or_else(|_| "unknown".into());
@@ -136,8 +275,51 @@ impl AndroidTool {
         Ok(output)
     }
 
+    /// Handle the `wait_for` action: poll UI dumps until a matching element
+    /// appears, returning its coordinates.
+    async fn handle_wait_for(&self, adb: &AdbExecutor, args: &Value) -> Result<String> {
+        let query = screen::ElementQuery {
+            text: args.get("text").and_then(|v| v.as_str()).map(String::from),
+            resource_id: args
+                .get("resource_id")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+        };
+        let millis = |key: &str, default: std::time::Duration| {
+            args.get(key)
+                .and_then(|v| v.as_u64())
+                .map(std::time::Duration::from_millis)
+                .unwrap_or(default)
+        };
+        let interval = millis("interval_ms", screen::WAIT_DEFAULT_INTERVAL);
+        let timeout = millis("timeout_ms", screen::WAIT_DEFAULT_TIMEOUT);
+
+        let started = std::time::Instant::now();
+        let elem = screen::wait_for_element(
+            || adb.shell("uiautomator dump /dev/tty"),
+            &query,
+            interval,
+            timeout,
+        )
+        .await?;
+
+        let output = json!({
+            "found": true,
+            "text": elem.text,
+            "id": elem.id,
+            "center": elem.center,
+            "elapsed_ms": started.elapsed().as_millis() as u64,
+        });
+        Ok(output.to_string())
+    }
+
     /// Dispatch an action, recording it for stuck detection.
-    async fn dispatch_action(&self, action: &str, args: &Value) -> Result<String> {
+    async fn dispatch_action(
+        &self,
+        action: &str,
+        args: &Value,
+        target: &DeviceTarget<'_>,
+    ) -> Result<String> {
         // Record action for stuck detection
         {
             let mut detector = self.stuck.lock().await;
@@ -149,7 +331,15 @@ impl AndroidTool {
         }
 
         match action {
-            "screen" => self.handle_screen().await,
+            "screen" => self.handle_screen(target.adb().await?).await,
+            "wait_for" => {
+                if args.get("text").is_none() && args.get("resource_id").is_none() {
+                    return Err(ZeptoError::tool(ToolErrorKind::InvalidArgument,
+                        "Missing 'text' or 'resource_id' parameter",
+                    ));
+                }
+                self.handle_wait_for(target.adb().await?, args).await
+            }
             "list_devices" => {
                 let devices = self.adb.list_devices().await?;
                 if devices.is_empty() {
@@ -158,13 +348,14 @@ impl AndroidTool {
                             .into(),
                     )
                 } else {
-                    Ok(format!("Connected devices: {}", devices.join(", ")))
+                    serde_json::to_string(&devices)
+                        .map_err(|e| ZeptoError::Tool(format!("Serialization failed: {}", e)))
                 }
             }
             "tap" => {
                 let (x, y) =
                     actions::parse_coordinates(args.get("x"), args.get("y"), args.get("coords"))?;
-                actions::tap(&self.adb, x, y).await
+                actions::tap(target.adb().await?, x, y).await
             }
             "long_press" => {
                 let (x, y) =
@@ -173,97 +364,105 @@ impl AndroidTool {
                     .get("duration_ms")
                     .map(actions::value_to_i32)
                     .transpose()?;
-                actions::long_press(&self.adb, x, y, duration).await
+                actions::long_press(target.adb().await?, x, y, duration).await
             }
             "swipe" => {
                 let x1 =
                     actions::value_to_i32(args.get("x1").ok_or_else(|| {
-                        ZeptoError::Tool("Missing required parameter 'x1'".into())
+                        ZeptoError::tool(ToolErrorKind::InvalidArgument, "Missing required parameter 'x1'")
                     })?)?;
                 let y1 =
                     actions::value_to_i32(args.get("y1").ok_or_else(|| {
-                        ZeptoError::Tool("Missing required parameter 'y1'".into())
+                        ZeptoError::tool(ToolErrorKind::InvalidArgument, "Missing required parameter 'y1'")
                     })?)?;
                 let x2 =
                     actions::value_to_i32(args.get("x2").ok_or_else(|| {
-                        ZeptoError::Tool("Missing required parameter 'x2'".into())
+                        ZeptoError::tool(ToolErrorKind::InvalidArgument, "Missing required parameter 'x2'")
                     })?)?;
                 let y2 =
                     actions::value_to_i32(args.get("y2").ok_or_else(|| {
-                        ZeptoError::Tool("Missing required parameter 'y2'".into())
+                        ZeptoError::tool(ToolErrorKind::InvalidArgument, "Missing required parameter 'y2'")
                     })?)?;
                 let dur = args
                     .get("duration_ms")
                     .map(actions::value_to_i32)
                     .transpose()?;
-                actions::swipe(&self.adb, x1, y1, x2, y2, dur).await
+                actions::swipe(target.adb().await?, x1, y1, x2, y2, dur).await
             }
             "scroll" => {
                 let direction = args
                     .get("direction")
                     .and_then(|v| v.as_str())
                     .unwrap_or("down");
-                let (sw, sh) = self.adb.get_screen_size().await.unwrap_or((1080, 2400));
-                actions::scroll(&self.adb, direction, sw, sh).await
+                let (sw, sh) = target
+                    .adb()
+                    .await?
+                    .get_screen_size()
+                    .await
+                    .unwrap_or((1080, 2400));
+                actions::scroll(target.adb().await?, direction, sw, sh).await
             }
             "type" => {
                 let text = args
                     .get("text")
                     .and_then(|v| v.as_str())
-                    .ok_or_else(|| ZeptoError::Tool("Missing 'text' parameter".into()))?;
-                actions::type_text(&self.adb, text).await
+                    .ok_or_else(|| ZeptoError::tool(ToolErrorKind::InvalidArgument, "Missing 'text' parameter"))?;
+                actions::type_text(target.adb().await?, text).await
             }
-            "clear_field" => actions::clear_field(&self.adb).await,
-            "back" => actions::back(&self.adb).await,
-            "home" => actions::home(&self.adb).await,
-            "recent" => actions::recent(&self.adb).await,
-            "enter" => actions::enter(&self.adb).await,
+            "clear_field" => actions::clear_field(target.adb().await?).await,
+            "back" => actions::back(target.adb().await?).await,
+            "home" => actions::home(target.adb().await?).await,
+            "recent" => actions::recent(target.adb().await?).await,
+            "enter" => actions::enter(target.adb().await?).await,
             "key_event" => {
                 let key = args
                     .get("key")
                     .and_then(|v| v.as_str())
-                    .ok_or_else(|| ZeptoError::Tool("Missing 'key' parameter".into()))?;
-                actions::key_event(&self.adb, key).await
+                    .ok_or_else(|| ZeptoError::tool(ToolErrorKind::InvalidArgument, "Missing 'key' parameter"))?;
+                actions::key_event(target.adb().await?, key).await
             }
             "set_clipboard" => {
                 let text = args
                     .get("text")
                     .and_then(|v| v.as_str())
-                    .ok_or_else(|| ZeptoError::Tool("Missing 'text' parameter".into()))?;
-                actions::set_clipboard(&self.adb, text).await
+                    .ok_or_else(|| ZeptoError::tool(ToolErrorKind::InvalidArgument, "Missing 'text' parameter"))?;
+                actions::set_clipboard(target.adb().await?, text).await
             }
-            "get_clipboard" => actions::get_clipboard(&self.adb).await,
-            "paste" => actions::paste(&self.adb).await,
+            "get_clipboard" => actions::get_clipboard(target.adb().await?).await,
+            "paste" => actions::paste(target.adb().await?).await,
             "launch" => {
                 let package = args
                     .get("package")
                     .and_then(|v| v.as_str())
-                    .ok_or_else(|| ZeptoError::Tool("Missing 'package' parameter".into()))?;
-                actions::launch_app(&self.adb, package).await
+                    .ok_or_else(|| ZeptoError::tool(ToolErrorKind::InvalidArgument, "Missing 'package' parameter"))?;
+                actions::launch_app(target.adb().await?, package).await
             }
             "open_url" => {
                 let url = args
                     .get("url")
                     .and_then(|v| v.as_str())
-                    .ok_or_else(|| ZeptoError::Tool("Missing 'url' parameter".into()))?;
-                actions::open_url(&self.adb, url).await
+                    .ok_or_else(|| ZeptoError::tool(ToolErrorKind::InvalidArgument, "Missing 'url' parameter"))?;
+                actions::open_url(target.adb().await?, url).await
             }
-            "open_notifications" => actions::open_notifications(&self.adb).await,
-            "open_quick_settings" => actions::open_quick_settings(&self.adb).await,
-            "screenshot" => actions::screenshot_base64(&self.adb).await,
-            "wake_screen" => actions::wake_screen(&self.adb).await,
+            "open_notifications" => actions::open_notifications(target.adb().await?).await,
+            "open_quick_settings" => actions::open_quick_settings(target.adb().await?).await,
+            "screenshot" => actions::screenshot_base64(target.adb().await?).await,
+            "wake_screen" => actions::wake_screen(target.adb().await?).await,
             "shell" => {
                 let cmd = args
                     .get("command")
                     .and_then(|v| v.as_str())
-                    .ok_or_else(|| ZeptoError::Tool("Missing 'command' parameter".into()))?;
-                actions::device_shell(&self.adb, cmd).await
+                    .ok_or_else(|| ZeptoError::tool(ToolErrorKind::InvalidArgument, "Missing 'command' parameter"))?;
+                // Long output is paged; the note says which `offset` to pass next.
+                let output = actions::device_shell(target.adb().await?, cmd).await?;
+                Ok(Page::of(&output, offset_arg(args), DEFAULT_PAGE_BYTES).render("android"))
             }
-            _ => Err(ZeptoError::Tool(format!(
-                "Unknown android action '{}'. Available: screen, list_devices, tap, long_press, \
+            _ => Err(ZeptoError::tool(ToolErrorKind::InvalidArgument, format!(
+                "Unknown android action '{}'. Available: screen, wait_for, list_devices, tap, long_press, \
                  swipe, scroll, type, clear_field, back, home, recent, enter, key_event, \
                  set_clipboard, get_clipboard, paste, launch, open_url, open_notifications, \
-                 open_quick_settings, screenshot, wake_screen, shell",
+                 open_quick_settings, screenshot, wake_screen, shell, record_macro, \
+                 play_macro, list_macros, delete_macro",
                 action
             ))),
         }
@@ -283,7 +482,9 @@ impl Tool for AndroidTool {
          'screen' again after each interaction to verify the result. Never guess \
          coordinates — always read them from the screen response. For text input, \
          tap a field first, then use 'type'. Use 'scroll' with direction to reveal \
-         off-screen content."
+         off-screen content. Repeated flows can be saved with 'record_macro' (name + \
+         steps, string args may use {{param}} placeholders) and replayed with \
+         'play_macro' (name + params)."
     }
 
     fn compact_description(&self) -> &str {
@@ -294,6 +495,10 @@ impl Tool for AndroidTool {
         ToolCategory::Hardware
     }
 
+    fn resource_key(&self) -> Option<&str> {
+        Some("android_device")
+    }
+
     fn parameters(&self) -> Value {
         json!({
             "type": "object",
@@ -301,11 +506,12 @@ impl Tool for AndroidTool {
                 "action": {
                     "type": "string",
                     "enum": [
-                        "screen", "list_devices", "tap", "long_press", "swipe", "scroll",
+                        "screen", "wait_for", "list_devices", "tap", "long_press", "swipe", "scroll",
                         "type", "clear_field", "back", "home", "recent", "enter",
                         "key_event", "set_clipboard", "get_clipboard", "paste",
                         "launch", "open_url", "open_notifications", "open_quick_settings",
-                        "screenshot", "wake_screen", "shell"
+                        "screenshot", "wake_screen", "shell",
+                        "record_macro", "play_macro", "list_macros", "delete_macro"
                     ],
                     "description": "Action to perform on the Android device"
                 },
@@ -331,7 +537,19 @@ impl Tool for AndroidTool {
                 },
                 "text": {
                     "type": "string",
-                    "description": "Text to type or set on clipboard"
+                    "description": "Text to type or set on clipboard, or text to match for wait_for"
+                },
+                "resource_id": {
+                    "type": "string",
+                    "description": "Resource ID to match for wait_for (e.g. 'btn_ok' or 'com.app:id/btn_ok')"
+                },
+                "timeout_ms": {
+                    "type": "integer",
+                    "description": "Total wait_for timeout in ms (default 10000, max 60000)"
+                },
+                "interval_ms": {
+                    "type": "integer",
+                    "description": "wait_for poll interval in ms (default 500, 200-5000)"
                 },
                 "key": {
                     "type": "string",
@@ -349,9 +567,27 @@ impl Tool for AndroidTool {
                     "type": "string",
                     "description": "Shell command for device shell action"
                 },
+                "offset": offset_schema(),
                 "duration_ms": {
                     "type": "integer",
                     "description": "Duration in ms for long_press (default 1000) or swipe (default 300)"
+                },
+                "name": {
+                    "type": "string",
+                    "description": "Macro name for record_macro/play_macro/delete_macro"
+                },
+                "steps": {
+                    "type": "array",
+                    "items": { "type": "object" },
+                    "description": "Macro steps for record_macro, each like {\"action\": \"tap\", \"x\": 540, \"y\": 1200}"
+                },
+                "params": {
+                    "type": "object",
+                    "description": "Values for {{param}} placeholders when playing a macro"
+                },
+                "device": {
+                    "type": "string",
+                    "description": "Device serial from list_devices (required when several devices are connected)"
                 }
             },
             "required": ["action"]
@@ -359,15 +595,21 @@ impl Tool for AndroidTool {
     }
 
     async fn execute(&self, args: Value, _ctx: &ToolContext) -> Result<ToolOutput> {
-        let action = args
-            .get("action")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| ZeptoError::Tool("Missing 'action' parameter".into()))?;
+        let action = args.get("action").and_then(|v| v.as_str()).ok_or_else(|| {
+            ZeptoError::tool(ToolErrorKind::InvalidArgument, "Missing 'action' parameter")
+        })?;
 
         debug!(action = action, "Android tool executing");
-        self.dispatch_action(action, &args)
-            .await
-            .map(ToolOutput::llm_only)
+        let result = match action {
+            "record_macro" | "play_macro" | "list_macros" | "delete_macro" => {
+                self.handle_macro(action, &args).await
+            }
+            _ => {
+                let target = DeviceTarget::new(&self.adb, &args);
+                self.dispatch_action(action, &args, &target).await
+            }
+        };
+        result.map(ToolOutput::llm_only)
     }
 }
 
@@ -484,6 +726,17 @@ mod tests {
             .contains("Missing 'command'"));
     }
 
+    #[tokio::test]
+    async fn test_wait_for_missing_query() {
+        let tool = AndroidTool::new();
+        let ctx = ToolContext::new();
+        let result = tool.execute(json!({"action": "wait_for"}), &ctx).await;
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Missing 'text' or 'resource_id'"));
+    }
+
     #[tokio::test]
     async fn test_open_url_missing_url() {
         let tool = AndroidTool::new();
@@ -493,6 +746,43 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("Missing 'url'"));
     }
 
+    #[tokio::test]
+    async fn test_macro_actions_via_tool() {
+        let dir = tempfile::tempdir().unwrap();
+        let tool = AndroidTool::new().with_macros_path(dir.path().join("macros.json"));
+        let ctx = ToolContext::new();
+
+        let out = tool
+            .execute(
+                json!({"action": "record_macro", "name": "go_home", "steps": [{"action": "home"}]}),
+                &ctx,
+            )
+            .await
+            .unwrap();
+        assert!(out.for_llm.contains("1 steps"));
+
+        let out = tool
+            .execute(json!({"action": "list_macros"}), &ctx)
+            .await
+            .unwrap();
+        assert!(out.for_llm.contains("go_home"));
+
+        let err = tool
+            .execute(json!({"action": "play_macro", "name": "missing"}), &ctx)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not found"));
+
+        tool.execute(json!({"action": "delete_macro", "name": "go_home"}), &ctx)
+            .await
+            .unwrap();
+        let out = tool
+            .execute(json!({"action": "list_macros"}), &ctx)
+            .await
+            .unwrap();
+        assert!(out.for_llm.contains("No macros"));
+    }
+
     #[test]
     fn test_with_device() {
         let tool = AndroidTool::with_device("emulator-5554");
@@ -5,6 +5,8 @@
 //! for the LLM.
 
 use std::collections::HashMap;
+use std::future::Future;
+use std::time::Duration;
 
 use quick_xml::events::Event;
 use quick_xml::Reader;
@@ -19,6 +21,23 @@ const DEFAULT_TOP_N: usize = 30;
 /// Pixel tolerance for deduplication bucket.
 const DEDUP_TOLERANCE: i32 = 5;
 
+/// Default / bounds for the `wait_for` poll interval.
+pub const WAIT_DEFAULT_INTERVAL: Duration = Duration::from_millis(500);
+const WAIT_MIN_INTERVAL: Duration = Duration::from_millis(200);
+const WAIT_MAX_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Default / upper bound for the `wait_for` total timeout.
+pub const WAIT_DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+const WAIT_MAX_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Strip any preamble (e.g. "UI hierarchy dumped to:") before the XML payload.
+pub fn extract_xml(dump: &str) -> &str {
+    match dump.find("<?xml").or_else(|| dump.find("<hierarchy")) {
+        Some(idx) => &dump[idx..],
+        None => dump,
+    }
+}
+
 /// Parse uiautomator XML dump into UI elements.
 ///
 /// Performs a depth-first traversal of the XML, extracting bounds, text,
@@ -264,6 +283,97 @@ pub fn process_elements(mut elements: Vec<UIElement>, top_n: Option<usize>) -> V
     elements
 }
 
+/// Criteria for locating an element on screen.
+#[derive(Debug, Clone, Default)]
+pub struct ElementQuery {
+    /// Case-insensitive substring of the element's text, content-desc or hint.
+    pub text: Option<String>,
+    /// Resource ID, either short (`btn_ok`) or full (`com.app:id/btn_ok`).
+    pub resource_id: Option<String>,
+}
+
+impl ElementQuery {
+    /// Whether at least one criterion is set.
+    pub fn is_empty(&self) -> bool {
+        self.text.is_none() && self.resource_id.is_none()
+    }
+
+    /// Whether `elem` satisfies every set criterion.
+    pub fn matches(&self, elem: &UIElement) -> bool {
+        let text_ok = self.text.as_deref().is_none_or(|needle| {
+            let needle = needle.to_lowercase();
+            elem.text.to_lowercase().contains(&needle)
+                || elem
+                    .hint
+                    .as_deref()
+                    .is_some_and(|h| h.to_lowercase().contains(&needle))
+        });
+        let id_ok = self.resource_id.as_deref().is_none_or(|rid| {
+            let short = rid.rsplit('/').next().unwrap_or(rid);
+            elem.id.as_deref() == Some(short)
+        });
+        text_ok && id_ok
+    }
+}
+
+/// Poll UI dumps until an element matching `query` appears.
+///
+/// `dump` is called once per poll and returns raw `uiautomator dump` output.
+/// The interval is clamped to 200ms–5s and the timeout to at most 60s. Dump
+/// failures are treated as "not yet" (the UI may be mid-transition) and only
+/// reported if the timeout is reached.
+pub async fn wait_for_element<F, Fut>(
+    mut dump: F,
+    query: &ElementQuery,
+    interval: Duration,
+    timeout: Duration,
+) -> Result<UIElement>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<String>>,
+{
+    if query.is_empty() {
+        return Err(ZeptoError::Tool(
+            "wait_for requires 'text' or 'resource_id'".into(),
+        ));
+    }
+    let interval = interval.clamp(WAIT_MIN_INTERVAL, WAIT_MAX_INTERVAL);
+    let timeout = timeout.min(WAIT_MAX_TIMEOUT);
+    let deadline = tokio::time::Instant::now() + timeout;
+    let mut polls = 0u32;
+    let mut last_error = None;
+
+    loop {
+        polls += 1;
+        match dump()
+            .await
+            .and_then(|raw| parse_ui_dump(extract_xml(&raw)))
+        {
+            Ok(elements) => {
+                if let Some(found) = elements.into_iter().find(|e| query.matches(e)) {
+                    return Ok(found);
+                }
+            }
+            Err(e) => last_error = Some(e.to_string()),
+        }
+
+        let now = tokio::time::Instant::now();
+        if now >= deadline {
+            break;
+        }
+        tokio::time::sleep(interval.min(deadline - now)).await;
+    }
+
+    Err(ZeptoError::Tool(format!(
+        "Timed out after {}ms waiting for element ({} polls){}",
+        timeout.as_millis(),
+        polls,
+        last_error
+            .map(|e| format!("; last dump error: {}", e))
+            .unwrap_or_default()
+    )))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -458,4 +568,112 @@ mod tests {
         assert!(elements[0].editable);
         assert_eq!(elements[0].action, "type");
     }
+
+    // ---- wait_for tests ----
+
+    fn mock_dumps(
+        dumps: Vec<Result<String>>,
+    ) -> (
+        std::sync::Arc<std::sync::atomic::AtomicUsize>,
+        impl FnMut() -> std::future::Ready<Result<String>>,
+    ) {
+        let calls = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = calls.clone();
+        let mut dumps = dumps.into_iter();
+        let last = EMPTY_XML.to_string();
+        let next = move || {
+            counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            let item = dumps.next().unwrap_or(Ok(last.clone()));
+            std::future::ready(item)
+        };
+        (calls, next)
+    }
+
+    const EMPTY_XML: &str = r#"<?xml version="1.0"?><hierarchy rotation="0"></hierarchy>"#;
+
+    #[tokio::test]
+    async fn test_wait_for_found_before_timeout() {
+        let (calls, dump) = mock_dumps(vec![
+            Ok(EMPTY_XML.to_string()),
+            Err(ZeptoError::Tool("device busy".into())),
+            Ok(format!("UI hierarchy dumped to: /dev/tty\n{}", SAMPLE_XML)),
+        ]);
+        let query = ElementQuery {
+            text: Some("sign in".into()),
+            resource_id: Some("com.example:id/btn_signin".into()),
+        };
+        let elem = wait_for_element(
+            dump,
+            &query,
+            Duration::from_millis(200),
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap();
+        assert_eq!(elem.center, [540, 860]);
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_timeout_exhausted() {
+        let (calls, dump) = mock_dumps(vec![Ok(SAMPLE_XML.to_string())]);
+        let query = ElementQuery {
+            text: Some("Continue".into()),
+            resource_id: None,
+        };
+        let err = wait_for_element(
+            dump,
+            &query,
+            Duration::from_millis(200),
+            Duration::from_millis(600),
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("Timed out after 600ms"));
+        // Polls at roughly t=0, 200, 400 and a final one at the deadline.
+        let polls = calls.load(std::sync::atomic::Ordering::SeqCst);
+        assert!((2..=4).contains(&polls), "unexpected poll count {polls}");
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_clamps_interval() {
+        let (calls, dump) = mock_dumps(vec![]);
+        let query = ElementQuery {
+            text: None,
+            resource_id: Some("missing".into()),
+        };
+        let _ = wait_for_element(dump, &query, Duration::ZERO, Duration::from_millis(400)).await;
+        // A zero interval is raised to the 200ms minimum instead of busy-polling.
+        assert!(calls.load(std::sync::atomic::Ordering::SeqCst) <= 3);
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_requires_query() {
+        let (_, dump) = mock_dumps(vec![]);
+        let err = wait_for_element(
+            dump,
+            &ElementQuery::default(),
+            WAIT_DEFAULT_INTERVAL,
+            WAIT_DEFAULT_TIMEOUT,
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("requires"));
+    }
+
+    #[test]
+    fn test_element_query_matches_hint_and_short_id() {
+        let elements = parse_ui_dump(SAMPLE_XML).unwrap();
+        let by_hint = ElementQuery {
+            text: Some("email".into()),
+            resource_id: None,
+        };
+        assert!(elements.iter().any(|e| by_hint.matches(e)));
+        let by_id = ElementQuery {
+            text: None,
+            resource_id: Some("cb_remember".into()),
+        };
+        let found = elements.iter().find(|e| by_id.matches(e)).unwrap();
+        assert_eq!(found.text, "Remember me");
+    }
 }
@@ -21,7 +21,34 @@
 //!     "approval": {
 //!         "enabled": true,
 //!         "policy": "require_for_dangerous",
-//!         "dangerous_tools": ["shell", "write_file", "edit_file", "google"]
+//!         "dangerous_tools": ["shell", "write_file", "edit_file", "google", "cache:clear"]
+//!     }
+//! }
+//! ```
+//!
+//! Under `require_for_dangerous`, calls the central [risk policy](super::risk)
+//! classifies as `confirm` also need approval, and calls it classifies as
+//! `blocked` are refused whatever the policy. Levels are adjusted per
+//! deployment under `approval.risk`.
+//!
+//! # Per-identity overrides
+//!
+//! `identities` adjusts the policy for specific senders or channels. Keys are
+//! `channel:sender_id` (one user), `channel` (everyone on a channel) or `*`
+//! (fallback); the most specific key wins. Patterns use the same globs as
+//! `require_for` and hand `require_approval_for`, and match either the tool
+//! name or the qualified `tool:action` (e.g. `google:gmail_send`), so an
+//! `action` argument never matches across tools. Overrides only replace the
+//! global tool lists: calls the risk policy rates `confirm` and tools gated
+//! by the active hand still need approval.
+//!
+//! ```json
+//! {
+//!     "approval": {
+//!         "identities": {
+//!             "telegram:12345": { "auto_approve": ["google:gmail_send", "shell"] },
+//!             "webhook": { "require_approval": ["*"] }
+//!         }
 //!     }
 //! }
 //! ```
@@ -38,10 +65,15 @@
 //! assert!(gate.requires_approval("shell"));
 //! ```
 
+use std::collections::HashMap;
+
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use super::risk::{RiskLevel, RiskPolicy, RiskPolicyConfig};
+use super::ToolCategory;
+
 // ---------------------------------------------------------------------------
 // Approval policy (runtime enum, not serialized directly)
 // ---------------------------------------------------------------------------
@@ -97,8 +129,10 @@ pub enum ApprovalPolicyConfig {
 /// - `enabled`: `true`
 /// - `policy`: `RequireForDangerous`
 /// - `require_for`: empty
-/// - `dangerous_tools`: `["shell", "write_file", "edit_file", "google"]`
+/// - `dangerous_tools`: `["shell", "write_file", "edit_file", "google", "cache:clear"]`
 /// - `auto_approve_timeout_secs`: `0` (disabled)
+/// - `pending_expiry_secs`: `3600`
+/// - `approvers`: empty
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct ApprovalConfig {
@@ -115,12 +149,66 @@ pub struct ApprovalConfig {
     pub require_for: Vec<String>,
 
     /// Tool names considered dangerous. Used when `policy` is
-    /// `RequireForDangerous`.
+    /// `RequireForDangerous`. A `tool:action` entry (e.g. `cache:clear`)
+    /// covers only that action of the tool.
     pub dangerous_tools: Vec<String>,
 
     /// If greater than zero, auto-approve after this many seconds without
     /// a response. `0` means no auto-approve (wait indefinitely).
     pub auto_approve_timeout_secs: u64,
+
+    /// How long a chat approval prompt (and an approval not yet used) stays
+    /// valid, in seconds. `0` keeps it until answered. See
+    /// [`approval_store`](super::approval_store).
+    pub pending_expiry_secs: u64,
+
+    /// Senders, as `channel:sender_id`, who may answer any chat approval
+    /// prompt. Otherwise only the sender whose message triggered the call
+    /// can approve or deny it.
+    pub approvers: Vec<String>,
+
+    /// Overrides keyed by `channel:sender_id`, `channel` or `*`.
+    pub identities: HashMap<String, IdentityApprovalPolicy>,
+
+    /// Risk level overrides for tool categories, tools and tool actions.
+    pub risk: RiskPolicyConfig,
+
+    /// Patterns from the active hand's `require_approval_for`. Set at
+    /// runtime, not from `config.json`; identity overrides cannot waive them.
+    #[serde(skip)]
+    pub hand_require_for: Vec<String>,
+}
+
+/// Approval overrides for one identity (sender, channel or fallback).
+///
+/// `require_approval` takes precedence over `auto_approve`; tools matching
+/// neither fall back to the global policy.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct IdentityApprovalPolicy {
+    /// Tool or action patterns executed without confirmation.
+    pub auto_approve: Vec<String>,
+    /// Tool or action patterns that always need confirmation.
+    pub require_approval: Vec<String>,
+}
+
+impl IdentityApprovalPolicy {
+    /// Decide for the given tool/action names, or `None` to defer to the
+    /// global policy.
+    fn decide(&self, names: &[&str]) -> Option<bool> {
+        let matches = |patterns: &[String]| {
+            patterns
+                .iter()
+                .any(|p| names.iter().any(|name| matches_tool_pattern(p, name)))
+        };
+        if matches(&self.require_approval) {
+            Some(true)
+        } else if matches(&self.auto_approve) {
+            Some(false)
+        } else {
+            None
+        }
+    }
 }
 
 impl Default for ApprovalConfig {
@@ -131,6 +219,11 @@ impl Default for ApprovalConfig {
             require_for: Vec::new(),
             dangerous_tools: ApprovalGate::default_dangerous_tools(),
             auto_approve_timeout_secs: 0,
+            pending_expiry_secs: 3600,
+            approvers: Vec::new(),
+            identities: HashMap::new(),
+            risk: RiskPolicyConfig::default(),
+            hand_require_for: Vec::new(),
         }
     }
 }
@@ -227,6 +320,14 @@ pub struct ApprovalGate {
     policy: ApprovalPolicy,
     /// Auto-approve timeout in seconds (0 = disabled).
     auto_approve_timeout_secs: u64,
+    /// Per-identity overrides.
+    identities: HashMap<String, IdentityApprovalPolicy>,
+    /// Central risk classification of tool calls.
+    risk: RiskPolicy,
+    /// Whether `Confirm`-level calls need approval (`require_for_dangerous`).
+    confirm_risky: bool,
+    /// Active hand patterns checked before identity overrides.
+    hand_require_for: Vec<String>,
 }
 
 impl ApprovalGate {
@@ -236,6 +337,8 @@ impl ApprovalGate {
     /// `ApprovalPolicy`, incorporating the `require_for` and
     /// `dangerous_tools` lists as needed.
     pub fn new(config: ApprovalConfig) -> Self {
+        let confirm_risky = config.policy == ApprovalPolicyConfig::RequireForDangerous;
+        let risk = RiskPolicy::new(&config.risk);
         let policy = match config.policy {
             ApprovalPolicyConfig::AlwaysAllow => ApprovalPolicy::AlwaysAllow,
             ApprovalPolicyConfig::AlwaysRequire => ApprovalPolicy::AlwaysRequire,
@@ -263,6 +366,10 @@ impl ApprovalGate {
             enabled: config.enabled,
             policy,
             auto_approve_timeout_secs: config.auto_approve_timeout_secs,
+            identities: config.identities,
+            risk,
+            confirm_risky,
+            hand_require_for: config.hand_require_for,
         }
     }
 
@@ -289,6 +396,94 @@ impl ApprovalGate {
         }
     }
 
+    /// Look up the override policy for a sender on a channel.
+    ///
+    /// Tries `channel:sender_id`, then `channel`, then `*`.
+    pub fn identity_policy(
+        &self,
+        channel: &str,
+        sender_id: &str,
+    ) -> Option<&IdentityApprovalPolicy> {
+        self.identities
+            .get(&format!("{}:{}", channel, sender_id))
+            .or_else(|| self.identities.get(channel))
+            .or_else(|| self.identities.get("*"))
+    }
+
+    /// Check whether a tool call by the given identity requires approval.
+    ///
+    /// Tools gated by the active hand always need approval. Otherwise the
+    /// identity's override policy is consulted, matching patterns against the
+    /// tool name and the qualified `tool:action`; failing that the global
+    /// policy decides as in [`requires_approval`](Self::requires_approval),
+    /// checking `tool:action` as well so a single action can be gated.
+    pub fn requires_approval_for(
+        &self,
+        tool_name: &str,
+        args: &Value,
+        channel: &str,
+        sender_id: &str,
+    ) -> bool {
+        self.requires_approval_for_call(tool_name, None, args, channel, sender_id)
+    }
+
+    /// Like [`requires_approval_for`](Self::requires_approval_for), but also
+    /// requires approval when the risk policy classifies the call as
+    /// [`RiskLevel::Confirm`] and the tool's category is known. Identity
+    /// overrides cannot waive a `Confirm` rating.
+    pub fn requires_approval_for_call(
+        &self,
+        tool_name: &str,
+        category: Option<ToolCategory>,
+        args: &Value,
+        channel: &str,
+        sender_id: &str,
+    ) -> bool {
+        if !self.enabled {
+            return false;
+        }
+        let qualified = args
+            .get("action")
+            .and_then(Value::as_str)
+            .map(|action| format!("{}:{}", tool_name, action));
+        let mut names = vec![tool_name];
+        names.extend(qualified.as_deref());
+
+        if self.risk_requires_approval(tool_name, category, args)
+            || self
+                .hand_require_for
+                .iter()
+                .any(|pattern| names.iter().any(|name| matches_tool_pattern(pattern, name)))
+        {
+            return true;
+        }
+        self.identity_policy(channel, sender_id)
+            .and_then(|policy| policy.decide(&names))
+            .unwrap_or_else(|| names.iter().any(|name| self.requires_approval(name)))
+    }
+
+    /// Classify a tool call with the central risk policy.
+    pub fn risk_level(&self, tool_name: &str, category: ToolCategory, args: &Value) -> RiskLevel {
+        self.risk.classify(tool_name, category, args)
+    }
+
+    /// Whether the risk policy alone requires approval for this call.
+    ///
+    /// Only applies under the `require_for_dangerous` policy and when the
+    /// gate is enabled; identity overrides are not consulted.
+    pub fn risk_requires_approval(
+        &self,
+        tool_name: &str,
+        category: Option<ToolCategory>,
+        args: &Value,
+    ) -> bool {
+        self.enabled
+            && self.confirm_risky
+            && category.is_some_and(|category| {
+                self.risk_level(tool_name, category, args) == RiskLevel::Confirm
+            })
+    }
+
     /// Format a human-readable approval prompt for the given tool invocation.
     ///
     /// The output is intended for display in a CLI or chat message to ask
@@ -328,6 +523,7 @@ impl ApprovalGate {
             "write_file".to_string(),
             "edit_file".to_string(),
             "google".to_string(),
+            "cache:clear".to_string(),
         ]
     }
 
@@ -518,7 +714,7 @@ mod tests {
         assert!(config.require_for.is_empty());
         assert_eq!(
             config.dangerous_tools,
-            vec!["shell", "write_file", "edit_file", "google"]
+            vec!["shell", "write_file", "edit_file", "google", "cache:clear"]
         );
         assert_eq!(config.auto_approve_timeout_secs, 0);
     }
@@ -667,6 +863,7 @@ mod tests {
                 "edit_file".to_string(),
             ],
             auto_approve_timeout_secs: 30,
+            ..Default::default()
         };
 
         let json_str = serde_json::to_string(&config).expect("serialize");
@@ -708,11 +905,22 @@ mod tests {
     #[test]
     fn test_default_dangerous_tools_list() {
         let defaults = ApprovalGate::default_dangerous_tools();
-        assert_eq!(defaults.len(), 4);
+        assert_eq!(defaults.len(), 5);
         assert!(defaults.contains(&"shell".to_string()));
         assert!(defaults.contains(&"write_file".to_string()));
         assert!(defaults.contains(&"edit_file".to_string()));
         assert!(defaults.contains(&"google".to_string()));
+        assert!(defaults.contains(&"cache:clear".to_string()));
+    }
+
+    #[test]
+    fn test_tool_action_entry_gates_only_that_action() {
+        let gate = ApprovalGate::new(ApprovalConfig::default());
+        let requires = |args: Value| gate.requires_approval_for("cache", &args, "cli", "user");
+
+        assert!(requires(json!({"action": "clear"})));
+        assert!(!requires(json!({"action": "stats"})));
+        assert!(!requires(json!({})));
     }
 
     #[test]
@@ -768,4 +976,221 @@ mod tests {
         let gate = ApprovalGate::new(config);
         assert_eq!(*gate.policy(), ApprovalPolicy::AlwaysAllow);
     }
+
+    // ---- Per-identity policies -----------------------------------------
+
+    fn identity_gate() -> ApprovalGate {
+        let mut identities = HashMap::new();
+        identities.insert(
+            "telegram:admin".to_string(),
+            IdentityApprovalPolicy {
+                auto_approve: vec!["google:gmail_send".into(), "shell*".into()],
+                ..Default::default()
+            },
+        );
+        identities.insert(
+            "webhook".to_string(),
+            IdentityApprovalPolicy {
+                require_approval: vec!["*".into()],
+                ..Default::default()
+            },
+        );
+        ApprovalGate::new(ApprovalConfig {
+            policy: ApprovalPolicyConfig::RequireForTools,
+            require_for: vec!["google".into(), "shell*".into()],
+            identities,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_identity_policy_lookup_order() {
+        let gate = identity_gate();
+        assert!(gate.identity_policy("telegram", "admin").is_some());
+        assert!(gate.identity_policy("telegram", "stranger").is_none());
+        assert_eq!(
+            gate.identity_policy("webhook", "anyone")
+                .unwrap()
+                .require_approval,
+            vec!["*"]
+        );
+    }
+
+    #[test]
+    fn test_identity_auto_approve_vs_require() {
+        let gate = identity_gate();
+        let send = json!({"action": "gmail_send", "to": "a@b.c"});
+        let search = json!({"action": "gmail_search"});
+
+        // Admin auto-approves the send action and `require_for`-gated shell tools.
+        assert!(!gate.requires_approval_for("google", &send, "telegram", "admin"));
+        assert!(!gate.requires_approval_for("shell", &json!({}), "telegram", "admin"));
+        // Other google actions still follow the global policy.
+        assert!(gate.requires_approval_for("google", &search, "telegram", "admin"));
+
+        // Unlisted users follow the global policy.
+        assert!(gate.requires_approval_for("google", &send, "telegram", "stranger"));
+        assert!(!gate.requires_approval_for("web_search", &json!({}), "telegram", "stranger"));
+
+        // Anonymous channels confirm everything.
+        assert!(gate.requires_approval_for("web_search", &json!({}), "webhook", "anyone"));
+    }
+
+    #[test]
+    fn test_identity_action_patterns_are_tool_qualified() {
+        let mut identities = HashMap::new();
+        identities.insert(
+            "telegram:admin".to_string(),
+            IdentityApprovalPolicy {
+                auto_approve: vec!["google:gmail_send".into()],
+                ..Default::default()
+            },
+        );
+        let gate = ApprovalGate::new(ApprovalConfig {
+            identities,
+            ..Default::default()
+        });
+
+        let spoofed = json!({"action": "gmail_send", "command": "rm -rf /"});
+        assert!(gate.requires_approval_for("shell", &spoofed, "telegram", "admin"));
+        assert!(!gate.requires_approval_for("google", &spoofed, "telegram", "admin"));
+    }
+
+    #[test]
+    fn test_identity_cannot_waive_hand_patterns() {
+        let mut identities = HashMap::new();
+        identities.insert(
+            "telegram:admin".to_string(),
+            IdentityApprovalPolicy {
+                auto_approve: vec!["*".into()],
+                ..Default::default()
+            },
+        );
+        let gate = ApprovalGate::new(ApprovalConfig {
+            policy: ApprovalPolicyConfig::RequireForTools,
+            require_for: vec!["shell*".into(), "write_file".into()],
+            hand_require_for: vec!["shell*".into()],
+            identities,
+            ..Default::default()
+        });
+
+        assert!(gate.requires_approval_for("shell", &json!({}), "telegram", "admin"));
+        assert!(!gate.requires_approval_for("write_file", &json!({}), "telegram", "admin"));
+    }
+
+    #[test]
+    fn test_identity_require_wins_and_disabled_bypasses() {
+        let mut identities = HashMap::new();
+        identities.insert(
+            "*".to_string(),
+            IdentityApprovalPolicy {
+                auto_approve: vec!["*".into()],
+                require_approval: vec!["shell".into()],
+            },
+        );
+        let gate = ApprovalGate::new(ApprovalConfig {
+            identities: identities.clone(),
+            ..Default::default()
+        });
+        assert!(gate.requires_approval_for("shell", &json!({}), "cli", "me"));
+        assert!(!gate.requires_approval_for("write_file", &json!({}), "cli", "me"));
+
+        let disabled = ApprovalGate::new(ApprovalConfig {
+            enabled: false,
+            identities,
+            ..Default::default()
+        });
+        assert!(!disabled.requires_approval_for("shell", &json!({}), "cli", "me"));
+    }
+
+    #[test]
+    fn test_identities_deserialize() {
+        let config: ApprovalConfig = serde_json::from_value(json!({
+            "identities": {
+                "discord:42": { "auto_approve": ["gmail_send"] }
+            }
+        }))
+        .unwrap();
+        assert_eq!(
+            config.identities["discord:42"].auto_approve,
+            vec!["gmail_send"]
+        );
+        assert!(config.identities["discord:42"].require_approval.is_empty());
+    }
+
+    // ---- Risk policy ---------------------------------------------------
+
+    #[test]
+    fn test_risk_policy_gates_confirm_calls() {
+        let send = json!({"to": "+100", "body": "hi"});
+        let gate = ApprovalGate::new(ApprovalConfig::default());
+        assert!(gate.requires_approval_for_call(
+            "whatsapp_send",
+            Some(ToolCategory::Messaging),
+            &send,
+            "cli",
+            "me"
+        ));
+        assert!(!gate.requires_approval_for_call(
+            "memory_get",
+            Some(ToolCategory::Memory),
+            &json!({}),
+            "cli",
+            "me"
+        ));
+        // Without a category only the name-based policy applies.
+        assert!(!gate.requires_approval_for("whatsapp_send", &send, "cli", "me"));
+
+        // Other policies leave confirmation to their own lists.
+        let allow_all = ApprovalGate::new(ApprovalConfig {
+            policy: ApprovalPolicyConfig::AlwaysAllow,
+            ..Default::default()
+        });
+        assert!(!allow_all.requires_approval_for_call(
+            "whatsapp_send",
+            Some(ToolCategory::Messaging),
+            &send,
+            "cli",
+            "me"
+        ));
+
+        // Identity overrides cannot waive a `confirm` rating.
+        let mut identities = HashMap::new();
+        identities.insert(
+            "telegram:42".to_string(),
+            IdentityApprovalPolicy {
+                auto_approve: vec!["whatsapp_send".into()],
+                require_approval: vec![],
+            },
+        );
+        let trusted = ApprovalGate::new(ApprovalConfig {
+            identities,
+            ..Default::default()
+        });
+        assert!(trusted.requires_approval_for_call(
+            "whatsapp_send",
+            Some(ToolCategory::Messaging),
+            &send,
+            "telegram",
+            "42"
+        ));
+
+        // Configured levels apply.
+        let config: ApprovalConfig = serde_json::from_value(json!({
+            "risk": { "categories": { "messaging": "safe", "memory": "blocked" } }
+        }))
+        .unwrap();
+        let relaxed = ApprovalGate::new(config);
+        assert!(!relaxed.requires_approval_for_call(
+            "whatsapp_send",
+            Some(ToolCategory::Messaging),
+            &send,
+            "cli",
+            "me"
+        ));
+        assert_eq!(
+            relaxed.risk_level("memory_get", ToolCategory::Memory, &json!({})),
+            RiskLevel::Blocked
+        );
+    }
 }
@@ -0,0 +1,416 @@
+//! Persistent pending tool approvals.
+//!
+//! On chat channels there is no inline approval handler: a gated tool call
+//! is refused with an approval prompt, and the user answers in a later
+//! message (typed or via the approve / deny buttons). [`PendingApprovals`]
+//! remembers each refused call — tool, arguments, session and expiry — so
+//! that answer can be matched to it, and so a restart between the prompt
+//! and the answer does not lose it.
+//!
+//! 1. A gated call is recorded with [`PendingApprovals::request`].
+//! 2. A reply from the sender who triggered a request (or from one of the
+//!    configured `approval.approvers`) approves or denies it, together with
+//!    that sender's other open requests in the session
+//!    ([`PendingApprovals::resolve`]).
+//! 3. When the agent repeats an approved call with the same arguments,
+//!    [`PendingApprovals::take_grant`] consumes the approval and the tool runs.
+//!
+//! Storage is pluggable through [`ApprovalStore`]; the gateway uses
+//! [`FileApprovalStore`] (`~/.zeptoclaw/approvals.json`). Requests expire
+//! after `approval.pending_expiry_secs` and expired ones are pruned on load.
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::warn;
+
+use crate::bus::{ActionStyle, OutputAction, StructuredOutput};
+use crate::config::Config;
+use crate::error::Result;
+use crate::utils::fs::write_atomic;
+
+/// Action ID of the "approve" button attached to an approval prompt.
+pub const TOOL_APPROVE_ACTION: &str = "tool_approve";
+/// Action ID of the "deny" button attached to an approval prompt.
+pub const TOOL_DENY_ACTION: &str = "tool_deny";
+
+/// A tool call waiting for (or granted) user approval.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PendingApproval {
+    /// Unique request ID.
+    pub id: String,
+    /// Session the call was made in.
+    pub session_key: String,
+    /// Sender whose message led to the call; only they (or a configured
+    /// approver) may answer the request.
+    #[serde(default)]
+    pub sender_id: String,
+    /// Tool awaiting approval.
+    pub tool_name: String,
+    /// Arguments the tool would be called with.
+    pub arguments: Value,
+    /// When the request was created.
+    pub created_at: DateTime<Utc>,
+    /// When the request (or an unused approval) lapses; `None` never expires.
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Whether the user approved the call.
+    #[serde(default)]
+    pub approved: bool,
+}
+
+impl PendingApproval {
+    /// Whether the request lapsed at `now`.
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|deadline| now >= deadline)
+    }
+
+    fn is_open(&self, session_key: &str, now: DateTime<Utc>) -> bool {
+        self.session_key == session_key && !self.approved && !self.is_expired(now)
+    }
+
+    fn is_for(&self, session_key: &str, tool_name: &str, arguments: &Value) -> bool {
+        self.session_key == session_key
+            && self.tool_name == tool_name
+            && self.arguments == *arguments
+    }
+}
+
+/// Backing storage for pending approvals.
+pub trait ApprovalStore: Send + Sync {
+    /// Load all stored approvals.
+    fn load(&self) -> Result<Vec<PendingApproval>>;
+    /// Replace the stored approvals.
+    fn save(&self, approvals: &[PendingApproval]) -> Result<()>;
+}
+
+/// Approvals kept in a JSON file.
+pub struct FileApprovalStore {
+    path: PathBuf,
+}
+
+impl FileApprovalStore {
+    /// Store approvals at `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Default location: `~/.zeptoclaw/approvals.json`.
+    pub fn default_path() -> PathBuf {
+        Config::dir().join("approvals.json")
+    }
+
+    /// Path of the backing file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl ApprovalStore for FileApprovalStore {
+    fn load(&self) -> Result<Vec<PendingApproval>> {
+        match std::fs::read_to_string(&self.path) {
+            Ok(raw) => Ok(serde_json::from_str(&raw)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn save(&self, approvals: &[PendingApproval]) -> Result<()> {
+        write_atomic(&self.path, serde_json::to_string_pretty(approvals)?)?;
+        Ok(())
+    }
+}
+
+/// Approvals kept in memory only (tests, ephemeral agents).
+#[derive(Default)]
+pub struct MemoryApprovalStore {
+    approvals: Mutex<Vec<PendingApproval>>,
+}
+
+impl ApprovalStore for MemoryApprovalStore {
+    fn load(&self) -> Result<Vec<PendingApproval>> {
+        Ok(self.approvals.lock().unwrap().clone())
+    }
+
+    fn save(&self, approvals: &[PendingApproval]) -> Result<()> {
+        *self.approvals.lock().unwrap() = approvals.to_vec();
+        Ok(())
+    }
+}
+
+/// Pending approvals of all sessions, written through to an [`ApprovalStore`].
+pub struct PendingApprovals {
+    store: Box<dyn ApprovalStore>,
+    expiry_secs: u64,
+    /// `channel:sender_id` of senders who may answer any request.
+    approvers: Vec<String>,
+    approvals: Mutex<Vec<PendingApproval>>,
+}
+
+impl PendingApprovals {
+    /// Load the approvals in `store`, dropping expired ones.
+    ///
+    /// `expiry_secs` is how long a new request (or an unused approval) stays
+    /// valid; `0` keeps it until it is answered or used.
+    pub fn new(store: Box<dyn ApprovalStore>, expiry_secs: u64) -> Self {
+        let loaded = store.load().unwrap_or_else(|e| {
+            warn!(error = %e, "Failed to load pending approvals; starting empty");
+            Vec::new()
+        });
+        let now = Utc::now();
+        let count = loaded.len();
+        let approvals: Vec<PendingApproval> =
+            loaded.into_iter().filter(|a| !a.is_expired(now)).collect();
+        let pending = Self {
+            store,
+            expiry_secs,
+            approvers: Vec::new(),
+            approvals: Mutex::new(Vec::new()),
+        };
+        if approvals.len() != count {
+            pending.persist(&approvals);
+        }
+        *pending.approvals.lock().unwrap() = approvals;
+        pending
+    }
+
+    /// Pending approvals backed by [`FileApprovalStore::default_path`].
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(
+            Box::new(FileApprovalStore::new(FileApprovalStore::default_path())),
+            config.approval.pending_expiry_secs,
+        )
+        .with_approvers(config.approval.approvers.clone())
+    }
+
+    /// Let these senders (`channel:sender_id`) answer any request.
+    pub fn with_approvers(mut self, approvers: Vec<String>) -> Self {
+        self.approvers = approvers;
+        self
+    }
+
+    /// Record that `tool_name` was refused pending approval in `session_key`
+    /// after a message from `sender_id`.
+    ///
+    /// Asking again for the same call replaces the earlier request.
+    pub fn request(
+        &self,
+        session_key: &str,
+        sender_id: &str,
+        tool_name: &str,
+        arguments: &Value,
+    ) -> PendingApproval {
+        let now = Utc::now();
+        let request = PendingApproval {
+            id: uuid::Uuid::new_v4().to_string(),
+            session_key: session_key.to_string(),
+            sender_id: sender_id.to_string(),
+            tool_name: tool_name.to_string(),
+            arguments: arguments.clone(),
+            created_at: now,
+            expires_at: self.deadline(now),
+            approved: false,
+        };
+        let mut approvals = self.approvals.lock().unwrap();
+        approvals.retain(|a| !a.is_expired(now) && !a.is_for(session_key, tool_name, arguments));
+        approvals.push(request.clone());
+        self.persist(&approvals);
+        request
+    }
+
+    /// Open (unanswered, unexpired) requests of a session.
+    pub fn pending_for(&self, session_key: &str) -> Vec<PendingApproval> {
+        let now = Utc::now();
+        self.approvals
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|a| a.is_open(session_key, now))
+            .cloned()
+            .collect()
+    }
+
+    /// Answer the open requests of a session that `sender_id` on `channel`
+    /// may answer: approve them, or drop them.
+    ///
+    /// A sender may answer the requests their own messages led to; a
+    /// configured approver may answer all of them. An approval stays valid
+    /// for a fresh expiry period so the agent has time to repeat the call.
+    /// Returns the number of requests answered.
+    pub fn resolve(
+        &self,
+        session_key: &str,
+        channel: &str,
+        sender_id: &str,
+        approve: bool,
+    ) -> usize {
+        let now = Utc::now();
+        let deadline = self.deadline(now);
+        let approver = self.is_approver(channel, sender_id);
+        let answerable = |a: &PendingApproval| {
+            a.is_open(session_key, now)
+                && (approver || (!a.sender_id.is_empty() && a.sender_id == sender_id))
+        };
+        let mut approvals = self.approvals.lock().unwrap();
+        approvals.retain(|a| !a.is_expired(now));
+        let before = approvals.len();
+        let mut answered = 0;
+        if approve {
+            for a in approvals.iter_mut().filter(|a| answerable(a)) {
+                a.approved = true;
+                a.expires_at = deadline;
+                answered += 1;
+            }
+        } else {
+            approvals.retain(|a| !answerable(a));
+            answered = before - approvals.len();
+        }
+        self.persist(&approvals);
+        answered
+    }
+
+    /// Consume an approval for this exact call, if the user granted one.
+    pub fn take_grant(&self, session_key: &str, tool_name: &str, arguments: &Value) -> bool {
+        let now = Utc::now();
+        let mut approvals = self.approvals.lock().unwrap();
+        let Some(index) = approvals.iter().position(|a| {
+            a.approved && !a.is_expired(now) && a.is_for(session_key, tool_name, arguments)
+        }) else {
+            return false;
+        };
+        approvals.remove(index);
+        self.persist(&approvals);
+        true
+    }
+
+    fn is_approver(&self, channel: &str, sender_id: &str) -> bool {
+        let identity = format!("{}:{}", channel, sender_id);
+        self.approvers.iter().any(|a| *a == identity)
+    }
+
+    fn deadline(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        (self.expiry_secs > 0).then(|| now + Duration::seconds(self.expiry_secs as i64))
+    }
+
+    fn persist(&self, approvals: &[PendingApproval]) {
+        if let Err(e) = self.store.save(approvals) {
+            warn!(error = %e, "Failed to persist pending approvals");
+        }
+    }
+}
+
+/// Approve / deny buttons for channels that render message actions.
+/// Pressing one sends back the matching `yes` / `no` reply.
+pub fn approval_actions() -> StructuredOutput {
+    StructuredOutput::new()
+        .with_action(
+            OutputAction::new(TOOL_APPROVE_ACTION, "Approve", "yes")
+                .with_style(ActionStyle::Primary),
+        )
+        .with_action(
+            OutputAction::new(TOOL_DENY_ACTION, "Deny", "no").with_style(ActionStyle::Danger),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_pending_approval_survives_reload() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("approvals.json");
+        let args = json!({"command": "make deploy"});
+
+        let pending = PendingApprovals::new(Box::new(FileApprovalStore::new(&path)), 3600);
+        pending.request("telegram:42", "7", "shell", &args);
+        drop(pending);
+
+        // After a restart the user's "yes" still resolves the request.
+        let pending = PendingApprovals::new(Box::new(FileApprovalStore::new(&path)), 3600);
+        let open = pending.pending_for("telegram:42");
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].tool_name, "shell");
+        assert!(pending.pending_for("telegram:7").is_empty());
+        assert_eq!(pending.resolve("telegram:42", "telegram", "7", true), 1);
+        assert!(pending.pending_for("telegram:42").is_empty());
+
+        let pending = PendingApprovals::new(Box::new(FileApprovalStore::new(&path)), 3600);
+        assert!(!pending.take_grant("telegram:42", "shell", &json!({"command": "rm -rf /"})));
+        assert!(pending.take_grant("telegram:42", "shell", &args));
+        assert!(!pending.take_grant("telegram:42", "shell", &args));
+    }
+
+    #[test]
+    fn test_expired_approvals_are_pruned_on_load() {
+        let store = MemoryApprovalStore::default();
+        let now = Utc::now();
+        let entry = |id: &str, expires_at| PendingApproval {
+            id: id.to_string(),
+            session_key: "slack:C1".to_string(),
+            sender_id: "U1".to_string(),
+            tool_name: "write_file".to_string(),
+            arguments: json!({"path": id}),
+            created_at: now - Duration::hours(2),
+            expires_at,
+            approved: false,
+        };
+        store
+            .save(&[
+                entry("stale", Some(now - Duration::hours(1))),
+                entry("fresh", Some(now + Duration::hours(1))),
+                entry("forever", None),
+            ])
+            .unwrap();
+
+        let pending = PendingApprovals::new(Box::new(store), 3600);
+        let ids: Vec<String> = pending
+            .pending_for("slack:C1")
+            .into_iter()
+            .map(|a| a.id)
+            .collect();
+        assert_eq!(ids, ["fresh", "forever"]);
+        let stored = pending.store.load().unwrap();
+        assert_eq!(stored.len(), 2);
+
+        assert_eq!(pending.resolve("slack:C1", "slack", "U1", false), 2);
+        assert!(pending.store.load().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_only_requester_or_approver_may_resolve() {
+        let args = json!({"command": "make deploy"});
+        let pending = PendingApprovals::new(Box::new(MemoryApprovalStore::default()), 3600)
+            .with_approvers(vec!["telegram:admin".to_string()]);
+
+        pending.request("telegram:group", "alice", "shell", &args);
+        pending.request("telegram:group", "bob", "write_file", &args);
+
+        // Another group member cannot approve alice's request.
+        assert_eq!(
+            pending.resolve("telegram:group", "telegram", "mallory", true),
+            0
+        );
+        // An approver id on a different channel is not an approver.
+        assert_eq!(pending.resolve("telegram:group", "slack", "admin", true), 0);
+        assert_eq!(pending.pending_for("telegram:group").len(), 2);
+
+        // alice answers only her own request.
+        assert_eq!(
+            pending.resolve("telegram:group", "telegram", "alice", true),
+            1
+        );
+        assert!(pending.take_grant("telegram:group", "shell", &args));
+        assert_eq!(pending.pending_for("telegram:group")[0].sender_id, "bob");
+
+        // The configured approver may answer bob's.
+        assert_eq!(
+            pending.resolve("telegram:group", "telegram", "admin", false),
+            1
+        );
+        assert!(pending.pending_for("telegram:group").is_empty());
+    }
+}
@@ -0,0 +1,272 @@
+//! Tool-call argument validation against a tool's `parameters()` schema.
+//!
+//! Models occasionally emit arguments that do not match the declared schema
+//! (a number as a string, a missing required field). Checking them before
+//! `execute` turns a failure deep inside the tool into a precise error the
+//! model can correct on its next turn.
+//!
+//! Only the subset of JSON Schema that tool schemas use is checked: `type`
+//! (including type lists), `required`, `properties`, `items` and `enum`.
+//! Unknown keywords are ignored, and `null` for an optional property is
+//! treated as absent.
+//!
+//! # Example
+//!
+//! ```rust
+//! use serde_json::json;
+//! use zeptoclaw::tools::arg_validation::validate_args;
+//!
+//! let schema = json!({
+//!     "type": "object",
+//!     "properties": { "count": { "type": "integer" } },
+//!     "required": ["count"]
+//! });
+//! assert!(validate_args(&schema, &json!({"count": 3})).is_ok());
+//!
+//! let errors = validate_args(&schema, &json!({"count": "3"})).unwrap_err();
+//! assert_eq!(errors[0].to_string(), "argument `count`: expected integer, got string");
+//! ```
+
+use std::fmt;
+
+use serde_json::Value;
+
+/// A single mismatch between the arguments and the schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArgError {
+    /// Dotted path to the offending argument (`options.limit`, `paths[2]`).
+    pub path: String,
+    /// What is wrong with it.
+    pub message: String,
+}
+
+impl fmt::Display for ArgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "arguments: {}", self.message)
+        } else {
+            write!(f, "argument `{}`: {}", self.path, self.message)
+        }
+    }
+}
+
+/// Check `args` against `schema`, collecting every mismatch.
+pub fn validate_args(schema: &Value, args: &Value) -> std::result::Result<(), Vec<ArgError>> {
+    let mut errors = Vec::new();
+    check(schema, args, "", &mut errors);
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Error text returned to the model when a tool call's arguments are invalid.
+pub fn format_errors(tool_name: &str, errors: &[ArgError]) -> String {
+    let details: Vec<String> = errors.iter().map(ToString::to_string).collect();
+    format!(
+        "Invalid arguments for tool '{}': {}. Fix the arguments to match the tool's parameter schema and call it again.",
+        tool_name,
+        details.join("; ")
+    )
+}
+
+fn check(schema: &Value, value: &Value, path: &str, errors: &mut Vec<ArgError>) {
+    let Some(schema) = schema.as_object() else {
+        return;
+    };
+
+    if let Some(expected) = schema.get("type") {
+        let allowed: Vec<&str> = match expected {
+            Value::String(t) => vec![t.as_str()],
+            Value::Array(types) => types.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !allowed.is_empty() && !allowed.iter().any(|t| matches_type(t, value)) {
+            errors.push(ArgError {
+                path: path.to_string(),
+                message: format!(
+                    "expected {}, got {}",
+                    allowed.join(" or "),
+                    type_name(value)
+                ),
+            });
+            return;
+        }
+    }
+
+    if let Some(Value::Array(options)) = schema.get("enum") {
+        if !options.contains(value) {
+            let options: Vec<String> = options.iter().map(Value::to_string).collect();
+            errors.push(ArgError {
+                path: path.to_string(),
+                message: format!("must be one of {}, got {}", options.join(", "), value),
+            });
+            return;
+        }
+    }
+
+    match value {
+        Value::Object(fields) => {
+            let properties = schema.get("properties").and_then(Value::as_object);
+            let required = schema
+                .get("required")
+                .and_then(Value::as_array)
+                .map(|names| names.iter().filter_map(Value::as_str).collect::<Vec<_>>())
+                .unwrap_or_default();
+
+            for name in &required {
+                if !fields.contains_key(*name) {
+                    errors.push(ArgError {
+                        path: join(path, name),
+                        message: "missing required argument".to_string(),
+                    });
+                }
+            }
+            let Some(properties) = properties else {
+                return;
+            };
+            for (name, field) in fields {
+                let Some(field_schema) = properties.get(name) else {
+                    continue;
+                };
+                if field.is_null() && !required.contains(&name.as_str()) {
+                    continue;
+                }
+                check(field_schema, field, &join(path, name), errors);
+            }
+        }
+        Value::Array(items) => {
+            if let Some(item_schema) = schema.get("items") {
+                for (i, item) in items.iter().enumerate() {
+                    check(item_schema, item, &format!("{}[{}]", path, i), errors);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn matches_type(expected: &str, value: &Value) -> bool {
+    match expected {
+        "string" => value.is_string(),
+        "integer" => {
+            value.is_i64() || value.is_u64() || value.as_f64().is_some_and(|n| n.fract() == 0.0)
+        }
+        "number" => value.is_number(),
+        "boolean" => value.is_boolean(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        "null" => value.is_null(),
+        // Unknown type names are not ours to reject.
+        _ => true,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn join(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", path, name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sample_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": { "type": "string" },
+                "limit": { "type": "integer" },
+                "mode": { "type": "string", "enum": ["read", "write"] },
+                "options": {
+                    "type": "object",
+                    "properties": { "recursive": { "type": "boolean" } },
+                    "required": ["recursive"]
+                },
+                "tags": { "type": "array", "items": { "type": "string" } }
+            },
+            "required": ["path", "limit"]
+        })
+    }
+
+    #[test]
+    fn test_missing_required_fields_are_reported() {
+        let errors = validate_args(&sample_schema(), &json!({"options": {}})).unwrap_err();
+        let messages: Vec<String> = errors.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "argument `path`: missing required argument",
+                "argument `limit`: missing required argument",
+                "argument `options.recursive`: missing required argument",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_type_mismatches_are_reported() {
+        let args = json!({
+            "path": 42,
+            "limit": "10",
+            "mode": "append",
+            "tags": ["a", 2]
+        });
+        let errors = validate_args(&sample_schema(), &args).unwrap_err();
+        let messages: Vec<String> = errors.iter().map(ToString::to_string).collect();
+        assert!(messages.contains(&"argument `path`: expected string, got integer".to_string()));
+        assert!(messages.contains(&"argument `limit`: expected integer, got string".to_string()));
+        assert!(messages.contains(
+            &r#"argument `mode`: must be one of "read", "write", got "append""#.to_string()
+        ));
+        assert!(messages.contains(&"argument `tags[1]`: expected string, got integer".to_string()));
+
+        let errors = validate_args(&sample_schema(), &json!("not an object")).unwrap_err();
+        assert_eq!(
+            errors[0].to_string(),
+            "arguments: expected object, got string"
+        );
+
+        let text = format_errors("read_file", &errors);
+        assert!(
+            text.starts_with("Invalid arguments for tool 'read_file': arguments: expected object")
+        );
+    }
+
+    #[test]
+    fn test_valid_args_pass_through() {
+        let args = json!({
+            "path": "notes.md",
+            "limit": 5.0,
+            "mode": "read",
+            "options": { "recursive": true },
+            "tags": ["a", "b"],
+            "tags_extra": "unknown fields are ignored"
+        });
+        assert!(validate_args(&sample_schema(), &args).is_ok());
+        // Null for an optional argument means "not given".
+        assert!(validate_args(
+            &sample_schema(),
+            &json!({"path": "a", "limit": 1, "mode": null})
+        )
+        .is_ok());
+        // Tools without a schema accept anything.
+        assert!(validate_args(&json!({}), &json!({"anything": [1, 2]})).is_ok());
+    }
+}
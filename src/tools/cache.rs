@@ -0,0 +1,226 @@
+//! Response cache management from chat.
+//!
+//! The `cache` tool reports response cache statistics and purges entries,
+//! mirroring the `zeptoclaw cache` CLI. `clear` wipes every entry, so the
+//! default approval policy lists it as `cache:clear`.
+
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+use crate::cache::{parse_age, ResponseCache};
+use crate::error::{Result, ZeptoError};
+
+use super::{Tool, ToolCategory, ToolContext, ToolOutput};
+
+/// Tool that inspects and purges the agent's response cache.
+pub struct CacheTool {
+    cache: Arc<Mutex<ResponseCache>>,
+}
+
+impl CacheTool {
+    /// Create the tool over the agent's response cache.
+    pub fn new(cache: Arc<Mutex<ResponseCache>>) -> Self {
+        Self { cache }
+    }
+
+    fn invalidate(cache: &mut ResponseCache, args: &Value) -> Result<Value> {
+        let model = args.get("model").and_then(Value::as_str);
+        let older_than = args.get("older_than").and_then(Value::as_str);
+        let max_age = older_than
+            .map(|age| {
+                parse_age(age).ok_or_else(|| {
+                    ZeptoError::Tool(format!(
+                        "Invalid older_than '{}': expected a number with optional s/m/h/d suffix",
+                        age
+                    ))
+                })
+            })
+            .transpose()?;
+        if model.is_none() && max_age.is_none() {
+            return Err(ZeptoError::Tool(
+                "invalidate requires 'model' and/or 'older_than'".to_string(),
+            ));
+        }
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let removed = cache.invalidate_matching(|_, entry| {
+            model.is_none_or(|m| entry.model.as_deref() == Some(m))
+                && max_age.is_none_or(|age| now.saturating_sub(entry.created_at) > age)
+        });
+        Ok(json!({
+            "action": "invalidate",
+            "model": model,
+            "older_than_secs": max_age,
+            "removed": removed,
+            "remaining": cache.len(),
+        }))
+    }
+}
+
+#[async_trait]
+impl Tool for CacheTool {
+    fn name(&self) -> &str {
+        "cache"
+    }
+
+    fn description(&self) -> &str {
+        "Inspect and manage the LLM response cache. Actions: stats (entries, size, hit rate), clear (remove every entry), invalidate (remove entries for a model and/or older than an age like 30m, 12h, 7d)."
+    }
+
+    fn compact_description(&self) -> &str {
+        "Response cache stats/clear/invalidate"
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::Memory
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["stats", "clear", "invalidate"],
+                    "description": "What to do (default: stats)"
+                },
+                "model": {
+                    "type": "string",
+                    "description": "Only invalidate entries produced by this model"
+                },
+                "older_than": {
+                    "type": "string",
+                    "description": "Only invalidate entries older than this age (e.g. 90s, 30m, 12h, 7d)"
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: Value, _ctx: &ToolContext) -> Result<ToolOutput> {
+        let mut cache = self
+            .cache
+            .lock()
+            .map_err(|_| ZeptoError::Tool("Response cache lock poisoned".to_string()))?;
+
+        let result = match args
+            .get("action")
+            .and_then(Value::as_str)
+            .unwrap_or("stats")
+        {
+            "stats" => {
+                let stats = cache.stats();
+                json!({
+                    "action": "stats",
+                    "entries": stats.total_entries,
+                    "size_bytes": stats.total_bytes,
+                    "hits": stats.total_hits,
+                    "misses": stats.misses,
+                    "hit_rate": stats.hit_rate(),
+                    "tokens_saved": stats.total_tokens_saved,
+                    "evictions": stats.evictions,
+                })
+            }
+            "clear" => json!({
+                "action": "clear",
+                "removed": cache.clear(),
+            }),
+            "invalidate" => Self::invalidate(&mut cache, &args)?,
+            other => {
+                return Err(ZeptoError::Tool(format!(
+                    "Unknown action '{}'. Use stats, clear or invalidate.",
+                    other
+                )))
+            }
+        };
+        Ok(ToolOutput::llm_only(result.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tool(dir: &tempfile::TempDir) -> (CacheTool, Arc<Mutex<ResponseCache>>) {
+        let cache = Arc::new(Mutex::new(ResponseCache::with_path(
+            dir.path().join("responses.json"),
+            3600,
+            100,
+        )));
+        (CacheTool::new(Arc::clone(&cache)), cache)
+    }
+
+    async fn run(tool: &CacheTool, args: Value) -> Value {
+        let out = tool.execute(args, &ToolContext::new()).await.unwrap();
+        serde_json::from_str(&out.for_llm).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_stats_reports_size_and_hit_rate() {
+        let dir = tempfile::tempdir().unwrap();
+        let (tool, cache) = tool(&dir);
+        {
+            let mut cache = cache.lock().unwrap();
+            cache.put_for_model("k1".into(), "gpt-5.1", "hello".into(), 10);
+            cache.get("k1");
+            cache.get("k1");
+            cache.get("k1");
+            cache.get("missing");
+        }
+
+        let stats = run(&tool, json!({"action": "stats"})).await;
+        assert_eq!(stats["entries"], 1);
+        assert_eq!(
+            stats["size_bytes"],
+            "k1".len() + "hello".len() + "gpt-5.1".len()
+        );
+        assert_eq!(stats["hits"], 3);
+        assert_eq!(stats["misses"], 1);
+        assert_eq!(stats["hit_rate"], 0.75);
+        assert_eq!(stats["tokens_saved"], 30);
+    }
+
+    #[tokio::test]
+    async fn test_clear_removes_entries_and_reports_count() {
+        let dir = tempfile::tempdir().unwrap();
+        let (tool, cache) = tool(&dir);
+        {
+            let mut cache = cache.lock().unwrap();
+            cache.put("k1".into(), "r1".into(), 1);
+            cache.put("k2".into(), "r2".into(), 1);
+        }
+
+        let result = run(&tool, json!({"action": "clear"})).await;
+        assert_eq!(result["removed"], 2);
+        assert!(cache.lock().unwrap().is_empty());
+
+        let reloaded = ResponseCache::with_path(dir.path().join("responses.json"), 3600, 100);
+        assert!(reloaded.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_invalidate_by_model_requires_a_filter() {
+        let dir = tempfile::tempdir().unwrap();
+        let (tool, cache) = tool(&dir);
+        {
+            let mut cache = cache.lock().unwrap();
+            cache.put_for_model("k1".into(), "old-model", "r1".into(), 1);
+            cache.put_for_model("k2".into(), "new-model", "r2".into(), 1);
+        }
+
+        let result = run(&tool, json!({"action": "invalidate", "model": "old-model"})).await;
+        assert_eq!(result["removed"], 1);
+        assert_eq!(result["remaining"], 1);
+
+        let err = tool
+            .execute(json!({"action": "invalidate"}), &ToolContext::new())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("older_than"));
+    }
+}
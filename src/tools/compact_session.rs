@@ -0,0 +1,342 @@
+//! Agent-triggered conversation compaction.
+//!
+//! The `compact_session` tool asks the LLM to summarize the oldest messages of
+//! the current session and replaces them with a single `[Conversation Summary]`
+//! system note.  The leading system prompt and the most recent turns are kept
+//! verbatim.  Unlike the automatic context recovery in
+//! [`crate::agent::compaction`], the agent decides when to run it.
+//!
+//! The tool edits the persisted session, so the agent loop saves the session
+//! before running it and reloads it afterwards.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+use crate::agent::context_monitor::ContextMonitor;
+use crate::error::{Result, ZeptoError};
+use crate::providers::{ChatOptions, LLMProvider};
+use crate::session::{Message, Role, SessionManager};
+
+use super::{Tool, ToolCategory, ToolContext, ToolOutput};
+
+/// Tool name, matched by the agent loop to sync the session around the call.
+pub const COMPACT_SESSION_TOOL: &str = "compact_session";
+
+/// Recent messages kept verbatim when the caller does not say otherwise.
+const DEFAULT_KEEP_RECENT: usize = 8;
+/// Lower bound on kept messages so the pending tool call is never summarized.
+const MIN_KEEP_RECENT: usize = 2;
+/// Per-message cap on text fed to the summarizer.
+const MAX_TRANSCRIPT_CHARS_PER_MESSAGE: usize = 2_000;
+
+const SUMMARY_PROMPT: &str =
+    "Summarize the following conversation excerpt for your own future reference. \
+Keep facts, decisions, open tasks, file paths, identifiers and user preferences. \
+Drop pleasantries and redundant tool output. Reply with the summary only.";
+
+/// Range of messages to replace with a summary.
+///
+/// Starts after a leading system prompt and ends before the `keep_recent`
+/// most recent messages.  The end is pushed forward past tool results so a
+/// result is never separated from the assistant message that requested it.
+/// Returns `None` when there is nothing to compact.
+pub fn compaction_range(
+    messages: &[Message],
+    max_messages: Option<usize>,
+    keep_recent: usize,
+) -> Option<std::ops::Range<usize>> {
+    let start = usize::from(messages.first().is_some_and(|m| m.role == Role::System));
+    let limit = messages.len().saturating_sub(keep_recent);
+    let mut end = match max_messages {
+        Some(n) => (start + n).min(limit),
+        None => limit,
+    };
+    while end < limit && messages[end].role == Role::Tool {
+        end += 1;
+    }
+    // The pushed-forward end may have eaten into the kept tail; back off to
+    // the preceding turn boundary instead.
+    while end > start && end < messages.len() && messages[end].role == Role::Tool {
+        end -= 1;
+    }
+    (end > start).then_some(start..end)
+}
+
+/// Replace `range` with a single summary system note.
+pub fn replace_with_summary(
+    messages: Vec<Message>,
+    range: std::ops::Range<usize>,
+    summary_text: &str,
+) -> Vec<Message> {
+    let mut result = Vec::with_capacity(messages.len() - range.len() + 1);
+    for (i, msg) in messages.into_iter().enumerate() {
+        if i == range.start {
+            result.push(Message::system(&format!(
+                "[Conversation Summary]\n{}",
+                summary_text.trim()
+            )));
+        }
+        if !range.contains(&i) {
+            result.push(msg);
+        }
+    }
+    result
+}
+
+/// Render messages as a plain-text transcript for the summarizer.
+fn transcript(messages: &[Message]) -> String {
+    let mut out = String::new();
+    for msg in messages {
+        let mut text = msg.content.trim().to_string();
+        if let Some(calls) = &msg.tool_calls {
+            for call in calls {
+                text.push_str(&format!("\n[called {}({})]", call.name, call.arguments));
+            }
+        }
+        if text.len() > MAX_TRANSCRIPT_CHARS_PER_MESSAGE {
+            let mut cut = MAX_TRANSCRIPT_CHARS_PER_MESSAGE;
+            while !text.is_char_boundary(cut) {
+                cut -= 1;
+            }
+            text.truncate(cut);
+            text.push('…');
+        }
+        out.push_str(&format!("{}: {}\n\n", msg.role, text));
+    }
+    out
+}
+
+/// Tool that summarizes old turns of the current session in place.
+pub struct CompactSessionTool {
+    sessions: Arc<SessionManager>,
+    provider: Arc<dyn LLMProvider>,
+    model: Option<String>,
+}
+
+impl CompactSessionTool {
+    /// Create the tool over the agent's session manager and provider.
+    pub fn new(sessions: Arc<SessionManager>, provider: Arc<dyn LLMProvider>) -> Self {
+        Self {
+            sessions,
+            provider,
+            model: None,
+        }
+    }
+
+    /// Use a specific model for summaries instead of the provider default.
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+
+    async fn summarize(&self, messages: &[Message]) -> Result<String> {
+        let request = vec![
+            Message::system(SUMMARY_PROMPT),
+            Message::user(&transcript(messages)),
+        ];
+        let options = ChatOptions::new()
+            .with_max_tokens(1024)
+            .with_temperature(0.0);
+        let response = self
+            .provider
+            .chat(request, vec![], self.model.as_deref(), options)
+            .await?;
+        let summary = response.content.trim();
+        if summary.is_empty() {
+            return Err(ZeptoError::Tool(
+                "Summarizer returned an empty summary".to_string(),
+            ));
+        }
+        Ok(summary.to_string())
+    }
+}
+
+#[async_trait]
+impl Tool for CompactSessionTool {
+    fn name(&self) -> &str {
+        COMPACT_SESSION_TOOL
+    }
+
+    fn description(&self) -> &str {
+        "Compress the oldest messages of this conversation into a single summary note to reduce context size. The system prompt and recent turns are kept verbatim. Reports estimated token counts before and after."
+    }
+
+    fn compact_description(&self) -> &str {
+        "Summarize old turns"
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::Memory
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "messages": {
+                    "type": "integer",
+                    "description": "How many of the oldest messages to summarize (default: all but the recent ones)"
+                },
+                "keep_recent": {
+                    "type": "integer",
+                    "description": "How many recent messages to keep verbatim (minimum 2, default: 8)"
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<ToolOutput> {
+        let (Some(channel), Some(chat_id)) = (ctx.channel.as_deref(), ctx.chat_id.as_deref())
+        else {
+            return Err(ZeptoError::Tool(
+                "compact_session requires a channel and chat context".to_string(),
+            ));
+        };
+        let max_messages = args
+            .get("messages")
+            .and_then(Value::as_u64)
+            .map(|n| n as usize);
+        let keep_recent = args
+            .get("keep_recent")
+            .and_then(Value::as_u64)
+            .map(|n| (n as usize).max(MIN_KEEP_RECENT))
+            .unwrap_or(DEFAULT_KEEP_RECENT);
+
+        let key = format!("{}:{}", channel, chat_id);
+        let Some(mut session) = self.sessions.get(&key).await? else {
+            return Ok(ToolOutput::llm_only("No session history to compact."));
+        };
+        let Some(range) = compaction_range(&session.messages, max_messages, keep_recent) else {
+            return Ok(ToolOutput::llm_only(format!(
+                "Nothing to compact: the session has {} message(s) and {} are kept verbatim.",
+                session.messages.len(),
+                keep_recent
+            )));
+        };
+
+        let before = ContextMonitor::estimate_tokens(&session.messages);
+        let summary = self.summarize(&session.messages[range.clone()]).await?;
+        let summarized = range.len();
+        session.messages = replace_with_summary(session.messages, range, &summary);
+        let after = ContextMonitor::estimate_tokens(&session.messages);
+        self.sessions.save(&session).await?;
+
+        Ok(ToolOutput::llm_only(format!(
+            "Compacted {} message(s) into a summary note. Estimated tokens: {} -> {}.",
+            summarized, before, after
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::{LLMResponse, ToolDefinition};
+
+    struct FixedSummarizer;
+
+    #[async_trait]
+    impl LLMProvider for FixedSummarizer {
+        fn name(&self) -> &str {
+            "fixed"
+        }
+        fn default_model(&self) -> &str {
+            "fixed"
+        }
+        async fn chat(
+            &self,
+            _messages: Vec<Message>,
+            _tools: Vec<ToolDefinition>,
+            _model: Option<&str>,
+            _options: ChatOptions,
+        ) -> Result<LLMResponse> {
+            Ok(LLMResponse::text("User is planning a trip to Lisbon."))
+        }
+    }
+
+    fn history(turns: usize) -> Vec<Message> {
+        let pad = "x".repeat(200);
+        let mut messages = vec![Message::system("You are helpful.")];
+        for i in 0..turns {
+            messages.push(Message::user(&format!("question {i} {pad}")));
+            messages.push(Message::assistant(&format!("answer {i} {pad}")));
+        }
+        messages
+    }
+
+    #[test]
+    fn test_replace_with_summary_structure() {
+        let messages = history(5);
+        let range = compaction_range(&messages, None, 4).unwrap();
+        assert_eq!(range, 1..7);
+
+        let result = replace_with_summary(messages.clone(), range, "summary");
+        assert_eq!(result.len(), 6);
+        assert_eq!(result[0].content, "You are helpful.");
+        assert_eq!(result[1].role, Role::System);
+        assert!(result[1].content.starts_with("[Conversation Summary]"));
+        assert!(!result.iter().any(|m| m.content.starts_with("question 0")));
+        for (kept, original) in result[2..].iter().zip(&messages[7..]) {
+            assert_eq!(kept.content, original.content);
+        }
+    }
+
+    #[test]
+    fn test_compaction_range_limits_to_oldest_n() {
+        let messages = history(5);
+        assert_eq!(compaction_range(&messages, Some(2), 4), Some(1..3));
+        assert_eq!(compaction_range(&messages, Some(0), 4), None);
+        assert_eq!(compaction_range(&messages, None, 20), None);
+    }
+
+    #[test]
+    fn test_compaction_range_keeps_tool_results_with_their_call() {
+        let mut call = Message::assistant("");
+        call.tool_calls = Some(vec![crate::session::ToolCall {
+            id: "c1".into(),
+            name: "shell".into(),
+            arguments: "{}".into(),
+        }]);
+        let messages = vec![
+            Message::user("run it"),
+            call,
+            Message::tool_result("c1", "ok"),
+            Message::assistant("done"),
+            Message::user("thanks"),
+        ];
+        let range = compaction_range(&messages, Some(2), 1).unwrap();
+        assert_eq!(range, 0..3);
+        assert_eq!(compaction_range(&messages, None, 3), Some(0..1));
+    }
+
+    #[tokio::test]
+    async fn test_compact_session_rewrites_stored_session() {
+        let sessions = Arc::new(SessionManager::new_memory());
+        let mut session = sessions.get_or_create("cli:chat").await.unwrap();
+        session.messages = history(6);
+        sessions.save(&session).await.unwrap();
+
+        let tool = CompactSessionTool::new(Arc::clone(&sessions), Arc::new(FixedSummarizer));
+        let ctx = ToolContext::new().with_channel("cli", "chat");
+        let out = tool.execute(json!({"keep_recent": 4}), &ctx).await.unwrap();
+        assert!(out.for_llm.contains("Compacted 8 message(s)"));
+        assert!(out.for_llm.contains("Estimated tokens"));
+
+        let stored = sessions.get("cli:chat").await.unwrap().unwrap();
+        assert_eq!(stored.messages.len(), 6);
+        assert!(stored.messages[1].content.contains("Lisbon"));
+        assert!(stored.messages[5].content.starts_with("answer 5"));
+    }
+
+    #[tokio::test]
+    async fn test_compact_session_requires_chat_context() {
+        let tool = CompactSessionTool::new(
+            Arc::new(SessionManager::new_memory()),
+            Arc::new(FixedSummarizer),
+        );
+        let err = tool.execute(json!({}), &ToolContext::new()).await;
+        assert!(err.is_err());
+    }
+}
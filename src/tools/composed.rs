@@ -8,6 +8,29 @@
 //!
 //! Definitions are persisted at `~/.zeptoclaw/composed_tools.json`.
 //!
+//! # Context placeholders
+//!
+//! Placeholders starting with `_` are reserved for values injected from the
+//! execution context rather than passed by the caller: `{{_date}}` (local
+//! date, `YYYY-MM-DD`), `{{_user}}` (the sender's ID) and `{{_workspace}}`
+//! (the workspace path). They are empty when the context lacks the value.
+//! Declared parameter names may not start with `_`.
+//!
+//! # Escaping
+//!
+//! Values are interpolated verbatim by default. A parameter can set
+//! `escape` to `shell` (single-quoted shell word), `url` (percent-encoded)
+//! or `json` (JSON string contents, without the surrounding quotes) when
+//! the action places it in such a context. Context placeholders and
+//! undeclared arguments are never escaped.
+//!
+//! # Sharing
+//!
+//! `create_tool` can `export` selected definitions as a versioned
+//! [`ComposedToolBundle`] and `import` a bundle, skipping, renaming or
+//! overwriting tools whose names already exist. Imported definitions are
+//! validated like created ones.
+//!
 //! # Example
 //!
 //! ```json
@@ -22,6 +45,7 @@
 //! ```
 
 use async_trait::async_trait;
+use chrono::{DateTime, Local};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::collections::HashMap;
@@ -48,6 +72,54 @@ pub struct ParamDef {
     /// Whether the parameter is required.
     #[serde(default = "default_true")]
     pub required: bool,
+    /// How the value is escaped when interpolated into the action.
+    #[serde(default, skip_serializing_if = "EscapeMode::is_none")]
+    pub escape: EscapeMode,
+}
+
+/// Escaping applied to a parameter value during interpolation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EscapeMode {
+    /// Insert the value as-is.
+    #[default]
+    None,
+    /// Quote the value as a single POSIX shell word.
+    Shell,
+    /// Percent-encode everything but RFC 3986 unreserved characters.
+    Url,
+    /// Escape the value for use inside a JSON string literal.
+    Json,
+}
+
+impl EscapeMode {
+    fn is_none(&self) -> bool {
+        *self == Self::None
+    }
+
+    /// Escape `value` according to this mode.
+    pub fn apply(self, value: &str) -> String {
+        match self {
+            Self::None => value.to_string(),
+            Self::Shell => format!("'{}'", value.replace('\'', "'\\''")),
+            Self::Url => {
+                let mut out = String::with_capacity(value.len());
+                for b in value.bytes() {
+                    match b {
+                        b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                            out.push(b as char)
+                        }
+                        _ => out.push_str(&format!("%{:02X}", b)),
+                    }
+                }
+                out
+            }
+            Self::Json => {
+                let quoted = Value::String(value.to_string()).to_string();
+                quoted[1..quoted.len() - 1].to_string()
+            }
+        }
+    }
 }
 
 fn default_param_type() -> String {
@@ -75,6 +147,136 @@ pub struct ComposedToolDef {
     pub created_at: String,
 }
 
+/// `format` marker of an exported bundle.
+pub const BUNDLE_FORMAT: &str = "zeptoclaw.composed_tools";
+/// Current bundle version. Bundles with a newer version are rejected.
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// A shareable set of composed tool definitions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComposedToolBundle {
+    /// Always [`BUNDLE_FORMAT`].
+    pub format: String,
+    /// Bundle schema version.
+    pub version: u32,
+    /// ISO-8601 export timestamp.
+    #[serde(default)]
+    pub exported_at: String,
+    /// The exported definitions.
+    pub tools: Vec<ComposedToolDef>,
+}
+
+/// What `import` does with a tool whose name already exists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictStrategy {
+    /// Keep the existing tool and drop the imported one.
+    Skip,
+    /// Import under a new name (`name_2`, `name_3`, ...).
+    Rename,
+    /// Replace the existing tool.
+    Overwrite,
+}
+
+impl std::str::FromStr for ConflictStrategy {
+    type Err = ZeptoError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "skip" => Ok(Self::Skip),
+            "rename" => Ok(Self::Rename),
+            "overwrite" => Ok(Self::Overwrite),
+            other => Err(ZeptoError::Tool(format!(
+                "Unknown on_conflict '{}'. Use: skip, rename, overwrite",
+                other
+            ))),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Validation
+// ---------------------------------------------------------------------------
+
+/// Check a tool name: 1-64 alphanumeric, underscore or hyphen characters.
+fn validate_name(name: &str) -> Result<()> {
+    if !name
+        .chars()
+        .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+    {
+        return Err(ZeptoError::Tool(
+            "Tool name must be alphanumeric with underscores/hyphens only".into(),
+        ));
+    }
+    if name.is_empty() || name.len() > 64 {
+        return Err(ZeptoError::Tool("Tool name must be 1-64 characters".into()));
+    }
+    Ok(())
+}
+
+/// `{{placeholder}}` names used in an action template.
+fn placeholders(template: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            break;
+        };
+        let name = &after[..end];
+        if !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+            && !names.contains(&name)
+        {
+            names.push(name);
+        }
+        rest = &after[end + 2..];
+    }
+    names
+}
+
+/// Validate a definition before it is stored: name rules, and every
+/// `{{placeholder}}` in the action must be a declared parameter.
+fn validate_def(def: &ComposedToolDef) -> Result<()> {
+    validate_name(&def.name)?;
+    if let Some(reserved) = def
+        .parameters
+        .keys()
+        .find(|name| name.starts_with(CONTEXT_PLACEHOLDER_PREFIX))
+    {
+        return Err(ZeptoError::Tool(format!(
+            "Parameter '{}' uses the reserved '{}' prefix",
+            reserved, CONTEXT_PLACEHOLDER_PREFIX
+        )));
+    }
+    let (context, declared): (Vec<&str>, Vec<&str>) = placeholders(&def.action)
+        .into_iter()
+        .partition(|name| name.starts_with(CONTEXT_PLACEHOLDER_PREFIX));
+    if let Some(unknown) = context
+        .iter()
+        .find(|name| !CONTEXT_PLACEHOLDERS.contains(name))
+    {
+        return Err(ZeptoError::Tool(format!(
+            "Unknown context placeholder '{{{{{}}}}}'. Available: {}",
+            unknown,
+            CONTEXT_PLACEHOLDERS.join(", ")
+        )));
+    }
+    let undeclared: Vec<&str> = declared
+        .into_iter()
+        .filter(|name| !def.parameters.contains_key(*name))
+        .collect();
+    if !undeclared.is_empty() {
+        return Err(ZeptoError::Tool(format!(
+            "Tool '{}' uses undeclared parameter(s) in its action: {}. Declare them in 'parameters'.",
+            def.name,
+            undeclared.join(", ")
+        )));
+    }
+    Ok(())
+}
+
 // ---------------------------------------------------------------------------
 // Store (load / save)
 // ---------------------------------------------------------------------------
@@ -124,15 +326,141 @@ impl ComposedToolStore {
 // ---------------------------------------------------------------------------
 
 /// Replace `{{key}}` placeholders in an action template with parameter values.
-/// Unlike shell-based custom tools, no shell escaping is needed since the output
-/// is natural language fed back to the LLM, not a shell command.
+/// Values arrive already escaped per their [`EscapeMode`] (see
+/// [`prepare_args`]); the output is natural language fed back to the LLM.
 fn interpolate_action(template: &str, args: &HashMap<String, String>) -> String {
-    let mut result = template.to_string();
-    for (key, value) in args {
-        let placeholder = format!("{{{{{}}}}}", key);
-        result = result.replace(&placeholder, value);
+    crate::utils::string::interpolate(template, args)
+}
+
+/// Prefix of placeholders injected from the execution context.
+pub const CONTEXT_PLACEHOLDER_PREFIX: &str = "_";
+/// Context placeholders, without braces.
+pub const CONTEXT_PLACEHOLDERS: &[&str] = &["_date", "_user", "_workspace"];
+
+/// Values for the context placeholders at `now`.
+fn context_args(ctx: &ToolContext, now: DateTime<Local>) -> HashMap<String, String> {
+    HashMap::from([
+        ("_date".to_string(), now.format("%Y-%m-%d").to_string()),
+        (
+            "_user".to_string(),
+            ctx.sender_id.clone().unwrap_or_default(),
+        ),
+        (
+            "_workspace".to_string(),
+            ctx.workspace.clone().unwrap_or_default(),
+        ),
+    ])
+}
+
+/// Interpolate caller arguments and context placeholders into `action`.
+/// Context values win over caller arguments with the same name.
+fn render_action(
+    action: &str,
+    mut args: HashMap<String, String>,
+    ctx: &ToolContext,
+    now: DateTime<Local>,
+) -> String {
+    args.extend(context_args(ctx, now));
+    interpolate_action(action, &args)
+}
+
+/// Render one argument as text, checking it against its declared type.
+///
+/// Strings holding a number or boolean are accepted for numeric/boolean
+/// parameters (`"5"` for an integer); anything that cannot be read as the
+/// declared type is rejected.
+fn coerce_param(value: &Value, param_type: &str) -> std::result::Result<String, String> {
+    let text = match value {
+        Value::String(s) => Some(s.trim()),
+        _ => None,
+    };
+    let coerced = match param_type {
+        "integer" => match value {
+            Value::Number(n) if n.is_i64() || n.is_u64() => Some(n.to_string()),
+            Value::Number(n) => n
+                .as_f64()
+                .filter(|f| f.fract() == 0.0 && f.abs() < i64::MAX as f64)
+                .map(|f| (f as i64).to_string()),
+            _ => text
+                .and_then(|t| t.parse::<i64>().ok())
+                .map(|i| i.to_string()),
+        },
+        "number" => match value {
+            Value::Number(n) => Some(n.to_string()),
+            _ => text
+                .filter(|t| t.parse::<f64>().is_ok_and(f64::is_finite))
+                .map(str::to_string),
+        },
+        "boolean" => match value {
+            Value::Bool(b) => Some(b.to_string()),
+            _ => text.and_then(|t| match t.to_ascii_lowercase().as_str() {
+                "true" => Some("true".to_string()),
+                "false" => Some("false".to_string()),
+                _ => None,
+            }),
+        },
+        _ => Some(match value {
+            Value::String(s) => s.clone(),
+            other => other.to_string(),
+        }),
+    };
+    coerced.ok_or_else(|| format!("expected {}, got {}", param_type, value))
+}
+
+/// Check `args` against a definition's parameters and render them for
+/// interpolation, applying each parameter's [`EscapeMode`].
+///
+/// Missing required parameters and values that do not match their type are
+/// reported together. Missing optional parameters interpolate as empty text;
+/// undeclared arguments are passed through as-is. Keys in `skip` are ignored.
+fn prepare_args(
+    def: &ComposedToolDef,
+    args: &Value,
+    skip: &[&str],
+) -> Result<HashMap<String, String>> {
+    let empty = serde_json::Map::new();
+    let obj = args.as_object().unwrap_or(&empty);
+    let mut rendered = HashMap::new();
+    let mut errors = Vec::new();
+
+    let mut names: Vec<&String> = def.parameters.keys().collect();
+    names.sort();
+    for name in names {
+        let param = &def.parameters[name];
+        match obj.get(name.as_str()).filter(|v| !v.is_null()) {
+            Some(value) => match coerce_param(value, &param.param_type) {
+                Ok(text) => {
+                    rendered.insert(name.clone(), param.escape.apply(&text));
+                }
+                Err(e) => errors.push(format!("parameter '{}' {}", name, e)),
+            },
+            None if param.required => {
+                errors.push(format!("missing required parameter '{}'", name));
+            }
+            None => {
+                rendered.insert(name.clone(), param.escape.apply(""));
+            }
+        }
+    }
+    if !errors.is_empty() {
+        return Err(ZeptoError::Tool(format!(
+            "Invalid arguments for composed tool '{}': {}",
+            def.name,
+            errors.join("; ")
+        )));
     }
-    result
+
+    for (key, value) in obj {
+        if skip.contains(&key.as_str()) || def.parameters.contains_key(key) {
+            continue;
+        }
+        let text = match value {
+            Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+        rendered.insert(key.clone(), text);
+    }
+    Ok(rendered)
 }
 
 // ---------------------------------------------------------------------------
@@ -179,7 +507,10 @@ impl Tool for ComposedTool {
         let mut properties = serde_json::Map::new();
         let mut required = Vec::new();
 
-        for (name, param) in &self.def.parameters {
+        // Sorted so the schema is byte-identical across runs (prompt caching).
+        let mut params: Vec<_> = self.def.parameters.iter().collect();
+        params.sort_by(|a, b| a.0.cmp(b.0));
+        for (name, param) in params {
             let mut prop = serde_json::Map::new();
             prop.insert("type".to_string(), json!(param.param_type));
             if !param.description.is_empty() {
@@ -198,23 +529,10 @@ impl Tool for ComposedTool {
         })
     }
 
-    async fn execute(&self, args: Value, _ctx: &ToolContext) -> Result<ToolOutput> {
-        // Extract string values from args
-        let string_args: HashMap<String, String> = if let Some(obj) = args.as_object() {
-            obj.iter()
-                .map(|(k, v)| {
-                    let val = match v {
-                        Value::String(s) => s.clone(),
-                        other => other.to_string(),
-                    };
-                    (k.clone(), val)
-                })
-                .collect()
-        } else {
-            HashMap::new()
-        };
+    async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<ToolOutput> {
+        let string_args = prepare_args(&self.def, &args, &[])?;
 
-        let instructions = interpolate_action(&self.def.action, &string_args);
+        let instructions = render_action(&self.def.action, string_args, ctx, Local::now());
 
         debug!(
             tool = %self.def.name,
@@ -241,6 +559,8 @@ impl Tool for ComposedTool {
 /// - `list` — list all composed tools
 /// - `delete` — remove a composed tool
 /// - `run` — execute a composed tool in the current session
+/// - `export` — write composed tools to a shareable bundle
+/// - `import` — merge a bundle into the stored tools
 pub struct CreateToolTool {
     store_path: PathBuf,
 }
@@ -270,18 +590,7 @@ impl CreateToolTool {
             .and_then(|v| v.as_str())
             .ok_or_else(|| ZeptoError::Tool("'name' is required".into()))?;
 
-        // Validate name: alphanumeric + underscores only
-        if !name
-            .chars()
-            .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
-        {
-            return Err(ZeptoError::Tool(
-                "Tool name must be alphanumeric with underscores/hyphens only".into(),
-            ));
-        }
-        if name.is_empty() || name.len() > 64 {
-            return Err(ZeptoError::Tool("Tool name must be 1-64 characters".into()));
-        }
+        validate_name(name)?;
 
         let description = args
             .get("description")
@@ -309,6 +618,7 @@ impl CreateToolTool {
                                 param_type: v.as_str().unwrap_or("string").to_string(),
                                 description: String::new(),
                                 required: true,
+                                escape: EscapeMode::None,
                             }
                         } else {
                             // Full object
@@ -316,6 +626,7 @@ impl CreateToolTool {
                                 param_type: "string".to_string(),
                                 description: String::new(),
                                 required: true,
+                                escape: EscapeMode::None,
                             })
                         };
                         (k.clone(), param)
@@ -346,6 +657,7 @@ impl CreateToolTool {
             parameters,
             created_at: now,
         };
+        validate_def(&def)?;
 
         defs.push(def);
         ComposedToolStore::save(&self.store_path, &defs)?;
@@ -414,7 +726,153 @@ impl CreateToolTool {
         )))
     }
 
-    fn handle_run(&self, args: &Value) -> Result<ToolOutput> {
+    fn handle_export(&self, args: &Value, ctx: &ToolContext) -> Result<ToolOutput> {
+        let defs = ComposedToolStore::load(&self.store_path)?;
+        let selected: Vec<ComposedToolDef> = match args.get("names").and_then(|v| v.as_array()) {
+            Some(names) => {
+                let names: Vec<&str> = names.iter().filter_map(|v| v.as_str()).collect();
+                let missing: Vec<&str> = names
+                    .iter()
+                    .copied()
+                    .filter(|name| !defs.iter().any(|d| d.name == *name))
+                    .collect();
+                if !missing.is_empty() {
+                    return Err(ZeptoError::Tool(format!(
+                        "No composed tool named: {}",
+                        missing.join(", ")
+                    )));
+                }
+                defs.into_iter()
+                    .filter(|d| names.contains(&d.name.as_str()))
+                    .collect()
+            }
+            None => defs,
+        };
+        if selected.is_empty() {
+            return Err(ZeptoError::Tool("No composed tools to export".into()));
+        }
+
+        let bundle = ComposedToolBundle {
+            format: BUNDLE_FORMAT.to_string(),
+            version: BUNDLE_VERSION,
+            exported_at: chrono::Utc::now().to_rfc3339(),
+            tools: selected,
+        };
+        let json = serde_json::to_string_pretty(&bundle)
+            .map_err(|e| ZeptoError::Tool(format!("Failed to serialize bundle: {}", e)))?;
+
+        match args.get("path").and_then(|v| v.as_str()) {
+            Some(path) => {
+                let target = ctx.resolve_in_workspace(path)?;
+                std::fs::write(target.as_path(), &json)
+                    .map_err(|e| ZeptoError::Tool(format!("Failed to write bundle: {}", e)))?;
+                info!(count = bundle.tools.len(), path = %path, "Exported composed tools");
+                Ok(ToolOutput::user_visible(format!(
+                    "Exported {} composed tool(s) to {}.",
+                    bundle.tools.len(),
+                    path
+                )))
+            }
+            None => Ok(ToolOutput::llm_only(json)),
+        }
+    }
+
+    fn handle_import(&self, args: &Value, ctx: &ToolContext) -> Result<ToolOutput> {
+        let bundle_value = match (
+            args.get("bundle"),
+            args.get("path").and_then(|v| v.as_str()),
+        ) {
+            (Some(Value::String(text)), _) => serde_json::from_str(text)
+                .map_err(|e| ZeptoError::Tool(format!("Invalid bundle JSON: {}", e)))?,
+            (Some(value), _) => value.clone(),
+            (None, Some(path)) => {
+                let source = ctx.resolve_in_workspace(path)?;
+                let text = std::fs::read_to_string(source.as_path())
+                    .map_err(|e| ZeptoError::Tool(format!("Failed to read bundle: {}", e)))?;
+                serde_json::from_str(&text)
+                    .map_err(|e| ZeptoError::Tool(format!("Invalid bundle JSON: {}", e)))?
+            }
+            (None, None) => {
+                return Err(ZeptoError::Tool(
+                    "'bundle' or 'path' is required for import".into(),
+                ))
+            }
+        };
+        let bundle: ComposedToolBundle = serde_json::from_value(bundle_value)
+            .map_err(|e| ZeptoError::Tool(format!("Invalid bundle: {}", e)))?;
+        if bundle.format != BUNDLE_FORMAT {
+            return Err(ZeptoError::Tool(format!(
+                "Not a composed tool bundle (format '{}')",
+                bundle.format
+            )));
+        }
+        if bundle.version > BUNDLE_VERSION {
+            return Err(ZeptoError::Tool(format!(
+                "Bundle version {} is newer than supported version {}",
+                bundle.version, BUNDLE_VERSION
+            )));
+        }
+        let strategy: ConflictStrategy = args
+            .get("on_conflict")
+            .and_then(|v| v.as_str())
+            .unwrap_or("skip")
+            .parse()?;
+
+        // Validate the whole bundle before touching the store.
+        for (i, def) in bundle.tools.iter().enumerate() {
+            validate_def(def)?;
+            if bundle.tools[..i].iter().any(|d| d.name == def.name) {
+                return Err(ZeptoError::Tool(format!(
+                    "Bundle contains '{}' more than once",
+                    def.name
+                )));
+            }
+        }
+
+        let mut defs = ComposedToolStore::load(&self.store_path)?;
+        let mut imported = Vec::new();
+        let mut notes = Vec::new();
+        for mut def in bundle.tools {
+            if def.created_at.is_empty() {
+                def.created_at = chrono::Utc::now().to_rfc3339();
+            }
+            match defs.iter().position(|d| d.name == def.name) {
+                None => {}
+                Some(_) if strategy == ConflictStrategy::Skip => {
+                    notes.push(format!("skipped '{}' (already exists)", def.name));
+                    continue;
+                }
+                Some(existing) if strategy == ConflictStrategy::Overwrite => {
+                    defs.remove(existing);
+                    notes.push(format!("overwrote '{}'", def.name));
+                }
+                Some(_) => {
+                    let renamed = unique_name(&def.name, &defs)?;
+                    notes.push(format!("renamed '{}' to '{}'", def.name, renamed));
+                    def.name = renamed;
+                }
+            }
+            imported.push(def.name.clone());
+            defs.push(def);
+        }
+
+        if !imported.is_empty() {
+            ComposedToolStore::save(&self.store_path, &defs)?;
+        }
+        info!(count = imported.len(), "Imported composed tools");
+
+        let mut message = format!("Imported {} composed tool(s)", imported.len());
+        if !imported.is_empty() {
+            message.push_str(&format!(": {}", imported.join(", ")));
+        }
+        if !notes.is_empty() {
+            message.push_str(&format!(" ({})", notes.join("; ")));
+        }
+        message.push('.');
+        Ok(ToolOutput::user_visible(message))
+    }
+
+    fn handle_run(&self, args: &Value, ctx: &ToolContext) -> Result<ToolOutput> {
         let name = args
             .get("name")
             .and_then(|v| v.as_str())
@@ -426,23 +884,10 @@ impl CreateToolTool {
             .find(|d| d.name == name)
             .ok_or_else(|| ZeptoError::Tool(format!("No composed tool named '{}'", name)))?;
 
-        // Extract params from the args (everything except action/name)
-        let string_args: HashMap<String, String> = if let Some(obj) = args.as_object() {
-            obj.iter()
-                .filter(|(k, _)| *k != "action" && *k != "name")
-                .map(|(k, v)| {
-                    let val = match v {
-                        Value::String(s) => s.clone(),
-                        other => other.to_string(),
-                    };
-                    (k.clone(), val)
-                })
-                .collect()
-        } else {
-            HashMap::new()
-        };
+        // Params are everything except action/name
+        let string_args = prepare_args(def, args, &["action", "name"])?;
 
-        let instructions = interpolate_action(&def.action, &string_args);
+        let instructions = render_action(&def.action, string_args, ctx, Local::now());
 
         debug!(tool = %name, "Running composed tool via create_tool");
 
@@ -452,6 +897,14 @@ impl CreateToolTool {
     }
 }
 
+/// First free `name_N` (N >= 2) that fits the name length limit.
+fn unique_name(name: &str, defs: &[ComposedToolDef]) -> Result<String> {
+    (2..1000)
+        .map(|n| format!("{}_{}", name, n))
+        .find(|candidate| candidate.len() <= 64 && !defs.iter().any(|d| d.name == *candidate))
+        .ok_or_else(|| ZeptoError::Tool(format!("No free name to rename '{}' to", name)))
+}
+
 #[async_trait]
 impl Tool for CreateToolTool {
     fn name(&self) -> &str {
@@ -459,13 +912,13 @@ impl Tool for CreateToolTool {
     }
 
     fn description(&self) -> &str {
-        "Create, list, delete, or run composed tools defined in natural language. \
+        "Create, list, delete, run, export or import composed tools defined in natural language. \
          Composed tools let you define new capabilities by describing what they do — \
-         no code needed. Actions: create, list, delete, run."
+         no code needed. Actions: create, list, delete, run, export, import."
     }
 
     fn compact_description(&self) -> &str {
-        "Manage composed tools (create/list/delete/run)"
+        "Manage composed tools (create/list/delete/run/export/import)"
     }
 
     fn category(&self) -> ToolCategory {
@@ -478,8 +931,8 @@ impl Tool for CreateToolTool {
             "properties": {
                 "action": {
                     "type": "string",
-                    "description": "Action to perform: create, list, delete, run",
-                    "enum": ["create", "list", "delete", "run"]
+                    "description": "Action to perform: create, list, delete, run, export, import",
+                    "enum": ["create", "list", "delete", "run", "export", "import"]
                 },
                 "name": {
                     "type": "string",
@@ -491,18 +944,36 @@ impl Tool for CreateToolTool {
                 },
                 "action_template": {
                     "type": "string",
-                    "description": "Natural language action with {{param}} placeholders (for create). This is what the agent will execute when the tool is called."
+                    "description": "Natural language action with {{param}} placeholders (for create). This is what the agent will execute when the tool is called. {{_date}}, {{_user}} and {{_workspace}} are filled in automatically."
                 },
                 "parameters": {
                     "type": "object",
-                    "description": "Parameter definitions: {\"param_name\": \"type\"} or {\"param_name\": {\"param_type\": \"string\", \"description\": \"...\", \"required\": true}} (for create)."
+                    "description": "Parameter definitions: {\"param_name\": \"type\"} or {\"param_name\": {\"param_type\": \"string\", \"description\": \"...\", \"required\": true, \"escape\": \"none|shell|url|json\"}} (for create). Use escape when the action puts the value in a shell command, URL or JSON string."
+                },
+                "names": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Tools to export (for export). Defaults to all."
+                },
+                "path": {
+                    "type": "string",
+                    "description": "Workspace file to write the bundle to (for export) or read it from (for import). Without it, export returns the bundle JSON."
+                },
+                "bundle": {
+                    "type": "object",
+                    "description": "Bundle to import (for import), as returned by export."
+                },
+                "on_conflict": {
+                    "type": "string",
+                    "description": "What import does with names that already exist: skip (default), rename or overwrite.",
+                    "enum": ["skip", "rename", "overwrite"]
                 }
             },
             "required": ["action"]
         })
     }
 
-    async fn execute(&self, args: Value, _ctx: &ToolContext) -> Result<ToolOutput> {
+    async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<ToolOutput> {
         let action = args
             .get("action")
             .and_then(|v| v.as_str())
@@ -513,9 +984,11 @@ impl Tool for CreateToolTool {
             "create" => self.handle_create(&args),
             "list" => self.handle_list(),
             "delete" => self.handle_delete(&args),
-            "run" => self.handle_run(&args),
+            "run" => self.handle_run(&args, ctx),
+            "export" => self.handle_export(&args, ctx),
+            "import" => self.handle_import(&args, ctx),
             other => Err(ZeptoError::Tool(format!(
-                "Unknown action '{}'. Use: create, list, delete, run",
+                "Unknown action '{}'. Use: create, list, delete, run, export, import",
                 other
             ))),
         }
@@ -593,6 +1066,52 @@ mod tests {
         assert!(!p.required);
     }
 
+    #[test]
+    fn test_escape_shell_quotes_metacharacters() {
+        let tool = ComposedTool::new(ComposedToolDef {
+            name: "grep_logs".into(),
+            description: "d".into(),
+            action: "Run: grep {{pattern}} /var/log/app.log".into(),
+            parameters: serde_json::from_value(json!({
+                "pattern": {"escape": "shell"}
+            }))
+            .unwrap(),
+            created_at: String::new(),
+        });
+        let args = prepare_args(&tool.def, &json!({"pattern": "x'; rm -rf / #"}), &[]).unwrap();
+        assert_eq!(args["pattern"], r"'x'\''; rm -rf / #'");
+        assert_eq!(
+            interpolate_action(&tool.def.action, &args),
+            r"Run: grep 'x'\''; rm -rf / #' /var/log/app.log"
+        );
+        assert_eq!(
+            EscapeMode::Shell.apply("$(id) `id` a b"),
+            "'$(id) `id` a b'"
+        );
+        assert_eq!(EscapeMode::Shell.apply(""), "''");
+    }
+
+    #[test]
+    fn test_escape_url_and_json_modes() {
+        assert_eq!(
+            EscapeMode::Url.apply("a b&c=d/é?#"),
+            "a%20b%26c%3Dd%2F%C3%A9%3F%23"
+        );
+        assert_eq!(EscapeMode::Url.apply("safe-_.~"), "safe-_.~");
+        assert_eq!(
+            EscapeMode::Json.apply("say \"hi\"\n\\"),
+            r#"say \"hi\"\n\\"#
+        );
+        assert_eq!(EscapeMode::None.apply("a b'\""), "a b'\"");
+
+        // Unset escape stays `none` and is omitted when serialized.
+        let p: ParamDef = serde_json::from_str(r#"{"escape":"url"}"#).unwrap();
+        assert_eq!(p.escape, EscapeMode::Url);
+        let plain: ParamDef = serde_json::from_str("{}").unwrap();
+        assert_eq!(plain.escape, EscapeMode::None);
+        assert!(!serde_json::to_string(&plain).unwrap().contains("escape"));
+    }
+
     // === ComposedToolDef serde ===
 
     #[test]
@@ -607,6 +1126,7 @@ mod tests {
                     param_type: "string".into(),
                     description: "The thing".into(),
                     required: true,
+                    escape: EscapeMode::None,
                 },
             )]),
             created_at: "2026-01-01T00:00:00Z".into(),
@@ -639,6 +1159,7 @@ mod tests {
                     param_type: "string".into(),
                     description: "Person name".into(),
                     required: true,
+                    escape: EscapeMode::None,
                 },
             )]),
             created_at: "2026-01-01T00:00:00Z".into(),
@@ -692,6 +1213,64 @@ mod tests {
         assert_eq!(result, "Just do it");
     }
 
+    #[test]
+    fn test_context_placeholders_substituted() {
+        use chrono::TimeZone;
+
+        let now = Local.with_ymd_and_hms(2026, 3, 14, 9, 30, 0).unwrap();
+        let ctx = ToolContext::new()
+            .with_sender("alice")
+            .with_workspace("/srv/ws");
+        let args = HashMap::from([
+            ("topic".to_string(), "Rust".to_string()),
+            ("_date".to_string(), "spoofed".to_string()),
+        ]);
+
+        let result = render_action(
+            "On {{_date}}, brief {{_user}} on {{topic}} using notes in {{_workspace}}",
+            args,
+            &ctx,
+            now,
+        );
+        assert_eq!(
+            result,
+            "On 2026-03-14, brief alice on Rust using notes in /srv/ws"
+        );
+
+        // Missing context values render empty.
+        let result = render_action("[{{_user}}]", HashMap::new(), &ToolContext::new(), now);
+        assert_eq!(result, "[]");
+    }
+
+    #[test]
+    fn test_context_placeholder_prefix_is_reserved() {
+        let mut reserved = ComposedToolDef {
+            name: "t".into(),
+            description: "d".into(),
+            action: "Plan {{_date}}".into(),
+            parameters: HashMap::new(),
+            created_at: String::new(),
+        };
+        assert!(validate_def(&reserved).is_ok());
+
+        reserved.action = "Plan {{_weather}}".into();
+        let err = validate_def(&reserved).unwrap_err().to_string();
+        assert!(err.contains("Unknown context placeholder '{{_weather}}'"));
+
+        reserved.action = "Plan {{_date}}".into();
+        reserved.parameters.insert(
+            "_date".into(),
+            ParamDef {
+                param_type: "string".into(),
+                description: String::new(),
+                required: true,
+                escape: EscapeMode::None,
+            },
+        );
+        let err = validate_def(&reserved).unwrap_err().to_string();
+        assert!(err.contains("reserved '_' prefix"));
+    }
+
     // === ComposedTool ===
 
     #[test]
@@ -745,6 +1324,7 @@ mod tests {
                     param_type: "string".into(),
                     description: "Search query".into(),
                     required: true,
+                    escape: EscapeMode::None,
                 },
             )]),
             created_at: String::new(),
@@ -757,6 +1337,40 @@ mod tests {
         assert!(req.iter().any(|v| v.as_str() == Some("query")));
     }
 
+    #[test]
+    fn test_composed_tool_parameters_are_stable() {
+        let param = |required| ParamDef {
+            param_type: "string".into(),
+            description: String::new(),
+            required,
+            escape: EscapeMode::None,
+        };
+        let build = || {
+            ComposedTool::new(ComposedToolDef {
+                name: "t".into(),
+                description: "d".into(),
+                action: "a".into(),
+                parameters: ["zeta", "alpha", "mid", "beta", "omega", "gamma"]
+                    .into_iter()
+                    .map(|name| (name.to_string(), param(name != "mid")))
+                    .collect(),
+                created_at: String::new(),
+            })
+            .parameters()
+        };
+        let first = build();
+        assert_eq!(
+            first["required"],
+            json!(["alpha", "beta", "gamma", "omega", "zeta"])
+        );
+        for _ in 0..5 {
+            assert_eq!(
+                serde_json::to_string(&build()).unwrap(),
+                serde_json::to_string(&first).unwrap()
+            );
+        }
+    }
+
     #[tokio::test]
     async fn test_composed_tool_execute() {
         let tool = ComposedTool::new(ComposedToolDef {
@@ -770,6 +1384,7 @@ mod tests {
                         param_type: "string".into(),
                         description: "".into(),
                         required: true,
+                        escape: EscapeMode::None,
                     },
                 ),
                 (
@@ -778,6 +1393,7 @@ mod tests {
                         param_type: "string".into(),
                         description: "".into(),
                         required: true,
+                        escape: EscapeMode::None,
                     },
                 ),
             ]),
@@ -806,6 +1422,73 @@ mod tests {
         assert!(result.for_llm.contains("Generate a daily briefing"));
     }
 
+    fn typed_tool() -> ComposedTool {
+        let param = |param_type: &str, required: bool| ParamDef {
+            param_type: param_type.into(),
+            description: String::new(),
+            required,
+            escape: EscapeMode::None,
+        };
+        ComposedTool::new(ComposedToolDef {
+            name: "top_posts".into(),
+            description: "Top posts".into(),
+            action: "Show the top {{count}} posts from {{site}} (nsfw: {{nsfw}})".into(),
+            parameters: HashMap::from([
+                ("count".into(), param("integer", true)),
+                ("site".into(), param("string", true)),
+                ("nsfw".into(), param("boolean", false)),
+            ]),
+            created_at: String::new(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_composed_tool_missing_required_param() {
+        let err = typed_tool()
+            .execute(json!({"count": 3}), &test_ctx())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains(
+            "Invalid arguments for composed tool 'top_posts': missing required parameter 'site'"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_composed_tool_type_mismatch_rejected() {
+        let err = typed_tool()
+            .execute(
+                json!({"count": "several", "site": "hn", "nsfw": "maybe"}),
+                &test_ctx(),
+            )
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains(r#"parameter 'count' expected integer, got "several""#));
+        assert!(err.contains(r#"parameter 'nsfw' expected boolean, got "maybe""#));
+    }
+
+    #[tokio::test]
+    async fn test_composed_tool_coerces_values() {
+        let result = typed_tool()
+            .execute(json!({"count": "5", "site": "hn"}), &test_ctx())
+            .await
+            .unwrap();
+        // Optional params may be omitted and leave no placeholder behind.
+        assert!(result.for_llm.contains("Show the top 5 posts from hn"));
+        assert!(!result.for_llm.contains("{{"));
+
+        let result = typed_tool()
+            .execute(
+                json!({"count": 10.0, "site": "hn", "nsfw": "TRUE"}),
+                &test_ctx(),
+            )
+            .await
+            .unwrap();
+        assert!(result
+            .for_llm
+            .contains("Show the top 10 posts from hn (nsfw: true)"));
+    }
+
     // === CreateToolTool ===
 
     #[test]
@@ -1123,4 +1806,165 @@ mod tests {
         assert!(!defs[0].parameters["depth"].required);
         let _ = std::fs::remove_file(&path);
     }
+
+    // === Export / import ===
+
+    fn def(name: &str, action: &str, params: &[&str]) -> ComposedToolDef {
+        ComposedToolDef {
+            name: name.into(),
+            description: format!("{} tool", name),
+            action: action.into(),
+            parameters: params
+                .iter()
+                .map(|p| {
+                    (
+                        p.to_string(),
+                        ParamDef {
+                            param_type: "string".into(),
+                            description: String::new(),
+                            required: true,
+                            escape: EscapeMode::None,
+                        },
+                    )
+                })
+                .collect(),
+            created_at: String::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_export_produces_valid_bundle() {
+        let path = temp_store_path();
+        ComposedToolStore::save(
+            &path,
+            &[
+                def("greet", "Say hello to {{name}}", &["name"]),
+                def("other", "Do other", &[]),
+            ],
+        )
+        .unwrap();
+        let tool = CreateToolTool::with_path(path.clone());
+
+        let result = tool
+            .execute(json!({"action": "export", "names": ["greet"]}), &test_ctx())
+            .await
+            .unwrap();
+        let bundle: ComposedToolBundle = serde_json::from_str(&result.for_llm).unwrap();
+        assert_eq!(bundle.format, BUNDLE_FORMAT);
+        assert_eq!(bundle.version, BUNDLE_VERSION);
+        assert_eq!(bundle.tools.len(), 1);
+        assert_eq!(bundle.tools[0].name, "greet");
+        assert!(bundle.tools.iter().all(|d| validate_def(d).is_ok()));
+
+        let missing = tool
+            .execute(json!({"action": "export", "names": ["ghost"]}), &test_ctx())
+            .await;
+        assert!(missing.unwrap_err().to_string().contains("ghost"));
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_import_conflict_strategies() {
+        let path = temp_store_path();
+        let tool = CreateToolTool::with_path(path.clone());
+        let bundle = json!({
+            "format": BUNDLE_FORMAT,
+            "version": BUNDLE_VERSION,
+            "tools": [def("greet", "Wave at {{name}}", &["name"]), def("fresh", "Do fresh", &[])]
+        });
+        let reset = || {
+            ComposedToolStore::save(&path, &[def("greet", "Say hello to {{name}}", &["name"])])
+                .unwrap()
+        };
+        let actions = |defs: &[ComposedToolDef]| -> Vec<(String, String)> {
+            defs.iter()
+                .map(|d| (d.name.clone(), d.action.clone()))
+                .collect()
+        };
+
+        reset();
+        let result = tool
+            .execute(json!({"action": "import", "bundle": bundle}), &test_ctx())
+            .await
+            .unwrap();
+        assert!(result.for_llm.contains("skipped 'greet'"));
+        let defs = ComposedToolStore::load(&path).unwrap();
+        assert_eq!(
+            actions(&defs),
+            vec![
+                ("greet".into(), "Say hello to {{name}}".into()),
+                ("fresh".into(), "Do fresh".into())
+            ]
+        );
+
+        reset();
+        tool.execute(
+            json!({"action": "import", "bundle": bundle, "on_conflict": "rename"}),
+            &test_ctx(),
+        )
+        .await
+        .unwrap();
+        let defs = ComposedToolStore::load(&path).unwrap();
+        assert_eq!(
+            actions(&defs),
+            vec![
+                ("greet".into(), "Say hello to {{name}}".into()),
+                ("greet_2".into(), "Wave at {{name}}".into()),
+                ("fresh".into(), "Do fresh".into())
+            ]
+        );
+
+        reset();
+        tool.execute(
+            json!({"action": "import", "bundle": bundle, "on_conflict": "overwrite"}),
+            &test_ctx(),
+        )
+        .await
+        .unwrap();
+        let defs = ComposedToolStore::load(&path).unwrap();
+        assert_eq!(
+            actions(&defs),
+            vec![
+                ("greet".into(), "Wave at {{name}}".into()),
+                ("fresh".into(), "Do fresh".into())
+            ]
+        );
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_import_validates_like_create() {
+        let path = temp_store_path();
+        let tool = CreateToolTool::with_path(path.clone());
+
+        let undeclared = json!({
+            "format": BUNDLE_FORMAT,
+            "version": BUNDLE_VERSION,
+            "tools": [def("ok", "Fine", &[]), def("bad", "Fetch {{url}}", &[])]
+        });
+        let err = tool
+            .execute(
+                json!({"action": "import", "bundle": undeclared}),
+                &test_ctx(),
+            )
+            .await
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("undeclared parameter(s) in its action: url"));
+        // Nothing is imported when any tool is invalid.
+        assert!(ComposedToolStore::load(&path).unwrap().is_empty());
+
+        let bad_name = json!({
+            "format": BUNDLE_FORMAT,
+            "version": BUNDLE_VERSION,
+            "tools": [def("bad name!", "x", &[])]
+        });
+        let err = tool
+            .execute(json!({"action": "import", "bundle": bad_name}), &test_ctx())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("alphanumeric"));
+        let _ = std::fs::remove_file(&path);
+    }
 }
@@ -3,24 +3,54 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use chrono_tz::Tz;
 use serde_json::{json, Value};
 
 use crate::cron::{
     is_valid_cron_expr, parse_at_datetime_ms, CronPayload, CronSchedule, CronService,
 };
 use crate::error::{Result, ZeptoError};
+use crate::memory::preferences::PreferencesStore;
 
 use super::{Tool, ToolCategory, ToolContext, ToolOutput};
 
 /// Tool for creating and managing scheduled jobs.
 pub struct CronTool {
     cron: Arc<CronService>,
+    timezone: Tz,
+    preferences: Option<Arc<PreferencesStore>>,
 }
 
 impl CronTool {
-    /// Create a new cron tool.
+    /// Create a new cron tool. Schedules are read in UTC until a timezone
+    /// is configured.
     pub fn new(cron: Arc<CronService>) -> Self {
-        Self { cron }
+        Self {
+            cron,
+            timezone: Tz::UTC,
+            preferences: None,
+        }
+    }
+
+    /// Timezone for cron expressions and naive `at` times (builder pattern).
+    pub fn with_timezone(mut self, timezone: Tz) -> Self {
+        self.timezone = timezone;
+        self
+    }
+
+    /// Prefer each user's `timezone` preference from `store` over the
+    /// configured timezone (builder pattern).
+    pub fn with_preferences(mut self, store: Arc<PreferencesStore>) -> Self {
+        self.preferences = Some(store);
+        self
+    }
+
+    /// Timezone for the user making the call.
+    fn timezone_for(&self, ctx: &ToolContext) -> Tz {
+        self.preferences
+            .as_ref()
+            .and_then(|store| store.timezone_for(ctx))
+            .unwrap_or(self.timezone)
     }
 }
 
@@ -65,11 +95,11 @@ impl Tool for CronTool {
                 },
                 "cron_expr": {
                     "type": "string",
-                    "description": "Cron expression (UTC)"
+                    "description": "Cron expression, evaluated in the user's timezone"
                 },
                 "at": {
                     "type": "string",
-                    "description": "One-shot ISO datetime"
+                    "description": "One-shot ISO datetime; without an offset it is read in the user's timezone"
                 },
                 "job_id": {
                     "type": "string",
@@ -167,6 +197,7 @@ impl CronTool {
             }
         }
 
+        let timezone = self.timezone_for(ctx);
         let (schedule, delete_after_run) = if let Some(seconds) = every_seconds {
             if seconds <= 0 {
                 return Err(ZeptoError::Tool(
@@ -182,6 +213,7 @@ impl CronTool {
         } else if let Some(expr) = cron_expr {
             let schedule = CronSchedule::Cron {
                 expr: expr.to_string(),
+                tz: Some(timezone.name().to_string()),
             };
             if !is_valid_cron_expr(expr) {
                 return Err(ZeptoError::Tool(format!(
@@ -191,7 +223,7 @@ impl CronTool {
             }
             (schedule, false)
         } else {
-            let at_ms = parse_at_datetime_ms(at.unwrap())?;
+            let at_ms = parse_at_datetime_ms(at.unwrap(), timezone)?;
             (CronSchedule::At { at_ms }, true)
         };
 
@@ -242,7 +274,8 @@ impl CronTool {
             let schedule = match &job.schedule {
                 CronSchedule::At { at_ms } => format!("at({})", at_ms),
                 CronSchedule::Every { every_ms } => format!("every({}ms)", every_ms),
-                CronSchedule::Cron { expr } => format!("cron({})", expr),
+                CronSchedule::Cron { expr, tz: Some(tz) } => format!("cron({}, {})", expr, tz),
+                CronSchedule::Cron { expr, tz: None } => format!("cron({})", expr),
             };
             lines.push(format!(
                 "- {} [{}] {} -> {}:{}",
@@ -286,6 +286,7 @@ mod tests {
             workspace: Some(std::env::temp_dir().to_string_lossy().to_string()),
             channel: None,
             chat_id: None,
+            sender_id: None,
             is_batch: false,
         }
     }
@@ -2,8 +2,11 @@
 //!
 //! The `DelegateTool` creates a temporary `AgentLoop` with a role-specific
 //! system prompt and tool whitelist, runs it to completion, and returns
-//! the result to the calling (lead) agent.
+//! the result to the calling (lead) agent. The `pipeline` action runs a
+//! pipeline hand's stages through the same sub-agent machinery.
 
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Arc;
 
 use async_trait::async_trait;
@@ -16,7 +19,11 @@ use futures::future::join_all;
 use crate::agent::{AgentLoop, ContextBuilder, SwarmScratchpad};
 use crate::bus::{InboundMessage, MessageBus};
 use crate::config::Config;
-use crate::error::{Result, ZeptoError};
+use crate::error::{Result, ToolErrorKind, ZeptoError};
+use crate::hands::pipeline::{
+    resolve_stages, run_pipeline, PipelineStage, StageOutput, StageRunner,
+};
+use crate::hands::resolve_hand;
 use crate::providers::{ChatOptions, LLMProvider, LLMResponse, ToolDefinition};
 use crate::runtime::NativeRuntime;
 use crate::session::{Message, SessionManager};
@@ -39,6 +46,8 @@ use super::{Tool, ToolCategory, ToolContext, ToolOutput};
 /// - `run` (default) — delegates a single task to one sub-agent.
 /// - `aggregate` — fans out multiple tasks (each with its own role) and merges
 ///   the results using a configurable merge strategy.
+/// - `pipeline` — runs a pipeline hand's stages in order, each as a sub-agent
+///   scoped to that stage's hand.
 ///
 /// Concurrency is bounded by a `Semaphore` whose capacity comes from
 /// `config.swarm.max_concurrent`.
@@ -50,6 +59,8 @@ pub struct DelegateTool {
     semaphore: Arc<Semaphore>,
     /// Shared scratchpad for passing context between sub-agents in a swarm session.
     scratchpad: SwarmScratchpad,
+    /// Directory searched for user-defined hands (`pipeline` action).
+    hands_dir: PathBuf,
 }
 
 impl DelegateTool {
@@ -74,6 +85,7 @@ impl DelegateTool {
             bus,
             semaphore,
             scratchpad: SwarmScratchpad::new(),
+            hands_dir: Config::dir().join("hands"),
         }
     }
 
@@ -93,9 +105,16 @@ impl DelegateTool {
             bus,
             semaphore,
             scratchpad: SwarmScratchpad::new(),
+            hands_dir: Config::dir().join("hands"),
         }
     }
 
+    /// Directory searched for user-defined hands.
+    pub fn with_hands_dir(mut self, dir: PathBuf) -> Self {
+        self.hands_dir = dir;
+        self
+    }
+
     /// Return a reference to the shared swarm scratchpad.
     ///
     /// Primarily useful in tests to inspect scratchpad state after delegating.
@@ -189,6 +208,43 @@ impl DelegateTool {
 
         info!(role = %role, task_len = task.len(), "Delegating task to sub-agent");
 
+        let context_builder = ContextBuilder::new().with_system_prompt(&system_prompt);
+        match self
+            .run_sub_agent(
+                self.config.clone(),
+                context_builder,
+                allowed_tool_names.as_deref(),
+                task,
+            )
+            .await
+        {
+            Ok((result, _)) => {
+                info!(role = %role, result_len = result.len(), "Sub-agent completed");
+                Ok(result)
+            }
+            Err(e) => {
+                warn!(role = %role, error = %e, "Sub-agent failed");
+                Err(ZeptoError::Tool(format!(
+                    "Sub-agent '{}' failed: {}",
+                    role, e
+                )))
+            }
+        }
+    }
+
+    /// Run `task` on a fresh sub-agent built from `config` and
+    /// `context_builder`, returning its reply and the number of model calls
+    /// it made.
+    ///
+    /// Acquires a semaphore permit first, so concurrent sub-agents are bounded
+    /// by `config.swarm.max_concurrent`.
+    async fn run_sub_agent(
+        &self,
+        config: Config,
+        context_builder: ContextBuilder,
+        tools: Option<&[String]>,
+        task: &str,
+    ) -> Result<(String, u32)> {
         // Acquire semaphore permit before creating the sub-agent.
         // The permit is held for the duration of this function and released
         // automatically when `_permit` drops at the end of the scope.
@@ -198,25 +254,18 @@ impl DelegateTool {
             .await
             .map_err(|_| ZeptoError::Tool("Swarm semaphore closed".into()))?;
 
-        // Create sub-agent with role-specific context
         let session_manager = SessionManager::new_memory();
         let sub_bus = Arc::new(MessageBus::new());
-        let context_builder = ContextBuilder::new().with_system_prompt(&system_prompt);
-
-        let sub_agent = AgentLoop::with_context_builder(
-            self.config.clone(),
-            session_manager,
-            sub_bus,
-            context_builder,
-        );
+        let sub_agent =
+            AgentLoop::with_context_builder(config, session_manager, sub_bus, context_builder);
 
         // Set the same LLM provider via the ProviderRef wrapper
-        sub_agent
-            .set_provider(Box::new(ProviderRef(Arc::clone(&self.provider))))
-            .await;
+        let provider = ProviderRef::new(Arc::clone(&self.provider));
+        let calls = Arc::clone(&provider.calls);
+        sub_agent.set_provider(Box::new(provider)).await;
 
         // Register tools (filtered by whitelist)
-        let sub_tools = self.create_sub_agent_tools(allowed_tool_names.as_deref());
+        let sub_tools = self.create_sub_agent_tools(tools);
         for tool in sub_tools {
             sub_agent.register_tool(tool).await;
         }
@@ -235,19 +284,40 @@ impl DelegateTool {
         );
 
         // Run the sub-agent to completion
-        match sub_agent.process_message(&inbound).await {
-            Ok(result) => {
-                info!(role = %role, result_len = result.len(), "Sub-agent completed");
-                Ok(result)
-            }
-            Err(e) => {
-                warn!(role = %role, error = %e, "Sub-agent failed");
-                Err(ZeptoError::Tool(format!(
-                    "Sub-agent '{}' failed: {}",
-                    role, e
-                )))
-            }
+        let result = sub_agent.process_message(&inbound).await?;
+        Ok((result, calls.load(Ordering::Relaxed)))
+    }
+}
+
+#[async_trait]
+impl StageRunner for DelegateTool {
+    async fn run_stage(
+        &self,
+        stage: &PipelineStage,
+        input: &str,
+        max_iterations: u32,
+    ) -> Result<StageOutput> {
+        let manifest = &stage.hand.manifest;
+        // The first model call is not a tool iteration.
+        let mut config = self.config.clone();
+        config.agents.defaults.max_tool_iterations = max_iterations.saturating_sub(1);
+
+        let mut context_builder = ContextBuilder::new().with_system_prompt(&manifest.system_prompt);
+        if !stage.hand.skill_md.trim().is_empty() {
+            context_builder = context_builder.with_skills(&stage.hand.skill_md);
         }
+
+        info!(hand = %manifest.name, budget = max_iterations, "Running pipeline stage");
+        let (text, iterations) = self
+            .run_sub_agent(
+                config,
+                context_builder,
+                Some(&manifest.required_tools),
+                input,
+            )
+            .await?;
+        info!(hand = %manifest.name, iterations, "Pipeline stage completed");
+        Ok(StageOutput { text, iterations })
     }
 }
 
@@ -269,7 +339,9 @@ impl Tool for DelegateTool {
          (2) Sequential — agents run one after another, each can build on \
          prior agents' results (slower but coordinated). \
          If the user already specified a preference (e.g. 'run in parallel', \
-         'run them together', 'one by one'), respect that directly."
+         'run them together', 'one by one'), respect that directly. \
+         Use action='pipeline' with 'hand' and 'task' to run a pipeline hand, \
+         whose stages each hand their output to the next."
     }
 
     fn compact_description(&self) -> &str {
@@ -286,9 +358,14 @@ impl Tool for DelegateTool {
             "properties": {
                 "action": {
                     "type": "string",
-                    "enum": ["run", "aggregate"],
+                    "enum": ["run", "aggregate", "pipeline"],
                     "description": "Action to perform. 'run' (default) delegates a single task. \
-                                    'aggregate' fans out multiple tasks and merges results."
+                                    'aggregate' fans out multiple tasks and merges results. \
+                                    'pipeline' runs the stages of a pipeline hand in order."
+                },
+                "hand": {
+                    "type": "string",
+                    "description": "For action='pipeline': name of the pipeline hand to run."
                 },
                 "role": {
                     "type": "string",
@@ -297,7 +374,8 @@ impl Tool for DelegateTool {
                 },
                 "task": {
                     "type": "string",
-                    "description": "The task for the sub-agent to complete. Required for action='run'."
+                    "description": "The task for the sub-agent to complete. \
+                                    Required for action='run' and action='pipeline'."
                 },
                 "tools": {
                     "type": "array",
@@ -486,8 +564,50 @@ impl Tool for DelegateTool {
                 Ok(ToolOutput::user_visible(format_results(&results, merge)))
             }
 
+            "pipeline" => {
+                let name = args.get("hand").and_then(Value::as_str).ok_or_else(|| {
+                    ZeptoError::tool(
+                        ToolErrorKind::InvalidArgument,
+                        "'pipeline' requires a 'hand' argument",
+                    )
+                })?;
+                let task = args.get("task").and_then(Value::as_str).ok_or_else(|| {
+                    ZeptoError::tool(
+                        ToolErrorKind::InvalidArgument,
+                        "Missing required 'task' argument",
+                    )
+                })?;
+                let hand = resolve_hand(name, &self.hands_dir)?.ok_or_else(|| {
+                    ZeptoError::tool(ToolErrorKind::NotFound, format!("Unknown hand '{}'", name))
+                })?;
+                if !hand.manifest.is_pipeline() {
+                    return Err(ZeptoError::tool(
+                        ToolErrorKind::InvalidArgument,
+                        format!(
+                            "Hand '{}' is not a pipeline; use action='run' or `use_hand` instead",
+                            hand.manifest.name
+                        ),
+                    ));
+                }
+                let stages = resolve_stages(&hand.manifest, &self.hands_dir)?;
+                let budget = hand
+                    .manifest
+                    .max_iterations
+                    .unwrap_or(self.config.agents.defaults.max_tool_iterations);
+
+                info!(hand = %hand.manifest.name, stages = stages.len(), budget, "Running pipeline");
+                let outcome = run_pipeline(&stages, task, budget, self).await;
+                if outcome.stages.is_empty() {
+                    return Err(ZeptoError::Tool(outcome.render()));
+                }
+                for stage in &outcome.stages {
+                    self.scratchpad.write(&stage.hand, &stage.output).await;
+                }
+                Ok(ToolOutput::user_visible(outcome.render()))
+            }
+
             other => Err(ZeptoError::Tool(format!(
-                "Unknown action '{}'. Valid actions are: run, aggregate",
+                "Unknown action '{}'. Valid actions are: run, aggregate, pipeline",
                 other
             ))),
         }
@@ -525,16 +645,33 @@ fn format_results(results: &[(String, String)], strategy: &str) -> String {
 ///
 /// Since `set_provider()` takes `Box<dyn LLMProvider>`, we need this thin wrapper
 /// to share the same provider instance via Arc without cloning the provider itself.
-struct ProviderRef(Arc<dyn LLMProvider>);
+/// It also counts model calls, which pipeline stages charge against their budget.
+struct ProviderRef {
+    inner: Arc<dyn LLMProvider>,
+    calls: Arc<AtomicU32>,
+}
+
+impl ProviderRef {
+    fn new(inner: Arc<dyn LLMProvider>) -> Self {
+        Self {
+            inner,
+            calls: Arc::new(AtomicU32::new(0)),
+        }
+    }
+}
 
 #[async_trait]
 impl LLMProvider for ProviderRef {
     fn name(&self) -> &str {
-        self.0.name()
+        self.inner.name()
     }
 
     fn default_model(&self) -> &str {
-        self.0.default_model()
+        self.inner.default_model()
+    }
+
+    fn supports_streaming(&self) -> bool {
+        self.inner.supports_streaming()
     }
 
     async fn chat(
@@ -544,7 +681,8 @@ impl LLMProvider for ProviderRef {
         model: Option<&str>,
         options: ChatOptions,
     ) -> Result<LLMResponse> {
-        self.0.chat(messages, tools, model, options).await
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        self.inner.chat(messages, tools, model, options).await
     }
 
     async fn chat_stream(
@@ -554,7 +692,10 @@ impl LLMProvider for ProviderRef {
         model: Option<&str>,
         options: ChatOptions,
     ) -> crate::error::Result<tokio::sync::mpsc::Receiver<crate::providers::StreamEvent>> {
-        self.0.chat_stream(messages, tools, model, options).await
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        self.inner
+            .chat_stream(messages, tools, model, options)
+            .await
     }
 }
 
@@ -1074,6 +1215,97 @@ mod tests {
         );
     }
 
+    // -------------------------------------------------------------------------
+    // Pipeline tests
+    // -------------------------------------------------------------------------
+
+    /// Replies "stage N" and records each call's tool names and last user message.
+    #[derive(Default)]
+    struct StageProvider {
+        seen: std::sync::Mutex<Vec<(Vec<String>, String)>>,
+    }
+
+    #[async_trait]
+    impl LLMProvider for StageProvider {
+        fn name(&self) -> &str {
+            "stage"
+        }
+
+        fn default_model(&self) -> &str {
+            "stage"
+        }
+
+        async fn chat(
+            &self,
+            messages: Vec<Message>,
+            tools: Vec<ToolDefinition>,
+            _model: Option<&str>,
+            _options: ChatOptions,
+        ) -> Result<LLMResponse> {
+            let input = messages
+                .iter()
+                .rev()
+                .find(|m| m.role == crate::session::Role::User)
+                .map(|m| m.content.clone())
+                .unwrap_or_default();
+            let mut names: Vec<String> = tools.into_iter().map(|t| t.name).collect();
+            names.sort();
+            let mut seen = self.seen.lock().unwrap();
+            seen.push((names, input));
+            Ok(LLMResponse::text(&format!("stage {}", seen.len())))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_action_chains_scoped_stages() {
+        let dir = tempfile::tempdir().unwrap();
+        let hand_dir = dir.path().join("report");
+        std::fs::create_dir_all(&hand_dir).unwrap();
+        std::fs::write(
+            hand_dir.join("HAND.toml"),
+            "name = \"report\"\ndescription = \"d\"\nsystem_prompt = \"p\"\n\n\
+             [[stages]]\nhand = \"researcher\"\n\n[[stages]]\nhand = \"coder\"\n",
+        )
+        .unwrap();
+
+        let mut config = Config::default();
+        config.swarm.enabled = true;
+        let provider = Arc::new(StageProvider::default());
+        let tool = DelegateTool::new(config, provider.clone(), Arc::new(MessageBus::new()))
+            .with_hands_dir(dir.path().into());
+        let ctx = ToolContext::new().with_channel("telegram", "chat-1");
+
+        let out = tool
+            .execute(
+                json!({"action": "pipeline", "hand": "report", "task": "Audit the parser"}),
+                &ctx,
+            )
+            .await
+            .unwrap();
+        assert_eq!(out.for_llm, "stage 2");
+
+        let seen = provider.seen.lock().unwrap();
+        assert_eq!(seen.len(), 2);
+        // Each stage only sees its own hand's tools.
+        assert!(seen[0].0.contains(&"web_fetch".to_string()));
+        assert!(!seen[0].0.contains(&"read_file".to_string()));
+        assert!(seen[1].0.contains(&"read_file".to_string()));
+        assert!(!seen[1].0.contains(&"web_fetch".to_string()));
+        // The second stage gets the first stage's output.
+        assert!(seen[1]
+            .1
+            .ends_with("## Output from the researcher stage\nstage 1"));
+
+        let err = tool
+            .execute(
+                json!({"action": "pipeline", "hand": "coder", "task": "t"}),
+                &ctx,
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not a pipeline"));
+    }
+
     /// The description must mention both execution modes.
     #[test]
     fn test_description_mentions_execution_modes() {
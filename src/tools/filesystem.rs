@@ -17,7 +17,7 @@ use unicode_normalization::UnicodeNormalization;
 use crate::error::{Result, ZeptoError};
 #[cfg(not(unix))]
 use crate::security::check_hardlink_write;
-use crate::security::{ensure_directory_chain_secure, revalidate_path, validate_path_in_workspace};
+use crate::security::{ensure_directory_chain_secure, revalidate_path};
 use crate::tools::diff::apply_unified_diff;
 
 use super::output::{truncate_tool_output, DEFAULT_MAX_BYTES, DEFAULT_MAX_LINES};
@@ -31,16 +31,9 @@ use super::{Tool, ToolCategory, ToolContext, ToolOutput};
 ///
 /// Returns `(resolved_path, workspace)` so callers can re-validate before I/O.
 fn resolve_path(path: &str, ctx: &ToolContext) -> Result<(String, String)> {
-    let workspace = ctx.workspace.as_ref().ok_or_else(|| {
-        ZeptoError::SecurityViolation(
-            "Workspace not configured; filesystem tools require a workspace for safety".to_string(),
-        )
-    })?;
-    let safe_path = validate_path_in_workspace(path, workspace)?;
-    Ok((
-        safe_path.as_path().to_string_lossy().to_string(),
-        workspace.clone(),
-    ))
+    let workspace = ctx.workspace_root()?.to_string_lossy().to_string();
+    let safe_path = ctx.resolve_in_workspace(path)?;
+    Ok((safe_path.as_path().to_string_lossy().to_string(), workspace))
 }
 
 #[cfg(unix)]
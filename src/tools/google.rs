@@ -1,5 +1,7 @@
 //! Google Workspace tool for Gmail and Calendar operations.
 
+use std::sync::Arc;
+
 use async_trait::async_trait;
 use reqwest013::Client;
 use serde_json::{json, Value};
@@ -12,18 +14,28 @@ use gog_gmail::get::{get_message, MessageFormat};
 use gog_gmail::search::{search_messages, SearchParams};
 use gog_gmail::send::{send_message, SendParams};
 
-use crate::error::{Result, ZeptoError};
+use crate::bus::{StructuredField, StructuredOutput};
+use crate::error::{Result, ToolErrorKind, ZeptoError};
+use crate::memory::preferences::{identity, PreferencesStore};
+use crate::utils::format::DisplayFormat;
 
+use super::partial::PartialResults;
+use super::risk::{RiskLevel, RiskPolicy};
 use super::{Tool, ToolCategory, ToolContext, ToolOutput};
 
-/// Actions that modify external state and require user confirmation.
-const DANGEROUS_ACTIONS: &[&str] = &["gmail_send", "gmail_reply", "calendar_create"];
+/// Gmail `batchModify` endpoint for the authenticated user.
+const GMAIL_BATCH_MODIFY_URL: &str =
+    "https://gmail.googleapis.com/gmail/v1/users/me/messages/batchModify";
+
+/// Maximum message IDs Gmail accepts in one `batchModify` request.
+const BATCH_MODIFY_LIMIT: usize = 1000;
 
 /// Google Workspace tool for Gmail and Google Calendar operations.
 ///
-/// Supports 7 actions:
+/// Supports 8 actions:
 /// - `gmail_search`: Search Gmail messages by query
 /// - `gmail_read`: Read a full Gmail message by ID
+/// - `gmail_modify`: Add/remove labels on messages (archive, mark read, star)
 /// - `gmail_send`: Send a new email
 /// - `gmail_reply`: Reply to an existing email thread
 /// - `calendar_list`: List upcoming calendar events
@@ -35,6 +47,8 @@ pub struct GoogleTool {
     access_token: String,
     default_calendar: String,
     max_search_results: u32,
+    display: DisplayFormat,
+    preferences: Option<Arc<PreferencesStore>>,
 }
 
 impl GoogleTool {
@@ -50,12 +64,55 @@ impl GoogleTool {
             access_token: access_token.to_string(),
             default_calendar: default_calendar.to_string(),
             max_search_results,
+            display: DisplayFormat::default(),
+            preferences: None,
+        }
+    }
+
+    /// Set the default timezone and locale for event times (builder pattern).
+    pub fn with_display_format(mut self, display: DisplayFormat) -> Self {
+        self.display = display;
+        self
+    }
+
+    /// Use per-user `timezone` and `language` preferences from `store`
+    /// when formatting event times (builder pattern).
+    pub fn with_preferences(mut self, store: Arc<PreferencesStore>) -> Self {
+        self.preferences = Some(store);
+        self
+    }
+
+    /// Display format for the user making the call.
+    fn display_for(&self, ctx: &ToolContext) -> DisplayFormat {
+        let mut display = self.display.clone();
+        let (Some(store), Some(channel), Some(sender)) = (
+            self.preferences.as_ref(),
+            ctx.channel.as_deref(),
+            ctx.sender_id.as_deref(),
+        ) else {
+            return display;
+        };
+        let Ok(prefs) = store.load(&identity(channel, sender)) else {
+            return display;
+        };
+        if let Some(tz) = prefs.timezone.and_then(|tz| tz.parse().ok()) {
+            display = display.with_timezone(tz);
+        }
+        if let Some(language) = prefs.language {
+            display = display.with_locale(&language);
         }
+        display
     }
 
     /// Return `true` when the given action modifies external state (send/create).
+    ///
+    /// Uses the built-in [`RiskPolicy`] classification of `google:<action>`.
     pub fn is_dangerous_action(action: &str) -> bool {
-        DANGEROUS_ACTIONS.contains(&action)
+        RiskPolicy::default().classify(
+            "google",
+            ToolCategory::Messaging,
+            &json!({ "action": action }),
+        ) != RiskLevel::Safe
     }
 }
 
@@ -66,7 +123,7 @@ impl Tool for GoogleTool {
     }
 
     fn description(&self) -> &str {
-        "Google Workspace tool for Gmail and Calendar operations. Actions: gmail_search, gmail_read, gmail_send, gmail_reply, calendar_list, calendar_create, calendar_freebusy."
+        "Google Workspace tool for Gmail and Calendar operations. Actions: gmail_search, gmail_read, gmail_modify, gmail_send, gmail_reply, calendar_list, calendar_create, calendar_freebusy."
     }
 
     fn compact_description(&self) -> &str {
@@ -77,6 +134,12 @@ impl Tool for GoogleTool {
         ToolCategory::Messaging
     }
 
+    fn is_idempotent(&self, args: &Value) -> bool {
+        args.get("action")
+            .and_then(Value::as_str)
+            .is_none_or(|action| !Self::is_dangerous_action(action))
+    }
+
     fn parameters(&self) -> Value {
         json!({
             "type": "object",
@@ -86,6 +149,7 @@ impl Tool for GoogleTool {
                     "enum": [
                         "gmail_search",
                         "gmail_read",
+                        "gmail_modify",
                         "gmail_send",
                         "gmail_reply",
                         "calendar_list",
@@ -100,7 +164,22 @@ impl Tool for GoogleTool {
                 },
                 "message_id": {
                     "type": "string",
-                    "description": "Gmail message ID. Required for gmail_read."
+                    "description": "Gmail message ID. Required for gmail_read and gmail_modify unless message_ids is given."
+                },
+                "message_ids": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Several Gmail message IDs to act on at once. Optional for gmail_read and gmail_modify; failures are reported per message."
+                },
+                "add_labels": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Label IDs to add (e.g. 'STARRED', 'IMPORTANT', 'Label_12'). Optional for gmail_modify."
+                },
+                "remove_labels": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Label IDs to remove. Use 'UNREAD' to mark read and 'INBOX' to archive. Optional for gmail_modify."
                 },
                 "to": {
                     "type": "string",
@@ -144,11 +223,11 @@ impl Tool for GoogleTool {
                 },
                 "start": {
                     "type": "string",
-                    "description": "Event start time (RFC3339). Required for calendar_create."
+                    "description": "Event start time (RFC3339; without an offset it is read in the user's timezone). Required for calendar_create."
                 },
                 "end": {
                     "type": "string",
-                    "description": "Event end time (RFC3339). Required for calendar_create."
+                    "description": "Event end time (RFC3339; without an offset it is read in the user's timezone). Required for calendar_create."
                 },
                 "description": {
                     "type": "string",
@@ -173,22 +252,27 @@ impl Tool for GoogleTool {
         })
     }
 
-    async fn execute(&self, args: Value, _ctx: &ToolContext) -> Result<ToolOutput> {
-        let action = args
-            .get("action")
-            .and_then(Value::as_str)
-            .ok_or_else(|| ZeptoError::Tool("Missing 'action' parameter".to_string()))?;
+    async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<ToolOutput> {
+        let action = args.get("action").and_then(Value::as_str).ok_or_else(|| {
+            ZeptoError::tool(ToolErrorKind::InvalidArgument, "Missing 'action' parameter")
+        })?;
 
         let output = match action {
             "gmail_search" => self.gmail_search(&args).await?,
             "gmail_read" => self.gmail_read(&args).await?,
+            "gmail_modify" => self.gmail_modify(&args).await?,
             "gmail_send" => self.gmail_send(&args, false).await?,
             "gmail_reply" => self.gmail_send(&args, true).await?,
-            "calendar_list" => self.calendar_list(&args).await?,
-            "calendar_create" => self.calendar_create(&args).await?,
+            "calendar_list" => {
+                return self.calendar_list(&args, &self.display_for(ctx)).await;
+            }
+            "calendar_create" => self.calendar_create(&args, &self.display_for(ctx)).await?,
             "calendar_freebusy" => self.calendar_freebusy(&args).await?,
             other => {
-                return Err(ZeptoError::Tool(format!("Unknown action '{}'", other)));
+                return Err(ZeptoError::tool(
+                    ToolErrorKind::InvalidArgument,
+                    format!("Unknown action '{}'", other),
+                ));
             }
         };
 
@@ -202,10 +286,12 @@ impl Tool for GoogleTool {
 
 impl GoogleTool {
     async fn gmail_search(&self, args: &Value) -> Result<String> {
-        let query = args
-            .get("query")
-            .and_then(Value::as_str)
-            .ok_or_else(|| ZeptoError::Tool("Missing 'query' for gmail_search".to_string()))?;
+        let query = args.get("query").and_then(Value::as_str).ok_or_else(|| {
+            ZeptoError::tool(
+                ToolErrorKind::InvalidArgument,
+                "Missing 'query' for gmail_search",
+            )
+        })?;
 
         let params = SearchParams {
             query: query.to_string(),
@@ -215,7 +301,7 @@ impl GoogleTool {
 
         let result = search_messages(&self.client, &self.access_token, &params)
             .await
-            .map_err(|e| ZeptoError::Tool(format!("Gmail search failed: {}", e)))?;
+            .map_err(|e| api_error("Gmail search", e))?;
 
         if result.messages.is_empty() {
             return Ok("No messages found.".to_string());
@@ -238,11 +324,30 @@ impl GoogleTool {
     }
 
     async fn gmail_read(&self, args: &Value) -> Result<String> {
+        if let Some(ids) = args.get("message_ids").and_then(Value::as_array) {
+            let mut results = PartialResults::new();
+            for id in ids.iter().filter_map(Value::as_str) {
+                match self.read_message(id).await {
+                    Ok(text) => results.success(id, text),
+                    Err(e) => results.failure(id, e.to_string()),
+                }
+            }
+            return results.into_result(&format!("Read {} message(s):", ids.len()));
+        }
+
         let message_id = args
             .get("message_id")
             .and_then(Value::as_str)
-            .ok_or_else(|| ZeptoError::Tool("Missing 'message_id' for gmail_read".to_string()))?;
+            .ok_or_else(|| {
+                ZeptoError::tool(
+                    ToolErrorKind::InvalidArgument,
+                    "Missing 'message_id' for gmail_read",
+                )
+            })?;
+        self.read_message(message_id).await
+    }
 
+    async fn read_message(&self, message_id: &str) -> Result<String> {
         let msg = get_message(
             &self.client,
             &self.access_token,
@@ -250,7 +355,7 @@ impl GoogleTool {
             MessageFormat::Full,
         )
         .await
-        .map_err(|e| ZeptoError::Tool(format!("Gmail read failed: {}", e)))?;
+        .map_err(|e| api_error("Gmail read", e))?;
 
         let mut lines = Vec::new();
         lines.push(format!("Message ID: {}", msg.id));
@@ -275,6 +380,35 @@ impl GoogleTool {
         Ok(lines.join("\n"))
     }
 
+    async fn gmail_modify(&self, args: &Value) -> Result<String> {
+        let request = ModifyRequest::from_args(args)?;
+
+        let mut batches = Vec::new();
+        for body in request.batch_bodies() {
+            let sent = self
+                .client
+                .post(GMAIL_BATCH_MODIFY_URL)
+                .bearer_auth(&self.access_token)
+                .json(&body)
+                .send()
+                .await;
+            let outcome = match sent {
+                Ok(resp) if resp.status().is_success() => Ok(()),
+                Ok(resp) => {
+                    let status = resp.status();
+                    let text = resp.text().await.unwrap_or_default();
+                    Err(format!("HTTP {}: {}", status, text.trim()))
+                }
+                Err(e) => Err(e.to_string()),
+            };
+            batches.push((body, outcome));
+        }
+
+        request
+            .results(&batches)
+            .into_result(&format!("Modified labels ({}):", request.change_summary()))
+    }
+
     async fn gmail_send(&self, args: &Value, is_reply: bool) -> Result<String> {
         let to = args.get("to").and_then(Value::as_str).ok_or_else(|| {
             let action = if is_reply {
@@ -282,7 +416,10 @@ impl GoogleTool {
             } else {
                 "gmail_send"
             };
-            ZeptoError::Tool(format!("Missing 'to' for {}", action))
+            ZeptoError::tool(
+                ToolErrorKind::InvalidArgument,
+                format!("Missing 'to' for {}", action),
+            )
         })?;
 
         let subject = args.get("subject").and_then(Value::as_str).ok_or_else(|| {
@@ -291,7 +428,10 @@ impl GoogleTool {
             } else {
                 "gmail_send"
             };
-            ZeptoError::Tool(format!("Missing 'subject' for {}", action))
+            ZeptoError::tool(
+                ToolErrorKind::InvalidArgument,
+                format!("Missing 'subject' for {}", action),
+            )
         })?;
 
         let body = args.get("body").and_then(Value::as_str).ok_or_else(|| {
@@ -300,7 +440,10 @@ impl GoogleTool {
             } else {
                 "gmail_send"
             };
-            ZeptoError::Tool(format!("Missing 'body' for {}", action))
+            ZeptoError::tool(
+                ToolErrorKind::InvalidArgument,
+                format!("Missing 'body' for {}", action),
+            )
         })?;
 
         let thread_id = if is_reply {
@@ -308,7 +451,10 @@ impl GoogleTool {
                 .get("thread_id")
                 .and_then(Value::as_str)
                 .ok_or_else(|| {
-                    ZeptoError::Tool("Missing 'thread_id' for gmail_reply".to_string())
+                    ZeptoError::tool(
+                        ToolErrorKind::InvalidArgument,
+                        "Missing 'thread_id' for gmail_reply",
+                    )
                 })?;
             Some(tid.to_string())
         } else {
@@ -328,7 +474,7 @@ impl GoogleTool {
 
         let sent = send_message(&self.client, &self.access_token, "me", &params)
             .await
-            .map_err(|e| ZeptoError::Tool(format!("Gmail send failed: {}", e)))?;
+            .map_err(|e| api_error("Gmail send", e))?;
 
         let action_label = if is_reply { "Reply sent" } else { "Email sent" };
         Ok(format!(
@@ -337,7 +483,7 @@ impl GoogleTool {
         ))
     }
 
-    async fn calendar_list(&self, args: &Value) -> Result<String> {
+    async fn calendar_list(&self, args: &Value, display: &DisplayFormat) -> Result<ToolOutput> {
         let calendar_id = args
             .get("calendar_id")
             .and_then(Value::as_str)
@@ -365,14 +511,21 @@ impl GoogleTool {
 
         let events = list_events(&self.client, &self.access_token, &params)
             .await
-            .map_err(|e| ZeptoError::Tool(format!("Calendar list failed: {}", e)))?;
+            .map_err(|e| api_error("Calendar list", e))?;
 
         if events.items.is_empty() {
-            return Ok("No events found.".to_string());
+            return Ok(ToolOutput::llm_only("No events found."));
         }
 
+        // Friendly times for the text; raw RFC3339 values stay in the
+        // structured fields.
         let mut lines = Vec::new();
-        lines.push(format!("Found {} event(s):", events.items.len()));
+        let mut fields = Vec::new();
+        lines.push(format!(
+            "Found {} event(s) (times in {}):",
+            events.items.len(),
+            display.timezone()
+        ));
         for event in &events.items {
             let start = event
                 .start
@@ -385,35 +538,50 @@ impl GoogleTool {
                 .and_then(|e| e.date_time.as_deref().or(e.date.as_deref()))
                 .unwrap_or("unknown");
             lines.push(format!(
-                "  [{}] {} → {}  ID: {}",
+                "  [{}] {}  ID: {}",
                 event.display_summary(),
-                start,
-                end,
+                display.event_range(start, end),
                 event.id.as_deref().unwrap_or("?")
             ));
             if let Some(loc) = &event.location {
                 lines.push(format!("      Location: {}", loc));
             }
+            fields.push(StructuredField {
+                label: event.display_summary().to_string(),
+                value: format!("{}/{}", start, end),
+            });
         }
 
-        Ok(lines.join("\n"))
+        Ok(
+            ToolOutput::llm_only(lines.join("\n")).with_structured(StructuredOutput {
+                title: Some("Calendar events".to_string()),
+                fields,
+                ..Default::default()
+            }),
+        )
     }
 
-    async fn calendar_create(&self, args: &Value) -> Result<String> {
-        let summary = args
-            .get("summary")
-            .and_then(Value::as_str)
-            .ok_or_else(|| ZeptoError::Tool("Missing 'summary' for calendar_create".to_string()))?;
+    async fn calendar_create(&self, args: &Value, display: &DisplayFormat) -> Result<String> {
+        let summary = args.get("summary").and_then(Value::as_str).ok_or_else(|| {
+            ZeptoError::tool(
+                ToolErrorKind::InvalidArgument,
+                "Missing 'summary' for calendar_create",
+            )
+        })?;
 
-        let start = args
-            .get("start")
-            .and_then(Value::as_str)
-            .ok_or_else(|| ZeptoError::Tool("Missing 'start' for calendar_create".to_string()))?;
+        let start = args.get("start").and_then(Value::as_str).ok_or_else(|| {
+            ZeptoError::tool(
+                ToolErrorKind::InvalidArgument,
+                "Missing 'start' for calendar_create",
+            )
+        })?;
 
-        let end = args
-            .get("end")
-            .and_then(Value::as_str)
-            .ok_or_else(|| ZeptoError::Tool("Missing 'end' for calendar_create".to_string()))?;
+        let end = args.get("end").and_then(Value::as_str).ok_or_else(|| {
+            ZeptoError::tool(
+                ToolErrorKind::InvalidArgument,
+                "Missing 'end' for calendar_create",
+            )
+        })?;
 
         let calendar_id = args
             .get("calendar_id")
@@ -443,15 +611,15 @@ impl GoogleTool {
                 .get("location")
                 .and_then(Value::as_str)
                 .map(String::from),
-            start: EventDateTime::date_time(start, None),
-            end: EventDateTime::date_time(end, None),
+            start: EventDateTime::date_time(&display.localize(start), None),
+            end: EventDateTime::date_time(&display.localize(end), None),
             attendees,
             recurrence: vec![],
         };
 
         let event = create_event(&self.client, &self.access_token, &params)
             .await
-            .map_err(|e| ZeptoError::Tool(format!("Calendar create failed: {}", e)))?;
+            .map_err(|e| api_error("Calendar create", e))?;
 
         let mut lines = Vec::new();
         lines.push("Event created successfully.".to_string());
@@ -471,14 +639,20 @@ impl GoogleTool {
             .get("time_min")
             .and_then(Value::as_str)
             .ok_or_else(|| {
-                ZeptoError::Tool("Missing 'time_min' for calendar_freebusy".to_string())
+                ZeptoError::tool(
+                    ToolErrorKind::InvalidArgument,
+                    "Missing 'time_min' for calendar_freebusy",
+                )
             })?;
 
         let time_max = args
             .get("time_max")
             .and_then(Value::as_str)
             .ok_or_else(|| {
-                ZeptoError::Tool("Missing 'time_max' for calendar_freebusy".to_string())
+                ZeptoError::tool(
+                    ToolErrorKind::InvalidArgument,
+                    "Missing 'time_max' for calendar_freebusy",
+                )
             })?;
 
         let calendars: Vec<String> = args
@@ -500,49 +674,203 @@ impl GoogleTool {
             time_max,
         )
         .await
-        .map_err(|e| ZeptoError::Tool(format!("Calendar freebusy failed: {}", e)))?;
+        .map_err(|e| api_error("Calendar freebusy", e))?;
 
-        let mut lines = Vec::new();
-        lines.push(format!(
+        let heading = format!(
             "Free/busy query from {} to {}:",
             result.time_min.as_deref().unwrap_or(time_min),
             result.time_max.as_deref().unwrap_or(time_max)
-        ));
+        );
+        freebusy_results(&calendars, result.calendars.as_ref()).into_result(&heading)
+    }
+}
 
-        match &result.calendars {
-            Some(cals) if cals.is_object() => {
-                for cal_id in &calendars {
-                    if let Some(cal_data) = cals.get(cal_id) {
-                        let busy_slots = cal_data
-                            .get("busy")
-                            .and_then(Value::as_array)
-                            .cloned()
-                            .unwrap_or_default();
-                        if busy_slots.is_empty() {
-                            lines.push(format!("  {}: FREE (no busy slots)", cal_id));
-                        } else {
-                            lines.push(format!("  {}: {} busy slot(s)", cal_id, busy_slots.len()));
-                            for slot in &busy_slots {
-                                let slot_start =
-                                    slot.get("start").and_then(Value::as_str).unwrap_or("?");
-                                let slot_end =
-                                    slot.get("end").and_then(Value::as_str).unwrap_or("?");
-                                lines.push(format!("    {} → {}", slot_start, slot_end));
-                            }
-                        }
-                    }
-                }
-            }
-            Some(cals) => {
-                lines.push(format!("Raw calendars data: {}", cals));
+/// Classified error for a failed Google API call.
+fn api_error(operation: &str, error: impl std::fmt::Display) -> ZeptoError {
+    let message = format!("{} failed: {}", operation, error);
+    ZeptoError::tool(ToolErrorKind::from_api_message(&message), message)
+}
+
+/// Validated `gmail_modify` arguments.
+#[derive(Debug, Clone, PartialEq)]
+struct ModifyRequest {
+    /// Distinct, well-formed message IDs, in request order
+    ids: Vec<String>,
+    /// Malformed message IDs with the reason they were rejected
+    rejected: Vec<(String, String)>,
+    add: Vec<String>,
+    remove: Vec<String>,
+}
+
+impl ModifyRequest {
+    fn from_args(args: &Value) -> Result<Self> {
+        let raw_ids: Vec<String> = match args.get("message_ids").and_then(Value::as_array) {
+            Some(ids) => ids
+                .iter()
+                .map(|id| id.as_str().unwrap_or_default().trim().to_string())
+                .collect(),
+            None => args
+                .get("message_id")
+                .and_then(Value::as_str)
+                .map(|id| vec![id.trim().to_string()])
+                .unwrap_or_default(),
+        };
+        if raw_ids.is_empty() {
+            return Err(ZeptoError::tool(
+                ToolErrorKind::InvalidArgument,
+                "Missing 'message_id' or 'message_ids' for gmail_modify",
+            ));
+        }
+
+        let mut ids = Vec::new();
+        let mut rejected = Vec::new();
+        for id in raw_ids {
+            if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric()) {
+                rejected.push((id, "invalid message ID".to_string()));
+            } else if !ids.contains(&id) {
+                ids.push(id);
             }
-            None => {
-                lines.push("No calendar data returned.".to_string());
+        }
+
+        let add = label_list(args, "add_labels")?;
+        let remove = label_list(args, "remove_labels")?;
+        if add.is_empty() && remove.is_empty() {
+            return Err(ZeptoError::tool(
+                ToolErrorKind::InvalidArgument,
+                "gmail_modify needs 'add_labels' or 'remove_labels'",
+            ));
+        }
+        if let Some(label) = add.iter().find(|l| remove.contains(l)) {
+            return Err(ZeptoError::tool(
+                ToolErrorKind::InvalidArgument,
+                format!("Label '{}' cannot be both added and removed", label),
+            ));
+        }
+
+        Ok(Self {
+            ids,
+            rejected,
+            add,
+            remove,
+        })
+    }
+
+    /// `batchModify` request bodies, at most [`BATCH_MODIFY_LIMIT`] IDs each.
+    fn batch_bodies(&self) -> Vec<Value> {
+        self.ids
+            .chunks(BATCH_MODIFY_LIMIT)
+            .map(|ids| {
+                let mut body = json!({ "ids": ids });
+                if !self.add.is_empty() {
+                    body["addLabelIds"] = json!(self.add);
+                }
+                if !self.remove.is_empty() {
+                    body["removeLabelIds"] = json!(self.remove);
+                }
+                body
+            })
+            .collect()
+    }
+
+    /// Per-message outcomes given each batch body and how it went.
+    fn results(&self, batches: &[(Value, std::result::Result<(), String>)]) -> PartialResults {
+        let mut results = PartialResults::new();
+        for (body, outcome) in batches {
+            let ids = body["ids"]
+                .as_array()
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            for id in ids.iter().filter_map(Value::as_str) {
+                match outcome {
+                    Ok(()) => results.success(id, format!("  {}: updated", id)),
+                    Err(e) => results.failure(id, e.clone()),
+                }
             }
         }
+        for (id, reason) in &self.rejected {
+            results.failure(id.clone(), reason.clone());
+        }
+        results
+    }
 
-        Ok(lines.join("\n"))
+    /// `+STARRED -UNREAD` style summary of the label changes.
+    fn change_summary(&self) -> String {
+        self.add
+            .iter()
+            .map(|l| format!("+{}", l))
+            .chain(self.remove.iter().map(|l| format!("-{}", l)))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Distinct label IDs from the string array `key`.
+fn label_list(args: &Value, key: &str) -> Result<Vec<String>> {
+    let mut labels: Vec<String> = Vec::new();
+    let Some(values) = args.get(key).and_then(Value::as_array) else {
+        return Ok(labels);
+    };
+    for value in values {
+        let label = value.as_str().unwrap_or_default().trim();
+        if label.is_empty() || label.chars().any(char::is_whitespace) {
+            return Err(ZeptoError::tool(
+                ToolErrorKind::InvalidArgument,
+                format!("Invalid label ID {} in '{}'", value, key),
+            ));
+        }
+        if !labels.iter().any(|l| l == label) {
+            labels.push(label.to_string());
+        }
+    }
+    Ok(labels)
+}
+
+/// Per-calendar free/busy outcomes.
+///
+/// Google reports per-calendar problems (no access, unknown calendar) inside
+/// the response as `calendars.<id>.errors`, so one bad calendar shows up as a
+/// failure here instead of failing the whole query.
+fn freebusy_results(calendars: &[String], data: Option<&Value>) -> PartialResults {
+    let mut results = PartialResults::new();
+    for cal_id in calendars {
+        let Some(cal_data) = data.and_then(|cals| cals.get(cal_id)) else {
+            results.failure(cal_id, "no free/busy data returned");
+            continue;
+        };
+
+        let errors: Vec<&str> = cal_data
+            .get("errors")
+            .and_then(Value::as_array)
+            .map(|errors| {
+                errors
+                    .iter()
+                    .map(|e| e.get("reason").and_then(Value::as_str).unwrap_or("error"))
+                    .collect()
+            })
+            .unwrap_or_default();
+        if !errors.is_empty() {
+            results.failure(cal_id, errors.join(", "));
+            continue;
+        }
+
+        let busy_slots = cal_data
+            .get("busy")
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default();
+        if busy_slots.is_empty() {
+            results.success(cal_id, format!("  {}: FREE (no busy slots)", cal_id));
+        } else {
+            let mut lines = vec![format!("  {}: {} busy slot(s)", cal_id, busy_slots.len())];
+            for slot in &busy_slots {
+                let slot_start = slot.get("start").and_then(Value::as_str).unwrap_or("?");
+                let slot_end = slot.get("end").and_then(Value::as_str).unwrap_or("?");
+                lines.push(format!("    {} → {}", slot_start, slot_end));
+            }
+            results.success(cal_id, lines.join("\n"));
+        }
     }
+    results
 }
 
 // ---------------------------------------------------------------------------
@@ -597,6 +925,7 @@ mod tests {
     fn test_is_dangerous_action_safe() {
         assert!(!GoogleTool::is_dangerous_action("gmail_search"));
         assert!(!GoogleTool::is_dangerous_action("gmail_read"));
+        assert!(!GoogleTool::is_dangerous_action("gmail_modify"));
         assert!(!GoogleTool::is_dangerous_action("calendar_list"));
         assert!(!GoogleTool::is_dangerous_action("calendar_freebusy"));
     }
@@ -614,7 +943,7 @@ mod tests {
         let tool = GoogleTool::new("t", "primary", 20);
         let params = tool.parameters();
         let action_enum = params["properties"]["action"]["enum"].as_array().unwrap();
-        assert_eq!(action_enum.len(), 7);
+        assert_eq!(action_enum.len(), 8);
     }
 
     #[test]
@@ -655,6 +984,26 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("Missing 'query'"));
     }
 
+    #[tokio::test]
+    async fn test_errors_are_classified() {
+        let tool = GoogleTool::new("t", "primary", 20);
+        let err = tool
+            .execute(json!({"action": "gmail_search"}), &ToolContext::default())
+            .await
+            .unwrap_err();
+        assert_eq!(err.tool_error_kind(), Some(ToolErrorKind::InvalidArgument));
+
+        let err = api_error("Gmail read", "HTTP 404: Requested entity was not found.");
+        assert_eq!(err.tool_error_kind(), Some(ToolErrorKind::NotFound));
+        assert!(err
+            .to_tool_result()
+            .starts_with("Error [not_found]: Gmail read failed: HTTP 404"));
+        assert_eq!(
+            api_error("Calendar list", "HTTP 429 rateLimitExceeded").tool_error_kind(),
+            Some(ToolErrorKind::RateLimited)
+        );
+    }
+
     #[tokio::test]
     async fn test_gmail_read_missing_message_id() {
         let tool = GoogleTool::new("t", "primary", 20);
@@ -801,4 +1150,110 @@ mod tests {
             .to_string()
             .contains("Missing 'time_max'"));
     }
+
+    #[test]
+    fn test_gmail_modify_builds_batch_requests() {
+        let ids: Vec<String> = (0..1001).map(|i| format!("{:x}", i)).collect();
+        let request = ModifyRequest::from_args(&json!({
+            "action": "gmail_modify",
+            "message_ids": ids,
+            "remove_labels": ["UNREAD", "INBOX", "UNREAD"]
+        }))
+        .unwrap();
+
+        let bodies = request.batch_bodies();
+        assert_eq!(bodies.len(), 2);
+        assert_eq!(bodies[0]["ids"].as_array().unwrap().len(), 1000);
+        assert_eq!(bodies[1]["ids"], json!(["3e8"]));
+        assert_eq!(bodies[0]["removeLabelIds"], json!(["UNREAD", "INBOX"]));
+        assert!(bodies[0].get("addLabelIds").is_none());
+        assert_eq!(request.change_summary(), "-UNREAD -INBOX");
+
+        let single = ModifyRequest::from_args(&json!({
+            "message_id": "18c2f",
+            "add_labels": ["STARRED"]
+        }))
+        .unwrap();
+        assert_eq!(
+            single.batch_bodies(),
+            vec![json!({"ids": ["18c2f"], "addLabelIds": ["STARRED"]})]
+        );
+
+        // Inputs are validated before anything is sent.
+        for bad in [
+            json!({"message_id": "18c2f"}),
+            json!({"add_labels": ["STARRED"]}),
+            json!({"message_id": "18c2f", "add_labels": ["INBOX"], "remove_labels": ["INBOX"]}),
+            json!({"message_id": "18c2f", "add_labels": ["my label"]}),
+        ] {
+            assert!(ModifyRequest::from_args(&bad).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_gmail_modify_reports_partial_results() {
+        let request = ModifyRequest::from_args(&json!({
+            "message_ids": ["aaa1", "../etc", "bbb2", "aaa1"],
+            "add_labels": ["STARRED"]
+        }))
+        .unwrap();
+        assert_eq!(request.ids, ["aaa1", "bbb2"]);
+
+        let bodies = request.batch_bodies();
+        let batches = vec![(bodies[0].clone(), Ok(()))];
+        let results = request.results(&batches);
+        assert_eq!(results.succeeded(), 2);
+        assert_eq!(results.failed(), 1);
+        let text = results.into_result("Modified labels (+STARRED):").unwrap();
+        assert!(text.contains("aaa1: updated"));
+        assert!(text.contains("Failed (1):\n  ../etc: invalid message ID"));
+
+        let failed = vec![(bodies[0].clone(), Err("HTTP 403 Forbidden".to_string()))];
+        let err = request.results(&failed).into_result("x").unwrap_err();
+        assert!(err.to_string().contains("HTTP 403"));
+    }
+
+    #[test]
+    fn test_freebusy_results_reports_unauthorized_calendar_separately() {
+        let calendars = vec![
+            "primary".to_string(),
+            "boss@example.com".to_string(),
+            "team@example.com".to_string(),
+        ];
+        let data = json!({
+            "primary": {
+                "busy": [{"start": "2026-03-01T09:00:00Z", "end": "2026-03-01T10:00:00Z"}]
+            },
+            "boss@example.com": {
+                "errors": [{"domain": "calendar", "reason": "notFound"}],
+                "busy": []
+            },
+            "team@example.com": {"busy": []}
+        });
+
+        let results = freebusy_results(&calendars, Some(&data));
+        assert_eq!(results.succeeded(), 2);
+        assert_eq!(results.failed(), 1);
+
+        let text = results.into_result("Free/busy:").unwrap();
+        assert!(text.contains("primary: 1 busy slot(s)"));
+        assert!(text.contains("team@example.com: FREE"));
+        assert!(text.contains("Failed (1):\n  boss@example.com: notFound"));
+        assert!(text.contains("Summary: 2 succeeded, 1 failed"));
+    }
+
+    #[test]
+    fn test_freebusy_results_all_failed_is_error() {
+        let calendars = vec!["a@example.com".to_string()];
+        let data = json!({
+            "a@example.com": {"errors": [{"reason": "forbidden"}]}
+        });
+        let err = freebusy_results(&calendars, Some(&data))
+            .into_result("Free/busy:")
+            .unwrap_err();
+        assert!(err.to_string().contains("forbidden"));
+        assert!(freebusy_results(&calendars, None)
+            .into_result("Free/busy:")
+            .is_err());
+    }
 }
@@ -6,13 +6,14 @@ use serde_json::{json, Value};
 use crate::error::{Result, ZeptoError};
 use crate::security::validate_path_in_workspace;
 
-use super::output::{truncate_tool_output, DEFAULT_MAX_BYTES, DEFAULT_MAX_LINES};
+use super::output::{offset_arg, offset_schema, Page, DEFAULT_PAGE_BYTES};
 use super::{Tool, ToolCategory, ToolContext, ToolOutput};
 
 /// Tool for searching file contents by pattern.
 ///
 /// Shells out to system `grep -rn` for performance. Supports regex patterns,
-/// glob file filters, case-insensitive search, and result limiting.
+/// glob file filters, case-insensitive search, and result limiting. Long
+/// results are paged; pass the `offset` from the truncation note to continue.
 pub struct GrepTool;
 
 #[async_trait]
@@ -56,7 +57,8 @@ impl Tool for GrepTool {
                 "limit": {
                     "type": "integer",
                     "description": "Maximum matches to return (default: 100)"
-                }
+                },
+                "offset": offset_schema()
             },
             "required": ["pattern"]
         })
@@ -158,11 +160,8 @@ impl Tool for GrepTool {
             ));
         }
 
-        Ok(ToolOutput::llm_only(truncate_tool_output(
-            &result,
-            DEFAULT_MAX_LINES,
-            DEFAULT_MAX_BYTES,
-        )))
+        let page = Page::of(&result, offset_arg(&args), DEFAULT_PAGE_BYTES);
+        Ok(ToolOutput::llm_only(page.render("grep")))
     }
 }
 
@@ -189,6 +188,7 @@ mod tests {
         assert!(params["properties"]["glob"].is_object());
         assert!(params["properties"]["ignore_case"].is_object());
         assert!(params["properties"]["limit"].is_object());
+        assert!(params["properties"]["offset"].is_object());
         assert_eq!(params["required"], json!(["pattern"]));
     }
 
@@ -306,4 +306,46 @@ mod tests {
         );
         assert!(result.for_llm.contains("more matches"));
     }
+
+    #[tokio::test]
+    async fn test_grep_pages_long_results_by_offset() {
+        let dir = tempfile::tempdir().unwrap();
+        let content: String = (0..400)
+            .map(|i| format!("match {:04} {}\n", i, "x".repeat(80)))
+            .collect();
+        std::fs::write(dir.path().join("big.txt"), &content).unwrap();
+        let ctx = ToolContext::new().with_workspace(dir.path().to_str().unwrap());
+
+        let first = GrepTool
+            .execute(json!({"pattern": "match", "limit": 1000}), &ctx)
+            .await
+            .unwrap()
+            .for_llm;
+        assert!(first.len() < DEFAULT_PAGE_BYTES + 200);
+        let marker = first.lines().last().unwrap();
+        assert!(marker.starts_with("[output truncated: bytes 0-"));
+        let offset: usize = marker
+            .split("\"offset\": ")
+            .nth(1)
+            .and_then(|rest| rest.split(' ').next())
+            .unwrap()
+            .parse()
+            .unwrap();
+
+        let second = GrepTool
+            .execute(
+                json!({"pattern": "match", "limit": 1000, "offset": offset}),
+                &ctx,
+            )
+            .await
+            .unwrap()
+            .for_llm;
+        // The next page starts at the line after the last one of the first page.
+        let last_first = first.lines().rev().nth(1).unwrap();
+        let n: usize = last_first.split("match ").nth(1).unwrap()[..4]
+            .parse()
+            .unwrap();
+        let first_second = second.lines().next().unwrap();
+        assert!(first_second.contains(&format!("match {:04}", n + 1)));
+    }
 }
@@ -22,7 +22,7 @@ impl GoogleSheetsTool {
     /// Create with an OAuth access token.
     pub fn new(access_token: &str) -> Self {
         Self {
-            client: Client::new(),
+            client: crate::utils::http::default_client(),
             access_token: access_token.to_string(),
         }
     }
@@ -265,6 +265,11 @@ impl Tool for GoogleSheetsTool {
         ToolCategory::NetworkWrite
     }
 
+    fn is_idempotent(&self, args: &Value) -> bool {
+        // Appending twice adds duplicate rows; reads and updates repeat safely.
+        args.get("action").and_then(Value::as_str) != Some("append")
+    }
+
     fn parameters(&self) -> Value {
         json!({
             "type": "object",
@@ -10,7 +10,7 @@ use crate::tools::web::{
 };
 use crate::tools::{Tool, ToolContext, ToolOutput};
 use async_trait::async_trait;
-use reqwest::{Client, Method, Url};
+use reqwest::{Method, Url};
 use serde_json::{json, Value};
 use std::time::Duration;
 
@@ -79,6 +79,91 @@ impl HttpRequestTool {
             .filter(|(k, _)| !blocked.contains(&k.to_lowercase().as_str()))
             .collect()
     }
+
+    /// Perform a validated request and return `(status, body)`.
+    ///
+    /// Applies the same allowlist, SSRF, DNS-pinning, redirect and header
+    /// hardening as the tool entry point. Bodies larger than
+    /// `max_response_bytes` are truncated with a trailing marker.
+    pub async fn fetch(
+        &self,
+        method: &str,
+        url: &str,
+        headers: Vec<(String, String)>,
+        body: Option<&str>,
+    ) -> Result<(u16, String)> {
+        let method_str = method.to_uppercase();
+        let parsed = self.validate_url(url)?;
+
+        // DNS-level SSRF check: resolve the hostname and verify it is not
+        // private/local.  We keep the returned pinned address so the HTTP
+        // client can be told to connect to that exact IP, eliminating the
+        // DNS rebinding window between this check and the actual connection.
+        let pinned = resolve_and_check_host(&parsed).await?;
+
+        let method = Method::from_bytes(method_str.as_bytes())
+            .map_err(|_| ZeptoError::Tool(format!("Unknown HTTP method: {method_str}")))?;
+
+        // Build a client that pins the DNS resolution to the IP we already
+        // validated and checks every redirect hop before following.
+        let mut builder = crate::utils::http::client_builder()?
+            .timeout(Duration::from_secs(self.timeout_secs))
+            .redirect(http_request_redirect_policy());
+        if let Some((host, addr)) = pinned {
+            builder = builder.resolve(&host, addr);
+        }
+        let client = builder
+            .build()
+            .map_err(|e| ZeptoError::Tool(format!("HTTP client error: {e}")))?;
+
+        let mut req = client.request(method, parsed.as_str());
+
+        // Auto-set Content-Type to application/json when the body looks
+        // like JSON and the caller has not already provided a content-type
+        // header (prevents silent broken POSTs where the server rejects an
+        // untyped JSON payload).
+        let caller_set_ct = headers
+            .iter()
+            .any(|(k, _)| k.to_lowercase() == "content-type");
+        for (k, v) in Self::strip_dangerous_headers(headers) {
+            req = req.header(&k, &v);
+        }
+
+        if let Some(body) = body {
+            let trimmed = body.trim_start();
+            if !caller_set_ct && (trimmed.starts_with('{') || trimmed.starts_with('[')) {
+                req = req.header("Content-Type", "application/json");
+            }
+            req = req.body(body.to_string());
+        }
+
+        let response = req
+            .send()
+            .await
+            .map_err(|e| ZeptoError::Tool(format!("Request failed: {e}")))?;
+
+        // Defense in depth: validate final redirect destination too.
+        validate_redirect_target(response.url()).await?;
+
+        let status = response.status().as_u16();
+        let body_bytes = response
+            .bytes()
+            .await
+            .map_err(|e| ZeptoError::Tool(format!("Failed to read response body: {e}")))?;
+
+        let body_str = if body_bytes.len() > self.max_response_bytes {
+            let truncated = &body_bytes[..self.max_response_bytes];
+            format!(
+                "{}\n[TRUNCATED — {} bytes total]",
+                String::from_utf8_lossy(truncated),
+                body_bytes.len()
+            )
+        } else {
+            String::from_utf8_lossy(&body_bytes).into_owned()
+        };
+
+        Ok((status, body_str))
+    }
 }
 
 /// Check whether `host` matches `pattern`, supporting wildcard subdomains.
@@ -153,84 +238,21 @@ impl Tool for HttpRequestTool {
         let url_str = args["url"].as_str().unwrap_or("").to_string();
         let method_str = args["method"]
             .as_str()
-            .ok_or_else(|| ZeptoError::Tool("Missing required parameter: method".into()))?
-            .to_uppercase();
-
-        let parsed = self.validate_url(&url_str)?;
-
-        // DNS-level SSRF check: resolve the hostname and verify it is not
-        // private/local.  We keep the returned pinned address so the HTTP
-        // client can be told to connect to that exact IP, eliminating the
-        // DNS rebinding window between this check and the actual connection.
-        let pinned = resolve_and_check_host(&parsed).await?;
-
-        let method = Method::from_bytes(method_str.as_bytes())
-            .map_err(|_| ZeptoError::Tool(format!("Unknown HTTP method: {method_str}")))?;
-
-        // Build a client that pins the DNS resolution to the IP we already
-        // validated and checks every redirect hop before following.
-        let mut builder = Client::builder()
-            .timeout(Duration::from_secs(self.timeout_secs))
-            .redirect(http_request_redirect_policy());
-        if let Some((host, addr)) = pinned {
-            builder = builder.resolve(&host, addr);
-        }
-        let client = builder
-            .build()
-            .map_err(|e| ZeptoError::Tool(format!("HTTP client error: {e}")))?;
-
-        let mut req = client.request(method, parsed.as_str());
-
-        if let Some(headers) = args["headers"].as_object() {
-            let pairs: Vec<(String, String)> = headers
-                .iter()
-                .filter_map(|(k, v)| v.as_str().map(|s| (k.clone(), s.to_string())))
-                .collect();
-            for (k, v) in Self::strip_dangerous_headers(pairs) {
-                req = req.header(&k, &v);
-            }
-        }
-
-        if let Some(body) = args["body"].as_str() {
-            // Auto-set Content-Type to application/json when the body looks
-            // like JSON and the caller has not already provided a content-type
-            // header (prevents silent broken POSTs where the server rejects an
-            // untyped JSON payload).
-            let caller_set_ct = args["headers"]
-                .as_object()
-                .map(|h| h.keys().any(|k| k.to_lowercase() == "content-type"))
-                .unwrap_or(false);
-            let trimmed = body.trim_start();
-            if !caller_set_ct && (trimmed.starts_with('{') || trimmed.starts_with('[')) {
-                req = req.header("Content-Type", "application/json");
-            }
-            req = req.body(body.to_string());
-        }
-
-        let response = req
-            .send()
-            .await
-            .map_err(|e| ZeptoError::Tool(format!("Request failed: {e}")))?;
-
-        // Defense in depth: validate final redirect destination too.
-        validate_redirect_target(response.url()).await?;
-
-        let status = response.status().as_u16();
-        let body_bytes = response
-            .bytes()
-            .await
-            .map_err(|e| ZeptoError::Tool(format!("Failed to read response body: {e}")))?;
-
-        let body_str = if body_bytes.len() > self.max_response_bytes {
-            let truncated = &body_bytes[..self.max_response_bytes];
-            format!(
-                "{}\n[TRUNCATED — {} bytes total]",
-                String::from_utf8_lossy(truncated),
-                body_bytes.len()
-            )
-        } else {
-            String::from_utf8_lossy(&body_bytes).into_owned()
-        };
+            .ok_or_else(|| ZeptoError::Tool("Missing required parameter: method".into()))?;
+
+        let headers: Vec<(String, String)> = args["headers"]
+            .as_object()
+            .map(|headers| {
+                headers
+                    .iter()
+                    .filter_map(|(k, v)| v.as_str().map(|s| (k.clone(), s.to_string())))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let (status, body_str) = self
+            .fetch(method_str, &url_str, headers, args["body"].as_str())
+            .await?;
 
         Ok(ToolOutput::llm_only(format!(
             "Status: {status}\n\n{body_str}"
@@ -0,0 +1,397 @@
+//! Small key-value state for agents.
+//!
+//! The `kv` tool stashes bits of state between turns — counters, flags,
+//! last-seen values — that do not belong in searchable memory. Values are
+//! arbitrary JSON. Each conversation (`channel:chat_id`) gets its own
+//! namespace; `scope: "global"` shares one namespace across conversations.
+//! Everything lives in one JSON file (`~/.zeptoclaw/kv.json`), written on
+//! every change.
+//!
+//! Keys, values and the number of keys per namespace are capped so the
+//! store cannot be used as bulk storage.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+use crate::config::Config;
+use crate::error::{Result, ZeptoError};
+use crate::utils::fs::write_atomic;
+
+use super::{Tool, ToolCategory, ToolContext, ToolOutput};
+
+/// Longest key accepted, in characters.
+const MAX_KEY_CHARS: usize = 128;
+/// Default cap on a value's serialized size, in bytes.
+pub const DEFAULT_MAX_VALUE_BYTES: usize = 4_096;
+/// Default cap on keys per namespace.
+pub const DEFAULT_MAX_KEYS: usize = 256;
+
+/// Namespace used by `scope: "global"`.
+const GLOBAL_SCOPE: &str = "global";
+
+type Namespaces = BTreeMap<String, BTreeMap<String, Value>>;
+
+/// Tool for per-conversation or global key-value state.
+pub struct KvTool {
+    path: PathBuf,
+    max_value_bytes: usize,
+    max_keys: usize,
+    lock: Mutex<()>,
+}
+
+impl KvTool {
+    /// Create the tool storing its state at `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            max_value_bytes: DEFAULT_MAX_VALUE_BYTES,
+            max_keys: DEFAULT_MAX_KEYS,
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Default state file: `~/.zeptoclaw/kv.json`.
+    pub fn default_path() -> PathBuf {
+        Config::dir().join("kv.json")
+    }
+
+    /// Cap a value's serialized size.
+    pub fn with_max_value_bytes(mut self, max_value_bytes: usize) -> Self {
+        self.max_value_bytes = max_value_bytes;
+        self
+    }
+
+    /// Cap the number of keys per namespace.
+    pub fn with_max_keys(mut self, max_keys: usize) -> Self {
+        self.max_keys = max_keys;
+        self
+    }
+
+    fn load(&self) -> Result<Namespaces> {
+        match std::fs::read_to_string(&self.path) {
+            Ok(raw) => Ok(serde_json::from_str(&raw)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Namespaces::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn save(&self, namespaces: &Namespaces) -> Result<()> {
+        write_atomic(&self.path, serde_json::to_string_pretty(namespaces)?)?;
+        Ok(())
+    }
+
+    /// Store `value` under `key`, enforcing the size and key-count caps.
+    fn put(&self, entries: &mut BTreeMap<String, Value>, key: &str, value: Value) -> Result<()> {
+        let size = serde_json::to_string(&value)?.len();
+        if size > self.max_value_bytes {
+            return Err(ZeptoError::Tool(format!(
+                "Value for '{}' is {} bytes; the limit is {} bytes",
+                key, size, self.max_value_bytes
+            )));
+        }
+        if !entries.contains_key(key) && entries.len() >= self.max_keys {
+            return Err(ZeptoError::Tool(format!(
+                "Key limit reached ({} keys); delete a key first",
+                self.max_keys
+            )));
+        }
+        entries.insert(key.to_string(), value);
+        Ok(())
+    }
+}
+
+fn namespace(args: &Value, ctx: &ToolContext) -> Result<String> {
+    match args
+        .get("scope")
+        .and_then(Value::as_str)
+        .unwrap_or("session")
+    {
+        "session" => Ok(format!(
+            "{}:{}",
+            ctx.channel.as_deref().unwrap_or("cli"),
+            ctx.chat_id.as_deref().unwrap_or("default")
+        )),
+        "global" => Ok(GLOBAL_SCOPE.to_string()),
+        other => Err(ZeptoError::Tool(format!(
+            "Unknown scope '{}'. Use session or global.",
+            other
+        ))),
+    }
+}
+
+fn key_arg(args: &Value) -> Result<&str> {
+    let key = args
+        .get("key")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|k| !k.is_empty())
+        .ok_or_else(|| ZeptoError::Tool("Missing 'key' parameter".to_string()))?;
+    if key.chars().count() > MAX_KEY_CHARS {
+        return Err(ZeptoError::Tool(format!(
+            "Key is longer than {} characters",
+            MAX_KEY_CHARS
+        )));
+    }
+    Ok(key)
+}
+
+#[async_trait]
+impl Tool for KvTool {
+    fn name(&self) -> &str {
+        "kv"
+    }
+
+    fn description(&self) -> &str {
+        "Small persistent key-value state for counters, flags and last-seen values between turns. Values are any JSON. Keys are per conversation by default; scope=global shares them across conversations. Not searchable — use longterm_memory for facts worth recalling."
+    }
+
+    fn compact_description(&self) -> &str {
+        "Key-value state"
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::Memory
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["get", "set", "delete", "incr", "list"],
+                    "description": "get/set/delete a key, incr an integer key (missing keys start at 0), or list keys"
+                },
+                "key": {
+                    "type": "string",
+                    "description": "Key (required except for list)"
+                },
+                "value": {
+                    "description": "JSON value for set"
+                },
+                "by": {
+                    "type": "integer",
+                    "description": "Amount to add for incr (default 1)"
+                },
+                "prefix": {
+                    "type": "string",
+                    "description": "Only list keys starting with this"
+                },
+                "scope": {
+                    "type": "string",
+                    "enum": ["session", "global"],
+                    "description": "session (default): this conversation only; global: shared"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<ToolOutput> {
+        let action = args
+            .get("action")
+            .and_then(Value::as_str)
+            .ok_or_else(|| ZeptoError::Tool("Missing 'action' parameter".to_string()))?;
+        let scope = namespace(&args, ctx)?;
+
+        let _guard = self.lock.lock().unwrap();
+        let mut namespaces = self.load()?;
+
+        match action {
+            "get" => {
+                let key = key_arg(&args)?;
+                let value = namespaces.get(&scope).and_then(|entries| entries.get(key));
+                Ok(ToolOutput::llm_only(match value {
+                    Some(value) => value.to_string(),
+                    None => format!("Key '{}' is not set.", key),
+                }))
+            }
+            "set" => {
+                let key = key_arg(&args)?;
+                let value = args
+                    .get("value")
+                    .cloned()
+                    .ok_or_else(|| ZeptoError::Tool("Missing 'value' parameter".to_string()))?;
+                self.put(namespaces.entry(scope).or_default(), key, value)?;
+                self.save(&namespaces)?;
+                Ok(ToolOutput::llm_only(format!("Key '{}' saved.", key)))
+            }
+            "delete" => {
+                let key = key_arg(&args)?;
+                let removed = namespaces
+                    .get_mut(&scope)
+                    .and_then(|entries| entries.remove(key))
+                    .is_some();
+                if !removed {
+                    return Ok(ToolOutput::llm_only(format!("Key '{}' was not set.", key)));
+                }
+                if namespaces.get(&scope).is_some_and(BTreeMap::is_empty) {
+                    namespaces.remove(&scope);
+                }
+                self.save(&namespaces)?;
+                Ok(ToolOutput::llm_only(format!("Key '{}' deleted.", key)))
+            }
+            "incr" => {
+                let key = key_arg(&args)?;
+                let by = args.get("by").and_then(Value::as_i64).unwrap_or(1);
+                let entries = namespaces.entry(scope).or_default();
+                let current = match entries.get(key) {
+                    None => 0,
+                    Some(value) => value.as_i64().ok_or_else(|| {
+                        ZeptoError::Tool(format!("Key '{}' does not hold an integer", key))
+                    })?,
+                };
+                let next = current
+                    .checked_add(by)
+                    .ok_or_else(|| ZeptoError::Tool(format!("Key '{}' would overflow", key)))?;
+                self.put(entries, key, json!(next))?;
+                self.save(&namespaces)?;
+                Ok(ToolOutput::llm_only(next.to_string()))
+            }
+            "list" => {
+                let prefix = args.get("prefix").and_then(Value::as_str).unwrap_or("");
+                let keys: Vec<&str> = namespaces
+                    .get(&scope)
+                    .into_iter()
+                    .flat_map(|entries| entries.keys())
+                    .filter(|k| k.starts_with(prefix))
+                    .map(String::as_str)
+                    .collect();
+                if keys.is_empty() {
+                    return Ok(ToolOutput::llm_only("No keys set."));
+                }
+                Ok(ToolOutput::llm_only(keys.join("\n")))
+            }
+            other => Err(ZeptoError::Tool(format!(
+                "Unknown action '{}'. Use get, set, delete, incr, or list.",
+                other
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_set_get_round_trip_per_scope() {
+        let dir = tempfile::tempdir().unwrap();
+        let tool = KvTool::new(dir.path().join("kv.json"));
+        let chat1 = ToolContext::new().with_channel("telegram", "1");
+        let chat2 = ToolContext::new().with_channel("telegram", "2");
+
+        tool.execute(
+            json!({"action": "set", "key": "last_seen", "value": {"id": 7, "ok": true}}),
+            &chat1,
+        )
+        .await
+        .unwrap();
+        let out = tool
+            .execute(json!({"action": "get", "key": "last_seen"}), &chat1)
+            .await
+            .unwrap();
+        assert_eq!(out.for_llm, r#"{"id":7,"ok":true}"#);
+
+        // Other conversations have their own namespace; global is shared.
+        let out = tool
+            .execute(json!({"action": "get", "key": "last_seen"}), &chat2)
+            .await
+            .unwrap();
+        assert!(out.for_llm.contains("not set"));
+        tool.execute(
+            json!({"action": "set", "key": "flag", "value": true, "scope": "global"}),
+            &chat1,
+        )
+        .await
+        .unwrap();
+        let out = tool
+            .execute(
+                json!({"action": "get", "key": "flag", "scope": "global"}),
+                &chat2,
+            )
+            .await
+            .unwrap();
+        assert_eq!(out.for_llm, "true");
+
+        // State survives a new tool instance (restart).
+        let tool = KvTool::new(dir.path().join("kv.json"));
+        let out = tool
+            .execute(json!({"action": "list"}), &chat1)
+            .await
+            .unwrap();
+        assert_eq!(out.for_llm, "last_seen");
+        tool.execute(json!({"action": "delete", "key": "last_seen"}), &chat1)
+            .await
+            .unwrap();
+        let out = tool
+            .execute(json!({"action": "list"}), &chat1)
+            .await
+            .unwrap();
+        assert_eq!(out.for_llm, "No keys set.");
+    }
+
+    #[tokio::test]
+    async fn test_incr_starts_new_keys_at_zero() {
+        let dir = tempfile::tempdir().unwrap();
+        let tool = KvTool::new(dir.path().join("kv.json"));
+        let ctx = ToolContext::new().with_channel("cli", "cli");
+
+        let out = tool
+            .execute(json!({"action": "incr", "key": "runs"}), &ctx)
+            .await
+            .unwrap();
+        assert_eq!(out.for_llm, "1");
+        let out = tool
+            .execute(json!({"action": "incr", "key": "runs", "by": 4}), &ctx)
+            .await
+            .unwrap();
+        assert_eq!(out.for_llm, "5");
+
+        tool.execute(json!({"action": "set", "key": "name", "value": "x"}), &ctx)
+            .await
+            .unwrap();
+        let err = tool
+            .execute(json!({"action": "incr", "key": "name"}), &ctx)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("does not hold an integer"));
+    }
+
+    #[tokio::test]
+    async fn test_size_caps_reject_large_values_and_extra_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let tool = KvTool::new(dir.path().join("kv.json"))
+            .with_max_value_bytes(16)
+            .with_max_keys(2);
+        let ctx = ToolContext::new().with_channel("cli", "cli");
+
+        let err = tool
+            .execute(
+                json!({"action": "set", "key": "big", "value": "x".repeat(32)}),
+                &ctx,
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("limit is 16 bytes"));
+
+        for key in ["a", "b"] {
+            tool.execute(json!({"action": "set", "key": key, "value": 1}), &ctx)
+                .await
+                .unwrap();
+        }
+        let err = tool
+            .execute(json!({"action": "set", "key": "c", "value": 1}), &ctx)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Key limit reached"));
+        // Overwriting an existing key is still allowed at the limit.
+        tool.execute(json!({"action": "set", "key": "a", "value": 2}), &ctx)
+            .await
+            .unwrap();
+    }
+}
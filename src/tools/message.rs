@@ -1,18 +1,27 @@
 //! Message tool for proactive outbound messages.
 //!
 //! Supports multiple action types:
-//! - `send` (default): Plain text message
+//! - `send` (default): Plain text message, optionally with workspace file
+//!   attachments (delivered by channels that support files, e.g. email)
 //! - `react`: Add emoji reaction (Discord only)
 //! - `rich_message`: Send Slack Block Kit message (Slack only)
 //! - `inline_keyboard`: Send inline keyboard buttons (Telegram only)
+//!
+//! Email goes only to the sender of the current email conversation or to
+//! addresses matching `channels.email.allowed_recipients`.
 
 use std::sync::Arc;
 
 use async_trait::async_trait;
 use serde_json::{json, Value};
 
-use crate::bus::{MessageBus, OutboundMessage};
+use crate::bus::{MediaAttachment, MediaType, MessageBus, OutboundMessage};
+use crate::channels::email_channel::{
+    address_matches, parse_address_list, EMAIL_BCC_METADATA_KEY, EMAIL_CC_METADATA_KEY,
+    EMAIL_TO_METADATA_KEY,
+};
 use crate::error::{Result, ZeptoError};
+use crate::security::validate_path_in_workspace;
 
 use super::{Tool, ToolCategory, ToolContext, ToolOutput};
 
@@ -25,8 +34,129 @@ const ALLOWED_CHANNELS: &[&str] = &[
     "whatsapp",
     "whatsapp_web",
     "whatsapp_cloud",
+    "email",
 ];
 
+/// Largest single file the tool will read for an attachment. Channels apply
+/// their own (usually smaller) limits on top of this.
+const MAX_ATTACHMENT_FILE_BYTES: u64 = 25 * 1024 * 1024;
+
+/// Guess a MIME type and media kind from a file extension.
+fn attachment_kind(path: &std::path::Path) -> (&'static str, MediaType) {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_ascii_lowercase())
+        .unwrap_or_default();
+    match ext.as_str() {
+        "pdf" => ("application/pdf", MediaType::Document),
+        "txt" | "log" => ("text/plain", MediaType::Document),
+        "md" => ("text/markdown", MediaType::Document),
+        "csv" => ("text/csv", MediaType::Document),
+        "json" => ("application/json", MediaType::Document),
+        "html" | "htm" => ("text/html", MediaType::Document),
+        "zip" => ("application/zip", MediaType::Document),
+        "docx" => (
+            "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+            MediaType::Document,
+        ),
+        "xlsx" => (
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+            MediaType::Document,
+        ),
+        "png" => ("image/png", MediaType::Image),
+        "jpg" | "jpeg" => ("image/jpeg", MediaType::Image),
+        "gif" => ("image/gif", MediaType::Image),
+        "webp" => ("image/webp", MediaType::Image),
+        "mp3" => ("audio/mpeg", MediaType::Audio),
+        "wav" => ("audio/wav", MediaType::Audio),
+        "mp4" => ("video/mp4", MediaType::Video),
+        _ => ("application/octet-stream", MediaType::Document),
+    }
+}
+
+/// Read an optional address list argument given as a string or an array of
+/// strings, returning it comma-joined (the form the email channel parses).
+fn address_list_arg(args: &Value, key: &str) -> Result<Option<String>> {
+    let list = match args.get(key) {
+        None | Some(Value::Null) => return Ok(None),
+        Some(Value::String(s)) => s.trim().to_string(),
+        Some(Value::Array(items)) => items
+            .iter()
+            .map(|v| {
+                v.as_str()
+                    .map(str::trim)
+                    .ok_or_else(|| ZeptoError::Tool(format!("'{key}' must contain only strings")))
+            })
+            .collect::<Result<Vec<_>>>()?
+            .join(","),
+        Some(_) => {
+            return Err(ZeptoError::Tool(format!(
+                "'{key}' must be a string or an array of addresses"
+            )))
+        }
+    };
+    Ok((!list.is_empty()).then_some(list))
+}
+
+/// Read workspace files listed in `attachments` into outbound attachments.
+async fn load_attachments(paths: &[Value], ctx: &ToolContext) -> Result<Vec<MediaAttachment>> {
+    if paths.is_empty() {
+        return Ok(Vec::new());
+    }
+    let workspace = ctx
+        .workspace
+        .as_deref()
+        .ok_or_else(|| ZeptoError::Tool("Attachments require a workspace directory".to_string()))?;
+
+    let mut out = Vec::with_capacity(paths.len());
+    for value in paths {
+        let raw = value
+            .as_str()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .ok_or_else(|| {
+                ZeptoError::Tool("'attachments' must be an array of file paths".to_string())
+            })?;
+        let safe = validate_path_in_workspace(raw, workspace)?;
+        let path = safe.as_path();
+
+        let meta = tokio::fs::metadata(path)
+            .await
+            .map_err(|e| ZeptoError::Tool(format!("Cannot read attachment '{}': {}", raw, e)))?;
+        if !meta.is_file() {
+            return Err(ZeptoError::Tool(format!(
+                "Attachment '{}' is not a file",
+                raw
+            )));
+        }
+        if meta.len() > MAX_ATTACHMENT_FILE_BYTES {
+            return Err(ZeptoError::Tool(format!(
+                "Attachment '{}' is too large ({} bytes, max {})",
+                raw,
+                meta.len(),
+                MAX_ATTACHMENT_FILE_BYTES
+            )));
+        }
+
+        let data = tokio::fs::read(path)
+            .await
+            .map_err(|e| ZeptoError::Tool(format!("Cannot read attachment '{}': {}", raw, e)))?;
+        let (mime, media_type) = attachment_kind(path);
+        let filename = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("attachment");
+        out.push(
+            MediaAttachment::new(media_type)
+                .with_filename(filename)
+                .with_mime_type(mime)
+                .with_data(data),
+        );
+    }
+    Ok(out)
+}
+
 /// Tool for sending outbound messages to channels.
 ///
 /// Supports plain text sends as well as channel-specific rich actions
@@ -34,12 +164,46 @@ const ALLOWED_CHANNELS: &[&str] = &[
 /// keyboards (Telegram).
 pub struct MessageTool {
     bus: Arc<MessageBus>,
+    /// Email addresses/domains allowed besides the current conversation's sender.
+    email_recipients: Vec<String>,
 }
 
 impl MessageTool {
     /// Create a new message tool.
     pub fn new(bus: Arc<MessageBus>) -> Self {
-        Self { bus }
+        Self {
+            bus,
+            email_recipients: Vec::new(),
+        }
+    }
+
+    /// Allow emailing addresses matching this list (`allowed_senders` syntax).
+    pub fn with_email_recipients(mut self, recipients: Vec<String>) -> Self {
+        self.email_recipients = recipients;
+        self
+    }
+
+    /// Reject email recipients that are neither the sender being replied to
+    /// nor covered by the configured allowlist.
+    fn check_email_recipients<'a>(
+        &self,
+        ctx: &ToolContext,
+        recipients: impl IntoIterator<Item = &'a str>,
+    ) -> Result<()> {
+        let replying_to = ctx
+            .chat_id
+            .as_deref()
+            .filter(|_| ctx.channel.as_deref() == Some("email"));
+        for addr in recipients {
+            let is_reply = replying_to.is_some_and(|r| r.trim().eq_ignore_ascii_case(addr));
+            if !is_reply && !address_matches(&self.email_recipients, addr) {
+                return Err(ZeptoError::Tool(format!(
+                    "Email recipient '{}' is not the current sender and not in channels.email.allowed_recipients",
+                    addr
+                )));
+            }
+        }
+        Ok(())
     }
 }
 
@@ -65,6 +229,11 @@ impl Tool for MessageTool {
         ToolCategory::Messaging
     }
 
+    fn is_idempotent(&self, args: &Value) -> bool {
+        // Repeating a reaction is harmless; every other action posts a message.
+        args.get("action").and_then(|v| v.as_str()) == Some("react")
+    }
+
     fn parameters(&self) -> Value {
         json!({
             "type": "object",
@@ -75,7 +244,7 @@ impl Tool for MessageTool {
                 },
                 "channel": {
                     "type": "string",
-                    "description": "Destination channel name (telegram, discord, slack, whatsapp_web, webhook). Omit when replying — the originating channel is used automatically."
+                    "description": "Destination channel name (telegram, discord, slack, whatsapp_web, webhook, email). Omit when replying — the originating channel is used automatically."
                 },
                 "chat_id": {
                     "type": "string",
@@ -85,6 +254,11 @@ impl Tool for MessageTool {
                     "type": "string",
                     "description": "Optional message ID to reply to (send action only)."
                 },
+                "attachments": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Workspace file paths to attach (send action only; delivered by channels with file support, e.g. email)."
+                },
                 "discord_thread_name": {
                     "type": "string",
                     "description": "Discord only: create a thread in this channel with this name (send action only)."
@@ -97,6 +271,21 @@ impl Tool for MessageTool {
                     "type": "integer",
                     "description": "Discord only: auto archive duration in minutes for new thread (send action only)."
                 },
+                "email_to": {
+                    "type": ["string", "array"],
+                    "items": { "type": "string" },
+                    "description": "Email only: extra To addresses besides chat_id (send action only)."
+                },
+                "email_cc": {
+                    "type": ["string", "array"],
+                    "items": { "type": "string" },
+                    "description": "Email only: Cc addresses (send action only)."
+                },
+                "email_bcc": {
+                    "type": ["string", "array"],
+                    "items": { "type": "string" },
+                    "description": "Email only: Bcc addresses (send action only)."
+                },
                 "action": {
                     "type": "string",
                     "description": "Action to perform. Default: 'send'. Options: 'send', 'react', 'rich_message', 'inline_keyboard'",
@@ -164,6 +353,17 @@ impl Tool for MessageTool {
             })
             .map(|n| n.to_string());
 
+        let mut email_recipients = Vec::new();
+        for key in [
+            EMAIL_TO_METADATA_KEY,
+            EMAIL_CC_METADATA_KEY,
+            EMAIL_BCC_METADATA_KEY,
+        ] {
+            if let Some(list) = address_list_arg(&args, key)? {
+                email_recipients.push((key, list));
+            }
+        }
+
         // Validate channel name: only allow known channel types to prevent
         // the LLM from targeting arbitrary/unexpected channels.
         if !ALLOWED_CHANNELS
@@ -203,6 +403,42 @@ impl Tool for MessageTool {
                     .to_string(),
             ));
         }
+        if !email_recipients.is_empty() {
+            if !channel.eq_ignore_ascii_case("email") {
+                return Err(ZeptoError::Tool(
+                    "email_to/email_cc/email_bcc require channel='email'".to_string(),
+                ));
+            }
+            if action != "send" {
+                return Err(ZeptoError::Tool(
+                    "email_to/email_cc/email_bcc are only supported with action='send'".to_string(),
+                ));
+            }
+        }
+        if channel.eq_ignore_ascii_case("email") {
+            let extra: Vec<String> = email_recipients
+                .iter()
+                .flat_map(|(_, list)| parse_address_list(list))
+                .collect();
+            self.check_email_recipients(
+                ctx,
+                std::iter::once(chat_id.trim()).chain(extra.iter().map(String::as_str)),
+            )?;
+        }
+        let attachment_paths = match args.get("attachments") {
+            None | Some(Value::Null) => Vec::new(),
+            Some(Value::Array(items)) => items.clone(),
+            Some(_) => {
+                return Err(ZeptoError::Tool(
+                    "'attachments' must be an array of file paths".to_string(),
+                ))
+            }
+        };
+        if !attachment_paths.is_empty() && action != "send" {
+            return Err(ZeptoError::Tool(
+                "attachments are only supported with action='send'".to_string(),
+            ));
+        }
 
         match action {
             "send" => {
@@ -224,6 +460,12 @@ impl Tool for MessageTool {
                         auto_archive_minutes,
                     );
                 }
+                for (key, list) in &email_recipients {
+                    outbound = outbound.with_metadata(key, list);
+                }
+                for attachment in load_attachments(&attachment_paths, ctx).await? {
+                    outbound = outbound.with_attachment(attachment);
+                }
 
                 self.bus
                     .publish_outbound(outbound)
@@ -362,6 +604,16 @@ mod tests {
         assert!(tool.description().contains("inline_keyboard"));
     }
 
+    #[test]
+    fn test_message_tool_send_is_not_idempotent() {
+        let tool = MessageTool::new(Arc::new(MessageBus::new()));
+
+        assert!(!tool.is_idempotent(&json!({"content": "hi"})));
+        assert!(!tool.is_idempotent(&json!({"content": "hi", "action": "send"})));
+        assert!(!tool.is_idempotent(&json!({"action": "rich_message"})));
+        assert!(tool.is_idempotent(&json!({"action": "react", "emoji": "👍"})));
+    }
+
     #[tokio::test]
     async fn test_message_tool_with_context_target() {
         let bus = Arc::new(MessageBus::new());
@@ -573,6 +825,95 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_message_tool_sets_email_recipients() {
+        let bus = Arc::new(MessageBus::new());
+        let tool =
+            MessageTool::new(bus.clone()).with_email_recipients(vec!["@example.com".to_string()]);
+
+        let result = tool
+            .execute(
+                json!({
+                    "content": "Status update",
+                    "channel": "email",
+                    "chat_id": "a@example.com",
+                    "email_cc": ["b@example.com", "c@example.com"],
+                    "email_bcc": "d@example.com"
+                }),
+                &ToolContext::new(),
+            )
+            .await;
+
+        assert!(result.is_ok());
+        let outbound = bus.consume_outbound().await.expect("outbound message");
+        assert_eq!(
+            outbound.metadata.get("email_cc").map(String::as_str),
+            Some("b@example.com,c@example.com")
+        );
+        assert_eq!(
+            outbound.metadata.get("email_bcc").map(String::as_str),
+            Some("d@example.com")
+        );
+        assert!(!outbound.metadata.contains_key("email_to"));
+    }
+
+    #[tokio::test]
+    async fn test_message_tool_email_recipients_require_allowlist() {
+        let bus = Arc::new(MessageBus::new());
+        let tool = MessageTool::new(bus.clone())
+            .with_email_recipients(vec!["team@example.com".to_string()]);
+        let ctx = ToolContext::new().with_channel("email", "alice@example.org");
+
+        // Replying to the current sender needs no allowlist entry.
+        let reply = tool
+            .execute(
+                json!({"content": "Thanks", "email_cc": "team@example.com"}),
+                &ctx,
+            )
+            .await;
+        assert!(reply.is_ok());
+        bus.consume_outbound().await.expect("outbound message");
+
+        for args in [
+            json!({"content": "x", "chat_id": "eve@evil.test"}),
+            json!({"content": "x", "email_bcc": ["eve@evil.test"]}),
+        ] {
+            let err = tool.execute(args, &ctx).await.unwrap_err().to_string();
+            assert!(err.contains("eve@evil.test"), "{err}");
+        }
+
+        // Outside an email conversation the sender is not implicitly allowed.
+        let err = tool
+            .execute(
+                json!({"content": "x", "channel": "email", "chat_id": "alice@example.org"}),
+                &ToolContext::new(),
+            )
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("allowed_recipients"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_message_tool_email_recipients_require_email_channel() {
+        let bus = Arc::new(MessageBus::new());
+        let tool = MessageTool::new(bus);
+
+        let result = tool
+            .execute(
+                json!({
+                    "content": "hi",
+                    "channel": "slack",
+                    "chat_id": "C1",
+                    "email_cc": "b@example.com"
+                }),
+                &ToolContext::new(),
+            )
+            .await;
+
+        assert!(result.unwrap_err().to_string().contains("channel='email'"));
+    }
+
     #[tokio::test]
     async fn test_message_tool_discord_thread_rejects_non_discord_channel() {
         let bus = Arc::new(MessageBus::new());
@@ -921,4 +1262,92 @@ mod tests {
         assert!(err.contains("rich_message"));
         assert!(err.contains("inline_keyboard"));
     }
+
+    #[tokio::test]
+    async fn test_message_tool_email_with_attachment() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("report.pdf"), b"%PDF-1.7").unwrap();
+        let bus = Arc::new(MessageBus::new());
+        let tool = MessageTool::new(bus.clone());
+        let ctx = ToolContext::new()
+            .with_channel("email", "me@example.com")
+            .with_workspace(dir.path().to_str().unwrap());
+
+        let result = tool
+            .execute(
+                json!({
+                    "content": "Report attached",
+                    "channel": "email",
+                    "chat_id": "me@example.com",
+                    "attachments": ["report.pdf"]
+                }),
+                &ctx,
+            )
+            .await;
+
+        assert!(result.is_ok());
+        let outbound = bus.consume_outbound().await.expect("outbound message");
+        assert_eq!(outbound.channel, "email");
+        assert_eq!(outbound.attachments.len(), 1);
+        let att = &outbound.attachments[0];
+        assert_eq!(att.filename.as_deref(), Some("report.pdf"));
+        assert_eq!(att.mime_type.as_deref(), Some("application/pdf"));
+        assert_eq!(att.data.as_deref(), Some(&b"%PDF-1.7"[..]));
+    }
+
+    #[tokio::test]
+    async fn test_message_tool_attachment_outside_workspace_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let bus = Arc::new(MessageBus::new());
+        let tool = MessageTool::new(bus);
+        let ctx = ToolContext::new()
+            .with_channel("email", "me@example.com")
+            .with_workspace(dir.path().to_str().unwrap());
+
+        let result = tool
+            .execute(
+                json!({
+                    "content": "x",
+                    "channel": "email",
+                    "chat_id": "me@example.com",
+                    "attachments": ["../../etc/passwd"]
+                }),
+                &ctx,
+            )
+            .await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_message_tool_attachments_require_send_action() {
+        let bus = Arc::new(MessageBus::new());
+        let tool = MessageTool::new(bus);
+
+        let result = tool
+            .execute(
+                json!({
+                    "content": "x",
+                    "channel": "discord",
+                    "chat_id": "1",
+                    "action": "react",
+                    "attachments": ["a.txt"]
+                }),
+                &ToolContext::new(),
+            )
+            .await;
+
+        assert!(result.unwrap_err().to_string().contains("attachments"));
+    }
+
+    #[test]
+    fn test_attachment_kind_from_extension() {
+        use std::path::Path;
+        assert_eq!(attachment_kind(Path::new("a.PDF")).0, "application/pdf");
+        assert_eq!(attachment_kind(Path::new("shot.png")).1, MediaType::Image);
+        assert_eq!(
+            attachment_kind(Path::new("blob")).0,
+            "application/octet-stream"
+        );
+    }
 }
@@ -27,6 +27,13 @@
 //! - `MessageTool`: Send proactive outbound chat messages
 //! - `MemorySearchTool`: Search workspace markdown memory files
 //! - `MemoryGetTool`: Read memory files with line windows
+//! - `IndexWorkspaceTool` / `RagQueryTool`: Embedding search over workspace files
+//! - `CompactSessionTool`: Summarize old conversation turns on demand
+//! - `PinTool`: Pin notes that survive history truncation and compaction
+//! - `UseHandTool`: Switch the active hand for the current conversation
+//! - `CacheTool`: Response cache stats and purging
+//! - `PreferencesTool`: Per-user persistent preferences (tone, units, language)
+//! - `KvTool`: Small per-conversation or global key-value state
 //! - `WhatsAppTool`: Send WhatsApp Cloud API messages
 //! - `GoogleSheetsTool`: Read and write Google Sheets ranges
 //! - `R8rTool`: Execute r8r workflows for deterministic automation
@@ -59,9 +66,13 @@
 #[cfg(feature = "android")]
 pub mod android;
 pub mod approval;
+pub mod approval_store;
+pub mod arg_validation;
 pub mod binary_plugin;
 pub mod browser;
+pub mod cache;
 pub mod clarification;
+pub mod compact_session;
 pub mod composed;
 pub mod cron;
 pub mod custom;
@@ -77,28 +88,39 @@ pub mod grep;
 pub mod gsheets;
 pub mod hardware;
 pub mod http_request;
+pub mod kv;
 pub mod longterm_memory;
 pub mod mcp;
 pub mod memory;
 pub mod message;
 pub mod output;
+pub mod partial;
 pub mod pdf_read;
+pub mod pin;
 pub mod plugin;
+pub mod preferences;
 pub mod project;
 pub mod r8r;
+pub mod rag;
 mod registry;
 pub mod reminder;
+pub mod resource_lock;
+pub mod risk;
 #[cfg(feature = "screenshot")]
 pub mod screenshot;
+pub mod serial;
 pub mod shell;
 pub mod skills_install;
 pub mod skills_search;
 pub mod spawn;
 pub mod stripe;
+pub mod suggest;
 #[cfg(feature = "panel")]
 pub mod task;
 pub mod transcribe;
 mod types;
+pub mod use_hand;
+pub mod user_output;
 pub mod web;
 pub mod whatsapp;
 
@@ -106,7 +128,9 @@ pub mod whatsapp;
 pub use android::AndroidTool;
 pub use binary_plugin::BinaryPluginTool;
 pub use browser::BrowserTool;
+pub use cache::CacheTool;
 pub use clarification::AskClarificationTool;
+pub use compact_session::CompactSessionTool;
 pub use composed::{ComposedTool, CreateToolTool};
 pub use custom::CustomTool;
 pub use delegate::DelegateTool;
@@ -119,23 +143,30 @@ pub use grep::GrepTool;
 pub use gsheets::GoogleSheetsTool;
 pub use hardware::HardwareTool;
 pub use http_request::HttpRequestTool;
+pub use kv::KvTool;
 pub use longterm_memory::LongTermMemoryTool;
 pub use memory::{MemoryGetTool, MemorySearchTool};
 pub use message::MessageTool;
 pub use pdf_read::PdfReadTool;
+pub use pin::PinTool;
+pub use preferences::PreferencesTool;
 pub use project::ProjectTool;
 pub use r8r::R8rTool;
+pub use rag::{IndexWorkspaceTool, RagQueryTool};
 pub use registry::ToolRegistry;
 pub use reminder::ReminderTool;
 #[cfg(feature = "screenshot")]
 pub use screenshot::WebScreenshotTool;
+#[cfg(feature = "hardware")]
+pub use serial::SerialTool;
 pub use skills_install::InstallSkillTool;
 pub use skills_search::FindSkillsTool;
 pub use stripe::StripeTool;
 #[cfg(feature = "panel")]
 pub use task::TaskTool;
 pub use transcribe::TranscribeTool;
-pub use types::{Tool, ToolCategory, ToolContext, ToolOutput};
+pub use use_hand::UseHandTool;
+pub use types::{Tool, ToolCategory, ToolChunk, ToolContext, ToolOutput};
 pub use web::{
     is_blocked_host, resolve_and_check_host, DdgSearchTool, SearxngSearchTool, WebFetchTool,
     WebSearchTool,
@@ -3,6 +3,13 @@
 //! Tools that produce potentially large output (shell commands, file reads, etc.)
 //! should use [`truncate_tool_output`] to cap output size before returning it to
 //! the LLM. This prevents context window exhaustion from runaway commands.
+//!
+//! Tools whose output the model may need in full (`web_fetch`, `grep`, the
+//! Android `shell` action) page it instead: they accept an `offset` argument
+//! ([`offset_schema`], [`offset_arg`]) and return one [`Page`] at a time. A
+//! truncated page ends with a marker giving the offset to pass next, e.g.
+//! `[output truncated: bytes 0-16384 of 40000 shown. Call grep again with the
+//! same arguments and "offset": 16384 to continue.]`
 
 /// Default maximum number of lines before truncation.
 pub const DEFAULT_MAX_LINES: usize = 2_000;
@@ -10,6 +17,103 @@ pub const DEFAULT_MAX_LINES: usize = 2_000;
 /// Default maximum number of bytes before truncation.
 pub const DEFAULT_MAX_BYTES: usize = 50_000;
 
+/// Default page size for paged tool output. Kept under the agent loop's
+/// default per-result clamp so a page normally reaches the model whole.
+pub const DEFAULT_PAGE_BYTES: usize = 16_384;
+
+/// JSON schema for the `offset` argument of paged tools.
+pub fn offset_schema() -> serde_json::Value {
+    serde_json::json!({
+        "type": "integer",
+        "minimum": 0,
+        "description": "Continue a truncated result from this byte offset \
+                        (given in the truncation note of the previous result)"
+    })
+}
+
+/// Read the `offset` argument; missing or invalid values mean 0.
+pub fn offset_arg(args: &serde_json::Value) -> usize {
+    args.get("offset")
+        .and_then(serde_json::Value::as_u64)
+        .map_or(0, |v| v as usize)
+}
+
+/// One page of a larger output.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Page<'a> {
+    /// The page's text.
+    pub text: &'a str,
+    /// Byte offset of the page within the full output.
+    pub offset: usize,
+    /// Offset of the next page, if the output continues.
+    pub next_offset: Option<usize>,
+    /// Length of the full output in bytes.
+    pub total: usize,
+}
+
+impl Page<'_> {
+    /// Cut the page of `output` starting at `offset`, at most `max_bytes` long.
+    ///
+    /// Offsets are moved back to a char boundary. When the output continues
+    /// past the page, the cut is made after the last newline in the page's
+    /// second half, so line-oriented output is not split mid-line.
+    pub fn of(output: &str, offset: usize, max_bytes: usize) -> Page<'_> {
+        let total = output.len();
+        let start = floor_char_boundary(output, offset.min(total));
+        let mut end =
+            floor_char_boundary(output, start.saturating_add(max_bytes.max(1)).min(total));
+        if end < total {
+            let window = &output[start..end];
+            if let Some(newline) = window.rfind('\n').filter(|&i| i >= window.len() / 2) {
+                end = start + newline + 1;
+            }
+            if end == start {
+                // A single char wider than the page: take it whole.
+                end = start + output[start..].chars().next().map_or(0, char::len_utf8);
+            }
+        }
+        Page {
+            text: &output[start..end],
+            offset: start,
+            next_offset: (end < total).then_some(end),
+            total,
+        }
+    }
+
+    /// Continuation note for a truncated page, or `None` for the last page.
+    pub fn marker(&self, tool: &str) -> Option<String> {
+        if self.offset >= self.total && self.total > 0 {
+            return Some(format!(
+                "[offset {} is past the end of the output ({} bytes)]",
+                self.offset, self.total
+            ));
+        }
+        self.next_offset.map(|next| {
+            format!(
+                "[output truncated: bytes {}-{} of {} shown. Call {} again with the same \
+                 arguments and \"offset\": {} to continue.]",
+                self.offset, next, self.total, tool, next
+            )
+        })
+    }
+
+    /// The page text followed by its continuation note, if any.
+    pub fn render(&self, tool: &str) -> String {
+        match self.marker(tool) {
+            Some(marker) if self.text.is_empty() => marker,
+            Some(marker) => format!("{}\n{}", self.text.trim_end_matches('\n'), marker),
+            None => self.text.to_string(),
+        }
+    }
+}
+
+fn floor_char_boundary(s: &str, mut index: usize) -> usize {
+    while index > 0 && !s.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
 /// Truncate tool output that exceeds either a line count or byte count limit.
 ///
 /// Iterates lines preserving original line endings (via `split_inclusive('\n')`),
@@ -168,6 +272,56 @@ mod tests {
         assert!(result2.contains("[output truncated at 2 lines"));
     }
 
+    #[test]
+    fn page_marker_offset_returns_next_chunk() {
+        let input: String = (0..100).map(|i| format!("line {i:03}\n")).collect();
+        assert_eq!(input.len(), 900);
+
+        let first = Page::of(&input, 0, 400);
+        // Cut after the last full line that fits: 44 lines of 9 bytes.
+        assert_eq!(first.text.len(), 396);
+        assert_eq!(first.next_offset, Some(396));
+        let rendered = first.render("grep");
+        assert!(rendered.ends_with(
+            "[output truncated: bytes 0-396 of 900 shown. Call grep again with the same \
+             arguments and \"offset\": 396 to continue.]"
+        ));
+
+        let second = Page::of(&input, 396, 400);
+        assert!(second.text.starts_with("line 044\n"));
+        let last = Page::of(&input, 792, 400);
+        assert_eq!(last.text, &input[792..]);
+        assert!(last.marker("grep").is_none());
+        assert_eq!(last.render("grep"), &input[792..]);
+
+        // Pages tile the output exactly.
+        let mut offset = 0;
+        let mut rebuilt = String::new();
+        while let Some(next) = {
+            let page = Page::of(&input, offset, 100);
+            rebuilt.push_str(page.text);
+            page.next_offset
+        } {
+            offset = next;
+        }
+        assert_eq!(rebuilt, input);
+
+        assert!(Page::of(&input, 5_000, 400)
+            .render("grep")
+            .contains("past the end"));
+        assert_eq!(offset_arg(&serde_json::json!({"offset": 396})), 396);
+        assert_eq!(offset_arg(&serde_json::json!({})), 0);
+    }
+
+    #[test]
+    fn page_respects_char_boundaries() {
+        let input = "\u{1F600}".repeat(4);
+        let page = Page::of(&input, 2, 6);
+        assert_eq!(page.offset, 0);
+        assert_eq!(page.text, "\u{1F600}");
+        assert_eq!(page.next_offset, Some(4));
+    }
+
     #[test]
     fn byte_trailer_accurate_after_backtrack() {
         // 4 emojis, each 4 bytes = 16 bytes total. Byte limit 6 falls mid-emoji.
@@ -0,0 +1,126 @@
+//! Per-target results for tools that act on several targets in one call.
+//!
+//! A multi-target action (free/busy across calendars, reading a batch of
+//! messages) should not fail as a whole because one target is unreachable.
+//! Collect each target's outcome in [`PartialResults`] and report a summary
+//! of what succeeded and what failed; the call only errors when every target
+//! failed.
+
+use crate::error::{Result, ZeptoError};
+
+/// Outcome for one target of a multi-target action.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TargetOutcome {
+    /// Target identifier (calendar ID, message ID, ...)
+    pub target: String,
+    /// Rendered result on success, error message on failure
+    pub result: std::result::Result<String, String>,
+}
+
+/// Ordered per-target outcomes of a multi-target action.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PartialResults {
+    outcomes: Vec<TargetOutcome>,
+}
+
+impl PartialResults {
+    /// Create an empty result set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a successful target with its rendered result.
+    pub fn success(&mut self, target: impl Into<String>, detail: impl Into<String>) {
+        self.outcomes.push(TargetOutcome {
+            target: target.into(),
+            result: Ok(detail.into()),
+        });
+    }
+
+    /// Record a failed target with its error message.
+    pub fn failure(&mut self, target: impl Into<String>, error: impl Into<String>) {
+        self.outcomes.push(TargetOutcome {
+            target: target.into(),
+            result: Err(error.into()),
+        });
+    }
+
+    /// All recorded outcomes, in insertion order.
+    pub fn outcomes(&self) -> &[TargetOutcome] {
+        &self.outcomes
+    }
+
+    /// Number of successful targets.
+    pub fn succeeded(&self) -> usize {
+        self.outcomes.iter().filter(|o| o.result.is_ok()).count()
+    }
+
+    /// Number of failed targets.
+    pub fn failed(&self) -> usize {
+        self.outcomes.len() - self.succeeded()
+    }
+
+    /// Render the outcomes under `heading`, successes first, then failures.
+    pub fn render(&self, heading: &str) -> String {
+        let mut lines = vec![heading.to_string()];
+        for outcome in &self.outcomes {
+            if let Ok(detail) = &outcome.result {
+                lines.push(detail.clone());
+            }
+        }
+        if self.failed() > 0 {
+            lines.push(format!("Failed ({}):", self.failed()));
+            for outcome in &self.outcomes {
+                if let Err(error) = &outcome.result {
+                    lines.push(format!("  {}: {}", outcome.target, error));
+                }
+            }
+        }
+        lines.push(format!(
+            "Summary: {} succeeded, {} failed",
+            self.succeeded(),
+            self.failed()
+        ));
+        lines.join("\n")
+    }
+
+    /// Rendered text, or an error when there were targets and all failed.
+    pub fn into_result(self, heading: &str) -> Result<String> {
+        let rendered = self.render(heading);
+        if !self.outcomes.is_empty() && self.succeeded() == 0 {
+            return Err(ZeptoError::Tool(rendered));
+        }
+        Ok(rendered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_lists_failures_after_successes() {
+        let mut results = PartialResults::new();
+        results.success("a", "  a: ok");
+        results.failure("b", "forbidden");
+        results.success("c", "  c: ok");
+
+        assert_eq!(results.succeeded(), 2);
+        assert_eq!(results.failed(), 1);
+        assert_eq!(
+            results.render("Heading:"),
+            "Heading:\n  a: ok\n  c: ok\nFailed (1):\n  b: forbidden\nSummary: 2 succeeded, 1 failed"
+        );
+        assert!(results.into_result("Heading:").is_ok());
+    }
+
+    #[test]
+    fn test_into_result_errors_only_when_all_fail() {
+        let mut results = PartialResults::new();
+        results.failure("a", "gone");
+        let err = results.into_result("Heading:").unwrap_err();
+        assert!(err.to_string().contains("a: gone"));
+
+        assert!(PartialResults::new().into_result("Heading:").is_ok());
+    }
+}
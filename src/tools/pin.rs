@@ -0,0 +1,237 @@
+//! Conversation context pinning.
+//!
+//! The `pin` tool keeps important context (project constraints, user facts)
+//! in the current session outside the rolling history. Pinned notes are
+//! re-injected right after the system prompt on every turn, so truncation
+//! and compaction of old messages never drop them.
+//!
+//! The tool edits the persisted session, so the agent loop saves the session
+//! before running it and reloads it afterwards.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+use crate::error::{Result, ZeptoError};
+use crate::session::{Role, SessionManager};
+
+use super::{Tool, ToolCategory, ToolContext, ToolOutput};
+
+/// Tool name, matched by the agent loop to sync the session around the call.
+pub const PIN_TOOL: &str = "pin";
+
+/// Pinned notes allowed per session when not configured.
+pub const DEFAULT_MAX_PINNED_NOTES: usize = 20;
+/// Longest note accepted, in characters.
+const MAX_NOTE_CHARS: usize = 2_000;
+
+/// Tool that pins and unpins notes on the current session.
+pub struct PinTool {
+    sessions: Arc<SessionManager>,
+    max_notes: usize,
+}
+
+impl PinTool {
+    /// Create the tool over the agent's session manager.
+    pub fn new(sessions: Arc<SessionManager>) -> Self {
+        Self {
+            sessions,
+            max_notes: DEFAULT_MAX_PINNED_NOTES,
+        }
+    }
+
+    /// Cap the number of pinned notes per session.
+    pub fn with_max_notes(mut self, max_notes: usize) -> Self {
+        self.max_notes = max_notes;
+        self
+    }
+}
+
+/// Text of the note to pin: explicit `text`, or the content of the history
+/// message at `message_index`.
+fn note_text(args: &Value, messages: &[crate::session::Message]) -> Result<String> {
+    if let Some(text) = args.get("text").and_then(Value::as_str) {
+        return Ok(text.trim().to_string());
+    }
+    let Some(index) = args.get("message_index").and_then(Value::as_u64) else {
+        return Err(ZeptoError::Tool(
+            "pin requires 'text' or 'message_index'".to_string(),
+        ));
+    };
+    let message = messages
+        .get(index as usize)
+        .filter(|m| m.role != Role::Tool)
+        .ok_or_else(|| {
+            ZeptoError::Tool(format!(
+                "No pinnable message at index {} ({} message(s) in history)",
+                index,
+                messages.len()
+            ))
+        })?;
+    Ok(format!("{}: {}", message.role, message.content.trim()))
+}
+
+#[async_trait]
+impl Tool for PinTool {
+    fn name(&self) -> &str {
+        PIN_TOOL
+    }
+
+    fn description(&self) -> &str {
+        "Pin important context (constraints, user facts, decisions) to this conversation so it is never dropped when old messages are truncated or summarized. Actions: pin a note or a history message, unpin by ID, or list pinned notes."
+    }
+
+    fn compact_description(&self) -> &str {
+        "Pin/unpin conversation context"
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::Memory
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["pin", "unpin", "list"],
+                    "description": "What to do (default: pin)"
+                },
+                "text": {
+                    "type": "string",
+                    "description": "Note to pin"
+                },
+                "message_index": {
+                    "type": "integer",
+                    "description": "Pin the content of this history message (0-based) instead of a note"
+                },
+                "id": {
+                    "type": "integer",
+                    "description": "ID of the pinned note to remove (for unpin)"
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<ToolOutput> {
+        let (Some(channel), Some(chat_id)) = (ctx.channel.as_deref(), ctx.chat_id.as_deref())
+        else {
+            return Err(ZeptoError::Tool(
+                "pin requires a channel and chat context".to_string(),
+            ));
+        };
+        let key = format!("{}:{}", channel, chat_id);
+        let mut session = self.sessions.get_or_create(&key).await?;
+
+        match args.get("action").and_then(Value::as_str).unwrap_or("pin") {
+            "pin" => {
+                let text = note_text(&args, &session.messages)?;
+                if text.is_empty() {
+                    return Err(ZeptoError::Tool("Cannot pin an empty note".to_string()));
+                }
+                if text.chars().count() > MAX_NOTE_CHARS {
+                    return Err(ZeptoError::Tool(format!(
+                        "Note too long to pin (max {} characters)",
+                        MAX_NOTE_CHARS
+                    )));
+                }
+                if session.pinned.len() >= self.max_notes {
+                    return Err(ZeptoError::Tool(format!(
+                        "Pin limit reached ({} notes). Unpin something first.",
+                        self.max_notes
+                    )));
+                }
+                let id = session.pin(&text);
+                self.sessions.save(&session).await?;
+                Ok(ToolOutput::llm_only(format!("Pinned note {}.", id)))
+            }
+            "unpin" => {
+                let id = args
+                    .get("id")
+                    .and_then(Value::as_u64)
+                    .ok_or_else(|| ZeptoError::Tool("unpin requires 'id'".to_string()))?;
+                let removed = u32::try_from(id).is_ok_and(|id| session.unpin(id));
+                if !removed {
+                    return Err(ZeptoError::Tool(format!("No pinned note with ID {}", id)));
+                }
+                self.sessions.save(&session).await?;
+                Ok(ToolOutput::llm_only(format!("Unpinned note {}.", id)))
+            }
+            "list" => Ok(ToolOutput::llm_only(
+                session
+                    .pinned_context()
+                    .unwrap_or_else(|| "No pinned notes.".to_string()),
+            )),
+            other => Err(ZeptoError::Tool(format!(
+                "Unknown action '{}'. Use pin, unpin or list.",
+                other
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::compaction::try_recover_context;
+    use crate::session::Message;
+
+    fn ctx() -> ToolContext {
+        ToolContext::new().with_channel("telegram", "42")
+    }
+
+    #[tokio::test]
+    async fn test_pin_list_unpin_round_trip() {
+        let sessions = Arc::new(SessionManager::new_memory());
+        let tool = PinTool::new(Arc::clone(&sessions)).with_max_notes(1);
+
+        let out = tool
+            .execute(json!({"text": "Budget is capped at $500"}), &ctx())
+            .await
+            .unwrap();
+        assert_eq!(out.for_llm, "Pinned note 1.");
+        let err = tool
+            .execute(json!({"text": "second"}), &ctx())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Pin limit"));
+
+        let out = tool
+            .execute(json!({"action": "list"}), &ctx())
+            .await
+            .unwrap();
+        assert!(out.for_llm.contains("[1] Budget is capped at $500"));
+
+        tool.execute(json!({"action": "unpin", "id": 1}), &ctx())
+            .await
+            .unwrap();
+        let session = sessions.get("telegram:42").await.unwrap().unwrap();
+        assert!(session.pinned.is_empty());
+    }
+
+    #[test]
+    fn test_pinned_notes_survive_truncation() {
+        let mut session = crate::session::Session::new("telegram:42");
+        session.add_message(Message::user("We must ship by Friday."));
+        session.pin("Deadline: ship by Friday");
+        for i in 0..40 {
+            session.add_message(Message::user(&format!("filler {} {}", i, "x".repeat(400))));
+            session.add_message(Message::assistant("ok"));
+        }
+
+        // The agent loop appends pinned context to the system prompt.
+        let system = format!("You are helpful.\n\n{}", session.pinned_context().unwrap());
+        let mut messages = vec![Message::system(&system)];
+        messages.extend(session.messages.clone());
+
+        let (recovered, tier) = try_recover_context(messages, 1_000, 4, 512, 1.0);
+        assert!(tier > 0);
+        assert!(!recovered
+            .iter()
+            .any(|m| m.content.contains("We must ship by Friday.")));
+        assert_eq!(recovered[0].role, Role::System);
+        assert!(recovered[0].content.contains("Deadline: ship by Friday"));
+    }
+}
@@ -0,0 +1,218 @@
+//! User preferences tool.
+//!
+//! Lets the user view and change their stored preferences (tone, units,
+//! language, timezone, preferred hand, free-form keys) conversationally.
+//! Preferences are keyed by the sender's identity, so they follow the user
+//! across sessions on the same channel.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+use crate::config::Config;
+use crate::error::{Result, ZeptoError};
+use crate::memory::preferences::{identity, PreferencesStore, KNOWN_KEYS};
+
+use super::{Tool, ToolCategory, ToolContext, ToolOutput};
+
+/// Tool for reading and updating the current user's preferences.
+pub struct PreferencesTool {
+    store: Arc<PreferencesStore>,
+    hands_dir: PathBuf,
+}
+
+impl PreferencesTool {
+    /// Create the tool over a shared preferences store.
+    pub fn new(store: Arc<PreferencesStore>) -> Self {
+        Self {
+            store,
+            hands_dir: Config::dir().join("hands"),
+        }
+    }
+
+    /// Directory searched for user-defined hands when validating `hand`.
+    pub fn with_hands_dir(mut self, dir: PathBuf) -> Self {
+        self.hands_dir = dir;
+        self
+    }
+}
+
+fn user_identity(ctx: &ToolContext) -> Result<String> {
+    let channel = ctx.channel.as_deref().unwrap_or("cli");
+    let sender = ctx
+        .sender_id
+        .as_deref()
+        .or(ctx.chat_id.as_deref())
+        .ok_or_else(|| ZeptoError::Tool("preferences require a sender context".to_string()))?;
+    Ok(identity(channel, sender))
+}
+
+fn required<'a>(args: &'a Value, field: &str) -> Result<&'a str> {
+    args.get(field)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| ZeptoError::Tool(format!("Missing '{}' parameter", field)))
+}
+
+#[async_trait]
+impl Tool for PreferencesTool {
+    fn name(&self) -> &str {
+        "preferences"
+    }
+
+    fn description(&self) -> &str {
+        "View or change the current user's persistent preferences. Known keys: tone (free text), units (metric|imperial), language (e.g. es, pt-BR), timezone (IANA name, e.g. Europe/Berlin), hand (researcher, coder, ...), usage_footer (on|off: show tokens and cost under each reply). Other keys are stored as free-form notes. Preferences apply to all future conversations with this user."
+    }
+
+    fn compact_description(&self) -> &str {
+        "User preferences"
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::Memory
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["get", "set", "unset"],
+                    "description": "get: list preferences, set: store key=value, unset: remove key"
+                },
+                "key": {
+                    "type": "string",
+                    "description": format!("Preference key ({} or a custom key)", KNOWN_KEYS.join(", "))
+                },
+                "value": {
+                    "type": "string",
+                    "description": "Value for set"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<ToolOutput> {
+        let id = user_identity(ctx)?;
+        let mut prefs = self.store.load(&id)?;
+
+        match required(&args, "action")? {
+            "get" => {
+                let entries = prefs.entries();
+                if entries.is_empty() {
+                    return Ok(ToolOutput::llm_only("No preferences set."));
+                }
+                let lines: Vec<String> = entries
+                    .iter()
+                    .map(|(k, v)| format!("- {}: {}", k, v))
+                    .collect();
+                Ok(ToolOutput::llm_only(format!(
+                    "Preferences:\n{}",
+                    lines.join("\n")
+                )))
+            }
+            "set" => {
+                let key = required(&args, "key")?;
+                let value = required(&args, "value")?;
+                if key.eq_ignore_ascii_case("hand")
+                    && crate::hands::resolve_hand(value, &self.hands_dir)?.is_none()
+                {
+                    return Err(ZeptoError::Tool(format!("Unknown hand '{}'", value)));
+                }
+                prefs.set(key, value)?;
+                self.store.save(&id, &prefs)?;
+                Ok(ToolOutput::llm_only(format!(
+                    "Preference '{}' saved. It applies from the next message.",
+                    key.to_ascii_lowercase()
+                )))
+            }
+            "unset" => {
+                let key = required(&args, "key")?;
+                if !prefs.unset(key) {
+                    return Ok(ToolOutput::llm_only(format!(
+                        "Preference '{}' was not set.",
+                        key
+                    )));
+                }
+                self.store.save(&id, &prefs)?;
+                Ok(ToolOutput::llm_only(format!(
+                    "Preference '{}' removed.",
+                    key
+                )))
+            }
+            other => Err(ZeptoError::Tool(format!(
+                "Unknown action '{}'. Use get, set, or unset.",
+                other
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::preferences::UnitSystem;
+
+    fn tool(dir: &std::path::Path) -> PreferencesTool {
+        PreferencesTool::new(Arc::new(PreferencesStore::new(dir.join("users"))))
+            .with_hands_dir(dir.join("hands"))
+    }
+
+    #[tokio::test]
+    async fn test_set_get_unset() {
+        let dir = tempfile::tempdir().unwrap();
+        let tool = tool(dir.path());
+        let ctx = ToolContext::new()
+            .with_channel("telegram", "chat1")
+            .with_sender("42");
+
+        tool.execute(
+            json!({"action": "set", "key": "units", "value": "imperial"}),
+            &ctx,
+        )
+        .await
+        .unwrap();
+        tool.execute(
+            json!({"action": "set", "key": "hand", "value": "researcher"}),
+            &ctx,
+        )
+        .await
+        .unwrap();
+
+        let stored = PreferencesStore::new(dir.path().join("users"))
+            .load("telegram:42")
+            .unwrap();
+        assert_eq!(stored.units, Some(UnitSystem::Imperial));
+        assert_eq!(stored.hand.as_deref(), Some("researcher"));
+
+        let out = tool.execute(json!({"action": "get"}), &ctx).await.unwrap();
+        assert!(out.for_llm.contains("- units: imperial"));
+
+        tool.execute(json!({"action": "unset", "key": "units"}), &ctx)
+            .await
+            .unwrap();
+        let out = tool.execute(json!({"action": "get"}), &ctx).await.unwrap();
+        assert!(!out.for_llm.contains("units"));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_hand_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let ctx = ToolContext::new()
+            .with_channel("cli", "cli")
+            .with_sender("me");
+        let err = tool(dir.path())
+            .execute(
+                json!({"action": "set", "key": "hand", "value": "juggler"}),
+                &ctx,
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Unknown hand"));
+    }
+}
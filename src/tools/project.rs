@@ -41,7 +41,7 @@ impl ProjectTool {
     /// Create a new ProjectTool from a `ProjectConfig`.
     pub fn new(config: ProjectConfig) -> Self {
         Self {
-            client: Client::new(),
+            client: crate::utils::http::default_client(),
             config,
         }
     }
@@ -60,10 +60,12 @@ impl R8rTool {
     /// # Arguments
     /// * `endpoint` - The r8r server endpoint (e.g., "http://localhost:8080")
     pub fn new(endpoint: &str) -> Self {
-        let client = match Client::builder()
-            .timeout(Duration::from_secs(DEFAULT_TIMEOUT_SECS))
-            .build()
-        {
+        let client = match crate::utils::http::client_builder().and_then(|builder| {
+            builder
+                .timeout(Duration::from_secs(DEFAULT_TIMEOUT_SECS))
+                .build()
+                .map_err(|e| ZeptoError::Tool(e.to_string()))
+        }) {
             Ok(client) => client,
             Err(error) => {
                 warn!(
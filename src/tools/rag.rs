@@ -0,0 +1,242 @@
+//! Workspace retrieval tools.
+//!
+//! Provides:
+//! - `index_workspace`: embed workspace files into a local vector index
+//!   (incremental — only files changed since the last pass are re-embedded).
+//! - `rag_query`: retrieve the chunks most similar to a question, with
+//!   `path#Lx-Ly` citations.
+//!
+//! Both tools share a [`WorkspaceIndex`] backed by the provider's `embed()`.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+use crate::error::{Result, ZeptoError};
+use crate::memory::workspace_index::WorkspaceIndex;
+
+use super::{Tool, ToolCategory, ToolContext, ToolOutput};
+
+const DEFAULT_TOP_K: usize = 5;
+const MAX_TOP_K: usize = 20;
+
+/// Tool that builds or refreshes the workspace embedding index.
+pub struct IndexWorkspaceTool {
+    index: Arc<WorkspaceIndex>,
+}
+
+impl IndexWorkspaceTool {
+    /// Create the tool over a shared index.
+    pub fn new(index: Arc<WorkspaceIndex>) -> Self {
+        Self { index }
+    }
+}
+
+/// Tool that answers questions by retrieving indexed workspace chunks.
+pub struct RagQueryTool {
+    index: Arc<WorkspaceIndex>,
+}
+
+impl RagQueryTool {
+    /// Create the tool over a shared index.
+    pub fn new(index: Arc<WorkspaceIndex>) -> Self {
+        Self { index }
+    }
+}
+
+fn workspace(ctx: &ToolContext) -> Result<&Path> {
+    ctx.workspace
+        .as_deref()
+        .map(Path::new)
+        .ok_or_else(|| ZeptoError::Tool("RAG tools require a workspace context".to_string()))
+}
+
+#[async_trait]
+impl Tool for IndexWorkspaceTool {
+    fn name(&self) -> &str {
+        "index_workspace"
+    }
+
+    fn description(&self) -> &str {
+        "Build or refresh the semantic search index over workspace files. Only files changed since the last run are re-embedded; paths in .zeptoignore are skipped."
+    }
+
+    fn compact_description(&self) -> &str {
+        "Index workspace"
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::FilesystemRead
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {}
+        })
+    }
+
+    async fn execute(&self, _args: Value, ctx: &ToolContext) -> Result<ToolOutput> {
+        let stats = self.index.refresh(workspace(ctx)?).await?;
+        Ok(ToolOutput::llm_only(format!(
+            "Workspace indexed: {} file(s) embedded, {} unchanged, {} removed; {} chunk(s) total.",
+            stats.indexed, stats.unchanged, stats.removed, stats.chunks
+        )))
+    }
+}
+
+#[async_trait]
+impl Tool for RagQueryTool {
+    fn name(&self) -> &str {
+        "rag_query"
+    }
+
+    fn description(&self) -> &str {
+        "Semantic search over indexed workspace files. Returns the most relevant chunks with file/line citations. Run index_workspace first."
+    }
+
+    fn compact_description(&self) -> &str {
+        "Search workspace"
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::FilesystemRead
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "query": {
+                    "type": "string",
+                    "description": "Question or topic to search for"
+                },
+                "top_k": {
+                    "type": "integer",
+                    "description": "Number of chunks to return (1-20, default: 5)"
+                }
+            },
+            "required": ["query"]
+        })
+    }
+
+    async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<ToolOutput> {
+        let query = args
+            .get("query")
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .ok_or_else(|| ZeptoError::Tool("Missing 'query' parameter".to_string()))?;
+        let top_k = args
+            .get("top_k")
+            .and_then(Value::as_u64)
+            .map(|v| (v as usize).clamp(1, MAX_TOP_K))
+            .unwrap_or(DEFAULT_TOP_K);
+
+        let workspace = workspace(ctx)?;
+        if !self.index.is_indexed(workspace).await {
+            return Ok(ToolOutput::llm_only(
+                "Workspace is not indexed yet. Run index_workspace first.",
+            ));
+        }
+
+        let hits = self.index.query(workspace, query, top_k).await?;
+        if hits.is_empty() {
+            return Ok(ToolOutput::llm_only(format!(
+                "No indexed content matched '{}'.",
+                query
+            )));
+        }
+
+        let mut output = format!("Top {} chunk(s) for '{}':\n\n", hits.len(), query);
+        for (i, hit) in hits.iter().enumerate() {
+            output.push_str(&format!(
+                "{}. [{}] (score {:.3})\n{}\n\n",
+                i + 1,
+                hit.citation(),
+                hit.score,
+                hit.text.trim()
+            ));
+        }
+        Ok(ToolOutput::llm_only(output.trim_end().to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::{ChatOptions, LLMProvider, LLMResponse, ToolDefinition};
+    use crate::session::Message;
+
+    struct LengthEmbedder;
+
+    #[async_trait]
+    impl LLMProvider for LengthEmbedder {
+        fn name(&self) -> &str {
+            "length"
+        }
+        fn default_model(&self) -> &str {
+            "length"
+        }
+        async fn chat(
+            &self,
+            _messages: Vec<Message>,
+            _tools: Vec<ToolDefinition>,
+            _model: Option<&str>,
+            _options: ChatOptions,
+        ) -> Result<LLMResponse> {
+            Ok(LLMResponse::text("ok"))
+        }
+        async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+            Ok(texts.iter().map(|t| vec![1.0, t.len() as f32]).collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_index_then_query_cites_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let ws = dir.path().join("ws");
+        std::fs::create_dir_all(&ws).unwrap();
+        std::fs::write(ws.join("notes.md"), "alpha\nbeta").unwrap();
+        let index = Arc::new(WorkspaceIndex::new(
+            Arc::new(LengthEmbedder),
+            dir.path().join("index"),
+        ));
+        let ctx = ToolContext::new().with_workspace(ws.to_str().unwrap());
+
+        let out = RagQueryTool::new(index.clone())
+            .execute(json!({"query": "alpha"}), &ctx)
+            .await
+            .unwrap();
+        assert!(out.for_llm.contains("index_workspace"));
+
+        let out = IndexWorkspaceTool::new(index.clone())
+            .execute(json!({}), &ctx)
+            .await
+            .unwrap();
+        assert!(out.for_llm.contains("1 file(s) embedded"));
+
+        let out = RagQueryTool::new(index)
+            .execute(json!({"query": "alpha", "top_k": 3}), &ctx)
+            .await
+            .unwrap();
+        assert!(out.for_llm.contains("[notes.md#L1-L2]"));
+    }
+
+    #[tokio::test]
+    async fn test_rag_query_requires_query() {
+        let dir = tempfile::tempdir().unwrap();
+        let index = Arc::new(WorkspaceIndex::new(
+            Arc::new(LengthEmbedder),
+            dir.path().to_path_buf(),
+        ));
+        let ctx = ToolContext::new().with_workspace(dir.path().to_str().unwrap());
+        let err = RagQueryTool::new(index)
+            .execute(json!({"query": "  "}), &ctx)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("query"));
+    }
+}
@@ -3,16 +3,19 @@
 //! This module provides the `ToolRegistry` struct for managing and executing tools.
 //! Tools can be registered, looked up by name, and executed with context.
 
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::time::Instant;
 
 use serde_json::Value;
-use tracing::{error, info};
+use tracing::{debug, error, info, warn};
 
-use crate::error::Result;
+use crate::error::{Result, ZeptoError};
 use crate::providers::ToolDefinition;
 
-use super::{Tool, ToolContext, ToolOutput};
+use super::arg_validation::{format_errors, validate_args};
+use super::resource_lock::ResourceLocks;
+use super::suggest::missing_tool_suggestion;
+use super::{Tool, ToolChunk, ToolContext, ToolOutput};
 
 /// Returns a setup hint for tools that are opt-in (not registered by default).
 fn opt_in_tool_hint(name: &str) -> &'static str {
@@ -24,6 +27,26 @@ fn opt_in_tool_hint(name: &str) -> &'static str {
     }
 }
 
+/// Run `tool` via [`Tool::execute_stream`], forwarding progress chunks to
+/// `ctx.progress` and returning the final result.
+async fn run_tool(tool: &dyn Tool, args: Value, ctx: &ToolContext) -> Result<ToolOutput> {
+    let mut chunks = tool.execute_stream(args, ctx).await;
+    while let Some(chunk) = chunks.recv().await {
+        match chunk {
+            ToolChunk::Progress(line) => {
+                if let Some(progress) = &ctx.progress {
+                    let _ = progress.send(line);
+                }
+            }
+            ToolChunk::Done(result) => return result,
+        }
+    }
+    Err(ZeptoError::Tool(format!(
+        "Tool '{}' ended without a result",
+        tool.name()
+    )))
+}
+
 /// A registry that holds and manages tools.
 ///
 /// The registry allows tools to be registered, looked up by name,
@@ -46,7 +69,12 @@ fn opt_in_tool_hint(name: &str) -> &'static str {
 /// # });
 /// ```
 pub struct ToolRegistry {
-    tools: HashMap<String, Box<dyn Tool>>,
+    /// Keyed by name; the ordered map keeps definitions (and so the schema
+    /// sent to the model) stable across runs.
+    tools: BTreeMap<String, Box<dyn Tool>>,
+    /// Check arguments against each tool's `parameters()` schema before
+    /// executing it.
+    validate_args: bool,
 }
 
 impl ToolRegistry {
@@ -61,10 +89,42 @@ impl ToolRegistry {
     /// ```
     pub fn new() -> Self {
         Self {
-            tools: HashMap::new(),
+            tools: BTreeMap::new(),
+            validate_args: false,
         }
     }
 
+    /// Enable or disable argument validation against each tool's
+    /// `parameters()` schema (off by default).
+    ///
+    /// When enabled, calls whose arguments do not match the schema are not
+    /// executed; they return a `ToolOutput::error` naming each offending
+    /// argument so the model can retry with corrected arguments.
+    ///
+    /// # Example
+    /// ```
+    /// use zeptoclaw::tools::{ToolRegistry, EchoTool};
+    /// use serde_json::json;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let mut registry = ToolRegistry::new().with_arg_validation(true);
+    /// registry.register(Box::new(EchoTool));
+    ///
+    /// let output = registry.execute("echo", json!({"message": 7})).await.unwrap();
+    /// assert!(output.is_error);
+    /// assert!(output.for_llm.contains("argument `message`: expected string, got integer"));
+    /// # });
+    /// ```
+    pub fn with_arg_validation(mut self, enabled: bool) -> Self {
+        self.validate_args = enabled;
+        self
+    }
+
+    /// Enable or disable argument validation on an existing registry.
+    pub fn set_arg_validation(&mut self, enabled: bool) {
+        self.validate_args = enabled;
+    }
+
     /// Register a new tool in the registry.
     ///
     /// If a tool with the same name already exists, it will be replaced.
@@ -173,16 +233,43 @@ impl ToolRegistry {
             Some(t) => t,
             None => {
                 let hint = opt_in_tool_hint(name);
+                if !hint.is_empty() {
+                    return Ok(ToolOutput::error(format!(
+                        "Tool not found: {}{}",
+                        name, hint
+                    )));
+                }
                 return Ok(ToolOutput::error(format!(
-                    "Tool not found: {}{}",
-                    name, hint
+                    "Tool not found: {}\n{}",
+                    name,
+                    missing_tool_suggestion(name, &self.names())
                 )));
             }
         };
 
+        if self.validate_args {
+            if let Err(errors) = validate_args(&tool.parameters(), &args) {
+                warn!(
+                    tool = name,
+                    errors = errors.len(),
+                    "Rejected tool call with invalid arguments"
+                );
+                return Ok(ToolOutput::error(format_errors(name, &errors)));
+            }
+        }
+
+        // Serialize tools that share a physical resource (phone, serial port...).
+        let _resource_permit = match tool.resource_key() {
+            Some(key) => {
+                debug!(tool = name, resource = key, "Acquiring tool resource lock");
+                Some(ResourceLocks::global().acquire(key).await)
+            }
+            None => None,
+        };
+
         let start = Instant::now();
 
-        match tool.execute(args, ctx).await {
+        match run_tool(tool.as_ref(), args, ctx).await {
             Ok(output) => {
                 info!(
                     tool = name,
@@ -388,6 +475,8 @@ mod tests {
     use super::*;
     use crate::tools::EchoTool;
     use serde_json::json;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
 
     #[test]
     fn test_registry_new() {
@@ -491,6 +580,19 @@ mod tests {
         assert!(output.for_llm.contains("Tool not found: nonexistent"));
     }
 
+    #[tokio::test]
+    async fn test_tool_not_found_suggests_how_to_add_it() {
+        let mut registry = ToolRegistry::new();
+        registry.register(Box::new(EchoTool));
+        let output = registry
+            .execute("jira_create_issue", json!({}))
+            .await
+            .unwrap();
+        assert!(output.is_error);
+        assert!(output.for_llm.contains("create_tool"));
+        assert!(output.for_llm.contains("MCP server"));
+    }
+
     #[tokio::test]
     async fn test_registry_execute_missing_message() {
         let mut registry = ToolRegistry::new();
@@ -513,6 +615,32 @@ mod tests {
         assert_eq!(result.unwrap().for_llm, "(no message)");
     }
 
+    #[tokio::test]
+    async fn test_arg_validation_rejects_before_execute() {
+        let mut registry = ToolRegistry::new().with_arg_validation(true);
+        registry.register(Box::new(EchoTool));
+
+        let output = registry.execute("echo", json!({})).await.unwrap();
+        assert!(output.is_error);
+        assert_eq!(
+            output.for_llm,
+            "Invalid arguments for tool 'echo': argument `message`: missing required argument. \
+             Fix the arguments to match the tool's parameter schema and call it again."
+        );
+
+        let output = registry
+            .execute("echo", json!({"message": "hi"}))
+            .await
+            .unwrap();
+        assert!(!output.is_error);
+        assert_eq!(output.for_llm, "hi");
+
+        // Disabled validation leaves argument handling to the tool.
+        registry.set_arg_validation(false);
+        let output = registry.execute("echo", json!({})).await.unwrap();
+        assert_eq!(output.for_llm, "(no message)");
+    }
+
     #[test]
     fn test_registry_replace_tool() {
         let mut registry = ToolRegistry::new();
@@ -562,6 +690,35 @@ mod tests {
         assert_eq!(defs[0].description, "Echo message");
     }
 
+    #[test]
+    fn test_definitions_are_sorted_and_stable() {
+        use crate::tools::filesystem::{ListDirTool, ReadFileTool, WriteFileTool};
+
+        let mut first = ToolRegistry::new();
+        first.register(Box::new(WriteFileTool));
+        first.register(Box::new(EchoTool));
+        first.register(Box::new(ReadFileTool));
+        first.register(Box::new(ListDirTool));
+
+        let mut second = ToolRegistry::new();
+        second.register(Box::new(ListDirTool));
+        second.register(Box::new(ReadFileTool));
+        second.register(Box::new(EchoTool));
+        second.register(Box::new(WriteFileTool));
+
+        let names: Vec<String> = first.definitions().into_iter().map(|d| d.name).collect();
+        assert_eq!(names, ["echo", "list_dir", "read_file", "write_file"]);
+        assert_eq!(first.names(), second.names());
+        assert_eq!(
+            serde_json::to_string(&first.definitions()).unwrap(),
+            serde_json::to_string(&second.definitions()).unwrap()
+        );
+        assert_eq!(
+            serde_json::to_string(&first.definitions_with_options(true)).unwrap(),
+            serde_json::to_string(&second.definitions_with_options(true)).unwrap()
+        );
+    }
+
     #[test]
     fn test_opt_in_tool_hint_grep() {
         assert!(opt_in_tool_hint("grep").contains("--template coder"));
@@ -576,4 +733,130 @@ mod tests {
     fn test_opt_in_tool_hint_unknown() {
         assert_eq!(opt_in_tool_hint("unknown"), "");
     }
+
+    /// Sleeps while tracking how many instances run at once.
+    struct ResourceTool {
+        name: &'static str,
+        key: &'static str,
+        active: Arc<AtomicUsize>,
+        peak: Arc<AtomicUsize>,
+    }
+
+    #[async_trait::async_trait]
+    impl Tool for ResourceTool {
+        fn name(&self) -> &str {
+            self.name
+        }
+        fn description(&self) -> &str {
+            "Holds a resource briefly"
+        }
+        fn parameters(&self) -> Value {
+            json!({"type": "object", "properties": {}})
+        }
+        fn resource_key(&self) -> Option<&str> {
+            Some(self.key)
+        }
+        async fn execute(&self, _args: Value, _ctx: &ToolContext) -> Result<ToolOutput> {
+            let now = self.active.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(now, Ordering::SeqCst);
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            self.active.fetch_sub(1, Ordering::SeqCst);
+            Ok(ToolOutput::llm_only("done"))
+        }
+    }
+
+    fn resource_registry(keys: [&'static str; 2]) -> (ToolRegistry, Arc<AtomicUsize>) {
+        let active = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let mut registry = ToolRegistry::new();
+        for (name, key) in ["first", "second"].into_iter().zip(keys) {
+            registry.register(Box::new(ResourceTool {
+                name,
+                key,
+                active: Arc::clone(&active),
+                peak: Arc::clone(&peak),
+            }));
+        }
+        (registry, peak)
+    }
+
+    #[tokio::test]
+    async fn test_shared_resource_key_serializes_tools() {
+        let (registry, peak) = resource_registry(["test_registry_phone", "test_registry_phone"]);
+        let ctx = ToolContext::new();
+        let (a, b) = tokio::join!(
+            registry.execute_with_context("first", json!({}), &ctx),
+            registry.execute_with_context("second", json!({}), &ctx),
+        );
+        assert!(a.is_ok() && b.is_ok());
+        assert_eq!(peak.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_different_resource_keys_run_concurrently() {
+        let (registry, peak) =
+            resource_registry(["test_registry_phone_a", "test_registry_serial_b"]);
+        let ctx = ToolContext::new();
+        let (a, b) = tokio::join!(
+            registry.execute_with_context("first", json!({}), &ctx),
+            registry.execute_with_context("second", json!({}), &ctx),
+        );
+        assert!(a.is_ok() && b.is_ok());
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+    }
+
+    /// Reports two progress lines before its result.
+    struct ProgressTool;
+
+    #[async_trait::async_trait]
+    impl Tool for ProgressTool {
+        fn name(&self) -> &str {
+            "progress"
+        }
+        fn description(&self) -> &str {
+            "Reports progress"
+        }
+        fn parameters(&self) -> Value {
+            json!({"type": "object", "properties": {}})
+        }
+        async fn execute(&self, _args: Value, _ctx: &ToolContext) -> Result<ToolOutput> {
+            Ok(ToolOutput::llm_only("done"))
+        }
+        async fn execute_stream(
+            &self,
+            _args: Value,
+            _ctx: &ToolContext,
+        ) -> tokio::sync::mpsc::Receiver<ToolChunk> {
+            let (tx, rx) = tokio::sync::mpsc::channel(3);
+            tx.send(ToolChunk::Progress("one".into())).await.unwrap();
+            tx.send(ToolChunk::Progress("two".into())).await.unwrap();
+            tx.send(ToolChunk::Done(Ok(ToolOutput::llm_only("done"))))
+                .await
+                .unwrap();
+            rx
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_forwards_progress_to_context() {
+        let mut registry = ToolRegistry::new();
+        registry.register(Box::new(ProgressTool));
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let ctx = ToolContext::new().with_progress(tx);
+
+        let output = registry
+            .execute_with_context("progress", json!({}), &ctx)
+            .await
+            .unwrap();
+        assert_eq!(output.for_llm, "done");
+        assert_eq!(rx.recv().await.as_deref(), Some("one"));
+        assert_eq!(rx.recv().await.as_deref(), Some("two"));
+
+        // Without a progress sink the chunks are simply dropped.
+        let output = registry
+            .execute_with_context("progress", json!({}), &ToolContext::new())
+            .await
+            .unwrap();
+        assert_eq!(output.for_llm, "done");
+    }
 }
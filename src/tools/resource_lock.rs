@@ -0,0 +1,70 @@
+//! Process-wide locks for tools that share a physical resource.
+//!
+//! Tools such as `android`, `serial` and `web_screenshot` drive a single
+//! attached device or browser and must not run concurrently. Each declares a
+//! [`Tool::resource_key`](super::Tool::resource_key); the registry acquires
+//! the matching lock from [`ResourceLocks::global`] before executing, so
+//! tools with the same key are serialized while everything else proceeds.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use once_cell::sync::Lazy;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+static GLOBAL: Lazy<ResourceLocks> = Lazy::new(ResourceLocks::new);
+
+/// Named exclusive locks, created on first use.
+#[derive(Default)]
+pub struct ResourceLocks {
+    locks: Mutex<HashMap<String, Arc<Semaphore>>>,
+}
+
+impl ResourceLocks {
+    /// Create an empty lock registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The registry shared by every `ToolRegistry` in the process.
+    pub fn global() -> &'static ResourceLocks {
+        &GLOBAL
+    }
+
+    /// Wait for exclusive access to `key`. Released when the permit drops.
+    pub async fn acquire(&self, key: &str) -> OwnedSemaphorePermit {
+        let semaphore = {
+            let mut locks = self.locks.lock().unwrap_or_else(|e| e.into_inner());
+            Arc::clone(
+                locks
+                    .entry(key.to_string())
+                    .or_insert_with(|| Arc::new(Semaphore::new(1))),
+            )
+        };
+        semaphore
+            .acquire_owned()
+            .await
+            .expect("resource semaphores are never closed")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_acquire_same_key_waits_for_release() {
+        let locks = ResourceLocks::new();
+        let permit = locks.acquire("phone").await;
+        assert!(
+            tokio::time::timeout(Duration::from_millis(50), locks.acquire("phone"))
+                .await
+                .is_err()
+        );
+        drop(permit);
+        let _again = locks.acquire("phone").await;
+        // Other keys are independent.
+        let _other = locks.acquire("serial").await;
+    }
+}
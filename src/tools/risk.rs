@@ -0,0 +1,257 @@
+//! Central tool risk policy.
+//!
+//! Maps tool categories, tools and individual tool actions to a
+//! [`RiskLevel`]. The approval gate consults it for every tool call:
+//! `Blocked` calls are refused outright and, under the
+//! `require_for_dangerous` approval policy, `Confirm` calls need user
+//! approval.
+//!
+//! Lookups go from most to least specific: a `tool:action` entry (matched
+//! against the call's `action` argument), then the tool name, then the
+//! tool's [`ToolCategory`]. Configured entries win over the built-in
+//! defaults at every level.
+//!
+//! # Configuration
+//!
+//! ```json
+//! {
+//!     "approval": {
+//!         "risk": {
+//!             "categories": { "messaging": "safe", "network_write": "confirm" },
+//!             "tools": { "whatsapp_send": "confirm", "cron:remove": "blocked" }
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! # Example
+//!
+//! ```rust
+//! use serde_json::json;
+//! use zeptoclaw::tools::risk::{RiskLevel, RiskPolicy};
+//! use zeptoclaw::tools::ToolCategory;
+//!
+//! let policy = RiskPolicy::default();
+//! let send = json!({"action": "gmail_send"});
+//! assert_eq!(
+//!     policy.classify("google", ToolCategory::Messaging, &send),
+//!     RiskLevel::Confirm
+//! );
+//! ```
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::ToolCategory;
+
+/// How risky a tool call is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RiskLevel {
+    /// Runs without confirmation.
+    Safe,
+    /// Needs user confirmation before it runs.
+    Confirm,
+    /// Never runs.
+    Blocked,
+}
+
+impl std::fmt::Display for RiskLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Safe => write!(f, "safe"),
+            Self::Confirm => write!(f, "confirm"),
+            Self::Blocked => write!(f, "blocked"),
+        }
+    }
+}
+
+/// Per-deployment overrides for the risk policy (`approval.risk`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct RiskPolicyConfig {
+    /// Risk level per tool category.
+    pub categories: HashMap<ToolCategory, RiskLevel>,
+    /// Risk level per tool (`shell`) or tool action (`google:gmail_send`).
+    pub tools: HashMap<String, RiskLevel>,
+}
+
+/// Built-in tool and action levels that differ from their category's.
+const DEFAULT_TOOL_LEVELS: &[(&str, RiskLevel)] = &[
+    // Replies to the current conversation are the agent's normal output.
+    ("message", RiskLevel::Safe),
+    // Reading mail and calendars is harmless; sending and creating is not.
+    ("google", RiskLevel::Safe),
+    ("google:gmail_send", RiskLevel::Confirm),
+    ("google:gmail_reply", RiskLevel::Confirm),
+    ("google:calendar_create", RiskLevel::Confirm),
+    // Raw device shell stays confirmed even if hardware is marked safe.
+    ("android:shell", RiskLevel::Confirm),
+];
+
+/// Resolved risk policy: built-in defaults plus configured overrides.
+#[derive(Debug, Clone)]
+pub struct RiskPolicy {
+    categories: HashMap<ToolCategory, RiskLevel>,
+    tool_overrides: HashMap<String, RiskLevel>,
+    tool_defaults: HashMap<String, RiskLevel>,
+}
+
+impl Default for RiskPolicy {
+    fn default() -> Self {
+        Self::new(&RiskPolicyConfig::default())
+    }
+}
+
+impl RiskPolicy {
+    /// Build the policy from configured overrides.
+    pub fn new(config: &RiskPolicyConfig) -> Self {
+        let mut categories: HashMap<ToolCategory, RiskLevel> = ToolCategory::all()
+            .into_iter()
+            .map(|category| (category, Self::default_category_level(category)))
+            .collect();
+        categories.extend(config.categories.iter().map(|(c, l)| (*c, *l)));
+
+        Self {
+            categories,
+            tool_overrides: config.tools.clone(),
+            tool_defaults: DEFAULT_TOOL_LEVELS
+                .iter()
+                .map(|(name, level)| (name.to_string(), *level))
+                .collect(),
+        }
+    }
+
+    /// Built-in risk level for a category.
+    pub fn default_category_level(category: ToolCategory) -> RiskLevel {
+        match category {
+            ToolCategory::FilesystemRead
+            | ToolCategory::FilesystemWrite
+            | ToolCategory::NetworkRead
+            | ToolCategory::NetworkWrite
+            | ToolCategory::Memory => RiskLevel::Safe,
+            ToolCategory::Messaging
+            | ToolCategory::Shell
+            | ToolCategory::Hardware
+            | ToolCategory::Destructive => RiskLevel::Confirm,
+        }
+    }
+
+    /// Classify a call to `tool_name` (of `category`) with `args`.
+    pub fn classify(&self, tool_name: &str, category: ToolCategory, args: &Value) -> RiskLevel {
+        let action_key = args
+            .get("action")
+            .and_then(Value::as_str)
+            .map(|action| format!("{}:{}", tool_name, action));
+        for table in [&self.tool_overrides, &self.tool_defaults] {
+            let level = action_key
+                .as_deref()
+                .and_then(|key| table.get(key))
+                .or_else(|| table.get(tool_name));
+            if let Some(level) = level {
+                return *level;
+            }
+        }
+        self.categories
+            .get(&category)
+            .copied()
+            .unwrap_or_else(|| Self::default_category_level(category))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_default_policy_classification() {
+        let policy = RiskPolicy::default();
+
+        assert_eq!(
+            policy.classify(
+                "whatsapp_send",
+                ToolCategory::Messaging,
+                &json!({"to": "1"})
+            ),
+            RiskLevel::Confirm
+        );
+        assert_eq!(
+            policy.classify(
+                "google",
+                ToolCategory::Messaging,
+                &json!({"action": "gmail_send"})
+            ),
+            RiskLevel::Confirm
+        );
+        assert_eq!(
+            policy.classify(
+                "google",
+                ToolCategory::Messaging,
+                &json!({"action": "gmail_search"})
+            ),
+            RiskLevel::Safe
+        );
+        assert_eq!(
+            policy.classify(
+                "longterm_memory",
+                ToolCategory::Memory,
+                &json!({"action": "get"})
+            ),
+            RiskLevel::Safe
+        );
+        assert_eq!(
+            policy.classify("message", ToolCategory::Messaging, &json!({})),
+            RiskLevel::Safe
+        );
+        assert_eq!(
+            policy.classify("shell", ToolCategory::Shell, &json!({})),
+            RiskLevel::Confirm
+        );
+    }
+
+    #[test]
+    fn test_config_overrides_apply() {
+        let config: RiskPolicyConfig = serde_json::from_value(json!({
+            "categories": { "messaging": "safe", "memory": "blocked" },
+            "tools": { "google": "blocked", "whatsapp_send:bulk": "confirm" }
+        }))
+        .unwrap();
+        let policy = RiskPolicy::new(&config);
+
+        // Category override.
+        assert_eq!(
+            policy.classify("whatsapp_send", ToolCategory::Messaging, &json!({})),
+            RiskLevel::Safe
+        );
+        assert_eq!(
+            policy.classify("memory_get", ToolCategory::Memory, &json!({})),
+            RiskLevel::Blocked
+        );
+        // A configured tool entry beats the built-in action defaults.
+        assert_eq!(
+            policy.classify(
+                "google",
+                ToolCategory::Messaging,
+                &json!({"action": "gmail_read"})
+            ),
+            RiskLevel::Blocked
+        );
+        // Action-level entries only match that action.
+        assert_eq!(
+            policy.classify(
+                "whatsapp_send",
+                ToolCategory::Messaging,
+                &json!({"action": "bulk"})
+            ),
+            RiskLevel::Confirm
+        );
+        // Untouched categories keep their defaults.
+        assert_eq!(
+            policy.classify("shell", ToolCategory::Shell, &json!({})),
+            RiskLevel::Confirm
+        );
+    }
+}
@@ -203,6 +203,10 @@ impl Tool for WebScreenshotTool {
         ToolCategory::FilesystemWrite
     }
 
+    fn resource_key(&self) -> Option<&str> {
+        Some("headless_browser")
+    }
+
     /// Define JSON schema for tool arguments.
     fn parameters(&self) -> Value {
         json!({
@@ -214,7 +218,7 @@ impl Tool for WebScreenshotTool {
                 },
                 "output_path": {
                     "type": "string",
-                    "description": "File path to save the screenshot PNG. If omitted, returns base64-encoded data."
+                    "description": "Workspace-relative path to save the screenshot PNG. If omitted, returns base64-encoded data."
                 },
                 "timeout_secs": {
                     "type": "integer",
@@ -240,7 +244,7 @@ impl Tool for WebScreenshotTool {
     }
 
     /// Execute screenshot capture with browser-native SSRF redirect checks.
-    async fn execute(&self, args: Value, _ctx: &ToolContext) -> Result<ToolOutput> {
+    async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<ToolOutput> {
         // ---- Parse and validate URL ----
         let url_str = args
             .get("url")
@@ -286,7 +290,9 @@ impl Tool for WebScreenshotTool {
             .and_then(|v| v.as_str())
             .map(str::trim)
             .filter(|s| !s.is_empty())
-            .map(String::from);
+            .map(|p| ctx.resolve_in_workspace(p))
+            .transpose()?
+            .map(|safe| safe.into_path_buf());
 
         let width = args
             .get("width")
@@ -454,12 +460,16 @@ impl Tool for WebScreenshotTool {
             tokio::fs::write(&path, &screenshot_result)
                 .await
                 .map_err(|e| {
-                    ZeptoError::Tool(format!("Failed to write screenshot to '{}': {}", path, e))
+                    ZeptoError::Tool(format!(
+                        "Failed to write screenshot to '{}': {}",
+                        path.display(),
+                        e
+                    ))
                 })?;
 
             json!({
                 "url": url_str,
-                "output_path": path,
+                "output_path": path.to_string_lossy(),
                 "size_bytes": screenshot_result.len(),
                 "width": width,
                 "height": height,
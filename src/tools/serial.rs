@@ -0,0 +1,445 @@
+//! Serial tool -- raw read/write access to discovered USB serial devices.
+//!
+//! The tool only opens serial paths that belong to a device reported by
+//! [`HardwareManager::discover_devices`], so the agent cannot use it to open
+//! arbitrary files. Each call opens the port, performs an optional write and
+//! an optional bounded read, then closes the port.
+//!
+//! The I/O is gated behind the `hardware` feature; the path guard and framing
+//! helpers are always compiled.
+
+use std::time::Duration;
+
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use crate::error::{Result, ZeptoError};
+use crate::hardware::DiscoveredDevice;
+
+#[cfg(feature = "hardware")]
+use crate::hardware::HardwareManager;
+#[cfg(feature = "hardware")]
+use crate::tools::{Tool, ToolCategory, ToolContext, ToolOutput};
+#[cfg(feature = "hardware")]
+use async_trait::async_trait;
+#[cfg(feature = "hardware")]
+use serde_json::Value;
+
+/// Default baud rate when none is given.
+pub const DEFAULT_BAUD: u32 = 115_200;
+
+/// Default read timeout in milliseconds.
+pub const DEFAULT_READ_TIMEOUT_MS: u64 = 2_000;
+
+/// Upper bound on the read timeout the agent may request.
+pub const MAX_READ_TIMEOUT_MS: u64 = 30_000;
+
+/// Maximum bytes returned by a single read (64 KB).
+pub const MAX_READ_BYTES: usize = 64 * 1024;
+
+/// Resolve a `device` argument (name, `VID:PID`, or serial path) to the
+/// serial path of a discovered device.
+///
+/// Only paths reported by discovery are accepted; anything else is rejected
+/// so the tool cannot be pointed at arbitrary files.
+pub fn resolve_device_path(query: &str, devices: &[DiscoveredDevice]) -> Result<String> {
+    let query = query.trim();
+    let matched = devices.iter().find(|d| {
+        d.device_path.as_deref() == Some(query)
+            || d.name == query
+            || format!("{:04x}:{:04x}", d.vid, d.pid).eq_ignore_ascii_case(query)
+    });
+
+    match matched {
+        Some(DiscoveredDevice {
+            device_path: Some(path),
+            ..
+        }) => Ok(path.clone()),
+        Some(dev) => Err(ZeptoError::Tool(format!(
+            "Device '{}' has no serial port",
+            dev.name
+        ))),
+        None => {
+            let available: Vec<&str> = devices
+                .iter()
+                .filter_map(|d| d.device_path.as_deref())
+                .collect();
+            Err(ZeptoError::Tool(format!(
+                "'{query}' is not a discovered serial device. Available: {}",
+                if available.is_empty() {
+                    "none".to_string()
+                } else {
+                    available.join(", ")
+                }
+            )))
+        }
+    }
+}
+
+/// Read until `delimiter` is seen, returning the bytes before it.
+///
+/// Reads one byte at a time so nothing past the delimiter is consumed. Fails
+/// on timeout, EOF, or when `max_bytes` is exceeded before the delimiter.
+pub async fn read_until<R: AsyncRead + Unpin>(
+    reader: &mut R,
+    delimiter: &[u8],
+    timeout: Duration,
+    max_bytes: usize,
+) -> Result<Vec<u8>> {
+    if delimiter.is_empty() {
+        return Err(ZeptoError::Tool("Delimiter must not be empty".into()));
+    }
+
+    let deadline = tokio::time::Instant::now() + timeout;
+    let mut buf = Vec::new();
+    let mut byte = [0u8; 1];
+
+    loop {
+        let n = match tokio::time::timeout_at(deadline, reader.read(&mut byte)).await {
+            Ok(res) => res.map_err(|e| ZeptoError::Tool(format!("Serial read failed: {e}")))?,
+            Err(_) => {
+                return Err(ZeptoError::Tool(format!(
+                    "Timed out after {}ms waiting for delimiter (received {} bytes: {:?})",
+                    timeout.as_millis(),
+                    buf.len(),
+                    String::from_utf8_lossy(&buf)
+                )))
+            }
+        };
+        if n == 0 {
+            return Err(ZeptoError::Tool(format!(
+                "Serial stream closed before delimiter (received {} bytes)",
+                buf.len()
+            )));
+        }
+
+        buf.push(byte[0]);
+        if buf.ends_with(delimiter) {
+            buf.truncate(buf.len() - delimiter.len());
+            return Ok(buf);
+        }
+        if buf.len() > max_bytes {
+            return Err(ZeptoError::Tool(format!(
+                "Serial response exceeded max size ({max_bytes} bytes) before delimiter"
+            )));
+        }
+    }
+}
+
+/// Collect whatever arrives within `timeout`, up to `max_bytes`.
+///
+/// Running out of time is the normal end of a timed read, not an error.
+pub async fn read_for<R: AsyncRead + Unpin>(
+    reader: &mut R,
+    timeout: Duration,
+    max_bytes: usize,
+) -> Result<Vec<u8>> {
+    let deadline = tokio::time::Instant::now() + timeout;
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 256];
+
+    while buf.len() < max_bytes {
+        match tokio::time::timeout_at(deadline, reader.read(&mut chunk)).await {
+            Err(_) | Ok(Ok(0)) => break,
+            Ok(Ok(n)) => buf.extend_from_slice(&chunk[..n]),
+            Ok(Err(e)) => return Err(ZeptoError::Tool(format!("Serial read failed: {e}"))),
+        }
+    }
+    buf.truncate(max_bytes);
+    Ok(buf)
+}
+
+/// Decode escape sequences the model is likely to send (`\n`, `\r`, `\t`).
+#[cfg_attr(not(feature = "hardware"), allow(dead_code))]
+fn unescape_delimiter(raw: &str) -> Vec<u8> {
+    raw.replace("\\r", "\r")
+        .replace("\\n", "\n")
+        .replace("\\t", "\t")
+        .into_bytes()
+}
+
+/// Agent-facing serial tool for discovered USB serial devices.
+#[cfg(feature = "hardware")]
+pub struct SerialTool {
+    manager: HardwareManager,
+}
+
+#[cfg(feature = "hardware")]
+impl SerialTool {
+    /// Create a new SerialTool.
+    pub fn new() -> Self {
+        Self {
+            manager: HardwareManager::new(),
+        }
+    }
+}
+
+#[cfg(feature = "hardware")]
+impl Default for SerialTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "hardware")]
+#[async_trait]
+impl Tool for SerialTool {
+    fn name(&self) -> &str {
+        "serial"
+    }
+
+    fn description(&self) -> &str {
+        "Read from and write to a discovered USB serial device. \
+         Actions: write (send data), read (collect output for timeout_ms), \
+         read_until (read up to a delimiter). read/read_until can send 'data' first."
+    }
+
+    fn compact_description(&self) -> &str {
+        "Serial device read/write"
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::Hardware
+    }
+
+    fn resource_key(&self) -> Option<&str> {
+        Some("serial_port")
+    }
+
+    fn parameters(&self) -> Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["write", "read", "read_until"],
+                    "description": "The serial action to perform"
+                },
+                "device": {
+                    "type": "string",
+                    "description": "Discovered device name, VID:PID, or serial path (e.g. /dev/ttyACM0)"
+                },
+                "baud": {
+                    "type": "integer",
+                    "description": "Baud rate (default 115200)"
+                },
+                "data": {
+                    "type": "string",
+                    "description": "Data to write (required for write, optional before a read)"
+                },
+                "newline": {
+                    "type": "boolean",
+                    "description": "Append a newline to written data (default true)"
+                },
+                "delimiter": {
+                    "type": "string",
+                    "description": "Delimiter for read_until (default \"\\n\")"
+                },
+                "timeout_ms": {
+                    "type": "integer",
+                    "description": "Read timeout in milliseconds (default 2000, max 30000)"
+                }
+            },
+            "required": ["action", "device"]
+        })
+    }
+
+    async fn execute(&self, args: Value, _ctx: &ToolContext) -> Result<ToolOutput> {
+        use tokio::io::AsyncWriteExt;
+        use tokio_serial::SerialPortBuilderExt;
+
+        let action = args
+            .get("action")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| ZeptoError::Tool("Missing 'action' parameter".into()))?;
+        if !matches!(action, "write" | "read" | "read_until") {
+            return Err(ZeptoError::Tool(format!(
+                "Unknown serial action: '{action}'. Valid actions: write, read, read_until"
+            )));
+        }
+        let device = args
+            .get("device")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| ZeptoError::Tool("Missing 'device' parameter".into()))?;
+        let baud = args
+            .get("baud")
+            .and_then(|v| v.as_u64())
+            .map(|b| u32::try_from(b).unwrap_or(DEFAULT_BAUD))
+            .unwrap_or(DEFAULT_BAUD);
+        let newline = args
+            .get("newline")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
+        let timeout = Duration::from_millis(
+            args.get("timeout_ms")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(DEFAULT_READ_TIMEOUT_MS)
+                .min(MAX_READ_TIMEOUT_MS),
+        );
+        let data = args.get("data").and_then(|v| v.as_str());
+        if action == "write" && data.is_none() {
+            return Err(ZeptoError::Tool(
+                "Missing 'data' parameter for write action".into(),
+            ));
+        }
+
+        let path = resolve_device_path(device, &self.manager.discover_devices())?;
+        let mut port = tokio_serial::new(&path, baud)
+            .open_native_async()
+            .map_err(|e| ZeptoError::Tool(format!("Failed to open {path}: {e}")))?;
+
+        let mut written = String::new();
+        if let Some(data) = data {
+            written = if newline {
+                format!("{data}\n")
+            } else {
+                data.to_string()
+            };
+            port.write_all(written.as_bytes())
+                .await
+                .map_err(|e| ZeptoError::Tool(format!("Serial write failed: {e}")))?;
+            port.flush()
+                .await
+                .map_err(|e| ZeptoError::Tool(format!("Serial flush failed: {e}")))?;
+        }
+
+        let received = match action {
+            "read" => Some(read_for(&mut port, timeout, MAX_READ_BYTES).await?),
+            "read_until" => {
+                let delimiter = args
+                    .get("delimiter")
+                    .and_then(|v| v.as_str())
+                    .map(unescape_delimiter)
+                    .unwrap_or_else(|| b"\n".to_vec());
+                Some(read_until(&mut port, &delimiter, timeout, MAX_READ_BYTES).await?)
+            }
+            _ => None,
+        };
+
+        let result = serde_json::json!({
+            "device_path": path,
+            "baud": baud,
+            "written_bytes": written.len(),
+            "received": received.as_deref().map(String::from_utf8_lossy),
+        });
+        Ok(ToolOutput::llm_only(result.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::AsyncWriteExt;
+
+    fn device(name: &str, path: Option<&str>) -> DiscoveredDevice {
+        DiscoveredDevice {
+            name: name.to_string(),
+            detail: None,
+            device_path: path.map(String::from),
+            vid: 0x2341,
+            pid: 0x0043,
+            architecture: None,
+        }
+    }
+
+    #[test]
+    fn test_resolve_device_path_by_path_name_and_vid_pid() {
+        let devices = vec![device("arduino-uno", Some("/dev/ttyACM0"))];
+        for query in ["/dev/ttyACM0", "arduino-uno", "2341:0043"] {
+            assert_eq!(
+                resolve_device_path(query, &devices).unwrap(),
+                "/dev/ttyACM0"
+            );
+        }
+    }
+
+    #[test]
+    fn test_resolve_device_path_rejects_undiscovered_paths() {
+        let devices = vec![device("arduino-uno", Some("/dev/ttyACM0"))];
+        for query in ["/etc/passwd", "/dev/ttyACM1", "/dev/ttyACM0/../sda"] {
+            let err = resolve_device_path(query, &devices).unwrap_err();
+            assert!(err.to_string().contains("not a discovered serial device"));
+        }
+        assert!(resolve_device_path("/dev/ttyACM0", &[]).is_err());
+    }
+
+    #[test]
+    fn test_resolve_device_path_requires_serial_port() {
+        let devices = vec![device("arduino-uno", None)];
+        let err = resolve_device_path("arduino-uno", &devices).unwrap_err();
+        assert!(err.to_string().contains("no serial port"));
+    }
+
+    #[test]
+    fn test_unescape_delimiter() {
+        assert_eq!(unescape_delimiter("\\r\\n"), b"\r\n");
+        assert_eq!(unescape_delimiter("OK"), b"OK");
+    }
+
+    #[tokio::test]
+    async fn test_read_until_stops_at_delimiter() {
+        let mut stream: &[u8] = b"hello\r\nworld";
+        let out = read_until(&mut stream, b"\r\n", Duration::from_secs(1), 1024)
+            .await
+            .unwrap();
+        assert_eq!(out, b"hello");
+        // Bytes after the delimiter are left unread.
+        assert_eq!(stream, b"world");
+    }
+
+    #[tokio::test]
+    async fn test_read_until_across_chunks() {
+        let (mut board, mut host) = tokio::io::duplex(64);
+        tokio::spawn(async move {
+            board.write_all(b"temp=2").await.unwrap();
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            board.write_all(b"1.5\nnext").await.unwrap();
+        });
+        let out = read_until(&mut host, b"\n", Duration::from_secs(1), 1024)
+            .await
+            .unwrap();
+        assert_eq!(out, b"temp=21.5");
+    }
+
+    #[tokio::test]
+    async fn test_read_until_times_out() {
+        let (_board, mut host) = tokio::io::duplex(64);
+        let err = read_until(&mut host, b"\n", Duration::from_millis(50), 1024)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Timed out"));
+    }
+
+    #[tokio::test]
+    async fn test_read_until_eof_and_oversize() {
+        let mut stream: &[u8] = b"no delimiter";
+        let err = read_until(&mut stream, b"\n", Duration::from_secs(1), 1024)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("closed before delimiter"));
+
+        let mut stream: &[u8] = b"0123456789\n";
+        let err = read_until(&mut stream, b"\n", Duration::from_secs(1), 4)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("exceeded max size"));
+    }
+
+    #[tokio::test]
+    async fn test_read_for_collects_until_timeout() {
+        let (mut board, mut host) = tokio::io::duplex(64);
+        board.write_all(b"boot ok\n").await.unwrap();
+        let out = read_for(&mut host, Duration::from_millis(50), 1024)
+            .await
+            .unwrap();
+        assert_eq!(out, b"boot ok\n");
+    }
+
+    #[tokio::test]
+    async fn test_read_for_caps_size() {
+        let mut stream: &[u8] = b"0123456789";
+        let out = read_for(&mut stream, Duration::from_secs(1), 4)
+            .await
+            .unwrap();
+        assert_eq!(out, b"0123");
+    }
+}
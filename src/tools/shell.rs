@@ -2,18 +2,24 @@
 //!
 //! This module provides a tool for executing shell commands. Commands are run
 //! in a subprocess with configurable timeout and workspace directory support.
+//! `execute_stream` reports each output line as it is produced when the
+//! runtime supports it.
 
 use async_trait::async_trait;
 use serde_json::{json, Value};
 use std::path::PathBuf;
 use std::sync::Arc;
+use tokio::sync::mpsc;
 
 use crate::error::{Result, ZeptoError};
 use crate::runtime::{ContainerConfig, ContainerRuntime, NativeRuntime};
 use crate::security::ShellSecurityConfig;
 
 use super::output::{truncate_tool_output, DEFAULT_MAX_BYTES, DEFAULT_MAX_LINES};
-use super::{Tool, ToolCategory, ToolContext, ToolOutput};
+use super::{Tool, ToolCategory, ToolChunk, ToolContext, ToolOutput};
+
+/// Buffered output lines before a slow consumer applies backpressure.
+const STREAM_CHANNEL_CAPACITY: usize = 64;
 
 /// Tool for executing shell commands.
 ///
@@ -99,6 +105,32 @@ impl ShellTool {
     pub fn runtime_name(&self) -> &str {
         self.runtime.name()
     }
+
+    /// Validate the arguments and build the command and container config.
+    fn prepare(&self, args: &Value, ctx: &ToolContext) -> Result<(String, ContainerConfig)> {
+        let command = args
+            .get("command")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| ZeptoError::Tool("Missing 'command' argument".into()))?;
+
+        // Security check
+        self.security_config.validate_command(command)?;
+
+        let timeout_secs = args.get("timeout").and_then(|v| v.as_u64()).unwrap_or(60);
+
+        // Build container configuration
+        let mut container_config = ContainerConfig::new().with_timeout(timeout_secs);
+
+        // Set working directory and mount if workspace is specified
+        if let Some(ref workspace) = ctx.workspace {
+            let workspace_path = PathBuf::from(workspace);
+            container_config = container_config
+                .with_workdir(workspace_path.clone())
+                .with_mount(workspace_path.clone(), workspace_path, false);
+        }
+
+        Ok((command.to_string(), container_config))
+    }
 }
 
 impl Default for ShellTool {
@@ -143,31 +175,12 @@ impl Tool for ShellTool {
     }
 
     async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<ToolOutput> {
-        let command = args
-            .get("command")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| ZeptoError::Tool("Missing 'command' argument".into()))?;
-
-        // Security check
-        self.security_config.validate_command(command)?;
-
-        let timeout_secs = args.get("timeout").and_then(|v| v.as_u64()).unwrap_or(60);
-
-        // Build container configuration
-        let mut container_config = ContainerConfig::new().with_timeout(timeout_secs);
-
-        // Set working directory and mount if workspace is specified
-        if let Some(ref workspace) = ctx.workspace {
-            let workspace_path = PathBuf::from(workspace);
-            container_config = container_config
-                .with_workdir(workspace_path.clone())
-                .with_mount(workspace_path.clone(), workspace_path, false);
-        }
+        let (command, container_config) = self.prepare(&args, ctx)?;
 
         // Execute command via runtime
         let output = self
             .runtime
-            .execute(command, &container_config)
+            .execute(&command, &container_config)
             .await
             .map_err(|e| ZeptoError::Tool(e.to_string()))?;
 
@@ -175,6 +188,43 @@ impl Tool for ShellTool {
             truncate_tool_output(&output.format(), DEFAULT_MAX_LINES, DEFAULT_MAX_BYTES);
         Ok(ToolOutput::user_visible(formatted))
     }
+
+    async fn execute_stream(&self, args: Value, ctx: &ToolContext) -> mpsc::Receiver<ToolChunk> {
+        let (tx, rx) = mpsc::channel(STREAM_CHANNEL_CAPACITY);
+        let (command, container_config) = match self.prepare(&args, ctx) {
+            Ok(prepared) => prepared,
+            Err(e) => {
+                let _ = tx.send(ToolChunk::Done(Err(e))).await;
+                return rx;
+            }
+        };
+
+        let runtime = Arc::clone(&self.runtime);
+        tokio::spawn(async move {
+            // Forward each output line as a progress chunk while the command runs.
+            let (line_tx, mut line_rx) = mpsc::channel::<String>(STREAM_CHANNEL_CAPACITY);
+            let (result, ()) = tokio::join!(
+                runtime.execute_streaming(&command, &container_config, line_tx),
+                async {
+                    while let Some(line) = line_rx.recv().await {
+                        let _ = tx.send(ToolChunk::Progress(line)).await;
+                    }
+                }
+            );
+
+            let done = result
+                .map(|output| {
+                    ToolOutput::user_visible(truncate_tool_output(
+                        &output.format(),
+                        DEFAULT_MAX_LINES,
+                        DEFAULT_MAX_BYTES,
+                    ))
+                })
+                .map_err(|e| ZeptoError::Tool(e.to_string()));
+            let _ = tx.send(ToolChunk::Done(done)).await;
+        });
+        rx
+    }
 }
 
 #[cfg(test)]
@@ -477,4 +527,50 @@ mod tests {
         let tool = ShellTool::permissive();
         assert_eq!(tool.runtime_name(), "native");
     }
+
+    async fn collect_chunks(mut rx: mpsc::Receiver<ToolChunk>) -> Vec<ToolChunk> {
+        let mut chunks = Vec::new();
+        while let Some(chunk) = rx.recv().await {
+            chunks.push(chunk);
+        }
+        chunks
+    }
+
+    #[tokio::test]
+    async fn test_shell_execute_stream_emits_progress_chunks() {
+        let tool = ShellTool::new();
+        let rx = tool
+            .execute_stream(
+                json!({"command": "echo first; sleep 0.1; echo second"}),
+                &ToolContext::new(),
+            )
+            .await;
+        let mut chunks = collect_chunks(rx).await;
+
+        assert_eq!(chunks.len(), 3);
+        let progress: Vec<&str> = chunks[..2]
+            .iter()
+            .map(|c| match c {
+                ToolChunk::Progress(line) => line.as_str(),
+                other => panic!("expected Progress, got {:?}", other),
+            })
+            .collect();
+        assert_eq!(progress, vec!["first", "second"]);
+        match chunks.pop().unwrap() {
+            ToolChunk::Done(Ok(output)) => assert_eq!(output.for_llm, "first\nsecond\n"),
+            other => panic!("expected Done(Ok), got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_shell_execute_stream_blocked_command_is_single_done() {
+        let tool = ShellTool::new();
+        let rx = tool
+            .execute_stream(json!({"command": "rm -rf /"}), &ToolContext::new())
+            .await;
+        let chunks = collect_chunks(rx).await;
+
+        assert_eq!(chunks.len(), 1);
+        assert!(matches!(chunks[0], ToolChunk::Done(Err(_))));
+    }
 }
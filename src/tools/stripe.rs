@@ -139,7 +139,7 @@ impl StripeTool {
             secret_key: secret_key.to_string(),
             default_currency: default_currency.to_string(),
             webhook_secret: None,
-            client: Client::new(),
+            client: crate::utils::http::default_client(),
         }
     }
 
@@ -172,7 +172,7 @@ impl StripeTool {
             secret_key: secret_key.to_string(),
             default_currency: stripe_cfg.default_currency.clone(),
             webhook_secret: stripe_cfg.webhook_secret.clone(),
-            client: Client::new(),
+            client: crate::utils::http::default_client(),
         })
     }
 
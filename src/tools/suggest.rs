@@ -0,0 +1,104 @@
+//! Guidance for tool calls that match no installed tool.
+//!
+//! When the model calls a tool that is not registered (say `jira_create_issue`
+//! with no Jira integration installed), a bare "Tool not found" invites it to
+//! retry with invented names or to claim the action happened. Instead the
+//! registry answers with [`missing_tool_suggestion`]: close matches among the
+//! installed tools, and the two ways to add the capability — a composed tool
+//! via `create_tool`, or an MCP server.
+
+use crate::config::validate::levenshtein;
+
+/// Name of the composed-tool management tool.
+const CREATE_TOOL: &str = "create_tool";
+
+/// Largest edit distance still reported as a likely typo.
+const MAX_TYPO_DISTANCE: usize = 3;
+
+/// Installed tools whose names look like `name`: a small edit distance, or
+/// a shared `_`-separated word such as `issue` in `github_issue`.
+pub fn similar_tools<'a>(name: &str, available: &[&'a str]) -> Vec<&'a str> {
+    let words: Vec<&str> = name.split('_').filter(|w| w.len() >= 3).collect();
+    let mut matches: Vec<(&str, usize)> = available
+        .iter()
+        .filter(|tool| **tool != name && **tool != CREATE_TOOL)
+        .filter_map(|tool| {
+            let distance = levenshtein(name, tool);
+            let shares_word = tool.split('_').any(|w| words.contains(&w));
+            (distance <= MAX_TYPO_DISTANCE || shares_word).then_some((*tool, distance))
+        })
+        .collect();
+    matches.sort_by(|a, b| a.1.cmp(&b.1).then(a.0.cmp(b.0)));
+    matches.into_iter().take(3).map(|(tool, _)| tool).collect()
+}
+
+/// Actionable guidance for a call to the unknown tool `name`, given the
+/// names of the installed tools.
+pub fn missing_tool_suggestion(name: &str, available: &[&str]) -> String {
+    let mut lines = vec![format!(
+        "No installed tool provides '{}'. Do not report the action as done.",
+        name
+    )];
+
+    let similar = similar_tools(name, available);
+    if !similar.is_empty() {
+        let list: Vec<String> = similar.iter().map(|t| format!("`{}`", t)).collect();
+        lines.push(format!(
+            "- If you meant an installed tool: {}",
+            list.join(", ")
+        ));
+    }
+
+    if available.contains(&CREATE_TOOL) {
+        lines.push(format!(
+            "- To add it now, define a composed tool with `{}` (action \"create\") that \
+             describes the steps using installed tools such as `http_request` or `shell`.",
+            CREATE_TOOL
+        ));
+    } else {
+        lines.push(
+            "- Composed tools are disabled here; the user can enable `create_tool` to \
+             define it in natural language."
+                .to_string(),
+        );
+    }
+    lines.push(
+        "- Or ask the user to install an MCP server that provides it: add it to \
+         `mcp.servers` in config, `{workspace}/.mcp.json`, or `~/.mcp/servers.json`, \
+         then restart."
+            .to_string(),
+    );
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unmatched_tool_suggests_composed_tool_and_mcp() {
+        let available = ["shell", "http_request", "create_tool", "github_issue"];
+        let text = missing_tool_suggestion("jira_create_issue", &available);
+
+        assert!(text.contains("No installed tool provides 'jira_create_issue'"));
+        assert!(text.contains("`create_tool` (action \"create\")"));
+        assert!(text.contains(".mcp.json"));
+        assert!(text.contains("`github_issue`"));
+
+        // Without create_tool the MCP route is still offered.
+        let text = missing_tool_suggestion("jira_create_issue", &["shell"]);
+        assert!(text.contains("Composed tools are disabled"));
+        assert!(text.contains("mcp.servers"));
+        assert!(!text.contains("If you meant"));
+    }
+
+    #[test]
+    fn test_similar_tools_finds_typos_and_shared_words() {
+        let available = ["web_fetch", "web_search", "read_file", "echo"];
+        assert_eq!(similar_tools("read_fiel", &available), ["read_file"]);
+        let mut web = similar_tools("web_browse", &available);
+        web.sort();
+        assert_eq!(web, ["web_fetch", "web_search"]);
+        assert!(similar_tools("jira", &available).is_empty());
+    }
+}
@@ -37,7 +37,7 @@ impl TranscribeTool {
     ///
     /// Returns `Err` if the underlying HTTP client cannot be built.
     pub fn new(api_key: impl Into<String>, model: impl Into<String>) -> Result<Self> {
-        let client = reqwest::Client::builder()
+        let client = crate::utils::http::client_builder()?
             .timeout(std::time::Duration::from_secs(60))
             .build()
             .map_err(|e| ZeptoError::Tool(format!("Failed to build HTTP client: {}", e)))?;
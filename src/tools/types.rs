@@ -4,11 +4,16 @@
 //! that all tools must implement, and the `ToolContext` struct that provides
 //! execution context to tools.
 
+use std::path::Path;
+
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use tokio::sync::mpsc;
 
-use crate::error::Result;
+use crate::bus::StructuredOutput;
+use crate::error::{Result, ZeptoError};
+use crate::security::{validate_path_in_workspace, SafePath};
 
 /// Category for agent mode enforcement.
 ///
@@ -89,6 +94,9 @@ pub struct ToolOutput {
     /// When true, the agent loop should break after this tool result
     /// and wait for the next user message before continuing.
     pub pause_for_input: bool,
+    /// Structured rendering of `for_user` (fields, buttons) for channels
+    /// that support rich output; others fall back to the text.
+    pub structured: Option<StructuredOutput>,
 }
 
 impl ToolOutput {
@@ -100,6 +108,7 @@ impl ToolOutput {
             is_error: false,
             is_async: false,
             pause_for_input: false,
+            structured: None,
         }
     }
 
@@ -112,6 +121,7 @@ impl ToolOutput {
             is_error: false,
             is_async: false,
             pause_for_input: false,
+            structured: None,
         }
     }
 
@@ -123,6 +133,7 @@ impl ToolOutput {
             is_error: true,
             is_async: false,
             pause_for_input: false,
+            structured: None,
         }
     }
 
@@ -134,6 +145,7 @@ impl ToolOutput {
             is_error: false,
             is_async: true,
             pause_for_input: false,
+            structured: None,
         }
     }
 
@@ -149,6 +161,7 @@ impl ToolOutput {
             is_error: false,
             is_async: false,
             pause_for_input: false,
+            structured: None,
         }
     }
 
@@ -160,6 +173,21 @@ impl ToolOutput {
         self.pause_for_input = true;
         self
     }
+
+    /// Attach a structured rendering of the user-facing output.
+    pub fn with_structured(mut self, structured: StructuredOutput) -> Self {
+        self.structured = Some(structured);
+        self
+    }
+}
+
+/// A piece of output from [`Tool::execute_stream`].
+#[derive(Debug)]
+pub enum ToolChunk {
+    /// Intermediate output, such as one line from a running command.
+    Progress(String),
+    /// The final result. Always the last chunk.
+    Done(Result<ToolOutput>),
 }
 
 /// Trait that all tools must implement.
@@ -223,6 +251,23 @@ pub trait Tool: Send + Sync {
     /// A `ToolOutput` with dual-audience content (LLM vs user).
     async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<ToolOutput>;
 
+    /// Execute the tool, reporting output while it runs.
+    ///
+    /// The receiver yields any number of [`ToolChunk::Progress`] chunks
+    /// followed by exactly one [`ToolChunk::Done`]. Callers should keep
+    /// draining it, since a streaming tool may wait for room in the channel.
+    ///
+    /// Defaults to running [`execute`](Tool::execute) and sending its result
+    /// as the single final chunk. Long-running tools override this so channels
+    /// can show progress (e.g. by editing a message in place).
+    async fn execute_stream(&self, args: Value, ctx: &ToolContext) -> mpsc::Receiver<ToolChunk> {
+        let (tx, rx) = mpsc::channel(1);
+        let _ = tx
+            .send(ToolChunk::Done(self.execute(args, ctx).await))
+            .await;
+        rx
+    }
+
     /// Get a compact (shorter) description for token-constrained environments.
     ///
     /// Defaults to the full description. Override in individual tools for
@@ -243,6 +288,25 @@ pub trait Tool: Send + Sync {
     fn category(&self) -> ToolCategory {
         ToolCategory::Shell
     }
+
+    /// Name of an exclusive resource this tool needs, if any.
+    ///
+    /// Tools returning the same key (e.g. `"android_device"`) never run at
+    /// the same time, even across sessions; the registry holds a per-key lock
+    /// for the duration of `execute`. Defaults to `None` (no locking).
+    fn resource_key(&self) -> Option<&str> {
+        None
+    }
+
+    /// Whether repeating this call with the same `args` is harmless.
+    ///
+    /// Calls that return `false` (sending mail, creating events) run at most
+    /// once per agent run: an identical replay, e.g. after a provider retry,
+    /// gets the first call's result instead of executing again. Defaults to
+    /// `true`.
+    fn is_idempotent(&self, _args: &Value) -> bool {
+        true
+    }
 }
 
 /// Context provided to tools during execution.
@@ -255,10 +319,15 @@ pub struct ToolContext {
     pub channel: Option<String>,
     /// The chat/conversation ID within the channel
     pub chat_id: Option<String>,
+    /// The ID of the user who sent the triggering message
+    pub sender_id: Option<String>,
     /// The workspace directory for file operations
     pub workspace: Option<String>,
     /// Whether the tool is running in batch mode (no interactive user).
     pub is_batch: bool,
+    /// Receives [`ToolChunk::Progress`] output while the tool runs, when the
+    /// caller can show it (e.g. a streamed channel reply).
+    pub progress: Option<mpsc::UnboundedSender<String>>,
 }
 
 impl ToolContext {
@@ -296,6 +365,20 @@ impl ToolContext {
         self
     }
 
+    /// Set the ID of the user who sent the triggering message.
+    ///
+    /// # Example
+    /// ```
+    /// use zeptoclaw::tools::ToolContext;
+    ///
+    /// let ctx = ToolContext::new().with_sender("user42");
+    /// assert_eq!(ctx.sender_id.as_deref(), Some("user42"));
+    /// ```
+    pub fn with_sender(mut self, sender_id: &str) -> Self {
+        self.sender_id = Some(sender_id.to_string());
+        self
+    }
+
     /// Set the workspace directory.
     ///
     /// # Arguments
@@ -322,6 +405,39 @@ impl ToolContext {
         self.is_batch = is_batch;
         self
     }
+
+    /// Forward progress output of tools run with this context to `progress`.
+    pub fn with_progress(mut self, progress: mpsc::UnboundedSender<String>) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// The workspace root, or a security error if none is configured.
+    ///
+    /// Tools that write files must not fall back to the process working
+    /// directory, so a missing workspace is an error rather than `None`.
+    pub fn workspace_root(&self) -> Result<&Path> {
+        self.workspace.as_deref().map(Path::new).ok_or_else(|| {
+            ZeptoError::SecurityViolation(
+                "Workspace not configured; this tool requires a workspace for safety".to_string(),
+            )
+        })
+    }
+
+    /// Resolve `path` (relative or absolute) to a location inside the
+    /// workspace, rejecting traversal and symlink escapes.
+    ///
+    /// # Example
+    /// ```
+    /// use zeptoclaw::tools::ToolContext;
+    ///
+    /// let ctx = ToolContext::new().with_workspace("/tmp");
+    /// assert!(ctx.resolve_in_workspace("../etc/passwd").is_err());
+    /// ```
+    pub fn resolve_in_workspace(&self, path: &str) -> Result<SafePath> {
+        let root = self.workspace_root()?;
+        validate_path_in_workspace(path, &root.to_string_lossy())
+    }
 }
 
 #[cfg(test)]
@@ -403,6 +519,47 @@ mod tests {
         assert_eq!(ctx1.workspace, ctx2.workspace);
     }
 
+    #[test]
+    fn test_workspace_root_requires_workspace() {
+        let err = ToolContext::new().workspace_root().unwrap_err();
+        assert!(err.to_string().contains("Workspace not configured"));
+
+        let ctx = ToolContext::new().with_workspace("/tmp/workspace");
+        assert_eq!(ctx.workspace_root().unwrap(), Path::new("/tmp/workspace"));
+    }
+
+    #[test]
+    fn test_resolve_in_workspace_relative_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let ctx = ToolContext::new().with_workspace(dir.path().to_str().unwrap());
+
+        let safe = ctx.resolve_in_workspace("out/shot.png").unwrap();
+        assert!(safe.as_path().starts_with(dir.path()));
+        assert!(safe.as_path().ends_with("out/shot.png"));
+    }
+
+    #[test]
+    fn test_resolve_in_workspace_rejects_escapes() {
+        let dir = tempfile::tempdir().unwrap();
+        let ctx = ToolContext::new().with_workspace(dir.path().to_str().unwrap());
+
+        assert!(ctx.resolve_in_workspace("../outside.txt").is_err());
+        assert!(ctx.resolve_in_workspace("a/../../outside.txt").is_err());
+        assert!(ctx.resolve_in_workspace("/etc/passwd").is_err());
+        assert!(ToolContext::new().resolve_in_workspace("file.txt").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resolve_in_workspace_rejects_symlink_escape() {
+        let dir = tempfile::tempdir().unwrap();
+        let outside = tempfile::tempdir().unwrap();
+        std::os::unix::fs::symlink(outside.path(), dir.path().join("link")).unwrap();
+        let ctx = ToolContext::new().with_workspace(dir.path().to_str().unwrap());
+
+        assert!(ctx.resolve_in_workspace("link/file.txt").is_err());
+    }
+
     #[test]
     fn test_tool_category_display() {
         assert_eq!(ToolCategory::FilesystemRead.to_string(), "filesystem_read");
@@ -513,4 +670,22 @@ mod tests {
         assert!(out.pause_for_input);
         assert_eq!(out.for_user.as_deref(), Some("user"));
     }
+
+    #[tokio::test]
+    async fn test_default_execute_stream_emits_single_done() {
+        let tool = crate::tools::EchoTool;
+        let mut rx = tool
+            .execute_stream(serde_json::json!({"message": "hi"}), &ToolContext::new())
+            .await;
+
+        let mut chunks = Vec::new();
+        while let Some(chunk) = rx.recv().await {
+            chunks.push(chunk);
+        }
+        assert_eq!(chunks.len(), 1);
+        match chunks.pop().unwrap() {
+            ToolChunk::Done(Ok(output)) => assert_eq!(output.for_llm, "hi"),
+            other => panic!("expected Done(Ok), got {:?}", other),
+        }
+    }
 }
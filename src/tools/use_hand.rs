@@ -0,0 +1,181 @@
+//! Switch the active hand for the current conversation.
+//!
+//! The `use_hand` tool lets a user say "switch to coder mode" mid-chat. It
+//! records the hand on the persisted session; from the next turn the agent
+//! loop uses that hand's system prompt and limits the offered tools to its
+//! `required_tools`, keeping the conversation history intact.
+//!
+//! Like `pin`, the tool edits the persisted session, so the agent loop saves
+//! the session before running it and reloads it afterwards.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+use crate::config::Config;
+use crate::error::{Result, ToolErrorKind, ZeptoError};
+use crate::hands::resolve_hand;
+use crate::session::SessionManager;
+
+use super::{Tool, ToolCategory, ToolContext, ToolOutput};
+
+/// Tool name, matched by the agent loop to sync the session around the call
+/// and always offered so the user can switch back.
+pub const USE_HAND_TOOL: &str = "use_hand";
+
+/// Values of `hand` that clear the session's hand.
+const CLEAR_VALUES: [&str; 3] = ["none", "off", "default"];
+
+/// Tool that sets or clears the hand for the current session.
+pub struct UseHandTool {
+    sessions: Arc<SessionManager>,
+    hands_dir: PathBuf,
+}
+
+impl UseHandTool {
+    /// Create the tool over the agent's session manager.
+    pub fn new(sessions: Arc<SessionManager>) -> Self {
+        Self {
+            sessions,
+            hands_dir: Config::dir().join("hands"),
+        }
+    }
+
+    /// Directory searched for user-defined hands.
+    pub fn with_hands_dir(mut self, dir: PathBuf) -> Self {
+        self.hands_dir = dir;
+        self
+    }
+}
+
+#[async_trait]
+impl Tool for UseHandTool {
+    fn name(&self) -> &str {
+        USE_HAND_TOOL
+    }
+
+    fn description(&self) -> &str {
+        "Switch this conversation to a different hand (e.g. researcher, coder, monitor), or back to the default with \"none\". The hand's instructions and tool set apply from the next message; history is kept. Use when the user asks to change mode."
+    }
+
+    fn compact_description(&self) -> &str {
+        "Switch conversation hand"
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::Memory
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "hand": {
+                    "type": "string",
+                    "description": "Hand name to switch to, or \"none\" for the default"
+                }
+            },
+            "required": ["hand"]
+        })
+    }
+
+    async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<ToolOutput> {
+        let (Some(channel), Some(chat_id)) = (ctx.channel.as_deref(), ctx.chat_id.as_deref())
+        else {
+            return Err(ZeptoError::tool(
+                ToolErrorKind::InvalidArgument,
+                "use_hand requires a channel and chat context",
+            ));
+        };
+        let requested = args
+            .get("hand")
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .ok_or_else(|| {
+                ZeptoError::tool(ToolErrorKind::InvalidArgument, "Missing 'hand' parameter")
+            })?;
+
+        let key = format!("{}:{}", channel, chat_id);
+        let mut session = self.sessions.get_or_create(&key).await?;
+
+        if CLEAR_VALUES
+            .iter()
+            .any(|v| v.eq_ignore_ascii_case(requested))
+        {
+            let previous = session.active_hand.take();
+            self.sessions.save(&session).await?;
+            let text = match previous {
+                Some(name) => format!("Left the {} hand; back to the default setup.", name),
+                None => "No hand was active for this conversation.".to_string(),
+            };
+            return Ok(ToolOutput::split(text.clone(), text));
+        }
+
+        let hand = resolve_hand(requested, &self.hands_dir)?.ok_or_else(|| {
+            ZeptoError::tool(
+                ToolErrorKind::NotFound,
+                format!(
+                    "Unknown hand '{}'. Run `zeptoclaw hand list` to see available hands.",
+                    requested
+                ),
+            )
+        })?;
+        let name = hand.manifest.name;
+        session.active_hand = Some(name.clone());
+        self.sessions.save(&session).await?;
+
+        let tools = if hand.manifest.required_tools.is_empty() {
+            "no tools".to_string()
+        } else {
+            hand.manifest.required_tools.join(", ")
+        };
+        Ok(ToolOutput::split(
+            format!(
+                "Switched this conversation to the {} hand ({}). From the next message its instructions apply and tools are limited to: {}.",
+                name, hand.manifest.description, tools
+            ),
+            format!("Switched to the {} hand.", name),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx() -> ToolContext {
+        ToolContext::new().with_channel("telegram", "42")
+    }
+
+    #[tokio::test]
+    async fn test_use_hand_sets_and_clears_session_hand() {
+        let sessions = Arc::new(SessionManager::new_memory());
+        let dir = tempfile::tempdir().unwrap();
+        let tool = UseHandTool::new(Arc::clone(&sessions)).with_hands_dir(dir.path().into());
+
+        let out = tool
+            .execute(json!({"hand": "Coder"}), &ctx())
+            .await
+            .unwrap();
+        assert_eq!(out.for_user.as_deref(), Some("Switched to the coder hand."));
+        assert!(out.for_llm.contains("read_file"));
+        let session = sessions.get_or_create("telegram:42").await.unwrap();
+        assert_eq!(session.active_hand.as_deref(), Some("coder"));
+
+        let err = tool
+            .execute(json!({"hand": "juggler"}), &ctx())
+            .await
+            .unwrap_err();
+        assert_eq!(err.tool_error_kind(), Some(ToolErrorKind::NotFound));
+        let session = sessions.get_or_create("telegram:42").await.unwrap();
+        assert_eq!(session.active_hand.as_deref(), Some("coder"));
+
+        let out = tool.execute(json!({"hand": "none"}), &ctx()).await.unwrap();
+        assert!(out.for_llm.contains("Left the coder hand"));
+        let session = sessions.get_or_create("telegram:42").await.unwrap();
+        assert!(session.active_hand.is_none());
+    }
+}
@@ -0,0 +1,106 @@
+//! Policy for whether a tool's user-visible output reaches the channel.
+//!
+//! Tools decide what the user sees by setting `ToolOutput::for_user`.
+//! Operators can override that per tool or per [`ToolCategory`] with
+//! `tools.user_output`, optionally on some channels only — for example to
+//! keep progress chatter out of Telegram while the panel still shows it.
+//! Without configuration every `for_user` message is forwarded. The policy
+//! never changes what the model receives (`for_llm`).
+//!
+//! ```json
+//! "tools": {
+//!   "user_output": {
+//!     "channels": ["telegram"],
+//!     "categories": { "network_read": "suppress" },
+//!     "tools": { "web_fetch": "forward" }
+//!   }
+//! }
+//! ```
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::ToolCategory;
+
+/// Whether `for_user` output is sent to the channel.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UserOutputMode {
+    /// Send it (the tool's own choice stands).
+    #[default]
+    Forward,
+    /// Drop it; the model still sees the tool result.
+    Suppress,
+}
+
+/// Operator overrides for tool `for_user` output.
+///
+/// A tool entry wins over its category entry, which wins over `default`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UserOutputConfig {
+    /// Mode for tools matched by neither `tools` nor `categories`.
+    pub default: UserOutputMode,
+    /// Per-category modes.
+    pub categories: HashMap<ToolCategory, UserOutputMode>,
+    /// Per-tool modes, keyed by tool name.
+    pub tools: HashMap<String, UserOutputMode>,
+    /// Channels the policy applies to (empty = all). Other channels always
+    /// receive the output.
+    pub channels: Vec<String>,
+}
+
+impl UserOutputConfig {
+    /// Whether `tool`'s `for_user` output should be sent on `channel`.
+    pub fn forwards(&self, tool: &str, category: Option<ToolCategory>, channel: &str) -> bool {
+        if !self.channels.is_empty() && !self.channels.iter().any(|c| c == channel) {
+            return true;
+        }
+        let mode = self
+            .tools
+            .get(tool)
+            .or_else(|| category.and_then(|c| self.categories.get(&c)))
+            .copied()
+            .unwrap_or(self.default);
+        mode == UserOutputMode::Forward
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_forwards_everything() {
+        let policy = UserOutputConfig::default();
+        assert!(policy.forwards("web_fetch", Some(ToolCategory::NetworkRead), "telegram"));
+        assert!(policy.forwards("unknown", None, "cli"));
+    }
+
+    #[test]
+    fn test_tool_and_category_overrides_scoped_to_channels() {
+        let policy: UserOutputConfig = serde_json::from_value(serde_json::json!({
+            "channels": ["telegram"],
+            "categories": { "network_read": "suppress", "shell": "suppress" },
+            "tools": { "web_search": "forward", "message": "suppress" }
+        }))
+        .unwrap();
+
+        // Category suppresses; a tool entry overrides its category.
+        assert!(!policy.forwards("web_fetch", Some(ToolCategory::NetworkRead), "telegram"));
+        assert!(policy.forwards("web_search", Some(ToolCategory::NetworkRead), "telegram"));
+        assert!(!policy.forwards("message", Some(ToolCategory::Messaging), "telegram"));
+        assert!(policy.forwards("read_file", Some(ToolCategory::FilesystemRead), "telegram"));
+
+        // Channels outside the scope (e.g. the panel) still get everything.
+        assert!(policy.forwards("web_fetch", Some(ToolCategory::NetworkRead), "panel"));
+        assert!(policy.forwards("message", Some(ToolCategory::Messaging), "panel"));
+
+        let strict = UserOutputConfig {
+            default: UserOutputMode::Suppress,
+            ..Default::default()
+        };
+        assert!(!strict.forwards("shell", Some(ToolCategory::Shell), "slack"));
+    }
+}
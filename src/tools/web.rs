@@ -19,13 +19,15 @@ use tokio::net::lookup_host;
 
 use crate::error::{Result, ZeptoError};
 
+use super::output::{offset_arg, offset_schema, Page, DEFAULT_PAGE_BYTES};
 use super::{Tool, ToolCategory, ToolContext, ToolOutput};
 
 const BRAVE_API_URL: &str = "https://api.search.brave.com/res/v1/web/search";
 const DDG_HTML_URL: &str = "https://html.duckduckgo.com/html/";
 const WEB_USER_AGENT: &str = "zeptoclaw/0.1 (+https://github.com/zeptoclaw/zeptoclaw)";
 const MAX_WEB_SEARCH_COUNT: usize = 10;
-const DEFAULT_MAX_FETCH_CHARS: usize = 50_000;
+/// Default page size; longer pages are continued with `offset`.
+const DEFAULT_MAX_FETCH_CHARS: usize = DEFAULT_PAGE_BYTES;
 const MAX_FETCH_CHARS: usize = 200_000;
 const MIN_FETCH_CHARS: usize = 256;
 const MAX_WEB_FETCH_REDIRECTS: usize = 5;
@@ -63,7 +65,7 @@ impl WebSearchTool {
     pub fn new(api_key: &str) -> Self {
         Self {
             api_key: api_key.to_string(),
-            client: Client::new(),
+            client: crate::utils::http::default_client(),
             max_results: 5,
         }
     }
@@ -72,7 +74,7 @@ impl WebSearchTool {
     pub fn with_max_results(api_key: &str, max_results: usize) -> Self {
         Self {
             api_key: api_key.to_string(),
-            client: Client::new(),
+            client: crate::utils::http::default_client(),
             max_results: max_results.clamp(1, MAX_WEB_SEARCH_COUNT),
         }
     }
@@ -287,7 +289,7 @@ impl DdgSearchTool {
     /// Create a new DDG search tool with default settings.
     pub fn new() -> Self {
         Self {
-            client: Client::new(),
+            client: crate::utils::http::default_client(),
             max_results: 5,
         }
     }
@@ -295,7 +297,7 @@ impl DdgSearchTool {
     /// Create with custom max results.
     pub fn with_max_results(max_results: usize) -> Self {
         Self {
-            client: Client::new(),
+            client: crate::utils::http::default_client(),
             max_results: max_results.clamp(1, MAX_WEB_SEARCH_COUNT),
         }
     }
@@ -466,7 +468,7 @@ impl SearxngSearchTool {
         let parsed = validate_searxng_url(api_url)?;
         Ok(Self {
             api_url: parsed,
-            client: Client::new(),
+            client: crate::utils::http::default_client(),
             max_results: max_results.clamp(1, MAX_WEB_SEARCH_COUNT),
         })
     }
@@ -589,7 +591,8 @@ pub struct WebFetchTool {
 impl WebFetchTool {
     /// Create a new web fetch tool.
     pub fn new() -> Self {
-        let client = Client::builder()
+        let client = crate::utils::http::client_builder()
+            .unwrap_or_else(|_| Client::builder())
             .redirect(web_fetch_redirect_policy())
             .timeout(Duration::from_secs(30))
             .build()
@@ -660,7 +663,8 @@ impl Tool for WebFetchTool {
     }
 
     fn description(&self) -> &str {
-        "Fetch a URL and return extracted readable content."
+        "Fetch a URL and return extracted readable content. Long pages are split: \
+         when `truncated` is true, call again with `offset` set to `next_offset`."
     }
 
     fn compact_description(&self) -> &str {
@@ -688,7 +692,8 @@ impl Tool for WebFetchTool {
                 "include_links": {
                     "type": "boolean",
                     "description": "Include a list of links found on the page"
-                }
+                },
+                "offset": offset_schema()
             },
             "required": ["url"]
         })
@@ -737,7 +742,8 @@ impl Tool for WebFetchTool {
         // validated, so the HTTP library cannot re-resolve to a different
         // (potentially private) address.
         let client = if let Some((host, addr)) = pinned {
-            Client::builder()
+            crate::utils::http::client_builder()
+                .unwrap_or_else(|_| Client::builder())
                 .redirect(web_fetch_redirect_policy())
                 .timeout(Duration::from_secs(30))
                 .resolve(&host, addr)
@@ -780,7 +786,7 @@ impl Tool for WebFetchTool {
             .and_then(|v| v.as_bool())
             .unwrap_or(false);
 
-        let (extractor, mut text) = if content_type.contains("application/json") {
+        let (extractor, text) = if content_type.contains("application/json") {
             ("json", body)
         } else if content_type.contains("text/html") || body.trim_start().starts_with('<') {
             let document = Html::parse_document(&body);
@@ -795,28 +801,31 @@ impl Tool for WebFetchTool {
             ("raw", body)
         };
 
-        let truncated = text.len() > max_chars;
-        if truncated {
-            // Find a valid UTF-8 char boundary at or before max_chars to avoid panic
-            let mut end = max_chars;
-            while !text.is_char_boundary(end) {
-                end -= 1;
-            }
-            text.truncate(end);
-        }
+        let page = Page::of(&text, offset_arg(&args), max_chars);
+        let mut result = json!({
+            "url": url,
+            "final_url": final_url,
+            "status": status.as_u16(),
+            "extractor": extractor,
+        });
+        append_page(&mut result, &page);
+        Ok(ToolOutput::llm_only(result.to_string()))
+    }
+}
 
-        Ok(ToolOutput::llm_only(
-            json!({
-                "url": url,
-                "final_url": final_url,
-                "status": status.as_u16(),
-                "extractor": extractor,
-                "truncated": truncated,
-                "length": text.len(),
-                "text": text,
-            })
-            .to_string(),
-        ))
+/// Add a page of fetched text to a `web_fetch` result, with the offset to
+/// continue from when the text was cut.
+fn append_page(result: &mut Value, page: &Page<'_>) {
+    result["truncated"] = json!(page.next_offset.is_some());
+    result["offset"] = json!(page.offset);
+    result["length"] = json!(page.text.len());
+    result["total_length"] = json!(page.total);
+    result["text"] = json!(page.text);
+    if let Some(next) = page.next_offset {
+        result["next_offset"] = json!(next);
+    }
+    if let Some(note) = page.marker("web_fetch") {
+        result["note"] = json!(note);
     }
 }
 
@@ -1783,6 +1792,25 @@ mod tests {
         assert_eq!(tool.max_chars, 10_000);
     }
 
+    #[test]
+    fn test_web_fetch_page_reports_next_offset() {
+        let text = "line of page text\n".repeat(200);
+        let mut result = json!({"url": "https://example.com"});
+        append_page(&mut result, &Page::of(&text, 0, 1000));
+        assert_eq!(result["truncated"], true);
+        let next = result["next_offset"].as_u64().unwrap() as usize;
+        assert!(result["note"]
+            .as_str()
+            .unwrap()
+            .contains(&format!("\"offset\": {}", next)));
+
+        let mut rest = json!({});
+        append_page(&mut rest, &Page::of(&text, next, text.len()));
+        assert_eq!(rest["truncated"], false);
+        assert!(rest.get("next_offset").is_none());
+        assert_eq!(rest["length"].as_u64().unwrap() as usize, text.len() - next);
+    }
+
     #[test]
     fn test_is_blocked_host_no_host() {
         // A URL with no host should be blocked
@@ -25,7 +25,7 @@ impl WhatsAppTool {
             phone_number_id: phone_number_id.to_string(),
             access_token: access_token.to_string(),
             default_language: "ms".to_string(),
-            client: Client::new(),
+            client: crate::utils::http::default_client(),
         }
     }
 
@@ -39,7 +39,7 @@ impl WhatsAppTool {
             phone_number_id: phone_number_id.to_string(),
             access_token: access_token.to_string(),
             default_language: default_language.to_string(),
-            client: Client::new(),
+            client: crate::utils::http::default_client(),
         }
     }
 }
@@ -62,6 +62,10 @@ impl Tool for WhatsAppTool {
         ToolCategory::Messaging
     }
 
+    fn is_idempotent(&self, _args: &Value) -> bool {
+        false
+    }
+
     fn parameters(&self) -> Value {
         json!({
             "type": "object",
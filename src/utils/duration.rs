@@ -0,0 +1,40 @@
+//! Human duration parsing shared by CLI flags, tools and config values.
+
+/// Parse a duration like `90`, `90s`, `30m`, `12h` or `7d` into seconds.
+///
+/// The unit suffix is case-insensitive and defaults to seconds. Returns
+/// `None` for anything else, including values that overflow `u64`.
+pub fn parse_duration_secs(input: &str) -> Option<u64> {
+    let input = input.trim().to_ascii_lowercase();
+    let (digits, multiplier) = match input.char_indices().last() {
+        Some((i, 's')) => (&input[..i], 1),
+        Some((i, 'm')) => (&input[..i], 60),
+        Some((i, 'h')) => (&input[..i], 60 * 60),
+        Some((i, 'd')) => (&input[..i], 24 * 60 * 60),
+        _ => (input.as_str(), 1),
+    };
+    digits.trim().parse::<u64>().ok()?.checked_mul(multiplier)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration_secs_units() {
+        assert_eq!(parse_duration_secs("90"), Some(90));
+        assert_eq!(parse_duration_secs("90s"), Some(90));
+        assert_eq!(parse_duration_secs(" 30M "), Some(1_800));
+        assert_eq!(parse_duration_secs("12h"), Some(43_200));
+        assert_eq!(parse_duration_secs("7d"), Some(604_800));
+    }
+
+    #[test]
+    fn test_parse_duration_secs_invalid() {
+        assert_eq!(parse_duration_secs(""), None);
+        assert_eq!(parse_duration_secs("h"), None);
+        assert_eq!(parse_duration_secs("5w"), None);
+        assert_eq!(parse_duration_secs("-5s"), None);
+        assert_eq!(parse_duration_secs(&format!("{}d", u64::MAX)), None);
+    }
+}
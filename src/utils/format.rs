@@ -0,0 +1,269 @@
+//! Human-friendly formatting of times and numbers.
+//!
+//! Tools and reports use [`DisplayFormat`] for the text a person reads:
+//! calendar times in the user's timezone (`Tue Mar 3, 2:00–3:00 PM`) and
+//! counts with thousands separators (`1,234,567`). Machine-readable values
+//! (RFC3339 timestamps, raw numbers) belong in structured or JSON output.
+//!
+//! Weekday and month names are always English; the locale chooses the clock
+//! style and the digit separators.
+
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, TimeZone};
+use chrono_tz::Tz;
+
+/// Timezone and locale used to render values for a user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisplayFormat {
+    tz: Tz,
+    locale: String,
+}
+
+impl Default for DisplayFormat {
+    fn default() -> Self {
+        Self::new(Tz::UTC, "en")
+    }
+}
+
+impl DisplayFormat {
+    /// Create a format for `tz` and a BCP 47 `locale` (e.g. `en-US`, `de`).
+    pub fn new(tz: Tz, locale: &str) -> Self {
+        Self {
+            tz,
+            locale: locale.trim().replace('_', "-").to_ascii_lowercase(),
+        }
+    }
+
+    /// Create a format from an IANA timezone name, falling back to UTC when
+    /// the name is unknown.
+    pub fn from_names(timezone: &str, locale: &str) -> Self {
+        Self::new(timezone.trim().parse().unwrap_or(Tz::UTC), locale)
+    }
+
+    /// Timezone times are shown in.
+    pub fn timezone(&self) -> Tz {
+        self.tz
+    }
+
+    /// Replace the timezone (builder pattern).
+    pub fn with_timezone(mut self, tz: Tz) -> Self {
+        self.tz = tz;
+        self
+    }
+
+    /// Replace the locale (builder pattern).
+    pub fn with_locale(self, locale: &str) -> Self {
+        Self::new(self.tz, locale)
+    }
+
+    fn language(&self) -> &str {
+        self.locale.split('-').next().unwrap_or_default()
+    }
+
+    /// English outside the UK and Ireland uses a 12-hour clock; everything
+    /// else is shown with 24 hours.
+    fn twelve_hour(&self) -> bool {
+        self.language() == "en" && !matches!(self.locale.as_str(), "en-gb" | "en-ie")
+    }
+
+    /// `(thousands separator, decimal separator)` for the locale.
+    fn separators(&self) -> (char, char) {
+        match self.language() {
+            "de" | "es" | "it" | "nl" | "pt" | "id" | "tr" | "da" | "el" | "vi" => ('.', ','),
+            "fr" | "ru" | "uk" | "pl" | "cs" | "sv" | "nb" | "fi" => ('\u{a0}', ','),
+            _ => (',', '.'),
+        }
+    }
+
+    /// Integer with thousands separators: `1,234,567` (`1.234.567` in `de`).
+    pub fn count(&self, n: u64) -> String {
+        let (group, _) = self.separators();
+        let digits = n.to_string();
+        let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+        for (i, ch) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i).is_multiple_of(3) {
+                out.push(group);
+            }
+            out.push(ch);
+        }
+        out
+    }
+
+    /// Dollar amount with two decimals: `$1,234.50` (`$1.234,50` in `de`).
+    pub fn usd(&self, amount: f64) -> String {
+        let (_, decimal) = self.separators();
+        let cents = (amount.abs() * 100.0).round() as u64;
+        let sign = if amount < 0.0 && cents > 0 { "-" } else { "" };
+        format!(
+            "{}${}{}{:02}",
+            sign,
+            self.count(cents / 100),
+            decimal,
+            cents % 100
+        )
+    }
+
+    /// Friendly range for a calendar event.
+    ///
+    /// `start` and `end` are RFC3339 timestamps or, for all-day events,
+    /// `YYYY-MM-DD` dates with an exclusive end (as Google Calendar returns
+    /// them). Values that parse as neither are returned unchanged.
+    pub fn event_range(&self, start: &str, end: &str) -> String {
+        if let (Ok(start), Ok(end)) = (
+            DateTime::parse_from_rfc3339(start),
+            DateTime::parse_from_rfc3339(end),
+        ) {
+            let start = start.with_timezone(&self.tz);
+            let end = end.with_timezone(&self.tz);
+            let day = start.format("%a %b %-d");
+            if start.date_naive() != end.date_naive() {
+                return format!(
+                    "{}, {} – {}, {}",
+                    day,
+                    self.time(&start, true),
+                    end.format("%a %b %-d"),
+                    self.time(&end, true)
+                );
+            }
+            let same_half = start.format("%p").to_string() == end.format("%p").to_string();
+            let first = self.time(&start, !(self.twelve_hour() && same_half));
+            return format!("{}, {}–{}", day, first, self.time(&end, true));
+        }
+
+        if let (Ok(start), Ok(end)) = (
+            NaiveDate::parse_from_str(start, "%Y-%m-%d"),
+            NaiveDate::parse_from_str(end, "%Y-%m-%d"),
+        ) {
+            let last = end - Duration::days(1);
+            if last <= start {
+                return format!("{} (all day)", start.format("%a %b %-d"));
+            }
+            return format!(
+                "{} – {} (all day)",
+                start.format("%a %b %-d"),
+                last.format("%a %b %-d")
+            );
+        }
+
+        format!("{} → {}", start, end)
+    }
+
+    /// Pin a datetime without an offset (`2026-03-03T14:00`) to this
+    /// timezone and return it as RFC3339.
+    ///
+    /// Values that already carry an offset, dates, and anything unparseable
+    /// are returned unchanged. A local time skipped by a DST jump is moved
+    /// forward an hour.
+    pub fn localize(&self, value: &str) -> String {
+        let value = value.trim();
+        if DateTime::parse_from_rfc3339(value).is_ok() {
+            return value.to_string();
+        }
+        let Some(naive) = [
+            "%Y-%m-%dT%H:%M:%S",
+            "%Y-%m-%dT%H:%M",
+            "%Y-%m-%d %H:%M:%S",
+            "%Y-%m-%d %H:%M",
+        ]
+        .iter()
+        .find_map(|fmt| NaiveDateTime::parse_from_str(value, fmt).ok()) else {
+            return value.to_string();
+        };
+        self.tz
+            .from_local_datetime(&naive)
+            .earliest()
+            .or_else(|| {
+                self.tz
+                    .from_local_datetime(&(naive + Duration::hours(1)))
+                    .earliest()
+            })
+            .map(|at| at.to_rfc3339())
+            .unwrap_or_else(|| value.to_string())
+    }
+
+    /// `2:00 PM` / `2:00` (12-hour, without meridiem) / `14:00`.
+    fn time(&self, at: &DateTime<Tz>, with_meridiem: bool) -> String {
+        match (self.twelve_hour(), with_meridiem) {
+            (true, true) => at.format("%-I:%M %p").to_string(),
+            (true, false) => at.format("%-I:%M").to_string(),
+            (false, _) => at.format("%H:%M").to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_range_in_user_timezone() {
+        let ny = DisplayFormat::from_names("America/New_York", "en-US");
+        assert_eq!(
+            ny.event_range("2026-03-03T19:00:00Z", "2026-03-03T20:00:00Z"),
+            "Tue Mar 3, 2:00–3:00 PM"
+        );
+        assert_eq!(
+            ny.event_range("2026-03-03T16:00:00Z", "2026-03-03T18:30:00Z"),
+            "Tue Mar 3, 11:00 AM–1:30 PM"
+        );
+        // Same instant, but already the next day in Berlin, on a 24-hour clock.
+        let berlin = DisplayFormat::from_names("Europe/Berlin", "de");
+        assert_eq!(
+            berlin.event_range("2026-03-03T22:30:00-05:00", "2026-03-04T00:00:00-05:00"),
+            "Wed Mar 4, 04:30–06:00"
+        );
+        // Spanning midnight locally.
+        assert_eq!(
+            ny.event_range("2026-03-04T04:00:00Z", "2026-03-04T06:00:00Z"),
+            "Tue Mar 3, 11:00 PM – Wed Mar 4, 1:00 AM"
+        );
+        // All-day events use an exclusive end date.
+        assert_eq!(
+            ny.event_range("2026-03-03", "2026-03-04"),
+            "Tue Mar 3 (all day)"
+        );
+        assert_eq!(
+            ny.event_range("2026-03-03", "2026-03-06"),
+            "Tue Mar 3 – Thu Mar 5 (all day)"
+        );
+        assert_eq!(ny.event_range("soon", "later"), "soon → later");
+        // Unknown timezones fall back to UTC.
+        assert_eq!(
+            DisplayFormat::from_names("Mars/Olympus", "en").timezone(),
+            Tz::UTC
+        );
+    }
+
+    #[test]
+    fn test_localize_naive_time_in_configured_timezone() {
+        let kl = DisplayFormat::from_names("Asia/Kuala_Lumpur", "en");
+        assert_eq!(kl.localize("2026-03-03T14:00"), "2026-03-03T14:00:00+08:00");
+        let ny = DisplayFormat::from_names("America/New_York", "en");
+        assert_eq!(
+            ny.localize("2026-07-01 09:30:00"),
+            "2026-07-01T09:30:00-04:00"
+        );
+        // 02:30 does not exist on the spring-forward day.
+        assert_eq!(ny.localize("2026-03-08T02:30"), "2026-03-08T03:30:00-04:00");
+        // Explicit offsets, dates, and junk pass through.
+        assert_eq!(ny.localize("2026-03-03T14:00:00Z"), "2026-03-03T14:00:00Z");
+        assert_eq!(ny.localize("2026-03-03"), "2026-03-03");
+        assert_eq!(ny.localize("tomorrow"), "tomorrow");
+    }
+
+    #[test]
+    fn test_number_formatting_by_locale() {
+        let en = DisplayFormat::new(Tz::UTC, "en");
+        assert_eq!(en.count(0), "0");
+        assert_eq!(en.count(999), "999");
+        assert_eq!(en.count(1_234_567), "1,234,567");
+        assert_eq!(en.usd(1234.5), "$1,234.50");
+        assert_eq!(en.usd(0.004), "$0.00");
+
+        let de = DisplayFormat::new(Tz::UTC, "de-DE");
+        assert_eq!(de.count(1_234_567), "1.234.567");
+        assert_eq!(de.usd(1234.5), "$1.234,50");
+
+        let fr = DisplayFormat::new(Tz::UTC, "fr");
+        assert_eq!(fr.count(40_000), "40\u{a0}000");
+    }
+}
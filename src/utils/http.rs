@@ -0,0 +1,194 @@
+//! Shared HTTP client factory.
+//!
+//! Providers and HTTP tools build their `reqwest::Client` through
+//! [`client_builder`] so that the `http` config section (proxy, connect/read
+//! timeouts, extra root certificates) applies everywhere. Call [`configure`]
+//! once after loading the config; until then the factory produces clients
+//! with the same defaults as a plain `reqwest::Client::builder()`.
+
+use std::sync::RwLock;
+use std::time::Duration;
+
+use once_cell::sync::Lazy;
+use reqwest::{Certificate, Client, ClientBuilder, Proxy};
+use tracing::warn;
+
+use crate::config::{expand_home, HttpClientConfig};
+use crate::error::{Result, ZeptoError};
+
+static SETTINGS: Lazy<RwLock<HttpClientConfig>> =
+    Lazy::new(|| RwLock::new(HttpClientConfig::default()));
+
+/// Install the process-wide HTTP client settings.
+///
+/// Only clients built after this call pick up the new settings.
+pub fn configure(settings: &HttpClientConfig) {
+    match SETTINGS.write() {
+        Ok(mut guard) => *guard = settings.clone(),
+        Err(poisoned) => *poisoned.into_inner() = settings.clone(),
+    }
+}
+
+/// Current process-wide HTTP client settings.
+pub fn settings() -> HttpClientConfig {
+    match SETTINGS.read() {
+        Ok(guard) => guard.clone(),
+        Err(poisoned) => poisoned.into_inner().clone(),
+    }
+}
+
+/// A client builder with the global settings applied.
+///
+/// Callers may keep chaining (overall timeout, redirect policy, DNS
+/// pinning, ...) before calling `build()`.
+pub fn client_builder() -> Result<ClientBuilder> {
+    client_builder_with(&settings())
+}
+
+/// Like [`client_builder`], with explicit settings instead of the global ones.
+pub fn client_builder_with(settings: &HttpClientConfig) -> Result<ClientBuilder> {
+    let resolved = ResolvedHttpSettings::resolve(settings)?;
+
+    let mut builder = Client::builder();
+    if let Some(proxy) = resolved.proxy {
+        builder = builder.proxy(proxy);
+    }
+    if let Some(connect_timeout) = resolved.connect_timeout {
+        builder = builder.connect_timeout(connect_timeout);
+    }
+    if let Some(read_timeout) = resolved.read_timeout {
+        builder = builder.read_timeout(read_timeout);
+    }
+    for cert in resolved.root_certificates {
+        builder = builder.add_root_certificate(cert);
+    }
+    Ok(builder)
+}
+
+/// HTTP settings validated and converted to `reqwest` types.
+struct ResolvedHttpSettings {
+    proxy: Option<Proxy>,
+    connect_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+    root_certificates: Vec<Certificate>,
+}
+
+impl ResolvedHttpSettings {
+    fn resolve(settings: &HttpClientConfig) -> Result<Self> {
+        let proxy =
+            match settings.proxy.as_deref().map(str::trim) {
+                Some(url) if !url.is_empty() => Some(Proxy::all(url).map_err(|e| {
+                    ZeptoError::Config(format!("Invalid http.proxy '{}': {}", url, e))
+                })?),
+                _ => None,
+            };
+        let root_certificates = settings
+            .ca_certs
+            .iter()
+            .map(|path| load_certificate(path))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            proxy,
+            connect_timeout: settings.connect_timeout_secs.map(Duration::from_secs),
+            read_timeout: settings.read_timeout_secs.map(Duration::from_secs),
+            root_certificates,
+        })
+    }
+}
+
+/// Build a client with the global settings and `timeout` as the overall
+/// request timeout.
+///
+/// Invalid settings are logged and fall back to a client with only the
+/// timeout applied, so a bad proxy URL cannot take a provider offline.
+pub fn client(timeout: Duration) -> Client {
+    build_or_fallback(Some(timeout))
+}
+
+/// Build a client with the global settings and no overall request timeout,
+/// like `reqwest::Client::new()`.
+pub fn default_client() -> Client {
+    build_or_fallback(None)
+}
+
+fn build_or_fallback(timeout: Option<Duration>) -> Client {
+    let with_timeout = |builder: ClientBuilder| match timeout {
+        Some(timeout) => builder.timeout(timeout),
+        None => builder,
+    };
+    client_builder()
+        .and_then(|builder| {
+            with_timeout(builder)
+                .build()
+                .map_err(|e| ZeptoError::Config(format!("HTTP client error: {}", e)))
+        })
+        .unwrap_or_else(|e| {
+            warn!("Ignoring http settings: {}", e);
+            with_timeout(Client::builder())
+                .build()
+                .unwrap_or_else(|_| Client::new())
+        })
+}
+
+fn load_certificate(path: &str) -> Result<Certificate> {
+    let pem = std::fs::read(expand_home(path)).map_err(|e| {
+        ZeptoError::Config(format!(
+            "Failed to read http.ca_certs entry '{}': {}",
+            path, e
+        ))
+    })?;
+    Certificate::from_pem(&pem)
+        .map_err(|e| ZeptoError::Config(format!("Invalid PEM certificate in '{}': {}", path, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder_honors_proxy_and_timeouts() {
+        let settings = HttpClientConfig {
+            proxy: Some("http://proxy.corp.example:3128".to_string()),
+            connect_timeout_secs: Some(5),
+            read_timeout_secs: Some(45),
+            ca_certs: Vec::new(),
+        };
+        let resolved = ResolvedHttpSettings::resolve(&settings).unwrap();
+        assert!(resolved.proxy.is_some());
+        assert_eq!(resolved.connect_timeout, Some(Duration::from_secs(5)));
+        assert_eq!(resolved.read_timeout, Some(Duration::from_secs(45)));
+
+        client_builder_with(&settings)
+            .unwrap()
+            .timeout(Duration::from_secs(120))
+            .build()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_unset_settings_keep_defaults() {
+        let resolved = ResolvedHttpSettings::resolve(&HttpClientConfig::default()).unwrap();
+        assert!(resolved.proxy.is_none());
+        assert_eq!(resolved.connect_timeout, None);
+        assert_eq!(resolved.read_timeout, None);
+        assert!(resolved.root_certificates.is_empty());
+    }
+
+    #[test]
+    fn test_builder_rejects_bad_proxy_and_missing_cert() {
+        let bad_proxy = HttpClientConfig {
+            proxy: Some("not a url".to_string()),
+            ..Default::default()
+        };
+        let err = client_builder_with(&bad_proxy).unwrap_err();
+        assert!(err.to_string().contains("http.proxy"));
+
+        let missing_cert = HttpClientConfig {
+            ca_certs: vec!["/nonexistent/zeptoclaw-ca.pem".to_string()],
+            ..Default::default()
+        };
+        let err = client_builder_with(&missing_cert).unwrap_err();
+        assert!(err.to_string().contains("ca_certs"));
+    }
+}
@@ -7,17 +7,33 @@
 //! - `json`: structured JSON lines for log aggregators (e.g. Loki, CloudWatch)
 
 use crate::config::{LogFormat, LoggingConfig};
+use crate::utils::telemetry::OtlpConfig;
+
+/// Keeps optional exporters alive for the life of the process.
+///
+/// Hold the guard until shutdown; dropping it flushes pending OpenTelemetry
+/// spans and metrics.
+#[must_use = "dropping the guard stops telemetry export"]
+#[derive(Default)]
+pub struct LoggingGuard {
+    #[cfg(feature = "otel")]
+    _otel: Option<super::otel::OtelGuard>,
+}
 
 /// Initialize the global tracing subscriber from config.
 ///
 /// Call this once at startup before any tracing events are emitted.
-/// Falls back to `RUST_LOG` env var; if unset, uses `cfg.level`.
-pub fn init_logging(cfg: &LoggingConfig) {
-    use tracing_subscriber::EnvFilter;
+/// Falls back to `RUST_LOG` env var; if unset, uses `cfg.level`. When
+/// `otlp.enabled` is set and the binary was built with the `otel` feature,
+/// spans are also exported to the configured OTLP endpoint.
+pub fn init_logging(cfg: &LoggingConfig, otlp: &OtlpConfig) -> LoggingGuard {
+    use tracing_subscriber::layer::SubscriberExt;
+    use tracing_subscriber::util::SubscriberInitExt;
+    use tracing_subscriber::{EnvFilter, Layer};
 
     let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(&cfg.level));
 
-    match cfg.format {
+    let fmt_layer = match cfg.format {
         LogFormat::Json => {
             if let Some(path) = &cfg.file {
                 let file = std::fs::OpenOptions::new()
@@ -25,28 +41,54 @@ pub fn init_logging(cfg: &LoggingConfig) {
                     .append(true)
                     .open(path)
                     .expect("failed to open log file");
-                tracing_subscriber::fmt()
+                tracing_subscriber::fmt::layer()
                     .json()
-                    .with_env_filter(filter)
                     .with_writer(move || file.try_clone().expect("file writer"))
-                    .init();
+                    .boxed()
             } else {
-                tracing_subscriber::fmt()
-                    .json()
-                    .with_env_filter(filter)
-                    .init();
+                tracing_subscriber::fmt::layer().json().boxed()
             }
         }
         // Pretty and Component both use the compact text formatter.
         // Component-tagged events are emitted via the `log_component!` macro
         // which adds a structured `component` field — no custom layer needed.
-        _ => {
-            tracing_subscriber::fmt()
-                .with_env_filter(filter)
-                .with_target(true)
-                .compact()
-                .init();
+        _ => tracing_subscriber::fmt::layer()
+            .with_target(true)
+            .compact()
+            .boxed(),
+    };
+
+    #[cfg(feature = "otel")]
+    let (otel_layer, otel_guard) = if otlp.enabled {
+        match super::otel::init(otlp) {
+            Ok((layer, guard)) => (Some(layer), Some(guard)),
+            Err(e) => {
+                eprintln!("OpenTelemetry export disabled: {}", e);
+                (None, None)
+            }
         }
+    } else {
+        (None, None)
+    };
+    #[cfg(not(feature = "otel"))]
+    let otel_layer: Option<tracing_subscriber::layer::Identity> = None;
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(fmt_layer)
+        .with(otel_layer)
+        .init();
+
+    #[cfg(not(feature = "otel"))]
+    if otlp.enabled {
+        tracing::warn!(
+            "telemetry.otlp.enabled is set but this build lacks the `otel` feature; \
+             traces and metrics will not be exported"
+        );
+    }
+    LoggingGuard {
+        #[cfg(feature = "otel")]
+        _otel: otel_guard,
     }
 }
 
@@ -1,9 +1,17 @@
 //! Utils module - Utility functions and helpers
 
 pub mod cost;
+pub mod duration;
+pub mod format;
+pub mod fs;
+pub mod http;
 pub mod logging;
 pub mod metrics;
+#[cfg(feature = "otel")]
+pub mod otel;
 pub mod sanitize;
 pub mod slo;
+#[cfg(feature = "sqlite-store")]
+pub mod sqlite;
 pub mod string;
 pub mod telemetry;
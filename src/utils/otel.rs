@@ -0,0 +1,207 @@
+//! OpenTelemetry export of traces and metrics (feature `otel`).
+//!
+//! When `telemetry.otlp.enabled` is set, [`init`] builds a `tracing` layer
+//! that exports spans over OTLP/HTTP, and a meter provider that periodically
+//! exports provider latency and — once [`register_usage_metrics`] is called —
+//! the gateway's [`UsageMetrics`] counters.
+//!
+//! Span fields become OTel attributes, so the per-request span's `trace_id`
+//! (the same ID carried on bus messages) can be used to find a request in the
+//! collector.
+
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+use opentelemetry::metrics::MeterProvider as _;
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::{MetricExporter, SpanExporter, WithExportConfig, WithHttpConfig};
+use opentelemetry_sdk::metrics::{PeriodicReader, SdkMeterProvider};
+use opentelemetry_sdk::trace::TracerProvider;
+use opentelemetry_sdk::{runtime, Resource};
+use tracing::Subscriber;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+use super::telemetry::OtlpConfig;
+use crate::error::{Result, ZeptoError};
+use crate::health::UsageMetrics;
+use crate::providers::latency::{LatencyStats, LatencyTracker};
+
+/// Instrumentation scope name for spans and metrics.
+const SCOPE: &str = "zeptoclaw";
+
+/// Keeps the exporters alive; flushes and shuts them down on drop.
+pub struct OtelGuard {
+    tracer_provider: TracerProvider,
+    meter_provider: SdkMeterProvider,
+}
+
+impl Drop for OtelGuard {
+    fn drop(&mut self) {
+        if let Err(e) = self.tracer_provider.shutdown() {
+            eprintln!("OpenTelemetry trace shutdown failed: {}", e);
+        }
+        if let Err(e) = self.meter_provider.shutdown() {
+            eprintln!("OpenTelemetry metrics shutdown failed: {}", e);
+        }
+    }
+}
+
+/// Build the span-export layer and metric pipeline for `cfg`.
+///
+/// Must be called inside a Tokio runtime. The meter provider is installed
+/// globally so [`register_usage_metrics`] can attach to it later.
+pub fn init<S>(cfg: &OtlpConfig) -> Result<(impl Layer<S>, OtelGuard)>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    let resource = Resource::new(vec![KeyValue::new(
+        "service.name",
+        cfg.service_name.clone(),
+    )]);
+
+    let span_exporter = SpanExporter::builder()
+        .with_http()
+        .with_endpoint(cfg.traces_endpoint())
+        .with_headers(cfg.headers.clone())
+        .build()
+        .map_err(|e| ZeptoError::Config(format!("OTLP trace exporter: {}", e)))?;
+    let tracer_provider = TracerProvider::builder()
+        .with_batch_exporter(span_exporter, runtime::Tokio)
+        .with_resource(resource.clone())
+        .build();
+
+    let metric_exporter = MetricExporter::builder()
+        .with_http()
+        .with_endpoint(cfg.metrics_endpoint())
+        .with_headers(cfg.headers.clone())
+        .build()
+        .map_err(|e| ZeptoError::Config(format!("OTLP metric exporter: {}", e)))?;
+    let reader = PeriodicReader::builder(metric_exporter, runtime::Tokio)
+        .with_interval(Duration::from_secs(cfg.metrics_interval_secs.max(1)))
+        .build();
+    let meter_provider = SdkMeterProvider::builder()
+        .with_reader(reader)
+        .with_resource(resource)
+        .build();
+    opentelemetry::global::set_meter_provider(meter_provider.clone());
+    register_latency_metrics(&meter_provider);
+
+    let layer = span_layer(&tracer_provider);
+    Ok((
+        layer,
+        OtelGuard {
+            tracer_provider,
+            meter_provider,
+        },
+    ))
+}
+
+/// `tracing` layer exporting spans through `provider`.
+fn span_layer<S>(provider: &TracerProvider) -> impl Layer<S>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    tracing_opentelemetry::layer().with_tracer(provider.tracer(SCOPE))
+}
+
+/// Per-provider p50/p95 response times from [`LatencyTracker::global`].
+fn register_latency_metrics(provider: &SdkMeterProvider) {
+    let meter = provider.meter(SCOPE);
+    let gauges: [(&str, fn(&LatencyStats) -> u64); 2] = [
+        ("zeptoclaw.provider.latency.p50", |s| s.p50_ms),
+        ("zeptoclaw.provider.latency.p95", |s| s.p95_ms),
+    ];
+    for (name, pick) in gauges {
+        meter
+            .u64_observable_gauge(name)
+            .with_unit("ms")
+            .with_callback(move |observer| {
+                for (provider, stats) in LatencyTracker::global().snapshot() {
+                    observer.observe(pick(&stats), &[KeyValue::new("provider", provider)]);
+                }
+            })
+            .build();
+    }
+}
+
+/// Export the counters in `metrics` through the global meter provider.
+///
+/// A no-op until [`init`] has installed a provider.
+pub fn register_usage_metrics(metrics: Arc<UsageMetrics>) {
+    let meter = opentelemetry::global::meter(SCOPE);
+    let counters: [(&str, fn(&UsageMetrics) -> u64); 5] = [
+        ("zeptoclaw.requests", |m| m.requests.load(Ordering::Relaxed)),
+        ("zeptoclaw.tool_calls", |m| {
+            m.tool_calls.load(Ordering::Relaxed)
+        }),
+        ("zeptoclaw.tokens.input", |m| {
+            m.input_tokens.load(Ordering::Relaxed)
+        }),
+        ("zeptoclaw.tokens.output", |m| {
+            m.output_tokens.load(Ordering::Relaxed)
+        }),
+        ("zeptoclaw.errors", |m| m.errors.load(Ordering::Relaxed)),
+    ];
+    for (name, read) in counters {
+        let metrics = Arc::clone(&metrics);
+        meter
+            .u64_observable_counter(name)
+            .with_callback(move |observer| observer.observe(read(&metrics), &[]))
+            .build();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::Value;
+    use opentelemetry_sdk::testing::trace::InMemorySpanExporter;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn test_spans_carry_request_attributes() {
+        let exporter = InMemorySpanExporter::default();
+        let provider = TracerProvider::builder()
+            .with_simple_exporter(exporter.clone())
+            .build();
+        let subscriber = tracing_subscriber::registry().with(span_layer(&provider));
+
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!(
+                "request",
+                trace_id = "4bf92f3577b34da6",
+                channel = "telegram",
+                session_id = "telegram:42",
+            );
+            let _entered = span.enter();
+            tracing::info_span!("tool", tool = "web_fetch").in_scope(|| {});
+        });
+        for result in provider.force_flush() {
+            result.unwrap();
+        }
+
+        let spans = exporter.get_finished_spans().unwrap();
+        let request = spans.iter().find(|s| s.name == "request").unwrap();
+        let attr = |key: &str| {
+            request
+                .attributes
+                .iter()
+                .find(|kv| kv.key.as_str() == key)
+                .map(|kv| kv.value.clone())
+        };
+        assert_eq!(attr("trace_id"), Some(Value::from("4bf92f3577b34da6")));
+        assert_eq!(attr("channel"), Some(Value::from("telegram")));
+        assert_eq!(attr("session_id"), Some(Value::from("telegram:42")));
+
+        // Child spans share the request's OTel trace.
+        let tool = spans.iter().find(|s| s.name == "tool").unwrap();
+        assert_eq!(
+            tool.span_context.trace_id(),
+            request.span_context.trace_id()
+        );
+        assert_eq!(tool.parent_span_id, request.span_context.span_id());
+    }
+}
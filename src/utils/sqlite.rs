@@ -0,0 +1,84 @@
+//! Shared SQLite connection setup for the `sqlite-store` backend.
+//!
+//! Quota usage and paired devices share one database file. Connections run
+//! in WAL mode with a busy timeout so several processes (gateway, CLI) can
+//! read and write concurrently without `SQLITE_BUSY` failures.
+
+use std::path::Path;
+use std::time::Duration;
+
+use rusqlite::{Connection, Transaction, TransactionBehavior};
+
+/// How long a writer waits for another connection's lock before failing.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Open (creating if needed) the SQLite database at `path`.
+pub fn open(path: &Path) -> rusqlite::Result<Connection> {
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    let conn = Connection::open(path)?;
+    conn.busy_timeout(BUSY_TIMEOUT)?;
+    conn.pragma_update(None, "journal_mode", "WAL")?;
+    conn.pragma_update(None, "synchronous", "NORMAL")?;
+    Ok(conn)
+}
+
+/// Run a one-time data import, recording `name` so later opens skip it.
+///
+/// The check and the import share an immediate transaction, so two processes
+/// starting at once cannot both import. Returns the number of rows imported,
+/// or `None` when the import already ran.
+pub fn import_once(
+    conn: &mut Connection,
+    name: &str,
+    import: impl FnOnce(&Transaction<'_>) -> rusqlite::Result<usize>,
+) -> rusqlite::Result<Option<usize>> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS imports (name TEXT PRIMARY KEY, imported_at INTEGER NOT NULL)",
+        [],
+    )?;
+    let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+    let done: bool = tx.query_row(
+        "SELECT EXISTS(SELECT 1 FROM imports WHERE name = ?1)",
+        [name],
+        |row| row.get(0),
+    )?;
+    if done {
+        return Ok(None);
+    }
+    let rows = import(&tx)?;
+    tx.execute(
+        "INSERT INTO imports (name, imported_at) VALUES (?1, strftime('%s', 'now'))",
+        [name],
+    )?;
+    tx.commit()?;
+    Ok(Some(rows))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_open_creates_parent_dir_and_enables_wal() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("nested").join("state.db");
+        let conn = open(&path).unwrap();
+        let mode: String = conn
+            .pragma_query_value(None, "journal_mode", |row| row.get(0))
+            .unwrap();
+        assert_eq!(mode.to_lowercase(), "wal");
+        assert!(path.exists());
+    }
+
+    #[test]
+    fn test_import_once_runs_a_single_time() {
+        let tmp = TempDir::new().unwrap();
+        let mut conn = open(&tmp.path().join("state.db")).unwrap();
+        assert_eq!(import_once(&mut conn, "x", |_| Ok(3)).unwrap(), Some(3));
+        assert_eq!(import_once(&mut conn, "x", |_| Ok(3)).unwrap(), None);
+        assert_eq!(import_once(&mut conn, "y", |_| Ok(0)).unwrap(), Some(0));
+    }
+}
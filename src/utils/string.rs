@@ -2,7 +2,9 @@
 //!
 //! Provides small helpers to take the first N Unicode scalar values (chars)
 //! from a string without slicing by byte index which can panic on multibyte
-//! characters.
+//! characters, plus `{{key}}` placeholder interpolation.
+
+use std::collections::HashMap;
 
 /// Return the first `n` characters of `s` as a `String` (no ellipsis).
 pub fn prefix_chars(s: &str, n: usize) -> String {
@@ -19,6 +21,18 @@ pub fn preview(s: &str, n: usize) -> String {
     prefix
 }
 
+/// Replace `{{key}}` placeholders in `template` with values from `vars`.
+///
+/// Placeholders without a matching key are left untouched.
+pub fn interpolate(template: &str, vars: &HashMap<String, String>) -> String {
+    let mut result = template.to_string();
+    for (key, value) in vars {
+        let placeholder = format!("{{{{{}}}}}", key);
+        result = result.replace(&placeholder, value);
+    }
+    result
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
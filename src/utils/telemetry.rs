@@ -5,7 +5,7 @@
 //! transport logic.
 
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 
 use super::metrics::MetricsCollector;
 
@@ -32,6 +32,8 @@ pub struct TelemetryConfig {
     pub format: TelemetryFormat,
     /// HTTP endpoint path for serving metrics.
     pub endpoint: String,
+    /// OpenTelemetry export (requires the `otel` feature).
+    pub otlp: OtlpConfig,
 }
 
 impl Default for TelemetryConfig {
@@ -40,10 +42,64 @@ impl Default for TelemetryConfig {
             enabled: false,
             format: TelemetryFormat::default(),
             endpoint: "/metrics".to_string(),
+            otlp: OtlpConfig::default(),
         }
     }
 }
 
+/// OpenTelemetry OTLP/HTTP export of tracing spans and usage metrics.
+///
+/// Only takes effect in builds with the `otel` feature.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OtlpConfig {
+    /// Whether to export to the collector.
+    pub enabled: bool,
+    /// Collector base URL; `/v1/traces` and `/v1/metrics` are appended.
+    pub endpoint: String,
+    /// `service.name` resource attribute.
+    pub service_name: String,
+    /// Extra HTTP headers sent with each export (e.g. auth tokens).
+    pub headers: HashMap<String, String>,
+    /// Seconds between metric exports.
+    pub metrics_interval_secs: u64,
+}
+
+impl Default for OtlpConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            endpoint: "http://localhost:4318".to_string(),
+            service_name: "zeptoclaw".to_string(),
+            headers: HashMap::new(),
+            metrics_interval_secs: 60,
+        }
+    }
+}
+
+impl OtlpConfig {
+    /// OTLP/HTTP URL for trace export.
+    pub fn traces_endpoint(&self) -> String {
+        signal_endpoint(&self.endpoint, "traces")
+    }
+
+    /// OTLP/HTTP URL for metric export.
+    pub fn metrics_endpoint(&self) -> String {
+        signal_endpoint(&self.endpoint, "metrics")
+    }
+}
+
+/// `{base}/v1/{signal}`, unless `base` already names the signal path.
+fn signal_endpoint(base: &str, signal: &str) -> String {
+    let base = base.trim().trim_end_matches('/');
+    let suffix = format!("/v1/{}", signal);
+    if base.ends_with(&suffix) {
+        base.to_string()
+    } else {
+        format!("{}{}", base, suffix)
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Renderers
 // ---------------------------------------------------------------------------
@@ -237,6 +293,7 @@ mod tests {
             enabled: true,
             format: TelemetryFormat::Json,
             endpoint: "/custom-metrics".to_string(),
+            otlp: OtlpConfig::default(),
         };
         let json = serde_json::to_string(&config).unwrap();
         let restored: TelemetryConfig = serde_json::from_str(&json).unwrap();
@@ -255,6 +312,37 @@ mod tests {
         assert_eq!(config.endpoint, "/metrics");
     }
 
+    // -- OtlpConfig --
+
+    #[test]
+    fn test_otlp_config_parsing_and_endpoints() {
+        let config: TelemetryConfig = serde_json::from_str(
+            r#"{"otlp": {"enabled": true, "endpoint": "https://otel.example.com:4318/",
+                "headers": {"x-api-key": "k"}}}"#,
+        )
+        .unwrap();
+        let otlp = &config.otlp;
+        assert!(otlp.enabled);
+        assert_eq!(otlp.service_name, "zeptoclaw");
+        assert_eq!(otlp.metrics_interval_secs, 60);
+        assert_eq!(otlp.headers.get("x-api-key").map(String::as_str), Some("k"));
+        assert_eq!(
+            otlp.traces_endpoint(),
+            "https://otel.example.com:4318/v1/traces"
+        );
+        assert_eq!(
+            otlp.metrics_endpoint(),
+            "https://otel.example.com:4318/v1/metrics"
+        );
+
+        // Off by default, and a full signal URL is kept as is.
+        assert!(!TelemetryConfig::default().otlp.enabled);
+        assert_eq!(
+            signal_endpoint("http://collector/v1/traces", "traces"),
+            "http://collector/v1/traces"
+        );
+    }
+
     // -- Serde variants for TelemetryFormat --
 
     #[test]
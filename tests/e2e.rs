@@ -111,6 +111,7 @@ impl LLMProvider for MockToolCallingProvider {
                     r#"{"message": "e2e-tool-test"}"#,
                 )],
                 usage: None,
+                thoughts: None,
             })
         } else {
             // Subsequent call: return final text
@@ -157,6 +158,7 @@ impl LLMProvider for MockBudgetExhaustingProvider {
                 completion_tokens: self.tokens_per_call,
                 total_tokens: self.tokens_per_call * 2,
             }),
+            thoughts: None,
         })
     }
 }
@@ -215,6 +217,7 @@ impl LLMProvider for MockBatchToolProvider {
                 content: String::new(),
                 tool_calls,
                 usage: None,
+                thoughts: None,
             })
         } else {
             // Synthesis / no-tools call: return text
@@ -318,6 +321,7 @@ impl LLMProvider for MockTokenCountingProvider {
                 completion_tokens: 200,
                 total_tokens: 700,
             }),
+            thoughts: None,
         })
     }
 }